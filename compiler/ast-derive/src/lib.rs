@@ -0,0 +1,370 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Derive macros for the Leo AST.
+//!
+//! [`AstVisitor`] implements the visitor traits of `leo-ast` for a type, so that a pass
+//! only has to write the nodes it cares about.
+
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, DeriveInput, Error, GenericParam, Lifetime, LifetimeDef, Lit, Meta, NestedMeta,
+    Path, Result, Type,
+};
+
+/// The visitor traits, in the order their methods are listed in `leo_ast::passes::visitor`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Trait {
+    Expression,
+    Statement,
+    Program,
+}
+
+/// The methods of the visitor traits, with the AST node each of them visits.
+const METHODS: &[(&str, &str, Trait)] = &[
+    ("visit_expression", "Expression", Trait::Expression),
+    ("visit_access", "AccessExpression", Trait::Expression),
+    ("visit_binary", "BinaryExpression", Trait::Expression),
+    ("visit_call", "CallExpression", Trait::Expression),
+    ("visit_cast", "CastExpression", Trait::Expression),
+    ("visit_struct_init", "StructExpression", Trait::Expression),
+    ("visit_err", "ErrExpression", Trait::Expression),
+    ("visit_identifier", "Identifier", Trait::Expression),
+    ("visit_literal", "Literal", Trait::Expression),
+    ("visit_ternary", "TernaryExpression", Trait::Expression),
+    ("visit_tuple", "TupleExpression", Trait::Expression),
+    ("visit_unary", "UnaryExpression", Trait::Expression),
+    ("visit_statement", "Statement", Trait::Statement),
+    ("visit_assign", "AssignStatement", Trait::Statement),
+    ("visit_block", "Block", Trait::Statement),
+    ("visit_conditional", "ConditionalStatement", Trait::Statement),
+    ("visit_console", "ConsoleStatement", Trait::Statement),
+    ("visit_decrement", "DecrementStatement", Trait::Statement),
+    ("visit_definition", "DefinitionStatement", Trait::Statement),
+    ("visit_expression_statement", "ExpressionStatement", Trait::Statement),
+    ("visit_finalize", "FinalizeStatement", Trait::Statement),
+    ("visit_increment", "IncrementStatement", Trait::Statement),
+    ("visit_iteration", "IterationStatement", Trait::Statement),
+    ("visit_match", "MatchStatement", Trait::Statement),
+    ("visit_return", "ReturnStatement", Trait::Statement),
+    ("visit_program", "Program", Trait::Program),
+    ("visit_program_scope", "ProgramScope", Trait::Program),
+    ("visit_import", "Program", Trait::Program),
+    ("visit_struct", "Struct", Trait::Program),
+    ("visit_mapping", "Mapping", Trait::Program),
+    ("visit_function", "Function", Trait::Program),
+];
+
+/// The options given in the `#[visitor(...)]` attributes of a type.
+struct Options {
+    output: Type,
+    additional_input: Type,
+    lifetime: Option<Lifetime>,
+    crate_path: Path,
+    overrides: Vec<(usize, Span)>,
+}
+
+impl Options {
+    fn parse(input: &DeriveInput) -> Result<Self> {
+        let mut options = Options {
+            output: parse_quote!(()),
+            additional_input: parse_quote!(()),
+            lifetime: None,
+            crate_path: parse_quote!(::leo_ast),
+            overrides: Vec::new(),
+        };
+
+        for attribute in input
+            .attrs
+            .iter()
+            .filter(|attribute| attribute.path.is_ident("visitor"))
+        {
+            let list = match attribute.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(Error::new_spanned(meta, "expected `#[visitor(...)]`")),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) => {
+                        let name = path.get_ident().map(|ident| ident.to_string()).unwrap_or_default();
+                        match METHODS.iter().position(|(method, _, _)| *method == name) {
+                            Some(index) if options.overrides.iter().any(|(other, _)| *other == index) => {
+                                return Err(Error::new_spanned(path, "this node is already overridden"));
+                            }
+                            Some(index) => options.overrides.push((index, syn::spanned::Spanned::span(&path))),
+                            None => return Err(Error::new_spanned(path, "unknown visitor method")),
+                        }
+                    }
+                    NestedMeta::Meta(Meta::NameValue(pair)) => {
+                        let value = match &pair.lit {
+                            Lit::Str(value) => value,
+                            lit => return Err(Error::new_spanned(lit, "expected a string literal")),
+                        };
+                        let key = pair.path.get_ident().map(|ident| ident.to_string()).unwrap_or_default();
+                        match key.as_str() {
+                            "output" => options.output = value.parse()?,
+                            "additional_input" => options.additional_input = value.parse()?,
+                            "lifetime" => options.lifetime = Some(value.parse()?),
+                            "crate_path" => options.crate_path = value.parse()?,
+                            _ => return Err(Error::new_spanned(pair.path, "unknown visitor option")),
+                        }
+                    }
+                    nested => return Err(Error::new_spanned(nested, "expected a visitor method or option")),
+                }
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Implements `ExpressionVisitor`, `StatementVisitor` and `ProgramVisitor` for a type.
+///
+/// Every node named in `#[visitor(...)]` is forwarded to the inherent method of the same name,
+/// and every other node keeps the default walk. See the crate documentation for the options.
+#[proc_macro_derive(AstVisitor, attributes(visitor))]
+pub fn derive_ast_visitor(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(|error| error.to_compile_error()).into()
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let options = Options::parse(input)?;
+    let name = &input.ident;
+    let krate = &options.crate_path;
+    let output = &options.output;
+    let additional_input = &options.additional_input;
+
+    // The lifetime of the visited AST is either one of the type's own, or a fresh one.
+    let mut generics = input.generics.clone();
+    let lifetime = match &options.lifetime {
+        Some(lifetime) => {
+            if !generics.lifetimes().any(|def| def.lifetime == *lifetime) {
+                return Err(Error::new_spanned(
+                    lifetime,
+                    "this lifetime is not declared on the type",
+                ));
+            }
+            lifetime.clone()
+        }
+        None => {
+            let lifetime = Lifetime::new("'__ast", Span::call_site());
+            generics
+                .params
+                .insert(0, GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())));
+            lifetime
+        }
+    };
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let methods = |trait_: Trait| {
+        options
+            .overrides
+            .iter()
+            .filter(|(index, _)| METHODS[*index].2 == trait_)
+            .map(|(index, span)| {
+                let (method, node, _) = METHODS[*index];
+                let method = syn::Ident::new(method, *span);
+                let node = format_ident!("{}", node);
+                match trait_ {
+                    Trait::Expression => quote! {
+                        fn #method(&mut self, input: &#lifetime #krate::#node, additional: &Self::AdditionalInput) -> Self::Output {
+                            Self::#method(self, input, additional)
+                        }
+                    },
+                    Trait::Statement | Trait::Program => quote! {
+                        fn #method(&mut self, input: &#lifetime #krate::#node) {
+                            Self::#method(self, input)
+                        }
+                    },
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let expression_methods = methods(Trait::Expression);
+    let statement_methods = methods(Trait::Statement);
+    let program_methods = methods(Trait::Program);
+
+    Ok(quote! {
+        impl #impl_generics #krate::ExpressionVisitor<#lifetime> for #name #ty_generics #where_clause {
+            type AdditionalInput = #additional_input;
+            type Output = #output;
+
+            #(#expression_methods)*
+        }
+
+        impl #impl_generics #krate::StatementVisitor<#lifetime> for #name #ty_generics #where_clause {
+            #(#statement_methods)*
+        }
+
+        impl #impl_generics #krate::ProgramVisitor<#lifetime> for #name #ty_generics #where_clause {
+            #(#program_methods)*
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use syn::{File, ImplItem, Item, ItemImpl};
+
+    /// Expands the derive on `input`, and returns the implemented traits with the methods of each.
+    fn expand_impls(input: DeriveInput) -> Vec<ItemImpl> {
+        let file: File = syn::parse2(expand(&input).unwrap()).unwrap();
+        file.items
+            .into_iter()
+            .map(|item| match item {
+                Item::Impl(item) => item,
+                item => panic!("expected an impl, found `{}`", quote!(#item)),
+            })
+            .collect()
+    }
+
+    fn trait_path(item: &ItemImpl) -> String {
+        let path = &item.trait_.as_ref().unwrap().1;
+        quote!(#path).to_string()
+    }
+
+    fn method_names(item: &ItemImpl) -> Vec<String> {
+        item.items
+            .iter()
+            .filter_map(|item| match item {
+                ImplItem::Method(method) => Some(method.sig.ident.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn expand_error(input: DeriveInput) -> String {
+        match expand(&input) {
+            Ok(tokens) => panic!("expected an error, found `{}`", tokens),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn overrides_are_forwarded_by_their_traits() {
+        let impls = expand_impls(parse_quote! {
+            #[visitor(visit_call, visit_block)]
+            #[visitor(visit_function)]
+            struct Collector;
+        });
+
+        let traits = impls.iter().map(trait_path).collect::<Vec<_>>();
+        assert_eq!(
+            traits,
+            [
+                ":: leo_ast :: ExpressionVisitor < '__ast >",
+                ":: leo_ast :: StatementVisitor < '__ast >",
+                ":: leo_ast :: ProgramVisitor < '__ast >"
+            ]
+        );
+        let methods = impls.iter().map(method_names).collect::<Vec<_>>();
+        assert_eq!(
+            methods,
+            [vec!["visit_call"], vec!["visit_block"], vec!["visit_function"]]
+        );
+
+        // Without overrides, every node keeps the default walk.
+        let impls = expand_impls(parse_quote!(
+            struct Walker;
+        ));
+        assert!(impls.iter().all(|item| method_names(item).is_empty()));
+    }
+
+    #[test]
+    fn options_set_the_associated_types_lifetime_and_crate_path() {
+        let impls = expand_impls(parse_quote! {
+            #[visitor(output = "Option<u8>", additional_input = "bool", lifetime = "'a", crate_path = "crate")]
+            struct Folder<'a, T: Clone> {
+                nodes: Vec<&'a T>,
+            }
+        });
+
+        let expression = &impls[0];
+        assert_eq!(trait_path(expression), "crate :: ExpressionVisitor < 'a >");
+        let self_ty = &expression.self_ty;
+        assert_eq!(quote!(#self_ty).to_string(), "Folder < 'a , T >");
+        // The type's own lifetime is reused instead of adding a fresh one.
+        assert_eq!(expression.generics.lifetimes().count(), 1);
+        let types = expression
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ImplItem::Type(type_) => {
+                    let ty = &type_.ty;
+                    Some(format!("{} = {}", type_.ident, quote!(#ty)))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(types, ["AdditionalInput = bool", "Output = Option < u8 >"]);
+    }
+
+    #[test]
+    fn invalid_attributes_are_errors() {
+        assert_eq!(
+            expand_error(parse_quote! {
+                #[visitor(visit_nothing)]
+                struct Collector;
+            }),
+            "unknown visitor method"
+        );
+        assert_eq!(
+            expand_error(parse_quote! {
+                #[visitor(visit_call)]
+                #[visitor(visit_call)]
+                struct Collector;
+            }),
+            "this node is already overridden"
+        );
+        assert_eq!(
+            expand_error(parse_quote! {
+                #[visitor(input = "u8")]
+                struct Collector;
+            }),
+            "unknown visitor option"
+        );
+        assert_eq!(
+            expand_error(parse_quote! {
+                #[visitor(output = 1)]
+                struct Collector;
+            }),
+            "expected a string literal"
+        );
+        assert_eq!(
+            expand_error(parse_quote! {
+                #[visitor = "visit_call"]
+                struct Collector;
+            }),
+            "expected `#[visitor(...)]`"
+        );
+        assert_eq!(
+            expand_error(parse_quote! {
+                #[visitor(lifetime = "'a")]
+                struct Collector<'b>(&'b u8);
+            }),
+            "this lifetime is not declared on the type"
+        );
+    }
+}
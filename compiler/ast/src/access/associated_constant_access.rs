@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Identifier, Node, Type};
+use crate::{Identifier, Node, NodeID, Type};
 use leo_span::Span;
 
 use serde::{Deserialize, Serialize};
@@ -29,6 +29,8 @@ pub struct AssociatedConstant {
     pub name: Identifier,
     /// The span for the entire expression `Foo::bar()`.
     pub span: Span,
+    /// The id of the node.
+    pub id: NodeID,
 }
 
 impl fmt::Display for AssociatedConstant {
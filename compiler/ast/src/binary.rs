@@ -0,0 +1,62 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! A compact binary encoding of ASTs, and of the artifacts that contain them, for files that are too large as JSON.
+//!
+//! A value is encoded as MessagePack after a header of the magic bytes, the version of the encoding,
+//! and the version of the compiler that wrote it. Since the AST changes between versions of the compiler,
+//! a value is only decoded by the version that encoded it.
+
+use leo_errors::{AstError, Result};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// The bytes that start a binary encoded value.
+pub const BINARY_MAGIC: &[u8; 4] = b"LEOB";
+
+/// The version of the binary encoding, which changes whenever the layout of the header or of the values changes.
+pub const BINARY_FORMAT_VERSION: u16 = 2;
+
+/// Returns the header of values encoded by this version of the compiler.
+fn header() -> Vec<u8> {
+    let compiler_version = env!("CARGO_PKG_VERSION").as_bytes();
+    let mut header = BINARY_MAGIC.to_vec();
+    header.extend(BINARY_FORMAT_VERSION.to_le_bytes());
+    header.push(compiler_version.len() as u8);
+    header.extend(compiler_version);
+    header
+}
+
+/// Encodes `value` in the binary format.
+/// Structs are encoded as maps, so that fields which are skipped when they are empty can be decoded.
+pub fn to_binary<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    let mut bytes = header();
+    rmp_serde::encode::write_named(&mut bytes, value).map_err(AstError::failed_to_convert_ast_to_binary)?;
+    Ok(bytes)
+}
+
+/// Decodes a value in the binary format.
+/// Fails if the bytes are not in the binary format, or were encoded by another version of the compiler.
+pub fn from_binary<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    let header = header();
+    if !bytes.starts_with(BINARY_MAGIC) {
+        return Err(AstError::failed_to_read_binary_ast("the data does not start with a binary AST header").into());
+    }
+    if !bytes.starts_with(&header) {
+        return Err(AstError::incompatible_binary_ast(env!("CARGO_PKG_VERSION")).into());
+    }
+    Ok(rmp_serde::from_slice(&bytes[header.len()..]).map_err(AstError::failed_to_read_binary_ast)?)
+}
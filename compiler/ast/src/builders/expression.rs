@@ -0,0 +1,368 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    AccessExpression, BinaryExpression, BinaryOperation, CallExpression, Expression, Identifier, IntegerType, Literal,
+    MemberAccess, NodeBuilder, PositiveNumber, StructExpression, StructVariableInitializer, TernaryExpression,
+    TupleAccess, TupleExpression, UnaryExpression, UnaryOperation,
+};
+use leo_span::{Span, Symbol};
+
+use std::ops;
+
+/// A builder for expressions, e.g. `ExprBuilder::identifier(&nodes, a) + ExprBuilder::integer(&nodes, IntegerType::U8, 1)`.
+///
+/// Every node created by the builder is given a default span and an id allocated by its [`NodeBuilder`].
+#[derive(Clone, Debug)]
+pub struct ExprBuilder<'a> {
+    /// The expression built so far.
+    expression: Expression,
+    /// Allocates the ids of the nodes created by the builder.
+    node_builder: &'a NodeBuilder,
+}
+
+impl<'a> ExprBuilder<'a> {
+    /// Wraps an existing expression, so it can be combined with built ones.
+    pub fn from_expression(node_builder: &'a NodeBuilder, expression: Expression) -> Self {
+        Self {
+            expression,
+            node_builder,
+        }
+    }
+
+    /// Returns the built expression.
+    pub fn build(self) -> Expression {
+        self.expression
+    }
+
+    /// A variable, e.g. `a`.
+    pub fn identifier(node_builder: &'a NodeBuilder, name: Symbol) -> Self {
+        Self::from_expression(
+            node_builder,
+            Expression::Identifier(Identifier::new(name, node_builder.next_id())),
+        )
+    }
+
+    /// An address literal, e.g. `aleo1...`.
+    pub fn address(node_builder: &'a NodeBuilder, value: impl Into<String>) -> Self {
+        Self::literal(
+            node_builder,
+            Literal::Address(value.into(), Span::default(), node_builder.next_id()),
+        )
+    }
+
+    /// A boolean literal.
+    pub fn boolean(node_builder: &'a NodeBuilder, value: bool) -> Self {
+        Self::literal(
+            node_builder,
+            Literal::Boolean(value, Span::default(), node_builder.next_id()),
+        )
+    }
+
+    /// A field literal, e.g. `1field`.
+    pub fn field(node_builder: &'a NodeBuilder, value: impl ToString) -> Self {
+        Self::literal(
+            node_builder,
+            Literal::Field(value.to_string(), Span::default(), node_builder.next_id()),
+        )
+    }
+
+    /// An integer literal of the given type, e.g. `1u8`.
+    pub fn integer(node_builder: &'a NodeBuilder, type_: IntegerType, value: impl ToString) -> Self {
+        Self::literal(
+            node_builder,
+            Literal::Integer(type_, value.to_string(), Span::default(), node_builder.next_id()),
+        )
+    }
+
+    /// A scalar literal, e.g. `1scalar`.
+    pub fn scalar(node_builder: &'a NodeBuilder, value: impl ToString) -> Self {
+        Self::literal(
+            node_builder,
+            Literal::Scalar(value.to_string(), Span::default(), node_builder.next_id()),
+        )
+    }
+
+    /// A signature literal, e.g. `sign1...`.
+    pub fn signature(node_builder: &'a NodeBuilder, value: impl Into<String>) -> Self {
+        Self::literal(
+            node_builder,
+            Literal::Signature(value.into(), Span::default(), node_builder.next_id()),
+        )
+    }
+
+    /// A string literal.
+    pub fn string(node_builder: &'a NodeBuilder, value: impl Into<String>) -> Self {
+        Self::literal(
+            node_builder,
+            Literal::String(value.into(), Span::default(), node_builder.next_id()),
+        )
+    }
+
+    /// Any literal. Its id is kept as given.
+    pub fn literal(node_builder: &'a NodeBuilder, literal: Literal) -> Self {
+        Self::from_expression(node_builder, Expression::Literal(literal))
+    }
+
+    /// A call to the function `function` with `arguments`, e.g. `foo(a, b)`.
+    pub fn call(
+        node_builder: &'a NodeBuilder,
+        function: Symbol,
+        arguments: impl IntoIterator<Item = impl Into<Expression>>,
+    ) -> Self {
+        Self::from_expression(
+            node_builder,
+            Expression::Call(CallExpression {
+                function: Box::new(Expression::Identifier(Identifier::new(
+                    function,
+                    node_builder.next_id(),
+                ))),
+                arguments: arguments.into_iter().map(Into::into).collect(),
+                external: None,
+                span: Span::default(),
+                id: node_builder.next_id(),
+            }),
+        )
+    }
+
+    /// A call to the function `function` of the external program `program`, e.g. `token.leo/mint(a)`.
+    pub fn external_call(
+        node_builder: &'a NodeBuilder,
+        program: Symbol,
+        function: Symbol,
+        arguments: impl IntoIterator<Item = impl Into<Expression>>,
+    ) -> Self {
+        let mut call = Self::call(node_builder, function, arguments);
+        if let Expression::Call(call) = &mut call.expression {
+            call.external = Some(Box::new(Expression::Identifier(Identifier::new(
+                program,
+                node_builder.next_id(),
+            ))));
+        }
+        call
+    }
+
+    /// A struct or record initialization, e.g. `Foo { a: 1u8, b }`.
+    /// A member without a value uses the variable of the same name.
+    pub fn struct_init(
+        node_builder: &'a NodeBuilder,
+        name: Symbol,
+        members: impl IntoIterator<Item = (Symbol, Option<Expression>)>,
+    ) -> Self {
+        Self::from_expression(
+            node_builder,
+            Expression::Struct(StructExpression {
+                name: Identifier::new(name, node_builder.next_id()),
+                members: members
+                    .into_iter()
+                    .map(|(identifier, expression)| StructVariableInitializer {
+                        identifier: Identifier::new(identifier, node_builder.next_id()),
+                        expression,
+                    })
+                    .collect(),
+                span: Span::default(),
+                id: node_builder.next_id(),
+            }),
+        )
+    }
+
+    /// A tuple, e.g. `(a, b)`.
+    pub fn tuple(node_builder: &'a NodeBuilder, elements: impl IntoIterator<Item = impl Into<Expression>>) -> Self {
+        Self::from_expression(
+            node_builder,
+            Expression::Tuple(TupleExpression {
+                elements: elements.into_iter().map(Into::into).collect(),
+                span: Span::default(),
+                id: node_builder.next_id(),
+            }),
+        )
+    }
+
+    /// A ternary, e.g. `condition ? if_true : if_false`.
+    pub fn ternary(
+        node_builder: &'a NodeBuilder,
+        condition: impl Into<Expression>,
+        if_true: impl Into<Expression>,
+        if_false: impl Into<Expression>,
+    ) -> Self {
+        Self::from_expression(
+            node_builder,
+            Expression::Ternary(TernaryExpression {
+                condition: Box::new(condition.into()),
+                if_true: Box::new(if_true.into()),
+                if_false: Box::new(if_false.into()),
+                span: Span::default(),
+                id: node_builder.next_id(),
+            }),
+        )
+    }
+
+    /// Accesses the member `name` of this expression, e.g. `a.b`.
+    pub fn member(self, name: Symbol) -> Self {
+        let Self {
+            expression,
+            node_builder,
+        } = self;
+        Self::from_expression(
+            node_builder,
+            Expression::Access(AccessExpression::Member(MemberAccess {
+                inner: Box::new(expression),
+                name: Identifier::new(name, node_builder.next_id()),
+                span: Span::default(),
+                id: node_builder.next_id(),
+            })),
+        )
+    }
+
+    /// Accesses the element `index` of this tuple, e.g. `a.0`.
+    pub fn index(self, index: usize) -> Self {
+        let Self {
+            expression,
+            node_builder,
+        } = self;
+        Self::from_expression(
+            node_builder,
+            Expression::Access(AccessExpression::Tuple(TupleAccess {
+                tuple: Box::new(expression),
+                index: PositiveNumber {
+                    value: index.to_string(),
+                },
+                span: Span::default(),
+                id: node_builder.next_id(),
+            })),
+        )
+    }
+
+    /// Applies the binary operation `op` to this expression and `right`.
+    pub fn binary(self, op: BinaryOperation, right: impl Into<Expression>) -> Self {
+        let Self {
+            expression,
+            node_builder,
+        } = self;
+        Self::from_expression(
+            node_builder,
+            Expression::Binary(BinaryExpression {
+                left: Box::new(expression),
+                right: Box::new(right.into()),
+                op,
+                span: Span::default(),
+                id: node_builder.next_id(),
+            }),
+        )
+    }
+
+    /// Applies the unary operation `op` to this expression.
+    pub fn unary(self, op: UnaryOperation) -> Self {
+        let Self {
+            expression,
+            node_builder,
+        } = self;
+        Self::from_expression(
+            node_builder,
+            Expression::Unary(UnaryExpression {
+                receiver: Box::new(expression),
+                op,
+                span: Span::default(),
+                id: node_builder.next_id(),
+            }),
+        )
+    }
+
+    /// `self && right`.
+    pub fn and(self, right: impl Into<Expression>) -> Self {
+        self.binary(BinaryOperation::And, right)
+    }
+
+    /// `self || right`.
+    pub fn or(self, right: impl Into<Expression>) -> Self {
+        self.binary(BinaryOperation::Or, right)
+    }
+
+    /// `self == right`.
+    pub fn eq(self, right: impl Into<Expression>) -> Self {
+        self.binary(BinaryOperation::Eq, right)
+    }
+
+    /// `self != right`.
+    pub fn neq(self, right: impl Into<Expression>) -> Self {
+        self.binary(BinaryOperation::Neq, right)
+    }
+
+    /// `self < right`.
+    pub fn lt(self, right: impl Into<Expression>) -> Self {
+        self.binary(BinaryOperation::Lt, right)
+    }
+
+    /// `self <= right`.
+    pub fn lte(self, right: impl Into<Expression>) -> Self {
+        self.binary(BinaryOperation::Lte, right)
+    }
+
+    /// `self > right`.
+    pub fn gt(self, right: impl Into<Expression>) -> Self {
+        self.binary(BinaryOperation::Gt, right)
+    }
+
+    /// `self >= right`.
+    pub fn gte(self, right: impl Into<Expression>) -> Self {
+        self.binary(BinaryOperation::Gte, right)
+    }
+}
+
+impl From<ExprBuilder<'_>> for Expression {
+    fn from(builder: ExprBuilder<'_>) -> Self {
+        builder.build()
+    }
+}
+
+macro_rules! binary_operator {
+    ($trait_:ident, $method:ident, $op:ident) => {
+        impl<T: Into<Expression>> ops::$trait_<T> for ExprBuilder<'_> {
+            type Output = Self;
+
+            fn $method(self, right: T) -> Self {
+                self.binary(BinaryOperation::$op, right)
+            }
+        }
+    };
+}
+
+binary_operator!(Add, add, Add);
+binary_operator!(Sub, sub, Sub);
+binary_operator!(Mul, mul, Mul);
+binary_operator!(Div, div, Div);
+binary_operator!(Rem, rem, Rem);
+binary_operator!(BitAnd, bitand, BitwiseAnd);
+binary_operator!(BitOr, bitor, BitwiseOr);
+binary_operator!(BitXor, bitxor, Xor);
+binary_operator!(Shl, shl, Shl);
+binary_operator!(Shr, shr, Shr);
+
+impl ops::Not for ExprBuilder<'_> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.unary(UnaryOperation::Not)
+    }
+}
+
+impl ops::Neg for ExprBuilder<'_> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.unary(UnaryOperation::Negate)
+    }
+}
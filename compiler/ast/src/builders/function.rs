@@ -0,0 +1,209 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    Annotation, AssignStatement, Block, CallType, ConsoleFunction, ConsoleStatement, DeclarationType,
+    DefinitionStatement, Expression, Finalize, Function, FunctionInput, FunctionOutput, Identifier, Input, Mode,
+    NodeBuilder, Output, ReturnStatement, Statement, Type,
+};
+use leo_span::{Span, Symbol};
+
+/// A builder for functions, e.g. `FunctionBuilder::transition(&nodes, main).input(a, u8).output(u8).returns(a).build()`
+/// where `u8` is a [`Type`] and `a` an expression.
+///
+/// Every node created by the builder is given a default span and an id allocated by its [`NodeBuilder`].
+#[derive(Clone, Debug)]
+pub struct FunctionBuilder<'a> {
+    node_builder: &'a NodeBuilder,
+    documentation: Option<String>,
+    annotations: Vec<Annotation>,
+    call_type: CallType,
+    identifier: Identifier,
+    input: Vec<Input>,
+    output: Vec<Output>,
+    statements: Vec<Statement>,
+    finalize: Option<Finalize>,
+}
+
+impl<'a> FunctionBuilder<'a> {
+    /// Starts a function named `name` with the given call type and no inputs, outputs or statements.
+    pub fn new(node_builder: &'a NodeBuilder, name: Symbol, call_type: CallType) -> Self {
+        Self {
+            node_builder,
+            documentation: None,
+            annotations: Vec::new(),
+            call_type,
+            identifier: Identifier::new(name, node_builder.next_id()),
+            input: Vec::new(),
+            output: Vec::new(),
+            statements: Vec::new(),
+            finalize: None,
+        }
+    }
+
+    /// Starts a `function`.
+    pub fn function(node_builder: &'a NodeBuilder, name: Symbol) -> Self {
+        Self::new(node_builder, name, CallType::Standard)
+    }
+
+    /// Starts a `transition`.
+    pub fn transition(node_builder: &'a NodeBuilder, name: Symbol) -> Self {
+        Self::new(node_builder, name, CallType::Transition)
+    }
+
+    /// Starts an `inline` function.
+    pub fn inline(node_builder: &'a NodeBuilder, name: Symbol) -> Self {
+        Self::new(node_builder, name, CallType::Inline)
+    }
+
+    /// Sets the documentation comment of the function.
+    pub fn documentation(mut self, documentation: impl Into<String>) -> Self {
+        self.documentation = Some(documentation.into());
+        self
+    }
+
+    /// Adds the annotation `@name` to the function.
+    pub fn annotation(mut self, name: Symbol) -> Self {
+        self.annotations.push(Annotation {
+            identifier: Identifier::new(name, self.node_builder.next_id()),
+            arguments: Vec::new(),
+            span: Span::default(),
+        });
+        self
+    }
+
+    /// Adds the input `name: type_` to the function.
+    pub fn input(self, name: Symbol, type_: Type) -> Self {
+        self.input_with_mode(name, Mode::None, type_)
+    }
+
+    /// Adds the input `mode name: type_` to the function.
+    pub fn input_with_mode(mut self, name: Symbol, mode: Mode, type_: Type) -> Self {
+        self.input.push(Input::Internal(FunctionInput {
+            identifier: Identifier::new(name, self.node_builder.next_id()),
+            mode,
+            type_,
+            span: Span::default(),
+        }));
+        self
+    }
+
+    /// Adds the output `type_` to the function.
+    pub fn output(self, type_: Type) -> Self {
+        self.output_with_mode(Mode::None, type_)
+    }
+
+    /// Adds the output `mode type_` to the function.
+    pub fn output_with_mode(mut self, mode: Mode, type_: Type) -> Self {
+        self.output.push(Output::Internal(FunctionOutput {
+            mode,
+            type_,
+            span: Span::default(),
+        }));
+        self
+    }
+
+    /// Appends `statement` to the body of the function.
+    pub fn statement(mut self, statement: Statement) -> Self {
+        self.statements.push(statement);
+        self
+    }
+
+    /// Appends `let name: type_ = value;` to the body of the function.
+    pub fn define(self, name: Symbol, type_: Type, value: impl Into<Expression>) -> Self {
+        let node_builder = self.node_builder;
+        self.statement(Statement::Definition(DefinitionStatement {
+            declaration_type: DeclarationType::Let,
+            variable_name: Identifier::new(name, node_builder.next_id()),
+            type_,
+            value: value.into(),
+            span: Span::default(),
+            id: node_builder.next_id(),
+        }))
+    }
+
+    /// Appends `place = value;` to the body of the function.
+    pub fn assign(self, place: impl Into<Expression>, value: impl Into<Expression>) -> Self {
+        let node_builder = self.node_builder;
+        self.statement(Statement::Assign(Box::new(AssignStatement {
+            place: place.into(),
+            value: value.into(),
+            span: Span::default(),
+            id: node_builder.next_id(),
+        })))
+    }
+
+    /// Appends `console.assert(condition);` to the body of the function.
+    pub fn assert(self, condition: impl Into<Expression>) -> Self {
+        let node_builder = self.node_builder;
+        self.statement(Statement::Console(ConsoleStatement {
+            function: ConsoleFunction::Assert(condition.into(), None),
+            span: Span::default(),
+            id: node_builder.next_id(),
+        }))
+    }
+
+    /// Appends `return expression;` to the body of the function.
+    pub fn returns(self, expression: impl Into<Expression>) -> Self {
+        let node_builder = self.node_builder;
+        self.statement(Statement::Return(ReturnStatement {
+            expression: expression.into(),
+            span: Span::default(),
+            id: node_builder.next_id(),
+        }))
+    }
+
+    /// Sets the finalize block of the function, built from `finalize` by [`FunctionBuilder::build_finalize`].
+    pub fn finalize(mut self, finalize: FunctionBuilder<'_>) -> Self {
+        self.finalize = Some(finalize.build_finalize());
+        self
+    }
+
+    /// Returns the built function.
+    pub fn build(self) -> Function {
+        Function::new(
+            self.documentation,
+            self.annotations,
+            self.call_type,
+            self.identifier,
+            self.input,
+            self.output,
+            Self::block(self.node_builder, self.statements),
+            self.finalize,
+            Span::default(),
+        )
+    }
+
+    /// Returns the inputs, outputs and statements of the builder as a finalize block.
+    /// The call type, annotations and documentation are not part of a finalize block and are dropped.
+    pub fn build_finalize(self) -> Finalize {
+        Finalize::new(
+            self.identifier,
+            self.input,
+            self.output,
+            Self::block(self.node_builder, self.statements),
+            Span::default(),
+        )
+    }
+
+    fn block(node_builder: &NodeBuilder, statements: Vec<Statement>) -> Block {
+        Block {
+            statements,
+            span: Span::default(),
+            id: node_builder.next_id(),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Builders for constructing well-formed AST nodes programmatically.
+//!
+//! Tools that emit Leo, e.g. refactorings and code generators, would otherwise
+//! have to fill every field of each node by hand, including spans that do not
+//! correspond to any source. Nodes built here carry [`leo_span::Span::default()`],
+//! so they can be told apart from parsed nodes, and ids allocated by a
+//! [`crate::NodeBuilder`], so analyses can record facts about them. They are
+//! printed as Leo source by their `Display` implementations.
+
+mod expression;
+pub use expression::*;
+
+mod function;
+pub use function::*;
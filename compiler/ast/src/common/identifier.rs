@@ -17,7 +17,7 @@
 use leo_errors::Result;
 use leo_span::{Span, Symbol};
 
-use crate::{simple_node_impl, Node};
+use crate::{simple_node_impl, Node, NodeID};
 use serde::{
     de::{
         Visitor, {self},
@@ -41,16 +41,19 @@ pub struct Identifier {
     pub name: Symbol,
     /// A span locating where the identifier occurred in the source.
     pub span: Span,
+    /// The id of the identifier.
+    pub id: NodeID,
 }
 
 simple_node_impl!(Identifier);
 
 impl Identifier {
-    /// Constructs a new identifier with `name` and a default span.
-    pub fn new(name: Symbol) -> Self {
+    /// Constructs a new identifier with `name`, a default span, and the id `id`.
+    pub fn new(name: Symbol, id: NodeID) -> Self {
         Self {
             name,
             span: Span::default(),
+            id,
         }
     }
 
@@ -97,6 +100,7 @@ impl Serialize for Identifier {
         let mut key: BTreeMap<String, String> = BTreeMap::new();
         key.insert("name".to_string(), self.name.to_string());
         key.insert("span".to_string(), to_json_string(&self.span)?);
+        key.insert("id".to_string(), to_json_string(&self.id)?);
 
         // Convert the serialized object into a string for use as a key.
         serializer.serialize_str(&to_json_string(&key)?)
@@ -136,7 +140,12 @@ impl<'de> Deserialize<'de> for Identifier {
                     None => return Err(E::custom("missing 'span' in serialized Identifier struct")),
                 };
 
-                Ok(Identifier { name, span })
+                let id: NodeID = match key.get("id") {
+                    Some(id) => to_json_string(id)?,
+                    None => return Err(E::custom("missing 'id' in serialized Identifier struct")),
+                };
+
+                Ok(Identifier { name, span, id })
             }
         }
 
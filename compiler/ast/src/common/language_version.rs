@@ -0,0 +1,129 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+/// A version of the Leo language, e.g. `1.5`.
+/// A program declares the version it is written for with a `// leo:version 1.5` comment or in its manifest,
+/// and may only use the features available in that version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LanguageVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl LanguageVersion {
+    /// The version of Leo that this compiler implements.
+    /// Programs that do not declare a version are checked against it.
+    pub const CURRENT: Self = Self::new(1, 6);
+
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// Reads a version written as `major.minor`, e.g. `1.5`.
+    /// A patch number, as in `1.5.3`, is accepted and ignored, as patch releases do not change the language.
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut numbers = version.trim().split('.').map(|number| number.parse::<u32>().ok());
+        let major = numbers.next()??;
+        let minor = numbers.next()??;
+        match (numbers.next(), numbers.next()) {
+            (None, _) | (Some(Some(_)), None) => Some(Self::new(major, minor)),
+            _ => None,
+        }
+    }
+
+    /// Returns the version declared by a `// leo:version 1.5` comment, or `None` if `comment` is not such a comment.
+    /// The version is returned as written, to be read with [`LanguageVersion::parse`].
+    pub fn pragma(comment: &str) -> Option<&str> {
+        comment
+            .strip_prefix("//")?
+            .trim_start()
+            .strip_prefix("leo:version")
+            .map(str::trim)
+    }
+}
+
+impl fmt::Display for LanguageVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// A feature of the language that is only available from some version of Leo on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Feature {
+    /// Messages in console assertions, e.g. `console.assert(a, "a is false");`.
+    AssertMessages,
+    /// `async transition` and `async function` declarations, and the `Future` type.
+    AsyncFunctions,
+    /// Integers that are implicitly widened to a wider type, e.g. a `u8` used as a `u16`.
+    ImplicitWidening,
+    /// The `Mapping::get`, `get_or_use`, `set`, `remove`, and `contains` operations.
+    MappingOperations,
+    /// Mapping declarations, `finalize` blocks, and the `increment` and `decrement` statements.
+    Mappings,
+    /// `match` statements.
+    Match,
+    /// The `Poseidon2`, `Poseidon4`, and `Poseidon8` hash functions.
+    PoseidonHashes,
+    /// The `signature` type, signature literals, and `signature::verify`.
+    Signatures,
+    /// The domain-separated `commit_tagged` functions of the BHP hashes.
+    TaggedCommitments,
+    /// `witness` inputs of transitions.
+    Witnesses,
+}
+
+impl Feature {
+    /// Returns the first version of Leo in which the feature is available.
+    pub fn minimum_version(self) -> LanguageVersion {
+        match self {
+            Feature::AssertMessages => LanguageVersion::new(1, 5),
+            Feature::AsyncFunctions => LanguageVersion::new(1, 6),
+            Feature::ImplicitWidening => LanguageVersion::new(1, 6),
+            Feature::MappingOperations => LanguageVersion::new(1, 6),
+            Feature::Mappings => LanguageVersion::new(1, 5),
+            Feature::Match => LanguageVersion::new(1, 6),
+            Feature::PoseidonHashes => LanguageVersion::new(1, 5),
+            Feature::Signatures => LanguageVersion::new(1, 5),
+            Feature::TaggedCommitments => LanguageVersion::new(1, 6),
+            Feature::Witnesses => LanguageVersion::new(1, 6),
+        }
+    }
+
+    /// Returns `true` if the feature is available in `version`.
+    pub fn is_available_in(self, version: LanguageVersion) -> bool {
+        self.minimum_version() <= version
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Feature::AssertMessages => write!(f, "Messages in console assertions"),
+            Feature::AsyncFunctions => write!(f, "Async functions and futures"),
+            Feature::ImplicitWidening => write!(f, "Implicitly widened integers"),
+            Feature::MappingOperations => write!(f, "Mapping operations"),
+            Feature::Mappings => write!(f, "Mappings and finalize blocks"),
+            Feature::Match => write!(f, "Match statements"),
+            Feature::PoseidonHashes => write!(f, "Poseidon hash functions"),
+            Feature::Signatures => write!(f, "Signatures"),
+            Feature::TaggedCommitments => write!(f, "Tagged commitments"),
+            Feature::Witnesses => write!(f, "Witness inputs"),
+        }
+    }
+}
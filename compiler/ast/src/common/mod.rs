@@ -19,6 +19,9 @@ pub mod global_consts_json;
 pub mod identifier;
 pub use identifier::*;
 
+pub mod language_version;
+pub use language_version::*;
+
 pub mod imported_modules;
 pub use imported_modules::*;
 
@@ -29,3 +32,6 @@ pub mod node;
 pub mod static_string;
 
 pub use static_string::*;
+
+pub mod trivia;
+pub use trivia::*;
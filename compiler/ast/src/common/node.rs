@@ -16,6 +16,50 @@
 
 use leo_span::Span;
 
+use std::cell::Cell;
+
+/// The id of a node in the AST, e.g. an expression, a statement, an identifier or a literal,
+/// so that analyses can record facts about it in side tables.
+/// The parser numbers the nodes of a program and its imports from one,
+/// and passes allocate the ids of new nodes with a [`NodeBuilder`].
+/// The builders in [`crate::builders`] allocate the ids of the nodes they make in the same way.
+pub type NodeID = usize;
+
+/// Allocates the ids of new nodes, counting up from a starting id.
+#[derive(Debug)]
+pub struct NodeBuilder {
+    /// The id of the next node.
+    next_id: Cell<NodeID>,
+}
+
+impl NodeBuilder {
+    /// Returns a new builder whose first id is `next_id`.
+    pub fn new(next_id: NodeID) -> Self {
+        Self {
+            next_id: Cell::new(next_id),
+        }
+    }
+
+    /// Returns a new builder for the nodes added to `program`, whose ids follow those of its nodes.
+    pub fn for_program(program: &crate::Program) -> Self {
+        Self::new(program.max_node_id() + 1)
+    }
+
+    /// Returns the id of a new node.
+    pub fn next_id(&self) -> NodeID {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        id
+    }
+}
+
+impl Default for NodeBuilder {
+    /// Returns a new builder whose first id is `1`, since `0` is the id of nodes that are not part of a program.
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
 /// A node in the AST.
 pub trait Node:
     std::fmt::Debug + std::fmt::Display + Clone + PartialEq + Eq + serde::Serialize + serde::de::DeserializeOwned
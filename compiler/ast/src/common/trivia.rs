@@ -0,0 +1,39 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_span::{span::BytePos, Span};
+
+/// A comment in source code text, e.g. `// The total supply.`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Comment {
+    /// The text of the comment, including `//` or `/* */`, but without the newline ending a line comment.
+    pub text: String,
+    /// The span of the comment.
+    pub span: Span,
+    /// Whether the comment follows other code on the same line, e.g. `let x: u8 = 1u8; // One.`,
+    /// rather than being on a line of its own.
+    pub trailing: bool,
+}
+
+/// The comments and blank lines of source code text, which the parser discards.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trivia {
+    /// The comments, in the order they appear in the source.
+    pub comments: Vec<Comment>,
+    /// The positions of the blank lines, in the order they appear in the source.
+    /// A run of several blank lines is recorded once.
+    pub blank_lines: Vec<BytePos>,
+}
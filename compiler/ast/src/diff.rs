@@ -0,0 +1,331 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Compares two versions of a program item by item, e.g. to test what a pass changed,
+//! or to find the items whose compiled output must be invalidated.
+//!
+//! Items are compared by their source code as written by [`crate::pretty`], so that moving an item,
+//! which changes its spans and node ids, does not make it differ.
+//! The statements of a changed function are compared in the same way, to find the statements that were edited.
+
+use crate::{pretty, Block, Function, Node, NodeID, Program, ProgramScope, Statement};
+
+use indexmap::IndexMap;
+use leo_span::Span;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Returns the items that were added, removed, or changed from `old` to `new`,
+/// along with the statements that were edited in the changed functions.
+pub fn diff(old: &Program, new: &Program) -> AstDiff {
+    let mut diff = AstDiff::default();
+    diff.programs(old, new);
+    diff
+}
+
+/// The kind of an item of a program.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Import,
+    Program,
+    Struct,
+    Mapping,
+    Function,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Import => write!(f, "import"),
+            Self::Program => write!(f, "program"),
+            Self::Struct => write!(f, "struct"),
+            Self::Mapping => write!(f, "mapping"),
+            Self::Function => write!(f, "function"),
+        }
+    }
+}
+
+/// How an item or a statement differs between the old and the new program.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Change {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl Change {
+    /// The sign that marks the change in the text of a diff.
+    fn sign(self) -> char {
+        match self {
+            Self::Added => '+',
+            Self::Removed => '-',
+            Self::Changed => '~',
+        }
+    }
+}
+
+/// An item that differs between the old and the new program.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ItemDiff {
+    pub kind: ItemKind,
+    /// The name of the item, qualified with its program id, e.g. `test.aleo/main`.
+    pub name: String,
+    pub change: Change,
+    /// The edited statements of a changed function, in the order of the function.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub edits: Vec<StatementEdit>,
+}
+
+/// A statement of a function that was added, removed, or changed.
+/// A changed statement is one that was removed, and replaced with one that was added at the same place.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StatementEdit {
+    pub change: Change,
+    /// The statement of the old program, unless it was added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old: Option<StatementRef>,
+    /// The statement of the new program, unless it was removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new: Option<StatementRef>,
+}
+
+/// Refers to a statement of one of the compared programs.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StatementRef {
+    pub id: NodeID,
+    pub span: Span,
+    /// The source code of the statement.
+    pub source: String,
+}
+
+impl StatementRef {
+    fn new(statement: &Statement, source: String) -> Self {
+        Self {
+            id: statement.id(),
+            span: statement.span(),
+            source,
+        }
+    }
+}
+
+/// The differences between two programs.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AstDiff {
+    pub items: Vec<ItemDiff>,
+}
+
+impl AstDiff {
+    /// Returns `true` if the programs have the same items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Records the differences between two programs and their imports.
+    fn programs(&mut self, old: &Program, new: &Program) {
+        let (old_imports, new_imports) = (by_name(&old.imports), by_name(&new.imports));
+        for (name, &old_import) in old_imports.iter() {
+            match new_imports.get(name) {
+                Some(&new_import) => self.programs(old_import, new_import),
+                None => self.item(ItemKind::Import, name, Change::Removed),
+            }
+        }
+        for name in new_imports.keys().filter(|name| !old_imports.contains_key(*name)) {
+            self.item(ItemKind::Import, name, Change::Added);
+        }
+
+        let (old_scopes, new_scopes) = (by_name(&old.program_scopes), by_name(&new.program_scopes));
+        for (id, &old_scope) in old_scopes.iter() {
+            match new_scopes.get(id) {
+                Some(&new_scope) => self.program_scopes(old_scope, new_scope),
+                None => self.item(ItemKind::Program, id, Change::Removed),
+            }
+        }
+        for id in new_scopes.keys().filter(|id| !old_scopes.contains_key(*id)) {
+            self.item(ItemKind::Program, id, Change::Added);
+        }
+    }
+
+    /// Records the differences between the items of two program scopes with the same id.
+    fn program_scopes(&mut self, old: &ProgramScope, new: &ProgramScope) {
+        let program = old.program_id.to_string();
+        self.items_of(
+            ItemKind::Struct,
+            &program,
+            &old.structs,
+            &new.structs,
+            pretty::struct_,
+            |_, _| Vec::new(),
+        );
+        self.items_of(
+            ItemKind::Mapping,
+            &program,
+            &old.mappings,
+            &new.mappings,
+            pretty::mapping,
+            |_, _| Vec::new(),
+        );
+        self.items_of(
+            ItemKind::Function,
+            &program,
+            &old.functions,
+            &new.functions,
+            pretty::function,
+            function_edits,
+        );
+    }
+
+    /// Records the differences between the items of one kind of two program scopes,
+    /// which are matched by name and compared by their `source`.
+    fn items_of<K: fmt::Display, V>(
+        &mut self,
+        kind: ItemKind,
+        program: &str,
+        old: &IndexMap<K, V>,
+        new: &IndexMap<K, V>,
+        source: impl Fn(&V) -> String,
+        edits: impl Fn(&V, &V) -> Vec<StatementEdit>,
+    ) {
+        let (old, new) = (by_name(old), by_name(new));
+        for (name, &old_item) in old.iter() {
+            let name_ = format!("{}/{}", program, name);
+            match new.get(name) {
+                Some(&new_item) if source(old_item) != source(new_item) => self.items.push(ItemDiff {
+                    kind,
+                    name: name_,
+                    change: Change::Changed,
+                    edits: edits(old_item, new_item),
+                }),
+                Some(_) => {}
+                None => self.item(kind, name_, Change::Removed),
+            }
+        }
+        for name in new.keys().filter(|name| !old.contains_key(*name)) {
+            self.item(kind, format!("{}/{}", program, name), Change::Added);
+        }
+    }
+
+    /// Records an item without statement edits.
+    fn item(&mut self, kind: ItemKind, name: impl fmt::Display, change: Change) {
+        self.items.push(ItemDiff {
+            kind,
+            name: name.to_string(),
+            change,
+            edits: Vec::new(),
+        });
+    }
+}
+
+impl fmt::Display for AstDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for item in self.items.iter() {
+            writeln!(f, "{} {} {}", item.change.sign(), item.kind, item.name)?;
+            for edit in item.edits.iter() {
+                for (sign, statement) in [('-', &edit.old), ('+', &edit.new)] {
+                    if let Some(statement) = statement {
+                        for line in statement.source.lines() {
+                            writeln!(f, "    {} {}", sign, line)?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the entries of `map` keyed by the names of their keys.
+/// The keys themselves differ between programs, since identifiers are compared along with their spans.
+fn by_name<K: fmt::Display, V>(map: &IndexMap<K, V>) -> IndexMap<String, &V> {
+    map.iter().map(|(key, value)| (key.to_string(), value)).collect()
+}
+
+/// Returns the statements edited from `old` to `new`, first those of the bodies and then those of the finalize blocks.
+fn function_edits(old: &Function, new: &Function) -> Vec<StatementEdit> {
+    let mut edits = block_edits(Some(&old.block), Some(&new.block));
+    edits.extend(block_edits(
+        old.finalize.as_ref().map(|finalize| &finalize.block),
+        new.finalize.as_ref().map(|finalize| &finalize.block),
+    ));
+    edits
+}
+
+/// Returns the statements edited from `old` to `new`, which are matched by a longest common subsequence of their source.
+/// A run of removed statements that is directly followed by added statements is paired up into changed statements.
+fn block_edits(old: Option<&Block>, new: Option<&Block>) -> Vec<StatementEdit> {
+    let (old, new) = (sources(old), sources(new));
+
+    // `common[i][j]` is the length of the longest common subsequence of `old[i..]` and `new[j..]`.
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = match old[i].1 == new[j].1 {
+                true => common[i + 1][j + 1] + 1,
+                false => common[i + 1][j].max(common[i][j + 1]),
+            };
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i].1 == new[j].1 {
+            pair_up(&mut edits, &mut removed, &mut added);
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || common[i][j + 1] >= common[i + 1][j]) {
+            added.push(StatementRef::new(new[j].0, new[j].1.clone()));
+            j += 1;
+        } else {
+            removed.push(StatementRef::new(old[i].0, old[i].1.clone()));
+            i += 1;
+        }
+    }
+    pair_up(&mut edits, &mut removed, &mut added);
+    edits
+}
+
+/// Returns the statements of a block along with their source code.
+fn sources(block: Option<&Block>) -> Vec<(&Statement, String)> {
+    block
+        .map_or(&[][..], |block| &block.statements[..])
+        .iter()
+        .map(|statement| (statement, pretty::statement(statement)))
+        .collect()
+}
+
+/// Appends a run of removed and added statements to `edits`, pairing them up in order into changed statements.
+fn pair_up(edits: &mut Vec<StatementEdit>, removed: &mut Vec<StatementRef>, added: &mut Vec<StatementRef>) {
+    let mut removed = removed.drain(..);
+    let mut added = added.drain(..);
+    loop {
+        let edit = match (removed.next(), added.next()) {
+            (None, None) => break,
+            (old, new) => StatementEdit {
+                change: match (&old, &new) {
+                    (Some(_), Some(_)) => Change::Changed,
+                    (Some(_), None) => Change::Removed,
+                    _ => Change::Added,
+                },
+                old,
+                new,
+            },
+        };
+        edits.push(edit);
+    }
+}
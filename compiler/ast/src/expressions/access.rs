@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{access::*, Node};
+use crate::{access::*, Node, NodeID};
 use leo_span::Span;
 
 use serde::{Deserialize, Serialize};
@@ -37,6 +37,18 @@ pub enum AccessExpression {
     Tuple(TupleAccess),
 }
 
+impl AccessExpression {
+    /// Returns the id of the access.
+    pub fn id(&self) -> NodeID {
+        match self {
+            AccessExpression::AssociatedConstant(n) => n.id,
+            AccessExpression::AssociatedFunction(n) => n.id,
+            AccessExpression::Member(n) => n.id,
+            AccessExpression::Tuple(n) => n.id,
+        }
+    }
+}
+
 impl Node for AccessExpression {
     fn span(&self) -> Span {
         match self {
@@ -19,7 +19,7 @@ use leo_span::{sym, Symbol};
 
 /// A binary operator.
 ///
-/// Precedence is defined in the parser.
+/// Precedence is defined in the parser, and mirrored by [`BinaryOperation::precedence`].
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BinaryOperation {
     /// Addition, i.e. `+`, `.add()`.
@@ -161,6 +161,36 @@ impl BinaryOperation {
             _ => return None,
         })
     }
+
+    /// Returns the precedence of the operator, as defined in the parser.
+    /// Operators with a higher precedence bind more tightly.
+    /// Operators that can only be invoked as methods, e.g. `a.add_wrapped(b)`, bind most tightly.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Self::Or => 1,
+            Self::And => 2,
+            Self::Eq | Self::Neq => 3,
+            Self::Lt | Self::Lte | Self::Gt | Self::Gte => 4,
+            Self::Xor => 5,
+            Self::BitwiseOr => 6,
+            Self::BitwiseAnd => 7,
+            Self::Shl | Self::Shr => 8,
+            Self::Add | Self::Sub => 9,
+            Self::Mul | Self::Div | Self::Rem => 10,
+            Self::Pow => 11,
+            Self::AddWrapped
+            | Self::DivWrapped
+            | Self::Mod
+            | Self::MulWrapped
+            | Self::Nand
+            | Self::Nor
+            | Self::PowWrapped
+            | Self::RemWrapped
+            | Self::ShlWrapped
+            | Self::ShrWrapped
+            | Self::SubWrapped => 12,
+        }
+    }
 }
 
 /// A binary expression `left op right` of two operands separated by some operator.
@@ -175,11 +205,40 @@ pub struct BinaryExpression {
     pub op: BinaryOperation,
     /// The span from `left` to `right`.
     pub span: Span,
+    /// The id of the node.
+    pub id: NodeID,
 }
 
 impl fmt::Display for BinaryExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {} {}", self.left, self.op, self.right)
+        // The parser discards parentheses, so only those needed to preserve the structure of the expression are written.
+        // `**` is right-associative, the comparisons are not associative, and the other operators are left-associative.
+        let (left_associates, right_associates) = match self.op {
+            BinaryOperation::Pow => (false, true),
+            BinaryOperation::Eq
+            | BinaryOperation::Neq
+            | BinaryOperation::Lt
+            | BinaryOperation::Lte
+            | BinaryOperation::Gt
+            | BinaryOperation::Gte => (false, false),
+            _ => (true, false),
+        };
+        write_operand(f, &self.left, self.op.precedence(), left_associates)?;
+        write!(f, " {} ", self.op)?;
+        write_operand(f, &self.right, self.op.precedence(), right_associates)
+    }
+}
+
+/// Writes the operand of an operator with the given `precedence`, parenthesizing it if it binds more loosely.
+/// An operand with the same precedence is only left unparenthesized if the operator `associates` on its side.
+fn write_operand(f: &mut fmt::Formatter, operand: &Expression, precedence: u8, associates: bool) -> fmt::Result {
+    match operand {
+        Expression::Binary(binary)
+            if binary.op.precedence() < precedence || (binary.op.precedence() == precedence && !associates) =>
+        {
+            write!(f, "({})", binary)
+        }
+        _ => write!(f, "{}", operand),
     }
 }
 
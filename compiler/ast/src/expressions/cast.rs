@@ -0,0 +1,41 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::IntegerType;
+
+/// An expression converting an integer to a wider integer type, that is, `expression as type_`.
+/// Casts are not written in source code, but are inserted where the type checker implicitly widens an integer.
+/// Since an integer is only widened to a type that holds each of its values, a cast never truncates or halts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CastExpression {
+    /// The expression whose value is converted.
+    pub expression: Box<Expression>,
+    /// The integer type the value is converted to.
+    pub type_: IntegerType,
+    /// The span of `expression`.
+    pub span: Span,
+    /// The id of the node.
+    pub id: NodeID,
+}
+
+impl fmt::Display for CastExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({} as {})", self.expression, self.type_)
+    }
+}
+
+crate::simple_node_impl!(CastExpression);
@@ -24,34 +24,37 @@ use super::*;
 pub enum Literal {
     // todo: deserialize values here
     /// An address literal, e.g., `aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9`.
-    Address(String, #[serde(with = "leo_span::span_json")] Span),
+    Address(String, #[serde(with = "leo_span::span_json")] Span, NodeID),
     /// A boolean literal, either `true` or `false`.
-    Boolean(bool, #[serde(with = "leo_span::span_json")] Span),
+    Boolean(bool, #[serde(with = "leo_span::span_json")] Span, NodeID),
     /// A field literal, e.g., `42field`.
     /// A signed number followed by the keyword `field`.
-    Field(String, #[serde(with = "leo_span::span_json")] Span),
+    Field(String, #[serde(with = "leo_span::span_json")] Span, NodeID),
     /// A group literal, either product or affine.
     /// For example, `42group` or `(12, 52)group`.
     Group(Box<GroupLiteral>),
     /// An integer literal, e.g., `42`.
-    Integer(IntegerType, String, #[serde(with = "leo_span::span_json")] Span),
+    Integer(IntegerType, String, #[serde(with = "leo_span::span_json")] Span, NodeID),
     /// A scalar literal, e.g. `1scalar`.
     /// An unsigned number followed by the keyword `scalar`.
-    Scalar(String, #[serde(with = "leo_span::span_json")] Span),
+    Scalar(String, #[serde(with = "leo_span::span_json")] Span, NodeID),
+    /// A signature literal, e.g., `sign1...`.
+    Signature(String, #[serde(with = "leo_span::span_json")] Span, NodeID),
     /// A string literal, e.g., `"foobar"`.
-    String(String, #[serde(with = "leo_span::span_json")] Span),
+    String(String, #[serde(with = "leo_span::span_json")] Span, NodeID),
 }
 
 impl fmt::Display for Literal {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match &self {
-            Self::Address(address, _) => write!(f, "{}", address),
-            Self::Boolean(boolean, _) => write!(f, "{}", boolean),
-            Self::Field(field, _) => write!(f, "{}field", field),
+            Self::Address(address, ..) => write!(f, "{}", address),
+            Self::Boolean(boolean, ..) => write!(f, "{}", boolean),
+            Self::Field(field, ..) => write!(f, "{}field", field),
             Self::Group(group) => write!(f, "{}group", group),
-            Self::Integer(type_, value, _) => write!(f, "{}{}", value, type_),
-            Self::Scalar(scalar, _) => write!(f, "{}scalar", scalar),
-            Self::String(string, _) => write!(f, "\"{}\"", string),
+            Self::Integer(type_, value, ..) => write!(f, "{}{}", value, type_),
+            Self::Scalar(scalar, ..) => write!(f, "{}scalar", scalar),
+            Self::Signature(signature, ..) => write!(f, "{}", signature),
+            Self::String(string, ..) => write!(f, "\"{}\"", string),
         }
     }
 }
@@ -59,14 +62,15 @@ impl fmt::Display for Literal {
 impl Node for Literal {
     fn span(&self) -> Span {
         match &self {
-            Self::Address(_, span)
-            | Self::Boolean(_, span)
-            | Self::Field(_, span)
-            | Self::Integer(_, _, span)
-            | Self::Scalar(_, span)
-            | Self::String(_, span) => *span,
+            Self::Address(_, span, _)
+            | Self::Boolean(_, span, _)
+            | Self::Field(_, span, _)
+            | Self::Integer(_, _, span, _)
+            | Self::Scalar(_, span, _)
+            | Self::Signature(_, span, _)
+            | Self::String(_, span, _) => *span,
             Self::Group(group) => match &**group {
-                GroupLiteral::Single(_, span) => *span,
+                GroupLiteral::Single(_, span, _) => *span,
                 GroupLiteral::Tuple(tuple) => tuple.span,
             },
         }
@@ -74,16 +78,47 @@ impl Node for Literal {
 
     fn set_span(&mut self, new_span: Span) {
         match self {
-            Self::Address(_, span)
-            | Self::Boolean(_, span)
-            | Self::Field(_, span)
-            | Self::Integer(_, _, span)
-            | Self::Scalar(_, span)
-            | Self::String(_, span) => *span = new_span,
+            Self::Address(_, span, _)
+            | Self::Boolean(_, span, _)
+            | Self::Field(_, span, _)
+            | Self::Integer(_, _, span, _)
+            | Self::Scalar(_, span, _)
+            | Self::Signature(_, span, _)
+            | Self::String(_, span, _) => *span = new_span,
             Self::Group(group) => match &mut **group {
-                GroupLiteral::Single(_, span) => *span = new_span,
+                GroupLiteral::Single(_, span, _) => *span = new_span,
                 GroupLiteral::Tuple(tuple) => tuple.span = new_span,
             },
         }
     }
 }
+
+impl Literal {
+    /// Returns the id of the literal.
+    pub fn id(&self) -> NodeID {
+        match self {
+            Self::Address(_, _, id)
+            | Self::Boolean(_, _, id)
+            | Self::Field(_, _, id)
+            | Self::Integer(_, _, _, id)
+            | Self::Scalar(_, _, id)
+            | Self::Signature(_, _, id)
+            | Self::String(_, _, id) => *id,
+            Self::Group(group) => group.id(),
+        }
+    }
+
+    /// Sets the id of the literal.
+    pub fn set_id(&mut self, new_id: NodeID) {
+        match self {
+            Self::Address(_, _, id)
+            | Self::Boolean(_, _, id)
+            | Self::Field(_, _, id)
+            | Self::Integer(_, _, _, id)
+            | Self::Scalar(_, _, id)
+            | Self::Signature(_, _, id)
+            | Self::String(_, _, id) => *id = new_id,
+            Self::Group(group) => group.set_id(new_id),
+        }
+    }
+}
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Identifier, Node};
+use crate::{Identifier, Node, NodeID};
 use leo_span::Span;
 
 use serde::{Deserialize, Serialize};
@@ -29,6 +29,9 @@ pub use binary::*;
 mod call;
 pub use call::*;
 
+mod cast;
+pub use cast::*;
+
 mod struct_init;
 pub use struct_init::*;
 
@@ -56,6 +59,8 @@ pub enum Expression {
     Binary(BinaryExpression),
     /// A call expression, e.g., `my_fun(args)`.
     Call(CallExpression),
+    /// A cast inserted by the compiler for an implicitly widened integer, e.g., `x as u64`.
+    Cast(CastExpression),
     /// An expression constructing a struct like `Foo { bar: 42, baz }`.
     Struct(StructExpression),
     /// An expression of type "error".
@@ -73,6 +78,26 @@ pub enum Expression {
     Unary(UnaryExpression),
 }
 
+impl Expression {
+    /// Returns the id of the expression.
+    pub fn id(&self) -> NodeID {
+        use Expression::*;
+        match self {
+            Access(n) => n.id(),
+            Binary(n) => n.id,
+            Call(n) => n.id,
+            Cast(n) => n.id,
+            Struct(n) => n.id,
+            Err(n) => n.id,
+            Identifier(n) => n.id,
+            Literal(n) => n.id(),
+            Ternary(n) => n.id,
+            Tuple(n) => n.id,
+            Unary(n) => n.id,
+        }
+    }
+}
+
 impl Node for Expression {
     fn span(&self) -> Span {
         use Expression::*;
@@ -80,6 +105,7 @@ impl Node for Expression {
             Access(n) => n.span(),
             Binary(n) => n.span(),
             Call(n) => n.span(),
+            Cast(n) => n.span(),
             Struct(n) => n.span(),
             Err(n) => n.span(),
             Identifier(n) => n.span(),
@@ -96,6 +122,7 @@ impl Node for Expression {
             Access(n) => n.set_span(span),
             Binary(n) => n.set_span(span),
             Call(n) => n.set_span(span),
+            Cast(n) => n.set_span(span),
             Struct(n) => n.set_span(span),
             Identifier(n) => n.set_span(span),
             Literal(n) => n.set_span(span),
@@ -114,6 +141,7 @@ impl fmt::Display for Expression {
             Access(n) => n.fmt(f),
             Binary(n) => n.fmt(f),
             Call(n) => n.fmt(f),
+            Cast(n) => n.fmt(f),
             Struct(n) => n.fmt(f),
             Err(n) => n.fmt(f),
             Identifier(n) => n.fmt(f),
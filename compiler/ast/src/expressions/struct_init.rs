@@ -50,6 +50,8 @@ pub struct StructExpression {
     pub members: Vec<StructVariableInitializer>,
     /// A span from `name` to `}`.
     pub span: Span,
+    /// The id of the node.
+    pub id: NodeID,
 }
 
 impl StructExpression {
@@ -24,6 +24,8 @@ pub struct TupleExpression {
     pub elements: Vec<Expression>,
     /// The span from `(` to `)`.
     pub span: Span,
+    /// The id of the node.
+    pub id: NodeID,
 }
 
 impl fmt::Display for TupleExpression {
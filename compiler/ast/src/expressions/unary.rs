@@ -24,6 +24,8 @@ pub enum UnaryOperation {
     Abs,
     /// Absolute value wrapping around at the boundary of the type, i.e. `.abs_wrapped()`.
     AbsWrapped,
+    /// Waits for a future to complete, i.e. `.await()`.
+    Await,
     /// Double operation, i.e. `.double()`.
     Double,
     /// Multiplicative inverse, i.e. `.inv()`.
@@ -44,6 +46,7 @@ impl UnaryOperation {
         Some(match symbol {
             sym::abs => Self::Abs,
             sym::abs_wrapped => Self::AbsWrapped,
+            sym::Await => Self::Await,
             sym::double => Self::Double,
             sym::inv => Self::Inverse,
             sym::neg => Self::Negate,
@@ -55,10 +58,11 @@ impl UnaryOperation {
     }
 
     /// Represents the opera.tor as a string.
-    fn as_str(self) -> &'static str {
+    pub fn as_str(self) -> &'static str {
         match self {
             Self::Abs => "abs",
             Self::AbsWrapped => "abs_wrapped",
+            Self::Await => "await",
             Self::Double => "double",
             Self::Inverse => "inv",
             Self::Negate => "neg",
@@ -78,6 +82,8 @@ pub struct UnaryExpression {
     pub op: UnaryOperation,
     /// The span covering `op inner`.
     pub span: Span,
+    /// The id of the node.
+    pub id: NodeID,
 }
 
 impl fmt::Display for UnaryExpression {
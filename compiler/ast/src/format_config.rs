@@ -0,0 +1,126 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The layout of printed Leo source and of the comments in generated code, shared by `leo fmt`, the pretty-printer,
+//! and code generation, so that all of them follow the style configured for a package.
+
+use crate::{GroupLiteral, Literal};
+
+use serde::{Deserialize, Serialize};
+
+/// The layout of printed source, as configured under `format` in `program.json`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FormatConfig {
+    /// The number of spaces of one level of nesting.
+    pub indent_width: usize,
+    /// The length that lines are kept within where they can be broken,
+    /// i.e. in the parameters of a function signature and in comments in generated code.
+    pub max_line_length: usize,
+    /// How the digits of numeric literals are written.
+    pub literal_style: LiteralStyle,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            indent_width: 4,
+            max_line_length: 100,
+            literal_style: LiteralStyle::default(),
+        }
+    }
+}
+
+/// How the digits of numeric literals are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LiteralStyle {
+    /// The digits are written as they are in the source.
+    #[default]
+    Preserve,
+    /// Leading zeros are removed, e.g. `007u8` is written `7u8`.
+    Normalize,
+}
+
+impl FormatConfig {
+    /// Returns the indentation of the given level of nesting.
+    pub fn indent(&self, level: usize) -> String {
+        " ".repeat(level * self.indent_width)
+    }
+
+    /// Returns whether `text` fits on a line after `column` characters.
+    pub fn fits(&self, column: usize, text: &str) -> bool {
+        column + text.chars().count() <= self.max_line_length
+    }
+
+    /// Returns the source code of a literal in the configured style.
+    pub fn literal(&self, literal: &Literal) -> String {
+        match literal {
+            Literal::Field(value, ..) => format!("{}field", self.digits(value)),
+            Literal::Group(group) => match &**group {
+                GroupLiteral::Single(value, ..) => format!("{}group", self.digits(value)),
+                GroupLiteral::Tuple(tuple) => format!(
+                    "({}, {})group",
+                    self.digits(&tuple.x.to_string()),
+                    self.digits(&tuple.y.to_string())
+                ),
+            },
+            Literal::Integer(type_, value, ..) => format!("{}{}", self.digits(value), type_),
+            Literal::Scalar(value, ..) => format!("{}scalar", self.digits(value)),
+            _ => literal.to_string(),
+        }
+    }
+
+    /// Returns the digits of a numeric literal, which may be negative, in the configured style.
+    /// Coordinates of group literals that are not numbers, e.g. `+` or `_`, are returned as they are.
+    fn digits(&self, value: &str) -> String {
+        match self.literal_style {
+            LiteralStyle::Preserve => value.to_string(),
+            LiteralStyle::Normalize => {
+                let (sign, digits) = match value.strip_prefix('-') {
+                    Some(digits) => ("-", digits),
+                    None => ("", value),
+                };
+                if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                    return value.to_string();
+                }
+                match digits.trim_start_matches('0') {
+                    "" => "0".to_string(),
+                    digits => format!("{}{}", sign, digits),
+                }
+            }
+        }
+    }
+
+    /// Splits `text` into lines that fit within the maximum line length after a `prefix`, e.g. `// ` for comments.
+    /// Lines are broken between words, and a word that is longer than a line is kept whole.
+    pub fn wrap(&self, prefix: &str, text: &str) -> Vec<String> {
+        let width = self.max_line_length.saturating_sub(prefix.chars().count());
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        for word in text.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+        lines
+    }
+}
@@ -16,7 +16,7 @@
 
 use crate::{simple_node_impl, Identifier, Node};
 
-use leo_span::Span;
+use leo_span::{Span, Symbol};
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -27,14 +27,40 @@ pub struct Annotation {
     // TODO: Consider using a symbol instead of an identifier.
     /// The name of the annotation.
     pub identifier: Identifier,
+    /// The arguments of the annotation with their values as written, e.g. `cases = 256` in `@test(cases = 256)`.
+    /// An argument without a value, e.g. `none` in `@optimize(none)`, has an empty value.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<(Identifier, String)>,
     /// A span locating where the annotation occurred in the source.
     pub span: Span,
 }
 
 simple_node_impl!(Annotation);
 
+impl Annotation {
+    /// Returns the value of the argument `name`, if it is given.
+    pub fn argument(&self, name: Symbol) -> Option<&str> {
+        self.arguments
+            .iter()
+            .find(|(argument, _)| argument.name == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
 impl fmt::Display for Annotation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "@{}", self.identifier)
+        write!(f, "@{}", self.identifier)?;
+        if !self.arguments.is_empty() {
+            let arguments: Vec<_> = self
+                .arguments
+                .iter()
+                .map(|(name, value)| match value.is_empty() {
+                    true => name.to_string(),
+                    false => format!("{name} = {value}"),
+                })
+                .collect();
+            write!(f, "({})", arguments.join(", "))?;
+        }
+        Ok(())
     }
 }
@@ -20,9 +20,13 @@ use serde::{Deserialize, Serialize};
 /// A transition function is permitted the ability to manipulate records.
 /// A regular function is not permitted to manipulate records.
 /// An inline function is directly copied at the call site.
+/// An async transition returns a `Future` for the on-chain part of its execution, which is an async function.
+/// Async transitions and functions are desugared into transitions with finalize blocks right after parsing.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum CallType {
     Inline,
     Standard,
     Transition,
+    AsyncFunction,
+    AsyncTransition,
 }
@@ -44,6 +44,9 @@ use std::fmt;
 /// A function definition.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Function {
+    /// The doc comment of the function, without the leading `///`s, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<String>,
     /// Annotations on the function.
     pub annotations: Vec<Annotation>,
     /// Is this function a transition, inlined, or a regular function?.
@@ -76,6 +79,7 @@ impl Function {
     /// Initialize a new function.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
+        documentation: Option<String>,
         annotations: Vec<Annotation>,
         call_type: CallType,
         identifier: Identifier,
@@ -98,6 +102,7 @@ impl Function {
         };
 
         Function {
+            documentation,
             annotations,
             call_type,
             identifier,
@@ -114,6 +119,21 @@ impl Function {
         self.identifier.name
     }
 
+    /// Returns whether the function is annotated with `@test`, so that it is run by `leo test` instead of compiled.
+    pub fn is_test(&self) -> bool {
+        self.annotations
+            .iter()
+            .any(|annotation| annotation.identifier.name == sym::test)
+    }
+
+    /// Returns whether the function is annotated with `@optimize(none)`,
+    /// so that its instructions are generated as written, without removing the assertions that always hold.
+    pub fn is_unoptimized(&self) -> bool {
+        self.annotations
+            .iter()
+            .any(|annotation| annotation.identifier.name == sym::optimize && annotation.argument(sym::none) == Some(""))
+    }
+
     /// Returns `true` if the function name is `main`.
     pub fn is_main(&self) -> bool {
         self.name() == sym::main
@@ -127,6 +147,8 @@ impl Function {
             CallType::Inline => write!(f, "inline ")?,
             CallType::Standard => write!(f, "function ")?,
             CallType::Transition => write!(f, "transition ")?,
+            CallType::AsyncFunction => write!(f, "async function ")?,
+            CallType::AsyncTransition => write!(f, "async transition ")?,
         }
         write!(f, "{}", self.identifier)?;
 
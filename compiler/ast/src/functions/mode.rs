@@ -24,6 +24,9 @@ pub enum Mode {
     Const,
     Private,
     Public,
+    /// An input of a transition that the prover supplies as a private input,
+    /// but that is not an argument of calls to the transition.
+    Witness,
 }
 
 impl fmt::Display for Mode {
@@ -35,6 +38,7 @@ impl fmt::Display for Mode {
             Const => write!(f, "const"),
             Private => write!(f, "private"),
             Public => write!(f, "public"),
+            Witness => write!(f, "witness"),
         }
     }
 }
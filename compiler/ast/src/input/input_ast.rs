@@ -14,10 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{normalize_json_value, remove_key_from_json, Expression, Struct, Type};
+use crate::{normalize_json_value, remove_key_from_json, Expression, Mode, Node, Struct, Type};
 
 use super::*;
-use leo_errors::{AstError, Result};
+use leo_errors::{AstError, InputError, Result};
 
 /// Input data which includes [`ProgramInput`].
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -41,34 +41,37 @@ pub struct InputAst {
 
 impl InputAst {
     /// Returns all values of the input AST for execution with `leo run`.
-    pub fn program_inputs(&self, program_name: &str, structs: IndexMap<Symbol, Struct>) -> Vec<String> {
-        self.sections
+    /// Witnesses are the last inputs of a function, so their values follow the others, wherever they are defined.
+    /// Fails if an input has a struct or record type that is not in `structs`, or a record type but no record value.
+    pub fn program_inputs(&self, program_name: &str, structs: IndexMap<Symbol, Struct>) -> Result<Vec<String>> {
+        let mut definitions: Vec<_> = self
+            .sections
             .iter()
             .filter(|section| section.name() == program_name)
-            .flat_map(|section| {
-                section.definitions.iter().map(|definition| match &definition.type_ {
-                    // Handle case where the input may be record.
-                    Type::Identifier(identifier) => {
-                        match structs.get(&identifier.name) {
-                            // TODO: Better error handling.
-                            None => panic!(
-                                "Input error: A struct or record declaration does not exist for {}.",
-                                identifier.name
-                            ),
-                            Some(struct_) => match struct_.is_record {
-                                false => definition.value.to_string(),
-                                true => match &definition.value {
-                                    // Print out the record interface with visibility.
-                                    Expression::Struct(struct_expression) => struct_expression.to_record_string(),
-                                    _ => panic!("Input error: Expected a struct expression."),
-                                },
-                            },
-                        }
-                    }
-                    _ => definition.value.to_string(),
-                })
+            .flat_map(|section| section.definitions.iter())
+            .collect();
+        definitions.sort_by_key(|definition| definition.mode == Mode::Witness);
+
+        definitions
+            .into_iter()
+            .map(|definition| match &definition.type_ {
+                // Handle case where the input may be record.
+                Type::Identifier(identifier) => match structs.get(&identifier.name) {
+                    None => Err(InputError::unknown_input_struct(identifier.name, identifier.span).into()),
+                    Some(struct_) => match struct_.is_record {
+                        false => Ok(definition.value.to_string()),
+                        true => match &definition.value {
+                            // Print out the record interface with visibility.
+                            Expression::Struct(struct_expression) => Ok(struct_expression.to_record_string()),
+                            value => {
+                                Err(InputError::expected_record_value(identifier.name, value, value.span()).into())
+                            }
+                        },
+                    },
+                },
+                _ => Ok(definition.value.to_string()),
             })
-            .collect::<Vec<_>>()
+            .collect()
     }
 
     /// Serializes the `Input` into a JSON Value.
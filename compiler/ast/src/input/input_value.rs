@@ -27,6 +27,7 @@ pub enum InputValue {
     Field(String),
     Group(GroupLiteral),
     Integer(IntegerType, String),
+    Signature(String),
 }
 
 impl TryFrom<(Type, Expression)> for InputValue {
@@ -34,11 +35,12 @@ impl TryFrom<(Type, Expression)> for InputValue {
     fn try_from(value: (Type, Expression)) -> Result<Self> {
         Ok(match value {
             (type_, Expression::Literal(lit)) => match (type_, lit) {
-                (Type::Address, Literal::Address(value, _)) => Self::Address(value),
-                (Type::Boolean, Literal::Boolean(value, _)) => Self::Boolean(value),
-                (Type::Field, Literal::Field(value, _)) => Self::Field(value),
+                (Type::Address, Literal::Address(value, ..)) => Self::Address(value),
+                (Type::Boolean, Literal::Boolean(value, ..)) => Self::Boolean(value),
+                (Type::Field, Literal::Field(value, ..)) => Self::Field(value),
                 (Type::Group, Literal::Group(value)) => Self::Group(*value),
-                (Type::Integer(expected), Literal::Integer(actual, value, span)) => {
+                (Type::Signature, Literal::Signature(value, ..)) => Self::Signature(value),
+                (Type::Integer(expected), Literal::Integer(actual, value, span, _)) => {
                     if expected == actual {
                         Self::Integer(expected, value)
                     } else {
@@ -65,6 +67,7 @@ impl fmt::Display for InputValue {
             InputValue::Group(ref group) => write!(f, "{}", group),
             InputValue::Field(ref field) => write!(f, "{}", field),
             InputValue::Integer(ref type_, ref number) => write!(f, "{}{:?}", number, type_),
+            InputValue::Signature(ref signature) => write!(f, "{}", signature),
         }
     }
 }
@@ -30,12 +30,24 @@ pub use self::access::*;
 pub mod r#struct;
 pub use self::r#struct::*;
 
+pub mod binary;
+pub use self::binary::*;
+
+pub mod builders;
+pub use self::builders::*;
+
 pub mod common;
 pub use self::common::*;
 
+pub mod diff;
+pub use self::diff::*;
+
 pub mod expressions;
 pub use self::expressions::*;
 
+pub mod format_config;
+pub use self::format_config::*;
+
 pub mod functions;
 pub use self::functions::*;
 
@@ -51,6 +63,8 @@ pub use self::mapping::*;
 pub mod passes;
 pub use self::passes::*;
 
+pub mod pretty;
+
 pub mod program;
 pub use self::program::*;
 
@@ -65,6 +79,8 @@ pub use self::value::*;
 
 pub use common::node::*;
 
+pub use leo_ast_derive::AstVisitor;
+
 use leo_errors::{AstError, Result};
 
 /// The abstract syntax tree (AST) for a Leo program.
@@ -124,14 +140,38 @@ impl Ast {
         let file = std::fs::File::create(&path).map_err(|e| AstError::failed_to_create_ast_json_file(&path, &e))?;
         let writer = std::io::BufWriter::new(file);
 
-        let mut value = self.to_json_value().unwrap();
+        let value = self.to_json_value_without_keys(excluded_keys)?;
+
+        Ok(serde_json::to_writer_pretty(writer, &value)
+            .map_err(|e| AstError::failed_to_write_ast_to_json_file(&path, &e))?)
+    }
+
+    /// Converts the ast into a JSON value, removing keys from object mappings.
+    pub fn to_json_value_without_keys(&self, excluded_keys: &[&str]) -> Result<serde_json::Value> {
+        let mut value = self.to_json_value()?;
         for key in excluded_keys {
             value = remove_key_from_json(value, key);
         }
-        value = normalize_json_value(value);
+        Ok(normalize_json_value(value))
+    }
 
-        Ok(serde_json::to_writer_pretty(writer, &value)
-            .map_err(|e| AstError::failed_to_write_ast_to_json_file(&path, &e))?)
+    /// Serializes the ast into the compact binary format, which keeps spans and node ids.
+    pub fn to_binary(&self) -> Result<Vec<u8>> {
+        to_binary(&self.ast)
+    }
+
+    /// Serializes the ast into a file in the compact binary format.
+    pub fn to_binary_file(&self, mut path: std::path::PathBuf, file_name: &str) -> Result<()> {
+        path.push(file_name);
+        let bytes = self.to_binary()?;
+        Ok(std::fs::write(&path, bytes).map_err(|e| AstError::failed_to_write_ast_to_binary_file(&path, &e))?)
+    }
+
+    /// Deserializes an ast from the compact binary format.
+    pub fn from_binary(bytes: &[u8]) -> Result<Self> {
+        Ok(Self {
+            ast: from_binary(bytes)?,
+        })
     }
 
     /// Deserializes the JSON string into a ast.
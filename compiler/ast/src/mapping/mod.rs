@@ -24,6 +24,9 @@ use std::fmt;
 /// A mapping declaration, e.g `mapping balances: address => u128`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Mapping {
+    /// The doc comment of the mapping, without the leading `///`s, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<String>,
     /// The name of the mapping.
     pub identifier: Identifier,
     /// The type of the key.
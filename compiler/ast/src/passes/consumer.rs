@@ -28,6 +28,7 @@ pub trait ExpressionConsumer {
             Expression::Access(access) => self.consume_access(access),
             Expression::Binary(binary) => self.consume_binary(binary),
             Expression::Call(call) => self.consume_call(call),
+            Expression::Cast(cast) => self.consume_cast(cast),
             Expression::Struct(struct_) => self.consume_struct_init(struct_),
             Expression::Err(err) => self.consume_err(err),
             Expression::Identifier(identifier) => self.consume_identifier(identifier),
@@ -44,6 +45,8 @@ pub trait ExpressionConsumer {
 
     fn consume_call(&mut self, _input: CallExpression) -> Self::Output;
 
+    fn consume_cast(&mut self, _input: CastExpression) -> Self::Output;
+
     fn consume_struct_init(&mut self, _input: StructExpression) -> Self::Output;
 
     fn consume_err(&mut self, _input: ErrExpression) -> Self::Output {
@@ -73,9 +76,11 @@ pub trait StatementConsumer {
             Statement::Console(stmt) => self.consume_console(stmt),
             Statement::Decrement(stmt) => self.consume_decrement(stmt),
             Statement::Definition(stmt) => self.consume_definition(stmt),
+            Statement::Expression(stmt) => self.consume_expression_statement(stmt),
             Statement::Finalize(stmt) => self.consume_finalize(stmt),
             Statement::Increment(stmt) => self.consume_increment(stmt),
             Statement::Iteration(stmt) => self.consume_iteration(*stmt),
+            Statement::Match(stmt) => self.consume_match(stmt),
             Statement::Return(stmt) => self.consume_return(stmt),
         }
     }
@@ -92,12 +97,16 @@ pub trait StatementConsumer {
 
     fn consume_definition(&mut self, input: DefinitionStatement) -> Self::Output;
 
+    fn consume_expression_statement(&mut self, input: ExpressionStatement) -> Self::Output;
+
     fn consume_finalize(&mut self, input: FinalizeStatement) -> Self::Output;
 
     fn consume_increment(&mut self, input: IncrementStatement) -> Self::Output;
 
     fn consume_iteration(&mut self, input: IterationStatement) -> Self::Output;
 
+    fn consume_match(&mut self, input: MatchStatement) -> Self::Output;
+
     fn consume_return(&mut self, input: ReturnStatement) -> Self::Output;
 }
 
@@ -29,6 +29,7 @@ pub trait ExpressionReconstructor {
             Expression::Access(access) => self.reconstruct_access(access),
             Expression::Binary(binary) => self.reconstruct_binary(binary),
             Expression::Call(call) => self.reconstruct_call(call),
+            Expression::Cast(cast) => self.reconstruct_cast(cast),
             Expression::Struct(struct_) => self.reconstruct_struct_init(struct_),
             Expression::Err(err) => self.reconstruct_err(err),
             Expression::Identifier(identifier) => self.reconstruct_identifier(identifier),
@@ -52,17 +53,20 @@ pub trait ExpressionReconstructor {
                             .map(|arg| self.reconstruct_expression(arg).0)
                             .collect(),
                         span: function.span,
+                        id: function.id,
                     })
                 }
                 AccessExpression::Member(member) => AccessExpression::Member(MemberAccess {
                     inner: Box::new(self.reconstruct_expression(*member.inner).0),
                     name: member.name,
                     span: member.span,
+                    id: member.id,
                 }),
                 AccessExpression::Tuple(tuple) => AccessExpression::Tuple(TupleAccess {
                     tuple: Box::new(self.reconstruct_expression(*tuple.tuple).0),
                     index: tuple.index,
                     span: tuple.span,
+                    id: tuple.id,
                 }),
                 expr => expr,
             }),
@@ -77,6 +81,7 @@ pub trait ExpressionReconstructor {
                 right: Box::new(self.reconstruct_expression(*input.right).0),
                 op: input.op,
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -93,6 +98,19 @@ pub trait ExpressionReconstructor {
                     .collect(),
                 external: input.external,
                 span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_cast(&mut self, input: CastExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Cast(CastExpression {
+                expression: Box::new(self.reconstruct_expression(*input.expression).0),
+                type_: input.type_,
+                span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -121,6 +139,7 @@ pub trait ExpressionReconstructor {
                 if_true: Box::new(self.reconstruct_expression(*input.if_true).0),
                 if_false: Box::new(self.reconstruct_expression(*input.if_false).0),
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -135,6 +154,7 @@ pub trait ExpressionReconstructor {
                     .map(|element| self.reconstruct_expression(element).0)
                     .collect(),
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -146,6 +166,7 @@ pub trait ExpressionReconstructor {
                 receiver: Box::new(self.reconstruct_expression(*input.receiver).0),
                 op: input.op,
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -165,9 +186,11 @@ pub trait StatementReconstructor: ExpressionReconstructor {
             Statement::Console(stmt) => self.reconstruct_console(stmt),
             Statement::Decrement(stmt) => self.reconstruct_decrement(stmt),
             Statement::Definition(stmt) => self.reconstruct_definition(stmt),
+            Statement::Expression(stmt) => self.reconstruct_expression_statement(stmt),
             Statement::Finalize(stmt) => self.reconstruct_finalize(stmt),
             Statement::Increment(stmt) => self.reconstruct_increment(stmt),
             Statement::Iteration(stmt) => self.reconstruct_iteration(*stmt),
+            Statement::Match(stmt) => self.reconstruct_match(stmt),
             Statement::Return(stmt) => self.reconstruct_return(stmt),
         }
     }
@@ -178,6 +201,7 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                 place: input.place,
                 value: self.reconstruct_expression(input.value).0,
                 span: input.span,
+                id: input.id,
             })),
             Default::default(),
         )
@@ -192,6 +216,7 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                     .map(|s| self.reconstruct_statement(s).0)
                     .collect(),
                 span: input.span,
+                id: input.id,
             },
             Default::default(),
         )
@@ -204,6 +229,7 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                 then: self.reconstruct_block(input.then).0,
                 otherwise: input.otherwise.map(|n| Box::new(self.reconstruct_statement(*n).0)),
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -213,22 +239,40 @@ pub trait StatementReconstructor: ExpressionReconstructor {
         (
             Statement::Console(ConsoleStatement {
                 function: match input.function {
-                    ConsoleFunction::Assert(expr) => ConsoleFunction::Assert(self.reconstruct_expression(expr).0),
-                    ConsoleFunction::AssertEq(left, right) => ConsoleFunction::AssertEq(
+                    ConsoleFunction::Assert(expr, message) => ConsoleFunction::Assert(
+                        self.reconstruct_expression(expr).0,
+                        message.map(|message| self.reconstruct_assert_message(message)),
+                    ),
+                    ConsoleFunction::AssertEq(left, right, message) => ConsoleFunction::AssertEq(
                         self.reconstruct_expression(left).0,
                         self.reconstruct_expression(right).0,
+                        message.map(|message| self.reconstruct_assert_message(message)),
                     ),
-                    ConsoleFunction::AssertNeq(left, right) => ConsoleFunction::AssertNeq(
+                    ConsoleFunction::AssertNeq(left, right, message) => ConsoleFunction::AssertNeq(
                         self.reconstruct_expression(left).0,
                         self.reconstruct_expression(right).0,
+                        message.map(|message| self.reconstruct_assert_message(message)),
                     ),
                 },
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
     }
 
+    fn reconstruct_assert_message(&mut self, input: AssertMessage) -> AssertMessage {
+        AssertMessage {
+            string: input.string,
+            parameters: input
+                .parameters
+                .into_iter()
+                .map(|parameter| self.reconstruct_expression(parameter).0)
+                .collect(),
+            span: input.span,
+        }
+    }
+
     fn reconstruct_decrement(&mut self, input: DecrementStatement) -> (Statement, Self::AdditionalOutput) {
         (
             Statement::Decrement(DecrementStatement {
@@ -236,6 +280,7 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                 index: input.index,
                 amount: input.amount,
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -249,6 +294,18 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                 type_: input.type_,
                 value: self.reconstruct_expression(input.value).0,
                 span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_expression_statement(&mut self, input: ExpressionStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Expression(ExpressionStatement {
+                expression: self.reconstruct_expression(input.expression).0,
+                span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -263,6 +320,7 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                     .map(|arg| self.reconstruct_expression(arg).0)
                     .collect(),
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -275,6 +333,7 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                 index: input.index,
                 amount: input.amount,
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -292,16 +351,38 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                 block: self.reconstruct_block(input.block).0,
                 inclusive: input.inclusive,
                 span: input.span,
+                id: input.id,
             })),
             Default::default(),
         )
     }
 
+    fn reconstruct_match(&mut self, input: MatchStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Match(MatchStatement {
+                expression: self.reconstruct_expression(input.expression).0,
+                arms: input
+                    .arms
+                    .into_iter()
+                    .map(|arm| MatchArm {
+                        pattern: arm.pattern,
+                        block: self.reconstruct_block(arm.block).0,
+                        span: arm.span,
+                    })
+                    .collect(),
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
     fn reconstruct_return(&mut self, input: ReturnStatement) -> (Statement, Self::AdditionalOutput) {
         (
             Statement::Return(ReturnStatement {
                 expression: self.reconstruct_expression(input.expression).0,
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -322,6 +403,7 @@ pub trait ProgramReconstructor: StatementReconstructor {
                 .into_iter()
                 .map(|(id, scope)| (id, self.reconstruct_program_scope(scope)))
                 .collect(),
+            import_source: input.import_source,
         }
     }
 
@@ -349,6 +431,7 @@ pub trait ProgramReconstructor: StatementReconstructor {
 
     fn reconstruct_function(&mut self, input: Function) -> Function {
         Function {
+            documentation: input.documentation,
             annotations: input.annotations,
             call_type: input.call_type,
             identifier: input.identifier,
@@ -30,6 +30,7 @@ pub trait ExpressionVisitor<'a> {
             Expression::Access(access) => self.visit_access(access, additional),
             Expression::Binary(binary) => self.visit_binary(binary, additional),
             Expression::Call(call) => self.visit_call(call, additional),
+            Expression::Cast(cast) => self.visit_cast(cast, additional),
             Expression::Struct(struct_) => self.visit_struct_init(struct_, additional),
             Expression::Err(err) => self.visit_err(err, additional),
             Expression::Identifier(identifier) => self.visit_identifier(identifier, additional),
@@ -72,6 +73,11 @@ pub trait ExpressionVisitor<'a> {
         Default::default()
     }
 
+    fn visit_cast(&mut self, input: &'a CastExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        self.visit_expression(&input.expression, additional);
+        Default::default()
+    }
+
     fn visit_struct_init(&mut self, _input: &'a StructExpression, _additional: &Self::AdditionalInput) -> Self::Output {
         Default::default()
     }
@@ -118,9 +124,11 @@ pub trait StatementVisitor<'a>: ExpressionVisitor<'a> {
             Statement::Console(stmt) => self.visit_console(stmt),
             Statement::Decrement(stmt) => self.visit_decrement(stmt),
             Statement::Definition(stmt) => self.visit_definition(stmt),
+            Statement::Expression(stmt) => self.visit_expression_statement(stmt),
             Statement::Finalize(stmt) => self.visit_finalize(stmt),
             Statement::Increment(stmt) => self.visit_increment(stmt),
             Statement::Iteration(stmt) => self.visit_iteration(stmt),
+            Statement::Match(stmt) => self.visit_match(stmt),
             Statement::Return(stmt) => self.visit_return(stmt),
         }
     }
@@ -143,18 +151,23 @@ pub trait StatementVisitor<'a>: ExpressionVisitor<'a> {
 
     fn visit_console(&mut self, input: &'a ConsoleStatement) {
         match &input.function {
-            ConsoleFunction::Assert(expr) => {
+            ConsoleFunction::Assert(expr, _) => {
                 self.visit_expression(expr, &Default::default());
             }
-            ConsoleFunction::AssertEq(left, right) => {
+            ConsoleFunction::AssertEq(left, right, _) => {
                 self.visit_expression(left, &Default::default());
                 self.visit_expression(right, &Default::default());
             }
-            ConsoleFunction::AssertNeq(left, right) => {
+            ConsoleFunction::AssertNeq(left, right, _) => {
                 self.visit_expression(left, &Default::default());
                 self.visit_expression(right, &Default::default());
             }
         };
+        if let Some(message) = input.function.message() {
+            message.parameters.iter().for_each(|parameter| {
+                self.visit_expression(parameter, &Default::default());
+            });
+        }
     }
 
     fn visit_decrement(&mut self, input: &'a DecrementStatement) {
@@ -167,6 +180,10 @@ pub trait StatementVisitor<'a>: ExpressionVisitor<'a> {
         self.visit_expression(&input.value, &Default::default());
     }
 
+    fn visit_expression_statement(&mut self, input: &'a ExpressionStatement) {
+        self.visit_expression(&input.expression, &Default::default());
+    }
+
     fn visit_finalize(&mut self, input: &'a FinalizeStatement) {
         input.arguments.iter().for_each(|expr| {
             self.visit_expression(expr, &Default::default());
@@ -185,6 +202,11 @@ pub trait StatementVisitor<'a>: ExpressionVisitor<'a> {
         self.visit_block(&input.block);
     }
 
+    fn visit_match(&mut self, input: &'a MatchStatement) {
+        self.visit_expression(&input.expression, &Default::default());
+        input.arms.iter().for_each(|arm| self.visit_block(&arm.block));
+    }
+
     fn visit_return(&mut self, input: &'a ReturnStatement) {
         self.visit_expression(&input.expression, &Default::default());
     }
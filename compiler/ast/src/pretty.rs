@@ -0,0 +1,897 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Prints a Leo AST as source code, e.g. to read the intermediate ASTs of the compiler.
+//!
+//! The source is written in the layout of `leo fmt`. The AST does not keep comments, so they are only written
+//! if the [`Trivia`] of the source is given, as `leo fmt` does.
+//! Casts that the compiler inserted are printed as the expression they convert, which has the same value.
+//! Variables that the compiler introduced have names with a `$`, e.g. `$var$0`, that are printed as they are,
+//! so that they cannot clash with the names of the program, and the printed source cannot be compiled if it has them.
+
+use crate::*;
+
+use leo_span::span::BytePos;
+
+use std::{fmt::Write as _, iter::Peekable, vec::IntoIter};
+
+/// Returns the source code of a program.
+pub fn program(program: &Program) -> String {
+    program_with_config(program, &FormatConfig::default())
+}
+
+/// Returns the source code of a program in the layout of `config`.
+pub fn program_with_config(program: &Program, config: &FormatConfig) -> String {
+    let mut printer = Printer::new(config.clone(), None);
+    printer.visit_program(program);
+    printer.out
+}
+
+/// Returns the source code of a program in the layout of `config`,
+/// with the comments and blank lines of the source it was parsed from.
+///
+/// The comments are written before the first item or statement that follows them,
+/// or at the end of the line they follow, for trailing comments.
+/// The items of each program scope are written in the order of the source, rather than by kind.
+pub fn program_with_trivia(program: &Program, trivia: Trivia, config: &FormatConfig) -> String {
+    let mut printer = Printer::new(config.clone(), Some(trivia));
+    printer.visit_program(program);
+    printer.flush(BytePos(u32::MAX));
+    printer.out
+}
+
+/// Returns the source code of a struct or record definition.
+pub fn struct_(struct_: &Struct) -> String {
+    let mut printer = Printer::default();
+    printer.visit_struct(struct_);
+    printer.out
+}
+
+/// Returns the source code of a mapping definition.
+pub fn mapping(mapping: &Mapping) -> String {
+    let mut printer = Printer::default();
+    printer.visit_mapping(mapping);
+    printer.out
+}
+
+/// Returns the source code of a function, along with its annotations and finalize block.
+pub fn function(function: &Function) -> String {
+    let mut printer = Printer::default();
+    printer.visit_function(function);
+    printer.out
+}
+
+/// Returns the source code of a statement, with nested blocks indented from the start of its first line.
+pub fn statement(statement: &Statement) -> String {
+    let mut printer = Printer::default();
+    printer.visit_statement(statement);
+    printer.out
+}
+
+/// Returns the source code of an expression.
+pub fn expression(expression: &Expression) -> String {
+    Printer::default().visit_expression(expression, &())
+}
+
+/// Prints the nodes of an AST as source code.
+struct Printer {
+    /// The comments that have not been written yet.
+    comments: Peekable<IntoIter<Comment>>,
+    /// The blank lines of the source that have not been passed yet.
+    blank_lines: Peekable<IntoIter<BytePos>>,
+    /// Whether the source has a blank line before the next line to write.
+    blank_line_pending: bool,
+    /// Whether the items are written in the order of the source, which is only known if the trivia of the source is given.
+    source_order: bool,
+    /// The layout of the source code.
+    config: FormatConfig,
+    /// The current level of nesting.
+    indent: usize,
+    /// The source code written so far.
+    out: String,
+}
+
+impl Default for Printer {
+    fn default() -> Self {
+        Self::new(FormatConfig::default(), None)
+    }
+}
+
+impl Printer {
+    /// Returns a printer that writes source code in the layout of `config`,
+    /// with the comments and blank lines of `trivia` between the nodes of the AST if it is given.
+    fn new(config: FormatConfig, trivia: Option<Trivia>) -> Self {
+        let source_order = trivia.is_some();
+        let trivia = trivia.unwrap_or_default();
+        Self {
+            comments: trivia.comments.into_iter().peekable(),
+            blank_lines: trivia.blank_lines.into_iter().peekable(),
+            blank_line_pending: false,
+            source_order,
+            config,
+            indent: 0,
+            out: String::new(),
+        }
+    }
+
+    /// Writes the indentation of the current level of nesting.
+    fn write_indent(&mut self) {
+        let indent = self.config.indent(self.indent);
+        self.out.push_str(&indent);
+    }
+
+    /// Writes `text` on a line of its own.
+    fn line(&mut self, text: &str) {
+        self.write_indent();
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    /// Writes a blank line, unless it would be the first line of the file or of a block, or follow another blank line.
+    fn blank_line(&mut self) {
+        self.blank_line_pending = false;
+        if !self.out.is_empty() && !self.out.ends_with("{\n") && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    /// Writes a blank line if the source has one before the next line to write.
+    fn blank_line_if_pending(&mut self) {
+        if self.blank_line_pending {
+            self.blank_line();
+        }
+    }
+
+    /// Writes a comment at the end of the last line if it trails code in the source, and on a line of its own otherwise.
+    fn comment(&mut self, comment: Comment) {
+        if comment.trailing && self.out.ends_with('\n') && !self.out.ends_with("\n\n") {
+            self.out.pop();
+            self.out.push(' ');
+            self.out.push_str(&comment.text);
+            self.out.push('\n');
+        } else {
+            self.blank_line_if_pending();
+            self.line(&comment.text);
+        }
+    }
+
+    /// Writes the comments before position `until`, and notes whether the source has a blank line before it.
+    fn flush(&mut self, until: BytePos) {
+        loop {
+            let next_comment = self
+                .comments
+                .peek()
+                .map(|comment| comment.span.lo)
+                .filter(|lo| *lo < until);
+            if self
+                .blank_lines
+                .next_if(|pos| *pos < until && next_comment.map_or(true, |lo| *pos < lo))
+                .is_some()
+            {
+                self.blank_line_pending = true;
+            } else if next_comment.is_some() {
+                let comment = self.comments.next().unwrap();
+                self.comment(comment);
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Writes the comments before position `until` that trail the last line, e.g. before separating it from the next item.
+    fn flush_trailing(&mut self, until: BytePos) {
+        while let Some(comment) = self
+            .comments
+            .next_if(|comment| comment.trailing && comment.span.lo < until)
+        {
+            self.comment(comment);
+        }
+    }
+
+    /// Forgets the blank lines before position `until`, e.g. those within a multi-line statement.
+    fn skip_blank_lines(&mut self, until: BytePos) {
+        while self.blank_lines.next_if(|pos| *pos < until).is_some() {}
+    }
+
+    /// Returns whether there are comments before position `until` that have not been written yet.
+    fn has_comments_before(&mut self, until: BytePos) -> bool {
+        self.comments.peek().map_or(false, |comment| comment.span.lo < until)
+    }
+
+    /// Returns the source code of a comma separated list of expressions.
+    fn expressions(&mut self, expressions: &[Expression]) -> String {
+        expressions
+            .iter()
+            .map(|expression| self.visit_expression(expression, &()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Returns the source code of an operand of a binary operator with the given `precedence`,
+    /// parenthesized if it binds more loosely.
+    /// An operand with the same precedence is only left unparenthesized if the operator `associates` on its side.
+    fn operand(&mut self, operand: &Expression, precedence: u8, associates: bool) -> String {
+        let parenthesize = match uncast(operand) {
+            Expression::Binary(binary) if is_infix(binary.op) => {
+                binary.op.precedence() < precedence || (binary.op.precedence() == precedence && !associates)
+            }
+            Expression::Ternary(_) => true,
+            _ => false,
+        };
+        self.parenthesized(operand, parenthesize)
+    }
+
+    /// Returns the source code of the receiver of a method call, or of a member or tuple access.
+    /// Prefix and infix operations, ternaries, and negative literals are parenthesized.
+    fn receiver(&mut self, receiver: &Expression) -> String {
+        let parenthesize = match uncast(receiver) {
+            Expression::Binary(binary) => is_infix(binary.op),
+            Expression::Unary(unary) => is_prefix(unary.op),
+            Expression::Ternary(_) => true,
+            Expression::Literal(Literal::Integer(_, value, ..)) => value.starts_with('-'),
+            _ => false,
+        };
+        self.parenthesized(receiver, parenthesize)
+    }
+
+    /// Returns the source code of `expression`, in parentheses if `parenthesize` is set.
+    /// The parentheses are decided from the expression that a cast converts, since only that expression is printed.
+    fn parenthesized(&mut self, expression: &Expression, parenthesize: bool) -> String {
+        let source = self.visit_expression(expression, &());
+        match parenthesize {
+            true => format!("({})", source),
+            false => source,
+        }
+    }
+
+    /// Returns the source code of a pattern of a match arm.
+    fn pattern(&mut self, pattern: &Pattern) -> String {
+        match pattern {
+            Pattern::Literal(literal) => self.visit_literal(literal, &()),
+            Pattern::Tuple(tuple) => {
+                let elements = tuple
+                    .elements
+                    .iter()
+                    .map(|element| self.pattern(element))
+                    .collect::<Vec<_>>();
+                match elements.len() {
+                    // A tuple pattern of one element needs a trailing comma, like a tuple expression.
+                    1 => format!("({},)", elements[0]),
+                    _ => format!("({})", elements.join(", ")),
+                }
+            }
+            Pattern::Struct(struct_) if struct_.members.is_empty() => format!("{} {{}}", struct_.name),
+            Pattern::Struct(struct_) => {
+                let members = struct_
+                    .members
+                    .iter()
+                    .map(|member| match &member.pattern {
+                        Some(pattern) => format!("{}: {}", member.identifier, self.pattern(pattern)),
+                        None => member.identifier.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                format!("{} {{ {} }}", struct_.name, members.join(", "))
+            }
+            pattern => pattern.to_string(),
+        }
+    }
+}
+
+/// Returns the compound assignment operator, e.g. `+=`, that the parser simplified into a binary operation.
+fn compound_operator(op: BinaryOperation) -> Option<&'static str> {
+    Some(match op {
+        BinaryOperation::Add => "+=",
+        BinaryOperation::And => "&&=",
+        BinaryOperation::BitwiseAnd => "&=",
+        BinaryOperation::BitwiseOr => "|=",
+        BinaryOperation::Div => "/=",
+        BinaryOperation::Mul => "*=",
+        BinaryOperation::Or => "||=",
+        BinaryOperation::Pow => "**=",
+        BinaryOperation::Rem => "%=",
+        BinaryOperation::Shl => "<<=",
+        BinaryOperation::Shr => ">>=",
+        BinaryOperation::Sub => "-=",
+        BinaryOperation::Xor => "^=",
+        _ => return None,
+    })
+}
+
+/// An item of a program scope.
+enum Item<'a> {
+    Struct(&'a Struct),
+    Mapping(&'a Mapping),
+    Function(&'a Function),
+}
+
+impl Item<'_> {
+    /// The position where the item starts, including the annotations of a function.
+    fn lo(&self) -> BytePos {
+        match self {
+            Item::Struct(struct_) => struct_.span.lo,
+            Item::Mapping(mapping) => mapping.span.lo,
+            Item::Function(function) => function
+                .annotations
+                .first()
+                .map_or(function.span.lo, |annotation| annotation.span.lo),
+        }
+    }
+
+    /// The position where the item ends, including the finalize block of a function.
+    fn hi(&self) -> BytePos {
+        match self {
+            Item::Struct(struct_) => struct_.span.hi,
+            Item::Mapping(mapping) => mapping.span.hi,
+            Item::Function(function) => function
+                .finalize
+                .as_ref()
+                .map_or(function.span.hi, |finalize| finalize.span.hi),
+        }
+    }
+}
+
+/// Returns the expression that `expression` converts if it is a cast, and `expression` otherwise.
+fn uncast(expression: &Expression) -> &Expression {
+    match expression {
+        Expression::Cast(cast) => uncast(&cast.expression),
+        expression => expression,
+    }
+}
+
+/// Returns whether a binary operator can be written between its operands, e.g. `a + b`.
+/// The other operators are only written as methods, e.g. `a.add_wrapped(b)`.
+pub fn is_infix(op: BinaryOperation) -> bool {
+    !matches!(
+        op,
+        BinaryOperation::AddWrapped
+            | BinaryOperation::DivWrapped
+            | BinaryOperation::Mod
+            | BinaryOperation::MulWrapped
+            | BinaryOperation::Nand
+            | BinaryOperation::Nor
+            | BinaryOperation::PowWrapped
+            | BinaryOperation::RemWrapped
+            | BinaryOperation::ShlWrapped
+            | BinaryOperation::ShrWrapped
+            | BinaryOperation::SubWrapped
+    )
+}
+
+/// Returns the name of the method of a binary operator that cannot be written between its operands.
+pub fn method_name(op: BinaryOperation) -> &'static str {
+    match op {
+        BinaryOperation::AddWrapped => "add_wrapped",
+        BinaryOperation::DivWrapped => "div_wrapped",
+        BinaryOperation::Mod => "mod",
+        BinaryOperation::MulWrapped => "mul_wrapped",
+        BinaryOperation::Nand => "nand",
+        BinaryOperation::Nor => "nor",
+        BinaryOperation::PowWrapped => "pow_wrapped",
+        BinaryOperation::RemWrapped => "rem_wrapped",
+        BinaryOperation::ShlWrapped => "shl_wrapped",
+        BinaryOperation::ShrWrapped => "shr_wrapped",
+        BinaryOperation::SubWrapped => "sub_wrapped",
+        _ => unreachable!("`{}` is written between its operands", op),
+    }
+}
+
+/// Returns whether a unary operator is written before its operand, e.g. `-a`, rather than as a method, e.g. `a.abs()`.
+pub fn is_prefix(op: UnaryOperation) -> bool {
+    matches!(op, UnaryOperation::Negate | UnaryOperation::Not)
+}
+
+/// Returns the source code of a type.
+pub fn type_(ty: &Type) -> String {
+    match ty {
+        Type::Boolean => "bool".to_string(),
+        Type::Tuple(tuple) => format!("({})", tuple.0.iter().map(type_).collect::<Vec<_>>().join(", ")),
+        _ => ty.to_string(),
+    }
+}
+
+/// Returns the source code of an input or output mode, followed by a space if there is one.
+pub fn mode(mode: Mode) -> &'static str {
+    match mode {
+        Mode::None => "",
+        Mode::Const => "constant ",
+        Mode::Private => "private ",
+        Mode::Public => "public ",
+        Mode::Witness => "witness ",
+    }
+}
+
+/// Returns the source code of a function signature, e.g. `transition mint(a: u64) -> u64`.
+pub fn signature(keyword: &str, name: &Identifier, inputs: &[Input], outputs: &[Output]) -> String {
+    let inputs = inputs.iter().map(input).collect::<Vec<_>>();
+    format!("{} {}({}){}", keyword, name, inputs.join(", "), return_type(outputs))
+}
+
+/// Returns the source code of a function signature like [`signature`], with each input on a line of its own
+/// if the signature and the ` {` after it do not fit on a line that already has `column` characters.
+/// The inputs are indented one level deeper than `level`, the level of the line that the signature starts on.
+pub fn wrapped_signature(
+    config: &FormatConfig,
+    level: usize,
+    column: usize,
+    keyword: &str,
+    name: &Identifier,
+    inputs: &[Input],
+    outputs: &[Output],
+) -> String {
+    let signature = signature(keyword, name, inputs, outputs);
+    if inputs.is_empty() || config.fits(column, &format!("{} {{", signature)) {
+        return signature;
+    }
+
+    let inputs = inputs
+        .iter()
+        .map(|input_| format!("{}{},\n", config.indent(level + 1), input(input_)))
+        .collect::<String>();
+    format!(
+        "{} {}(\n{}{}){}",
+        keyword,
+        name,
+        inputs,
+        config.indent(level),
+        return_type(outputs)
+    )
+}
+
+/// Returns the source code of the outputs of a function signature, e.g. ` -> (u8, u8)`, or nothing if it has none.
+fn return_type(outputs: &[Output]) -> String {
+    let mut outputs = outputs.iter().map(output).collect::<Vec<_>>();
+    match outputs.len() {
+        0 => String::new(),
+        1 => format!(" -> {}", outputs.remove(0)),
+        _ => format!(" -> ({})", outputs.join(", ")),
+    }
+}
+
+/// Returns the keyword of a function, e.g. `transition`.
+pub fn call_type(call_type: &CallType) -> &'static str {
+    match call_type {
+        CallType::Inline => "inline",
+        CallType::Standard => "function",
+        CallType::Transition => "transition",
+        CallType::AsyncFunction => "async function",
+        CallType::AsyncTransition => "async transition",
+    }
+}
+
+/// Returns the source code of a function input.
+fn input(input: &Input) -> String {
+    match input {
+        Input::Internal(input) => format!("{}{}: {}", mode(input.mode), input.identifier, type_(&input.type_)),
+        Input::External(input) => format!("{}: {}", input.identifier, external_record(input)),
+    }
+}
+
+/// Returns the source code of a function output.
+fn output(output: &Output) -> String {
+    match output {
+        Output::Internal(output) => format!("{}{}", mode(output.mode), type_(&output.type_)),
+        Output::External(output) => external_record(output),
+    }
+}
+
+/// Returns the source code of a record of another program, e.g. `token.leo/token.record`.
+fn external_record(external: &External) -> String {
+    format!("{}.leo/{}.record", external.program_name, external.record)
+}
+
+impl<'a> ExpressionVisitor<'a> for Printer {
+    type AdditionalInput = ();
+    type Output = String;
+
+    fn visit_access(&mut self, input: &'a AccessExpression, _additional: &Self::AdditionalInput) -> Self::Output {
+        match input {
+            AccessExpression::AssociatedConstant(constant) => format!("{}::{}", type_(&constant.ty), constant.name),
+            AccessExpression::AssociatedFunction(function) => format!(
+                "{}::{}({})",
+                type_(&function.ty),
+                function.name,
+                self.expressions(&function.args)
+            ),
+            AccessExpression::Member(member) => format!("{}.{}", self.receiver(&member.inner), member.name),
+            AccessExpression::Tuple(tuple) => format!("{}.{}", self.receiver(&tuple.tuple), tuple.index),
+        }
+    }
+
+    fn visit_binary(&mut self, input: &'a BinaryExpression, _additional: &Self::AdditionalInput) -> Self::Output {
+        if !is_infix(input.op) {
+            let receiver = self.receiver(&input.left);
+            let argument = self.visit_expression(&input.right, &());
+            return format!("{}.{}({})", receiver, method_name(input.op), argument);
+        }
+
+        // `**` is right-associative, the comparisons are not associative, and the other operators are left-associative.
+        let (left_associates, right_associates) = match input.op {
+            BinaryOperation::Pow => (false, true),
+            BinaryOperation::Eq
+            | BinaryOperation::Neq
+            | BinaryOperation::Lt
+            | BinaryOperation::Lte
+            | BinaryOperation::Gt
+            | BinaryOperation::Gte => (false, false),
+            _ => (true, false),
+        };
+        let left = self.operand(&input.left, input.op.precedence(), left_associates);
+        let right = self.operand(&input.right, input.op.precedence(), right_associates);
+        format!("{} {} {}", left, input.op, right)
+    }
+
+    fn visit_call(&mut self, input: &'a CallExpression, _additional: &Self::AdditionalInput) -> Self::Output {
+        let function = self.visit_expression(&input.function, &());
+        let arguments = self.expressions(&input.arguments);
+        match &input.external {
+            Some(external) => format!(
+                "{}.leo/{}({})",
+                self.visit_expression(external, &()),
+                function,
+                arguments
+            ),
+            None => format!("{}({})", function, arguments),
+        }
+    }
+
+    fn visit_cast(&mut self, input: &'a CastExpression, _additional: &Self::AdditionalInput) -> Self::Output {
+        self.visit_expression(&input.expression, &())
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, _additional: &Self::AdditionalInput) -> Self::Output {
+        if input.members.is_empty() {
+            return format!("{} {{}}", input.name);
+        }
+        let members = input
+            .members
+            .iter()
+            .map(|member| match &member.expression {
+                Some(expression) => format!("{}: {}", member.identifier, self.visit_expression(expression, &())),
+                None => member.identifier.to_string(),
+            })
+            .collect::<Vec<_>>();
+        format!("{} {{ {} }}", input.name, members.join(", "))
+    }
+
+    fn visit_err(&mut self, input: &'a ErrExpression, _additional: &Self::AdditionalInput) -> Self::Output {
+        input.to_string()
+    }
+
+    fn visit_identifier(&mut self, input: &'a Identifier, _additional: &Self::AdditionalInput) -> Self::Output {
+        input.to_string()
+    }
+
+    fn visit_literal(&mut self, input: &'a Literal, _additional: &Self::AdditionalInput) -> Self::Output {
+        self.config.literal(input)
+    }
+
+    fn visit_ternary(&mut self, input: &'a TernaryExpression, _additional: &Self::AdditionalInput) -> Self::Output {
+        let condition = self.parenthesized(
+            &input.condition,
+            matches!(uncast(&input.condition), Expression::Ternary(_)),
+        );
+        let if_true = self.visit_expression(&input.if_true, &());
+        let if_false = self.visit_expression(&input.if_false, &());
+        format!("{} ? {} : {}", condition, if_true, if_false)
+    }
+
+    fn visit_tuple(&mut self, input: &'a TupleExpression, _additional: &Self::AdditionalInput) -> Self::Output {
+        match input.elements.len() {
+            // A tuple of one element needs a trailing comma to be distinguished from a parenthesized expression.
+            1 => format!("({},)", self.visit_expression(&input.elements[0], &())),
+            _ => format!("({})", self.expressions(&input.elements)),
+        }
+    }
+
+    fn visit_unary(&mut self, input: &'a UnaryExpression, _additional: &Self::AdditionalInput) -> Self::Output {
+        match input.op {
+            UnaryOperation::Negate | UnaryOperation::Not => {
+                // `-1u8` would be parsed as a negative literal, and `--a` is not valid.
+                let parenthesize = match uncast(&input.receiver) {
+                    Expression::Binary(binary) => is_infix(binary.op),
+                    Expression::Ternary(_) => true,
+                    Expression::Literal(Literal::Integer(..)) => input.op == UnaryOperation::Negate,
+                    Expression::Unary(unary) => is_prefix(unary.op),
+                    _ => false,
+                };
+                let operand = self.parenthesized(&input.receiver, parenthesize);
+                let operator = if input.op == UnaryOperation::Negate { "-" } else { "!" };
+                format!("{}{}", operator, operand)
+            }
+            _ => format!("{}.{}()", self.receiver(&input.receiver), input.op.as_str()),
+        }
+    }
+}
+
+impl<'a> StatementVisitor<'a> for Printer {
+    fn visit_assign(&mut self, input: &'a AssignStatement) {
+        let place = self.visit_expression(&input.place, &());
+
+        // The parser simplifies `x += 1u8` into `x = x + 1u8`, where `x` is the same node as the place, with the same span.
+        if let Expression::Binary(binary) = &input.value {
+            if binary.span == input.span && *binary.left == input.place {
+                if let Some(operator) = compound_operator(binary.op) {
+                    let value = self.visit_expression(&binary.right, &());
+                    write!(self.out, "{} {} {};", place, operator, value).unwrap();
+                    return;
+                }
+            }
+        }
+
+        let value = self.visit_expression(&input.value, &());
+        write!(self.out, "{} = {};", place, value).unwrap();
+    }
+
+    fn visit_block(&mut self, input: &'a Block) {
+        if input.statements.is_empty() && !self.has_comments_before(input.span.hi) {
+            self.out.push_str("{}");
+            return;
+        }
+
+        self.out.push_str("{\n");
+        self.indent += 1;
+        for statement in input.statements.iter() {
+            self.flush(statement.span().lo);
+            self.blank_line_if_pending();
+            self.write_indent();
+            self.visit_statement(statement);
+            self.out.push('\n');
+            self.skip_blank_lines(statement.span().hi);
+        }
+        self.flush(input.span.hi);
+        self.blank_line_pending = false;
+        self.indent -= 1;
+        self.write_indent();
+        self.out.push('}');
+    }
+
+    fn visit_conditional(&mut self, input: &'a ConditionalStatement) {
+        let condition = self.visit_expression(&input.condition, &());
+        write!(self.out, "if {} ", condition).unwrap();
+        self.visit_block(&input.then);
+        if let Some(otherwise) = &input.otherwise {
+            self.out.push_str(" else ");
+            self.visit_statement(otherwise);
+        }
+    }
+
+    fn visit_console(&mut self, input: &'a ConsoleStatement) {
+        let (name, mut arguments) = match &input.function {
+            ConsoleFunction::Assert(expression, _) => ("assert", vec![self.visit_expression(expression, &())]),
+            ConsoleFunction::AssertEq(left, right, _) => (
+                "assert_eq",
+                vec![self.visit_expression(left, &()), self.visit_expression(right, &())],
+            ),
+            ConsoleFunction::AssertNeq(left, right, _) => (
+                "assert_neq",
+                vec![self.visit_expression(left, &()), self.visit_expression(right, &())],
+            ),
+        };
+        if let Some(message) = input.function.message() {
+            arguments.push(format!("\"{}\"", message.string));
+            arguments.extend(
+                message
+                    .parameters
+                    .iter()
+                    .map(|parameter| self.visit_expression(parameter, &())),
+            );
+        }
+        write!(self.out, "console.{}({});", name, arguments.join(", ")).unwrap();
+    }
+
+    fn visit_decrement(&mut self, input: &'a DecrementStatement) {
+        let index = self.visit_expression(&input.index, &());
+        let amount = self.visit_expression(&input.amount, &());
+        write!(self.out, "decrement({}, {}, {});", input.mapping, index, amount).unwrap();
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        let value = self.visit_expression(&input.value, &());
+        write!(
+            self.out,
+            "{} {}: {} = {};",
+            input.declaration_type,
+            input.variable_name,
+            type_(&input.type_),
+            value
+        )
+        .unwrap();
+    }
+
+    fn visit_expression_statement(&mut self, input: &'a ExpressionStatement) {
+        let expression = self.visit_expression(&input.expression, &());
+        write!(self.out, "{};", expression).unwrap();
+    }
+
+    fn visit_finalize(&mut self, input: &'a FinalizeStatement) {
+        let arguments = self.expressions(&input.arguments);
+        write!(self.out, "async finalize({});", arguments).unwrap();
+    }
+
+    fn visit_increment(&mut self, input: &'a IncrementStatement) {
+        let index = self.visit_expression(&input.index, &());
+        let amount = self.visit_expression(&input.amount, &());
+        write!(self.out, "increment({}, {}, {});", input.mapping, index, amount).unwrap();
+    }
+
+    fn visit_iteration(&mut self, input: &'a IterationStatement) {
+        let start = self.visit_expression(&input.start, &());
+        let stop = self.visit_expression(&input.stop, &());
+        let range = if input.inclusive { "..=" } else { ".." };
+        write!(
+            self.out,
+            "for {}: {} in {}{}{} ",
+            input.variable,
+            type_(&input.type_),
+            start,
+            range,
+            stop
+        )
+        .unwrap();
+        self.visit_block(&input.block);
+    }
+
+    fn visit_match(&mut self, input: &'a MatchStatement) {
+        let expression = self.visit_expression(&input.expression, &());
+        writeln!(self.out, "match {} {{", expression).unwrap();
+        self.indent += 1;
+        for arm in input.arms.iter() {
+            self.flush(arm.span.lo);
+            self.blank_line_if_pending();
+            self.write_indent();
+            let pattern = self.pattern(&arm.pattern);
+            write!(self.out, "{} => ", pattern).unwrap();
+            self.visit_block(&arm.block);
+            self.out.push('\n');
+            self.skip_blank_lines(arm.span.hi);
+        }
+        self.flush(input.span.hi);
+        self.blank_line_pending = false;
+        self.indent -= 1;
+        self.write_indent();
+        self.out.push('}');
+    }
+
+    fn visit_return(&mut self, input: &'a ReturnStatement) {
+        let expression = self.visit_expression(&input.expression, &());
+        write!(self.out, "return {};", expression).unwrap();
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for Printer {
+    fn visit_program(&mut self, input: &'a Program) {
+        for name in input.imports.keys() {
+            self.flush(name.span.lo);
+            self.blank_line_if_pending();
+            self.line(&format!("import {}.leo;", name));
+        }
+
+        for scope in input.program_scopes.values() {
+            if !self.out.is_empty() {
+                self.flush_trailing(scope.span.lo);
+                self.blank_line();
+            }
+            self.flush(scope.span.lo);
+            self.blank_line_if_pending();
+            self.visit_program_scope(scope);
+        }
+    }
+
+    fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        self.line(&format!("program {} {{", input.program_id));
+        self.indent += 1;
+
+        // The passes may have reordered or added items, so without the trivia of the source they are written by kind.
+        let mut items = input
+            .structs
+            .values()
+            .map(Item::Struct)
+            .chain(input.mappings.values().map(Item::Mapping))
+            .chain(input.functions.values().map(Item::Function))
+            .collect::<Vec<_>>();
+        if self.source_order {
+            items.sort_by_key(|item| item.lo());
+        }
+
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.flush_trailing(item.lo());
+                self.blank_line();
+            }
+            self.flush(item.lo());
+            self.blank_line_pending = false;
+            match item {
+                Item::Struct(struct_) => self.visit_struct(struct_),
+                Item::Mapping(mapping) => self.visit_mapping(mapping),
+                Item::Function(function) => self.visit_function(function),
+            }
+            self.skip_blank_lines(item.hi());
+        }
+
+        self.flush(input.span.hi);
+        self.blank_line_pending = false;
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    fn visit_struct(&mut self, input: &'a Struct) {
+        let keyword = if input.is_record { "record" } else { "struct" };
+        if input.members.is_empty() && !self.has_comments_before(input.span.hi) {
+            self.line(&format!("{} {} {{}}", keyword, input.identifier));
+            return;
+        }
+
+        self.line(&format!("{} {} {{", keyword, input.identifier));
+        self.indent += 1;
+        for member in input.members.iter() {
+            self.flush(member.identifier.span.lo);
+            self.blank_line_if_pending();
+            self.line(&format!("{}: {},", member.identifier, type_(&member.type_)));
+        }
+        self.flush(input.span.hi);
+        self.blank_line_pending = false;
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    fn visit_mapping(&mut self, input: &'a Mapping) {
+        self.line(&format!(
+            "mapping {}: {} => {};",
+            input.identifier,
+            type_(&input.key_type),
+            type_(&input.value_type)
+        ));
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        for annotation in input.annotations.iter() {
+            self.line(&annotation.to_string());
+        }
+
+        let column = self.config.indent(self.indent).len();
+        let signature = wrapped_signature(
+            &self.config,
+            self.indent,
+            column,
+            call_type(&input.call_type),
+            &input.identifier,
+            &input.input,
+            &input.output,
+        );
+        self.write_indent();
+        self.out.push_str(&signature);
+        self.out.push(' ');
+        self.visit_block(&input.block);
+
+        if let Some(finalize) = &input.finalize {
+            // The finalize block starts on the line that closes the function, after `} `.
+            let signature = wrapped_signature(
+                &self.config,
+                self.indent,
+                column + 2,
+                "finalize",
+                &finalize.identifier,
+                &finalize.input,
+                &finalize.output,
+            );
+            self.out.push(' ');
+            self.out.push_str(&signature);
+            self.out.push(' ');
+            self.visit_block(&finalize.block);
+        }
+        self.out.push('\n');
+    }
+}
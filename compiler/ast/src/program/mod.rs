@@ -16,25 +16,43 @@
 
 //! A Leo program consists of import statements and program scopes.
 
+pub mod import_source;
+pub use import_source::*;
+
 pub mod program_id;
 pub use program_id::*;
 
 pub mod program_scope;
 pub use program_scope::*;
 
-use crate::Identifier;
+use crate::{
+    AccessExpression, Block, CallExpression, DecrementStatement, DefinitionStatement, Expression, ExpressionVisitor,
+    External, Function, Identifier, IncrementStatement, Input, IterationStatement, Mapping, MatchStatement, NodeID,
+    Output, Pattern, ProgramVisitor, Statement, StatementVisitor, Struct, StructExpression, Type,
+};
 
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Stores the Leo program abstract syntax tree.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Eq, Serialize, Deserialize)]
 pub struct Program {
     /// A map from import names to import definitions.
     pub imports: IndexMap<Identifier, Program>,
     /// A map from program names to program scopes.
     pub program_scopes: IndexMap<ProgramId, ProgramScope>,
+    /// Where the program was found, if it is imported.
+    /// It is not serialized, so that the ASTs of the same program found in different places are the same.
+    #[serde(skip)]
+    pub import_source: Option<ImportSource>,
+}
+
+impl PartialEq for Program {
+    /// Programs are compared without where they were found, like they are serialized.
+    fn eq(&self, other: &Self) -> bool {
+        self.imports == other.imports && self.program_scopes == other.program_scopes
+    }
 }
 
 impl fmt::Display for Program {
@@ -56,6 +74,258 @@ impl Default for Program {
         Self {
             imports: IndexMap::new(),
             program_scopes: IndexMap::new(),
+            import_source: None,
+        }
+    }
+}
+
+impl Program {
+    /// Returns the ids of the nodes of the program and its imports, in the order they are visited.
+    pub fn node_ids(&self) -> Vec<NodeID> {
+        let mut visitor = NodeIds(Vec::new());
+        visitor.visit_program(self);
+        visitor.0
+    }
+
+    /// Returns the largest id of the nodes of the program and its imports, or `0` if it has no nodes.
+    /// Passes that add nodes allocate their ids after it, see [`crate::NodeBuilder::for_program`].
+    pub fn max_node_id(&self) -> NodeID {
+        self.node_ids().into_iter().max().unwrap_or(0)
+    }
+}
+
+/// Collects the ids of the nodes it visits, including the identifiers and literals that are not expressions.
+struct NodeIds(Vec<NodeID>);
+
+impl NodeIds {
+    fn record(&mut self, id: NodeID) {
+        self.0.push(id);
+    }
+
+    fn record_type(&mut self, type_: &Type) {
+        match type_ {
+            Type::Identifier(identifier) => self.record(identifier.id),
+            Type::Mapping(mapping) => {
+                self.record_type(&mapping.key);
+                self.record_type(&mapping.value);
+            }
+            Type::Tuple(tuple) => tuple.iter().for_each(|type_| self.record_type(type_)),
+            _ => {}
+        }
+    }
+
+    fn record_pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Wildcard(_) => {}
+            Pattern::Binding(identifier) => self.record(identifier.id),
+            Pattern::Literal(literal) => self.record(literal.id()),
+            Pattern::Tuple(tuple) => tuple.elements.iter().for_each(|pattern| self.record_pattern(pattern)),
+            Pattern::Struct(struct_) => {
+                self.record(struct_.name.id);
+                struct_.members.iter().for_each(|member| {
+                    self.record(member.identifier.id);
+                    if let Some(pattern) = &member.pattern {
+                        self.record_pattern(pattern);
+                    }
+                });
+            }
+        }
+    }
+
+    fn record_inputs(&mut self, inputs: &[Input]) {
+        inputs.iter().for_each(|input| match input {
+            Input::Internal(input) => {
+                self.record(input.identifier.id);
+                self.record_type(&input.type_);
+            }
+            Input::External(input) => self.record_external(input),
+        });
+    }
+
+    fn record_outputs(&mut self, outputs: &[Output], output_type: &Type) {
+        outputs.iter().for_each(|output| match output {
+            Output::Internal(output) => self.record_type(&output.type_),
+            Output::External(output) => self.record_external(output),
+        });
+        self.record_type(output_type);
+    }
+
+    fn record_external(&mut self, external: &External) {
+        self.record(external.identifier.id);
+        self.record(external.program_name.id);
+        self.record(external.record.id);
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for NodeIds {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_expression(&mut self, input: &'a Expression, additional: &Self::AdditionalInput) -> Self::Output {
+        self.record(input.id());
+        match input {
+            Expression::Access(access) => self.visit_access(access, additional),
+            Expression::Binary(binary) => self.visit_binary(binary, additional),
+            Expression::Call(call) => self.visit_call(call, additional),
+            Expression::Cast(cast) => self.visit_cast(cast, additional),
+            Expression::Struct(struct_) => self.visit_struct_init(struct_, additional),
+            Expression::Ternary(ternary) => self.visit_ternary(ternary, additional),
+            Expression::Tuple(tuple) => self.visit_tuple(tuple, additional),
+            Expression::Unary(unary) => self.visit_unary(unary, additional),
+            Expression::Err(_) | Expression::Identifier(_) | Expression::Literal(_) => {}
+        }
+    }
+
+    fn visit_access(&mut self, input: &'a AccessExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        match input {
+            AccessExpression::AssociatedConstant(constant) => {
+                self.record_type(&constant.ty);
+                self.record(constant.name.id);
+            }
+            AccessExpression::AssociatedFunction(function) => {
+                self.record_type(&function.ty);
+                self.record(function.name.id);
+                function
+                    .args
+                    .iter()
+                    .for_each(|arg| self.visit_expression(arg, additional));
+            }
+            AccessExpression::Member(member) => {
+                self.record(member.name.id);
+                self.visit_expression(&member.inner, additional);
+            }
+            AccessExpression::Tuple(tuple) => self.visit_expression(&tuple.tuple, additional),
+        }
+    }
+
+    fn visit_call(&mut self, input: &'a CallExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        self.visit_expression(&input.function, additional);
+        input
+            .arguments
+            .iter()
+            .for_each(|argument| self.visit_expression(argument, additional));
+        if let Some(external) = &input.external {
+            self.visit_expression(external, additional);
+        }
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        self.record(input.name.id);
+        input.members.iter().for_each(|member| {
+            self.record(member.identifier.id);
+            if let Some(expression) = &member.expression {
+                self.visit_expression(expression, additional);
+            }
+        });
+    }
+}
+
+impl<'a> StatementVisitor<'a> for NodeIds {
+    fn visit_statement(&mut self, input: &'a Statement) {
+        // The id of a block is recorded when it is visited.
+        if !matches!(input, Statement::Block(_)) {
+            self.record(input.id());
+        }
+        match input {
+            Statement::Assign(stmt) => {
+                self.visit_expression(&stmt.place, &());
+                self.visit_assign(stmt);
+            }
+            Statement::Block(stmt) => self.visit_block(stmt),
+            Statement::Conditional(stmt) => self.visit_conditional(stmt),
+            Statement::Console(stmt) => self.visit_console(stmt),
+            Statement::Decrement(stmt) => self.visit_decrement(stmt),
+            Statement::Definition(stmt) => self.visit_definition(stmt),
+            Statement::Expression(stmt) => self.visit_expression_statement(stmt),
+            Statement::Finalize(stmt) => self.visit_finalize(stmt),
+            Statement::Increment(stmt) => self.visit_increment(stmt),
+            Statement::Iteration(stmt) => self.visit_iteration(stmt),
+            Statement::Match(stmt) => self.visit_match(stmt),
+            Statement::Return(stmt) => self.visit_return(stmt),
+        }
+    }
+
+    fn visit_block(&mut self, input: &'a Block) {
+        self.record(input.id);
+        input.statements.iter().for_each(|stmt| self.visit_statement(stmt));
+    }
+
+    fn visit_decrement(&mut self, input: &'a DecrementStatement) {
+        self.record(input.mapping.id);
+        self.visit_expression(&input.index, &());
+        self.visit_expression(&input.amount, &());
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        self.record(input.variable_name.id);
+        self.record_type(&input.type_);
+        self.visit_expression(&input.value, &());
+    }
+
+    fn visit_increment(&mut self, input: &'a IncrementStatement) {
+        self.record(input.mapping.id);
+        self.visit_expression(&input.index, &());
+        self.visit_expression(&input.amount, &());
+    }
+
+    fn visit_iteration(&mut self, input: &'a IterationStatement) {
+        self.record(input.variable.id);
+        self.record_type(&input.type_);
+        self.visit_expression(&input.start, &());
+        self.visit_expression(&input.stop, &());
+        self.visit_block(&input.block);
+    }
+
+    fn visit_match(&mut self, input: &'a MatchStatement) {
+        self.visit_expression(&input.expression, &());
+        input.arms.iter().for_each(|arm| {
+            self.record_pattern(&arm.pattern);
+            self.visit_block(&arm.block);
+        });
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for NodeIds {
+    fn visit_program(&mut self, input: &'a Program) {
+        input.imports.iter().for_each(|(name, import)| {
+            self.record(name.id);
+            self.visit_import(import);
+        });
+        input.program_scopes.iter().for_each(|(program_id, scope)| {
+            self.record(program_id.name.id);
+            self.record(program_id.network.id);
+            self.visit_program_scope(scope);
+        });
+    }
+
+    fn visit_struct(&mut self, input: &'a Struct) {
+        self.record(input.identifier.id);
+        input.members.iter().for_each(|member| {
+            self.record(member.identifier.id);
+            self.record_type(&member.type_);
+        });
+    }
+
+    fn visit_mapping(&mut self, input: &'a Mapping) {
+        self.record(input.identifier.id);
+        self.record_type(&input.key_type);
+        self.record_type(&input.value_type);
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        input.annotations.iter().for_each(|annotation| {
+            self.record(annotation.identifier.id);
+            annotation.arguments.iter().for_each(|(key, _)| self.record(key.id));
+        });
+        self.record(input.identifier.id);
+        self.record_inputs(&input.input);
+        self.record_outputs(&input.output, &input.output_type);
+        self.visit_block(&input.block);
+        if let Some(finalize) = &input.finalize {
+            self.record(finalize.identifier.id);
+            self.record_inputs(&finalize.input);
+            self.record_outputs(&finalize.output, &finalize.output_type);
+            self.visit_block(&finalize.block);
         }
     }
 }
@@ -45,7 +45,7 @@ impl Serialize for ProgramId {
 
         // Load the struct elements into a BTreeMap (to preserve serialized ordering of keys).
         let mut key: BTreeMap<String, String> = BTreeMap::new();
-        key.insert("name".to_string(), self.name.to_string());
+        key.insert("name".to_string(), to_json_string(&self.name)?);
         key.insert("network".to_string(), to_json_string(&self.network)?);
 
         // Convert the serialized object into a string for use as a key.
@@ -14,28 +14,89 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::Expression;
+use crate::{Expression, Node};
+use leo_span::Span;
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// A user-defined message attached to an assertion, e.g. `"expected {} to be {}", a, b`.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct AssertMessage {
+    /// The format string, containing one `{}` placeholder per parameter.
+    pub string: String,
+    /// The expressions substituted into the placeholders of the format string.
+    pub parameters: Vec<Expression>,
+    /// The span from the format string to the last parameter.
+    pub span: Span,
+}
+
+impl AssertMessage {
+    /// Returns the number of `{}` placeholders in the format string.
+    pub fn placeholder_count(&self) -> usize {
+        self.string.matches("{}").count()
+    }
+
+    /// Renders the message, substituting each placeholder with the corresponding parameter.
+    pub fn render(&self) -> String {
+        let mut parameters = self.parameters.iter();
+        let mut pieces = self.string.split("{}");
+        let mut rendered = pieces.next().unwrap_or_default().to_string();
+        for piece in pieces {
+            match parameters.next() {
+                Some(parameter) => rendered.push_str(&parameter.to_string()),
+                None => rendered.push_str("{}"),
+            }
+            rendered.push_str(piece);
+        }
+        rendered
+    }
+}
+
+impl fmt::Display for AssertMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"{}\"", self.string)?;
+        for parameter in self.parameters.iter() {
+            write!(f, ", {}", parameter)?;
+        }
+        Ok(())
+    }
+}
+
+crate::simple_node_impl!(AssertMessage);
+
 /// A console logging function to invoke.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum ConsoleFunction {
-    /// A `console.assert(expr)` call to invoke, asserting that the expression evaluates to true.
-    Assert(Expression),
-    /// A `console.assert_eq(expr1, expr2)` call to invoke, asserting that the operands are equal.
-    AssertEq(Expression, Expression),
-    /// A `console.assert_neq(expr1, expr2)` call to invoke, asserting that the operands are not equal.
-    AssertNeq(Expression, Expression),
+    /// A `console.assert(expr[, message])` call to invoke, asserting that the expression evaluates to true.
+    Assert(Expression, Option<AssertMessage>),
+    /// A `console.assert_eq(expr1, expr2[, message])` call to invoke, asserting that the operands are equal.
+    AssertEq(Expression, Expression, Option<AssertMessage>),
+    /// A `console.assert_neq(expr1, expr2[, message])` call to invoke, asserting that the operands are not equal.
+    AssertNeq(Expression, Expression, Option<AssertMessage>),
+}
+
+impl ConsoleFunction {
+    /// Returns the user-defined message of the assertion, if one was provided.
+    pub fn message(&self) -> Option<&AssertMessage> {
+        match self {
+            ConsoleFunction::Assert(_, message)
+            | ConsoleFunction::AssertEq(_, _, message)
+            | ConsoleFunction::AssertNeq(_, _, message) => message.as_ref(),
+        }
+    }
 }
 
 impl fmt::Display for ConsoleFunction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            ConsoleFunction::Assert(expr) => write!(f, "assert({})", expr),
-            ConsoleFunction::AssertEq(expr1, expr2) => write!(f, "assert_eq({}, {})", expr1, expr2),
-            ConsoleFunction::AssertNeq(expr1, expr2) => write!(f, "assert_neq({}, {})", expr1, expr2),
+            ConsoleFunction::Assert(expr, _) => write!(f, "assert({}", expr)?,
+            ConsoleFunction::AssertEq(expr1, expr2, _) => write!(f, "assert_eq({}, {}", expr1, expr2)?,
+            ConsoleFunction::AssertNeq(expr1, expr2, _) => write!(f, "assert_neq({}, {}", expr1, expr2)?,
+        }
+        match self.message() {
+            Some(message) => write!(f, ", {})", message),
+            None => write!(f, ")"),
         }
     }
 }
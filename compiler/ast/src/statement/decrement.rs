@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Expression, Identifier, Node};
+use crate::{Expression, Identifier, Node, NodeID};
 
 use leo_span::Span;
 
@@ -32,6 +32,8 @@ pub struct DecrementStatement {
     pub amount: Expression,
     /// The span of `decrement(foo, bar, 1)` excluding the semicolon.
     pub span: Span,
+    /// The id of the node.
+    pub id: NodeID,
 }
 
 impl fmt::Display for DecrementStatement {
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Expression, Node};
+use crate::{Expression, Node, NodeID};
 
 use leo_span::Span;
 
@@ -28,6 +28,8 @@ pub struct FinalizeStatement {
     pub arguments: Vec<Expression>,
     /// The span of `finalize(arg1, ..., argN)` excluding the semicolon.
     pub span: Span,
+    /// The id of the node.
+    pub id: NodeID,
 }
 
 impl fmt::Display for FinalizeStatement {
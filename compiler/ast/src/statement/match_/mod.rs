@@ -0,0 +1,66 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Block, Expression, Node, NodeID};
+use leo_span::Span;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+mod pattern;
+pub use pattern::*;
+
+/// A `match expression { (pattern => block)* }` statement.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct MatchStatement {
+    /// The value that is matched against the patterns of the arms.
+    pub expression: Expression,
+    /// The arms, of which the first whose pattern matches the value is evaluated.
+    pub arms: Vec<MatchArm>,
+    /// The span from `match` to the closing `}`.
+    pub span: Span,
+    /// The id of the node.
+    pub id: NodeID,
+}
+
+impl fmt::Display for MatchStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "match {} {{", self.expression)?;
+        self.arms.iter().try_for_each(|arm| writeln!(f, "\t{}", arm))?;
+        write!(f, "}}")
+    }
+}
+
+crate::simple_node_impl!(MatchStatement);
+
+/// An arm `pattern => block` of a match statement.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct MatchArm {
+    /// The pattern that the matched value is compared against, and that binds the parts of it.
+    pub pattern: Pattern,
+    /// The block to evaluate if the pattern matches.
+    pub block: Block,
+    /// The span from the pattern to the block.
+    pub span: Span,
+}
+
+impl fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} => {}", self.pattern, self.block)
+    }
+}
+
+crate::simple_node_impl!(MatchArm);
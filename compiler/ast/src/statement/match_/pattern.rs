@@ -0,0 +1,175 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Identifier, Literal, Node};
+use leo_span::Span;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A pattern in an arm of a match statement.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum Pattern {
+    /// A wildcard `_`, which matches any value.
+    Wildcard(Span),
+    /// A binding, e.g. `y`, which matches any value and names it.
+    Binding(Identifier),
+    /// A literal, e.g. `0u32`, which matches an equal value.
+    Literal(Literal),
+    /// A tuple pattern, e.g. `(0u8, b)`, which matches each element of a tuple.
+    Tuple(TuplePattern),
+    /// A struct pattern, e.g. `Point { x: 0u32, y }`, which matches members of a struct.
+    Struct(StructPattern),
+}
+
+impl Pattern {
+    /// Returns `true` if the pattern matches any value of its type.
+    pub fn is_irrefutable(&self) -> bool {
+        match self {
+            Pattern::Wildcard(_) | Pattern::Binding(_) => true,
+            Pattern::Literal(_) => false,
+            Pattern::Tuple(tuple) => tuple.elements.iter().all(Pattern::is_irrefutable),
+            Pattern::Struct(struct_) => struct_
+                .members
+                .iter()
+                .all(|member| member.pattern.as_ref().map_or(true, Pattern::is_irrefutable)),
+        }
+    }
+
+    /// Returns the variables bound by the pattern, in order.
+    pub fn bindings(&self) -> Vec<Identifier> {
+        match self {
+            Pattern::Wildcard(_) | Pattern::Literal(_) => Vec::new(),
+            Pattern::Binding(identifier) => vec![*identifier],
+            Pattern::Tuple(tuple) => tuple.elements.iter().flat_map(Pattern::bindings).collect(),
+            Pattern::Struct(struct_) => struct_
+                .members
+                .iter()
+                .flat_map(|member| match &member.pattern {
+                    Some(pattern) => pattern.bindings(),
+                    None => vec![member.identifier],
+                })
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pattern::Wildcard(_) => write!(f, "_"),
+            Pattern::Binding(identifier) => identifier.fmt(f),
+            Pattern::Literal(literal) => literal.fmt(f),
+            Pattern::Tuple(tuple) => tuple.fmt(f),
+            Pattern::Struct(struct_) => struct_.fmt(f),
+        }
+    }
+}
+
+impl Node for Pattern {
+    fn span(&self) -> Span {
+        match self {
+            Pattern::Wildcard(span) => *span,
+            Pattern::Binding(identifier) => identifier.span,
+            Pattern::Literal(literal) => literal.span(),
+            Pattern::Tuple(tuple) => tuple.span,
+            Pattern::Struct(struct_) => struct_.span,
+        }
+    }
+
+    fn set_span(&mut self, span: Span) {
+        match self {
+            Pattern::Wildcard(wildcard) => *wildcard = span,
+            Pattern::Binding(identifier) => identifier.span = span,
+            Pattern::Literal(literal) => literal.set_span(span),
+            Pattern::Tuple(tuple) => tuple.span = span,
+            Pattern::Struct(struct_) => struct_.span = span,
+        }
+    }
+}
+
+/// A tuple pattern `(pattern, pattern, ...)`.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct TuplePattern {
+    /// The patterns of the elements of the tuple.
+    pub elements: Vec<Pattern>,
+    /// The span from `(` to `)`.
+    pub span: Span,
+}
+
+impl fmt::Display for TuplePattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "({})",
+            self.elements
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+}
+
+crate::simple_node_impl!(TuplePattern);
+
+/// A struct pattern `Name { member: pattern, member, ... }`.
+/// Members that are not listed can have any value.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct StructPattern {
+    /// The name of the struct.
+    pub name: Identifier,
+    /// The patterns of the listed members.
+    pub members: Vec<StructMemberPattern>,
+    /// The span from the name to `}`.
+    pub span: Span,
+}
+
+impl fmt::Display for StructPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {{ {} }}",
+            self.name,
+            self.members
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+crate::simple_node_impl!(StructPattern);
+
+/// The pattern `member: pattern` of a member of a struct.
+/// A member without a pattern, e.g. `y`, binds a variable named after it.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct StructMemberPattern {
+    /// The name of the member.
+    pub identifier: Identifier,
+    /// The pattern of the member, if any.
+    pub pattern: Option<Pattern>,
+}
+
+impl fmt::Display for StructMemberPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.pattern {
+            Some(pattern) => write!(f, "{}: {}", self.identifier, pattern),
+            None => write!(f, "{}", self.identifier),
+        }
+    }
+}
@@ -32,6 +32,9 @@ pub use decrement::*;
 pub mod definition;
 pub use definition::*;
 
+pub mod expression;
+pub use expression::*;
+
 pub mod finalize;
 pub use finalize::*;
 
@@ -41,10 +44,13 @@ pub use increment::*;
 pub mod iteration;
 pub use iteration::*;
 
+pub mod match_;
+pub use match_::*;
+
 pub mod return_;
 pub use return_::*;
 
-use crate::Node;
+use crate::{Node, NodeID};
 
 use leo_span::Span;
 
@@ -66,24 +72,47 @@ pub enum Statement {
     Decrement(DecrementStatement),
     /// A binding or set of bindings / variables to declare.
     Definition(DefinitionStatement),
+    /// An expression statement, e.g. `Mapping::set(balances, addr, 1u64);`.
+    Expression(ExpressionStatement),
     /// A finalize statement.
     Finalize(FinalizeStatement),
     /// An increment statement.
     Increment(IncrementStatement),
     /// A `for` statement.
     Iteration(Box<IterationStatement>),
+    /// A `match` statement.
+    Match(MatchStatement),
     /// A return statement `return expr;`.
     Return(ReturnStatement),
 }
 
 impl Statement {
     /// Returns a dummy statement made from an empty block `{}`.
-    pub fn dummy(span: Span) -> Self {
+    pub fn dummy(span: Span, id: NodeID) -> Self {
         Self::Block(Block {
             statements: Vec::new(),
             span,
+            id,
         })
     }
+
+    /// Returns the id of the statement.
+    pub fn id(&self) -> NodeID {
+        match self {
+            Statement::Assign(n) => n.id,
+            Statement::Block(n) => n.id,
+            Statement::Conditional(n) => n.id,
+            Statement::Console(n) => n.id,
+            Statement::Decrement(n) => n.id,
+            Statement::Definition(n) => n.id,
+            Statement::Expression(n) => n.id,
+            Statement::Finalize(n) => n.id,
+            Statement::Increment(n) => n.id,
+            Statement::Iteration(n) => n.id,
+            Statement::Match(n) => n.id,
+            Statement::Return(n) => n.id,
+        }
+    }
 }
 
 impl fmt::Display for Statement {
@@ -95,9 +124,11 @@ impl fmt::Display for Statement {
             Statement::Console(x) => x.fmt(f),
             Statement::Decrement(x) => x.fmt(f),
             Statement::Definition(x) => x.fmt(f),
+            Statement::Expression(x) => x.fmt(f),
             Statement::Finalize(x) => x.fmt(f),
             Statement::Increment(x) => x.fmt(f),
             Statement::Iteration(x) => x.fmt(f),
+            Statement::Match(x) => x.fmt(f),
             Statement::Return(x) => x.fmt(f),
         }
     }
@@ -113,9 +144,11 @@ impl Node for Statement {
             Console(n) => n.span(),
             Decrement(n) => n.span(),
             Definition(n) => n.span(),
+            Expression(n) => n.span(),
             Finalize(n) => n.span(),
             Increment(n) => n.span(),
             Iteration(n) => n.span(),
+            Match(n) => n.span(),
             Return(n) => n.span(),
         }
     }
@@ -129,9 +162,11 @@ impl Node for Statement {
             Console(n) => n.set_span(span),
             Decrement(n) => n.set_span(span),
             Definition(n) => n.set_span(span),
+            Expression(n) => n.set_span(span),
             Finalize(n) => n.set_span(span),
             Increment(n) => n.set_span(span),
             Iteration(n) => n.set_span(span),
+            Match(n) => n.set_span(span),
             Return(n) => n.set_span(span),
         }
     }
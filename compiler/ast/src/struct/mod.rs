@@ -31,6 +31,9 @@ use std::fmt;
 /// The fields are named so `struct Foo(u8, u16)` is not allowed.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Struct {
+    /// The doc comment of the struct, without the leading `///`s, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<String>,
     /// The name of the type in the type system in this module.
     pub identifier: Identifier,
     /// The fields, constant variables, and functions of this structure.
@@ -42,6 +42,24 @@ impl IntegerType {
         matches!(self, I8 | I16 | I32 | I64 | I128)
     }
 
+    /// Returns the number of bits in a value of the integer type.
+    pub fn bits(&self) -> u32 {
+        use IntegerType::*;
+        match self {
+            U8 | I8 => 8,
+            U16 | I16 => 16,
+            U32 | I32 => 32,
+            U64 | I64 => 64,
+            U128 | I128 => 128,
+        }
+    }
+
+    /// Can every value of the integer type be represented in `other`?
+    /// This is the case for a wider type of the same signedness, and for a wider signed type of an unsigned one.
+    pub fn widens_to(&self, other: &IntegerType) -> bool {
+        (!self.is_signed() || other.is_signed()) && self.bits() < other.bits()
+    }
+
     /// Returns the symbol for the integer type.
     pub fn symbol(self) -> Symbol {
         match self {
@@ -16,7 +16,7 @@
 
 use crate::{GroupLiteral, Identifier, IntegerType, Literal, Type};
 
-use leo_errors::{type_name, FlattenError, LeoError, Result};
+use leo_errors::{type_name, AstError, FlattenError, LeoError, Result};
 use leo_span::{Span, Symbol};
 
 use indexmap::IndexMap;
@@ -25,6 +25,9 @@ use std::{
     ops::{BitAnd, BitOr, BitXor, Not},
 };
 
+mod record;
+pub use record::*;
+
 // TODO: Consider refactoring this module to use the console implementations from snarkVM.
 
 // This is temporary since the currently unused code is used in constant folding.
@@ -70,9 +73,7 @@ macro_rules! implement_const_unary {
             l: $logic:expr
         ]),+]
     ) => {
-        // TODO: This is temporary since the currently unused code is used in constant folding.
-        #[allow(dead_code)]
-        pub(crate) fn $name(self, span: Span) -> Result<Self> {
+        pub fn $name(self, span: Span) -> Result<Self> {
             use Value::*;
 
             match self {
@@ -156,9 +157,7 @@ macro_rules! implement_const_binary {
             logic: $logic:expr
         ]),+]
     ) => {
-        // This is temporary since the currently unused code is used in constant folding.
-        #[allow(dead_code)]
-        pub(crate) fn $name(self, other: Self, span: Span) -> Result<Self> {
+        pub fn $name(self, other: Self, span: Span) -> Result<Self> {
             use Value::*;
 
             match (self, other) {
@@ -197,13 +196,14 @@ pub enum Value {
     U64(u64, Span),
     U128(u128, Span),
     Scalar(String, Span),
+    Signature(String, Span),
     String(String, Span),
+    Tuple(Vec<Value>),
 }
 
 impl Value {
-    // TODO: This is temporary since the currently unused code is used in constant folding.
-    #[allow(dead_code)]
-    pub(crate) fn is_supported_const_fold_type(&self) -> bool {
+    /// Returns `true` if the operations on the value are evaluated at compile time, i.e. it is a boolean or an integer.
+    pub fn is_supported_const_fold_type(&self) -> bool {
         use Value::*;
         matches!(
             self,
@@ -221,6 +221,18 @@ impl Value {
         )
     }
 
+    /// Compares two constant values, ignoring their spans.
+    /// Returns `None` if the values cannot be compared at compile time.
+    pub fn const_eq(&self, other: &Self) -> Option<bool> {
+        if !self.is_supported_const_fold_type() || Type::from(self) != Type::from(other) {
+            return None;
+        }
+        match self.clone().eq(other.clone(), Default::default()) {
+            Ok(Value::Boolean(result, _)) => Some(result),
+            _ => None,
+        }
+    }
+
     implement_const_unary!(
         @overflowing
         name: abs,
@@ -390,6 +402,43 @@ impl Value {
         ]
     );
 
+    implement_const_binary!(
+        @overflowing
+        name: rem,
+        method: checked_rem,
+        string: "%",
+        patterns: [
+            [I8, [I8], I8, i8, i8],
+            [I16, [I16], I16, i16, i16],
+            [I32, [I32], I32, i32, i32],
+            [I64, [I64], I64, i64, i64],
+            [I128, [I128], I128, i128, i128],
+            [U8, [U8], U8, u8, u8],
+            [U16, [U16], U16, u16, u16],
+            [U32, [U32], U32, u32, u32],
+            [U64, [U64], U64, u64, u64],
+            [U128, [U128], U128, u128, u128]
+        ]
+    );
+
+    implement_const_binary!(
+        @non-overflowing
+        name: rem_wrapped,
+        method: wrapping_rem,
+        patterns: [
+            [I8, [I8], I8, i8, i8],
+            [I16, [I16], I16, i16, i16],
+            [I32, [I32], I32, i32, i32],
+            [I64, [I64], I64, i64, i64],
+            [I128, [I128], I128, i128, i128],
+            [U8, [U8], U8, u8, u8],
+            [U16, [U16], U16, u16, u16],
+            [U32, [U32], U32, u32, u32],
+            [U64, [U64], U64, u64, u64],
+            [U128, [U128], U128, u128, u128]
+        ]
+    );
+
     implement_const_binary!(
         @cmp
         name: eq,
@@ -743,7 +792,17 @@ impl Display for Value {
             U64(val, _) => write!(f, "{val}"),
             U128(val, _) => write!(f, "{val}"),
             Scalar(val, _) => write!(f, "{val}"),
+            Signature(val, _) => write!(f, "{val}"),
             String(val, _) => write!(f, "{val}"),
+            Tuple(values) => write!(
+                f,
+                "({})",
+                values
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
         }
     }
 }
@@ -859,61 +918,73 @@ impl From<&Value> for Type {
             U64(_, _) => Type::Integer(IntegerType::U64),
             U128(_, _) => Type::Integer(IntegerType::U128),
             Scalar(_, _) => Type::Scalar,
+            Signature(_, _) => Type::Signature,
             String(_, _) => Type::String,
+            Tuple(values) => Type::Tuple(crate::Tuple(values.iter().map(Type::from).collect())),
         }
     }
 }
 
-// TODO: Consider making this `Option<Value>` instead of `Value`.
+impl Value {
+    /// Converts a literal to a value, returning `None` if an integer literal is out of range for its type.
+    pub fn from_literal(literal: &Literal) -> Option<Self> {
+        Some(match literal {
+            Literal::Address(string, span, _) => Self::Address(string.clone(), *span),
+            Literal::Boolean(bool, span, _) => Self::Boolean(*bool, *span),
+            Literal::Field(string, span, _) => Self::Field(string.clone(), *span),
+            Literal::Group(group_literal) => Self::Group(group_literal.clone()),
+            Literal::Scalar(string, span, _) => Self::Scalar(string.clone(), *span),
+            Literal::Signature(string, span, _) => Self::Signature(string.clone(), *span),
+            Literal::String(string, span, _) => Self::String(string.clone(), *span),
+            Literal::Integer(integer_type, string, span, _) => match integer_type {
+                IntegerType::U8 => Self::U8(string.parse().ok()?, *span),
+                IntegerType::U16 => Self::U16(string.parse().ok()?, *span),
+                IntegerType::U32 => Self::U32(string.parse().ok()?, *span),
+                IntegerType::U64 => Self::U64(string.parse().ok()?, *span),
+                IntegerType::U128 => Self::U128(string.parse().ok()?, *span),
+                IntegerType::I8 => Self::I8(string.parse().ok()?, *span),
+                IntegerType::I16 => Self::I16(string.parse().ok()?, *span),
+                IntegerType::I32 => Self::I32(string.parse().ok()?, *span),
+                IntegerType::I64 => Self::I64(string.parse().ok()?, *span),
+                IntegerType::I128 => Self::I128(string.parse().ok()?, *span),
+            },
+        })
+    }
+}
+
 impl From<&Literal> for Value {
     /// Converts a literal to a value.
     /// This should only be invoked on literals that are known to be valid.
     fn from(literal: &Literal) -> Self {
-        match literal {
-            Literal::Address(string, span) => Self::Address(string.clone(), *span),
-            Literal::Boolean(bool, span) => Self::Boolean(*bool, *span),
-            Literal::Field(string, span) => Self::Field(string.clone(), *span),
-            Literal::Group(group_literal) => Self::Group(group_literal.clone()),
-            Literal::Scalar(string, span) => Self::Scalar(string.clone(), *span),
-            Literal::String(string, span) => Self::String(string.clone(), *span),
-            Literal::Integer(integer_type, string, span) => match integer_type {
-                IntegerType::U8 => Self::U8(string.parse().unwrap(), *span),
-                IntegerType::U16 => Self::U16(string.parse().unwrap(), *span),
-                IntegerType::U32 => Self::U32(string.parse().unwrap(), *span),
-                IntegerType::U64 => Self::U64(string.parse().unwrap(), *span),
-                IntegerType::U128 => Self::U128(string.parse().unwrap(), *span),
-                IntegerType::I8 => Self::I8(string.parse().unwrap(), *span),
-                IntegerType::I16 => Self::I16(string.parse().unwrap(), *span),
-                IntegerType::I32 => Self::I32(string.parse().unwrap(), *span),
-                IntegerType::I64 => Self::I64(string.parse().unwrap(), *span),
-                IntegerType::I128 => Self::I128(string.parse().unwrap(), *span),
-            },
-        }
+        Self::from_literal(literal).expect("literal should have been checked by the type checker")
     }
 }
 
-impl From<Value> for Literal {
-    fn from(v: Value) -> Self {
+impl TryFrom<Value> for Literal {
+    type Error = LeoError;
+
+    /// Converts a value to a literal, which has id `0`, as it is not a node of a program.
+    fn try_from(v: Value) -> Result<Self, Self::Error> {
         use Value::*;
-        match v {
-            Input(_, _) => todo!("We need to test if this is hittable"),
-            Address(v, span) => Literal::Address(v, span),
-            Boolean(v, span) => Literal::Boolean(v, span),
-            Struct(_ident, _values) => todo!("We need to test if this is hittable"),
-            Field(v, span) => Literal::Field(v, span),
+        Ok(match v {
+            Input(_, _) | Struct(_, _) | Tuple(_) => return Err(AstError::value_is_not_a_literal(v).into()),
+            Address(v, span) => Literal::Address(v, span, Default::default()),
+            Boolean(v, span) => Literal::Boolean(v, span, Default::default()),
+            Field(v, span) => Literal::Field(v, span, Default::default()),
             Group(v) => Literal::Group(v),
-            I8(v, span) => Literal::Integer(IntegerType::I8, v.to_string(), span),
-            I16(v, span) => Literal::Integer(IntegerType::I16, v.to_string(), span),
-            I32(v, span) => Literal::Integer(IntegerType::I32, v.to_string(), span),
-            I64(v, span) => Literal::Integer(IntegerType::I64, v.to_string(), span),
-            I128(v, span) => Literal::Integer(IntegerType::I128, v.to_string(), span),
-            U8(v, span) => Literal::Integer(IntegerType::U8, v.to_string(), span),
-            U16(v, span) => Literal::Integer(IntegerType::U16, v.to_string(), span),
-            U32(v, span) => Literal::Integer(IntegerType::U32, v.to_string(), span),
-            U64(v, span) => Literal::Integer(IntegerType::U64, v.to_string(), span),
-            U128(v, span) => Literal::Integer(IntegerType::U128, v.to_string(), span),
-            Scalar(v, span) => Literal::Scalar(v, span),
-            String(v, span) => Literal::String(v, span),
-        }
+            I8(v, span) => Literal::Integer(IntegerType::I8, v.to_string(), span, Default::default()),
+            I16(v, span) => Literal::Integer(IntegerType::I16, v.to_string(), span, Default::default()),
+            I32(v, span) => Literal::Integer(IntegerType::I32, v.to_string(), span, Default::default()),
+            I64(v, span) => Literal::Integer(IntegerType::I64, v.to_string(), span, Default::default()),
+            I128(v, span) => Literal::Integer(IntegerType::I128, v.to_string(), span, Default::default()),
+            U8(v, span) => Literal::Integer(IntegerType::U8, v.to_string(), span, Default::default()),
+            U16(v, span) => Literal::Integer(IntegerType::U16, v.to_string(), span, Default::default()),
+            U32(v, span) => Literal::Integer(IntegerType::U32, v.to_string(), span, Default::default()),
+            U64(v, span) => Literal::Integer(IntegerType::U64, v.to_string(), span, Default::default()),
+            U128(v, span) => Literal::Integer(IntegerType::U128, v.to_string(), span, Default::default()),
+            Scalar(v, span) => Literal::Scalar(v, span, Default::default()),
+            Signature(v, span) => Literal::Signature(v, span, Default::default()),
+            String(v, span) => Literal::String(v, span, Default::default()),
+        })
     }
 }
@@ -0,0 +1,105 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Identifier, Struct, Value};
+
+use leo_errors::{InputError, Result};
+use leo_span::{sym, Span, Symbol};
+
+use indexmap::IndexMap;
+
+/// The value of a record, e.g. an input of a transition that consumes a record, which is run locally
+/// from the members of the record rather than from its ciphertext.
+/// The members that every record has are kept apart from those that its declaration adds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordValue {
+    /// The name of the record type.
+    pub name: Identifier,
+    /// The address of the owner of the record.
+    pub owner: String,
+    /// The gates held by the record, i.e. its microcredits.
+    pub gates: u64,
+    /// The members that the declaration of the record adds to `owner` and `gates`, in the order they are declared.
+    pub members: IndexMap<Symbol, Value>,
+    /// The nonce of the record, if it is given, e.g. to spend a record that was created before.
+    pub nonce: Option<Value>,
+}
+
+impl RecordValue {
+    /// Returns the record of `value`, which is a value of the record `declaration`, e.g. one of an input file.
+    /// Fails at `span` if it is not a record of that type with an address `owner`, `u64` gates,
+    /// each member that the declaration adds, and at most a group nonce besides.
+    pub fn from_value(value: &Value, declaration: &Struct, span: Span) -> Result<Self> {
+        let name = declaration.identifier;
+        let invalid = |reason: String| InputError::invalid_record_value(name, reason, span);
+        let members = match value {
+            Value::Struct(struct_name, members) if struct_name.name == name.name => members,
+            value => return Err(invalid(format!("found `{}`", value)).into()),
+        };
+
+        let owner = match members.get(&sym::owner) {
+            Some(Value::Address(owner, _)) => owner.clone(),
+            _ => return Err(invalid("`owner` must be an address".to_string()).into()),
+        };
+        let gates = match members.get(&sym::gates) {
+            Some(Value::U64(gates, _)) => *gates,
+            _ => return Err(invalid("`gates` must be a `u64`".to_string()).into()),
+        };
+        let nonce = match members.get(&sym::_nonce) {
+            Some(nonce @ Value::Group(_)) => Some(nonce.clone()),
+            Some(_) => return Err(invalid("`_nonce` must be a group".to_string()).into()),
+            None => None,
+        };
+
+        let mut declared = IndexMap::new();
+        for member in declaration.members.iter() {
+            let member = member.name();
+            if member == sym::owner || member == sym::gates {
+                continue;
+            }
+            let value = members
+                .get(&member)
+                .ok_or_else(|| invalid(format!("the member `{}` is missing", member)))?;
+            declared.insert(member, value.clone());
+        }
+        if let Some(member) = members
+            .keys()
+            .find(|member| ![sym::owner, sym::gates, sym::_nonce].contains(member) && !declared.contains_key(*member))
+        {
+            return Err(invalid(format!("`{}` is not a member", member)).into());
+        }
+
+        Ok(Self {
+            name,
+            owner,
+            gates,
+            members: declared,
+            nonce,
+        })
+    }
+
+    /// Returns the record as a struct value, as the interpreter operates on it.
+    pub fn into_value(self) -> Value {
+        let mut members = IndexMap::new();
+        members.insert(sym::owner, Value::Address(self.owner, Span::default()));
+        members.insert(sym::gates, Value::U64(self.gates, Span::default()));
+        members.extend(self.members);
+        if let Some(nonce) = self.nonce {
+            members.insert(sym::_nonce, nonce);
+        }
+        Value::Struct(self.name, members)
+    }
+}
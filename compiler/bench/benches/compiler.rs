@@ -0,0 +1,171 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Benchmarks of the stages of the Leo compiler over representative and generated programs.
+
+use leo_compiler::Compiler;
+use leo_compiler_bench::{new_compiler, silent_handler, Sample};
+use leo_span::{source_map::FileName, symbol::SESSION_GLOBALS};
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::time::{Duration, Instant};
+
+/// The number of functions of the generated programs that the scaling benchmarks compile.
+const SCALING_SIZES: [usize; 4] = [10, 50, 250, 1000];
+
+/// Returns the total time of `iters` runs of `logic`, each with a fresh compiler in a fresh session.
+/// `logic` returns the time of the part of its work that is measured.
+fn time_iters(iters: u64, mut logic: impl FnMut(Compiler) -> Duration) -> Duration {
+    (0..iters)
+        .map(|_| SESSION_GLOBALS.set(&<_>::default(), || logic(new_compiler(&silent_handler()))))
+        .sum()
+}
+
+/// Parses `sample` into `compiler`.
+fn parse(compiler: &mut Compiler, sample: &Sample) {
+    compiler
+        .parse_program_from_string(black_box(&sample.source), FileName::Custom(sample.name.clone()))
+        .expect("failed to parse program");
+}
+
+/// Times `stage` on each representative sample, running `setup` on the parsed sample first without timing it.
+fn bench_stage<T>(
+    c: &mut Criterion,
+    group: &str,
+    setup: impl Fn(&mut Compiler) -> T,
+    stage: impl Fn(&mut Compiler, T),
+) {
+    let mut group = c.benchmark_group(group);
+    for sample in Sample::representative() {
+        group.bench_function(&sample.name, |b| {
+            b.iter_custom(|iters| {
+                time_iters(iters, |mut compiler| {
+                    parse(&mut compiler, &sample);
+                    let input = setup(&mut compiler);
+                    let start = Instant::now();
+                    stage(&mut compiler, input);
+                    start.elapsed()
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parsing");
+    for sample in Sample::representative() {
+        group.bench_function(&sample.name, |b| {
+            b.iter_custom(|iters| {
+                time_iters(iters, |mut compiler| {
+                    let start = Instant::now();
+                    parse(&mut compiler, &sample);
+                    start.elapsed()
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_type_checking(c: &mut Criterion) {
+    bench_stage(
+        c,
+        "type checking",
+        |compiler| compiler.symbol_table_pass().expect("failed to generate symbol table"),
+        |compiler, symbol_table| {
+            compiler
+                .type_checker_pass(symbol_table)
+                .expect("failed to run type checking pass");
+        },
+    );
+}
+
+fn bench_flattening(c: &mut Criterion) {
+    bench_stage(
+        c,
+        "flattening",
+        |compiler| {
+            let symbol_table = compiler.symbol_table_pass().expect("failed to generate symbol table");
+            let (symbol_table, widenings, match_types, mut type_table) = compiler
+                .type_checker_pass(symbol_table)
+                .expect("failed to run type checking pass");
+            compiler.match_lowering_pass(match_types);
+            compiler.implicit_widening_pass(widenings);
+            let symbol_table = compiler
+                .loop_unrolling_pass(symbol_table, &mut type_table)
+                .expect("failed to run loop unrolling pass");
+            let assigner = compiler
+                .static_single_assignment_pass(&symbol_table)
+                .expect("failed to run ssa pass");
+            (symbol_table, type_table, assigner)
+        },
+        |compiler, (symbol_table, type_table, assigner)| {
+            compiler
+                .flattening_pass(&symbol_table, &type_table, assigner)
+                .expect("failed to run flattening pass");
+        },
+    );
+}
+
+fn bench_code_generation(c: &mut Criterion) {
+    bench_stage(
+        c,
+        "code generation",
+        |compiler| {
+            compiler.compiler_stages().expect("failed to run compiler stages");
+        },
+        |compiler, ()| {
+            compiler
+                .code_generation_pass()
+                .expect("failed to generate instructions");
+        },
+    );
+}
+
+fn bench_scaling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scaling");
+    for functions in SCALING_SIZES {
+        let sample = Sample::generated(functions);
+        group.throughput(Throughput::Elements(functions as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(functions), &sample, |b, sample| {
+            b.iter_custom(|iters| {
+                time_iters(iters, |mut compiler| {
+                    let start = Instant::now();
+                    parse(&mut compiler, sample);
+                    compiler.compiler_stages().expect("failed to run compiler stages");
+                    compiler
+                        .code_generation_pass()
+                        .expect("failed to generate instructions");
+                    start.elapsed()
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().sample_size(50).measurement_time(Duration::from_secs(10));
+    targets =
+        bench_parsing,
+        bench_type_checking,
+        bench_flattening,
+        bench_code_generation,
+        bench_scaling
+);
+criterion_main!(benches);
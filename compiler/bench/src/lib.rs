@@ -0,0 +1,136 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Programs and helpers for benchmarking the Leo compiler.
+//!
+//! The benchmarks themselves are in `benches/compiler.rs`.
+
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use leo_compiler::Compiler;
+use leo_errors::{
+    emitter::{Emitter, Handler},
+    LeoError, LeoWarning,
+};
+
+use std::{fmt::Write, path::PathBuf};
+
+/// A program to benchmark the compiler on.
+#[derive(Clone, Debug)]
+pub struct Sample {
+    /// The name of the sample, used in the names of the benchmarks.
+    pub name: String,
+    /// The source of the program.
+    pub source: String,
+}
+
+impl Sample {
+    /// Returns a small, a medium, and a large program, representative of the programs the compiler is used on.
+    pub fn representative() -> Vec<Self> {
+        vec![
+            Self {
+                name: "small".to_string(),
+                source: include_str!("../../../examples/helloworld/src/main.leo").to_string(),
+            },
+            Self {
+                name: "medium".to_string(),
+                source: include_str!("../../../examples/token/src/main.leo").to_string(),
+            },
+            Self {
+                name: "large".to_string(),
+                source: generate_program("large", 100),
+            },
+        ]
+    }
+
+    /// Returns a program generated with `functions` functions, named after its size.
+    pub fn generated(functions: usize) -> Self {
+        Self {
+            name: format!("generated_{functions}"),
+            source: generate_program("generated", functions),
+        }
+    }
+}
+
+/// Returns the source of a valid program named `name` with `functions` functions and as many transitions calling them.
+/// Each function mixes the constructs that the passes spend their time on:
+/// struct values, bounded loops, conditionals, and arithmetic.
+/// The size of the program grows linearly with `functions`.
+pub fn generate_program(name: &str, functions: usize) -> String {
+    let mut source = String::new();
+    let _ = writeln!(source, "program {name}.aleo {{");
+    source.push_str("    struct Point {\n        x: u32,\n        y: u32,\n    }\n");
+
+    for i in 0..functions {
+        let _ = write!(
+            source,
+            "
+    function mix_{i}(a: u32, b: u32) -> Point {{
+        let x: u32 = a + {i}u32;
+        let y: u32 = b;
+        for j: u32 in 0u32..4u32 {{
+            if x > y {{
+                y = y + j;
+            }} else {{
+                x = x + j;
+            }}
+        }}
+        let z: u32 = x > 100u32 ? x - 100u32 : x * 2u32;
+        return Point {{ x: z, y: y ^ {i}u32 }};
+    }}
+
+    transition step_{i}(a: u32, b: u32) -> u32 {{
+        let p: Point = mix_{i}(a, b);
+        return p.x + p.y;
+    }}
+"
+        );
+    }
+
+    source.push_str("}\n");
+    source
+}
+
+/// An emitter that discards everything, since the benchmarks only compile valid programs.
+struct SilentEmitter;
+
+impl Emitter for SilentEmitter {
+    fn emit_err(&mut self, _: LeoError) {}
+
+    fn last_emitted_err_code(&self) -> Option<i32> {
+        None
+    }
+
+    fn emit_warning(&mut self, _: LeoWarning) {}
+}
+
+/// Returns a handler that discards all errors and warnings.
+pub fn silent_handler() -> Handler {
+    Handler::new(Box::new(SilentEmitter))
+}
+
+/// Returns a compiler that writes no output files, for benchmarking.
+pub fn new_compiler(handler: &Handler) -> Compiler<'_> {
+    Compiler::new(
+        String::from("bench"),
+        String::from("aleo"),
+        handler,
+        PathBuf::new(),
+        PathBuf::new(),
+        None,
+    )
+}
@@ -0,0 +1,240 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The on-disk cache of compiled Leo programs.
+//!
+//! A program is only compiled again if its file, the programs it imports, the options that it is compiled with,
+//! or the compiler changed since it was cached.
+
+use leo_errors::{CompilerError, Result};
+use leo_passes::SymbolTable;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The name of the directory in the build directory that holds the compilation cache.
+pub const CACHE_DIRECTORY_NAME: &str = "cache";
+
+/// The artifacts of compiling a Leo program, which are reused while the program and the compiler do not change.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CompilationArtifacts {
+    /// The key of the program when it was compiled.
+    key: String,
+    /// The symbol table of the program after type checking.
+    pub symbol_table: SymbolTable,
+    /// The Aleo instructions generated for the program.
+    pub instructions: String,
+}
+
+/// A cache of the compilation artifacts of Leo programs, with one entry per program.
+#[derive(Clone, Debug)]
+pub struct CompilationCache {
+    /// The directory that holds the entries of the cache.
+    directory: PathBuf,
+}
+
+impl CompilationCache {
+    /// Returns the compilation cache in the given build directory.
+    pub fn new(build_directory: &Path) -> Self {
+        Self {
+            directory: build_directory.join(CACHE_DIRECTORY_NAME),
+        }
+    }
+
+    /// Returns the key of a program, which changes whenever its source, the `options` that change how it is compiled,
+    /// the `imports` it parses to, i.e. the programs it imports transitively, or the compiler changes.
+    pub fn key(program_name: &str, network: &str, source: &str, options: &str, imports: &str) -> String {
+        let mut hasher = Sha256::new();
+        for part in [
+            env!("CARGO_PKG_VERSION"),
+            program_name,
+            network,
+            source,
+            options,
+            imports,
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Returns the cached artifacts of the program, if they were cached with the given key.
+    /// An entry that cannot be read, e.g. because it was written by another version of the compiler, is ignored.
+    pub fn load(&self, program_name: &str, key: &str) -> Option<CompilationArtifacts> {
+        let entry = fs::read(self.entry_path(program_name)).ok()?;
+        leo_ast::from_binary::<CompilationArtifacts>(&entry)
+            .ok()
+            .filter(|artifacts| artifacts.key == key)
+    }
+
+    /// Caches the artifacts of the program with the given key, replacing its previous entry.
+    pub fn store(
+        &self,
+        program_name: &str,
+        key: String,
+        symbol_table: SymbolTable,
+        instructions: String,
+    ) -> Result<()> {
+        let path = self.entry_path(program_name);
+        let artifacts = CompilationArtifacts {
+            key,
+            symbol_table,
+            instructions,
+        };
+        // The artifacts are stored in the binary format, since the JSON of a large AST takes hundreds of megabytes.
+        let entry = leo_ast::to_binary(&artifacts)?;
+        fs::create_dir_all(&self.directory)
+            .and_then(|_| fs::write(&path, entry))
+            .map_err(|e| CompilerError::failed_to_write_compilation_cache(&path, e))?;
+        Ok(())
+    }
+
+    /// Returns the path of the entry of the program.
+    fn entry_path(&self, program_name: &str) -> PathBuf {
+        self.directory.join(format!("{program_name}.bin"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CompilationCache, Compiler, ImportPolicy, ImportSearchPath, OutputOptions};
+
+    use leo_errors::{
+        emitter::{Handler, WarningConfig, WarningLevel},
+        Result,
+    };
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    use std::{fs, path::PathBuf};
+
+    /// A package in a temporary directory, whose program imports `helper.leo`.
+    struct Package {
+        directory: PathBuf,
+    }
+
+    impl Package {
+        fn new(name: &str) -> Self {
+            let directory = std::env::temp_dir().join(format!("leo-cache-{}-{}", name, std::process::id()));
+            fs::create_dir_all(directory.join("src")).unwrap();
+            fs::create_dir_all(directory.join("imports")).unwrap();
+            fs::write(
+                directory.join("src/main.leo"),
+                "import helper.leo;\nprogram main.aleo {\n    transition main(a: u8) -> u8 {\n        return a;\n    }\n}\n",
+            )
+            .unwrap();
+            let package = Self { directory };
+            package.write_helper("a + a");
+            package
+        }
+
+        /// Writes the imported program, whose transition returns `body`.
+        fn write_helper(&self, body: &str) {
+            let source = format!(
+                "program helper.aleo {{\n    transition double(a: u8) -> u8 {{\n        return {};\n    }}\n}}\n",
+                body
+            );
+            fs::write(self.directory.join("imports/helper.leo"), source).unwrap();
+        }
+
+        /// Compiles the program with `options`, returning whether the cached artifacts were reused.
+        fn compile(&self, options: OutputOptions) -> Result<bool> {
+            let (handler, _) = Handler::new_with_buf();
+            let mut compiler = Compiler::new(
+                "main".to_string(),
+                "aleo".to_string(),
+                &handler,
+                self.directory.join("src/main.leo"),
+                self.directory.join("outputs"),
+                Some(OutputOptions {
+                    import_search_path: ImportSearchPath::new(vec![self.directory.join("imports")]),
+                    ..options
+                }),
+            );
+            let cache = CompilationCache::new(&self.directory.join("build"));
+            Ok(compiler.compile_with_cache(&cache)?.2)
+        }
+    }
+
+    impl Drop for Package {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.directory);
+        }
+    }
+
+    #[test]
+    fn reused_until_an_import_or_option_changes() {
+        let package = Package::new("reuse");
+        // The program is compiled repeatedly in one session, so its imports are parsed at different spans each time.
+        create_session_if_not_set_then(|_| {
+            assert!(!package.compile(OutputOptions::default()).unwrap());
+            assert!(package.compile(OutputOptions::default()).unwrap());
+
+            // The options that change the instructions are part of the key.
+            let keep_proven_asserts = || OutputOptions {
+                keep_proven_asserts: true,
+                ..Default::default()
+            };
+            assert!(!package.compile(keep_proven_asserts()).unwrap());
+            assert!(package.compile(keep_proven_asserts()).unwrap());
+
+            // As are the levels of warnings, since a reused program emits no warnings that could be denied:
+            // the unused import is warned about, which fails the compilation if warnings are denied.
+            let deny_warnings = OutputOptions {
+                warnings: WarningConfig {
+                    default_level: WarningLevel::Deny,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            assert!(!package.compile(OutputOptions::default()).unwrap());
+            assert!(package.compile(deny_warnings).is_err());
+
+            let keep_unused_imports = OutputOptions {
+                keep_unused_imports: true,
+                ..Default::default()
+            };
+            assert!(!package.compile(keep_unused_imports).unwrap());
+
+            // So are the programs that it imports, even if they are not used.
+            assert!(!package.compile(OutputOptions::default()).unwrap());
+            package.write_helper("a * 2u8");
+            assert!(!package.compile(OutputOptions::default()).unwrap());
+            assert!(package.compile(OutputOptions::default()).unwrap());
+        });
+    }
+
+    #[test]
+    fn import_policy_checked_when_reused() {
+        let package = Package::new("policy");
+        create_session_if_not_set_then(|_| {
+            let denied = || OutputOptions {
+                import_policy: ImportPolicy {
+                    allowed: None,
+                    denied: vec!["helper".to_string()],
+                },
+                ..Default::default()
+            };
+            assert!(!package.compile(OutputOptions::default()).unwrap());
+            assert!(package.compile(denied()).is_err());
+            // The policy is checked even though the artifacts of the program are cached.
+            assert!(package.compile(OutputOptions::default()).unwrap());
+            assert!(package.compile(denied()).is_err());
+        });
+    }
+}
@@ -17,20 +17,33 @@
 //! The compiler for Leo programs.
 //!
 //! The [`Compiler`] type compiles Leo programs into R1CS circuits.
-use leo_ast::Program;
-pub use leo_ast::{Ast, InputAst};
+pub use leo_ast::{Ast, InputAst, LanguageVersion};
+use leo_ast::{Function, Mode, Node, Program, RecordValue, Type};
 use leo_errors::emitter::Handler;
-use leo_errors::{CompilerError, Result};
-pub use leo_passes::SymbolTable;
+use leo_errors::{AstError, CompilerError, InputError, InterpreterError, LintWarning, Result};
+#[cfg(feature = "rocksdb")]
+pub use leo_interpreter::RocksDbStorage;
+use leo_interpreter::{evaluate_constant, input_definition, Interpreter};
+pub use leo_interpreter::{
+    value_to_source, Breakpoint, DebugFrontend, DebugStep, Debugger, Execution, JsonStorage, Mappings, PauseReason,
+    StackFrame, StateStorage,
+};
 use leo_passes::*;
+pub use leo_passes::{
+    CallGraph, CallNode, CostEstimate, FunctionInstructions, GeneratedHeader, HaltPoint, HaltReason,
+    InstructionMapping, InstructionSourceMap, PartialTypeCheck, ProvenAssert, StatementInstructions, SymbolTable,
+    TypeTable,
+};
 use leo_span::source_map::FileName;
 use leo_span::symbol::with_session_globals;
+use leo_span::{Span, Symbol};
 
 use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
-use crate::OutputOptions;
+use crate::{AstFormat, CompilationCache, DumpFormat, ImportPolicy, OutputOptions, TestHarness};
 
 /// The primary entry point of the Leo compiler.
 #[derive(Clone)]
@@ -49,6 +62,22 @@ pub struct Compiler<'a> {
     pub ast: Ast,
     /// The input ast for the program if it exists.
     pub input_ast: Option<InputAst>,
+    /// The version of Leo that the program is written for.
+    pub language_version: LanguageVersion,
+    /// The operations that can halt at runtime, if they are listed in the output options.
+    pub halt_points: Vec<HaltPoint>,
+    /// The calls between the functions of the program, after type checking.
+    pub call_graph: CallGraph,
+    /// The estimated number of constraints of each function, if they are estimated in the output options.
+    pub cost_estimates: Vec<CostEstimate>,
+    /// The assertions that the range analysis proved to always hold, which are removed unless the output options keep them.
+    pub proven_asserts: Vec<ProvenAssert>,
+    /// The number of instructions generated for each statement of each function, after generating instructions.
+    pub instruction_counts: Vec<FunctionInstructions>,
+    /// The Leo statement each generated Aleo instruction came from, after generating instructions.
+    pub instruction_source_map: InstructionSourceMap,
+    /// How long each pass of the last compilation took, in the order they ran.
+    pub pass_durations: Vec<(&'static str, Duration)>,
     /// Compiler options on some optional output files.
     output_options: OutputOptions,
 }
@@ -63,6 +92,10 @@ impl<'a> Compiler<'a> {
         output_directory: PathBuf,
         output_options: Option<OutputOptions>,
     ) -> Self {
+        let output_options = output_options.unwrap_or_default();
+        handler.set_warning_config(output_options.warnings.clone());
+        handler.set_error_limit(output_options.error_limit);
+
         Self {
             handler,
             main_file_path,
@@ -71,7 +104,15 @@ impl<'a> Compiler<'a> {
             network,
             ast: Ast::new(Program::default()),
             input_ast: None,
-            output_options: output_options.unwrap_or_default(),
+            language_version: output_options.language_version.unwrap_or(LanguageVersion::CURRENT),
+            halt_points: Vec::new(),
+            call_graph: CallGraph::default(),
+            cost_estimates: Vec::new(),
+            proven_asserts: Vec::new(),
+            instruction_counts: Vec::new(),
+            instruction_source_map: InstructionSourceMap::default(),
+            pass_durations: Vec::new(),
+            output_options,
         }
     }
 
@@ -94,8 +135,19 @@ impl<'a> Compiler<'a> {
         // Register the source (`program_string`) in the source map.
         let prg_sf = with_session_globals(|s| s.source_map.new_source(program_string, name));
 
+        // A version declared in the source takes precedence over the one in the options.
+        if let Some(version) = leo_parser::declared_version(&prg_sf.src, prg_sf.start_pos)? {
+            self.language_version = version;
+        }
+
         // Use the parser to construct the abstract syntax tree (ast).
-        self.ast = leo_parser::parse_ast(self.handler, &prg_sf.src, prg_sf.start_pos)?;
+        self.ast = Ast::new(leo_parser::parse_with_version(
+            self.handler,
+            &prg_sf.src,
+            prg_sf.start_pos,
+            self.language_version,
+            &self.output_options.import_search_path,
+        )?);
 
         // If the program is imported, then check that the name of its program scope matches the file name.
         // Note that parsing enforces that there is exactly one program scope in a file.
@@ -111,11 +163,17 @@ impl<'a> Compiler<'a> {
             .into());
         }
 
+        // Check that each imported program is the one its file is named after.
+        check_import_names(self.ast.as_repr())?;
+
+        // Check that the package may import each program imported by the program, or by its imports.
+        check_import_policy(&self.output_options.import_policy, self.ast.as_repr(), &mut Vec::new())?;
+
         if self.output_options.initial_ast {
-            self.write_ast_to_json("initial_ast.json")?;
+            self.write_ast_snapshot("initial_ast")?;
         }
 
-        Ok(())
+        self.async_desugaring_pass()
     }
 
     /// Parses and stores the main program file, constructs a syntax tree, and generates a program.
@@ -128,6 +186,7 @@ impl<'a> Compiler<'a> {
     }
 
     /// Parses and stores the input file, constructs a syntax tree, and generates a program input.
+    /// An input file in TOML or JSON is read with the types of the inputs declared by the parsed program.
     pub fn parse_input(&mut self, input_file_path: PathBuf) -> Result<()> {
         if input_file_path.exists() {
             // Load the input file into the source map.
@@ -135,7 +194,12 @@ impl<'a> Compiler<'a> {
                 .map_err(|e| CompilerError::file_read_error(&input_file_path, e))?;
 
             // Parse and serialize it.
-            let input_ast = leo_parser::parse_input(self.handler, &input_sf.src, input_sf.start_pos)?;
+            let input_ast = match input_file_path.extension().and_then(|extension| extension.to_str()) {
+                Some("toml" | "json") => {
+                    leo_parser::parse_input_data(self.handler, &input_file_path, &input_sf.src, self.ast.as_repr())?
+                }
+                _ => leo_parser::parse_input(self.handler, &input_sf.src, input_sf.start_pos)?,
+            };
             if self.output_options.initial_ast {
                 // Write the input AST snapshot post parsing.
                 if self.output_options.spans_enabled {
@@ -144,7 +208,7 @@ impl<'a> Compiler<'a> {
                     input_ast.to_json_file_without_keys(
                         self.output_directory.clone(),
                         "initial_input_ast.json",
-                        &["span"],
+                        &["span", "id"],
                     )?;
                 }
             }
@@ -154,23 +218,121 @@ impl<'a> Compiler<'a> {
         Ok(())
     }
 
+    /// Checks the values of the parsed input file against the signatures of the functions of the program.
+    pub fn check_input(&self) -> Result<()> {
+        match &self.input_ast {
+            Some(input_ast) => crate::check_input(input_ast, self.ast.as_repr()),
+            None => Ok(()),
+        }
+    }
+
+    /// Desugars async transitions and async functions into transitions with finalize blocks.
+    pub fn async_desugaring_pass(&mut self) -> Result<()> {
+        self.ast = AsyncDesugarer::do_pass((std::mem::take(&mut self.ast), self.handler))?;
+        Ok(())
+    }
+
     /// Runs the symbol table pass.
     pub fn symbol_table_pass(&self) -> Result<SymbolTable> {
         CreateSymbolTable::do_pass((&self.ast, self.handler))
     }
 
     /// Runs the type checker pass.
-    pub fn type_checker_pass(&'a self, symbol_table: SymbolTable) -> Result<SymbolTable> {
-        TypeChecker::do_pass((&self.ast, self.handler, symbol_table))
+    /// Also returns the integers that are implicitly widened, which are made explicit by the implicit widening pass,
+    /// the types of the values matched by match statements, which are used by the match lowering pass,
+    /// and the types of the expressions, which are used by the flattening pass.
+    /// The calls between the functions of the program are stored in the call graph.
+    pub fn type_checker_pass(
+        &mut self,
+        symbol_table: SymbolTable,
+    ) -> Result<(SymbolTable, ImplicitWidenings, MatchTypes, TypeTable)> {
+        let (symbol_table, widenings, match_types, type_table, call_graph) =
+            TypeChecker::do_pass((&self.ast, self.handler, symbol_table, self.language_version))?;
+        self.call_graph = call_graph;
+
+        if self.output_options.type_checked_ast {
+            self.write_type_checked_ast_to_json(&type_table)?;
+        }
+
+        Ok((symbol_table, widenings, match_types, type_table))
+    }
+
+    /// Re-parses and type checks the function `name` from its new `source` text, against the rest of the current program.
+    /// Diagnostics are emitted to the handler and the current AST is left unchanged,
+    /// so that a function can be checked while it is edited without recompiling the whole program.
+    pub fn check_function(&self, name: Symbol, source: &str) -> Result<()> {
+        // Register the fragment in the source map, so that diagnostics point into it.
+        let sf = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom(name.to_string())));
+        let function = leo_parser::parse_function(self.handler, &sf.src, sf.start_pos)?;
+        if function.name() != name {
+            return Err(
+                CompilerError::function_name_does_not_match(function.name(), name, function.identifier.span).into(),
+            );
+        }
+
+        // Only the signatures of the program's items are collected, so this is cheap compared to type checking.
+        let mut symbol_table = self.symbol_table_pass()?;
+        if !symbol_table.replace_fn(name, &function) {
+            return Err(CompilerError::unknown_function(name, function.identifier.span).into());
+        }
+        TypeChecker::check_function(&function, self.handler, symbol_table, self.language_version)?;
+
+        Ok(())
+    }
+
+    /// Runs the finalize validation pass, which checks that the finalize blocks can be executed on chain.
+    pub fn finalize_validation_pass(&self) -> Result<()> {
+        FinalizeValidator::do_pass((&self.ast, self.handler))
+    }
+
+    /// Runs the lint passes, which only emit warnings.
+    /// Implicitly widened integers are only reported if `warn_implicit_widening` is enabled in the output options.
+    pub fn lint_pass(&self, widenings: &ImplicitWidenings) {
+        UnusedChecker::do_pass((&self.ast, self.handler));
+        CommitmentChecker::do_pass((&self.ast, self.handler));
+        self.naming_violations()
+            .iter()
+            .for_each(|violation| self.handler.emit_warning(violation.to_warning()));
+        if self.output_options.warn_implicit_widening {
+            widenings.values().for_each(|widening| {
+                self.handler.emit_warning(LintWarning::implicit_widening(
+                    widening.from,
+                    widening.to,
+                    widening.span,
+                ))
+            });
+        }
+    }
+
+    /// Returns the names in the current AST that do not follow the configured naming conventions,
+    /// each with a suggested name that can be applied with [`Compiler::rename`].
+    pub fn naming_violations(&self) -> Vec<NamingViolation> {
+        NamingChecker::do_pass((&self.ast, &self.output_options.naming))
+    }
+
+    /// Runs the match lowering pass, which lowers each match statement to a chain of conditional statements.
+    pub fn match_lowering_pass(&mut self, types: MatchTypes) {
+        self.ast = MatchLowerer::do_pass((std::mem::take(&mut self.ast), types));
+    }
+
+    /// Runs the implicit widening pass, which inserts a cast for each integer that the type checker implicitly widened.
+    pub fn implicit_widening_pass(&mut self, widenings: ImplicitWidenings) {
+        self.ast = WideningInserter::do_pass((std::mem::take(&mut self.ast), widenings));
     }
 
     /// Runs the loop unrolling pass.
-    pub fn loop_unrolling_pass(&mut self, symbol_table: SymbolTable) -> Result<SymbolTable> {
-        let (ast, symbol_table) = Unroller::do_pass((std::mem::take(&mut self.ast), self.handler, symbol_table))?;
+    /// The types of the nodes in `type_table` are also recorded for their unrolled copies.
+    pub fn loop_unrolling_pass(
+        &mut self,
+        symbol_table: SymbolTable,
+        type_table: &mut TypeTable,
+    ) -> Result<SymbolTable> {
+        let (ast, symbol_table) =
+            Unroller::do_pass((std::mem::take(&mut self.ast), self.handler, symbol_table, type_table))?;
         self.ast = ast;
 
         if self.output_options.unrolled_ast {
-            self.write_ast_to_json("unrolled_ast.json")?;
+            self.write_ast_snapshot("unrolled_ast")?;
         }
 
         Ok(symbol_table)
@@ -182,65 +344,821 @@ impl<'a> Compiler<'a> {
         self.ast = ast;
 
         if self.output_options.ssa_ast {
-            self.write_ast_to_json("ssa_ast.json")?;
+            self.write_ast_snapshot("ssa_ast")?;
         }
 
         Ok(assigner)
     }
 
+    /// Records the operations that can halt at runtime, such as checked arithmetic, division, and assertions.
+    /// This must be run on the type checked AST, before it is transformed by later passes.
+    pub fn halt_analysis_pass(&mut self, symbol_table: &SymbolTable) {
+        self.halt_points = HaltAnalyzer::do_pass((&self.ast, symbol_table));
+    }
+
+    /// Estimates the number of constraints of each function from static weights of its operations.
+    /// This must be run on the type checked AST, before it is transformed by later passes.
+    pub fn cost_estimation_pass(&mut self, symbol_table: &SymbolTable) {
+        self.cost_estimates = CostEstimator::do_pass((&self.ast, symbol_table));
+    }
+
+    /// Runs the range analysis, which warns about arithmetic that can overflow and records the assertions that always hold.
+    /// This must be run on the AST in static single assignment form.
+    pub fn range_analysis_pass(&mut self) {
+        self.proven_asserts = RangeAnalyzer::do_pass((&self.ast, self.handler));
+    }
+
+    /// Removes the assertions that the range analysis proved to always hold.
+    pub fn assert_elimination_pass(&mut self) {
+        self.ast = AssertEliminator::do_pass((std::mem::take(&mut self.ast), &self.proven_asserts));
+    }
+
+    /// Returns the report of the assertions that were removed, with the proof that each always holds,
+    /// so that they can be audited.
+    pub fn proven_asserts_report(&self) -> serde_json::Value {
+        let asserts: Vec<_> = self
+            .proven_asserts
+            .iter()
+            .map(|proven_assert| {
+                let location = with_session_globals(|s| s.source_map.span_to_location(proven_assert.span))
+                    .map(|location| serde_json::json!({ "line": location.line_start, "column": location.col_start }));
+                serde_json::json!({
+                    "function": proven_assert.function.to_string(),
+                    "location": location,
+                    "proof": proven_assert.reason,
+                })
+            })
+            .collect();
+
+        serde_json::json!({ "program": self.program_name, "asserts": asserts })
+    }
+
     /// Runs the flattening pass.
-    pub fn flattening_pass(&mut self, symbol_table: &SymbolTable, assigner: Assigner) -> Result<()> {
-        self.ast = Flattener::do_pass((std::mem::take(&mut self.ast), symbol_table, assigner))?;
+    pub fn flattening_pass(
+        &mut self,
+        symbol_table: &SymbolTable,
+        type_table: &TypeTable,
+        assigner: Assigner,
+    ) -> Result<()> {
+        self.ast = Flattener::do_pass((std::mem::take(&mut self.ast), symbol_table, type_table, assigner))?;
 
         if self.output_options.flattened_ast {
-            self.write_ast_to_json("flattened_ast.json")?;
+            self.write_ast_snapshot("flattened_ast")?;
         }
 
         Ok(())
     }
 
+    /// Runs the canonicalization pass, so that semantically identical programs produce identical artifacts.
+    pub fn canonicalization_pass(&mut self) -> Result<()> {
+        self.ast = Canonicalizer::do_pass(std::mem::take(&mut self.ast));
+
+        if self.output_options.canonicalized_ast {
+            self.write_ast_snapshot("canonicalized_ast")?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the imports that the program does not use, warning about each of them.
+    /// The imports are all kept if a function is annotated `@optimize(none)`, so that the program is compiled as written.
+    /// This must be run on the AST that instructions are generated from.
+    pub fn import_pruning_pass(&mut self) {
+        let unoptimized = self
+            .ast
+            .as_repr()
+            .program_scopes
+            .values()
+            .flat_map(|scope| scope.functions.values())
+            .any(Function::is_unoptimized);
+        if !unoptimized {
+            self.ast = ImportPruner::do_pass((std::mem::take(&mut self.ast), self.handler));
+        }
+    }
+
+    /// Runs `pass`, recording how long it took under `name` in the pass durations.
+    fn timed<T>(&mut self, name: &'static str, pass: impl FnOnce(&mut Self) -> T) -> T {
+        let start = Instant::now();
+        let result = pass(self);
+        self.pass_durations.push((name, start.elapsed()));
+        if self.output_options.dump_all {
+            if let Err(err) = self.write_pass_snapshot(name) {
+                self.handler.emit_err(err);
+            }
+        }
+        result
+    }
+
+    /// Writes the AST after the pass `name` to the directory of the program in the output directory,
+    /// numbered by the position of the pass in the pass durations, in the configured dump format.
+    fn write_pass_snapshot(&self, name: &str) -> Result<()> {
+        let directory = self.output_directory.join(&self.program_name);
+        fs::create_dir_all(&directory).map_err(|e| CompilerError::failed_to_write_pass_snapshot(&directory, e))?;
+        let file_name = format!("{:02}_{}", self.pass_durations.len() - 1, name.replace(' ', "_"));
+
+        match self.output_options.dump_format {
+            DumpFormat::Json => {
+                let json_file_name = format!("{}.json", file_name);
+                if self.output_options.spans_enabled {
+                    self.ast.to_json_file(directory, &json_file_name)?;
+                } else {
+                    self.ast
+                        .to_json_file_without_keys(directory, &json_file_name, &["span", "id"])?;
+                }
+            }
+            DumpFormat::Leo => {
+                let path = directory.join(format!("{}.leo", file_name));
+                fs::write(
+                    &path,
+                    leo_ast::pretty::program_with_config(self.ast.as_repr(), &self.output_options.format),
+                )
+                .map_err(|e| CompilerError::failed_to_write_pass_snapshot(&path, e))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Runs the compiler stages.
     pub fn compiler_stages(&mut self) -> Result<SymbolTable> {
-        let st = self.symbol_table_pass()?;
-        let st = self.type_checker_pass(st)?;
+        let st = self.timed("symbol table", |compiler| compiler.symbol_table_pass())?;
+        let (st, widenings, match_types, mut type_table) =
+            self.timed("type checking", |compiler| compiler.type_checker_pass(st))?;
+
+        self.timed("finalize validation", |compiler| compiler.finalize_validation_pass())?;
+
+        self.timed("linting", |compiler| compiler.lint_pass(&widenings));
+
+        // Match statements are lowered before implicit widening, so that the literals in their patterns are widened.
+        self.timed("match lowering", |compiler| compiler.match_lowering_pass(match_types));
+
+        if self.output_options.list_halt_points {
+            self.timed("halt analysis", |compiler| compiler.halt_analysis_pass(&st));
+        }
+
+        if self.output_options.estimate_costs {
+            self.timed("cost estimation", |compiler| compiler.cost_estimation_pass(&st));
+        }
+
+        self.timed("implicit widening", |compiler| {
+            compiler.implicit_widening_pass(widenings)
+        });
 
         // TODO: Make this pass optional.
-        let st = self.loop_unrolling_pass(st)?;
+        let st = self.timed("loop unrolling", |compiler| {
+            compiler.loop_unrolling_pass(st, &mut type_table)
+        })?;
 
         // TODO: Make this pass optional.
-        let assigner = self.static_single_assignment_pass(&st)?;
+        let assigner = self.timed("static single assignment", |compiler| {
+            compiler.static_single_assignment_pass(&st)
+        })?;
+
+        self.timed("range analysis", |compiler| compiler.range_analysis_pass());
+
+        if !self.output_options.keep_proven_asserts {
+            self.timed("assert elimination", |compiler| compiler.assert_elimination_pass());
+        }
+
+        self.timed("flattening", |compiler| {
+            compiler.flattening_pass(&st, &type_table, assigner)
+        })?;
+
+        self.timed("canonicalization", |compiler| compiler.canonicalization_pass())?;
 
-        self.flattening_pass(&st, assigner)?;
+        if !self.output_options.keep_unused_imports {
+            self.timed("import pruning", |compiler| compiler.import_pruning_pass());
+        }
+
+        self.check_denied_warnings()?;
 
         Ok(st)
     }
 
+    /// Parses and checks the program, without transforming it or generating instructions.
+    /// Errors and warnings are reported to the handler.
+    pub fn check(&mut self) -> Result<SymbolTable> {
+        self.parse_program()?;
+        self.check_stages()
+    }
+
+    /// Parses and checks the program in `program_string` instead of the main file, e.g. the unsaved contents of an editor.
+    /// Errors and warnings are reported to the handler.
+    pub fn check_source(&mut self, program_string: &str, name: FileName) -> Result<SymbolTable> {
+        self.parse_program_from_string(program_string, name)?;
+        self.check_stages()
+    }
+
+    /// Parses and checks the program in `program_string` like [`Compiler::check_source`],
+    /// but returns the types of its expressions even if some of its functions do not type check,
+    /// so that an editor can keep showing them while the program is edited.
+    /// Fails only if the program cannot be parsed or its items conflict. Errors and warnings are reported to the handler.
+    pub fn check_source_partially(&mut self, program_string: &str, name: FileName) -> Result<PartialTypeCheck> {
+        self.parse_program_from_string(program_string, name)?;
+        let st = self.symbol_table_pass()?;
+        let checked = TypeChecker::check_partially(&self.ast, self.handler, st, self.language_version);
+
+        // Lints assume a program that type checks.
+        if !checked.has_errors {
+            self.lint_pass(&checked.widenings);
+        }
+
+        Ok(checked)
+    }
+
+    /// Runs the stages that check the parsed program.
+    fn check_stages(&mut self) -> Result<SymbolTable> {
+        let st = self.symbol_table_pass()?;
+        let (st, widenings, match_types, _) = self.type_checker_pass(st)?;
+        self.finalize_validation_pass()?;
+
+        self.lint_pass(&widenings);
+
+        // Matches are lowered and widened integers are cast, so that the checked program can be interpreted.
+        self.match_lowering_pass(match_types);
+        self.implicit_widening_pass(widenings);
+
+        self.check_denied_warnings()?;
+
+        Ok(st)
+    }
+
+    /// Fails if any warnings were emitted at the `Deny` level.
+    pub fn check_denied_warnings(&self) -> Result<()> {
+        match self.handler.denied_warning_count() {
+            0 => Ok(()),
+            count => Err(CompilerError::warnings_denied(count).into()),
+        }
+    }
+
     /// Returns a compiled Leo program and prints the resulting bytecode.
     // TODO: Remove when code generation is ready to be integrated into the compiler.
     pub fn compile_and_generate_instructions(&mut self) -> Result<(SymbolTable, String)> {
-        self.parse_program()?;
+        self.pass_durations.clear();
+        self.timed("parsing", |compiler| compiler.parse_program())?;
         let symbol_table = self.compiler_stages()?;
-
-        let bytecode = CodeGenerator::do_pass((&self.ast, self.handler))?;
+        let bytecode = self.code_generation_pass()?;
 
         Ok((symbol_table, bytecode))
     }
 
+    /// Returns a compiled Leo program and its instructions like [`Compiler::compile_and_generate_instructions`],
+    /// reusing the artifacts in `cache` if neither the program, the programs it imports, the options it is compiled with,
+    /// nor the compiler changed since they were cached.
+    /// The program is parsed either way, which checks its imports against the import policy.
+    /// Also returns whether the artifacts were reused, in which case no other passes run and no warnings are emitted.
+    /// Note that the spans in a reused symbol table do not point into the current source map.
+    pub fn compile_with_cache(&mut self, cache: &CompilationCache) -> Result<(SymbolTable, String, bool)> {
+        self.pass_durations.clear();
+        let source = fs::read_to_string(&self.main_file_path)
+            .map_err(|e| CompilerError::file_read_error(&self.main_file_path, e))?;
+        self.timed("parsing", |compiler| {
+            let name = FileName::Real(compiler.main_file_path.clone());
+            compiler.parse_program_from_string(&source, name)
+        })?;
+
+        // The header is written into the cached instructions, so a change to its configuration invalidates them.
+        let header = self.generated_header()?.render();
+        // So does a change to the options that decide which instructions are generated, or whether the program compiles,
+        // e.g. the levels of warnings, since a reused program emits no warnings that could be denied.
+        let options = &self.output_options;
+        let mut warning_levels = options.warnings.levels.iter().collect::<Vec<_>>();
+        warning_levels.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let options = format!(
+            "{:?}",
+            (
+                options.keep_unused_imports,
+                options.keep_proven_asserts,
+                &options.naming,
+                &options.import_policy,
+                self.language_version,
+                options.warn_implicit_widening,
+                options.warnings.default_level,
+                warning_levels,
+            )
+        );
+        let mut imports = String::new();
+        import_sources(self.ast.as_repr(), &mut imports)?;
+        let key = CompilationCache::key(
+            &self.program_name,
+            &self.network,
+            &format!("{}{}", header, source),
+            &options,
+            &imports,
+        );
+
+        if let Some(artifacts) = cache.load(&self.program_name, &key) {
+            return Ok((artifacts.symbol_table, artifacts.instructions, true));
+        }
+
+        let symbol_table = self.compiler_stages()?;
+        let bytecode = self.code_generation_pass()?;
+
+        cache.store(&self.program_name, key, symbol_table.clone(), bytecode.clone())?;
+
+        Ok((symbol_table, bytecode, false))
+    }
+
+    /// Runs the code generation pass, recording the instructions generated for each statement.
+    /// This must be run after the compiler stages.
+    pub fn code_generation_pass(&mut self) -> Result<String> {
+        let header = self.generated_header()?;
+        let (bytecode, instruction_counts) = self.timed("code generation", |compiler| {
+            CodeGenerator::do_pass((&compiler.ast, compiler.handler, &header))
+        })?;
+        self.instruction_source_map = InstructionSourceMap::new(&bytecode, &instruction_counts);
+        self.instruction_counts = instruction_counts;
+
+        Ok(bytecode)
+    }
+
+    /// Returns the header configured in the output options for the code generated from the program file.
+    /// The program file is only hashed if the header records its provenance.
+    pub fn generated_header(&self) -> Result<GeneratedHeader> {
+        let config = &self.output_options.header;
+        let source_hash = if config.provenance {
+            self.checksum()?
+        } else {
+            String::new()
+        };
+        Ok(GeneratedHeader::new(
+            config,
+            &self.output_options.format,
+            env!("CARGO_PKG_VERSION"),
+            &source_hash,
+        ))
+    }
+
     /// Returns a compiled Leo program.
     pub fn compile(&mut self) -> Result<SymbolTable> {
-        self.parse_program()?;
+        self.pass_durations.clear();
+        self.timed("parsing", |compiler| compiler.parse_program())?;
         self.compiler_stages()
     }
 
-    /// Writes the AST to a JSON file.
-    fn write_ast_to_json(&self, file_name: &str) -> Result<()> {
-        // Remove `Span`s if they are not enabled.
-        if self.output_options.spans_enabled {
-            self.ast.to_json_file(self.output_directory.clone(), file_name)?;
-        } else {
-            self.ast
-                .to_json_file_without_keys(self.output_directory.clone(), file_name, &["span"])?;
+    /// Returns the ranges of code in the current AST that can never be executed, so that editors can dim them.
+    /// This should be called after type checking, before the AST is transformed by later passes.
+    pub fn dead_code(&self) -> Vec<UnusedCode> {
+        DeadCodeAnalyzer::do_pass(&self.ast)
+    }
+
+    /// Returns a harness that runs the functions of the checked program that are annotated with `@test`.
+    pub fn test_harness(&self) -> Result<TestHarness<'_>> {
+        // Note that parsing enforces that there is exactly one program scope in a file.
+        self.ast
+            .as_repr()
+            .program_scopes
+            .values()
+            .next()
+            .map(TestHarness::new)
+            .ok_or_else(|| CompilerError::no_program_scope().into())
+    }
+
+    /// Calls `function` with the reference interpreter on `inputs`, e.g. `3u8`,
+    /// or on the values of its section of the input file if there are none.
+    /// The returned execution can be exported as a regression test.
+    pub fn interpret(&self, function: Symbol, inputs: &[String]) -> Result<Execution> {
+        self.interpret_with_storage(function, inputs, Box::new(Mappings::default()))
+    }
+
+    /// Calls `function` like [`Compiler::interpret`], with the mappings of the program kept in `storage`.
+    pub fn interpret_with_storage(
+        &self,
+        function: Symbol,
+        inputs: &[String],
+        storage: Box<dyn StateStorage>,
+    ) -> Result<Execution> {
+        self.run_interpreter(function, inputs, storage, None)
+    }
+
+    /// Calls `function` like [`Compiler::interpret`], pausing whenever `debugger` asks to.
+    pub fn debug<'b>(&'b self, function: Symbol, inputs: &[String], debugger: Debugger<'b>) -> Result<Execution> {
+        self.run_interpreter(function, inputs, Box::new(Mappings::default()), Some(debugger))
+    }
+
+    /// Calls `function` with the reference interpreter, with the mappings kept in `storage`,
+    /// and with `debugger` attached, if any.
+    fn run_interpreter<'b>(
+        &'b self,
+        function: Symbol,
+        inputs: &[String],
+        storage: Box<dyn StateStorage>,
+        debugger: Option<Debugger<'b>>,
+    ) -> Result<Execution> {
+        // Note that parsing enforces that there is exactly one program scope in a file.
+        let scope = self
+            .ast
+            .as_repr()
+            .program_scopes
+            .values()
+            .next()
+            .ok_or_else(|| InterpreterError::unknown_function(function, Span::default()))?;
+
+        let definitions = match inputs.is_empty() {
+            true => {
+                let mut definitions = self
+                    .input_ast
+                    .iter()
+                    .flat_map(|input_ast| input_ast.sections.iter())
+                    .find(|section| section.name == function)
+                    .ok_or_else(|| InterpreterError::missing_input_section(function))?
+                    .definitions
+                    .clone();
+                // Witnesses are the last inputs of a function, wherever the input file defines them.
+                definitions.sort_by_key(|definition| definition.mode == Mode::Witness);
+                definitions
+            }
+            false => {
+                let declaration = scope
+                    .functions
+                    .values()
+                    .find(|declaration| declaration.name() == function)
+                    .ok_or_else(|| InterpreterError::unknown_function(function, Span::default()))?;
+                if declaration.input.len() != inputs.len() {
+                    return Err(InterpreterError::incorrect_num_args(
+                        function,
+                        declaration.input.len(),
+                        inputs.len(),
+                        Span::default(),
+                    )
+                    .into());
+                }
+
+                // Parse each input as the section of an input file, so that it is written as in one.
+                // A value that does not parse to exactly its own definition, e.g. `1u8; b: u8 = 2u8`, is rejected.
+                let mut definitions = Vec::with_capacity(inputs.len());
+                for (input, value) in declaration.input.iter().zip(inputs) {
+                    let section = format!("[{}]\n{}\n", function, input_definition(input, value));
+                    let input_sf =
+                        with_session_globals(|s| s.source_map.new_source(&section, FileName::Custom("inputs".into())));
+                    let input_ast = leo_parser::parse_input(self.handler, &input_sf.src, input_sf.start_pos)?;
+                    let mut parsed: Vec<_> = input_ast
+                        .sections
+                        .into_iter()
+                        .flat_map(|section| section.definitions)
+                        .collect();
+                    match (parsed.pop(), parsed.is_empty()) {
+                        (Some(definition), true) if definition.name.name == input.identifier().name => {
+                            definitions.push(definition)
+                        }
+                        _ => return Err(InputError::invalid_input_value(input.identifier(), value).into()),
+                    }
+                }
+                definitions
+            }
+        };
+
+        let arguments = definitions
+            .iter()
+            .map(|definition| {
+                let value = evaluate_constant(&definition.value)
+                    .ok_or_else(|| InterpreterError::input_not_constant(definition.name, definition.value.span()))?;
+                // A record is run on from its members rather than its ciphertext, so they are checked to be those of its type.
+                let record = match &definition.type_ {
+                    Type::Identifier(identifier) => scope
+                        .structs
+                        .values()
+                        .find(|struct_| struct_.is_record && struct_.identifier.name == identifier.name),
+                    _ => None,
+                };
+                match record {
+                    Some(record) => Ok(RecordValue::from_value(&value, record, definition.value.span())?.into_value()),
+                    None => Ok(value),
+                }
+            })
+            .collect::<Result<_>>()?;
+        let interpreter = Interpreter::with_storage(scope, storage);
+        match debugger {
+            Some(debugger) => interpreter.with_debugger(debugger),
+            None => interpreter,
+        }
+        .execute(function, arguments, Span::default())
+    }
+
+    /// Inlines the variable defined at `target` into each of its uses.
+    /// The program is left unchanged if the refactored program does not type check.
+    pub fn inline_variable(&mut self, target: Span) -> Result<()> {
+        let ast = VariableInliner::do_pass((self.ast.clone(), target))?;
+        self.apply_refactoring(ast)
+    }
+
+    /// Extracts the statements covered by `selection` into a new function called `name`.
+    /// The program is left unchanged if the refactored program does not type check.
+    pub fn extract_function(&mut self, selection: Span, name: Symbol) -> Result<()> {
+        let ast = FunctionExtractor::do_pass((self.ast.clone(), selection, name))?;
+        self.apply_refactoring(ast)
+    }
+
+    /// Renames the function, struct, record, or variable whose name at its declaration encloses `target` to `name`,
+    /// along with each of its uses.
+    /// The program is left unchanged if the refactored program does not type check.
+    pub fn rename(&mut self, target: Span, name: Symbol) -> Result<()> {
+        let ast = Renamer::do_pass((self.ast.clone(), target, name))?;
+        self.apply_refactoring(ast)
+    }
+
+    /// Renames each name that does not follow the configured naming conventions to its suggested name,
+    /// skipping suggestions that are not machine-applicable.
+    pub fn fix_naming_violations(&mut self) -> Result<()> {
+        // Renaming preserves the spans of declarations, so each violation can still be found after earlier renames.
+        for violation in self.naming_violations() {
+            if violation.is_machine_applicable() {
+                self.rename(violation.span, violation.suggestion)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Type checks a refactored AST and, if it is valid, replaces the current AST with it.
+    fn apply_refactoring(&mut self, ast: Ast) -> Result<()> {
+        let symbol_table = CreateSymbolTable::do_pass((&ast, self.handler))?;
+        TypeChecker::do_pass((&ast, self.handler, symbol_table, self.language_version))?;
+        self.ast = ast;
+        Ok(())
+    }
+
+    /// Writes the type checked AST to a JSON file, along with the types of its expressions keyed by node id.
+    /// Node ids are kept even if spans are not enabled, so that the types can be matched with their expressions.
+    fn write_type_checked_ast_to_json(&self, type_table: &TypeTable) -> Result<()> {
+        let ast = match self.output_options.spans_enabled {
+            true => self.ast.to_json_value()?,
+            false => self.ast.to_json_value_without_keys(&["span"])?,
+        };
+        let json = serde_json::json!({ "ast": ast, "types": type_table });
+
+        let path = self.output_directory.join("type_checked_ast.json");
+        let file = std::fs::File::create(&path).map_err(|e| AstError::failed_to_create_ast_json_file(&path, &e))?;
+        let writer = std::io::BufWriter::new(file);
+        Ok(serde_json::to_writer_pretty(writer, &json)
+            .map_err(|e| AstError::failed_to_write_ast_to_json_file(&path, &e))?)
+    }
+
+    /// Writes the AST to `<name>.json`, or to `<name>.bin` in the binary format.
+    fn write_ast_snapshot(&self, name: &str) -> Result<()> {
+        let output_directory = self.output_directory.clone();
+        match self.output_options.ast_format {
+            // The binary format is read back by tools rather than diffed, so it always keeps spans and node ids.
+            AstFormat::Binary => self.ast.to_binary_file(output_directory, &format!("{}.bin", name))?,
+            // Remove `Span`s and node ids if spans are not enabled, since both depend on the position of a node.
+            AstFormat::Json if self.output_options.spans_enabled => {
+                self.ast.to_json_file(output_directory, &format!("{}.json", name))?
+            }
+            AstFormat::Json => {
+                self.ast
+                    .to_json_file_without_keys(output_directory, &format!("{}.json", name), &["span", "id"])?
+            }
         }
         Ok(())
     }
 }
+
+/// Fails if a program imported by `program`, directly or through its imports, does not declare the program it is
+/// imported as, e.g. if `imports/foo.leo` declares `bar.aleo`.
+fn check_import_names(program: &Program) -> Result<()> {
+    for (name, import) in program.imports.iter() {
+        // Note that parsing enforces that there is exactly one program scope in a file.
+        let declared = import
+            .program_scopes
+            .keys()
+            .next()
+            .map(|program_id| program_id.name.name);
+        if let (Some(declared), Some(source)) = (declared, &import.import_source) {
+            if declared != name.name {
+                return Err(
+                    CompilerError::imported_program_name_mismatch(name.name, declared, source, name.span).into(),
+                );
+            }
+        }
+        check_import_names(import)?;
+    }
+    Ok(())
+}
+
+/// Appends the path and the contents of the file of each program that `program` imports, transitively,
+/// so that a change to any of them invalidates the cached artifacts of the program.
+fn import_sources(program: &Program, sources: &mut String) -> Result<()> {
+    for import in program.imports.values() {
+        if let Some(source) = &import.import_source {
+            let contents =
+                fs::read_to_string(&source.path).map_err(|e| CompilerError::file_read_error(&source.path, e))?;
+            sources.push_str(&format!("{}\0{}\0", source.path.display(), contents));
+        }
+        import_sources(import, sources)?;
+    }
+    Ok(())
+}
+
+/// Fails if `program` imports a program that `policy` does not allow, either directly or through the `importers`,
+/// which are the imports that lead to `program`, starting from the main program.
+fn check_import_policy(policy: &ImportPolicy, program: &Program, importers: &mut Vec<Symbol>) -> Result<()> {
+    for (name, import) in program.imports.iter() {
+        if let Some(reason) = policy.violation(&name.name.to_string()) {
+            let through = match importers.is_empty() {
+                true => String::new(),
+                false => format!(
+                    " through {}",
+                    importers
+                        .iter()
+                        .map(|importer| format!("`{importer}.leo`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            };
+            return Err(CompilerError::import_not_allowed(name.name, through, reason, name.span).into());
+        }
+        importers.push(name.name);
+        check_import_policy(policy, import, importers)?;
+        importers.pop();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    const PROGRAM: &str = "program test.aleo {
+    function helper(a: u8) -> u8 {
+        return a + 1u8;
+    }
+
+    transition main(a: u8) -> u8 {
+        return helper(a);
+    }
+}
+";
+
+    /// Parses `PROGRAM`, then checks `source` as the new definition of the function `name`,
+    /// returning the message of the error if it fails.
+    fn check_function(name: &str, source: &str) -> std::result::Result<(), String> {
+        create_session_if_not_set_then(|_| {
+            let (handler, _) = Handler::new_with_buf();
+            let mut compiler = Compiler::new(
+                "test".to_string(),
+                "aleo".to_string(),
+                &handler,
+                PathBuf::from("test.leo"),
+                PathBuf::new(),
+                None,
+            );
+            compiler
+                .parse_program_from_string(PROGRAM, FileName::Custom("test".to_string()))
+                .unwrap();
+            compiler
+                .check_function(Symbol::intern(name), source)
+                .map_err(|err| err.to_string())
+        })
+    }
+
+    /// Compiles `source`, returning its instructions.
+    fn compile(source: &str) -> String {
+        create_session_if_not_set_then(|_| {
+            let (handler, _) = Handler::new_with_buf();
+            let mut compiler = Compiler::new(
+                "test".to_string(),
+                "aleo".to_string(),
+                &handler,
+                PathBuf::from("test.leo"),
+                PathBuf::new(),
+                None,
+            );
+            compiler
+                .parse_program_from_string(source, FileName::Custom("test".to_string()))
+                .unwrap();
+            compiler.compiler_stages().unwrap();
+            compiler.code_generation_pass().unwrap()
+        })
+    }
+
+    #[test]
+    fn wrapping_unary_operations_are_widened_after_they_are_applied() {
+        // `!0u8` is `255u8`, which is widened to `255u16` rather than flipping the bits of `0u16`.
+        let bytecode = compile("program test.aleo { transition main(a: u8) -> u16 { let b: u16 = !a; return b; } }");
+        assert!(bytecode.contains("    not r0 into r1;\n    cast r1 into r2 as u16;\n"));
+
+        // The absolute value of `-128i8` wraps to `-128i8`, which is widened to `-128i16` rather than being `128i16`.
+        let bytecode =
+            compile("program test.aleo { transition main(a: i8) -> i16 { let b: i16 = a.abs_wrapped(); return b; } }");
+        assert!(bytecode.contains("    abs.w r0 into r1;\n    cast r1 into r2 as i16;\n"));
+    }
+
+    #[test]
+    fn edited_function_is_checked_against_the_other_signatures() {
+        assert_eq!(
+            check_function("main", "transition main(a: u8) -> u8 { return helper(helper(a)); }"),
+            Ok(())
+        );
+        assert!(check_function("main", "transition main(a: u8) -> u8 { return helper(true); }").is_err());
+    }
+
+    #[test]
+    fn edited_signature_replaces_the_old_one() {
+        // The return type is checked against the edited signature rather than the one in the program.
+        assert_eq!(
+            check_function("helper", "function helper(a: u8, b: u16) -> u16 { return b; }"),
+            Ok(())
+        );
+        assert!(check_function("helper", "function helper(a: u8) -> u8 { return 1u16; }").is_err());
+    }
+
+    #[test]
+    fn edited_function_must_be_the_named_one() {
+        let error = check_function("main", "transition other(a: u8) -> u8 { return a; }").unwrap_err();
+        assert!(error.contains("Expected a definition of the function `main`, but found `other`."));
+        let error = check_function("other", "transition other(a: u8) -> u8 { return a; }").unwrap_err();
+        assert!(error.contains("The program does not contain a function named `other`."));
+    }
+
+    #[test]
+    fn unrolled_iterations_have_unique_node_ids() {
+        create_session_if_not_set_then(|_| {
+            let (handler, _) = Handler::new_with_buf();
+            let mut compiler = Compiler::new(
+                "test".to_string(),
+                "aleo".to_string(),
+                &handler,
+                PathBuf::from("test.leo"),
+                PathBuf::new(),
+                None,
+            );
+            let source = "program test.aleo {
+    transition main(a: u8) -> u8 {
+        let b: u8 = a;
+        for i: u8 in 0u8..2u8 {
+            for j: u8 in 0u8..3u8 {
+                b = b + i * j;
+            }
+        }
+        return b;
+    }
+}
+";
+            compiler
+                .parse_program_from_string(source, FileName::Custom("test".to_string()))
+                .unwrap();
+            let st = compiler.symbol_table_pass().unwrap();
+            let (st, widenings, match_types, mut type_table) = compiler.type_checker_pass(st).unwrap();
+            compiler.match_lowering_pass(match_types);
+            compiler.implicit_widening_pass(widenings);
+            compiler.loop_unrolling_pass(st, &mut type_table).unwrap();
+
+            let mut ids = compiler.ast.as_repr().node_ids();
+            let count = ids.len();
+            ids.sort_unstable();
+            ids.dedup();
+            assert_eq!(ids.len(), count);
+        })
+    }
+
+    #[test]
+    fn extracted_function_has_new_node_ids() {
+        create_session_if_not_set_then(|_| {
+            let (handler, _) = Handler::new_with_buf();
+            let mut compiler = Compiler::new(
+                "test".to_string(),
+                "aleo".to_string(),
+                &handler,
+                PathBuf::from("test.leo"),
+                PathBuf::new(),
+                None,
+            );
+            let source = "program test.aleo {
+    transition main(a: u8, b: u8) -> u8 {
+        let c: u8 = a + b;
+        let d: u8 = c * 2u8;
+        return d + c;
+    }
+}
+";
+            compiler
+                .parse_program_from_string(source, FileName::Custom("test".to_string()))
+                .unwrap();
+            let max_id = compiler.ast.as_repr().max_node_id();
+            let main = compiler
+                .ast
+                .as_repr()
+                .program_scopes
+                .values()
+                .next()
+                .unwrap()
+                .functions
+                .values()
+                .next()
+                .unwrap();
+            let selection = main.block.statements[1].span();
+            compiler
+                .extract_function(selection, Symbol::intern("extracted"))
+                .unwrap();
+
+            // The new function and the call to it are numbered after the nodes of the program.
+            let mut ids = compiler.ast.as_repr().node_ids();
+            let count = ids.len();
+            ids.sort_unstable();
+            ids.dedup();
+            assert_eq!(ids.len(), count);
+            assert!(compiler.ast.as_repr().max_node_id() > max_id);
+            assert!(!ids.contains(&0));
+        })
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The search path that the programs named by `import` statements are looked for in.
+
+use leo_ast::{Identifier, ImportSource};
+use leo_errors::{CompilerError, Result};
+use leo_parser::ImportResolver;
+use leo_span::Symbol;
+
+use indexmap::IndexMap;
+use std::path::{Path, PathBuf};
+
+/// The name of the directory of a package that holds the Leo files of the programs it imports.
+pub const IMPORTS_DIRECTORY: &str = "imports";
+
+/// The directory in the home directory of the user that caches the files of programs, for any package to import.
+pub const REGISTRY_DIRECTORY: &str = ".leo/registry";
+
+/// The directories that the files of imported programs are looked for in, in order.
+/// `import foo.leo;` resolves to the first `foo.leo` found in them, and `import foo.aleo;` to the first `foo.aleo`.
+/// The Leo files of programs imported from other packages, e.g. the members of a workspace, are found before them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportSearchPath {
+    directories: Vec<PathBuf>,
+    programs: IndexMap<Symbol, PathBuf>,
+}
+
+impl ImportSearchPath {
+    /// Returns a search path of `directories`, in the order they are searched in.
+    pub fn new(directories: Vec<PathBuf>) -> Self {
+        Self {
+            directories,
+            programs: IndexMap::new(),
+        }
+    }
+
+    /// Resolves `import <name>.leo;` to the Leo `file` of a program of another package.
+    pub fn with_program(mut self, name: Symbol, file: PathBuf) -> Self {
+        self.programs.insert(name, file);
+        self
+    }
+
+    /// Returns the search path of the package in the current directory: its `imports/` directory,
+    /// then `directories`, and then the registry in the home directory, if there is one.
+    pub fn for_package(directories: Vec<PathBuf>) -> Self {
+        let mut search_path = vec![PathBuf::from(IMPORTS_DIRECTORY)];
+        search_path.extend(directories);
+        search_path.extend(registry_directory());
+        Self::new(search_path)
+    }
+
+    /// Returns the directories, in the order they are searched in.
+    pub fn directories(&self) -> &[PathBuf] {
+        &self.directories
+    }
+}
+
+impl Default for ImportSearchPath {
+    /// Returns the search path of the package in the current directory, without additional directories.
+    fn default() -> Self {
+        Self::for_package(Vec::new())
+    }
+}
+
+impl ImportResolver for ImportSearchPath {
+    fn resolve(&self, name: &Identifier) -> Result<ImportSource> {
+        self.find(name, "leo")
+    }
+
+    fn resolve_bytecode(&self, name: &Identifier) -> Result<ImportSource> {
+        self.find(name, "aleo")
+    }
+}
+
+impl ImportSearchPath {
+    /// Returns the first file of the program `name` with the `extension` in the search path.
+    fn find(&self, name: &Identifier, extension: &str) -> Result<ImportSource> {
+        if let (Some(file), "leo") = (self.programs.get(&name.name), extension) {
+            return Ok(ImportSource {
+                path: file.clone(),
+                directory: file.parent().map(Path::to_path_buf).unwrap_or_default(),
+            });
+        }
+
+        let file_name = format!("{}.{}", name.name, extension);
+        self.directories
+            .iter()
+            .map(|directory| ImportSource {
+                path: directory.join(&file_name),
+                directory: directory.clone(),
+            })
+            .find(|source| source.path.is_file())
+            .ok_or_else(|| {
+                let directories = self
+                    .directories
+                    .iter()
+                    .map(|directory| format!("`{}`", directory.display()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                CompilerError::import_not_in_search_path(file_name, directories, name.span).into()
+            })
+    }
+}
+
+/// Returns the registry in the home directory of the user, if the home directory is known.
+fn registry_directory() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+    Some(PathBuf::from(home).join(REGISTRY_DIRECTORY))
+}
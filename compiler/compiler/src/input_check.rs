@@ -0,0 +1,181 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks the values of an input file against the signatures of the functions they are given for,
+//! so that a mismatched input is reported at its definition rather than when the program is run.
+
+use leo_ast::{Expression, Function, Identifier, InputAst, Literal, Mode, Node, Program, Struct, Type, UnaryOperation};
+use leo_errors::{InputError, Result};
+use leo_span::{sym, Symbol};
+
+use indexmap::IndexMap;
+
+/// Checks that the values of `input_ast` have the types and modes of the inputs of the functions of `program` they are given for,
+/// including the members of structs and records, and that each of those functions is given all of its inputs.
+/// Sections for functions that the program does not declare are not checked.
+pub fn check_input(input_ast: &InputAst, program: &Program) -> Result<()> {
+    let scopes = program.program_scopes.values().chain(
+        program
+            .imports
+            .values()
+            .flat_map(|import| import.program_scopes.values()),
+    );
+    let mut functions: IndexMap<Symbol, &Function> = IndexMap::new();
+    let mut structs: IndexMap<Symbol, &Struct> = IndexMap::new();
+    for scope in scopes {
+        for function in scope.functions.values() {
+            functions.entry(function.name()).or_insert(function);
+        }
+        for struct_ in scope.structs.values() {
+            structs.entry(struct_.identifier.name).or_insert(struct_);
+        }
+    }
+
+    for section in input_ast.sections.iter() {
+        let function = match functions.get(&section.name) {
+            Some(function) => function,
+            None => continue,
+        };
+        for definition in section.definitions.iter() {
+            let input = function
+                .input
+                .iter()
+                .find(|input| input.identifier().name == definition.name.name)
+                .ok_or_else(|| InputError::unknown_function_input(section.name, definition.name, definition.span))?;
+            let expected = input.type_();
+            if !definition.type_.eq_flat(&expected) {
+                return Err(InputError::input_type_mismatch(
+                    definition.name,
+                    &expected,
+                    &definition.type_,
+                    definition.span,
+                )
+                .into());
+            }
+            // An input file may leave out the mode of an input, but may not give it another one.
+            if definition.mode != Mode::None && visibility(definition.mode) != visibility(input.mode()) {
+                return Err(InputError::input_mode_mismatch(
+                    definition.name,
+                    visibility(input.mode()),
+                    definition.mode,
+                    definition.span,
+                )
+                .into());
+            }
+            check_value(&definition.name.to_string(), &expected, &definition.value, &structs)?;
+        }
+        if let Some(input) = function.input.iter().find(|input| {
+            !section
+                .definitions
+                .iter()
+                .any(|definition| definition.name.name == input.identifier().name)
+        }) {
+            return Err(InputError::missing_function_input(section.name, input.identifier(), section.span).into());
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `value`, the value of the input or member `name`, has type `expected`.
+/// Only literals, negated literals and struct expressions are checked, since other expressions are not valid inputs.
+fn check_value(name: &str, expected: &Type, value: &Expression, structs: &IndexMap<Symbol, &Struct>) -> Result<()> {
+    match value {
+        Expression::Literal(literal) => {
+            let found = literal_type(literal);
+            match found.eq_flat(expected) {
+                true => Ok(()),
+                false => Err(InputError::input_type_mismatch(name, expected, found, literal.span()).into()),
+            }
+        }
+        Expression::Unary(unary) if unary.op == UnaryOperation::Negate => {
+            check_value(name, expected, &unary.receiver, structs)
+        }
+        Expression::Struct(struct_expression) => {
+            let found = Type::Identifier(struct_expression.name);
+            let struct_ = match expected {
+                Type::Identifier(identifier) if identifier.name == struct_expression.name.name => {
+                    // The members of a struct whose declaration is not known, e.g. of an import that was not resolved, are not checked.
+                    match structs.get(&identifier.name) {
+                        Some(struct_) => struct_,
+                        None => return Ok(()),
+                    }
+                }
+                _ => return Err(InputError::input_type_mismatch(name, expected, found, struct_expression.span).into()),
+            };
+            for initializer in struct_expression.members.iter() {
+                let member_name = format!("{}.{}", name, initializer.identifier);
+                // The nonce of a record is not declared, but is given to spend a record that was created before.
+                let member_type = match struct_
+                    .members
+                    .iter()
+                    .find(|member| member.name() == initializer.identifier.name)
+                {
+                    Some(member) => member.type_.clone(),
+                    None if struct_.is_record && initializer.identifier.name == sym::_nonce => Type::Group,
+                    None => {
+                        return Err(InputError::unknown_member_input(
+                            member_name,
+                            struct_.identifier,
+                            initializer.identifier.span,
+                        )
+                        .into())
+                    }
+                };
+                if let Some(expression) = &initializer.expression {
+                    check_value(&member_name, &member_type, expression, structs)?;
+                }
+            }
+            if let Some(member) = struct_.members.iter().find(|member| {
+                !struct_expression
+                    .members
+                    .iter()
+                    .any(|initializer| initializer.identifier.name == member.name())
+            }) {
+                return Err(InputError::missing_member_input(
+                    name,
+                    Identifier::new(member.name(), Default::default()),
+                    struct_expression.span,
+                )
+                .into());
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the visibility of an input of mode `mode`: inputs without a mode are private,
+/// and so are witnesses, which the prover supplies as private inputs.
+fn visibility(mode: Mode) -> Mode {
+    match mode {
+        Mode::None | Mode::Witness => Mode::Private,
+        mode => mode,
+    }
+}
+
+/// Returns the type of `literal`.
+fn literal_type(literal: &Literal) -> Type {
+    match literal {
+        Literal::Address(..) => Type::Address,
+        Literal::Boolean(..) => Type::Boolean,
+        Literal::Field(..) => Type::Field,
+        Literal::Group(..) => Type::Group,
+        Literal::Integer(integer_type, ..) => Type::Integer(*integer_type),
+        Literal::Scalar(..) => Type::Scalar,
+        Literal::Signature(..) => Type::Signature,
+        Literal::String(..) => Type::String,
+    }
+}
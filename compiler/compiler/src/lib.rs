@@ -19,11 +19,24 @@
 #![allow(clippy::upper_case_acronyms)]
 #![doc = include_str!("../README.md")]
 
+mod cache;
+pub use cache::*;
+
 mod compiler;
 pub use compiler::*;
 
+mod imports;
+pub use imports::*;
+
+mod input_check;
+pub use input_check::*;
+
 mod options;
 pub use options::*;
 
-#[cfg(test)]
-mod test;
+mod repl;
+pub use repl::*;
+
+mod test_harness;
+pub use test_harness::*;
+
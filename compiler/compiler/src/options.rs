@@ -14,18 +14,145 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::ImportSearchPath;
+
+use leo_ast::{FormatConfig, LanguageVersion};
+use leo_errors::emitter::WarningConfig;
+pub use leo_passes::{HeaderConfig, NamingConfig, NamingConvention};
+
+use std::str::FromStr;
+
 #[derive(Clone, Default)]
 pub struct OutputOptions {
     /// Whether spans are enabled in the output ASTs.
     pub spans_enabled: bool,
+    /// The format of the AST snapshots written after each stage.
+    pub ast_format: AstFormat,
     /// If enabled writes the AST after parsing.
     pub initial_ast: bool,
     /// If enabled writes the input AST after parsing.
     pub initial_input_ast: bool,
+    /// If enabled writes the AST after type checking, along with the types of its expressions.
+    pub type_checked_ast: bool,
     /// If enabled writes the AST after loop unrolling.
     pub unrolled_ast: bool,
     /// If enabled writes the AST after static single assignment.
     pub ssa_ast: bool,
     /// If enabled writes the AST after flattening.
     pub flattened_ast: bool,
+    /// If enabled writes the AST after canonicalization.
+    pub canonicalized_ast: bool,
+    /// If enabled writes the AST after every pass to `<program>/NN_<pass>.json` or `<program>/NN_<pass>.leo`, numbered in the order the passes ran,
+    /// so that a miscompilation can be bisected to the pass that introduced it.
+    pub dump_all: bool,
+    /// The format in which the AST is written after every pass, if `dump_all` is enabled.
+    pub dump_format: DumpFormat,
+    /// The levels at which warnings are reported.
+    pub warnings: WarningConfig,
+    /// The number of errors after which further errors are suppressed and compilation is aborted, if any.
+    pub error_limit: Option<usize>,
+    /// The naming conventions enforced by the naming lint.
+    pub naming: NamingConfig,
+    /// The license, notice and provenance written at the top of the generated Aleo program.
+    pub header: HeaderConfig,
+    /// The layout of the Leo source written by `dump_all` and of the comments in the generated Aleo program.
+    pub format: FormatConfig,
+    /// The programs that the program may import, directly or through other imports.
+    pub import_policy: ImportPolicy,
+    /// The directories that the Leo files of imported programs are looked for in.
+    pub import_search_path: ImportSearchPath,
+    /// The version of Leo that the program is written for, if it is configured.
+    /// A `// leo:version` comment in the program takes precedence over it, and the current version is the default.
+    pub language_version: Option<LanguageVersion>,
+    /// If enabled records the operations that can halt at runtime, after type checking.
+    pub list_halt_points: bool,
+    /// If enabled estimates the number of constraints of each function, after type checking.
+    pub estimate_costs: bool,
+    /// If enabled keeps the imports that the program does not use, instead of removing them before code generation.
+    pub keep_unused_imports: bool,
+    /// If enabled keeps the assertions that are proven to always hold, instead of removing them before code generation.
+    pub keep_proven_asserts: bool,
+    /// If enabled warns about each integer that is implicitly widened, for projects that prefer explicit types.
+    pub warn_implicit_widening: bool,
+}
+
+/// The programs that a package may import, configured by `allowed_imports` and `denied_imports` in `program.json`.
+/// Programs are named without their `.aleo` or `.leo` extension.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportPolicy {
+    /// The only programs that may be imported, if the package restricts its imports to a list.
+    pub allowed: Option<Vec<String>>,
+    /// The programs that may not be imported, even if they are allowed.
+    pub denied: Vec<String>,
+}
+
+impl ImportPolicy {
+    /// Returns the name of a program without its `.aleo` or `.leo` extension, as it is listed in the policy.
+    pub fn program_name(program: &str) -> &str {
+        program
+            .strip_suffix(".aleo")
+            .or_else(|| program.strip_suffix(".leo"))
+            .unwrap_or(program)
+    }
+
+    /// Returns why `program` may not be imported, or `None` if it may.
+    pub fn violation(&self, program: &str) -> Option<&'static str> {
+        let program = Self::program_name(program);
+        if self.denied.iter().any(|denied| Self::program_name(denied) == program) {
+            Some("it is listed in `denied_imports`")
+        } else if let Some(allowed) = &self.allowed {
+            match allowed.iter().any(|allowed| Self::program_name(allowed) == program) {
+                true => None,
+                false => Some("it is not listed in `allowed_imports`"),
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The format of the AST snapshots written after each stage.
+/// The input AST and the type checked AST, which is written with the types of its expressions, are always JSON.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AstFormat {
+    /// The AST is written as JSON, to `<stage>_ast.json`.
+    #[default]
+    Json,
+    /// The AST is written in the compact binary format of `leo_ast`, to `<stage>_ast.bin`,
+    /// which is much smaller for large programs, e.g. after loops are unrolled.
+    Binary,
+}
+
+impl FromStr for AstFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "binary" => Ok(Self::Binary),
+            _ => Err(format!("unknown AST format `{s}`, expected `json` or `binary`")),
+        }
+    }
+}
+
+/// The format in which the AST is written after every pass.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DumpFormat {
+    /// The AST is written as JSON.
+    #[default]
+    Json,
+    /// The program is written as Leo source, which is easier to read, e.g. for the flattened AST.
+    Leo,
+}
+
+impl FromStr for DumpFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "leo" => Ok(Self::Leo),
+            _ => Err(format!("unknown dump format `{s}`, expected `json` or `leo`")),
+        }
+    }
 }
@@ -0,0 +1,245 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    Expression, ExpressionReconstructor, Identifier, LanguageVersion, NodeBuilder, ProgramId, ProgramScope, Statement,
+    Value,
+};
+use leo_errors::emitter::Handler;
+use leo_errors::{CompilerError, Result};
+use leo_interpreter::{evaluate_constant, type_to_source, value_to_source, Interpreter, Mappings};
+use leo_passes::{SymbolTable, TypeChecker, VariableType, WideningInserter};
+use leo_span::source_map::FileName;
+use leo_span::symbol::with_session_globals;
+use leo_span::{sym, Span, Symbol};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// Evaluates Leo expressions and definitions one line at a time, e.g. as they are entered at a prompt.
+/// Each line is type checked and then evaluated with the reference interpreter,
+/// and the variables that it defines can be used by the lines after it.
+///
+/// Lines can call the functions of the program that the `Repl` is created with, and the updates that their
+/// finalize blocks make to its mappings are kept for the lines after them.
+pub struct Repl {
+    /// The program scope that lines are evaluated in, which declares nothing unless a program is loaded.
+    scope: ProgramScope,
+    /// The types of the items of the program, without the variables defined at the prompt.
+    program_symbol_table: SymbolTable,
+    /// The types of the items of the program and of the variables defined so far.
+    symbol_table: SymbolTable,
+    /// The values of the variables defined so far.
+    variables: IndexMap<Symbol, Value>,
+    /// The entries of the program's mappings, as updated by the calls made so far.
+    mappings: Mappings,
+    /// The version of Leo that lines are checked against.
+    language_version: LanguageVersion,
+    /// The number of lines read so far, which is used to name each line in diagnostics.
+    lines: usize,
+}
+
+/// The state of a [`Repl`] as it is saved to disk, with each value written as Leo source code.
+#[derive(Debug, Serialize, Deserialize)]
+struct Session {
+    /// The program that the session ran against, e.g. `token.aleo`.
+    program: String,
+    /// The definitions of the variables in the order they were defined in, e.g. `let a: u8 = 3u8`.
+    definitions: Vec<String>,
+    /// The entries of the mappings, as the name of their mapping and the source code of their key and value.
+    mappings: Vec<(String, String, String)>,
+}
+
+impl Repl {
+    /// Returns a new `Repl` without any variables or program, which checks lines against `language_version`.
+    pub fn new(language_version: LanguageVersion) -> Self {
+        let scope = ProgramScope {
+            program_id: ProgramId {
+                name: Identifier::new(Symbol::intern("repl"), Default::default()),
+                network: Identifier::new(sym::aleo, Default::default()),
+            },
+            structs: IndexMap::new(),
+            mappings: IndexMap::new(),
+            functions: IndexMap::new(),
+            span: Span::default(),
+        };
+        Self::with_program(language_version, scope, SymbolTable::default())
+    }
+
+    /// Returns a new `Repl` without any variables, whose lines can call the functions of `scope` and use its structs.
+    /// `symbol_table` holds the types of its items, as returned by checking the program, and its mappings start out empty.
+    pub fn with_program(language_version: LanguageVersion, scope: ProgramScope, symbol_table: SymbolTable) -> Self {
+        Self {
+            scope,
+            program_symbol_table: symbol_table.clone(),
+            symbol_table,
+            variables: IndexMap::new(),
+            mappings: Mappings::default(),
+            language_version,
+            lines: 0,
+        }
+    }
+
+    /// Returns the id of the program that lines are evaluated in, e.g. `token.aleo`.
+    pub fn program_id(&self) -> &ProgramId {
+        &self.scope.program_id
+    }
+
+    /// Evaluates `line`, which is either an expression, e.g. `255u8 + 1u8`, or a definition, e.g. `let a: u8 = 255u8;`.
+    /// Returns the value of the expression, or the value of the defined variable.
+    /// Diagnostics are emitted to `handler`, and a line that fails does not define anything.
+    pub fn evaluate(&mut self, handler: &Handler, line: &str) -> Result<Value> {
+        self.lines += 1;
+
+        // A definition is a statement, but its semicolon can be left out at the prompt.
+        // An expression does not need a semicolon, but one is accepted.
+        let is_definition = matches!(line.split_whitespace().next(), Some("let" | "const"));
+        let line = line.trim_end().trim_end_matches(';');
+        let source = match is_definition {
+            true => format!("{};", line),
+            false => line.to_string(),
+        };
+        let name = FileName::Custom(format!("line {}", self.lines));
+        let sf = with_session_globals(|s| s.source_map.new_source(&source, name));
+
+        if is_definition {
+            let definition = match leo_parser::parse_statement(handler, &sf.src, sf.start_pos)? {
+                Statement::Definition(definition) => definition,
+                _ => unreachable!("Statements that begin with `let` or `const` are definitions."),
+            };
+
+            let (symbol_table, widenings) =
+                TypeChecker::check_definition(&definition, handler, self.symbol_table.clone(), self.language_version)?;
+            // Implicitly widened integers are cast, so that the value has the declared type.
+            let (value, _) =
+                WideningInserter::new(widenings, NodeBuilder::default()).reconstruct_expression(definition.value);
+            let value = self.run(&value)?;
+
+            self.symbol_table = symbol_table;
+            self.variables.insert(definition.variable_name.name, value.clone());
+            return Ok(value);
+        }
+
+        let expression = leo_parser::parse_expression(handler, &sf.src, sf.start_pos)?;
+        let (_, widenings) =
+            TypeChecker::check_expression(&expression, handler, self.symbol_table.clone(), self.language_version)?;
+        let (expression, _) =
+            WideningInserter::new(widenings, NodeBuilder::default()).reconstruct_expression(expression);
+        self.run(&expression)
+    }
+
+    /// Evaluates `expression` with the variables defined so far,
+    /// keeping the updates that the finalize blocks of the functions it calls make to the mappings.
+    fn run(&mut self, expression: &Expression) -> Result<Value> {
+        let interpreter = Interpreter::with_storage(&self.scope, Box::new(std::mem::take(&mut self.mappings)));
+        let value = interpreter.evaluate(expression, &self.variables);
+        self.mappings = interpreter.mappings()?;
+        value
+    }
+
+    /// Returns the entries of `mapping` as their keys and values, sorted by key,
+    /// or `None` if the program has no such mapping.
+    pub fn mapping_entries(&self, mapping: Symbol) -> Option<Vec<(&Value, &Value)>> {
+        if !self.scope.mappings.keys().any(|name| name.name == mapping) {
+            return None;
+        }
+        Some(
+            self.mappings
+                .entries()
+                .into_iter()
+                .filter(|(name, ..)| *name == mapping)
+                .map(|(_, key, value)| (key, value))
+                .collect(),
+        )
+    }
+
+    /// Returns the variables and the mapping entries of the session as JSON, which [`Repl::restore`] reads back.
+    pub fn save(&self) -> String {
+        let definitions = self
+            .variables
+            .iter()
+            .filter_map(|(name, value)| {
+                let variable = self.symbol_table.lookup_variable(*name)?;
+                let keyword = match variable.declaration {
+                    VariableType::Const => "const",
+                    _ => "let",
+                };
+                Some(format!(
+                    "{} {}: {} = {}",
+                    keyword,
+                    name,
+                    type_to_source(&variable.type_),
+                    value_to_source(value)
+                ))
+            })
+            .collect();
+        let mappings = self
+            .mappings
+            .entries()
+            .into_iter()
+            .map(|(mapping, key, value)| (mapping.to_string(), value_to_source(key), value_to_source(value)))
+            .collect();
+        let session = Session {
+            program: self.scope.program_id.to_string(),
+            definitions,
+            mappings,
+        };
+        // Note that this unwrap is safe since the session only holds strings.
+        serde_json::to_string_pretty(&session).unwrap()
+    }
+
+    /// Replaces the variables and the mapping entries with those of `session`, as returned by [`Repl::save`]
+    /// for the same program. Nothing is replaced if the session cannot be restored.
+    pub fn restore(&mut self, handler: &Handler, session: &str) -> Result<()> {
+        let session: Session = serde_json::from_str(session).map_err(CompilerError::invalid_repl_session)?;
+        let program = self.scope.program_id.to_string();
+        if session.program != program {
+            return Err(CompilerError::invalid_repl_session(format!(
+                "it was saved with `{}` rather than `{}`",
+                session.program, program
+            ))
+            .into());
+        }
+
+        // The definitions are checked and evaluated again, as if they were entered at the prompt.
+        let mut restored = Self::with_program(
+            self.language_version,
+            self.scope.clone(),
+            self.program_symbol_table.clone(),
+        );
+        restored.lines = self.lines;
+        for definition in session.definitions.iter() {
+            restored.evaluate(handler, definition)?;
+        }
+        for (mapping, key, value) in session.mappings.iter() {
+            restored
+                .mappings
+                .set(Symbol::intern(mapping), parse_value(key)?, parse_value(value)?);
+        }
+
+        *self = restored;
+        Ok(())
+    }
+}
+
+/// Parses the source code of a value in a saved session, e.g. `3u8`.
+fn parse_value(source: &str) -> Result<Value> {
+    let sf = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom("session".into())));
+    leo_parser::parse_expression(&Handler::default(), &sf.src, sf.start_pos)
+        .ok()
+        .and_then(|expression| evaluate_constant(&expression))
+        .ok_or_else(|| CompilerError::invalid_repl_session(format!("`{}` is not a value", source)).into())
+}
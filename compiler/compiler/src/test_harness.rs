@@ -0,0 +1,339 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{Function, ProgramScope, RecordValue, Type, Value};
+use leo_errors::{emitter::Handler, InterpreterError, LeoError};
+use leo_interpreter::{evaluate_constant, shrink_value, Interpreter, ValueGenerator};
+use leo_span::{
+    source_map::FileName,
+    sym,
+    symbol::{set_session_globals_then, with_session_globals},
+    Span, Symbol,
+};
+
+use indexmap::IndexMap;
+use rand::{rngs::StdRng, SeedableRng};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The number of random cases that a test with inputs is run on, unless its annotation says otherwise.
+pub const DEFAULT_TEST_CASES: usize = 256;
+
+/// The most calls that are made to shrink the inputs of a failing case.
+const MAX_SHRINK_CALLS: usize = 1000;
+
+/// The outcome of running a test function.
+#[derive(Debug)]
+pub struct TestOutcome {
+    /// The name of the test function.
+    pub name: Symbol,
+    /// The span of the test function.
+    pub span: Span,
+    /// The error that failed the test, e.g. that of a `console.assert`, or `None` if it passed.
+    pub failure: Option<LeoError>,
+    /// The inputs of the failing case of a test with inputs, shrunk to the simplest ones that still fail.
+    pub inputs: Vec<Value>,
+    /// How long the test took to run, including the shrinking of its failing inputs.
+    pub duration: Duration,
+}
+
+impl TestOutcome {
+    /// Returns the span of the failure, e.g. that of the assertion that failed, or of the test if the error has none.
+    pub fn failure_span(&self) -> Option<Span> {
+        self.failure.as_ref().map(|failure| failure.span().unwrap_or(self.span))
+    }
+}
+
+/// Collects the functions of a program scope that are annotated with `@test`, and runs them with the interpreter.
+/// A test passes if it returns without an error, and each call starts with empty mappings.
+///
+/// A test with inputs is a property, which is run on random inputs, e.g. 256 times for `@test(cases = 256)`.
+/// The inputs of the first failing case are shrunk to simpler ones that still fail, e.g. `0u8` instead of `173u8`.
+/// An input can be fixed to a value by the annotation instead, e.g. a record in `@test(token = token { .. })`,
+/// and a test whose inputs are all fixed is run once.
+///
+/// Tests run on the current thread, unless [`TestHarness::with_threads`] spreads them over several.
+/// Each of those threads runs the tests on its own clone of the program scope, since the AST is not `Sync`,
+/// and shares the session of the current thread, so that the symbols and spans of the program stay valid.
+///
+/// Tests are interpreted rather than proven, so no proving or verifying keys are synthesized for them.
+/// Proving an execution is left to `leo run` and `leo execute`.
+pub struct TestHarness<'a> {
+    scope: &'a ProgramScope,
+    /// The seed of the random inputs, so that a failing run can be reproduced.
+    seed: u64,
+    /// The number of threads that the tests run on.
+    threads: usize,
+}
+
+impl<'a> TestHarness<'a> {
+    /// Returns a harness for the tests of `scope`.
+    pub fn new(scope: &'a ProgramScope) -> Self {
+        Self {
+            scope,
+            seed: 0,
+            threads: 1,
+        }
+    }
+
+    /// Sets the seed of the random inputs of the tests with inputs.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the number of threads that the tests run on, e.g. that of the cores of the machine.
+    /// The outcome of a test does not depend on it, since each test draws its random inputs from its own generator.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Returns the test functions, in the order in which they are defined.
+    pub fn tests(&self) -> impl Iterator<Item = &'a Function> {
+        self.scope.functions.values().filter(|function| function.is_test())
+    }
+
+    /// Runs the tests whose names contain `filter`, returning their outcomes in the order in which they are defined.
+    pub fn run(&self, filter: &str) -> Vec<TestOutcome> {
+        self.run_with_progress(filter, |_, _, _| {})
+    }
+
+    /// Runs the tests like [`TestHarness::run`], calling `progress` with the outcome of each test as soon as it finishes,
+    /// along with the number of tests that have finished and the number of tests that are run.
+    pub fn run_with_progress(
+        &self,
+        filter: &str,
+        mut progress: impl FnMut(usize, usize, &TestOutcome),
+    ) -> Vec<TestOutcome> {
+        // The tests are referred to by their index among the functions, which is the same in every clone of the scope.
+        let tests: Vec<usize> = self
+            .scope
+            .functions
+            .values()
+            .enumerate()
+            .filter(|(_, function)| function.is_test() && function.name().to_string().contains(filter))
+            .map(|(index, _)| index)
+            .collect();
+
+        let threads = self.threads.min(tests.len());
+        if threads <= 1 {
+            let mut outcomes = Vec::with_capacity(tests.len());
+            for index in tests.iter() {
+                let outcome = self.run_test(&self.scope.functions[*index]);
+                progress(outcomes.len() + 1, tests.len(), &outcome);
+                outcomes.push(outcome);
+            }
+            return outcomes;
+        }
+
+        // Each thread takes the next test that has not been taken, and sends its outcome back to the current thread.
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        let mut outcomes: Vec<Option<TestOutcome>> = tests.iter().map(|_| None).collect();
+        let seed = self.seed;
+        with_session_globals(|session_globals| {
+            thread::scope(|threads_scope| {
+                for _ in 0..threads {
+                    let scope = self.scope.clone();
+                    let sender = sender.clone();
+                    let (next, tests) = (&next, &tests);
+                    threads_scope.spawn(move || {
+                        set_session_globals_then(session_globals, || {
+                            let harness = TestHarness {
+                                scope: &scope,
+                                seed,
+                                threads: 1,
+                            };
+                            loop {
+                                let position = next.fetch_add(1, Ordering::Relaxed);
+                                let index = match tests.get(position) {
+                                    Some(index) => *index,
+                                    None => break,
+                                };
+                                let outcome = harness.run_test(&scope.functions[index]);
+                                if sender.send((position, outcome)).is_err() {
+                                    break;
+                                }
+                            }
+                        })
+                    });
+                }
+                drop(sender);
+
+                for (finished, (position, outcome)) in receiver.iter().enumerate() {
+                    progress(finished + 1, tests.len(), &outcome);
+                    outcomes[position] = Some(outcome);
+                }
+            })
+        });
+        outcomes.into_iter().flatten().collect()
+    }
+
+    /// Runs the test `function`, once if it has no inputs, and otherwise as a property on random inputs.
+    fn run_test(&self, function: &Function) -> TestOutcome {
+        let start = Instant::now();
+        let mut outcome = match function.input.is_empty() {
+            true => TestOutcome {
+                name: function.name(),
+                span: function.span,
+                failure: self.call(function, Vec::new()).err(),
+                inputs: Vec::new(),
+                duration: Duration::default(),
+            },
+            false => self.run_property(function),
+        };
+        outcome.duration = start.elapsed();
+        outcome
+    }
+
+    /// Runs the test `function` on random inputs, shrinking those of the first case that fails.
+    fn run_property(&self, function: &Function) -> TestOutcome {
+        let mut outcome = TestOutcome {
+            name: function.name(),
+            span: function.span,
+            failure: None,
+            inputs: Vec::new(),
+            duration: Duration::default(),
+        };
+        let fixed = match self.fixed_inputs(function) {
+            Ok(fixed) => fixed,
+            Err(error) => {
+                outcome.failure = Some(error);
+                return outcome;
+            }
+        };
+        let cases = match fixed.len() == function.input.len() {
+            true => 1,
+            false => function
+                .annotations
+                .iter()
+                .find_map(|annotation| annotation.argument(sym::cases))
+                .and_then(|cases| cases.parse().ok())
+                .unwrap_or(DEFAULT_TEST_CASES),
+        };
+
+        let generator = ValueGenerator::new(self.scope);
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        for _ in 0..cases {
+            let mut inputs = Vec::with_capacity(function.input.len());
+            for input in function.input.iter() {
+                if let Some(value) = fixed.get(&input.identifier().name) {
+                    inputs.push(value.clone());
+                    continue;
+                }
+                match generator.generate(&input.type_(), &mut rng) {
+                    Some(value) => inputs.push(value),
+                    None => {
+                        let error = InterpreterError::cannot_generate_input(input.type_(), input.identifier().span);
+                        outcome.failure = Some(error.into());
+                        return outcome;
+                    }
+                }
+            }
+            if let Err(error) = self.call(function, inputs.clone()) {
+                let (inputs, error) = self.shrink(function, inputs, error, &fixed);
+                outcome.failure = Some(error);
+                outcome.inputs = inputs;
+                break;
+            }
+        }
+        outcome
+    }
+
+    /// Greedily replaces each of the failing `inputs` of `function` with a simpler value on which it still fails,
+    /// until none is left, returning the simplest inputs found and their error. The `fixed` inputs are kept.
+    fn shrink(
+        &self,
+        function: &Function,
+        mut inputs: Vec<Value>,
+        mut error: LeoError,
+        fixed: &IndexMap<Symbol, Value>,
+    ) -> (Vec<Value>, LeoError) {
+        let mut calls = 0;
+        'shrink: while calls < MAX_SHRINK_CALLS {
+            for i in 0..inputs.len() {
+                if fixed.contains_key(&function.input[i].identifier().name) {
+                    continue;
+                }
+                for candidate in shrink_value(&inputs[i]) {
+                    let mut candidates = inputs.clone();
+                    candidates[i] = candidate;
+                    calls += 1;
+                    if let Err(candidate_error) = self.call(function, candidates.clone()) {
+                        inputs = candidates;
+                        error = candidate_error;
+                        continue 'shrink;
+                    }
+                    if calls >= MAX_SHRINK_CALLS {
+                        break 'shrink;
+                    }
+                }
+            }
+            break;
+        }
+        (inputs, error)
+    }
+
+    /// Returns the values that the annotations of `function` fix its inputs to, by the names of the inputs.
+    /// A record is checked to have the members of its type, since the test runs on them without a ciphertext.
+    fn fixed_inputs(&self, function: &Function) -> Result<IndexMap<Symbol, Value>, LeoError> {
+        let mut fixed = IndexMap::new();
+        for (name, source) in function
+            .annotations
+            .iter()
+            .flat_map(|annotation| annotation.arguments.iter())
+        {
+            let input = match function.input.iter().find(|input| input.identifier().name == name.name) {
+                Some(input) if name.name != sym::cases => input,
+                _ => continue,
+            };
+            let handler = Handler::default();
+            let source_file = with_session_globals(|s| {
+                s.source_map
+                    .new_source(source, FileName::Custom(format!("@test({} = ..)", name)))
+            });
+            let expression = leo_parser::parse_expression(&handler, &source_file.src, source_file.start_pos)?;
+            let value =
+                evaluate_constant(&expression).ok_or_else(|| InterpreterError::input_not_constant(name, name.span))?;
+            let record = match input.type_() {
+                Type::Identifier(identifier) => self
+                    .scope
+                    .structs
+                    .values()
+                    .find(|struct_| struct_.is_record && struct_.identifier.name == identifier.name),
+                _ => None,
+            };
+            let value = match record {
+                Some(record) => RecordValue::from_value(&value, record, name.span)?.into_value(),
+                None => value,
+            };
+            fixed.insert(name.name, value);
+        }
+        Ok(fixed)
+    }
+
+    /// Calls `function` on `arguments` with a new interpreter, so that it starts with empty mappings.
+    fn call(&self, function: &Function, arguments: Vec<Value>) -> Result<Value, LeoError> {
+        Interpreter::new(self.scope).call(function.name(), arguments, function.span)
+    }
+}
@@ -144,3 +144,91 @@ impl CoreFunction for BHP1024Commit {
         Type::Field
     }
 }
+
+pub struct BHP256CommitTagged;
+
+impl CoreFunction for BHP256CommitTagged {
+    const NUM_ARGS: usize = 3;
+
+    fn first_arg_is_allowed_type(type_: &Type) -> bool {
+        matches!(type_, Type::Field)
+    }
+
+    fn second_arg_is_allowed_type(type_: &Type) -> bool {
+        !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
+    }
+
+    fn third_arg_is_allowed_type(type_: &Type) -> bool {
+        matches!(type_, Type::Scalar)
+    }
+
+    fn return_type() -> Type {
+        Type::Field
+    }
+}
+
+pub struct BHP512CommitTagged;
+
+impl CoreFunction for BHP512CommitTagged {
+    const NUM_ARGS: usize = 3;
+
+    fn first_arg_is_allowed_type(type_: &Type) -> bool {
+        matches!(type_, Type::Field)
+    }
+
+    fn second_arg_is_allowed_type(type_: &Type) -> bool {
+        !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
+    }
+
+    fn third_arg_is_allowed_type(type_: &Type) -> bool {
+        matches!(type_, Type::Scalar)
+    }
+
+    fn return_type() -> Type {
+        Type::Field
+    }
+}
+
+pub struct BHP768CommitTagged;
+
+impl CoreFunction for BHP768CommitTagged {
+    const NUM_ARGS: usize = 3;
+
+    fn first_arg_is_allowed_type(type_: &Type) -> bool {
+        matches!(type_, Type::Field)
+    }
+
+    fn second_arg_is_allowed_type(type_: &Type) -> bool {
+        !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
+    }
+
+    fn third_arg_is_allowed_type(type_: &Type) -> bool {
+        matches!(type_, Type::Scalar)
+    }
+
+    fn return_type() -> Type {
+        Type::Field
+    }
+}
+
+pub struct BHP1024CommitTagged;
+
+impl CoreFunction for BHP1024CommitTagged {
+    const NUM_ARGS: usize = 3;
+
+    fn first_arg_is_allowed_type(type_: &Type) -> bool {
+        matches!(type_, Type::Field)
+    }
+
+    fn second_arg_is_allowed_type(type_: &Type) -> bool {
+        !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
+    }
+
+    fn third_arg_is_allowed_type(type_: &Type) -> bool {
+        matches!(type_, Type::Scalar)
+    }
+
+    fn return_type() -> Type {
+        Type::Field
+    }
+}
@@ -22,19 +22,26 @@ pub use pedersen::*;
 mod poseidon;
 pub use poseidon::*;
 
-use leo_ast::Type;
+mod signature;
+pub use signature::*;
+
+use leo_ast::{Feature, Type};
 use leo_span::{sym, Symbol};
 
 /// A core instruction that maps directly to an AVM bytecode instruction.
 #[derive(Clone, PartialEq, Eq)]
 pub enum CoreInstruction {
     BHP256Commit,
+    BHP256CommitTagged,
     BHP256Hash,
     BHP512Commit,
+    BHP512CommitTagged,
     BHP512Hash,
     BHP768Commit,
+    BHP768CommitTagged,
     BHP768Hash,
     BHP1024Commit,
+    BHP1024CommitTagged,
     BHP1024Hash,
 
     Pedersen64Commit,
@@ -45,6 +52,8 @@ pub enum CoreInstruction {
     Poseidon2Hash,
     Poseidon4Hash,
     Poseidon8Hash,
+
+    SignatureVerify,
 }
 
 impl CoreInstruction {
@@ -52,12 +61,16 @@ impl CoreInstruction {
     pub fn from_symbols(module: Symbol, function: Symbol) -> Option<Self> {
         Some(match (module, function) {
             (sym::BHP256, sym::commit) => Self::BHP256Commit,
+            (sym::BHP256, sym::commit_tagged) => Self::BHP256CommitTagged,
             (sym::BHP256, sym::hash) => Self::BHP256Hash,
             (sym::BHP512, sym::commit) => Self::BHP512Commit,
+            (sym::BHP512, sym::commit_tagged) => Self::BHP512CommitTagged,
             (sym::BHP512, sym::hash) => Self::BHP512Hash,
             (sym::BHP768, sym::commit) => Self::BHP768Commit,
+            (sym::BHP768, sym::commit_tagged) => Self::BHP768CommitTagged,
             (sym::BHP768, sym::hash) => Self::BHP768Hash,
             (sym::BHP1024, sym::commit) => Self::BHP1024Commit,
+            (sym::BHP1024, sym::commit_tagged) => Self::BHP1024CommitTagged,
             (sym::BHP1024, sym::hash) => Self::BHP1024Hash,
 
             (sym::Pedersen64, sym::commit) => Self::Pedersen64Commit,
@@ -68,20 +81,39 @@ impl CoreInstruction {
             (sym::Poseidon2, sym::hash) => Self::Poseidon2Hash,
             (sym::Poseidon4, sym::hash) => Self::Poseidon4Hash,
             (sym::Poseidon8, sym::hash) => Self::Poseidon8Hash,
+
+            (sym::signature, sym::verify) => Self::SignatureVerify,
             _ => return None,
         })
     }
 
+    /// Returns the language feature that the instruction belongs to, if it is not available in every version of Leo.
+    pub fn feature(&self) -> Option<Feature> {
+        match self {
+            Self::BHP256CommitTagged
+            | Self::BHP512CommitTagged
+            | Self::BHP768CommitTagged
+            | Self::BHP1024CommitTagged => Some(Feature::TaggedCommitments),
+            Self::Poseidon2Hash | Self::Poseidon4Hash | Self::Poseidon8Hash => Some(Feature::PoseidonHashes),
+            Self::SignatureVerify => Some(Feature::Signatures),
+            _ => None,
+        }
+    }
+
     /// Returns the number of arguments required by the instruction.
     pub fn num_args(&self) -> usize {
         match self {
             Self::BHP256Commit => BHP256Commit::NUM_ARGS,
+            Self::BHP256CommitTagged => BHP256CommitTagged::NUM_ARGS,
             Self::BHP256Hash => BHP256Hash::NUM_ARGS,
             Self::BHP512Commit => BHP512Commit::NUM_ARGS,
+            Self::BHP512CommitTagged => BHP512CommitTagged::NUM_ARGS,
             Self::BHP512Hash => BHP512Hash::NUM_ARGS,
             Self::BHP768Commit => BHP768Commit::NUM_ARGS,
+            Self::BHP768CommitTagged => BHP768CommitTagged::NUM_ARGS,
             Self::BHP768Hash => BHP768Hash::NUM_ARGS,
             Self::BHP1024Commit => BHP1024Commit::NUM_ARGS,
+            Self::BHP1024CommitTagged => BHP1024CommitTagged::NUM_ARGS,
             Self::BHP1024Hash => BHP1024Hash::NUM_ARGS,
 
             Self::Pedersen64Commit => Pedersen64Commit::NUM_ARGS,
@@ -92,6 +124,8 @@ impl CoreInstruction {
             Self::Poseidon2Hash => Poseidon2Hash::NUM_ARGS,
             Self::Poseidon4Hash => Poseidon4Hash::NUM_ARGS,
             Self::Poseidon8Hash => Poseidon8Hash::NUM_ARGS,
+
+            Self::SignatureVerify => SignatureVerify::NUM_ARGS,
         }
     }
 
@@ -99,12 +133,16 @@ impl CoreInstruction {
     pub fn first_arg_is_allowed_type(&self, type_: &Type) -> bool {
         match self {
             CoreInstruction::BHP256Commit => BHP256Commit::first_arg_is_allowed_type(type_),
+            CoreInstruction::BHP256CommitTagged => BHP256CommitTagged::first_arg_is_allowed_type(type_),
             CoreInstruction::BHP256Hash => BHP256Hash::first_arg_is_allowed_type(type_),
             CoreInstruction::BHP512Commit => BHP512Commit::first_arg_is_allowed_type(type_),
+            CoreInstruction::BHP512CommitTagged => BHP512CommitTagged::first_arg_is_allowed_type(type_),
             CoreInstruction::BHP512Hash => BHP512Hash::first_arg_is_allowed_type(type_),
             CoreInstruction::BHP768Commit => BHP768Commit::first_arg_is_allowed_type(type_),
+            CoreInstruction::BHP768CommitTagged => BHP768CommitTagged::first_arg_is_allowed_type(type_),
             CoreInstruction::BHP768Hash => BHP768Hash::first_arg_is_allowed_type(type_),
             CoreInstruction::BHP1024Commit => BHP1024Commit::first_arg_is_allowed_type(type_),
+            CoreInstruction::BHP1024CommitTagged => BHP1024CommitTagged::first_arg_is_allowed_type(type_),
             CoreInstruction::BHP1024Hash => BHP1024Hash::first_arg_is_allowed_type(type_),
             CoreInstruction::Pedersen64Commit => Pedersen64Commit::first_arg_is_allowed_type(type_),
             CoreInstruction::Pedersen64Hash => Pedersen64Hash::first_arg_is_allowed_type(type_),
@@ -113,6 +151,7 @@ impl CoreInstruction {
             CoreInstruction::Poseidon2Hash => Poseidon2Hash::first_arg_is_allowed_type(type_),
             CoreInstruction::Poseidon4Hash => Poseidon4Hash::first_arg_is_allowed_type(type_),
             CoreInstruction::Poseidon8Hash => Poseidon8Hash::first_arg_is_allowed_type(type_),
+            CoreInstruction::SignatureVerify => SignatureVerify::first_arg_is_allowed_type(type_),
         }
     }
 
@@ -120,12 +159,16 @@ impl CoreInstruction {
     pub fn second_arg_is_allowed_type(&self, type_: &Type) -> bool {
         match self {
             CoreInstruction::BHP256Commit => BHP256Commit::second_arg_is_allowed_type(type_),
+            CoreInstruction::BHP256CommitTagged => BHP256CommitTagged::second_arg_is_allowed_type(type_),
             CoreInstruction::BHP256Hash => BHP256Hash::second_arg_is_allowed_type(type_),
             CoreInstruction::BHP512Commit => BHP512Commit::second_arg_is_allowed_type(type_),
+            CoreInstruction::BHP512CommitTagged => BHP512CommitTagged::second_arg_is_allowed_type(type_),
             CoreInstruction::BHP512Hash => BHP512Hash::second_arg_is_allowed_type(type_),
             CoreInstruction::BHP768Commit => BHP768Commit::second_arg_is_allowed_type(type_),
+            CoreInstruction::BHP768CommitTagged => BHP768CommitTagged::second_arg_is_allowed_type(type_),
             CoreInstruction::BHP768Hash => BHP768Hash::second_arg_is_allowed_type(type_),
             CoreInstruction::BHP1024Commit => BHP1024Commit::second_arg_is_allowed_type(type_),
+            CoreInstruction::BHP1024CommitTagged => BHP1024CommitTagged::second_arg_is_allowed_type(type_),
             CoreInstruction::BHP1024Hash => BHP1024Hash::second_arg_is_allowed_type(type_),
             CoreInstruction::Pedersen64Commit => Pedersen64Commit::second_arg_is_allowed_type(type_),
             CoreInstruction::Pedersen64Hash => Pedersen64Hash::second_arg_is_allowed_type(type_),
@@ -134,6 +177,52 @@ impl CoreInstruction {
             CoreInstruction::Poseidon2Hash => Poseidon2Hash::second_arg_is_allowed_type(type_),
             CoreInstruction::Poseidon4Hash => Poseidon4Hash::second_arg_is_allowed_type(type_),
             CoreInstruction::Poseidon8Hash => Poseidon8Hash::second_arg_is_allowed_type(type_),
+            CoreInstruction::SignatureVerify => SignatureVerify::second_arg_is_allowed_type(type_),
+        }
+    }
+
+    /// Returns whether or not the third argument is an allowed type.
+    pub fn third_arg_is_allowed_type(&self, type_: &Type) -> bool {
+        match self {
+            CoreInstruction::BHP256CommitTagged => BHP256CommitTagged::third_arg_is_allowed_type(type_),
+            CoreInstruction::BHP512CommitTagged => BHP512CommitTagged::third_arg_is_allowed_type(type_),
+            CoreInstruction::BHP768CommitTagged => BHP768CommitTagged::third_arg_is_allowed_type(type_),
+            CoreInstruction::BHP1024CommitTagged => BHP1024CommitTagged::third_arg_is_allowed_type(type_),
+            CoreInstruction::SignatureVerify => SignatureVerify::third_arg_is_allowed_type(type_),
+            _ => false,
+        }
+    }
+
+    /// Returns whether the instruction is a hash, whose output reveals inputs that have few possible values.
+    pub fn is_hash(&self) -> bool {
+        matches!(
+            self,
+            Self::BHP256Hash
+                | Self::BHP512Hash
+                | Self::BHP768Hash
+                | Self::BHP1024Hash
+                | Self::Pedersen64Hash
+                | Self::Pedersen128Hash
+                | Self::Poseidon2Hash
+                | Self::Poseidon4Hash
+                | Self::Poseidon8Hash
+        )
+    }
+
+    /// Returns the index of the randomness argument of a commitment, or `None` if the instruction is not a commitment.
+    pub fn randomness_index(&self) -> Option<usize> {
+        match self {
+            Self::BHP256Commit
+            | Self::BHP512Commit
+            | Self::BHP768Commit
+            | Self::BHP1024Commit
+            | Self::Pedersen64Commit
+            | Self::Pedersen128Commit => Some(1),
+            Self::BHP256CommitTagged
+            | Self::BHP512CommitTagged
+            | Self::BHP768CommitTagged
+            | Self::BHP1024CommitTagged => Some(2),
+            _ => None,
         }
     }
 
@@ -141,12 +230,16 @@ impl CoreInstruction {
     pub fn return_type(&self) -> Type {
         match self {
             Self::BHP256Commit => BHP256Commit::return_type(),
+            Self::BHP256CommitTagged => BHP256CommitTagged::return_type(),
             Self::BHP256Hash => BHP256Hash::return_type(),
             Self::BHP512Commit => BHP512Commit::return_type(),
+            Self::BHP512CommitTagged => BHP512CommitTagged::return_type(),
             Self::BHP512Hash => BHP512Hash::return_type(),
             Self::BHP768Commit => BHP768Commit::return_type(),
+            Self::BHP768CommitTagged => BHP768CommitTagged::return_type(),
             Self::BHP768Hash => BHP768Hash::return_type(),
             Self::BHP1024Commit => BHP1024Commit::return_type(),
+            Self::BHP1024CommitTagged => BHP1024CommitTagged::return_type(),
             Self::BHP1024Hash => BHP1024Hash::return_type(),
 
             Self::Pedersen64Commit => Pedersen64Commit::return_type(),
@@ -157,6 +250,8 @@ impl CoreInstruction {
             Self::Poseidon2Hash => Poseidon2Hash::return_type(),
             Self::Poseidon4Hash => Poseidon4Hash::return_type(),
             Self::Poseidon8Hash => Poseidon8Hash::return_type(),
+
+            Self::SignatureVerify => SignatureVerify::return_type(),
         }
     }
 }
@@ -176,6 +271,11 @@ trait CoreFunction {
         false
     }
 
+    /// Returns whether or not the third argument is an allowed type.
+    fn third_arg_is_allowed_type(_: &Type) -> bool {
+        false
+    }
+
     /// The return type of the core function.
     fn return_type() -> Type;
 }
@@ -19,3 +19,6 @@
 
 mod algorithms;
 pub use algorithms::*;
+
+mod mapping;
+pub use mapping::*;
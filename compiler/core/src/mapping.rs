@@ -0,0 +1,76 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_span::{sym, Symbol};
+
+/// An operation on a mapping, e.g. `Mapping::get(balances, addr)`, that maps to an AVM finalize instruction.
+///
+/// Unlike a [`CoreInstruction`](crate::CoreInstruction), the types of its arguments and of its output depend on
+/// the key and value types of the mapping it is given as its first argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MappingOperation {
+    /// `Mapping::get(mapping, key)`, which halts if `key` is not in the mapping.
+    Get,
+    /// `Mapping::get_or_use(mapping, key, default)`, which returns `default` if `key` is not in the mapping.
+    GetOrUse,
+    /// `Mapping::set(mapping, key, value)`.
+    Set,
+    /// `Mapping::remove(mapping, key)`.
+    Remove,
+    /// `Mapping::contains(mapping, key)`.
+    Contains,
+}
+
+impl MappingOperation {
+    /// Returns a `MappingOperation` from the given module and method symbols.
+    pub fn from_symbols(module: Symbol, function: Symbol) -> Option<Self> {
+        if module != sym::Mapping {
+            return None;
+        }
+        Some(match function {
+            sym::get => Self::Get,
+            sym::get_or_use => Self::GetOrUse,
+            sym::set => Self::Set,
+            sym::remove => Self::Remove,
+            sym::contains => Self::Contains,
+            _ => return None,
+        })
+    }
+
+    /// Returns the number of arguments required by the operation, including the mapping.
+    pub fn num_args(&self) -> usize {
+        match self {
+            Self::Get | Self::Remove | Self::Contains => 2,
+            Self::GetOrUse | Self::Set => 3,
+        }
+    }
+
+    /// Returns whether the operation updates the mapping, and so has no output.
+    pub fn is_update(&self) -> bool {
+        matches!(self, Self::Set | Self::Remove)
+    }
+
+    /// Returns the opcode of the AVM instruction, e.g. `get.or_use`.
+    pub fn opcode(&self) -> &'static str {
+        match self {
+            Self::Get => "get",
+            Self::GetOrUse => "get.or_use",
+            Self::Set => "set",
+            Self::Remove => "remove",
+            Self::Contains => "contains",
+        }
+    }
+}
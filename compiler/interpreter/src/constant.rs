@@ -0,0 +1,68 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{evaluate_binary, evaluate_cast, evaluate_unary, expect_boolean, values_equal};
+
+use leo_ast::{ConsoleFunction, Expression, Value};
+
+/// Returns the value of `expression` if it does not depend on any variable, e.g. `1u8 + 2u8`.
+/// Returns `None` if it does, or if its evaluation fails.
+pub fn evaluate_constant(expression: &Expression) -> Option<Value> {
+    match expression {
+        Expression::Literal(literal) => Value::from_literal(literal),
+        Expression::Unary(unary) => evaluate_unary(unary.op, evaluate_constant(&unary.receiver)?, unary.span).ok(),
+        Expression::Cast(cast) => evaluate_cast(evaluate_constant(&cast.expression)?, cast.type_, cast.span).ok(),
+        Expression::Binary(binary) => evaluate_binary(
+            binary.op,
+            evaluate_constant(&binary.left)?,
+            evaluate_constant(&binary.right)?,
+            binary.span,
+        )
+        .ok(),
+        // Both branches are evaluated, as in snarkVM.
+        Expression::Ternary(ternary) => {
+            let condition = expect_boolean(&evaluate_constant(&ternary.condition)?, ternary.span).ok()?;
+            let if_true = evaluate_constant(&ternary.if_true)?;
+            let if_false = evaluate_constant(&ternary.if_false)?;
+            Some(if condition { if_true } else { if_false })
+        }
+        Expression::Tuple(tuple) => Some(Value::Tuple(
+            tuple.elements.iter().map(evaluate_constant).collect::<Option<_>>()?,
+        )),
+        Expression::Struct(struct_) => Some(Value::Struct(
+            struct_.name,
+            struct_
+                .members
+                .iter()
+                .map(|member| Some((member.identifier.name, evaluate_constant(member.expression.as_ref()?)?)))
+                .collect::<Option<_>>()?,
+        )),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the assertion is provably false, i.e. its operands are constants that do not satisfy it.
+pub fn assertion_always_fails(function: &ConsoleFunction) -> bool {
+    let constants_equal = |left: &Expression, right: &Expression| {
+        let (left, right) = (evaluate_constant(left)?, evaluate_constant(right)?);
+        values_equal(&left, &right, Default::default()).ok()
+    };
+    match function {
+        ConsoleFunction::Assert(expr, _) => matches!(evaluate_constant(expr), Some(Value::Boolean(false, _))),
+        ConsoleFunction::AssertEq(left, right, _) => constants_equal(left, right) == Some(false),
+        ConsoleFunction::AssertNeq(left, right, _) => constants_equal(left, right) == Some(true),
+    }
+}
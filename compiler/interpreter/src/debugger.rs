@@ -0,0 +1,172 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::Value;
+use leo_errors::Result;
+use leo_span::{
+    source_map::{FileName, SpanLocation},
+    symbol::with_session_globals,
+    Span, Symbol,
+};
+
+use indexmap::IndexMap;
+use std::path::{Path, PathBuf};
+
+/// A line of a source file at which the interpreter pauses, e.g. `main.leo:12`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Breakpoint {
+    /// The source file, or `None` for a line of any file.
+    /// A path matches the files that it is a suffix of, so `main.leo` matches `src/main.leo`.
+    pub file: Option<PathBuf>,
+    /// The line, counting from 1.
+    pub line: usize,
+}
+
+impl Breakpoint {
+    /// Parses a breakpoint written as `FILE:LINE` or `LINE`, returning `None` if the line is not a number.
+    pub fn parse(breakpoint: &str) -> Option<Self> {
+        let (file, line) = match breakpoint.rsplit_once(':') {
+            Some((file, line)) => (Some(PathBuf::from(file)), line),
+            None => (None, breakpoint),
+        };
+        Some(Self {
+            file,
+            line: line.trim().parse().ok()?,
+        })
+    }
+
+    /// Returns whether the breakpoint is on the first line of `location`.
+    pub fn matches(&self, location: &SpanLocation) -> bool {
+        let file_matches = match (&self.file, &location.source_file.name) {
+            (None, _) => true,
+            (Some(file), FileName::Real(path)) => path.ends_with(file) || file.ends_with(path),
+            (Some(file), FileName::Custom(name)) => Path::new(name) == file,
+        };
+        file_matches && self.line == location.line_start
+    }
+}
+
+/// A call of a function that is being evaluated, as shown by a debugger.
+#[derive(Clone, Debug)]
+pub struct StackFrame {
+    /// The function that was called.
+    pub function: Symbol,
+    /// The statement that is being evaluated, or the function itself before its first statement.
+    pub span: Span,
+    /// The variables in scope, with those of inner blocks shadowing those of outer blocks.
+    pub variables: IndexMap<Symbol, Value>,
+}
+
+impl StackFrame {
+    /// Returns the location of the statement that is being evaluated.
+    pub fn location(&self) -> Option<SpanLocation> {
+        with_session_globals(|s| s.source_map.span_to_location(self.span))
+    }
+}
+
+/// Why the interpreter paused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseReason {
+    /// The interpreter paused before the first statement, as requested.
+    Entry,
+    /// The statement is on a breakpoint.
+    Breakpoint,
+    /// A step has completed.
+    Step,
+}
+
+/// How the interpreter continues after it has paused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebugStep {
+    /// Run until the next breakpoint.
+    Continue,
+    /// Pause at the next statement, including those of the functions it calls.
+    StepIn,
+    /// Pause at the next statement of the same function, or of its caller once it returns.
+    Next,
+    /// Pause once the function has returned to its caller.
+    StepOut,
+    /// Stop the run with an error.
+    Stop,
+}
+
+/// The user interface of a debugger, e.g. a prompt or an editor, which decides how the interpreter continues
+/// whenever it pauses.
+pub trait DebugFrontend {
+    /// Shows the paused interpreter, with its calls from the outermost to the innermost, and returns how to continue.
+    /// The breakpoints can be changed while the interpreter is paused.
+    fn pause(
+        &mut self,
+        reason: PauseReason,
+        stack: &[StackFrame],
+        breakpoints: &mut Vec<Breakpoint>,
+    ) -> Result<DebugStep>;
+}
+
+/// Pauses the interpreter before the statements on breakpoints and after steps, and asks a frontend how to continue.
+pub struct Debugger<'a> {
+    /// The frontend that is asked how to continue.
+    frontend: &'a mut dyn DebugFrontend,
+    /// The lines before whose statements the interpreter pauses.
+    breakpoints: Vec<Breakpoint>,
+    /// The step that is being taken, with the number of calls that were being evaluated when it was requested.
+    step: Option<(DebugStep, usize)>,
+}
+
+impl<'a> Debugger<'a> {
+    /// Returns a debugger that pauses at `breakpoints`, and before the first statement if `stop_on_entry` is set.
+    pub fn new(frontend: &'a mut dyn DebugFrontend, breakpoints: Vec<Breakpoint>, stop_on_entry: bool) -> Self {
+        Self {
+            frontend,
+            breakpoints,
+            step: stop_on_entry.then_some((DebugStep::StepIn, 0)),
+        }
+    }
+
+    /// Pauses before the statement that the innermost call of `stack` is evaluating, if it is on a breakpoint
+    /// or completes a step, returning whether the run should stop.
+    pub(crate) fn before_statement(&mut self, stack: &[StackFrame]) -> Result<bool> {
+        let frame = match stack.last() {
+            Some(frame) => frame,
+            None => return Ok(false),
+        };
+        let reason = match self.step {
+            Some((DebugStep::StepIn, 0)) => Some(PauseReason::Entry),
+            Some((DebugStep::StepIn, _)) => Some(PauseReason::Step),
+            Some((DebugStep::Next, depth)) if stack.len() <= depth => Some(PauseReason::Step),
+            Some((DebugStep::StepOut, depth)) if stack.len() < depth => Some(PauseReason::Step),
+            _ => None,
+        };
+        let reason = match (reason, frame.location()) {
+            (None, Some(location)) if self.breakpoints.iter().any(|b| b.matches(&location)) => {
+                Some(PauseReason::Breakpoint)
+            }
+            (reason, _) => reason,
+        };
+
+        match reason {
+            Some(reason) => {
+                let step = self.frontend.pause(reason, stack, &mut self.breakpoints)?;
+                self.step = match step {
+                    DebugStep::Continue => None,
+                    step => Some((step, stack.len())),
+                };
+                Ok(step == DebugStep::Stop)
+            }
+            None => Ok(false),
+        }
+    }
+}
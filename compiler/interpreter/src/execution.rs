@@ -0,0 +1,105 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Mappings;
+
+use leo_ast::{Input, Literal, Mode, Type, Value};
+use leo_span::Symbol;
+
+/// A successful call of a function on concrete values.
+/// It can be exported as an input file and a test that expects the same output, to turn an exploratory run into
+/// a regression test.
+#[derive(Clone, Debug)]
+pub struct Execution {
+    /// The function that was called.
+    pub function: Symbol,
+    /// The inputs of the function, with the argument of each.
+    pub inputs: Vec<(Input, Value)>,
+    /// The output of the function, or the empty tuple if it has none.
+    pub output: Value,
+    /// The entries of the program's mappings after the call, including the updates of its finalize block.
+    pub mappings: Mappings,
+}
+
+impl Execution {
+    /// Returns an input file with a section for the function, e.g. `[main]\na: u8 = 3u8;\n`.
+    pub fn input_file(&self) -> String {
+        let mut file = format!("[{}]\n", self.function);
+        for (input, argument) in self.inputs.iter() {
+            file.push_str(&input_definition(input, &value_to_source(argument)));
+            file.push('\n');
+        }
+        file
+    }
+
+    /// Returns the header of a compiler test that calls the function on the inputs of `input_file`
+    /// and expects the same output, e.g. `expected_output: 13u8`.
+    pub fn test_header(&self, input_file: &str) -> String {
+        format!(
+            "/*\nnamespace: Compile\nexpectation: Pass\ninput_file: {}\nexecute: {}\nexpected_output: \"{}\"\n*/\n",
+            input_file,
+            self.function,
+            value_to_source(&self.output).replace('"', "\\\"")
+        )
+    }
+}
+
+/// Returns the definition of `input` in an input file, with the Leo source code `value`, e.g. `public a: u8 = 3u8;`.
+pub fn input_definition(input: &Input, value: &str) -> String {
+    let mode = match input.mode() {
+        Mode::Const => "constant ",
+        Mode::Public => "public ",
+        Mode::Witness => "witness ",
+        Mode::None | Mode::Private => "",
+    };
+    format!(
+        "{}{}: {} = {};",
+        mode,
+        input.identifier(),
+        type_to_source(&input.type_()),
+        value
+    )
+}
+
+/// Returns `type_` as Leo source code, e.g. `bool` rather than the `boolean` it is displayed as.
+pub fn type_to_source(type_: &Type) -> String {
+    match type_ {
+        Type::Boolean => "bool".to_string(),
+        type_ => type_.to_string(),
+    }
+}
+
+/// Returns `value` as Leo source code, e.g. `3u8` or `Point { x: 1u8, y: 2u8 }`.
+pub fn value_to_source(value: &Value) -> String {
+    match value {
+        Value::Struct(name, members) => format!(
+            "{} {{ {} }}",
+            name,
+            members
+                .iter()
+                .map(|(member, value)| format!("{}: {}", member, value_to_source(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Tuple(elements) => format!(
+            "({})",
+            elements.iter().map(value_to_source).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Input(..) => value.to_string(),
+        // Every other value is a literal.
+        value => Literal::try_from(value.clone()).map_or_else(|_| value.to_string(), |literal| literal.to_string()),
+    }
+}
@@ -0,0 +1,217 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{IntegerType, ProgramScope, Type, Value};
+use leo_span::Span;
+
+use indexmap::IndexMap;
+use rand::Rng;
+
+/// The addresses that generated `address` values are picked from, since the interpreter cannot derive new ones.
+const ADDRESSES: [&str; 3] = [
+    "aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8sta57j8",
+    "aleo1fj982yqchhy973kz7e9jk6er7t6qd6jm9anplnlprem507w6lv9spwvfxx",
+    "aleo10qerras5799u6k7rjtc9y3hcwxuykr45qra7x7dp6jgnc0923czqm0lgta",
+];
+
+/// Returns a random integer of type `$type_`, which is an edge case, e.g. `0` or the maximum, one time in two.
+macro_rules! random_integer {
+    ($rng:expr, $type_:ty) => {
+        match $rng.gen_range(0..8) {
+            0 => 0,
+            1 => 1,
+            2 => <$type_>::MIN,
+            3 => <$type_>::MAX,
+            _ => $rng.gen::<$type_>(),
+        }
+    };
+}
+
+/// Returns the integers that are simpler than the unsigned `$value`, i.e. closer to zero.
+macro_rules! shrink_unsigned {
+    ($value:expr, $variant:ident) => {{
+        let value = *$value;
+        let candidates = match value {
+            0 => vec![],
+            _ => vec![0, value / 2, value - 1],
+        };
+        candidates
+            .into_iter()
+            .map(|v| Value::$variant(v, Span::default()))
+            .collect()
+    }};
+}
+
+/// Returns the integers that are simpler than the signed `$value`, i.e. closer to zero, or positive.
+macro_rules! shrink_signed {
+    ($value:expr, $variant:ident) => {{
+        let value = *$value;
+        let mut candidates = match value {
+            0 => vec![],
+            _ => vec![0, value / 2, value - value.signum()],
+        };
+        if let Some(negated) = value.checked_neg().filter(|negated| *negated > 0) {
+            candidates.insert(1, negated);
+        }
+        candidates
+            .into_iter()
+            .map(|v| Value::$variant(v, Span::default()))
+            .collect()
+    }};
+}
+
+/// Generates random values of types for property-based tests.
+/// The values of a struct type are generated from its definition in the program scope.
+pub struct ValueGenerator<'a> {
+    scope: &'a ProgramScope,
+}
+
+impl<'a> ValueGenerator<'a> {
+    /// Returns a generator for the types of `scope`.
+    pub fn new(scope: &'a ProgramScope) -> Self {
+        Self { scope }
+    }
+
+    /// Returns a random value of `type_`, or `None` if values of the type cannot be generated,
+    /// e.g. `group` values, which only snarkVM can compute.
+    pub fn generate(&self, type_: &Type, rng: &mut impl Rng) -> Option<Value> {
+        let span = Span::default();
+        Some(match type_ {
+            Type::Address => Value::Address(ADDRESSES[rng.gen_range(0..ADDRESSES.len())].to_string(), span),
+            Type::Boolean => Value::Boolean(rng.gen(), span),
+            // Note that the random fields are small, since they are written in decimal without a modulus at hand.
+            Type::Field => Value::Field(random_integer!(rng, u64).to_string(), span),
+            Type::Integer(integer_type) => match integer_type {
+                IntegerType::U8 => Value::U8(random_integer!(rng, u8), span),
+                IntegerType::U16 => Value::U16(random_integer!(rng, u16), span),
+                IntegerType::U32 => Value::U32(random_integer!(rng, u32), span),
+                IntegerType::U64 => Value::U64(random_integer!(rng, u64), span),
+                IntegerType::U128 => Value::U128(random_integer!(rng, u128), span),
+                IntegerType::I8 => Value::I8(random_integer!(rng, i8), span),
+                IntegerType::I16 => Value::I16(random_integer!(rng, i16), span),
+                IntegerType::I32 => Value::I32(random_integer!(rng, i32), span),
+                IntegerType::I64 => Value::I64(random_integer!(rng, i64), span),
+                IntegerType::I128 => Value::I128(random_integer!(rng, i128), span),
+            },
+            Type::Identifier(identifier) => {
+                let struct_ = self
+                    .scope
+                    .structs
+                    .values()
+                    .find(|struct_| struct_.identifier.name == identifier.name)?;
+                let members = struct_
+                    .members
+                    .iter()
+                    .map(|member| Some((member.identifier.name, self.generate(&member.type_, rng)?)))
+                    .collect::<Option<IndexMap<_, _>>>()?;
+                Value::Struct(*identifier, members)
+            }
+            Type::Tuple(tuple) => Value::Tuple(
+                tuple
+                    .0
+                    .iter()
+                    .map(|type_| self.generate(type_, rng))
+                    .collect::<Option<_>>()?,
+            ),
+            Type::Group | Type::Mapping(_) | Type::Scalar | Type::Signature | Type::String | Type::Unit | Type::Err => {
+                return None
+            }
+        })
+    }
+}
+
+/// Returns values of the same type that are simpler than `value`, from the simplest, to try in its place
+/// when a test fails on it. A value whose parts are all as simple as they can be has none.
+pub fn shrink_value(value: &Value) -> Vec<Value> {
+    let candidates: Vec<Value> = match value {
+        Value::Boolean(true, span) => vec![Value::Boolean(false, *span)],
+        Value::Address(address, _) if address != ADDRESSES[0] => {
+            vec![Value::Address(ADDRESSES[0].to_string(), Span::default())]
+        }
+        Value::Field(field, _) => match field.parse::<u128>() {
+            Ok(0) => vec![],
+            Ok(field) => [0, field / 2, field - 1]
+                .iter()
+                .map(|v| Value::Field(v.to_string(), Span::default()))
+                .collect(),
+            Err(_) => vec![Value::Field("0".to_string(), Span::default())],
+        },
+        Value::U8(value, _) => shrink_unsigned!(value, U8),
+        Value::U16(value, _) => shrink_unsigned!(value, U16),
+        Value::U32(value, _) => shrink_unsigned!(value, U32),
+        Value::U64(value, _) => shrink_unsigned!(value, U64),
+        Value::U128(value, _) => shrink_unsigned!(value, U128),
+        Value::I8(value, _) => shrink_signed!(value, I8),
+        Value::I16(value, _) => shrink_signed!(value, I16),
+        Value::I32(value, _) => shrink_signed!(value, I32),
+        Value::I64(value, _) => shrink_signed!(value, I64),
+        Value::I128(value, _) => shrink_signed!(value, I128),
+        // A struct or a tuple is shrunk by shrinking one of its parts.
+        Value::Struct(identifier, members) => members
+            .iter()
+            .flat_map(|(name, member)| {
+                shrink_value(member).into_iter().map(move |member| {
+                    let mut members = members.clone();
+                    members.insert(*name, member);
+                    Value::Struct(*identifier, members)
+                })
+            })
+            .collect(),
+        Value::Tuple(elements) => (0..elements.len())
+            .flat_map(|i| {
+                shrink_value(&elements[i]).into_iter().map(move |element| {
+                    let mut elements = elements.clone();
+                    elements[i] = element;
+                    Value::Tuple(elements)
+                })
+            })
+            .collect(),
+        _ => vec![],
+    };
+
+    // Remove the candidates that repeat an earlier one, e.g. both halving and decrementing `2u8` give `1u8`.
+    let mut distinct = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        if !distinct.contains(&candidate) {
+            distinct.push(candidate);
+        }
+    }
+    distinct
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinking() {
+        let u8 = |value: u8| Value::U8(value, Span::default());
+        let i8 = |value: i8| Value::I8(value, Span::default());
+
+        assert_eq!(shrink_value(&u8(0)), vec![]);
+        assert_eq!(shrink_value(&u8(10)), vec![u8(0), u8(5), u8(9)]);
+        assert_eq!(shrink_value(&i8(-10)), vec![i8(0), i8(10), i8(-5), i8(-9)]);
+        assert_eq!(shrink_value(&i8(i8::MIN)), vec![i8(0), i8(-64), i8(-127)]);
+        assert_eq!(
+            shrink_value(&Value::Tuple(vec![u8(2), Value::Boolean(true, Span::default())])),
+            vec![
+                Value::Tuple(vec![u8(0), Value::Boolean(true, Span::default())]),
+                Value::Tuple(vec![u8(1), Value::Boolean(true, Span::default())]),
+                Value::Tuple(vec![u8(2), Value::Boolean(false, Span::default())]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,929 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    evaluate_binary, evaluate_cast, evaluate_unary, expect_boolean, value_to_source, values_equal, Debugger, Execution,
+    Mappings, StackFrame, StateStorage,
+};
+
+use leo_ast::{
+    AccessExpression, AssertMessage, AssociatedFunction, BinaryOperation, Block, CallExpression, ConsoleFunction,
+    ConsoleStatement, Expression, Function, Identifier, IterationStatement, Literal, Node, ProgramScope, Statement,
+    StructExpression, Type, Value,
+};
+use leo_errors::{InterpreterError, Result};
+use leo_span::{sym, Span, Symbol};
+
+use indexmap::IndexMap;
+use std::cell::RefCell;
+
+/// Evaluates the functions of a program scope on concrete values.
+/// The mappings of the program are kept in a [`StateStorage`], in memory unless another store is given.
+pub struct Interpreter<'a> {
+    /// The program scope whose functions are called.
+    scope: &'a ProgramScope,
+    /// The entries of the program's mappings, which finalize blocks update.
+    storage: RefCell<Box<dyn StateStorage + 'a>>,
+    /// The updates of the finalize block being evaluated, which are only written to the store once it succeeds.
+    /// Each is keyed by its mapping and the source code of its key, and holds the new value, or `None` if it is removed.
+    updates: RefCell<IndexMap<(Symbol, String), (Value, Option<Value>)>>,
+    /// The debugger that pauses the evaluation, if one is attached.
+    debugger: Option<RefCell<Debugger<'a>>>,
+    /// The calls that are being evaluated, from the outermost to the innermost, which are only tracked for a debugger.
+    stack: RefCell<Vec<StackFrame>>,
+}
+
+/// The variables of a function call, with a map for each block that is being evaluated.
+#[derive(Default)]
+struct Frame {
+    /// The variables defined in each block, from the outermost to the innermost.
+    scopes: Vec<IndexMap<Symbol, Value>>,
+    /// The arguments of the finalize block, once a finalize statement has been evaluated.
+    finalize: Option<Vec<Value>>,
+}
+
+impl Frame {
+    /// Defines the variable `name` in the innermost block.
+    fn define(&mut self, name: Symbol, value: Value) {
+        if self.scopes.is_empty() {
+            self.scopes.push(IndexMap::new());
+        }
+        // Note that this unwrap is safe since a block was pushed above if there was none.
+        self.scopes.last_mut().unwrap().insert(name, value);
+    }
+
+    /// Returns the value of the variable `name`, looking from the innermost block outwards.
+    fn lookup(&self, name: Symbol) -> Option<&Value> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(&name))
+    }
+
+    /// Returns the variables in scope, with those of inner blocks shadowing those of outer blocks.
+    fn variables(&self) -> IndexMap<Symbol, Value> {
+        let mut variables = IndexMap::new();
+        for scope in self.scopes.iter() {
+            variables.extend(scope.iter().map(|(name, value)| (*name, value.clone())));
+        }
+        variables
+    }
+
+    /// Replaces the value of the variable `name`, returning `None` if it is not defined.
+    fn assign(&mut self, name: Symbol, value: Value) -> Option<()> {
+        let variable = self.scopes.iter_mut().rev().find_map(|scope| scope.get_mut(&name))?;
+        *variable = value;
+        Some(())
+    }
+}
+
+impl<'a> Interpreter<'a> {
+    /// Returns a new interpreter for the functions of `scope`, whose mappings start out empty.
+    pub fn new(scope: &'a ProgramScope) -> Self {
+        Self::with_storage(scope, Box::new(Mappings::default()))
+    }
+
+    /// Returns a new interpreter for the functions of `scope`, whose mappings are kept in `storage`.
+    pub fn with_storage(scope: &'a ProgramScope, storage: Box<dyn StateStorage + 'a>) -> Self {
+        Self {
+            scope,
+            storage: RefCell::new(storage),
+            updates: RefCell::default(),
+            debugger: None,
+            stack: RefCell::default(),
+        }
+    }
+
+    /// Attaches `debugger`, which pauses the evaluation of the functions that are called.
+    pub fn with_debugger(mut self, debugger: Debugger<'a>) -> Self {
+        self.debugger = Some(RefCell::new(debugger));
+        self
+    }
+
+    /// Returns the store of the program's mappings, e.g. to run another program on the same state.
+    pub fn into_storage(self) -> Box<dyn StateStorage + 'a> {
+        self.storage.into_inner()
+    }
+
+    /// Returns the entries of the program's mappings, as updated by the finalize blocks run so far.
+    pub fn mappings(&self) -> Result<Mappings> {
+        self.storage.borrow().snapshot()
+    }
+
+    /// Calls the function `name` with `arguments`, returning its output.
+    /// A function without an output returns the empty tuple.
+    pub fn call(&self, name: Symbol, arguments: Vec<Value>, span: Span) -> Result<Value> {
+        let function = self
+            .function(name)
+            .ok_or_else(|| InterpreterError::unknown_function(name, span))?;
+        if function.input.len() != arguments.len() {
+            return Err(InterpreterError::incorrect_num_args(name, function.input.len(), arguments.len(), span).into());
+        }
+
+        if self.debugger.is_some() {
+            self.stack.borrow_mut().push(StackFrame {
+                function: name,
+                span: function.span,
+                variables: IndexMap::new(),
+            });
+        }
+        let output = self.call_function(function, arguments);
+        if self.debugger.is_some() {
+            self.stack.borrow_mut().pop();
+        }
+        output
+    }
+
+    /// Evaluates the block of `function` on `arguments`, and then its finalize block if it reaches a finalize statement.
+    fn call_function(&self, function: &Function, arguments: Vec<Value>) -> Result<Value> {
+        let mut frame = Frame::default();
+        for (input, argument) in function.input.iter().zip(arguments) {
+            frame.define(input.identifier().name, argument);
+        }
+
+        let output = match self.evaluate_block(&mut frame, &function.block)? {
+            Some(output) => output,
+            None if function.output_type == Type::Unit => Value::Tuple(Vec::new()),
+            None => return Err(InterpreterError::missing_return(function.identifier.name, function.span).into()),
+        };
+
+        // The finalize block runs once the function has returned, and its updates are discarded if it fails.
+        if let (Some(arguments), Some(finalize)) = (frame.finalize, &function.finalize) {
+            let mut frame = Frame::default();
+            for (input, argument) in finalize.input.iter().zip(arguments) {
+                frame.define(input.identifier().name, argument);
+            }
+            let result = self.evaluate_block(&mut frame, &finalize.block);
+            let updates = self.updates.take();
+            result?;
+            let mut storage = self.storage.borrow_mut();
+            for ((mapping, _), (key, value)) in updates {
+                match value {
+                    Some(value) => storage.set(mapping, key, value)?,
+                    None => storage.remove(mapping, &key)?,
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Calls the function `name` with `arguments` like [`Interpreter::call`], and records the call,
+    /// so that it can be exported as a regression test.
+    pub fn execute(&self, name: Symbol, arguments: Vec<Value>, span: Span) -> Result<Execution> {
+        let output = self.call(name, arguments.clone(), span)?;
+        // Note that this unwrap is safe since the call fails if there is no such function.
+        let function = self.function(name).unwrap();
+        Ok(Execution {
+            function: name,
+            inputs: function.input.iter().cloned().zip(arguments).collect(),
+            output,
+            mappings: self.mappings()?,
+        })
+    }
+
+    /// Evaluates `expression` with the values of `variables`, e.g. those defined by earlier lines of a REPL.
+    /// The expression can call the functions of the program scope.
+    pub fn evaluate(&self, expression: &Expression, variables: &IndexMap<Symbol, Value>) -> Result<Value> {
+        let mut frame = Frame {
+            scopes: vec![variables.clone()],
+            finalize: None,
+        };
+        self.evaluate_expression(&mut frame, expression)
+    }
+
+    /// Returns the function `name` of the program scope.
+    fn function(&self, name: Symbol) -> Option<&'a Function> {
+        self.scope
+            .functions
+            .values()
+            .find(|function| function.identifier.name == name)
+    }
+
+    /// Evaluates the statements of `block` in a new scope, returning the returned value, if any.
+    fn evaluate_block(&self, frame: &mut Frame, block: &Block) -> Result<Option<Value>> {
+        frame.scopes.push(IndexMap::new());
+        let mut output = Ok(None);
+        for statement in block.statements.iter() {
+            output = self.evaluate_statement(frame, statement);
+            if !matches!(output, Ok(None)) {
+                break;
+            }
+        }
+        frame.scopes.pop();
+        output
+    }
+
+    /// Evaluates `statement`, returning the returned value, if it is a return statement or contains one that is reached.
+    fn evaluate_statement(&self, frame: &mut Frame, statement: &Statement) -> Result<Option<Value>> {
+        // A debugger pauses before the statements of a block, rather than before the block itself.
+        if !matches!(statement, Statement::Block(_)) {
+            self.debug(frame, statement.span())?;
+        }
+
+        match statement {
+            Statement::Assign(assign) => {
+                let value = self.evaluate_expression(frame, &assign.value)?;
+                match &assign.place {
+                    Expression::Identifier(identifier) => frame
+                        .assign(identifier.name, value)
+                        .ok_or_else(|| InterpreterError::unknown_variable(identifier.name, identifier.span))?,
+                    place => {
+                        return Err(
+                            InterpreterError::unsupported_construct("assignments to members", place.span()).into(),
+                        )
+                    }
+                }
+                Ok(None)
+            }
+            Statement::Block(block) => self.evaluate_block(frame, block),
+            Statement::Conditional(conditional) => {
+                let condition = self.evaluate_expression(frame, &conditional.condition)?;
+                match expect_boolean(&condition, conditional.condition.span())? {
+                    true => self.evaluate_block(frame, &conditional.then),
+                    false => match &conditional.otherwise {
+                        Some(otherwise) => self.evaluate_statement(frame, otherwise),
+                        None => Ok(None),
+                    },
+                }
+            }
+            Statement::Console(console) => self.evaluate_console(frame, console).map(|_| None),
+            Statement::Decrement(decrement) => self
+                .update_mapping(
+                    frame,
+                    BinaryOperation::Sub,
+                    &decrement.mapping,
+                    &decrement.index,
+                    &decrement.amount,
+                )
+                .map(|_| None),
+            Statement::Definition(definition) => {
+                let value = self.evaluate_expression(frame, &definition.value)?;
+                frame.define(definition.variable_name.name, value);
+                Ok(None)
+            }
+            Statement::Expression(statement) => self.evaluate_expression(frame, &statement.expression).map(|_| None),
+            Statement::Finalize(finalize) => {
+                let arguments = finalize
+                    .arguments
+                    .iter()
+                    .map(|argument| self.evaluate_expression(frame, argument))
+                    .collect::<Result<_>>()?;
+                frame.finalize = Some(arguments);
+                Ok(None)
+            }
+            Statement::Increment(increment) => self
+                .update_mapping(
+                    frame,
+                    BinaryOperation::Add,
+                    &increment.mapping,
+                    &increment.index,
+                    &increment.amount,
+                )
+                .map(|_| None),
+            Statement::Iteration(iteration) => self.evaluate_iteration(frame, iteration),
+            Statement::Match(_) => {
+                unreachable!("`MatchStatement`s are lowered to conditional statements before interpretation.")
+            }
+            Statement::Return(return_) => self.evaluate_expression(frame, &return_.expression).map(Some),
+        }
+    }
+
+    /// Shows `frame`, which is about to evaluate the statement at `span`, to the debugger, if one is attached,
+    /// returning an error if the debugger stops the run.
+    fn debug(&self, frame: &Frame, span: Span) -> Result<()> {
+        let debugger = match &self.debugger {
+            Some(debugger) => debugger,
+            None => return Ok(()),
+        };
+        let mut stack = self.stack.borrow_mut();
+        if let Some(call) = stack.last_mut() {
+            call.span = span;
+            call.variables = frame.variables();
+        }
+        match debugger.borrow_mut().before_statement(&stack)? {
+            true => Err(InterpreterError::stopped_by_debugger(span).into()),
+            false => Ok(()),
+        }
+    }
+
+    /// Evaluates the loop `iteration`, binding its variable to each value of its range in turn.
+    fn evaluate_iteration(&self, frame: &mut Frame, iteration: &IterationStatement) -> Result<Option<Value>> {
+        let integer_type = match &iteration.type_ {
+            Type::Integer(integer_type) => *integer_type,
+            type_ => return Err(InterpreterError::type_mismatch("integer", type_, iteration.span).into()),
+        };
+        let one = Value::from(&Literal::Integer(
+            integer_type,
+            "1".to_string(),
+            iteration.span,
+            Default::default(),
+        ));
+
+        let mut current = self.evaluate_expression(frame, &iteration.start)?;
+        let stop = self.evaluate_expression(frame, &iteration.stop)?;
+        let condition = match iteration.inclusive {
+            true => BinaryOperation::Lte,
+            false => BinaryOperation::Lt,
+        };
+        while expect_boolean(
+            &evaluate_binary(condition, current.clone(), stop.clone(), iteration.span)?,
+            iteration.span,
+        )? {
+            frame.scopes.push(IndexMap::new());
+            frame.define(iteration.variable.name, current.clone());
+            let output = self.evaluate_block(frame, &iteration.block);
+            frame.scopes.pop();
+            if let Some(output) = output? {
+                return Ok(Some(output));
+            }
+
+            // Stop before incrementing past the largest value of the type.
+            if values_equal(&current, &stop, iteration.span)? {
+                break;
+            }
+            current = evaluate_binary(BinaryOperation::Add, current, one.clone(), iteration.span)?;
+        }
+        Ok(None)
+    }
+
+    /// Adds `amount` to or subtracts it from the value of `index` in `mapping`, as `op` says.
+    /// A key that has not been set counts as zero, so the first decrement of a key underflows.
+    fn update_mapping(
+        &self,
+        frame: &mut Frame,
+        op: BinaryOperation,
+        mapping: &Identifier,
+        index: &Expression,
+        amount: &Expression,
+    ) -> Result<()> {
+        let key = self.evaluate_expression(frame, index)?;
+        let value = self.evaluate_expression(frame, amount)?;
+        let current = match (self.mapping_value(mapping.name, &key)?, Type::from(&value)) {
+            (Some(current), _) => current,
+            (None, Type::Integer(integer_type)) => Value::from(&Literal::Integer(
+                integer_type,
+                "0".to_string(),
+                mapping.span,
+                Default::default(),
+            )),
+            (None, _) => return Err(InterpreterError::type_mismatch("integer", value, amount.span()).into()),
+        };
+        let value = evaluate_binary(op, current, value, amount.span())?;
+        self.write_mapping_value(mapping.name, key, Some(value));
+        Ok(())
+    }
+
+    /// Returns the value of `key` in `mapping`, as updated by the finalize block being evaluated.
+    fn mapping_value(&self, mapping: Symbol, key: &Value) -> Result<Option<Value>> {
+        match self.updates.borrow().get(&(mapping, value_to_source(key))) {
+            Some((_, value)) => Ok(value.clone()),
+            None => self.storage.borrow().get(mapping, key),
+        }
+    }
+
+    /// Sets the value of `key` in `mapping`, or removes it if `value` is `None`, once the finalize block succeeds.
+    fn write_mapping_value(&self, mapping: Symbol, key: Value, value: Option<Value>) {
+        self.updates
+            .borrow_mut()
+            .insert((mapping, value_to_source(&key)), (key, value));
+    }
+
+    /// Evaluates an operation on a mapping, e.g. `Mapping::get(balances, addr)`.
+    fn evaluate_mapping_operation(&self, frame: &mut Frame, function: &AssociatedFunction) -> Result<Value> {
+        let (mapping, key, value) = match function.args.as_slice() {
+            [Expression::Identifier(mapping), key] => (mapping.name, self.evaluate_expression(frame, key)?, None),
+            [Expression::Identifier(mapping), key, value] => (
+                mapping.name,
+                self.evaluate_expression(frame, key)?,
+                Some(self.evaluate_expression(frame, value)?),
+            ),
+            _ => return Err(InterpreterError::unsupported_construct("this mapping operation", function.span).into()),
+        };
+
+        match (function.name.name, value) {
+            (sym::get, None) => self.mapping_value(mapping, &key)?.ok_or_else(|| {
+                InterpreterError::missing_mapping_key(mapping, value_to_source(&key), function.span).into()
+            }),
+            (sym::get_or_use, Some(default)) => Ok(self.mapping_value(mapping, &key)?.unwrap_or(default)),
+            (sym::contains, None) => Ok(Value::Boolean(
+                self.mapping_value(mapping, &key)?.is_some(),
+                function.span,
+            )),
+            (sym::set, Some(value)) => {
+                self.write_mapping_value(mapping, key, Some(value));
+                Ok(Value::Tuple(Vec::new()))
+            }
+            (sym::remove, None) => {
+                self.write_mapping_value(mapping, key, None);
+                Ok(Value::Tuple(Vec::new()))
+            }
+            _ => Err(InterpreterError::unsupported_construct("this mapping operation", function.span).into()),
+        }
+    }
+
+    /// Evaluates the assertion of `console`, returning an error if it does not hold.
+    fn evaluate_console(&self, frame: &mut Frame, console: &ConsoleStatement) -> Result<()> {
+        let holds = match &console.function {
+            ConsoleFunction::Assert(expr, _) => {
+                let value = self.evaluate_expression(frame, expr)?;
+                expect_boolean(&value, expr.span())?
+            }
+            ConsoleFunction::AssertEq(left, right, _) | ConsoleFunction::AssertNeq(left, right, _) => {
+                let left = self.evaluate_expression(frame, left)?;
+                let right = self.evaluate_expression(frame, right)?;
+                values_equal(&left, &right, console.span)? == matches!(console.function, ConsoleFunction::AssertEq(..))
+            }
+        };
+        if holds {
+            return Ok(());
+        }
+
+        let message = match console.function.message() {
+            Some(message) => Some(self.render_message(frame, message)?),
+            None => None,
+        };
+        Err(InterpreterError::assertion_failed(&console.function, message, console.span).into())
+    }
+
+    /// Renders the message of a failed assertion, substituting each placeholder with the value of its parameter.
+    fn render_message(&self, frame: &mut Frame, message: &AssertMessage) -> Result<String> {
+        let mut parameters = message.parameters.iter();
+        let mut pieces = message.string.split("{}");
+        let mut rendered = pieces.next().unwrap_or_default().to_string();
+        for piece in pieces {
+            match parameters.next() {
+                Some(parameter) => rendered.push_str(&self.evaluate_expression(frame, parameter)?.to_string()),
+                None => rendered.push_str("{}"),
+            }
+            rendered.push_str(piece);
+        }
+        Ok(rendered)
+    }
+
+    /// Returns the value of `expression`.
+    fn evaluate_expression(&self, frame: &mut Frame, expression: &Expression) -> Result<Value> {
+        match expression {
+            Expression::Access(access) => self.evaluate_access(frame, access),
+            Expression::Binary(binary) => {
+                // Both operands are evaluated, i.e. `&&` and `||` do not short-circuit, as in snarkVM.
+                let left = self.evaluate_expression(frame, &binary.left)?;
+                let right = self.evaluate_expression(frame, &binary.right)?;
+                evaluate_binary(binary.op, left, right, binary.span)
+            }
+            Expression::Call(call) => self.evaluate_call(frame, call),
+            Expression::Cast(cast) => {
+                let operand = self.evaluate_expression(frame, &cast.expression)?;
+                evaluate_cast(operand, cast.type_, cast.span)
+            }
+            Expression::Struct(struct_) => self.evaluate_struct(frame, struct_),
+            Expression::Err(err) => {
+                Err(InterpreterError::unsupported_construct("invalid expressions", err.span).into())
+            }
+            Expression::Identifier(identifier) => frame
+                .lookup(identifier.name)
+                .cloned()
+                .ok_or_else(|| InterpreterError::unknown_variable(identifier.name, identifier.span).into()),
+            Expression::Literal(literal) => match (Value::from_literal(literal), literal) {
+                (Some(value), _) => Ok(value),
+                (None, Literal::Integer(integer_type, _, span, _)) => {
+                    Err(InterpreterError::type_mismatch(integer_type, literal, *span).into())
+                }
+                (None, _) => unreachable!("only integer literals can be out of the range of their type"),
+            },
+            Expression::Ternary(ternary) => {
+                // Both branches are evaluated, as in snarkVM.
+                let condition = self.evaluate_expression(frame, &ternary.condition)?;
+                let if_true = self.evaluate_expression(frame, &ternary.if_true)?;
+                let if_false = self.evaluate_expression(frame, &ternary.if_false)?;
+                match expect_boolean(&condition, ternary.condition.span())? {
+                    true => Ok(if_true),
+                    false => Ok(if_false),
+                }
+            }
+            Expression::Tuple(tuple) => Ok(Value::Tuple(
+                tuple
+                    .elements
+                    .iter()
+                    .map(|element| self.evaluate_expression(frame, element))
+                    .collect::<Result<_>>()?,
+            )),
+            Expression::Unary(unary) => {
+                let receiver = self.evaluate_expression(frame, &unary.receiver)?;
+                evaluate_unary(unary.op, receiver, unary.span)
+            }
+        }
+    }
+
+    /// Returns the value of the member of a struct or of the element of a tuple.
+    fn evaluate_access(&self, frame: &mut Frame, access: &AccessExpression) -> Result<Value> {
+        match access {
+            AccessExpression::Member(member) => {
+                // `self.caller` depends on the transaction that calls the program.
+                if matches!(member.inner.as_ref(), Expression::Identifier(identifier) if identifier.name == sym::SelfLower)
+                {
+                    return Err(InterpreterError::unsupported_construct("`self`", member.span).into());
+                }
+                match self.evaluate_expression(frame, &member.inner)? {
+                    Value::Struct(_, members) => members
+                        .get(&member.name.name)
+                        .cloned()
+                        .ok_or_else(|| InterpreterError::unknown_variable(member.name.name, member.name.span).into()),
+                    value => Err(InterpreterError::type_mismatch("struct", value, member.inner.span()).into()),
+                }
+            }
+            AccessExpression::Tuple(access) => match self.evaluate_expression(frame, &access.tuple)? {
+                Value::Tuple(mut elements) if access.index.to_usize() < elements.len() => {
+                    Ok(elements.swap_remove(access.index.to_usize()))
+                }
+                value => Err(InterpreterError::type_mismatch("tuple", value, access.tuple.span()).into()),
+            },
+            AccessExpression::AssociatedConstant(constant) => {
+                Err(InterpreterError::unsupported_construct("associated constants", constant.span).into())
+            }
+            AccessExpression::AssociatedFunction(function) => match &function.ty {
+                Type::Identifier(module) if module.name == sym::Mapping => {
+                    self.evaluate_mapping_operation(frame, function)
+                }
+                _ => Err(InterpreterError::unsupported_construct("core functions", function.span).into()),
+            },
+        }
+    }
+
+    /// Evaluates the arguments of `call` and calls the function of the program scope.
+    fn evaluate_call(&self, frame: &mut Frame, call: &CallExpression) -> Result<Value> {
+        let name = match (call.function.as_ref(), &call.external) {
+            (Expression::Identifier(identifier), None) => identifier.name,
+            _ => return Err(InterpreterError::unsupported_construct("calls to other programs", call.span).into()),
+        };
+        let arguments = call
+            .arguments
+            .iter()
+            .map(|argument| self.evaluate_expression(frame, argument))
+            .collect::<Result<_>>()?;
+        self.call(name, arguments, call.span)
+    }
+
+    /// Returns the struct initialized by `input`.
+    fn evaluate_struct(&self, frame: &mut Frame, input: &StructExpression) -> Result<Value> {
+        let mut members = IndexMap::new();
+        for member in input.members.iter() {
+            let value = match &member.expression {
+                Some(expression) => self.evaluate_expression(frame, expression)?,
+                // `Foo { a }` is short for `Foo { a: a }`.
+                None => frame.lookup(member.identifier.name).cloned().ok_or_else(|| {
+                    InterpreterError::unknown_variable(member.identifier.name, member.identifier.span)
+                })?,
+            };
+            members.insert(member.identifier.name, value);
+        }
+        Ok(Value::Struct(input.name, members))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Breakpoint, DebugFrontend, DebugStep, PauseReason};
+    use leo_errors::emitter::Handler;
+    use leo_span::{
+        source_map::FileName,
+        symbol::{create_session_if_not_set_then, with_session_globals},
+    };
+
+    const PROGRAM: &str = "
+program test.aleo {
+    struct Point {
+        x: u8,
+        y: u8,
+    }
+
+    function sum(p: Point) -> u8 {
+        return p.x + p.y;
+    }
+
+    transition main(a: u8) -> u8 {
+        let total: u8 = 0u8;
+        for i: u8 in 0u8..4u8 {
+            total = total + a;
+        }
+        let p: Point = Point { x: total, y: 1u8 };
+        return sum(p);
+    }
+
+    transition check(a: u8) {
+        console.assert_eq(a, 1u8, \"expected {} to be 1\", a);
+    }
+}";
+
+    const COUNTER: &str = "
+program counter.aleo {
+    mapping counts: u8 => u64;
+
+    transition bump(a: u8, b: u64) {
+        async finalize(a, b);
+    }
+
+    finalize bump(a: u8, b: u64) {
+        increment(counts, a, 5u64);
+        decrement(counts, a, b);
+    }
+}";
+
+    const TOKEN: &str = "
+program token.aleo {
+    mapping balances: u8 => u64;
+
+    transition spend(a: u8, b: u64) {
+        async finalize(a, b);
+    }
+
+    finalize spend(a: u8, b: u64) {
+        let balance: u64 = Mapping::get_or_use(balances, a, 10u64);
+        Mapping::set(balances, a, balance - b);
+    }
+
+    transition close(a: u8) {
+        async finalize(a);
+    }
+
+    finalize close(a: u8) {
+        console.assert(Mapping::contains(balances, a));
+        let balance: u64 = Mapping::get(balances, a);
+        console.assert_eq(balance, 0u64);
+        Mapping::remove(balances, a);
+    }
+}";
+
+    /// Calls the function `name` of `PROGRAM` with the `u8` argument `a`.
+    fn call(name: &str, a: u8) -> Result<Value> {
+        create_session_if_not_set_then(|_| {
+            let sf = with_session_globals(|s| s.source_map.new_source(PROGRAM, FileName::Custom("test".into())));
+            let ast = leo_parser::parse_ast(&Handler::default(), &sf.src, sf.start_pos).unwrap();
+            let scope = ast.as_repr().program_scopes.values().next().unwrap();
+            let output = Interpreter::new(scope).call(
+                Symbol::intern(name),
+                vec![Value::U8(a, Span::default())],
+                Span::default(),
+            );
+            output
+        })
+    }
+
+    #[test]
+    fn calls() {
+        assert!(matches!(call("main", 3), Ok(Value::U8(13, _))));
+        // The loop overflows on its last iteration.
+        assert!(call("main", 64).is_err());
+        assert_eq!(call("check", 1).unwrap(), Value::Tuple(Vec::new()));
+    }
+
+    #[test]
+    fn failed_assertion() {
+        let err = call("check", 2).unwrap_err();
+        assert!(err.message().ends_with("failed: expected 2 to be 1"));
+    }
+
+    #[test]
+    fn evaluate() {
+        create_session_if_not_set_then(|_| {
+            let sf = with_session_globals(|s| s.source_map.new_source(PROGRAM, FileName::Custom("test".into())));
+            let ast = leo_parser::parse_ast(&Handler::default(), &sf.src, sf.start_pos).unwrap();
+            let scope = ast.as_repr().program_scopes.values().next().unwrap();
+            let evaluate = |source: &str, variables: &IndexMap<Symbol, Value>| {
+                let sf = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom("line".into())));
+                let expression = leo_parser::parse_expression(&Handler::default(), &sf.src, sf.start_pos).unwrap();
+                Interpreter::new(scope).evaluate(&expression, variables)
+            };
+
+            let mut variables = IndexMap::new();
+            variables.insert(Symbol::intern("a"), Value::U8(200, Span::default()));
+            assert!(matches!(evaluate("a + 55u8", &variables), Ok(Value::U8(255, _))));
+            assert!(evaluate("a + 56u8", &variables).is_err());
+            assert!(matches!(evaluate("main(a / 100u8)", &variables), Ok(Value::U8(9, _))));
+            assert!(evaluate("b", &variables).is_err());
+        })
+    }
+
+    #[test]
+    fn finalize() {
+        create_session_if_not_set_then(|_| {
+            let sf = with_session_globals(|s| s.source_map.new_source(COUNTER, FileName::Custom("test".into())));
+            let ast = leo_parser::parse_ast(&Handler::default(), &sf.src, sf.start_pos).unwrap();
+            let scope = ast.as_repr().program_scopes.values().next().unwrap();
+            let interpreter = Interpreter::new(scope);
+            let bump = |b: u64| {
+                interpreter.call(
+                    Symbol::intern("bump"),
+                    vec![Value::U8(1, Span::default()), Value::U64(b, Span::default())],
+                    Span::default(),
+                )
+            };
+            let count = || {
+                interpreter
+                    .mappings()
+                    .unwrap()
+                    .get(Symbol::intern("counts"), &Value::U8(1, Span::default()))
+                    .cloned()
+            };
+
+            bump(2).unwrap();
+            assert!(matches!(count(), Some(Value::U64(3, _))));
+            // The decrement underflows, so the increment before it is discarded as well.
+            assert!(bump(9).is_err());
+            assert!(matches!(count(), Some(Value::U64(3, _))));
+        })
+    }
+
+    #[test]
+    fn mapping_operations() {
+        create_session_if_not_set_then(|_| {
+            let sf = with_session_globals(|s| s.source_map.new_source(TOKEN, FileName::Custom("test".into())));
+            let ast = leo_parser::parse_ast(&Handler::default(), &sf.src, sf.start_pos).unwrap();
+            let scope = ast.as_repr().program_scopes.values().next().unwrap();
+            let interpreter = Interpreter::new(scope);
+            let spend = |b: u64| {
+                interpreter.call(
+                    Symbol::intern("spend"),
+                    vec![Value::U8(1, Span::default()), Value::U64(b, Span::default())],
+                    Span::default(),
+                )
+            };
+            let close = || {
+                interpreter.call(
+                    Symbol::intern("close"),
+                    vec![Value::U8(1, Span::default())],
+                    Span::default(),
+                )
+            };
+            let balance = || {
+                interpreter
+                    .mappings()
+                    .unwrap()
+                    .get(Symbol::intern("balances"), &Value::U8(1, Span::default()))
+                    .cloned()
+            };
+
+            // A key that has not been set reads as the default of `get_or_use`, but halts `get`.
+            assert!(close().is_err());
+            spend(4).unwrap();
+            assert!(matches!(balance(), Some(Value::U64(6, _))));
+            assert!(close().is_err());
+            assert!(matches!(balance(), Some(Value::U64(6, _))));
+            spend(6).unwrap();
+            close().unwrap();
+            assert!(balance().is_none());
+        })
+    }
+
+    #[test]
+    fn export() {
+        create_session_if_not_set_then(|_| {
+            let sf = with_session_globals(|s| s.source_map.new_source(PROGRAM, FileName::Custom("test".into())));
+            let ast = leo_parser::parse_ast(&Handler::default(), &sf.src, sf.start_pos).unwrap();
+            let scope = ast.as_repr().program_scopes.values().next().unwrap();
+            let execution = Interpreter::new(scope)
+                .execute(
+                    Symbol::intern("main"),
+                    vec![Value::U8(3, Span::default())],
+                    Span::default(),
+                )
+                .unwrap();
+
+            assert_eq!(execution.input_file(), "[main]\na: u8 = 3u8;\n");
+            assert!(execution
+                .test_header("inputs/main.in")
+                .ends_with("input_file: inputs/main.in\nexecute: main\nexpected_output: \"13u8\"\n*/\n"));
+        })
+    }
+
+    #[test]
+    fn restore() {
+        create_session_if_not_set_then(|_| {
+            let sf = with_session_globals(|s| s.source_map.new_source(COUNTER, FileName::Custom("test".into())));
+            let ast = leo_parser::parse_ast(&Handler::default(), &sf.src, sf.start_pos).unwrap();
+            let scope = ast.as_repr().program_scopes.values().next().unwrap();
+            let bump = |interpreter: &Interpreter, b: u64| {
+                interpreter
+                    .call(
+                        Symbol::intern("bump"),
+                        vec![Value::U8(1, Span::default()), Value::U64(b, Span::default())],
+                        Span::default(),
+                    )
+                    .unwrap();
+            };
+
+            let interpreter = Interpreter::new(scope);
+            bump(&interpreter, 2);
+            let snapshot = interpreter.mappings().unwrap();
+            bump(&interpreter, 1);
+
+            // A new interpreter on the restored store continues from the snapshot.
+            let mut storage = interpreter.into_storage();
+            storage.restore(&snapshot).unwrap();
+            let interpreter = Interpreter::with_storage(scope, storage);
+            bump(&interpreter, 4);
+            let count = interpreter
+                .mappings()
+                .unwrap()
+                .get(Symbol::intern("counts"), &Value::U8(1, Span::default()))
+                .cloned();
+            assert!(matches!(count, Some(Value::U64(4, _))));
+        })
+    }
+
+    #[test]
+    fn persist() {
+        create_session_if_not_set_then(|_| {
+            let sf = with_session_globals(|s| s.source_map.new_source(COUNTER, FileName::Custom("test".into())));
+            let ast = leo_parser::parse_ast(&Handler::default(), &sf.src, sf.start_pos).unwrap();
+            let scope = ast.as_repr().program_scopes.values().next().unwrap();
+            let path = std::env::temp_dir().join(format!("leo-state-{}.json", std::process::id()));
+            let bump = |b: u64| {
+                let interpreter = Interpreter::with_storage(scope, Box::new(crate::JsonStorage::open(&path).unwrap()));
+                interpreter
+                    .call(
+                        Symbol::intern("bump"),
+                        vec![Value::U8(1, Span::default()), Value::U64(b, Span::default())],
+                        Span::default(),
+                    )
+                    .map(|_| interpreter.mappings().unwrap())
+            };
+
+            // Each run starts from the entries the previous one wrote to the file.
+            bump(2).unwrap();
+            let count = bump(1)
+                .unwrap()
+                .get(Symbol::intern("counts"), &Value::U8(1, Span::default()))
+                .cloned();
+            assert!(matches!(count, Some(Value::U64(7, _))));
+            // The failed run leaves the file as it was.
+            assert!(bump(13).is_err());
+            let count = bump(5)
+                .unwrap()
+                .get(Symbol::intern("counts"), &Value::U8(1, Span::default()))
+                .cloned();
+            assert!(matches!(count, Some(Value::U64(7, _))));
+
+            std::fs::remove_file(&path).unwrap();
+        })
+    }
+
+    /// A debugger frontend that records where it pauses, and takes the given steps in turn.
+    #[derive(Default)]
+    struct Recorder {
+        steps: Vec<DebugStep>,
+        pauses: Vec<(PauseReason, usize, usize)>,
+    }
+
+    impl DebugFrontend for Recorder {
+        fn pause(&mut self, reason: PauseReason, stack: &[StackFrame], _: &mut Vec<Breakpoint>) -> Result<DebugStep> {
+            let line = stack.last().unwrap().location().unwrap().line_start;
+            self.pauses.push((reason, stack.len(), line));
+            Ok(match self.steps.is_empty() {
+                true => DebugStep::Continue,
+                false => self.steps.remove(0),
+            })
+        }
+    }
+
+    #[test]
+    fn debugger() {
+        create_session_if_not_set_then(|_| {
+            let sf = with_session_globals(|s| s.source_map.new_source(PROGRAM, FileName::Custom("test".into())));
+            let ast = leo_parser::parse_ast(&Handler::default(), &sf.src, sf.start_pos).unwrap();
+            let scope = ast.as_repr().program_scopes.values().next().unwrap();
+            let mut recorder = Recorder {
+                steps: vec![DebugStep::Continue; 4],
+                ..Default::default()
+            };
+            recorder.steps.push(DebugStep::StepIn);
+            let breakpoints = vec![Breakpoint::parse("test:15").unwrap(), Breakpoint::parse("18").unwrap()];
+            let output = Interpreter::new(scope)
+                .with_debugger(Debugger::new(&mut recorder, breakpoints, false))
+                .call(
+                    Symbol::intern("main"),
+                    vec![Value::U8(2, Span::default())],
+                    Span::default(),
+                )
+                .unwrap();
+            assert!(matches!(output, Value::U8(9, _)));
+
+            // The loop body pauses on each iteration, and stepping into the call pauses in `sum`.
+            let mut expected = vec![(PauseReason::Breakpoint, 1, 15); 4];
+            expected.push((PauseReason::Breakpoint, 1, 18));
+            expected.push((PauseReason::Step, 2, 9));
+            assert_eq!(recorder.pauses, expected);
+        })
+    }
+}
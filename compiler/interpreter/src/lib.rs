@@ -0,0 +1,42 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+pub mod constant;
+pub use constant::*;
+
+pub mod debugger;
+pub use debugger::*;
+
+pub mod execution;
+pub use execution::*;
+
+pub mod generator;
+pub use generator::*;
+
+pub mod interpreter;
+pub use interpreter::*;
+
+pub mod mappings;
+pub use mappings::*;
+
+pub mod semantics;
+pub use semantics::*;
+
+pub mod storage;
+pub use storage::*;
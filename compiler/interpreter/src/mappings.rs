@@ -0,0 +1,69 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::value_to_source;
+
+use leo_ast::Value;
+use leo_span::Symbol;
+
+use std::collections::HashMap;
+
+/// The entries of a program's mappings, kept in memory instead of in the state of the chain.
+#[derive(Clone, Debug, Default)]
+pub struct Mappings {
+    /// The entries of each mapping, keyed by the source code of their key, e.g. `1u8`.
+    mappings: HashMap<Symbol, HashMap<String, (Value, Value)>>,
+}
+
+impl Mappings {
+    /// Returns the value of `key` in `mapping`, if it has been set.
+    pub fn get(&self, mapping: Symbol, key: &Value) -> Option<&Value> {
+        self.mappings
+            .get(&mapping)?
+            .get(&value_to_source(key))
+            .map(|(_, value)| value)
+    }
+
+    /// Sets the value of `key` in `mapping` to `value`.
+    pub fn set(&mut self, mapping: Symbol, key: Value, value: Value) {
+        self.mappings
+            .entry(mapping)
+            .or_default()
+            .insert(value_to_source(&key), (key, value));
+    }
+
+    /// Removes `key` from `mapping`, if it has been set.
+    pub fn remove(&mut self, mapping: Symbol, key: &Value) {
+        if let Some(entries) = self.mappings.get_mut(&mapping) {
+            entries.remove(&value_to_source(key));
+        }
+    }
+
+    /// Returns every entry as its mapping, key, and value, sorted by mapping and then by key.
+    pub fn entries(&self) -> Vec<(Symbol, &Value, &Value)> {
+        let mut entries: Vec<_> = self
+            .mappings
+            .iter()
+            .flat_map(|(mapping, entries)| {
+                entries
+                    .iter()
+                    .map(move |(source, (key, value))| (mapping.to_string(), source, (*mapping, key, value)))
+            })
+            .collect();
+        entries.sort_by(|(a, a_key, _), (b, b_key, _)| (a, a_key).cmp(&(b, b_key)));
+        entries.into_iter().map(|(_, _, entry)| entry).collect()
+    }
+}
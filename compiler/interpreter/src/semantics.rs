@@ -0,0 +1,288 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{BinaryOperation, IntegerType, Literal, Type, UnaryOperation, Value};
+use leo_errors::{InterpreterError, Result};
+use leo_span::Span;
+
+/// Returns the result of applying the unary operation `op` to `operand`.
+/// See the crate documentation for the semantics of each operation.
+pub fn evaluate_unary(op: UnaryOperation, operand: Value, span: Span) -> Result<Value> {
+    use UnaryOperation::*;
+
+    let type_ = Type::from(&operand);
+    let supported = match op {
+        Abs | AbsWrapped | Negate => is_signed_integer(&type_),
+        Not => matches!(type_, Type::Boolean | Type::Integer(_)),
+        Await | Double | Inverse | Square | SquareRoot => false,
+    };
+    if !supported {
+        return Err(InterpreterError::unsupported_operation(op.as_str(), type_, span).into());
+    }
+
+    match op {
+        Abs => operand.abs(span),
+        AbsWrapped => operand.abs_wrapped(span),
+        Negate => operand.neg(span),
+        Not => operand.not(span),
+        Await | Double | Inverse | Square | SquareRoot => unreachable!("unsupported operations are rejected above"),
+    }
+}
+
+/// Returns the result of applying the binary operation `op` to `lhs` and `rhs`.
+/// Both operands are always evaluated by the caller, i.e. `&&` and `||` do not short-circuit.
+/// See the crate documentation for the semantics of each operation.
+pub fn evaluate_binary(op: BinaryOperation, lhs: Value, rhs: Value, span: Span) -> Result<Value> {
+    use BinaryOperation::*;
+
+    match op {
+        Eq => return Ok(Value::Boolean(values_equal(&lhs, &rhs, span)?, span)),
+        Neq => return Ok(Value::Boolean(!values_equal(&lhs, &rhs, span)?, span)),
+        And | Or | Nand | Nor => {
+            let (lhs, rhs) = (expect_boolean(&lhs, span)?, expect_boolean(&rhs, span)?);
+            let result = match op {
+                And => lhs && rhs,
+                Or => lhs || rhs,
+                Nand => !(lhs && rhs),
+                _ => !(lhs || rhs),
+            };
+            return Ok(Value::Boolean(result, span));
+        }
+        _ => {}
+    }
+
+    let (lhs_type, rhs_type) = (Type::from(&lhs), Type::from(&rhs));
+    let supported = match op {
+        BitwiseAnd | BitwiseOr | Xor => matches!(lhs_type, Type::Boolean | Type::Integer(_)) && lhs_type == rhs_type,
+        Pow | PowWrapped | Shl | ShlWrapped | Shr | ShrWrapped => {
+            matches!(lhs_type, Type::Integer(_)) && is_magnitude(&rhs_type)
+        }
+        Mod => is_unsigned_integer(&lhs_type) && lhs_type == rhs_type,
+        _ => matches!(lhs_type, Type::Integer(_)) && lhs_type == rhs_type,
+    };
+    if !supported {
+        return Err(InterpreterError::unsupported_operation(op, lhs_type, span).into());
+    }
+
+    // The wrapping operations of Rust panic on division by zero, and snarkVM halts on it.
+    if matches!(op, Div | DivWrapped | Rem | RemWrapped | Mod) && is_zero(&rhs) {
+        return Err(InterpreterError::division_by_zero(op, span).into());
+    }
+
+    match op {
+        Add => lhs.add(rhs, span),
+        AddWrapped => lhs.add_wrapped(rhs, span),
+        BitwiseAnd => lhs.bitand(rhs, span),
+        BitwiseOr => lhs.bitor(rhs, span),
+        Div => lhs.div(rhs, span),
+        DivWrapped => lhs.div_wrapped(rhs, span),
+        Gte => lhs.ge(rhs, span),
+        Gt => lhs.gt(rhs, span),
+        Lte => lhs.le(rhs, span),
+        Lt => lhs.lt(rhs, span),
+        // The remainder of unsigned integers is never negative, so it is also their modulus.
+        Mod | Rem => lhs.rem(rhs, span),
+        RemWrapped => lhs.rem_wrapped(rhs, span),
+        Mul => lhs.mul(rhs, span),
+        MulWrapped => lhs.mul_wrapped(rhs, span),
+        Pow => lhs.pow(rhs, span),
+        PowWrapped => lhs.pow_wrapped(rhs, span),
+        Shl => lhs.shl(rhs, span),
+        ShlWrapped => lhs.shl_wrapped(rhs, span),
+        Shr => lhs.shr(rhs, span),
+        ShrWrapped => lhs.shr_wrapped(rhs, span),
+        Sub => lhs.sub(rhs, span),
+        SubWrapped => lhs.sub_wrapped(rhs, span),
+        Xor => lhs.xor(rhs, span),
+        Eq | Neq | And | Or | Nand | Nor => unreachable!("boolean operations are evaluated above"),
+    }
+}
+
+/// Returns `operand` converted to the integer type `type_`, which the type checker only allows if it is wider.
+/// Widening keeps the value, so it never fails on an integer of a narrower type.
+pub fn evaluate_cast(operand: Value, type_: IntegerType, span: Span) -> Result<Value> {
+    let value = match Type::from(&operand) {
+        Type::Integer(from) if from.widens_to(&type_) && from.is_signed() => i128::try_from(&operand)?.to_string(),
+        Type::Integer(from) if from.widens_to(&type_) => u128::try_from(&operand)?.to_string(),
+        from => return Err(InterpreterError::unsupported_operation(format!("as {type_}"), from, span).into()),
+    };
+    Ok(Value::from(&Literal::Integer(type_, value, span, Default::default())))
+}
+
+/// Returns `true` if `lhs` and `rhs` are structurally equal.
+/// Errors if the values cannot be compared by the interpreter, e.g. if they are fields.
+pub fn values_equal(lhs: &Value, rhs: &Value, span: Span) -> Result<bool> {
+    match (lhs, rhs) {
+        (Value::Address(lhs, _), Value::Address(rhs, _)) => Ok(lhs == rhs),
+        (Value::Signature(lhs, _), Value::Signature(rhs, _)) => Ok(lhs == rhs),
+        (Value::Struct(lhs_name, lhs), Value::Struct(rhs_name, rhs)) if lhs_name.name == rhs_name.name => {
+            for (member, lhs) in lhs.iter() {
+                match rhs.get(member) {
+                    Some(rhs) if values_equal(lhs, rhs, span)? => {}
+                    _ => return Ok(false),
+                }
+            }
+            Ok(lhs.len() == rhs.len())
+        }
+        (Value::Tuple(lhs), Value::Tuple(rhs)) if lhs.len() == rhs.len() => {
+            for (lhs, rhs) in lhs.iter().zip(rhs.iter()) {
+                if !values_equal(lhs, rhs, span)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        _ if lhs.is_supported_const_fold_type() && Type::from(lhs) == Type::from(rhs) => {
+            match lhs.clone().eq(rhs.clone(), span)? {
+                Value::Boolean(equal, _) => Ok(equal),
+                value => unreachable!("`eq` always returns a boolean, but returned `{value}`"),
+            }
+        }
+        _ => Err(InterpreterError::unsupported_operation("==", Type::from(lhs), span).into()),
+    }
+}
+
+/// Returns the boolean that `value` holds, or an error if it is not a boolean.
+pub fn expect_boolean(value: &Value, span: Span) -> Result<bool> {
+    match value {
+        Value::Boolean(value, _) => Ok(*value),
+        value => Err(InterpreterError::type_mismatch(Type::Boolean, value, span).into()),
+    }
+}
+
+/// Returns `true` if `type_` is a signed integer type.
+fn is_signed_integer(type_: &Type) -> bool {
+    matches!(
+        type_,
+        Type::Integer(IntegerType::I8 | IntegerType::I16 | IntegerType::I32 | IntegerType::I64 | IntegerType::I128)
+    )
+}
+
+/// Returns `true` if `type_` is an unsigned integer type.
+fn is_unsigned_integer(type_: &Type) -> bool {
+    matches!(type_, Type::Integer(_)) && !is_signed_integer(type_)
+}
+
+/// Returns `true` if `type_` is a type of exponents and shifts, i.e. `u8`, `u16`, or `u32`.
+fn is_magnitude(type_: &Type) -> bool {
+    matches!(
+        type_,
+        Type::Integer(IntegerType::U8 | IntegerType::U16 | IntegerType::U32)
+    )
+}
+
+/// Returns `true` if `value` is an integer equal to zero.
+fn is_zero(value: &Value) -> bool {
+    match value {
+        Value::I8(v, _) => *v == 0,
+        Value::I16(v, _) => *v == 0,
+        Value::I32(v, _) => *v == 0,
+        Value::I64(v, _) => *v == 0,
+        Value::I128(v, _) => *v == 0,
+        Value::U8(v, _) => *v == 0,
+        Value::U16(v, _) => *v == 0,
+        Value::U32(v, _) => *v == 0,
+        Value::U64(v, _) => *v == 0,
+        Value::U128(v, _) => *v == 0,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BinaryOperation::*;
+
+    fn u8(value: u8) -> Value {
+        Value::U8(value, Span::default())
+    }
+
+    fn i8(value: i8) -> Value {
+        Value::I8(value, Span::default())
+    }
+
+    fn boolean(value: bool) -> Value {
+        Value::Boolean(value, Span::default())
+    }
+
+    fn binary(op: BinaryOperation, lhs: Value, rhs: Value) -> Result<Value> {
+        evaluate_binary(op, lhs, rhs, Span::default())
+    }
+
+    #[test]
+    fn overflow() {
+        assert!(binary(Add, u8(255), u8(1)).is_err());
+        assert_eq!(binary(AddWrapped, u8(255), u8(1)).unwrap(), u8(0));
+        assert!(binary(Sub, u8(0), u8(1)).is_err());
+        assert_eq!(binary(SubWrapped, u8(0), u8(1)).unwrap(), u8(255));
+        assert!(binary(Div, i8(i8::MIN), i8(-1)).is_err());
+        assert_eq!(binary(DivWrapped, i8(i8::MIN), i8(-1)).unwrap(), i8(i8::MIN));
+        assert!(binary(Pow, u8(2), u8(8)).is_err());
+        assert!(evaluate_unary(UnaryOperation::Abs, i8(i8::MIN), Span::default()).is_err());
+        assert_eq!(
+            evaluate_unary(UnaryOperation::AbsWrapped, i8(i8::MIN), Span::default()).unwrap(),
+            i8(i8::MIN)
+        );
+    }
+
+    #[test]
+    fn division_by_zero() {
+        for op in [Div, DivWrapped, Rem, RemWrapped, Mod] {
+            assert!(binary(op, u8(1), u8(0)).is_err());
+        }
+        assert_eq!(binary(Rem, i8(-7), i8(2)).unwrap(), i8(-1));
+        assert_eq!(binary(Mod, u8(7), u8(2)).unwrap(), u8(1));
+    }
+
+    #[test]
+    fn shifts() {
+        assert!(binary(Shl, u8(1), u8(8)).is_err());
+        assert_eq!(binary(ShlWrapped, u8(1), u8(9)).unwrap(), u8(2));
+        assert_eq!(binary(Shr, i8(-8), u8(1)).unwrap(), i8(-4));
+    }
+
+    #[test]
+    fn booleans_and_equality() {
+        assert_eq!(binary(Nand, boolean(true), boolean(true)).unwrap(), boolean(false));
+        assert_eq!(binary(Nor, boolean(false), boolean(false)).unwrap(), boolean(true));
+        assert_eq!(binary(Neq, u8(1), u8(2)).unwrap(), boolean(true));
+
+        // Fields are only evaluated by snarkVM.
+        let field = Value::Field("1".to_string(), Span::default());
+        assert!(binary(Eq, field.clone(), field).is_err());
+    }
+
+    #[test]
+    fn widening() {
+        let cast = |operand, type_| evaluate_cast(operand, type_, Span::default());
+        assert_eq!(
+            cast(u8(255), IntegerType::U64).unwrap(),
+            Value::U64(255, Span::default())
+        );
+        assert_eq!(
+            cast(u8(255), IntegerType::I16).unwrap(),
+            Value::I16(255, Span::default())
+        );
+        assert_eq!(
+            cast(i8(-128), IntegerType::I128).unwrap(),
+            Value::I128(-128, Span::default())
+        );
+
+        // Only lossless conversions are casts.
+        assert!(cast(u8(1), IntegerType::I8).is_err());
+        assert!(cast(i8(-1), IntegerType::U64).is_err());
+    }
+}
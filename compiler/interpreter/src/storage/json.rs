@@ -0,0 +1,117 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{parse_stored_value, value_to_source, Mappings, StateStorage};
+
+use leo_ast::Value;
+use leo_errors::{InterpreterError, Result};
+use leo_span::Symbol;
+
+use serde_json::{Map, Value as Json};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The entries of a program's mappings, kept in memory and written to a JSON file after every update.
+///
+/// The file maps the name of each mapping to an object from the source code of each key to the source code
+/// of its value, e.g. `{ "counts": { "1u8": "2u64" } }`, so that it can be read and edited by hand.
+pub struct JsonStorage {
+    path: PathBuf,
+    mappings: Mappings,
+}
+
+impl JsonStorage {
+    /// Opens the file at `path`, which is created on the first update if it does not exist.
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut mappings = Mappings::default();
+        if path.exists() {
+            let source = fs::read_to_string(path).map_err(InterpreterError::failed_to_access_state)?;
+            let json: Map<String, Json> =
+                serde_json::from_str(&source).map_err(InterpreterError::failed_to_access_state)?;
+            for (mapping, entries) in json {
+                let entries = entries
+                    .as_object()
+                    .ok_or_else(|| InterpreterError::invalid_stored_entry(&mapping))?;
+                for (key, value) in entries {
+                    let value = value
+                        .as_str()
+                        .ok_or_else(|| InterpreterError::invalid_stored_entry(value))?;
+                    mappings.set(
+                        Symbol::intern(&mapping),
+                        parse_stored_value(key)?,
+                        parse_stored_value(value)?,
+                    );
+                }
+            }
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            mappings,
+        })
+    }
+
+    /// Writes the entries to the file.
+    fn write(&self) -> Result<()> {
+        let mut json = Map::new();
+        for (mapping, key, value) in self.mappings.entries() {
+            json.entry(mapping.to_string())
+                .or_insert_with(|| Json::Object(Map::new()))
+                .as_object_mut()
+                .expect("every mapping is an object")
+                .insert(value_to_source(key), Json::String(value_to_source(value)));
+        }
+
+        let source = serde_json::to_string_pretty(&json).map_err(InterpreterError::failed_to_access_state)?;
+        fs::write(&self.path, source).map_err(|err| InterpreterError::failed_to_access_state(err).into())
+    }
+}
+
+impl StateStorage for JsonStorage {
+    fn get(&self, mapping: Symbol, key: &Value) -> Result<Option<Value>> {
+        Ok(self.mappings.get(mapping, key).cloned())
+    }
+
+    fn set(&mut self, mapping: Symbol, key: Value, value: Value) -> Result<()> {
+        self.mappings.set(mapping, key, value);
+        self.write()
+    }
+
+    fn remove(&mut self, mapping: Symbol, key: &Value) -> Result<()> {
+        self.mappings.remove(mapping, key);
+        self.write()
+    }
+
+    fn entries(&self) -> Result<Vec<(Symbol, Value, Value)>> {
+        StateStorage::entries(&self.mappings)
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.mappings = Mappings::default();
+        self.write()
+    }
+
+    fn snapshot(&self) -> Result<Mappings> {
+        Ok(self.mappings.clone())
+    }
+
+    fn restore(&mut self, snapshot: &Mappings) -> Result<()> {
+        self.mappings = snapshot.clone();
+        self.write()
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+pub mod json;
+pub use json::*;
+
+#[cfg(feature = "rocksdb")]
+pub mod rocksdb;
+#[cfg(feature = "rocksdb")]
+pub use self::rocksdb::*;
+
+use crate::{evaluate_constant, Mappings};
+
+use leo_ast::Value;
+use leo_errors::{emitter::Handler, InterpreterError, Result};
+use leo_span::{
+    source_map::FileName,
+    symbol::{with_session_globals, Symbol},
+};
+
+/// A store for the entries of a program's mappings, which the interpreter reads and the finalize blocks update.
+///
+/// [`Mappings`] keeps the entries in memory, [`JsonStorage`] keeps them in a JSON file that persists across runs,
+/// and `RocksDbStorage`, with the `rocksdb` feature, keeps them in a database on disk, so that the state of a
+/// local devnet persists across sessions.
+pub trait StateStorage {
+    /// Returns the value of `key` in `mapping`, if it has been set.
+    fn get(&self, mapping: Symbol, key: &Value) -> Result<Option<Value>>;
+
+    /// Sets the value of `key` in `mapping` to `value`.
+    fn set(&mut self, mapping: Symbol, key: Value, value: Value) -> Result<()>;
+
+    /// Removes `key` from `mapping`, if it has been set.
+    fn remove(&mut self, mapping: Symbol, key: &Value) -> Result<()>;
+
+    /// Returns every entry as its mapping, key, and value, sorted by mapping and then by key.
+    fn entries(&self) -> Result<Vec<(Symbol, Value, Value)>>;
+
+    /// Removes every entry.
+    fn clear(&mut self) -> Result<()>;
+
+    /// Returns a copy of the entries, which [`StateStorage::restore`] can later return the store to.
+    fn snapshot(&self) -> Result<Mappings> {
+        let mut mappings = Mappings::default();
+        for (mapping, key, value) in self.entries()? {
+            mappings.set(mapping, key, value);
+        }
+        Ok(mappings)
+    }
+
+    /// Replaces the entries with those of `snapshot`.
+    fn restore(&mut self, snapshot: &Mappings) -> Result<()> {
+        self.clear()?;
+        for (mapping, key, value) in snapshot.entries() {
+            self.set(mapping, key.clone(), value.clone())?;
+        }
+        Ok(())
+    }
+}
+
+impl StateStorage for Mappings {
+    fn get(&self, mapping: Symbol, key: &Value) -> Result<Option<Value>> {
+        Ok(Mappings::get(self, mapping, key).cloned())
+    }
+
+    fn set(&mut self, mapping: Symbol, key: Value, value: Value) -> Result<()> {
+        Mappings::set(self, mapping, key, value);
+        Ok(())
+    }
+
+    fn remove(&mut self, mapping: Symbol, key: &Value) -> Result<()> {
+        Mappings::remove(self, mapping, key);
+        Ok(())
+    }
+
+    fn entries(&self) -> Result<Vec<(Symbol, Value, Value)>> {
+        Ok(Mappings::entries(self)
+            .into_iter()
+            .map(|(mapping, key, value)| (mapping, key.clone(), value.clone()))
+            .collect())
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        *self = Mappings::default();
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Result<Mappings> {
+        Ok(self.clone())
+    }
+
+    fn restore(&mut self, snapshot: &Mappings) -> Result<()> {
+        *self = snapshot.clone();
+        Ok(())
+    }
+}
+
+/// Parses a value of a mapping from the source code it is stored as, e.g. `1u8`.
+pub(crate) fn parse_stored_value(source: &str) -> Result<Value> {
+    let sf = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom("state".into())));
+    leo_parser::parse_expression(&Handler::default(), &sf.src, sf.start_pos)
+        .ok()
+        .and_then(|expression| evaluate_constant(&expression))
+        .ok_or_else(|| InterpreterError::invalid_stored_entry(source).into())
+}
@@ -0,0 +1,108 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{parse_stored_value, value_to_source, StateStorage};
+
+use leo_ast::Value;
+use leo_errors::{InterpreterError, Result};
+use leo_span::Symbol;
+
+use ::rocksdb::{IteratorMode, WriteBatch, DB};
+use std::path::Path;
+
+/// Separates the name of the mapping from the source code of the key in the keys of the database.
+const SEPARATOR: u8 = 0;
+
+/// The entries of a program's mappings, kept in a RocksDB database on disk.
+///
+/// Each entry is stored under the name of its mapping and the source code of its key, e.g. `counts\01u8`,
+/// with the source code of its value, so that iterating over the database yields the entries in order.
+pub struct RocksDbStorage {
+    db: DB,
+}
+
+impl RocksDbStorage {
+    /// Opens the database at `path`, creating it if it does not exist.
+    pub fn open(path: &Path) -> Result<Self> {
+        let db = DB::open_default(path).map_err(InterpreterError::failed_to_access_state)?;
+        Ok(Self { db })
+    }
+
+    /// Returns the key of the database that stores `key` of `mapping`.
+    fn db_key(mapping: Symbol, key: &Value) -> Vec<u8> {
+        let mut db_key = mapping.to_string().into_bytes();
+        db_key.push(SEPARATOR);
+        db_key.extend(value_to_source(key).into_bytes());
+        db_key
+    }
+
+    /// Parses a value from the source code stored in the database.
+    fn parse_value(bytes: &[u8]) -> Result<Value> {
+        parse_stored_value(&String::from_utf8_lossy(bytes))
+    }
+}
+
+impl StateStorage for RocksDbStorage {
+    fn get(&self, mapping: Symbol, key: &Value) -> Result<Option<Value>> {
+        match self
+            .db
+            .get(Self::db_key(mapping, key))
+            .map_err(InterpreterError::failed_to_access_state)?
+        {
+            Some(bytes) => Ok(Some(Self::parse_value(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn set(&mut self, mapping: Symbol, key: Value, value: Value) -> Result<()> {
+        self.db
+            .put(Self::db_key(mapping, &key), value_to_source(&value))
+            .map_err(|err| InterpreterError::failed_to_access_state(err).into())
+    }
+
+    fn remove(&mut self, mapping: Symbol, key: &Value) -> Result<()> {
+        self.db
+            .delete(Self::db_key(mapping, key))
+            .map_err(|err| InterpreterError::failed_to_access_state(err).into())
+    }
+
+    fn entries(&self) -> Result<Vec<(Symbol, Value, Value)>> {
+        self.db
+            .iterator(IteratorMode::Start)
+            .map(|entry| -> Result<_> {
+                let (db_key, value) = entry.map_err(InterpreterError::failed_to_access_state)?;
+                let separator = db_key
+                    .iter()
+                    .position(|byte| *byte == SEPARATOR)
+                    .ok_or_else(|| InterpreterError::invalid_stored_entry(String::from_utf8_lossy(&db_key)))?;
+                let mapping = Symbol::intern(&String::from_utf8_lossy(&db_key[..separator]));
+                let key = Self::parse_value(&db_key[separator + 1..])?;
+                Ok((mapping, key, Self::parse_value(&value)?))
+            })
+            .collect()
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        for entry in self.db.iterator(IteratorMode::Start) {
+            let (db_key, _) = entry.map_err(InterpreterError::failed_to_access_state)?;
+            batch.delete(db_key);
+        }
+        self.db
+            .write(batch)
+            .map_err(|err| InterpreterError::failed_to_access_state(err).into())
+    }
+}
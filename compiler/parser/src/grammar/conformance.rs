@@ -0,0 +1,647 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests that the parser accepts the programs described by the grammar, and rejects the programs that are not.
+//!
+//! Programs are generated from the syntactic grammar, and mutated into programs that the grammar does not describe.
+//! The grammar is checked against programs with an Earley recognizer, which works on tokens:
+//! the tokens defined in the lexical grammar, such as identifiers and literals, are terminals.
+
+use super::*;
+use crate::{tokenize, ParserContext, Token};
+
+use leo_errors::emitter::Handler;
+use leo_span::{span::BytePos, symbol::create_session_if_not_set_then};
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+/// The tokens of the lexical grammar that appear in the syntactic grammar, and examples of each.
+const TOKEN_EXAMPLES: &[(&str, &[&str])] = &[
+    ("identifier", &["a", "b1", "_c", "Foo", "abs"]),
+    ("item-identifier", &["a", "b1", "Foo", "main"]),
+    ("annotation", &["@program", "@foo"]),
+    ("numeral", &["0", "1", "42", "007"]),
+    ("integer-literal", &["1u8", "0i8", "255u128", "7i64"]),
+    ("field-literal", &["0field", "12field"]),
+    ("product-group-literal", &["0group", "2group"]),
+    ("scalar-literal", &["1scalar"]),
+    ("address-literal", &["aleo10qerras5799u6k7rjtc9y3hcwxuykr45qra7x7dp6jgnc0923czqm0lgta"]),
+    ("signature-literal", &["sign1qqqq"]),
+    ("string-literal", &["\"\"", "\"x = {}\""]),
+];
+
+/// A terminal of a compiled grammar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Terminal {
+    /// A token or character with the given text.
+    Text { value: String, case_sensitive: bool },
+    /// A token of the given lexical rule.
+    Class(String),
+    /// A character in a range of code points.
+    Range(u32, u32),
+}
+
+/// A token or a character of the input to the recognizer.
+#[derive(Clone, Debug)]
+struct Input {
+    /// The lexical rules that the token belongs to, if any.
+    classes: Vec<&'static str>,
+    /// The text of the token.
+    text: String,
+}
+
+impl Input {
+    /// Returns `true` if the input is a keyword, a symbol, or an identifier.
+    /// Words such as `aleo` are strings of the syntactic grammar, but identifiers of the tokenizer.
+    fn is_word(&self) -> bool {
+        self.classes.is_empty() || self.classes.contains(&"identifier")
+    }
+}
+
+impl Terminal {
+    fn matches(&self, input: &Input) -> bool {
+        match self {
+            Terminal::Text {
+                value,
+                case_sensitive: true,
+            } => input.is_word() && &input.text == value,
+            Terminal::Text {
+                value,
+                case_sensitive: false,
+            } => input.is_word() && input.text.eq_ignore_ascii_case(value),
+            Terminal::Class(class) => input.classes.contains(&class.as_str()),
+            Terminal::Range(low, high) => {
+                let mut chars = input.text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => (*low..=*high).contains(&(c as u32)),
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Symbol {
+    Nonterminal(usize),
+    Terminal(Terminal),
+}
+
+/// A grammar in Backus-Naur form, compiled from ABNF for recognition.
+struct Bnf {
+    /// The productions of each nonterminal.
+    productions: Vec<Vec<Vec<Symbol>>>,
+    /// Whether each nonterminal derives the empty string.
+    nullable: Vec<bool>,
+    /// The nonterminals of the rules of the ABNF grammar.
+    rules: HashMap<String, usize>,
+}
+
+impl Bnf {
+    /// Compiles the rules of `grammar`.
+    /// References to the rules in `tokens` become terminals.
+    /// If `characters` is set, strings become sequences of characters, otherwise they are tokens.
+    fn new(grammar: &Grammar, tokens: &HashSet<&str>, characters: bool) -> Self {
+        let mut bnf = Self {
+            productions: Vec::new(),
+            nullable: Vec::new(),
+            rules: HashMap::new(),
+        };
+        for name in grammar.rules.keys() {
+            bnf.rules.insert(name.clone(), bnf.productions.len());
+            bnf.productions.push(Vec::new());
+        }
+        for (name, element) in &grammar.rules {
+            let sequence = bnf.compile(element, tokens, characters);
+            bnf.productions[bnf.rules[name]].push(sequence);
+        }
+
+        // Find the nullable nonterminals.
+        bnf.nullable = vec![false; bnf.productions.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for nonterminal in 0..bnf.productions.len() {
+                if !bnf.nullable[nonterminal]
+                    && bnf.productions[nonterminal].iter().any(|production| {
+                        production
+                            .iter()
+                            .all(|symbol| matches!(symbol, Symbol::Nonterminal(n) if bnf.nullable[*n]))
+                    })
+                {
+                    bnf.nullable[nonterminal] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        bnf
+    }
+
+    fn add_nonterminal(&mut self, productions: Vec<Vec<Symbol>>) -> Symbol {
+        self.productions.push(productions);
+        Symbol::Nonterminal(self.productions.len() - 1)
+    }
+
+    fn compile(&mut self, element: &GrammarElement, tokens: &HashSet<&str>, characters: bool) -> Vec<Symbol> {
+        match element {
+            GrammarElement::Alternation(alternatives) => {
+                let productions = alternatives
+                    .iter()
+                    .map(|alternative| self.compile(alternative, tokens, characters))
+                    .collect();
+                vec![self.add_nonterminal(productions)]
+            }
+            GrammarElement::Concatenation(elements) => elements
+                .iter()
+                .flat_map(|element| self.compile(element, tokens, characters))
+                .collect(),
+            GrammarElement::Optional(element) => {
+                let sequence = self.compile(element, tokens, characters);
+                vec![self.add_nonterminal(vec![sequence, vec![]])]
+            }
+            GrammarElement::Repetition { min, max, element } => {
+                let sequence = self.compile(element, tokens, characters);
+                let mut symbols: Vec<Symbol> = (0..*min).flat_map(|_| sequence.clone()).collect();
+                match max {
+                    None => {
+                        // `R = element R / ""`
+                        let repetition = self.add_nonterminal(vec![]);
+                        if let Symbol::Nonterminal(n) = repetition {
+                            let mut recursive = sequence;
+                            recursive.push(repetition.clone());
+                            self.productions[n] = vec![recursive, vec![]];
+                        }
+                        symbols.push(repetition);
+                    }
+                    Some(max) => {
+                        let optional = self.add_nonterminal(vec![sequence, vec![]]);
+                        symbols.extend((*min..*max).map(|_| optional.clone()));
+                    }
+                }
+                symbols
+            }
+            GrammarElement::Rule(name) if tokens.contains(name.as_str()) => {
+                vec![Symbol::Terminal(Terminal::Class(name.clone()))]
+            }
+            GrammarElement::Rule(name) => vec![Symbol::Nonterminal(self.rules[name])],
+            GrammarElement::String { value, case_sensitive } if characters => value
+                .chars()
+                .map(|c| {
+                    Symbol::Terminal(Terminal::Text {
+                        value: c.to_string(),
+                        case_sensitive: *case_sensitive,
+                    })
+                })
+                .collect(),
+            GrammarElement::String { value, case_sensitive } => vec![Symbol::Terminal(Terminal::Text {
+                value: value.clone(),
+                case_sensitive: *case_sensitive,
+            })],
+            GrammarElement::Range(low, high) => vec![Symbol::Terminal(Terminal::Range(*low, *high))],
+        }
+    }
+
+    /// Returns `true` if `input` is derived from the rule `start`, using Earley's algorithm.
+    fn recognizes(&self, start: &str, input: &[Input]) -> bool {
+        // An item is a production of a nonterminal, a position in it, and the input position where it started.
+        type Item = (usize, usize, usize, usize);
+        let start = self.rules[start];
+        let mut sets: Vec<Vec<Item>> = vec![Vec::new(); input.len() + 1];
+        let mut seen: Vec<HashSet<Item>> = vec![HashSet::new(); input.len() + 1];
+        for production in 0..self.productions[start].len() {
+            sets[0].push((start, production, 0, 0));
+            seen[0].insert((start, production, 0, 0));
+        }
+
+        for position in 0..=input.len() {
+            let mut index = 0;
+            while index < sets[position].len() {
+                let (nonterminal, production, dot, origin) = sets[position][index];
+                index += 1;
+                let symbols = &self.productions[nonterminal][production];
+                let mut add = |set: usize, item: Item, sets: &mut Vec<Vec<Item>>| {
+                    if seen[set].insert(item) {
+                        sets[set].push(item);
+                    }
+                };
+                match symbols.get(dot) {
+                    // Complete the items that were waiting for this nonterminal.
+                    None => {
+                        for waiting in 0..sets[origin].len() {
+                            let (n, p, d, o) = sets[origin][waiting];
+                            if self.productions[n][p].get(d) == Some(&Symbol::Nonterminal(nonterminal)) {
+                                add(position, (n, p, d + 1, o), &mut sets);
+                            }
+                        }
+                    }
+                    // Predict the productions of the nonterminal.
+                    Some(Symbol::Nonterminal(next)) => {
+                        for p in 0..self.productions[*next].len() {
+                            add(position, (*next, p, 0, position), &mut sets);
+                        }
+                        if self.nullable[*next] {
+                            add(position, (nonterminal, production, dot + 1, origin), &mut sets);
+                        }
+                    }
+                    // Scan the next input.
+                    Some(Symbol::Terminal(terminal)) => {
+                        if input.get(position).map_or(false, |input| terminal.matches(input)) {
+                            add(position + 1, (nonterminal, production, dot + 1, origin), &mut sets);
+                        }
+                    }
+                }
+            }
+        }
+
+        sets[input.len()]
+            .iter()
+            .any(|(n, p, d, o)| *n == start && *o == 0 && *d == self.productions[*n][*p].len())
+    }
+}
+
+/// Returns the tokens of `source` as inputs to the recognizer, or `None` if it cannot be tokenized.
+/// Tokens that are adjacent form a single input if they are a single token of the lexical grammar, e.g. `1u8`.
+fn tokens(source: &str) -> Option<Vec<Input>> {
+    let tokens: Vec<_> = tokenize(source, BytePos(0))
+        .ok()?
+        .into_iter()
+        .filter(|token| !matches!(token.token, Token::CommentLine(_) | Token::CommentBlock(_)))
+        .collect();
+
+    let mut inputs = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let (token, span) = (&tokens[i].token, tokens[i].span);
+        let next = tokens
+            .get(i + 1)
+            .filter(|next| next.span.lo == span.hi)
+            .map(|next| &next.token);
+        let merged = |class: &'static str, next: &Token| Input {
+            classes: vec![class],
+            text: format!("{token}{next}"),
+        };
+        let input = match (token, next) {
+            (Token::Integer(_), Some(next @ Token::Field)) => merged("field-literal", next),
+            (Token::Integer(_), Some(next @ Token::Group)) => merged("product-group-literal", next),
+            (Token::Integer(_), Some(next @ Token::Scalar)) => merged("scalar-literal", next),
+            (Token::Integer(_), Some(next)) if ParserContext::token_to_int_type(next).is_some() => {
+                merged("integer-literal", next)
+            }
+            (Token::At, Some(next @ (Token::Identifier(_) | Token::Program))) => merged("annotation", next),
+            (Token::RightParen, Some(Token::Group)) => Input {
+                classes: Vec::new(),
+                text: ")group".to_string(),
+            },
+            _ => {
+                i += 1;
+                inputs.push(match token {
+                    Token::Integer(value) => Input {
+                        classes: vec!["numeral"],
+                        text: value.clone(),
+                    },
+                    Token::Identifier(name) if name.to_string().starts_with('_') => Input {
+                        classes: vec!["identifier"],
+                        text: name.to_string(),
+                    },
+                    Token::Identifier(name) => Input {
+                        classes: vec!["identifier", "item-identifier"],
+                        text: name.to_string(),
+                    },
+                    Token::AddressLit(address) => Input {
+                        classes: vec!["address-literal"],
+                        text: address.clone(),
+                    },
+                    Token::SignatureLit(signature) => Input {
+                        classes: vec!["signature-literal"],
+                        text: signature.clone(),
+                    },
+                    Token::StaticString(_) => Input {
+                        classes: vec!["string-literal"],
+                        text: token.to_string(),
+                    },
+                    _ => Input {
+                        classes: Vec::new(),
+                        text: token.to_string(),
+                    },
+                });
+                continue;
+            }
+        };
+        inputs.push(input);
+        i += 2;
+    }
+
+    Some(inputs)
+}
+
+/// Returns `true` if the parser accepts `source` without errors.
+fn parser_accepts(source: &str) -> bool {
+    let handler = Handler::new(Box::new(leo_errors::emitter::BufferEmitter::new()));
+    crate::parse(&handler, source, BytePos(0)).is_ok() && !handler.had_errors()
+}
+
+/// Generates programs from the syntactic grammar.
+struct Generator<'a> {
+    grammar: &'a Grammar,
+    /// The least depth of a derivation of each rule.
+    heights: HashMap<&'a str, usize>,
+    /// The state of the pseudo-random number generator.
+    state: u64,
+}
+
+impl<'a> Generator<'a> {
+    /// The depth after which the shortest derivations are chosen.
+    const MAX_DEPTH: usize = 30;
+    /// The number of tokens after which the shortest derivations are chosen.
+    const MAX_TOKENS: usize = 1000;
+
+    fn new(grammar: &'a Grammar, seed: u64) -> Self {
+        let mut generator = Self {
+            grammar,
+            heights: TOKEN_EXAMPLES.iter().map(|(name, _)| (*name, 0)).collect(),
+            state: seed,
+        };
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (name, element) in &grammar.rules {
+                if generator.heights.contains_key(name.as_str()) {
+                    continue;
+                }
+                if let Some(height) = generator.height(element) {
+                    generator.heights.insert(name, height + 1);
+                    changed = true;
+                }
+            }
+        }
+        generator
+    }
+
+    /// Returns the least depth of a derivation of `element`, if one is known.
+    fn height(&self, element: &GrammarElement) -> Option<usize> {
+        match element {
+            GrammarElement::Alternation(alternatives) => alternatives.iter().filter_map(|a| self.height(a)).min(),
+            GrammarElement::Concatenation(elements) => elements
+                .iter()
+                .map(|e| self.height(e))
+                .collect::<Option<Vec<_>>>()
+                .map(|heights| heights.into_iter().max().unwrap_or(0)),
+            GrammarElement::Optional(_) => Some(0),
+            GrammarElement::Repetition { min: 0, .. } => Some(0),
+            GrammarElement::Repetition { element, .. } => self.height(element),
+            GrammarElement::Rule(name) => self.heights.get(name.as_str()).copied(),
+            GrammarElement::String { .. } | GrammarElement::Range(..) => Some(0),
+        }
+    }
+
+    fn next(&mut self, bound: usize) -> usize {
+        // xorshift64
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state % bound as u64) as usize
+    }
+
+    /// Appends the tokens of a random derivation of `element` to `output`.
+    fn generate(&mut self, element: &'a GrammarElement, depth: usize, output: &mut Vec<String>) {
+        let shortest = depth > Self::MAX_DEPTH || output.len() > Self::MAX_TOKENS;
+        match element {
+            GrammarElement::Alternation(alternatives) => {
+                let alternative = match shortest {
+                    true => alternatives.iter().min_by_key(|a| self.height(a)).unwrap(),
+                    false => &alternatives[self.next(alternatives.len())],
+                };
+                self.generate(alternative, depth, output)
+            }
+            GrammarElement::Concatenation(elements) => {
+                elements.iter().for_each(|element| self.generate(element, depth, output))
+            }
+            GrammarElement::Optional(element) => {
+                if !shortest && self.next(2) == 0 {
+                    self.generate(element, depth, output)
+                }
+            }
+            GrammarElement::Repetition { min, max, element } => {
+                let extra = if shortest { 0 } else { self.next(3) };
+                let count = max.map_or(min + extra, |max| (min + extra).min(max));
+                (0..count).for_each(|_| self.generate(element, depth, output))
+            }
+            GrammarElement::Rule(name) => match TOKEN_EXAMPLES.iter().find(|(token, _)| token == name) {
+                Some((_, examples)) => output.push(examples[self.next(examples.len())].to_string()),
+                // Struct expressions are not allowed at the top level of restricted expressions, so they are parenthesized.
+                None if name == "restricted-expression" => {
+                    output.push("(".to_string());
+                    self.generate(&self.grammar.rules["expression"], depth + 1, output);
+                    output.push(")".to_string());
+                }
+                None => self.generate(&self.grammar.rules[name], depth + 1, output),
+            },
+            GrammarElement::String { value, .. } => output.push(value.clone()),
+            GrammarElement::Range(..) => unreachable!("the syntactic grammar only contains strings"),
+        }
+    }
+
+    /// Returns the tokens of a random program.
+    fn program(&mut self) -> Vec<String> {
+        let mut output = Vec::new();
+        // Imports are read from the file system, so programs are generated without them.
+        self.generate(&self.grammar.rules["program-declaration"], 0, &mut output);
+        output
+    }
+
+    /// Returns a random mutation of `tokens`: a token is removed, duplicated, swapped with the next, or replaced.
+    fn mutate(&mut self, tokens: &[String], vocabulary: &[String]) -> Vec<String> {
+        let mut tokens = tokens.to_vec();
+        let index = self.next(tokens.len());
+        match self.next(4) {
+            0 => {
+                tokens.remove(index);
+            }
+            1 => tokens.insert(index, tokens[index].clone()),
+            2 if index + 1 < tokens.len() => tokens.swap(index, index + 1),
+            _ => tokens[index] = vocabulary[self.next(vocabulary.len())].clone(),
+        }
+        tokens
+    }
+}
+
+/// Returns the strings of the rule `name`, if it is an alternation of strings.
+fn strings(grammar: &Grammar, name: &str) -> Vec<String> {
+    match &grammar.rules[name] {
+        GrammarElement::Alternation(alternatives) => alternatives
+            .iter()
+            .filter_map(|alternative| match alternative {
+                GrammarElement::String { value, .. } => Some(value.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn token_rules() -> HashSet<&'static str> {
+    TOKEN_EXAMPLES.iter().map(|(name, _)| *name).collect()
+}
+
+#[test]
+fn grammar_is_well_formed() {
+    fn check(grammar: &Grammar, element: &GrammarElement) {
+        match element {
+            GrammarElement::Alternation(elements) | GrammarElement::Concatenation(elements) => {
+                elements.iter().for_each(|element| check(grammar, element))
+            }
+            GrammarElement::Optional(element) | GrammarElement::Repetition { element, .. } => check(grammar, element),
+            GrammarElement::Rule(name) => assert!(grammar.rules.contains_key(name), "`{name}` is not defined"),
+            GrammarElement::String { .. } | GrammarElement::Range(..) => {}
+        }
+    }
+
+    let grammar = Grammar::leo();
+    grammar.rules.values().for_each(|element| check(&grammar, element));
+    assert!(Grammar::parse("a = b c\n").is_ok());
+    assert!(Grammar::parse("a = ( b\n").is_err());
+    assert!(Grammar::parse("a = b\na = c\n").is_err());
+}
+
+#[test]
+fn lexical_grammar_matches_tokenizer() {
+    create_session_if_not_set_then(|_| {
+        let grammar = Grammar::leo();
+
+        // Each keyword and symbol is a single token.
+        for name in ["keyword", "symbol"] {
+            for string in strings(&grammar, name) {
+                let tokens = tokenize(&string, BytePos(0)).unwrap();
+                assert_eq!(tokens.len(), 1, "`{string}` is not a single token");
+                assert_eq!(tokens[0].token.to_string(), string);
+                assert!(!matches!(tokens[0].token, Token::Identifier(_)), "`{string}` is not a keyword");
+            }
+        }
+        let keywords = strings(&grammar, "keyword");
+        for token in crate::KEYWORD_TOKENS {
+            if !matches!(token, Token::Static) {
+                assert!(keywords.contains(&token.to_string()), "`{token}` is missing from the keywords");
+            }
+        }
+
+        // The examples of each token are described by the lexical grammar.
+        let bnf = Bnf::new(&grammar, &HashSet::new(), true);
+        for (name, examples) in TOKEN_EXAMPLES {
+            for example in *examples {
+                let characters: Vec<_> = example
+                    .chars()
+                    .map(|c| Input {
+                        classes: Vec::new(),
+                        text: c.to_string(),
+                    })
+                    .collect();
+                assert!(bnf.recognizes(name, &characters), "`{example}` is not a {name}");
+            }
+        }
+        for comment in ["// a comment\n", "/* a\n comment **/", "/**/"] {
+            let characters: Vec<_> = comment
+                .chars()
+                .map(|c| Input {
+                    classes: Vec::new(),
+                    text: c.to_string(),
+                })
+                .collect();
+            assert!(bnf.recognizes("comment", &characters), "`{comment}` is not a comment");
+        }
+    })
+}
+
+#[test]
+fn parser_accepts_generated_programs() {
+    create_session_if_not_set_then(|_| {
+        let grammar = Grammar::leo();
+        let bnf = Bnf::new(&grammar, &token_rules(), false);
+        let mut generator = Generator::new(&grammar, 0x1e0_1e0);
+
+        for _ in 0..200 {
+            let program = generator.program().join(" ");
+            assert!(parser_accepts(&program), "the parser rejects a program of the grammar:\n{program}");
+            assert!(bnf.recognizes("file", &tokens(&program).unwrap()));
+        }
+    })
+}
+
+#[test]
+fn parser_rejects_programs_outside_grammar() {
+    create_session_if_not_set_then(|_| {
+        let grammar = Grammar::leo();
+        let bnf = Bnf::new(&grammar, &token_rules(), false);
+        let mut generator = Generator::new(&grammar, 0xa1e0);
+        let vocabulary: Vec<String> = strings(&grammar, "keyword")
+            .into_iter()
+            .chain(strings(&grammar, "symbol"))
+            .chain(["a", "1", "1u8"].map(String::from))
+            .filter(|token| token != "import")
+            .collect();
+
+        let mut rejected = 0;
+        for _ in 0..200 {
+            let program = generator.program();
+            for _ in 0..5 {
+                let mutant = generator.mutate(&program, &vocabulary).join(" ");
+                let in_grammar = tokens(&mutant).map_or(false, |tokens| bnf.recognizes("file", &tokens));
+                if !in_grammar {
+                    rejected += 1;
+                    assert!(!parser_accepts(&mutant), "the parser accepts a program outside the grammar:\n{mutant}");
+                }
+            }
+        }
+        assert!(rejected > 0);
+    })
+}
+
+#[test]
+fn grammar_accepts_test_programs() {
+    fn leo_files(directory: &Path, files: &mut Vec<std::path::PathBuf>) {
+        for entry in std::fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                leo_files(&path, files);
+            } else if path.extension().map_or(false, |extension| extension == "leo") {
+                files.push(path);
+            }
+        }
+    }
+
+    create_session_if_not_set_then(|_| {
+        let grammar = Grammar::leo();
+        let bnf = Bnf::new(&grammar, &token_rules(), false);
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let mut files = Vec::new();
+        leo_files(&root.join("tests/compiler"), &mut files);
+        leo_files(&root.join("examples"), &mut files);
+
+        let mut accepted = 0;
+        for file in files {
+            let source = std::fs::read_to_string(&file).unwrap();
+            if parser_accepts(&source) {
+                accepted += 1;
+                let tokens = tokens(&source).unwrap();
+                assert!(bnf.recognizes("file", &tokens), "the grammar rejects {}", file.display());
+            }
+        }
+        assert!(accepted > 0);
+    })
+}
@@ -0,0 +1,295 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The grammar of the Leo language, and a reader for the ABNF it is written in.
+
+use leo_errors::{ParserError, Result};
+
+use indexmap::IndexMap;
+use std::{iter::Peekable, str::CharIndices};
+
+#[cfg(test)]
+mod conformance;
+
+/// The grammar of Leo in ABNF, as accepted by the parser.
+/// It is published for the authors of tools such as syntax highlighters and formatters.
+pub const LEO_GRAMMAR: &str = include_str!("leo.abnf");
+
+/// An element of the right-hand side of an ABNF rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GrammarElement {
+    /// One of the elements, e.g. `a / b`.
+    Alternation(Vec<GrammarElement>),
+    /// Each of the elements in order, e.g. `a b`.
+    Concatenation(Vec<GrammarElement>),
+    /// The element repeated between `min` and `max` times, e.g. `1*a`.
+    Repetition {
+        min: usize,
+        max: Option<usize>,
+        element: Box<GrammarElement>,
+    },
+    /// The element or nothing, e.g. `[ a ]`.
+    Optional(Box<GrammarElement>),
+    /// A reference to another rule.
+    Rule(String),
+    /// A string, e.g. `"->"` or `%s"let"`.
+    String { value: String, case_sensitive: bool },
+    /// A character in a range of code points, e.g. `%x30-39`.
+    Range(u32, u32),
+}
+
+/// A grammar in ABNF, as a map from rule names to their definitions, in the order they are defined.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Grammar {
+    pub rules: IndexMap<String, GrammarElement>,
+}
+
+impl Grammar {
+    /// Returns the grammar of Leo.
+    pub fn leo() -> Self {
+        Self::parse(LEO_GRAMMAR).expect("the Leo grammar is valid ABNF")
+    }
+
+    /// Reads a grammar written in ABNF.
+    /// Prose values `<...>` and concatenated numeric values such as `%x41.42` are not supported.
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut grammar = Self::default();
+
+        // Join each rule with the lines that continue it, i.e. the lines that are indented.
+        let mut definitions: Vec<(usize, String)> = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = strip_comment(line);
+            if line.trim().is_empty() {
+                continue;
+            }
+            match definitions.last_mut() {
+                Some((_, definition)) if line.starts_with(char::is_whitespace) => {
+                    definition.push(' ');
+                    definition.push_str(line);
+                }
+                _ => definitions.push((index + 1, line.to_string())),
+            }
+        }
+
+        for (line, definition) in definitions {
+            let (name, incremental, elements) = match definition.split_once('=') {
+                Some((name, elements)) => match elements.strip_prefix('/') {
+                    Some(elements) => (name.trim(), true, elements),
+                    None => (name.trim(), false, elements),
+                },
+                None => return Err(ParserError::invalid_grammar(line, "expected `=`").into()),
+            };
+            if !is_rule_name(name) {
+                return Err(ParserError::invalid_grammar(line, format!("invalid rule name `{name}`")).into());
+            }
+
+            let mut reader = ElementReader {
+                line,
+                chars: elements.char_indices().peekable(),
+                source: elements,
+            };
+            let element = reader.read_alternation()?;
+            reader.skip_whitespace();
+            if let Some((_, c)) = reader.chars.peek() {
+                return Err(ParserError::invalid_grammar(line, format!("unexpected `{c}`")).into());
+            }
+
+            match (grammar.rules.get_mut(name), incremental) {
+                (None, false) => {
+                    grammar.rules.insert(name.to_string(), element);
+                }
+                (Some(GrammarElement::Alternation(alternatives)), true) => alternatives.push(element),
+                (Some(existing), true) => {
+                    *existing = GrammarElement::Alternation(vec![existing.clone(), element]);
+                }
+                (Some(_), false) => {
+                    return Err(ParserError::invalid_grammar(line, format!("rule `{name}` is defined twice")).into())
+                }
+                (None, true) => {
+                    return Err(ParserError::invalid_grammar(line, format!("rule `{name}` is not defined")).into())
+                }
+            }
+        }
+
+        Ok(grammar)
+    }
+}
+
+/// Removes the comment at the end of `line`, if any.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            ';' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Returns `true` if `name` is a valid ABNF rule name.
+fn is_rule_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic()) && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Reads the elements of a rule definition.
+struct ElementReader<'a> {
+    /// The line on which the rule is defined.
+    line: usize,
+    /// The remaining characters of the definition.
+    chars: Peekable<CharIndices<'a>>,
+    /// The definition.
+    source: &'a str,
+}
+
+impl ElementReader<'_> {
+    fn error(&self, reason: impl std::fmt::Display) -> leo_errors::LeoError {
+        ParserError::invalid_grammar(self.line, reason).into()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        self.chars.next_if(|(_, c)| *c == expected).is_some()
+    }
+
+    fn read_number(&mut self, radix: u32) -> Option<u32> {
+        let start = self.chars.peek()?.0;
+        let mut end = start;
+        while let Some((i, c)) = self.chars.next_if(|(_, c)| c.is_digit(radix)) {
+            end = i + c.len_utf8();
+        }
+        u32::from_str_radix(&self.source[start..end], radix).ok()
+    }
+
+    /// Reads `concatenation *( "/" concatenation )`.
+    fn read_alternation(&mut self) -> Result<GrammarElement> {
+        let mut alternatives = vec![self.read_concatenation()?];
+        while self.eat('/') {
+            alternatives.push(self.read_concatenation()?);
+        }
+        Ok(match alternatives.len() {
+            1 => alternatives.remove(0),
+            _ => GrammarElement::Alternation(alternatives),
+        })
+    }
+
+    /// Reads `1*repetition`.
+    fn read_concatenation(&mut self) -> Result<GrammarElement> {
+        let mut elements = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                None | Some((_, '/' | ')' | ']')) => break,
+                _ => elements.push(self.read_repetition()?),
+            }
+        }
+        match elements.len() {
+            0 => Err(self.error("expected an element")),
+            1 => Ok(elements.remove(0)),
+            _ => Ok(GrammarElement::Concatenation(elements)),
+        }
+    }
+
+    /// Reads `[ repeat ] element`, where `repeat` is `n`, `*`, `n*`, `*m`, or `n*m`.
+    fn read_repetition(&mut self) -> Result<GrammarElement> {
+        let min = self.read_number(10);
+        let (min, max) = match self.chars.next_if(|(_, c)| *c == '*') {
+            Some(_) => (min.unwrap_or(0), self.read_number(10)),
+            None => match min {
+                Some(count) => (count, Some(count)),
+                None => return self.read_element(),
+            },
+        };
+        Ok(GrammarElement::Repetition {
+            min: min as usize,
+            max: max.map(|max| max as usize),
+            element: Box::new(self.read_element()?),
+        })
+    }
+
+    /// Reads a rule name, a group, an option, a string, or a numeric value.
+    fn read_element(&mut self) -> Result<GrammarElement> {
+        match self.chars.next() {
+            Some((start, c)) if c.is_ascii_alphabetic() => {
+                let mut end = start + 1;
+                while let Some((i, _)) = self.chars.next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '-') {
+                    end = i + 1;
+                }
+                Ok(GrammarElement::Rule(self.source[start..end].to_string()))
+            }
+            Some((_, '(')) => {
+                let element = self.read_alternation()?;
+                match self.eat(')') {
+                    true => Ok(element),
+                    false => Err(self.error("expected `)`")),
+                }
+            }
+            Some((_, '[')) => {
+                let element = self.read_alternation()?;
+                match self.eat(']') {
+                    true => Ok(GrammarElement::Optional(Box::new(element))),
+                    false => Err(self.error("expected `]`")),
+                }
+            }
+            Some((_, '"')) => self.read_string(false),
+            Some((_, '%')) => match self.chars.next() {
+                Some((_, 's' | 'S')) if self.chars.next_if(|(_, c)| *c == '"').is_some() => self.read_string(true),
+                Some((_, 'i' | 'I')) if self.chars.next_if(|(_, c)| *c == '"').is_some() => self.read_string(false),
+                Some((_, 'x' | 'X')) => self.read_range(16),
+                Some((_, 'd' | 'D')) => self.read_range(10),
+                Some((_, 'b' | 'B')) => self.read_range(2),
+                _ => Err(self.error("expected a string or a numeric value after `%`")),
+            },
+            Some((_, c)) => Err(self.error(format!("unexpected `{c}`"))),
+            None => Err(self.error("expected an element")),
+        }
+    }
+
+    /// Reads the rest of a string after its opening quote.
+    fn read_string(&mut self, case_sensitive: bool) -> Result<GrammarElement> {
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => break,
+                Some((_, c)) => value.push(c),
+                None => return Err(self.error("expected `\"`")),
+            }
+        }
+        Ok(GrammarElement::String { value, case_sensitive })
+    }
+
+    /// Reads the rest of a numeric value after its base, e.g. `30-39`.
+    fn read_range(&mut self, radix: u32) -> Result<GrammarElement> {
+        let low = self
+            .read_number(radix)
+            .ok_or_else(|| self.error("expected a number"))?;
+        let high = match self.chars.next_if(|(_, c)| *c == '-') {
+            Some(_) => self
+                .read_number(radix)
+                .ok_or_else(|| self.error("expected a number"))?,
+            None => low,
+        };
+        if self.chars.peek().map_or(false, |(_, c)| *c == '.') {
+            return Err(self.error("concatenated numeric values are not supported"));
+        }
+        Ok(GrammarElement::Range(low, high))
+    }
+}
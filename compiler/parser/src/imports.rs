@@ -0,0 +1,63 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The resolution of the programs that `import` statements name.
+
+use leo_ast::{Identifier, ImportSource};
+use leo_errors::{CompilerError, Result};
+
+/// Finds the file of each program that an `import` statement names, e.g. `foo.leo` for `import foo.leo;`
+/// and `foo.aleo` for `import foo.aleo;`.
+/// The programs that an imported program imports are resolved by the same resolver.
+pub trait ImportResolver {
+    /// Returns the Leo file of the imported program `name`, with where it was found,
+    /// or an error at `name` if it cannot be found.
+    fn resolve(&self, name: &Identifier) -> Result<ImportSource>;
+
+    /// Returns the bytecode of the imported program `name`, with where it was found,
+    /// or an error at `name` if it cannot be found.
+    fn resolve_bytecode(&self, name: &Identifier) -> Result<ImportSource>;
+}
+
+/// Resolves imports in the `imports/` directory of the current directory, which is the root of a package when
+/// `leo` commands run. This is how the parser resolves imports unless it is given another resolver.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CurrentDirectory;
+
+impl ImportResolver for CurrentDirectory {
+    fn resolve(&self, name: &Identifier) -> Result<ImportSource> {
+        self.find(name, "leo")
+    }
+
+    fn resolve_bytecode(&self, name: &Identifier) -> Result<ImportSource> {
+        self.find(name, "aleo")
+    }
+}
+
+impl CurrentDirectory {
+    /// Returns the file of the program `name` with the `extension` in `imports/`.
+    fn find(&self, name: &Identifier, extension: &str) -> Result<ImportSource> {
+        let mut directory = std::env::current_dir().map_err(|err| CompilerError::cannot_open_cwd(err, name.span))?;
+        directory.push("imports");
+        let path = directory.join(format!("{}.{}", name.name, extension));
+
+        // Throw an error if the import file doesn't exist.
+        match path.exists() {
+            true => Ok(ImportSource { path, directory }),
+            false => Err(CompilerError::import_not_found(path.display(), name.span).into()),
+        }
+    }
+}
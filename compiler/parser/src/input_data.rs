@@ -0,0 +1,344 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Input files in TOML or JSON, e.g. `inputs/token.toml`, as an alternative to the `.in` format.
+//! Each function has a table of its inputs by name, and the types of the inputs are those of the function's signature:
+//!
+//! ```toml
+//! [mint]
+//! receiver = "aleo1..."
+//! amount = 100
+//!
+//! [transfer.token]
+//! owner = "aleo1..."
+//! amount = 100
+//! ```
+//!
+//! Integers and booleans may be given as such, and structs and records as tables of their members.
+//! Any other value is a string with a Leo expression, e.g. `"1field"`.
+//! Since the file is not Leo source, its errors are reported at the path of the key, e.g. `transfer.token.amount`.
+
+use crate::parse_expression;
+use leo_ast::{
+    Definition, Expression, Function, Identifier, InputAst, Literal, Node, Program, Section, Struct, StructExpression,
+    StructVariableInitializer, Type,
+};
+use leo_errors::{emitter::Handler, InputError, Result};
+use leo_span::{source_map::FileName, sym, symbol::with_session_globals, Span, Symbol};
+
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::path::Path;
+
+/// Parses the input file `file`, which is TOML if its extension is `toml` and JSON otherwise,
+/// into the sections of the functions of `program`.
+pub fn parse_input_data(handler: &Handler, file: &Path, source: &str, program: &Program) -> Result<InputAst> {
+    let data: Value = match file.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => {
+            toml::from_str(source).map_err(|err| InputError::failed_to_parse_input_data(file.display(), err))?
+        }
+        _ => serde_json::from_str(source).map_err(|err| InputError::failed_to_parse_input_data(file.display(), err))?,
+    };
+    InputDataParser::new(handler, file, program).parse(&data)
+}
+
+/// Converts the values of an input file into Leo expressions of the types declared by a program.
+struct InputDataParser<'a> {
+    handler: &'a Handler,
+    /// The name of the file, which errors are reported in.
+    file: String,
+    /// The functions of the program and its imports, by name.
+    functions: IndexMap<Symbol, &'a Function>,
+    /// The structs and records of the program and its imports, by name.
+    structs: IndexMap<Symbol, &'a Struct>,
+}
+
+impl<'a> InputDataParser<'a> {
+    fn new(handler: &'a Handler, file: &Path, program: &'a Program) -> Self {
+        let scopes = program.program_scopes.values().chain(
+            program
+                .imports
+                .values()
+                .flat_map(|import| import.program_scopes.values()),
+        );
+        let mut functions = IndexMap::new();
+        let mut structs = IndexMap::new();
+        for scope in scopes {
+            // The functions of the program itself come first, so imports do not shadow them.
+            for function in scope.functions.values() {
+                functions.entry(function.name()).or_insert(function);
+            }
+            for struct_ in scope.structs.values() {
+                structs.entry(struct_.identifier.name).or_insert(struct_);
+            }
+        }
+        Self {
+            handler,
+            file: file.display().to_string(),
+            functions,
+            structs,
+        }
+    }
+
+    /// Returns the sections of the functions that `data` has tables for.
+    fn parse(&self, data: &Value) -> Result<InputAst> {
+        let tables = data
+            .as_object()
+            .ok_or_else(|| InputError::failed_to_parse_input_data(&self.file, "expected a table of functions"))?;
+
+        let mut sections = Vec::with_capacity(tables.len());
+        for (name, inputs) in tables {
+            let function = self
+                .functions
+                .get(&Symbol::intern(name))
+                .ok_or_else(|| InputError::unknown_input_function(&self.file, name))?;
+            let inputs = inputs
+                .as_object()
+                .ok_or_else(|| InputError::invalid_input_data(&self.file, name, "table", inputs))?;
+            if let Some(key) = inputs.keys().find(|key| {
+                !function
+                    .input
+                    .iter()
+                    .any(|input| input.identifier().name.to_string() == **key)
+            }) {
+                return Err(InputError::unknown_input_key(&self.file, format!("{}.{}", name, key)).into());
+            }
+
+            let mut definitions = Vec::with_capacity(function.input.len());
+            for input in function.input.iter() {
+                let path = format!("{}.{}", name, input.identifier());
+                let value = inputs
+                    .get(&input.identifier().to_string())
+                    .ok_or_else(|| InputError::missing_input_key(&self.file, &path))?;
+                definitions.push(Definition {
+                    mode: input.mode(),
+                    type_: input.type_(),
+                    name: Identifier::new(input.identifier().name, Default::default()),
+                    value: self.parse_value(&path, &input.type_(), value)?,
+                    span: Span::default(),
+                });
+            }
+            sections.push(Section {
+                name: Symbol::intern(name),
+                definitions,
+                span: Span::default(),
+            });
+        }
+        Ok(InputAst { sections })
+    }
+
+    /// Returns the expression of `value`, the value at `path` that is declared with type `type_`.
+    fn parse_value(&self, path: &str, type_: &Type, value: &Value) -> Result<Expression> {
+        let invalid = || InputError::invalid_input_data(&self.file, path, type_, value);
+        match (type_, value) {
+            (Type::Integer(integer_type), Value::Number(number)) if number.is_i64() || number.is_u64() => {
+                Ok(Expression::Literal(Literal::Integer(
+                    *integer_type,
+                    number.to_string(),
+                    Span::default(),
+                    Default::default(),
+                )))
+            }
+            (Type::Boolean, Value::Bool(boolean)) => Ok(Expression::Literal(Literal::Boolean(
+                *boolean,
+                Span::default(),
+                Default::default(),
+            ))),
+            (Type::Identifier(identifier), Value::Object(members)) => {
+                let struct_ = self.structs.get(&identifier.name).ok_or_else(invalid)?;
+                // The nonce of a record is not declared, but is given to spend a record that was created before.
+                let is_member = |key: &str| {
+                    (struct_.is_record && key == sym::_nonce.to_string())
+                        || struct_.members.iter().any(|member| member.name().to_string() == key)
+                };
+                if let Some(key) = members.keys().find(|key| !is_member(key)) {
+                    return Err(InputError::unknown_input_key(&self.file, format!("{}.{}", path, key)).into());
+                }
+
+                let mut initializers = Vec::with_capacity(members.len());
+                for member in struct_.members.iter() {
+                    let path = format!("{}.{}", path, member.name());
+                    let value = members
+                        .get(&member.name().to_string())
+                        .ok_or_else(|| InputError::missing_input_key(&self.file, &path))?;
+                    initializers.push(StructVariableInitializer {
+                        identifier: Identifier::new(member.name(), Default::default()),
+                        expression: Some(self.parse_value(&path, &member.type_, value)?),
+                    });
+                }
+                if let Some(nonce) = members.get(&sym::_nonce.to_string()) {
+                    let path = format!("{}.{}", path, sym::_nonce);
+                    initializers.push(StructVariableInitializer {
+                        identifier: Identifier::new(sym::_nonce, Default::default()),
+                        expression: Some(self.parse_value(&path, &Type::Group, nonce)?),
+                    });
+                }
+                Ok(Expression::Struct(StructExpression {
+                    name: Identifier::new(identifier.name, Default::default()),
+                    members: initializers,
+                    span: Span::default(),
+                    id: Default::default(),
+                }))
+            }
+            (_, Value::String(source)) => {
+                // The expression is parsed as a source of its own, named after the key, so that its errors point there.
+                let name = FileName::Custom(format!("{}: {}", self.file, path));
+                let source_file = with_session_globals(|s| s.source_map.new_source(source, name));
+                let expression = parse_expression(self.handler, &source_file.src, source_file.start_pos)?;
+                match &expression {
+                    Expression::Literal(literal) if !literal_has_type(literal, type_) => {
+                        Err(InputError::invalid_input_data(&self.file, path, type_, expression).into())
+                    }
+                    _ => Ok(expression),
+                }
+            }
+            _ => Err(invalid().into()),
+        }
+    }
+}
+
+/// Returns whether `literal` is a value of type `type_`.
+fn literal_has_type(literal: &Literal, type_: &Type) -> bool {
+    match (literal, type_) {
+        (Literal::Address(..), Type::Address)
+        | (Literal::Boolean(..), Type::Boolean)
+        | (Literal::Field(..), Type::Field)
+        | (Literal::Group(..), Type::Group)
+        | (Literal::Scalar(..), Type::Scalar)
+        | (Literal::Signature(..), Type::Signature)
+        | (Literal::String(..), Type::String) => true,
+        (Literal::Integer(literal_type, ..), Type::Integer(integer_type)) => literal_type == integer_type,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use leo_span::symbol::create_session_if_not_set_then;
+    use std::path::PathBuf;
+
+    const PROGRAM: &str = "program token.aleo {
+    record token {
+        owner: address,
+        gates: u64,
+        amount: u64,
+    }
+
+    transition mint(receiver: address, public amount: u64) -> token {
+        return token { owner: receiver, gates: 0u64, amount };
+    }
+
+    transition burn(token: token) -> bool {
+        return true;
+    }
+}
+";
+
+    /// Returns the inputs of `file` with `source`, or the message of the error they fail with.
+    fn inputs(file: &str, source: &str) -> std::result::Result<Vec<(String, Vec<String>)>, String> {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let ast = crate::parse_ast(&handler, PROGRAM, Default::default()).unwrap();
+            parse_input_data(&handler, &PathBuf::from(file), source, ast.as_repr())
+                .map(|input_ast| {
+                    input_ast
+                        .sections
+                        .iter()
+                        .map(|section| {
+                            let values = section
+                                .definitions
+                                .iter()
+                                .map(|definition| {
+                                    format!("{}: {} = {}", definition.name, definition.type_, definition.value)
+                                })
+                                .collect();
+                            (section.name(), values)
+                        })
+                        .collect()
+                })
+                .map_err(|err| err.to_string())
+        })
+    }
+
+    #[test]
+    fn test_toml_inputs() {
+        let source = r#"
+[mint]
+receiver = "aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9"
+amount = 100
+
+[burn.token]
+owner = "aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9"
+gates = "0u64"
+amount = 5
+"#;
+        let inputs = inputs("inputs/token.toml", source).unwrap();
+        assert_eq!(inputs[0].0, "mint");
+        assert_eq!(
+            inputs[0].1,
+            vec![
+                "receiver: address = aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9",
+                "amount: u64 = 100u64",
+            ]
+        );
+        assert_eq!(inputs[1].0, "burn");
+        assert!(inputs[1].1[0].starts_with("token: token = "));
+        assert!(inputs[1].1[0].contains("amount: 5u64"));
+    }
+
+    #[test]
+    fn test_json_inputs() {
+        let source = r#"{ "mint": { "receiver": "aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9", "amount": "7u64" } }"#;
+        let inputs = inputs("inputs/token.json", source).unwrap();
+        assert_eq!(inputs[0].1[1], "amount: u64 = 7u64");
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let error = inputs("inputs/token.toml", "[transfer]\n").unwrap_err();
+        assert!(error.contains("inputs/token.toml: `transfer`"), "{}", error);
+
+        let error = inputs(
+            "inputs/token.toml",
+            "[mint]\nreceiver = \"aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9\"\n",
+        )
+        .unwrap_err();
+        assert!(error.contains("`mint.amount`: the value is missing"), "{}", error);
+
+        let error = inputs("inputs/token.toml", "[mint]\nreceiver = 1\namount = 1\n").unwrap_err();
+        assert!(
+            error.contains("`mint.receiver`: expected a value of type `address`"),
+            "{}",
+            error
+        );
+
+        let error = inputs("inputs/token.toml", "[mint]\nreceiver = \"aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9\"\namount = \"1u8\"\n").unwrap_err();
+        assert!(
+            error.contains("`mint.amount`: expected a value of type `u64`, found `1u8`"),
+            "{}",
+            error
+        );
+
+        let error = inputs("inputs/token.toml", "[burn.token]\nowner = \"aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9\"\ngates = 0\namount = 0\nvalue = 0\n").unwrap_err();
+        assert!(
+            error.contains("`burn.token.value`: there is no input or member"),
+            "{}",
+            error
+        );
+    }
+}
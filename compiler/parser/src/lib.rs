@@ -25,16 +25,33 @@
 
 pub(crate) mod tokenizer;
 use leo_span::span::BytePos;
-pub use tokenizer::KEYWORD_TOKENS;
 pub(crate) use tokenizer::*;
+pub use tokenizer::{tokenize_trivia, Comment, Trivia, KEYWORD_TOKENS};
+
+pub mod grammar;
+pub use grammar::*;
+
+pub mod imports;
+pub use imports::*;
+
+pub mod input_data;
+pub use input_data::*;
+
+pub mod literal_fixes;
+pub use literal_fixes::*;
 
 pub mod parser;
 pub use parser::*;
 
+pub mod stubs;
+pub use stubs::*;
+
 use leo_ast::{input::InputData, Ast, ProgramInput};
 use leo_errors::emitter::Handler;
 use leo_errors::Result;
 
+use std::ops::Range;
+
 #[cfg(test)]
 mod test;
 
@@ -43,9 +60,31 @@ pub fn parse_ast(handler: &Handler, source: &str, start_pos: BytePos) -> Result<
     Ok(Ast::new(parser::parse(handler, source, start_pos)?))
 }
 
+/// Creates the AST of a program after an edit, reusing the items of `old_ast` that the edit did not touch.
+/// See [`reparse()`] for details.
+pub fn reparse_ast(handler: &Handler, old_ast: &Ast, edit_range: Range<usize>, new_text: &str) -> Result<Ast> {
+    Ok(Ast::new(parser::reparse(
+        handler,
+        old_ast.as_repr(),
+        edit_range,
+        new_text,
+    )?))
+}
+
 /// Parses program inputs from from the input file path and state file path
 pub fn parse_program_inputs(handler: &Handler, input_string: &str, start_pos: BytePos) -> Result<InputData> {
     let program_input: ProgramInput = parser::parse_input(handler, input_string, start_pos)?.try_into()?;
 
     Ok(InputData { program_input })
 }
+
+/// Returns `true` if `name` lexes as a single identifier, i.e. it may name a variable, function, or struct.
+pub fn is_identifier(name: &str) -> bool {
+    matches!(
+        tokenize(name, BytePos(0)).as_deref(),
+        Ok([SpannedToken {
+            token: Token::Identifier(_),
+            ..
+        }])
+    )
+}
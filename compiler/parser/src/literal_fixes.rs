@@ -0,0 +1,262 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Suggestions that fix the style of literals, computed from tokens so that they also apply to programs that do not parse,
+//! e.g. because an integer literal is missing its type suffix.
+
+use crate::tokenizer::*;
+
+use leo_errors::{Result, Suggestion};
+use leo_span::span::BytePos;
+
+/// The tokens that may follow a number to form a literal.
+const LITERAL_SUFFIXES: &[Token] = &[
+    Token::I8,
+    Token::I16,
+    Token::I32,
+    Token::I64,
+    Token::I128,
+    Token::U8,
+    Token::U16,
+    Token::U32,
+    Token::U64,
+    Token::U128,
+    Token::Field,
+    Token::Group,
+    Token::Scalar,
+];
+
+/// Returns the suggestions that normalize the literals in `source`, which starts at `start_pos` in the source map:
+/// - integer literals without a type suffix get the type they are declared with, e.g. `let x: u8 = 1;` becomes `let x: u8 = 1u8;`,
+/// - leading zeros are removed, e.g. `007field` becomes `7field`,
+/// - negative zero is made positive, e.g. `-0field` becomes `0field`.
+///
+/// The type of an unsuffixed literal is only inferred where it is unambiguous: in the value of a `let` or `const` with a literal type,
+/// in the bounds of a loop, or in a `return` from a function with a literal output type,
+/// when the expression only combines integers and variables with operators that preserve their type.
+pub fn literal_fixes(source: &str, start_pos: BytePos) -> Result<Vec<Suggestion>> {
+    let tokens: Vec<SpannedToken> = tokenize(source, start_pos)?
+        .into_iter()
+        .filter(|token| !matches!(token.token, Token::CommentLine(_) | Token::CommentBlock(_)))
+        .collect();
+    let hints = suffix_hints(&tokens);
+
+    let mut fixes = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let value = match &token.token {
+            // Tuple indices, e.g. `t.0`, are not literals.
+            Token::Integer(_) if i > 0 && tokens[i - 1].token == Token::Dot => continue,
+            Token::Integer(value) => value,
+            _ => continue,
+        };
+        let normalized = match value.trim_start_matches('0') {
+            "" => "0",
+            trimmed => trimmed,
+        };
+        let suffix = tokens
+            .get(i + 1)
+            .filter(|next| next.span.lo == token.span.hi && LITERAL_SUFFIXES.contains(&next.token));
+
+        match (suffix, &hints[i]) {
+            (None, Some(hint)) => fixes.push(Suggestion::new(
+                token.span,
+                format!("{normalized}{hint}"),
+                format!("Add the type suffix `{hint}`"),
+            )),
+            _ if normalized != value => fixes.push(Suggestion::new(token.span, normalized, "Remove the leading zeros")),
+            _ => {}
+        }
+
+        // A literal zero negated by a unary minus, e.g. `-0field`.
+        if normalized == "0"
+            && suffix.is_some()
+            && i > 0
+            && tokens[i - 1].token == Token::Sub
+            && is_unary(&tokens[..i - 1])
+        {
+            fixes.push(Suggestion::new(tokens[i - 1].span, "", "Remove the sign from zero"));
+        }
+    }
+
+    Ok(fixes)
+}
+
+/// Returns `true` if a `-` following `preceding` is a unary minus.
+fn is_unary(preceding: &[SpannedToken]) -> bool {
+    !matches!(
+        preceding.last().map(|token| &token.token),
+        Some(
+            Token::Identifier(_)
+                | Token::Integer(_)
+                | Token::True
+                | Token::False
+                | Token::RightParen
+                | Token::RightSquare
+                | Token::SelfLower
+        )
+    ) && !preceding
+        .last()
+        .map_or(false, |token| LITERAL_SUFFIXES.contains(&token.token))
+}
+
+/// Returns the suffix that each unsuffixed integer in `tokens` should be given, where it can be inferred.
+fn suffix_hints(tokens: &[SpannedToken]) -> Vec<Option<Token>> {
+    let mut hints = vec![None; tokens.len()];
+    // The literal output type of the enclosing function or finalize block.
+    let mut output = None;
+
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i].token {
+            Token::Function | Token::Transition | Token::Finalize => output = None,
+            // An output type, e.g. `-> u8 {`.
+            Token::Arrow => {
+                if let [suffix, SpannedToken {
+                    token: Token::LeftCurly,
+                    ..
+                }, ..] = &tokens[i + 1..]
+                {
+                    output = Some(suffix.token.clone()).filter(|token| LITERAL_SUFFIXES.contains(token));
+                }
+            }
+            // A definition, e.g. `let x: u8 = 1;`.
+            Token::Let | Token::Const => {
+                if let [_, SpannedToken {
+                    token: Token::Colon, ..
+                }, type_, SpannedToken {
+                    token: Token::Assign, ..
+                }, ..] = &tokens[i + 1..]
+                {
+                    let start = i + 5;
+                    let end = find(tokens, start, &Token::Semicolon);
+                    hint(&mut hints, tokens, start, end, &type_.token);
+                    i = end;
+                }
+            }
+            // A loop, e.g. `for i: u8 in 0..10 {`.
+            Token::For => {
+                if let [_, SpannedToken {
+                    token: Token::Colon, ..
+                }, type_, SpannedToken { token: Token::In, .. }, ..] = &tokens[i + 1..]
+                {
+                    let start = i + 5;
+                    let end = find(tokens, start, &Token::LeftCurly);
+                    let separator = find(tokens, start, &Token::DotDot).min(end);
+                    hint(&mut hints, tokens, start, separator, &type_.token);
+                    hint(&mut hints, tokens, (separator + 1).min(end), end, &type_.token);
+                    i = end;
+                }
+            }
+            Token::Return => {
+                let start = i + 1;
+                let end = find(tokens, start, &Token::Semicolon);
+                if let Some(output) = &output {
+                    hint(&mut hints, tokens, start, end, output);
+                }
+                i = end;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    hints
+}
+
+/// Returns the index of the first `token` at or after `start`, or the number of tokens if there is none.
+fn find(tokens: &[SpannedToken], start: usize, token: &Token) -> usize {
+    (start..tokens.len())
+        .find(|i| &tokens[*i].token == token)
+        .unwrap_or(tokens.len())
+}
+
+/// Hints `type_` as the suffix of the unsuffixed integers in `tokens[start..end]`,
+/// if the tokens form an expression whose integers all have the same type as its result.
+fn hint(hints: &mut [Option<Token>], tokens: &[SpannedToken], start: usize, end: usize, type_: &Token) {
+    if !LITERAL_SUFFIXES.contains(type_) || start >= end {
+        return;
+    }
+    let expression = &tokens[start..end];
+    let preserves_type = expression.iter().enumerate().all(|(i, token)| match &token.token {
+        // A parenthesis after an identifier is a call, whose arguments may have any type.
+        Token::LeftParen => i == 0 || !matches!(expression[i - 1].token, Token::Identifier(_)),
+        // A group can be multiplied by a scalar.
+        Token::Mul => type_ != &Token::Group,
+        Token::Integer(_)
+        | Token::Identifier(_)
+        | Token::RightParen
+        | Token::Add
+        | Token::Sub
+        | Token::Div
+        | Token::Rem
+        | Token::BitAnd
+        | Token::BitOr
+        | Token::BitXor
+        | Token::Not => true,
+        token => LITERAL_SUFFIXES.contains(token),
+    });
+    if preserves_type {
+        for (i, token) in expression.iter().enumerate() {
+            let suffixed = expression
+                .get(i + 1)
+                .map_or(false, |next| LITERAL_SUFFIXES.contains(&next.token));
+            if matches!(token.token, Token::Integer(_)) && !suffixed {
+                hints[start + i] = Some(type_.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    fn fix(source: &str) -> String {
+        create_session_if_not_set_then(|_| {
+            let fixes = literal_fixes(source, BytePos(0)).unwrap();
+            Suggestion::apply_all(source, BytePos(0), &fixes)
+        })
+    }
+
+    #[test]
+    fn adds_inferred_suffixes() {
+        assert_eq!(fix("let x: u8 = 1 + y;"), "let x: u8 = 1u8 + y;");
+        assert_eq!(fix("const x: field = 007;"), "const x: field = 7field;");
+        assert_eq!(fix("for i: u32 in 0..10 {}"), "for i: u32 in 0u32..10u32 {}");
+        assert_eq!(
+            fix("function f() -> i8 { return -1; }"),
+            "function f() -> i8 { return -1i8; }"
+        );
+    }
+
+    #[test]
+    fn skips_ambiguous_expressions() {
+        assert_eq!(fix("let x: u8 = y ** 2;"), "let x: u8 = y ** 2;");
+        assert_eq!(fix("let x: u8 = f(1);"), "let x: u8 = f(1);");
+        assert_eq!(fix("let x: bool = y > 1;"), "let x: bool = y > 1;");
+        assert_eq!(fix("let g: group = 2 * h;"), "let g: group = 2 * h;");
+        assert_eq!(fix("let x: u8 = t.0;"), "let x: u8 = t.0;");
+    }
+
+    #[test]
+    fn normalizes_literals() {
+        assert_eq!(
+            fix("x = 00u8 - -0field + (01, 2)group;"),
+            "x = 0u8 - 0field + (1, 2)group;"
+        );
+    }
+}
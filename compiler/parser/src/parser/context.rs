@@ -14,13 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{tokenizer::*, Token};
+use crate::{tokenizer::*, CurrentDirectory, ImportResolver, Token};
 
 use leo_ast::*;
 use leo_errors::emitter::Handler;
 use leo_errors::{ParserError, ParserWarning, Result};
-use leo_span::{Span, Symbol};
+use leo_span::{span::BytePos, sym, Span, Symbol};
 
+use indexmap::IndexMap;
 use std::fmt::Display;
 use std::mem;
 
@@ -39,8 +40,14 @@ pub(crate) struct ParserContext<'a> {
     pub(crate) prev_token: SpannedToken,
     /// true if parsing an expression for if and loop statements -- means struct inits are not legal
     pub(crate) disallow_struct_construction: bool,
-    /// true if parsing an identifier inside an input file.
-    pub(crate) allow_identifier_underscores: bool,
+    /// The version of Leo that the program is written for, which determines the features it may use.
+    pub(crate) version: LanguageVersion,
+    /// The doc comments of the tokens that follow them, keyed by the start of the token.
+    docs: IndexMap<BytePos, String>,
+    /// Allocates the ids of the parsed nodes.
+    pub(crate) node_builder: NodeBuilder,
+    /// Resolves the programs that the program imports.
+    pub(crate) imports: &'a dyn ImportResolver,
 }
 
 /// Dummy span used to appease borrow checker.
@@ -49,11 +56,32 @@ const DUMMY_EOF: SpannedToken = SpannedToken {
     span: Span::dummy(),
 };
 
+/// Returns the text of a `/// ...` doc comment line, without the `///` and the space that follows it.
+fn doc_comment_line(comment: &str) -> Option<&str> {
+    let text = comment.strip_prefix("///").filter(|text| !text.starts_with('/'))?;
+    let text = text.trim_end_matches(['\n', '\r']);
+    Some(text.strip_prefix(' ').unwrap_or(text))
+}
+
 impl<'a> ParserContext<'a> {
     /// Returns a new [`ParserContext`] type given a vector of tokens.
     pub fn new(handler: &'a Handler, mut tokens: Vec<SpannedToken>) -> Self {
-        // Strip out comments.
-        tokens.retain(|x| !matches!(x.token, Token::CommentLine(_) | Token::CommentBlock(_)));
+        // Strip out comments, keeping each run of doc comments for the token that follows it.
+        let mut docs = IndexMap::new();
+        let mut doc_lines = Vec::new();
+        tokens.retain(|x| match &x.token {
+            Token::CommentLine(comment) => {
+                doc_lines.extend(doc_comment_line(comment).map(str::to_owned));
+                false
+            }
+            Token::CommentBlock(_) => false,
+            _ => {
+                if !doc_lines.is_empty() {
+                    docs.insert(x.span.lo, mem::take(&mut doc_lines).join("\n"));
+                }
+                true
+            }
+        });
         // For performance we reverse so that we get cheap `.pop()`s.
         tokens.reverse();
 
@@ -61,10 +89,13 @@ impl<'a> ParserContext<'a> {
         let mut p = Self {
             handler,
             disallow_struct_construction: false,
-            allow_identifier_underscores: false,
+            version: LanguageVersion::CURRENT,
             prev_token: token.clone(),
             token,
             tokens,
+            docs,
+            node_builder: NodeBuilder::default(),
+            imports: &CurrentDirectory,
         };
         p.bump();
         p
@@ -90,6 +121,11 @@ impl<'a> ParserContext<'a> {
         self.prev_token = mem::replace(&mut self.token, next_token);
     }
 
+    /// Removes and returns the doc comment that precedes the current token, if there is one.
+    pub(super) fn take_documentation(&mut self) -> Option<String> {
+        self.docs.remove(&self.token.span.lo)
+    }
+
     /// Checks whether the current token is `tok`.
     pub(super) fn check(&self, tok: &Token) -> bool {
         &self.token.token == tok
@@ -106,6 +142,13 @@ impl<'a> ParserContext<'a> {
         self.check(token).then(|| self.bump()).is_some()
     }
 
+    /// Eats the `aleo` of an external call `foo.aleo/bar()`, which, unlike a member named `aleo`, is followed by `/`.
+    pub(super) fn eat_external_network(&mut self) -> bool {
+        let is_external =
+            self.check(&Token::Identifier(sym::aleo)) && self.look_ahead(1, |next| next.token == Token::Div);
+        is_external.then(|| self.bump()).is_some()
+    }
+
     /// Look-ahead `dist` tokens of `self.token` and get access to that token there.
     /// When `dist == 0` then the current token is looked at.
     pub(super) fn look_ahead<'s, R>(&'s self, dist: usize, looker: impl FnOnce(&'s SpannedToken) -> R) -> R {
@@ -126,9 +169,21 @@ impl<'a> ParserContext<'a> {
         self.handler.emit_err(err);
     }
 
+    /// Emits an error if `feature`, used at `span`, is not available in the version of Leo that the program is written for.
+    pub(super) fn check_feature(&self, feature: Feature, span: Span) {
+        if !feature.is_available_in(self.version) {
+            self.emit_err(ParserError::feature_requires_version(
+                feature,
+                feature.minimum_version(),
+                self.version,
+                span,
+            ));
+        }
+    }
+
     /// Emit the error `err`.
     pub(super) fn emit_warning(&self, warning: ParserWarning) {
-        self.handler.emit_warning(warning.into());
+        self.handler.emit_warning(warning);
     }
 
     /// Returns true if the next token exists.
@@ -139,7 +194,11 @@ impl<'a> ParserContext<'a> {
     /// At the previous token, return and make an identifier with `name`.
     fn mk_ident_prev(&self, name: Symbol) -> Identifier {
         let span = self.prev_token.span;
-        Identifier { name, span }
+        Identifier {
+            name,
+            span,
+            id: self.node_builder.next_id(),
+        }
     }
 
     /// Eats the next token if its an identifier and returns it.
@@ -157,6 +216,16 @@ impl<'a> ParserContext<'a> {
             .ok_or_else(|| ParserError::unexpected_str(&self.token.token, "identifier", self.token.span).into())
     }
 
+    /// Expects an [`Identifier`] naming a program item, such as a function, struct, or mapping.
+    /// Items appear by name in the compiled program, so unlike variables they cannot begin with an underscore.
+    pub(super) fn expect_item_identifier(&mut self) -> Result<Identifier> {
+        let identifier = self.expect_identifier()?;
+        if identifier.name.to_string().starts_with('_') {
+            return Err(ParserError::item_name_starts_with_underscore(identifier, identifier.span).into());
+        }
+        Ok(identifier)
+    }
+
     ///
     /// Removes the next token if it is a [`Token::Integer(_)`] and returns it, or [None] if
     /// the next token is not a [`Token::Integer(_)`] or if the next token does not exist.
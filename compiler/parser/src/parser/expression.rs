@@ -17,7 +17,7 @@
 use super::*;
 use leo_errors::{ParserError, Result};
 
-use leo_span::{sym, Symbol};
+use leo_span::sym;
 use snarkvm_console::{account::Address, network::Testnet3};
 
 const INT_TYPES: &[Token] = &[
@@ -73,18 +73,20 @@ impl ParserContext<'_> {
                 condition: Box::new(expr),
                 if_true: Box::new(if_true),
                 if_false: Box::new(if_false),
+                id: self.node_builder.next_id(),
             });
         }
         Ok(expr)
     }
 
     /// Constructs a binary expression `left op right`.
-    fn bin_expr(left: Expression, right: Expression, op: BinaryOperation) -> Expression {
+    fn bin_expr(&self, left: Expression, right: Expression, op: BinaryOperation) -> Expression {
         Expression::Binary(BinaryExpression {
             span: left.span() + right.span(),
             op,
             left: Box::new(left),
             right: Box::new(right),
+            id: self.node_builder.next_id(),
         })
     }
 
@@ -97,7 +99,8 @@ impl ParserContext<'_> {
     ) -> Result<Expression> {
         let mut expr = f(self)?;
         while let Some(op) = self.eat_bin_op(tokens) {
-            expr = Self::bin_expr(expr, f(self)?, op);
+            let right = f(self)?;
+            expr = self.bin_expr(expr, right, op);
         }
         Ok(expr)
     }
@@ -152,7 +155,7 @@ impl ParserContext<'_> {
         let mut expr = self.parse_bitwise_exclusive_or_expression()?;
         if let Some(op) = self.eat_bin_op(&[Token::Lt, Token::LtEq, Token::Gt, Token::GtEq]) {
             let right = self.parse_bitwise_exclusive_or_expression()?;
-            expr = Self::bin_expr(expr, right, op);
+            expr = self.bin_expr(expr, right, op);
         }
         Ok(expr)
     }
@@ -165,7 +168,7 @@ impl ParserContext<'_> {
         let mut expr = self.parse_ordering_expression()?;
         if let Some(op) = self.eat_bin_op(&[Token::Eq, Token::NotEq]) {
             let right = self.parse_ordering_expression()?;
-            expr = Self::bin_expr(expr, right, op);
+            expr = self.bin_expr(expr, right, op);
         }
         Ok(expr)
     }
@@ -230,7 +233,7 @@ impl ParserContext<'_> {
 
         if let Some(op) = self.eat_bin_op(&[Token::Pow]) {
             let right = self.parse_exponential_expression()?;
-            expr = Self::bin_expr(expr, right, op);
+            expr = self.bin_expr(expr, right, op);
         }
 
         Ok(expr)
@@ -260,16 +263,22 @@ impl ParserContext<'_> {
                 // If the unary operation is a negate, and the inner expression is a signed integer literal,
                 // then produce a negative integer literal.
                 // This helps handle a special case where -128i8, treated as a unary expression, overflows, but -128i8, treated as an integer literal doesn't.
-                Expression::Literal(Literal::Integer(integer_type, string, span))
+                Expression::Literal(Literal::Integer(integer_type, string, span, id))
                     if op == UnaryOperation::Negate && inner_is_integer =>
                 {
-                    Expression::Literal(Literal::Integer(integer_type, format!("-{}", string), op_span + span))
+                    Expression::Literal(Literal::Integer(
+                        integer_type,
+                        format!("-{}", string),
+                        op_span + span,
+                        id,
+                    ))
                 }
                 // Otherwise, produce a unary expression.
                 _ => Expression::Unary(UnaryExpression {
                     span: op_span + inner.span(),
                     op,
                     receiver: Box::new(inner),
+                    id: self.node_builder.next_id(),
                 }),
             };
         }
@@ -289,6 +298,7 @@ impl ParserContext<'_> {
                 span,
                 op,
                 receiver: Box::new(receiver),
+                id: self.node_builder.next_id(),
             }))
         } else if let (1, Some(op)) = (args.len(), BinaryOperation::from_symbol(method.name)) {
             // Found a binary operator and the argument list contains a single argument.
@@ -297,11 +307,15 @@ impl ParserContext<'_> {
                 op,
                 left: Box::new(receiver),
                 right: Box::new(args.swap_remove(0)),
+                id: self.node_builder.next_id(),
             }))
         } else {
             // Either an invalid unary/binary operator, or more arguments given.
             self.emit_err(ParserError::invalid_method_call(receiver, method, span));
-            Ok(Expression::Err(ErrExpression { span }))
+            Ok(Expression::Err(ErrExpression {
+                span,
+                id: self.node_builder.next_id(),
+            }))
         }
     }
 
@@ -329,6 +343,7 @@ impl ParserContext<'_> {
                 ty: type_,
                 name: member_name,
                 args,
+                id: self.node_builder.next_id(),
             })
         } else {
             // Return the struct constant.
@@ -336,6 +351,7 @@ impl ParserContext<'_> {
                 span: module_name.span() + member_name.span(),
                 ty: type_,
                 name: member_name,
+                id: self.node_builder.next_id(),
             })
         }))
     }
@@ -363,9 +379,10 @@ impl ParserContext<'_> {
                         tuple: Box::new(expr),
                         index,
                         span,
+                        id: self.node_builder.next_id(),
                     }))
-                } else if self.eat(&Token::Leo) {
-                    // Eat an external function call.
+                } else if self.eat(&Token::Leo) || self.eat_external_network() {
+                    // Eat an external function call, e.g. `foo.leo/bar()` or `foo.aleo/bar()`.
                     self.eat(&Token::Div); // todo: Make `/` a more general token.
 
                     // Parse function name.
@@ -378,6 +395,7 @@ impl ParserContext<'_> {
                         function: Box::new(Expression::Identifier(name)),
                         external: Some(Box::new(expr)),
                         arguments,
+                        id: self.node_builder.next_id(),
                     });
                 } else {
                     // Parse identifier name.
@@ -392,6 +410,7 @@ impl ParserContext<'_> {
                             span: expr.span(),
                             inner: Box::new(expr),
                             name,
+                            id: self.node_builder.next_id(),
                         }))
                     }
                 }
@@ -406,6 +425,7 @@ impl ParserContext<'_> {
                     function: Box::new(expr),
                     external: None,
                     arguments,
+                    id: self.node_builder.next_id(),
                 });
             }
             // Check if next token is a dot to see if we are calling recursive method.
@@ -428,7 +448,11 @@ impl ParserContext<'_> {
         if !trailing && tuple.len() == 1 {
             Ok(tuple.swap_remove(0))
         } else {
-            Ok(Expression::Tuple(TupleExpression { elements: tuple, span }))
+            Ok(Expression::Tuple(TupleExpression {
+                elements: tuple,
+                span,
+                id: self.node_builder.next_id(),
+            }))
         }
     }
 
@@ -480,6 +504,7 @@ impl ParserContext<'_> {
             span: start_span + &end_span,
             x: first_gc,
             y: second_gc,
+            id: self.node_builder.next_id(),
         };
 
         // Eat everything so that this isn't just peeking.
@@ -495,13 +520,7 @@ impl ParserContext<'_> {
     }
 
     fn parse_struct_member(&mut self) -> Result<StructVariableInitializer> {
-        let identifier = if self.allow_identifier_underscores && self.eat(&Token::Underscore) {
-            // Allow `_nonce` for struct records.
-            let identifier_without_underscore = self.expect_identifier()?;
-            Identifier::new(Symbol::intern(&format!("_{}", identifier_without_underscore.name)))
-        } else {
-            self.expect_identifier()?
-        };
+        let identifier = self.expect_identifier()?;
 
         let expression = if self.eat(&Token::Colon) {
             // Parse individual struct variable declarations.
@@ -525,6 +544,7 @@ impl ParserContext<'_> {
             span: identifier.span + end,
             name: identifier,
             members,
+            id: self.node_builder.next_id(),
         }))
     }
 
@@ -552,38 +572,53 @@ impl ParserContext<'_> {
                     // Literal followed by `field`, e.g., `42field`.
                     Some(Token::Field) => {
                         assert_no_whitespace("field")?;
-                        Expression::Literal(Literal::Field(value, full_span))
+                        Expression::Literal(Literal::Field(value, full_span, self.node_builder.next_id()))
                     }
                     // Literal followed by `group`, e.g., `42group`.
                     Some(Token::Group) => {
                         assert_no_whitespace("group")?;
-                        Expression::Literal(Literal::Group(Box::new(GroupLiteral::Single(value, full_span))))
+                        Expression::Literal(Literal::Group(Box::new(GroupLiteral::Single(
+                            value,
+                            full_span,
+                            self.node_builder.next_id(),
+                        ))))
                     }
                     // Literal followed by `scalar` e.g., `42scalar`.
                     Some(Token::Scalar) => {
                         assert_no_whitespace("scalar")?;
-                        Expression::Literal(Literal::Scalar(value, full_span))
+                        Expression::Literal(Literal::Scalar(value, full_span, self.node_builder.next_id()))
                     }
                     // Literal followed by other type suffix, e.g., `42u8`.
                     Some(suffix) => {
                         assert_no_whitespace(&suffix.to_string())?;
                         let int_ty = Self::token_to_int_type(suffix).expect("unknown int type token");
-                        Expression::Literal(Literal::Integer(int_ty, value, full_span))
+                        Expression::Literal(Literal::Integer(int_ty, value, full_span, self.node_builder.next_id()))
                     }
                     None => return Err(ParserError::implicit_values_not_allowed(value, span).into()),
                 }
             }
-            Token::True => Expression::Literal(Literal::Boolean(true, span)),
-            Token::False => Expression::Literal(Literal::Boolean(false, span)),
+            Token::True => Expression::Literal(Literal::Boolean(true, span, self.node_builder.next_id())),
+            Token::False => Expression::Literal(Literal::Boolean(false, span, self.node_builder.next_id())),
             Token::AddressLit(address_string) => {
                 if address_string.parse::<Address<Testnet3>>().is_err() {
                     self.emit_err(ParserError::invalid_address_lit(&address_string, span));
                 }
-                Expression::Literal(Literal::Address(address_string, span))
+                Expression::Literal(Literal::Address(address_string, span, self.node_builder.next_id()))
+            }
+            // Unlike addresses, signatures are only checked by snarkVM, when the program is executed.
+            Token::SignatureLit(signature) => {
+                self.check_feature(Feature::Signatures, span);
+                Expression::Literal(Literal::Signature(signature, span, self.node_builder.next_id()))
+            }
+            Token::StaticString(value) => {
+                Expression::Literal(Literal::String(value, span, self.node_builder.next_id()))
             }
-            Token::StaticString(value) => Expression::Literal(Literal::String(value, span)),
             Token::Identifier(name) => {
-                let ident = Identifier { name, span };
+                let ident = Identifier {
+                    name,
+                    span,
+                    id: self.node_builder.next_id(),
+                };
                 if !self.disallow_struct_construction && self.check(&Token::LeftCurly) {
                     // Parse struct and records inits as struct expressions.
                     // Enforce struct or record type later at type checking.
@@ -595,10 +630,12 @@ impl ParserContext<'_> {
             Token::SelfLower => Expression::Identifier(Identifier {
                 name: sym::SelfLower,
                 span,
+                id: self.node_builder.next_id(),
             }),
             t if crate::type_::TYPE_TOKENS.contains(&t) => Expression::Identifier(Identifier {
                 name: t.keyword_to_symbol().unwrap(),
                 span,
+                id: self.node_builder.next_id(),
             }),
             token => {
                 return Err(ParserError::unexpected_str(token, "expression", span).into());
@@ -15,12 +15,12 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
-use crate::parse_ast;
+use crate::aleo_stub;
 use leo_errors::{CompilerError, ParserError, ParserWarning, Result};
 use leo_span::source_map::FileName;
 use leo_span::symbol::with_session_globals;
 
-use std::fs;
+use std::{fs, mem};
 
 impl ParserContext<'_> {
     /// Returns a [`Program`] AST if all tokens can be consumed and represent a valid Leo program.
@@ -60,6 +60,7 @@ impl ParserContext<'_> {
         Ok(Program {
             imports,
             program_scopes,
+            import_source: None,
         })
     }
 
@@ -75,7 +76,7 @@ impl ParserContext<'_> {
         )
     }
 
-    /// Parses an import statement `import foo.leo;`.
+    /// Parses an import statement `import foo.leo;`, or `import foo.aleo;` for a deployed program.
     pub(super) fn parse_import(&mut self) -> Result<(Identifier, Program)> {
         // Parse `import`.
         let _start = self.expect(&Token::Import)?;
@@ -83,42 +84,53 @@ impl ParserContext<'_> {
         // Parse `foo`.
         let import_name = self.expect_identifier()?;
 
-        // Parse `.leo`.
+        // Parse `.leo` or `.aleo`.
         self.expect(&Token::Dot)?;
-        if !self.eat(&Token::Leo) {
-            // Throw error for non-leo files.
-            return Err(ParserError::leo_imports_only(self.token.span).into());
-        }
+        let is_bytecode = match &self.token.token {
+            Token::Leo => false,
+            Token::Identifier(extension) if *extension == sym::aleo => true,
+            // Throw error for other files.
+            _ => return Err(ParserError::leo_imports_only(self.token.span).into()),
+        };
+        self.bump();
 
         let _end = self.expect(&Token::Semicolon)?;
 
-        // Tokenize and parse import file.
-        // Todo: move this to a different module.
-        let mut import_file_path =
-            std::env::current_dir().map_err(|err| CompilerError::cannot_open_cwd(err, self.token.span))?;
-        import_file_path.push("imports");
-        import_file_path.push(format!("{}.leo", import_name.name));
-
-        // Throw an error if the import file doesn't exist.
-        if !import_file_path.exists() {
-            return Err(CompilerError::import_not_found(import_file_path.display(), self.prev_token.span).into());
-        }
+        // Find the import file.
+        let source = match is_bytecode {
+            true => self.imports.resolve_bytecode(&import_name)?,
+            false => self.imports.resolve(&import_name)?,
+        };
 
         // Read the import file into string.
         // Todo: protect against cyclic imports.
         let program_string =
-            fs::read_to_string(&import_file_path).map_err(|e| CompilerError::file_read_error(&import_file_path, e))?;
-
-        // Create import file name.
-        let name: FileName = FileName::Real(import_file_path);
+            fs::read_to_string(&source.path).map_err(|e| CompilerError::file_read_error(&source.path, e))?;
+
+        // The bytecode of a deployed program is parsed as its stub, which is named after the bytecode.
+        let (program_string, name) = match is_bytecode {
+            true => (
+                aleo_stub(&import_name, &program_string)?,
+                FileName::Custom(format!("stub of {}", source.path.display())),
+            ),
+            false => (program_string, FileName::Real(source.path.clone())),
+        };
 
         // Register the source (`program_string`) in the source map.
         let prg_sf = with_session_globals(|s| s.source_map.new_source(&program_string, name));
 
-        // Use the parser to construct the imported abstract syntax tree (ast).
-        let program_ast = parse_ast(self.handler, &prg_sf.src, prg_sf.start_pos)?;
-
-        Ok((import_name, program_ast.into_repr()))
+        // Use the parser to construct the imported abstract syntax tree (ast), resolving its imports in the same way.
+        let version = declared_version(&prg_sf.src, prg_sf.start_pos)?.unwrap_or(LanguageVersion::CURRENT);
+        let mut tokens = ParserContext::new(self.handler, crate::tokenize(&prg_sf.src, prg_sf.start_pos)?);
+        tokens.version = version;
+        tokens.imports = self.imports;
+        // The nodes of the import are numbered after the nodes parsed so far, so that the ids of a program are unique.
+        tokens.node_builder = mem::take(&mut self.node_builder);
+        let mut program = tokens.parse_program()?;
+        self.node_builder = tokens.node_builder;
+        program.import_source = Some(source);
+
+        Ok((import_name, program))
     }
 
     /// Parsers a program scope `program foo.aleo { ... }`.
@@ -127,7 +139,7 @@ impl ParserContext<'_> {
         let start = self.expect(&Token::Program)?;
 
         // Parse the program name.
-        let name = self.expect_identifier()?;
+        let name = self.expect_item_identifier()?;
 
         // Parse the program network.
         self.expect(&Token::Dot)?;
@@ -159,7 +171,7 @@ impl ParserContext<'_> {
                     let (id, mapping) = self.parse_mapping()?;
                     mappings.insert(id, mapping);
                 }
-                Token::At | Token::Function | Token::Transition => {
+                Token::At | Token::Async | Token::Function | Token::Transition => {
                     let (id, function) = self.parse_function()?;
                     functions.insert(id, function);
                 }
@@ -173,6 +185,7 @@ impl ParserContext<'_> {
                             Token::Record,
                             Token::Mapping,
                             Token::At,
+                            Token::Async,
                             Token::Function,
                             Token::Transition,
                         ],
@@ -235,16 +248,19 @@ impl ParserContext<'_> {
 
     /// Returns a [`Member`] AST node if the next tokens represent a struct member variable.
     fn parse_member_variable_declaration(&mut self) -> Result<Member> {
-        let (identifier, type_) = self.parse_typed_ident()?;
+        let identifier = self.expect_item_identifier()?;
+        self.expect(&Token::Colon)?;
+        let type_ = self.parse_type()?.0;
 
         Ok(Member { identifier, type_ })
     }
 
     /// Parses a struct or record definition, e.g., `struct Foo { ... }` or `record Foo { ... }`.
     pub(super) fn parse_struct(&mut self) -> Result<(Identifier, Struct)> {
+        let documentation = self.take_documentation();
         let is_record = matches!(&self.token.token, Token::Record);
         let start = self.expect_any(&[Token::Struct, Token::Record])?;
-        let struct_name = self.expect_identifier()?;
+        let struct_name = self.expect_item_identifier()?;
 
         self.expect(&Token::LeftCurly)?;
         let (members, end) = self.parse_struct_members()?;
@@ -252,6 +268,7 @@ impl ParserContext<'_> {
         Ok((
             struct_name,
             Struct {
+                documentation,
                 identifier: struct_name,
                 members,
                 is_record,
@@ -262,8 +279,10 @@ impl ParserContext<'_> {
 
     /// Parses a mapping declaration, e.g. `mapping balances: address => u128`.
     pub(super) fn parse_mapping(&mut self) -> Result<(Identifier, Mapping)> {
+        let documentation = self.take_documentation();
         let start = self.expect(&Token::Mapping)?;
-        let identifier = self.expect_identifier()?;
+        self.check_feature(Feature::Mappings, start);
+        let identifier = self.expect_item_identifier()?;
         self.expect(&Token::Colon)?;
         let (key_type, _) = self.parse_type()?;
         self.expect(&Token::BigArrow)?;
@@ -272,6 +291,7 @@ impl ParserContext<'_> {
         Ok((
             identifier,
             Mapping {
+                documentation,
                 identifier,
                 key_type,
                 value_type,
@@ -282,25 +302,45 @@ impl ParserContext<'_> {
 
     /// Returns a [`ParamMode`] AST node if the next tokens represent a function parameter mode.
     pub(super) fn parse_mode(&mut self) -> Result<Mode> {
-        // TODO: Allow explicit "private" mode.
-        let public = self.eat(&Token::Public).then_some(self.prev_token.span);
-        let constant = self.eat(&Token::Constant).then_some(self.prev_token.span);
-        let const_ = self.eat(&Token::Const).then_some(self.prev_token.span);
-
-        if let Some(span) = const_ {
-            self.emit_warning(ParserWarning::const_parameter_or_input(span));
+        let mut modes = Vec::new();
+        loop {
+            let mode = match &self.token.token {
+                Token::Public => Mode::Public,
+                Token::Constant => Mode::Const,
+                Token::Const => {
+                    self.emit_warning(ParserWarning::const_parameter_or_input(self.token.span));
+                    Mode::Const
+                }
+                // `private` and `witness` are only modes if a name, a type, or another mode follows them,
+                // so that they can still be used as names.
+                Token::Identifier(name)
+                    if (*name == sym::private || *name == sym::witness)
+                        && self.look_ahead(1, |next| {
+                            matches!(
+                                next.token,
+                                Token::Identifier(_) | Token::Public | Token::Constant | Token::Const
+                            ) || crate::type_::TYPE_TOKENS.contains(&next.token)
+                        }) =>
+                {
+                    match *name == sym::private {
+                        true => Mode::Private,
+                        false => {
+                            self.check_feature(Feature::Witnesses, self.token.span);
+                            Mode::Witness
+                        }
+                    }
+                }
+                _ => break,
+            };
+            self.bump();
+            modes.push((mode, self.prev_token.span));
         }
 
-        match (public, constant, const_) {
-            (None, Some(_), None) => Ok(Mode::Const),
-            (None, None, Some(_)) => Ok(Mode::Const),
-            (None, None, None) => Ok(Mode::None),
-            (Some(_), None, None) => Ok(Mode::Public),
-            (Some(m1), Some(m2), None) | (Some(m1), None, Some(m2)) | (None, Some(m1), Some(m2)) => {
-                Err(ParserError::inputs_multiple_variable_types_specified(m1 + m2).into())
-            }
-            (Some(m1), Some(m2), Some(m3)) => {
-                Err(ParserError::inputs_multiple_variable_types_specified(m1 + m2 + m3).into())
+        match modes.as_slice() {
+            [] => Ok(Mode::None),
+            [(mode, _)] => Ok(*mode),
+            [(_, first), .., (_, last)] => {
+                Err(ParserError::inputs_multiple_variable_types_specified(*first + *last).into())
             }
         }
     }
@@ -374,7 +414,7 @@ impl ParserContext<'_> {
             span = span + self.prev_token.span;
 
             Ok(Output::External(External {
-                identifier: Identifier::new(Symbol::intern("dummy")),
+                identifier: Identifier::new(Symbol::intern("dummy"), self.node_builder.next_id()),
                 program_name: external,
                 record,
                 span,
@@ -399,6 +439,7 @@ impl ParserContext<'_> {
             Token::Program => Identifier {
                 name: sym::program,
                 span: self.expect(&Token::Program)?,
+                id: self.node_builder.next_id(),
             },
             _ => self.expect_identifier()?,
         };
@@ -406,28 +447,71 @@ impl ParserContext<'_> {
 
         // TODO: Verify that this check is sound.
         // Check that there is no whitespace in between the `@` symbol and identifier.
-        match identifier.span.hi.0 - start.lo.0 > 1 + identifier.name.to_string().len() as u32 {
-            true => Err(ParserError::space_in_annotation(span).into()),
-            false => Ok(Annotation { identifier, span }),
+        if identifier.span.hi.0 - start.lo.0 > 1 + identifier.name.to_string().len() as u32 {
+            return Err(ParserError::space_in_annotation(span).into());
         }
+
+        // Parse the arguments, if any, e.g. `(cases = 256)` or `(token = token { owner: aleo1..., gates: 0u64 })`.
+        // A value other than a bare number, e.g. `5u64`, is kept as written, since it is only parsed when it is used.
+        // An argument without a value, e.g. `none` in `@optimize(none)`, is given an empty value.
+        let (arguments, span) = match self.peek_is_left_par() {
+            true => {
+                let (arguments, _, arguments_span) = self.parse_paren_comma_list(|p| {
+                    let name = p.expect_identifier()?;
+                    if !p.eat(&Token::Assign) {
+                        return Ok(Some((name, String::new())));
+                    }
+                    let value = match &p.token.token {
+                        Token::Integer(value)
+                            if p.look_ahead(1, |next| matches!(next.token, Token::Comma | Token::RightParen)) =>
+                        {
+                            let value = value.clone();
+                            p.bump();
+                            value
+                        }
+                        _ => {
+                            let expression = p.parse_expression()?;
+                            with_session_globals(|s| s.source_map.contents_of_span(expression.span()))
+                                .unwrap_or_else(|| expression.to_string())
+                        }
+                    };
+                    Ok(Some((name, value)))
+                })?;
+                (arguments, span + arguments_span)
+            }
+            false => (Vec::new(), span),
+        };
+
+        Ok(Annotation {
+            identifier,
+            arguments,
+            span,
+        })
     }
 
     /// Returns an [`(Identifier, Function)`] AST node if the next tokens represent a function name
     /// and function definition.
-    fn parse_function(&mut self) -> Result<(Identifier, Function)> {
+    pub(super) fn parse_function(&mut self) -> Result<(Identifier, Function)> {
+        let documentation = self.take_documentation();
         // TODO: Handle dangling annotations.
         // Parse annotations, if they exist.
         let mut annotations = Vec::new();
         while self.look_ahead(0, |t| &t.token) == &Token::At {
             annotations.push(self.parse_annotation()?)
         }
-        // Parse `<call_type> IDENT`, where `<call_type>` is `function` or `transition`.
-        let (call_type, start) = match self.token.token {
-            Token::Function => (CallType::Standard, self.expect(&Token::Function)?),
-            Token::Transition => (CallType::Transition, self.expect(&Token::Transition)?),
+        // Parse `<call_type> IDENT`, where `<call_type>` is `function` or `transition`, optionally preceded by `async`.
+        let async_start = self.eat(&Token::Async).then(|| self.prev_token.span);
+        let (call_type, start) = match (self.token.token.clone(), async_start) {
+            (Token::Function, None) => (CallType::Standard, self.expect(&Token::Function)?),
+            (Token::Transition, None) => (CallType::Transition, self.expect(&Token::Transition)?),
+            (Token::Function, Some(start)) => (CallType::AsyncFunction, start + self.expect(&Token::Function)?),
+            (Token::Transition, Some(start)) => (CallType::AsyncTransition, start + self.expect(&Token::Transition)?),
             _ => self.unexpected("'function', 'transition'")?,
         };
-        let name = self.expect_identifier()?;
+        if let Some(async_start) = async_start {
+            self.check_feature(Feature::AsyncFunctions, async_start);
+        }
+        let name = self.expect_item_identifier()?;
 
         // Parse parameters.
         let (inputs, ..) = self.parse_paren_comma_list(|p| p.parse_input().map(Some))?;
@@ -455,6 +539,7 @@ impl ParserContext<'_> {
             true => {
                 // Get starting span.
                 let start = self.prev_token.span;
+                self.check_feature(Feature::Mappings, start);
 
                 // Parse the identifier.
                 let identifier = self.expect_identifier()?;
@@ -487,7 +572,17 @@ impl ParserContext<'_> {
         let span = start + block.span;
         Ok((
             name,
-            Function::new(annotations, call_type, name, inputs, output, block, finalize, span),
+            Function::new(
+                documentation,
+                annotations,
+                call_type,
+                name,
+                inputs,
+                output,
+                block,
+                finalize,
+                span,
+            ),
         ))
     }
 }
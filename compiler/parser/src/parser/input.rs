@@ -21,8 +21,6 @@ use leo_errors::{ParserError, Result};
 impl ParserContext<'_> {
     /// Returns a [`ParsedInputFile`] struct filled with the data acquired in the file.
     pub(crate) fn parse_input_file(&mut self) -> Result<InputAst> {
-        // Allow underscores in identifiers for input record declarations.
-        self.allow_identifier_underscores = true;
         let mut sections = Vec::new();
 
         while self.has_next() {
@@ -33,9 +31,6 @@ impl ParserContext<'_> {
             }
         }
 
-        // Do not allow underscores in identifiers outside of input files.
-        self.allow_identifier_underscores = false;
-
         Ok(InputAst { sections })
     }
 
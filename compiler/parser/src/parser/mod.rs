@@ -19,11 +19,11 @@
 //! This module contains the [`parse()`] method which calls the underlying [`tokenize()`]
 //! method to create a new program ast.
 
-use crate::{tokenizer::*, Token};
+use crate::{tokenizer::*, CurrentDirectory, ImportResolver, Token};
 
 use leo_ast::*;
 use leo_errors::emitter::Handler;
-use leo_errors::Result;
+use leo_errors::{ParserError, Result};
 use leo_span::Span;
 
 use indexmap::IndexMap;
@@ -36,19 +36,324 @@ pub(super) use context::ParserContext;
 mod expression;
 mod file;
 mod input;
+mod reparse;
+pub use reparse::reparse;
 mod statement;
 pub(super) mod type_;
 
 /// Creates a new program from a given file path and source code text.
+/// The program is written for the version of Leo that it declares, or for the current version if it declares none.
+/// Its imports are resolved in the `imports/` directory of the current directory.
 pub fn parse(handler: &Handler, source: &str, start_pos: BytePos) -> Result<Program> {
+    parse_with_imports(handler, source, start_pos, &CurrentDirectory)
+}
+
+/// Creates a new program like [`parse()`], whose imports are resolved by `imports`.
+pub fn parse_with_imports(
+    handler: &Handler,
+    source: &str,
+    start_pos: BytePos,
+    imports: &dyn ImportResolver,
+) -> Result<Program> {
+    let version = declared_version(source, start_pos)?.unwrap_or(LanguageVersion::CURRENT);
+
+    parse_with_version(handler, source, start_pos, version, imports)
+}
+
+/// Creates a new program from source code text written for `version` of Leo, whose imports are resolved by `imports`.
+/// Using a feature that is not available in `version` is an error.
+pub fn parse_with_version(
+    handler: &Handler,
+    source: &str,
+    start_pos: BytePos,
+    version: LanguageVersion,
+    imports: &dyn ImportResolver,
+) -> Result<Program> {
     let mut tokens = ParserContext::new(handler, crate::tokenize(source, start_pos)?);
+    tokens.version = version;
+    tokens.imports = imports;
 
     tokens.parse_program()
 }
 
+/// Returns the version of Leo declared by a `// leo:version 1.5` comment before the first item of `source`, if any.
+/// Declaring a version that is newer than the current one is an error.
+pub fn declared_version(source: &str, start_pos: BytePos) -> Result<Option<LanguageVersion>> {
+    for token in crate::tokenize_iter(source, start_pos) {
+        let token = match token {
+            Ok(token) => token,
+            // The error is reported when the program is parsed.
+            Err(_) => break,
+        };
+        match &token.token {
+            Token::CommentLine(comment) => {
+                if let Some(version) = LanguageVersion::pragma(comment) {
+                    return match LanguageVersion::parse(version) {
+                        None => Err(ParserError::invalid_language_version(version, token.span).into()),
+                        Some(version) if version > LanguageVersion::CURRENT => Err(
+                            ParserError::unsupported_language_version(version, LanguageVersion::CURRENT, token.span)
+                                .into(),
+                        ),
+                        Some(version) => Ok(Some(version)),
+                    };
+                }
+            }
+            Token::CommentBlock(_) | Token::WhiteSpace => {}
+            _ => break,
+        }
+    }
+
+    Ok(None)
+}
+
+/// Parses a single function definition from the given `source` code text, e.g. a function that is being edited.
+pub fn parse_function(handler: &Handler, source: &str, start_pos: BytePos) -> Result<Function> {
+    let mut tokens = ParserContext::new(handler, crate::tokenize(source, start_pos)?);
+
+    let (_, function) = tokens.parse_function()?;
+    tokens.expect(&Token::Eof)?;
+
+    Ok(function)
+}
+
+/// Parses a single statement from the given `source` code text, e.g. one entered at a prompt.
+pub fn parse_statement(handler: &Handler, source: &str, start_pos: BytePos) -> Result<Statement> {
+    let mut tokens = ParserContext::new(handler, crate::tokenize(source, start_pos)?);
+
+    let statement = tokens.parse_statement()?;
+    tokens.expect(&Token::Eof)?;
+
+    Ok(statement)
+}
+
+/// Parses a single expression from the given `source` code text, e.g. one entered at a prompt.
+pub fn parse_expression(handler: &Handler, source: &str, start_pos: BytePos) -> Result<Expression> {
+    let mut tokens = ParserContext::new(handler, crate::tokenize(source, start_pos)?);
+
+    let expression = tokens.parse_expression()?;
+    tokens.expect(&Token::Eof)?;
+
+    Ok(expression)
+}
+
 /// Parses an input file at the given file `path` and `source` code text.
 pub fn parse_input(handler: &Handler, source: &str, start_pos: BytePos) -> Result<InputAst> {
     let mut tokens = ParserContext::new(handler, crate::tokenize(source, start_pos)?);
 
     tokens.parse_input_file()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    const MAPPING: &str = "program test.aleo {
+    mapping balances: address => u64;
+}
+";
+
+    fn version_of(source: &str) -> Result<Option<LanguageVersion>> {
+        create_session_if_not_set_then(|_| declared_version(source, BytePos(0)))
+    }
+
+    #[test]
+    fn pragma() {
+        let current = LanguageVersion::CURRENT;
+        assert_eq!(version_of(MAPPING).unwrap(), None);
+        assert_eq!(
+            version_of("// leo:version 1.4\n").unwrap(),
+            Some(LanguageVersion::new(1, 4))
+        );
+        assert_eq!(
+            version_of("/* License */\n\n//leo:version 1.6.2\nprogram").unwrap(),
+            Some(current)
+        );
+        // Only comments before the first item declare a version.
+        assert_eq!(version_of("program test.aleo {}\n// leo:version 1.4\n").unwrap(), None);
+
+        assert!(version_of("// leo:version one\n").is_err());
+        assert!(version_of("// leo:version 99.0\n").is_err());
+    }
+
+    #[test]
+    fn gated_feature() {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let source = format!("// leo:version 1.4\n{}", MAPPING);
+            assert!(parse(&handler, &source, BytePos(0)).is_ok());
+            assert_eq!(handler.err_count(), 1);
+
+            let handler = Handler::default();
+            assert!(parse(&handler, MAPPING, BytePos(0)).is_ok());
+            assert_eq!(handler.err_count(), 0);
+        })
+    }
+
+    #[test]
+    fn documentation() {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let source = "program test.aleo {
+    /// The balance of each account.
+    mapping balances: address => u64;
+
+    // Not documentation.
+    struct Point { x: u8, y: u8 }
+
+    /// Returns `a`.
+    ///
+    ///Ignores `b`.
+    //// Not documentation either.
+    @program
+    transition first(a: u8, b: u8) -> u8 {
+        /// Not attached to an item.
+        return a;
+    }
+}
+";
+            let program = parse(&handler, source, BytePos(0)).unwrap();
+            let scope = program.program_scopes.values().next().unwrap();
+
+            let mapping = scope.mappings.values().next().unwrap();
+            assert_eq!(mapping.documentation.as_deref(), Some("The balance of each account."));
+            let struct_ = scope.structs.values().next().unwrap();
+            assert_eq!(struct_.documentation.as_deref(), None);
+            let function = scope.functions.values().next().unwrap();
+            assert_eq!(function.documentation.as_deref(), Some("Returns `a`.\n\nIgnores `b`."));
+        })
+    }
+
+    #[test]
+    fn async_functions() {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let source = "program test.aleo {
+    async transition mint(public amount: u64) -> Future {
+        return finalize_mint(amount);
+    }
+
+    async function finalize_mint(public amount: u64) {
+        increment(supply, 0u8, amount);
+    }
+}
+";
+            let program = parse(&handler, source, BytePos(0)).unwrap();
+            let scope = program.program_scopes.values().next().unwrap();
+
+            let call_types: Vec<_> = scope.functions.values().map(|function| function.call_type).collect();
+            assert_eq!(call_types, [CallType::AsyncTransition, CallType::AsyncFunction]);
+            assert_eq!(handler.err_count(), 0);
+
+            let handler = Handler::default();
+            assert!(parse(&handler, "program test.aleo { async struct A {} }", BytePos(0)).is_err());
+        })
+    }
+
+    #[test]
+    fn awaits() {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let source = "program test.aleo {
+    async function finalize_transfer(f: Future, public amount: u64) {
+        f.await();
+        increment(supply, 0u8, amount);
+    }
+}
+";
+            let program = parse(&handler, source, BytePos(0)).unwrap();
+            let scope = program.program_scopes.values().next().unwrap();
+            let function = scope.functions.values().next().unwrap();
+
+            match &function.block.statements[0] {
+                Statement::Expression(ExpressionStatement {
+                    expression: Expression::Unary(unary),
+                    ..
+                }) => assert_eq!(unary.op, UnaryOperation::Await),
+                statement => panic!("expected an await, found `{}`", statement),
+            }
+            assert_eq!(handler.err_count(), 0);
+        })
+    }
+
+    #[test]
+    fn match_statements() {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let source = "program test.aleo {
+    transition main(p: Point, a: u8) -> u8 {
+        match (p, a) {
+            (Point { x: 0u8, y }, _) => {
+                return y;
+            },
+            (_, b) => {
+                return b;
+            }
+        }
+    }
+}
+";
+            let program = parse(&handler, source, BytePos(0)).unwrap();
+            let scope = program.program_scopes.values().next().unwrap();
+            let function = scope.functions.values().next().unwrap();
+
+            let match_ = match &function.block.statements[0] {
+                Statement::Match(match_) => match_,
+                statement => panic!("expected a match statement, found `{statement}`"),
+            };
+            let patterns: Vec<_> = match_.arms.iter().map(|arm| arm.pattern.to_string()).collect();
+            assert_eq!(patterns, ["(Point { x: 0u8, y },_)", "(_,b)"]);
+            assert!(match_.arms[1].pattern.is_irrefutable());
+            assert_eq!(handler.err_count(), 0);
+
+            // A parenthesized pattern is not a tuple pattern.
+            let handler = Handler::default();
+            let source = "program test.aleo { function f(a: u8) { match a { (b) => {} } } }";
+            assert!(parse(&handler, source, BytePos(0)).is_err());
+        })
+    }
+
+    /// Resolves every import to the same file.
+    struct ImportFile(std::path::PathBuf);
+
+    impl ImportResolver for ImportFile {
+        fn resolve(&self, _name: &Identifier) -> Result<ImportSource> {
+            Ok(ImportSource {
+                path: self.0.clone(),
+                directory: self.0.parent().unwrap().to_path_buf(),
+            })
+        }
+
+        fn resolve_bytecode(&self, name: &Identifier) -> Result<ImportSource> {
+            self.resolve(name)
+        }
+    }
+
+    #[test]
+    fn node_ids_are_unique_across_imports() {
+        let path = std::env::temp_dir().join(format!("leo-node-ids-{}.leo", std::process::id()));
+        std::fs::write(
+            &path,
+            "program foo.aleo { function f(a: u8) -> u8 { let b: u8 = a + 1u8; return b; } }",
+        )
+        .unwrap();
+
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let source = "import foo.leo;
+program test.aleo { function g(a: u8) -> u8 { let b: u8 = a * 2u8; return b; } }";
+            let program = parse_with_imports(&handler, source, BytePos(0), &ImportFile(path.clone())).unwrap();
+
+            let mut ids = program.node_ids();
+            // Each program has 12 nodes: the identifiers of its name, network, function, input and variables,
+            // a literal, a block, a definition, a binary expression, and a return statement.
+            // The name of the import is a node of the importing program.
+            ids.sort_unstable();
+            assert_eq!(ids, (1..=25).collect::<Vec<NodeID>>());
+            assert_eq!(program.max_node_id(), 25);
+        });
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
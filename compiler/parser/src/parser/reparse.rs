@@ -0,0 +1,873 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Re-parses a program after an edit, reusing the items that the edit did not touch.
+
+use super::*;
+
+use leo_span::{span::Pos, symbol::with_session_globals};
+
+use std::ops::Range;
+
+/// Parses the program that results from replacing the bytes in `edit` of the source of `old` with `new_text`.
+///
+/// When the edit is within a single struct, record, mapping, or function, only that item is re-lexed and re-parsed,
+/// and the other items of `old` are reused with their spans moved to the new source.
+/// Otherwise, e.g. when the edit spans several items or renames one, the whole program is parsed again.
+/// Either way, the new source is registered in the source map under the name of the old one,
+/// and the result is the program that `parse` would return for it, except for the ids of its nodes.
+/// The nodes of the reused items keep their ids, and those of the re-parsed item are numbered after the nodes of `old`.
+///
+/// Panics if `old` was not parsed from a source in the source map,
+/// or if `edit` is not a range of character boundaries in that source.
+pub fn reparse(handler: &Handler, old: &Program, edit: Range<usize>, new_text: &str) -> Result<Program> {
+    let scope = old
+        .program_scopes
+        .values()
+        .next()
+        .expect("a parsed program has a program scope");
+    let old_file = with_session_globals(|s| s.source_map.find_source_file(scope.span.lo))
+        .expect("the program was parsed from a source in the source map");
+
+    let mut source = old_file.src.clone();
+    source.replace_range(edit.clone(), new_text);
+    let file = with_session_globals(|s| s.source_map.new_source(&source, old_file.name.clone()));
+    let version = declared_version(&file.src, file.start_pos)?.unwrap_or(LanguageVersion::CURRENT);
+
+    // An edit is confined to an item if the first and last bytes of the item are unchanged,
+    // so that the item still begins and ends on the same tokens, and lexing it on its own yields the same tokens as lexing the whole file.
+    let start = old_file.start_pos + BytePos::from_usize(edit.start);
+    let end = old_file.start_pos + BytePos::from_usize(edit.end);
+    let items: Vec<_> = scope_items(scope).collect();
+    let edited = items
+        .iter()
+        .position(|(_, extent)| extent.lo < start && end < extent.hi);
+    let (edited, extent) = match edited {
+        Some(index) => (index, items[index].1),
+        None => return parse_with_version(handler, &file.src, file.start_pos, version, &CurrentDirectory),
+    };
+
+    // Spans before the edit move with the start of the file, and spans after it also by the change in length.
+    let before = file.start_pos.to_usize() - old_file.start_pos.to_usize();
+    let after = before + new_text.len() - edit.len();
+    let shifter = |span: Span| SpanShifter {
+        offset: match span.lo < start {
+            true => before,
+            false => after,
+        },
+    };
+
+    let item_start = extent.lo.to_usize() - old_file.start_pos.to_usize();
+    let item_end = extent.hi.to_usize() - old_file.start_pos.to_usize() + new_text.len() - edit.len();
+    let item = match parse_item(
+        handler,
+        &file.src[item_start..item_end],
+        file.start_pos + BytePos::from_usize(item_start),
+        version,
+        NodeBuilder::for_program(old),
+    ) {
+        Ok(item) => item,
+        // The whole program is parsed to report the errors, as the edit may have moved the end of the item.
+        Err(_) => return parse_with_version(handler, &file.src, file.start_pos, version, &CurrentDirectory),
+    };
+    // Renaming an item, or changing its kind, may clash with other items, so the whole program is parsed.
+    let unchanged_name = match (&items[edited].0, &item) {
+        (Item::Struct(old), Item::Struct(new)) => old.identifier.name == new.identifier.name,
+        (Item::Mapping(old), Item::Mapping(new)) => old.identifier.name == new.identifier.name,
+        (Item::Function(old), Item::Function(new)) => old.identifier.name == new.identifier.name,
+        _ => false,
+    };
+    if !unchanged_name {
+        return parse_with_version(handler, &file.src, file.start_pos, version, &CurrentDirectory);
+    }
+
+    let mut structs = IndexMap::new();
+    let mut mappings = IndexMap::new();
+    let mut functions = IndexMap::new();
+    let mut item = Some(item);
+    for (index, (old, _)) in items.into_iter().enumerate() {
+        let new = match index == edited {
+            true => item.take().expect("the edited item is only replaced once"),
+            false => match old {
+                Item::Struct(old) => Item::Struct(shifter(old.span).reconstruct_struct(old.clone())),
+                Item::Mapping(old) => Item::Mapping(shifter(old.span).reconstruct_mapping(old.clone())),
+                Item::Function(old) => Item::Function(shifter(old.span).reconstruct_function(old.clone())),
+            },
+        };
+        match new {
+            Item::Struct(new) => {
+                structs.insert(new.identifier, new);
+            }
+            Item::Mapping(new) => {
+                mappings.insert(new.identifier, new);
+            }
+            Item::Function(new) => {
+                functions.insert(new.identifier, new);
+            }
+        }
+    }
+
+    // The program scope starts before the edit and ends after it.
+    let program_id = ProgramId {
+        name: shifter(scope.span).shift_identifier(scope.program_id.name),
+        network: shifter(scope.span).shift_identifier(scope.program_id.network),
+    };
+    let span = Span::new(
+        scope.span.lo + BytePos::from_usize(before),
+        scope.span.hi + BytePos::from_usize(after),
+    );
+
+    Ok(Program {
+        // Imports are parsed from their own files, which the edit did not change.
+        imports: old.imports.clone(),
+        program_scopes: [(
+            program_id,
+            ProgramScope {
+                program_id,
+                structs,
+                mappings,
+                functions,
+                span,
+            },
+        )]
+        .into_iter()
+        .collect(),
+        import_source: old.import_source.clone(),
+    })
+}
+
+/// An item of a program scope, or the item parsed in its place.
+enum Item<T, M, F> {
+    Struct(T),
+    Mapping(M),
+    Function(F),
+}
+
+/// Returns the items of `scope` in the order they are declared, with the spans of their full text.
+fn scope_items(scope: &ProgramScope) -> impl Iterator<Item = (Item<&Struct, &Mapping, &Function>, Span)> {
+    let structs = scope
+        .structs
+        .values()
+        .map(|struct_| (Item::Struct(struct_), struct_.span));
+    let mappings = scope
+        .mappings
+        .values()
+        .map(|mapping| (Item::Mapping(mapping), mapping.span));
+    let functions = scope.functions.values().map(|function| {
+        // The span of a function starts at its `function` or `transition` keyword and ends with its body.
+        let mut extent = function.span;
+        extent = function
+            .annotations
+            .iter()
+            .fold(extent, |extent, annotation| extent + annotation.span);
+        if let Some(finalize) = &function.finalize {
+            extent = extent + finalize.span;
+        }
+        (Item::Function(function), extent)
+    });
+    let mut items: Vec<_> = structs.chain(mappings).chain(functions).collect();
+    items.sort_by_key(|(_, extent)| extent.lo);
+    items.into_iter()
+}
+
+/// Parses `source` as a single struct, record, mapping, or function written for `version` of Leo,
+/// allocating the ids of its nodes with `node_builder`.
+fn parse_item(
+    handler: &Handler,
+    source: &str,
+    start_pos: BytePos,
+    version: LanguageVersion,
+    node_builder: NodeBuilder,
+) -> Result<Item<Struct, Mapping, Function>> {
+    let mut tokens = ParserContext::new(handler, crate::tokenize(source, start_pos)?);
+    tokens.version = version;
+    tokens.node_builder = node_builder;
+
+    let item = match &tokens.token.token {
+        Token::Struct | Token::Record => Item::Struct(tokens.parse_struct()?.1),
+        Token::Mapping => Item::Mapping(tokens.parse_mapping()?.1),
+        _ => Item::Function(tokens.parse_function()?.1),
+    };
+    tokens.expect(&Token::Eof)?;
+
+    Ok(item)
+}
+
+/// Moves every span in the items that it reconstructs forward by `offset` bytes.
+struct SpanShifter {
+    offset: usize,
+}
+
+impl SpanShifter {
+    fn shift(&self, span: Span) -> Span {
+        Span::new(
+            span.lo + BytePos::from_usize(self.offset),
+            span.hi + BytePos::from_usize(self.offset),
+        )
+    }
+
+    fn shift_identifier(&self, identifier: Identifier) -> Identifier {
+        Identifier {
+            name: identifier.name,
+            span: self.shift(identifier.span),
+            id: identifier.id,
+        }
+    }
+
+    fn shift_type(&self, type_: Type) -> Type {
+        match type_ {
+            Type::Identifier(identifier) => Type::Identifier(self.shift_identifier(identifier)),
+            Type::Mapping(mapping) => Type::Mapping(MappingType {
+                key: Box::new(self.shift_type(*mapping.key)),
+                value: Box::new(self.shift_type(*mapping.value)),
+            }),
+            Type::Tuple(tuple) => Type::Tuple(Tuple(tuple.0.into_iter().map(|type_| self.shift_type(type_)).collect())),
+            type_ => type_,
+        }
+    }
+
+    fn shift_external(&self, external: External) -> External {
+        External {
+            identifier: self.shift_identifier(external.identifier),
+            program_name: self.shift_identifier(external.program_name),
+            record: self.shift_identifier(external.record),
+            span: self.shift(external.span),
+        }
+    }
+
+    fn shift_input(&self, input: functions::Input) -> functions::Input {
+        match input {
+            functions::Input::Internal(input) => functions::Input::Internal(FunctionInput {
+                identifier: self.shift_identifier(input.identifier),
+                mode: input.mode,
+                type_: self.shift_type(input.type_),
+                span: self.shift(input.span),
+            }),
+            functions::Input::External(external) => functions::Input::External(self.shift_external(external)),
+        }
+    }
+
+    fn shift_output(&self, output: Output) -> Output {
+        match output {
+            Output::Internal(output) => Output::Internal(FunctionOutput {
+                mode: output.mode,
+                type_: self.shift_type(output.type_),
+                span: self.shift(output.span),
+            }),
+            Output::External(external) => Output::External(self.shift_external(external)),
+        }
+    }
+
+    fn shift_pattern(&mut self, pattern: Pattern) -> Pattern {
+        match pattern {
+            Pattern::Wildcard(span) => Pattern::Wildcard(self.shift(span)),
+            Pattern::Binding(identifier) => Pattern::Binding(self.shift_identifier(identifier)),
+            Pattern::Literal(literal) => match self.reconstruct_literal(literal).0 {
+                Expression::Literal(literal) => Pattern::Literal(literal),
+                _ => unreachable!("a literal is reconstructed as a literal"),
+            },
+            Pattern::Tuple(tuple) => Pattern::Tuple(TuplePattern {
+                elements: tuple
+                    .elements
+                    .into_iter()
+                    .map(|element| self.shift_pattern(element))
+                    .collect(),
+                span: self.shift(tuple.span),
+            }),
+            Pattern::Struct(struct_) => Pattern::Struct(StructPattern {
+                name: self.shift_identifier(struct_.name),
+                members: struct_
+                    .members
+                    .into_iter()
+                    .map(|member| StructMemberPattern {
+                        identifier: self.shift_identifier(member.identifier),
+                        pattern: member.pattern.map(|pattern| self.shift_pattern(pattern)),
+                    })
+                    .collect(),
+                span: self.shift(struct_.span),
+            }),
+        }
+    }
+}
+
+impl ExpressionReconstructor for SpanShifter {
+    type AdditionalOutput = ();
+
+    fn reconstruct_access(&mut self, input: AccessExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Access(match input {
+                AccessExpression::AssociatedConstant(constant) => {
+                    AccessExpression::AssociatedConstant(AssociatedConstant {
+                        ty: self.shift_type(constant.ty),
+                        name: self.shift_identifier(constant.name),
+                        span: self.shift(constant.span),
+                        id: constant.id,
+                    })
+                }
+                AccessExpression::AssociatedFunction(function) => {
+                    AccessExpression::AssociatedFunction(AssociatedFunction {
+                        ty: self.shift_type(function.ty),
+                        name: self.shift_identifier(function.name),
+                        args: function
+                            .args
+                            .into_iter()
+                            .map(|arg| self.reconstruct_expression(arg).0)
+                            .collect(),
+                        span: self.shift(function.span),
+                        id: function.id,
+                    })
+                }
+                AccessExpression::Member(member) => AccessExpression::Member(MemberAccess {
+                    inner: Box::new(self.reconstruct_expression(*member.inner).0),
+                    name: self.shift_identifier(member.name),
+                    span: self.shift(member.span),
+                    id: member.id,
+                }),
+                AccessExpression::Tuple(tuple) => AccessExpression::Tuple(TupleAccess {
+                    tuple: Box::new(self.reconstruct_expression(*tuple.tuple).0),
+                    index: tuple.index,
+                    span: self.shift(tuple.span),
+                    id: tuple.id,
+                }),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_binary(&mut self, input: BinaryExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Binary(BinaryExpression {
+                left: Box::new(self.reconstruct_expression(*input.left).0),
+                right: Box::new(self.reconstruct_expression(*input.right).0),
+                op: input.op,
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_call(&mut self, input: CallExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Call(CallExpression {
+                function: Box::new(self.reconstruct_expression(*input.function).0),
+                arguments: input
+                    .arguments
+                    .into_iter()
+                    .map(|arg| self.reconstruct_expression(arg).0)
+                    .collect(),
+                external: input
+                    .external
+                    .map(|external| Box::new(self.reconstruct_expression(*external).0)),
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Struct(StructExpression {
+                name: self.shift_identifier(input.name),
+                members: input
+                    .members
+                    .into_iter()
+                    .map(|member| StructVariableInitializer {
+                        identifier: self.shift_identifier(member.identifier),
+                        expression: member
+                            .expression
+                            .map(|expression| self.reconstruct_expression(expression).0),
+                    })
+                    .collect(),
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_err(&mut self, input: ErrExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Err(ErrExpression {
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_identifier(&mut self, input: Identifier) -> (Expression, Self::AdditionalOutput) {
+        (Expression::Identifier(self.shift_identifier(input)), Default::default())
+    }
+
+    fn reconstruct_literal(&mut self, input: Literal) -> (Expression, Self::AdditionalOutput) {
+        let shift_coordinate = |coordinate| match coordinate {
+            GroupCoordinate::Number(number, span) => GroupCoordinate::Number(number, self.shift(span)),
+            coordinate => coordinate,
+        };
+        (
+            Expression::Literal(match input {
+                Literal::Address(address, span, id) => Literal::Address(address, self.shift(span), id),
+                Literal::Boolean(boolean, span, id) => Literal::Boolean(boolean, self.shift(span), id),
+                Literal::Field(field, span, id) => Literal::Field(field, self.shift(span), id),
+                Literal::Group(group) => Literal::Group(Box::new(match *group {
+                    GroupLiteral::Single(number, span, id) => GroupLiteral::Single(number, self.shift(span), id),
+                    GroupLiteral::Tuple(tuple) => GroupLiteral::Tuple(GroupTuple {
+                        x: shift_coordinate(tuple.x),
+                        y: shift_coordinate(tuple.y),
+                        span: self.shift(tuple.span),
+                        id: tuple.id,
+                    }),
+                })),
+                Literal::Integer(type_, integer, span, id) => Literal::Integer(type_, integer, self.shift(span), id),
+                Literal::Scalar(scalar, span, id) => Literal::Scalar(scalar, self.shift(span), id),
+                Literal::Signature(signature, span, id) => Literal::Signature(signature, self.shift(span), id),
+                Literal::String(string, span, id) => Literal::String(string, self.shift(span), id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_ternary(&mut self, input: TernaryExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Ternary(TernaryExpression {
+                condition: Box::new(self.reconstruct_expression(*input.condition).0),
+                if_true: Box::new(self.reconstruct_expression(*input.if_true).0),
+                if_false: Box::new(self.reconstruct_expression(*input.if_false).0),
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_tuple(&mut self, input: TupleExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Tuple(TupleExpression {
+                elements: input
+                    .elements
+                    .into_iter()
+                    .map(|element| self.reconstruct_expression(element).0)
+                    .collect(),
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_unary(&mut self, input: UnaryExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Unary(UnaryExpression {
+                receiver: Box::new(self.reconstruct_expression(*input.receiver).0),
+                op: input.op,
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+}
+
+impl StatementReconstructor for SpanShifter {
+    fn reconstruct_assign(&mut self, input: AssignStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Assign(Box::new(AssignStatement {
+                place: self.reconstruct_expression(input.place).0,
+                value: self.reconstruct_expression(input.value).0,
+                span: self.shift(input.span),
+                id: input.id,
+            })),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
+        (
+            Block {
+                statements: input
+                    .statements
+                    .into_iter()
+                    .map(|statement| self.reconstruct_statement(statement).0)
+                    .collect(),
+                span: self.shift(input.span),
+                id: input.id,
+            },
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_conditional(&mut self, input: ConditionalStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Conditional(ConditionalStatement {
+                condition: self.reconstruct_expression(input.condition).0,
+                then: self.reconstruct_block(input.then).0,
+                otherwise: input
+                    .otherwise
+                    .map(|otherwise| Box::new(self.reconstruct_statement(*otherwise).0)),
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_match(&mut self, input: MatchStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Match(MatchStatement {
+                expression: self.reconstruct_expression(input.expression).0,
+                arms: input
+                    .arms
+                    .into_iter()
+                    .map(|arm| MatchArm {
+                        pattern: self.shift_pattern(arm.pattern),
+                        block: self.reconstruct_block(arm.block).0,
+                        span: self.shift(arm.span),
+                    })
+                    .collect(),
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_console(&mut self, input: ConsoleStatement) -> (Statement, Self::AdditionalOutput) {
+        let function = match input.function {
+            ConsoleFunction::Assert(expr, message) => ConsoleFunction::Assert(
+                self.reconstruct_expression(expr).0,
+                message.map(|message| self.reconstruct_assert_message(message)),
+            ),
+            ConsoleFunction::AssertEq(left, right, message) => ConsoleFunction::AssertEq(
+                self.reconstruct_expression(left).0,
+                self.reconstruct_expression(right).0,
+                message.map(|message| self.reconstruct_assert_message(message)),
+            ),
+            ConsoleFunction::AssertNeq(left, right, message) => ConsoleFunction::AssertNeq(
+                self.reconstruct_expression(left).0,
+                self.reconstruct_expression(right).0,
+                message.map(|message| self.reconstruct_assert_message(message)),
+            ),
+        };
+        (
+            Statement::Console(ConsoleStatement {
+                function,
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_assert_message(&mut self, input: AssertMessage) -> AssertMessage {
+        AssertMessage {
+            string: input.string,
+            parameters: input
+                .parameters
+                .into_iter()
+                .map(|parameter| self.reconstruct_expression(parameter).0)
+                .collect(),
+            span: self.shift(input.span),
+        }
+    }
+
+    fn reconstruct_decrement(&mut self, input: DecrementStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Decrement(DecrementStatement {
+                mapping: self.shift_identifier(input.mapping),
+                index: self.reconstruct_expression(input.index).0,
+                amount: self.reconstruct_expression(input.amount).0,
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_definition(&mut self, input: DefinitionStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Definition(DefinitionStatement {
+                declaration_type: input.declaration_type,
+                variable_name: self.shift_identifier(input.variable_name),
+                type_: self.shift_type(input.type_),
+                value: self.reconstruct_expression(input.value).0,
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_expression_statement(&mut self, input: ExpressionStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Expression(ExpressionStatement {
+                expression: self.reconstruct_expression(input.expression).0,
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_finalize(&mut self, input: FinalizeStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Finalize(FinalizeStatement {
+                arguments: input
+                    .arguments
+                    .into_iter()
+                    .map(|arg| self.reconstruct_expression(arg).0)
+                    .collect(),
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_increment(&mut self, input: IncrementStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Increment(IncrementStatement {
+                mapping: self.shift_identifier(input.mapping),
+                index: self.reconstruct_expression(input.index).0,
+                amount: self.reconstruct_expression(input.amount).0,
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_iteration(&mut self, input: IterationStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Iteration(Box::new(IterationStatement {
+                variable: self.shift_identifier(input.variable),
+                type_: self.shift_type(input.type_),
+                start: self.reconstruct_expression(input.start).0,
+                start_value: input.start_value,
+                stop: self.reconstruct_expression(input.stop).0,
+                stop_value: input.stop_value,
+                block: self.reconstruct_block(input.block).0,
+                inclusive: input.inclusive,
+                span: self.shift(input.span),
+                id: input.id,
+            })),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_return(&mut self, input: ReturnStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Return(ReturnStatement {
+                expression: self.reconstruct_expression(input.expression).0,
+                span: self.shift(input.span),
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+}
+
+impl ProgramReconstructor for SpanShifter {
+    fn reconstruct_function(&mut self, input: Function) -> Function {
+        Function {
+            documentation: input.documentation,
+            annotations: input
+                .annotations
+                .into_iter()
+                .map(|annotation| Annotation {
+                    identifier: self.shift_identifier(annotation.identifier),
+                    arguments: annotation
+                        .arguments
+                        .into_iter()
+                        .map(|(name, value)| (self.shift_identifier(name), value))
+                        .collect(),
+                    span: self.shift(annotation.span),
+                })
+                .collect(),
+            call_type: input.call_type,
+            identifier: self.shift_identifier(input.identifier),
+            input: input.input.into_iter().map(|input| self.shift_input(input)).collect(),
+            output: input
+                .output
+                .into_iter()
+                .map(|output| self.shift_output(output))
+                .collect(),
+            output_type: self.shift_type(input.output_type),
+            block: self.reconstruct_block(input.block).0,
+            finalize: input.finalize.map(|finalize| Finalize {
+                identifier: self.shift_identifier(finalize.identifier),
+                input: finalize
+                    .input
+                    .into_iter()
+                    .map(|input| self.shift_input(input))
+                    .collect(),
+                output: finalize
+                    .output
+                    .into_iter()
+                    .map(|output| self.shift_output(output))
+                    .collect(),
+                output_type: self.shift_type(finalize.output_type),
+                block: self.reconstruct_block(finalize.block).0,
+                span: self.shift(finalize.span),
+            }),
+            span: self.shift(input.span),
+        }
+    }
+
+    fn reconstruct_struct(&mut self, input: Struct) -> Struct {
+        Struct {
+            documentation: input.documentation,
+            identifier: self.shift_identifier(input.identifier),
+            members: input
+                .members
+                .into_iter()
+                .map(|member| Member {
+                    identifier: self.shift_identifier(member.identifier),
+                    type_: self.shift_type(member.type_),
+                })
+                .collect(),
+            is_record: input.is_record,
+            span: self.shift(input.span),
+        }
+    }
+
+    fn reconstruct_mapping(&mut self, input: Mapping) -> Mapping {
+        Mapping {
+            documentation: input.documentation,
+            identifier: self.shift_identifier(input.identifier),
+            key_type: self.shift_type(input.key_type),
+            value_type: self.shift_type(input.value_type),
+            span: self.shift(input.span),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use leo_span::{source_map::FileName, symbol::create_session_if_not_set_then};
+
+    const PROGRAM: &str = "program test.aleo {
+    struct Point {
+        x: u8,
+        y: u8,
+    }
+
+    mapping points: address => Point;
+
+    @program
+    function sum(p: Point) -> u8 {
+        return p.x + p.y;
+    }
+
+    transition main(a: u8) -> u8 {
+        let p: Point = Point { x: a, y: 1u8 };
+        return sum(p);
+    } finalize main(b: u8) {
+        increment(points, self.caller, b);
+    }
+}
+";
+
+    /// Re-parses `PROGRAM` after replacing the first occurrence of `old` with `new`,
+    /// and checks that the result is the program parsed from the new source.
+    fn check(old: &str, new: &str) -> Result<Program> {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let file = with_session_globals(|s| s.source_map.new_source(PROGRAM, FileName::Custom("test".into())));
+            let program = parse(&handler, &file.src, file.start_pos).unwrap();
+
+            let start = PROGRAM.find(old).unwrap();
+            let reparsed = reparse(&handler, &program, start..start + old.len(), new)?;
+
+            let scope = reparsed.program_scopes.values().next().unwrap();
+            let file = with_session_globals(|s| s.source_map.find_source_file(scope.span.lo)).unwrap();
+            assert_eq!(file.src, PROGRAM.replacen(old, new, 1));
+            assert_eq!(reparsed, parse(&handler, &file.src, file.start_pos).unwrap());
+            Ok(reparsed)
+        })
+    }
+
+    #[test]
+    fn edit_within_item() {
+        // Changes the length of a function, so that the items after it move.
+        let program = check("p.x + p.y", "p.x * p.y + 1u8").unwrap();
+        let scope = program.program_scopes.values().next().unwrap();
+        assert_eq!(scope.functions.len(), 2);
+        assert_eq!(scope.structs.len(), 1);
+        assert_eq!(scope.mappings.len(), 1);
+
+        check("y: u8,\n    }", "y: u8,\n        z: u8,\n    }").unwrap();
+        check("address => Point", "u8 => u8").unwrap();
+        check("increment(points", "decrement(points").unwrap();
+    }
+
+    #[test]
+    fn edit_outside_items() {
+        // Renaming an item, or editing several items at once, parses the whole program.
+        check("sum(p: Point)", "total(p: Point)").unwrap();
+        check("Point;", "Point;\n\n    mapping totals: address => u8;").unwrap();
+        check(
+            "}\n\n    mapping",
+            "}\n    struct Line {\n        a: Point,\n    }\n\n    mapping",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn diff_of_edits() {
+        // The programs are compared in the session that holds their sources.
+        create_session_if_not_set_then(|_| {
+            // The items after an edit move, but only the edited statement differs.
+            let old = check("p.x + p.y", "p.x + p.y").unwrap();
+            let new = check("p.x + p.y", "p.x * p.y + 1u8").unwrap();
+            let diff = leo_ast::diff(&old, &new);
+            assert_eq!(diff.items.len(), 1);
+            assert_eq!(diff.items[0].name, "test.aleo/sum");
+            assert_eq!(diff.items[0].change, Change::Changed);
+            assert_eq!(diff.items[0].edits.len(), 1);
+            assert_eq!(diff.items[0].edits[0].change, Change::Changed);
+            assert_eq!(
+                diff.items[0].edits[0].new.as_ref().unwrap().source,
+                "return p.x * p.y + 1u8;"
+            );
+            assert!(leo_ast::diff(&new, &new).is_empty());
+
+            // A renamed item is removed and added.
+            let new = check("sum(p: Point)", "total(p: Point)").unwrap();
+            let changes: Vec<_> = leo_ast::diff(&old, &new)
+                .items
+                .into_iter()
+                .map(|item| (item.name, item.change))
+                .collect();
+            assert_eq!(
+                changes,
+                [
+                    ("test.aleo/sum".to_string(), Change::Removed),
+                    ("test.aleo/total".to_string(), Change::Added),
+                ]
+            );
+        })
+    }
+
+    #[test]
+    fn syntax_error() {
+        // Removes the end of a function, which changes where the next item starts.
+        assert!(check("return sum(p);\n    }", "return sum(p);").is_err());
+    }
+}
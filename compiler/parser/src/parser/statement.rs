@@ -16,7 +16,7 @@
 
 use super::*;
 
-use leo_errors::{ParserError, Result};
+use leo_errors::{ParserError, Result, Suggestion};
 use leo_span::sym;
 
 const ASSIGN_TOKENS: &[Token] = &[
@@ -48,6 +48,7 @@ impl ParserContext<'_> {
             Token::Decrement => Ok(Statement::Decrement(self.parse_decrement_statement()?)),
             Token::If => Ok(Statement::Conditional(self.parse_conditional_statement()?)),
             Token::For => Ok(Statement::Iteration(Box::new(self.parse_loop_statement()?))),
+            Token::Match => Ok(Statement::Match(self.parse_match_statement()?)),
             Token::Console => Ok(Statement::Console(self.parse_console_statement()?)),
             Token::Let | Token::Const => Ok(Statement::Definition(self.parse_definition_statement()?)),
             Token::LeftCurly => Ok(Statement::Block(self.parse_block()?)),
@@ -94,23 +95,50 @@ impl ParserContext<'_> {
                     right: Box::new(value),
                     op,
                     span,
+                    id: self.node_builder.next_id(),
                 }),
             };
 
-            Ok(Statement::Assign(Box::new(AssignStatement { span, place, value })))
+            Ok(Statement::Assign(Box::new(AssignStatement {
+                span,
+                place,
+                value,
+                id: self.node_builder.next_id(),
+            })))
+        } else if matches!(
+            place,
+            Expression::Access(AccessExpression::AssociatedFunction(_))
+                | Expression::Call(CallExpression { external: Some(_), .. })
+                | Expression::Unary(UnaryExpression {
+                    op: UnaryOperation::Await,
+                    ..
+                })
+        ) {
+            // Calls to associated functions, e.g. `Mapping::set(balances, addr, 1u64);`, calls to transitions of other
+            // programs, e.g. `token.leo/burn(1u64);`, and awaits of futures, e.g. `f.await();`, may be evaluated for their effect.
+            self.expect(&Token::Semicolon)?;
+            Ok(Statement::Expression(ExpressionStatement {
+                span: place.span(),
+                expression: place,
+                id: self.node_builder.next_id(),
+            }))
         } else {
             // Error on `expr;` but recover as an empty block `{}`.
             self.expect(&Token::Semicolon)?;
             let span = place.span() + self.prev_token.span;
             self.emit_err(ParserError::expr_stmts_disallowed(span));
-            Ok(Statement::dummy(span))
+            Ok(Statement::dummy(span, self.node_builder.next_id()))
         }
     }
 
     /// Returns a [`Block`] AST node if the next tokens represent a block of statements.
     pub(super) fn parse_block(&mut self) -> Result<Block> {
         self.parse_list(Delimiter::Brace, None, |p| p.parse_statement().map(Some))
-            .map(|(statements, _, span)| Block { statements, span })
+            .map(|(statements, _, span)| Block {
+                statements,
+                span,
+                id: self.node_builder.next_id(),
+            })
     }
 
     /// Returns a [`ReturnStatement`] AST node if the next tokens represent a return statement.
@@ -119,22 +147,32 @@ impl ParserContext<'_> {
         let expression = self.parse_expression()?;
         self.expect(&Token::Semicolon)?;
         let span = start + expression.span();
-        Ok(ReturnStatement { span, expression })
+        Ok(ReturnStatement {
+            span,
+            expression,
+            id: self.node_builder.next_id(),
+        })
     }
 
     /// Returns a [`FinalizeStatement`] AST node if the next tokens represent a finalize statement.
     fn parse_finalize_statement(&mut self) -> Result<FinalizeStatement> {
         self.expect(&Token::Async)?;
         let start = self.expect(&Token::Finalize)?;
+        self.check_feature(Feature::Mappings, start);
         let (arguments, _, span) = self.parse_paren_comma_list(|p| p.parse_expression().map(Some))?;
         self.expect(&Token::Semicolon)?;
         let span = start + span;
-        Ok(FinalizeStatement { span, arguments })
+        Ok(FinalizeStatement {
+            span,
+            arguments,
+            id: self.node_builder.next_id(),
+        })
     }
 
     /// Returns a [`DecrementStatement`] AST node if the next tokens represent a decrement statement.
     fn parse_decrement_statement(&mut self) -> Result<DecrementStatement> {
         let start = self.expect(&Token::Decrement)?;
+        self.check_feature(Feature::Mappings, start);
         self.expect(&Token::LeftParen)?;
         let mapping = self.expect_identifier()?;
         self.expect(&Token::Comma)?;
@@ -150,12 +188,14 @@ impl ParserContext<'_> {
             index,
             amount,
             span,
+            id: self.node_builder.next_id(),
         })
     }
 
     /// Returns an [`IncrementStatement`] AST node if the next tokens represent an increment statement.
     fn parse_increment_statement(&mut self) -> Result<IncrementStatement> {
         let start = self.expect(&Token::Increment)?;
+        self.check_feature(Feature::Mappings, start);
         self.expect(&Token::LeftParen)?;
         let mapping = self.expect_identifier()?;
         self.expect(&Token::Comma)?;
@@ -171,6 +211,7 @@ impl ParserContext<'_> {
             index,
             amount,
             span,
+            id: self.node_builder.next_id(),
         })
     }
 
@@ -196,9 +237,82 @@ impl ParserContext<'_> {
             condition: expr,
             then: body,
             otherwise: next,
+            id: self.node_builder.next_id(),
+        })
+    }
+
+    /// Returns a [`MatchStatement`] AST node if the next tokens represent a match statement.
+    fn parse_match_statement(&mut self) -> Result<MatchStatement> {
+        let start = self.expect(&Token::Match)?;
+        self.check_feature(Feature::Match, start);
+        self.disallow_struct_construction = true;
+        let expression = self.parse_conditional_expression()?;
+        self.disallow_struct_construction = false;
+
+        // Parse the arms, which may be followed by a comma.
+        self.expect(&Token::LeftCurly)?;
+        let mut arms = Vec::new();
+        while !self.check(&Token::RightCurly) {
+            let pattern = self.parse_pattern()?;
+            self.expect(&Token::BigArrow)?;
+            let block = self.parse_block()?;
+            arms.push(MatchArm {
+                span: pattern.span() + block.span,
+                pattern,
+                block,
+            });
+            self.eat(&Token::Comma);
+        }
+        let end = self.expect(&Token::RightCurly)?;
+
+        Ok(MatchStatement {
+            expression,
+            arms,
+            span: start + end,
+            id: self.node_builder.next_id(),
         })
     }
 
+    /// Returns a [`Pattern`] AST node if the next tokens represent the pattern of a match arm.
+    fn parse_pattern(&mut self) -> Result<Pattern> {
+        match &self.token.token {
+            Token::Underscore => Ok(Pattern::Wildcard(self.expect(&Token::Underscore)?)),
+            Token::LeftParen => {
+                let (elements, trailing, span) = self.parse_paren_comma_list(|p| p.parse_pattern().map(Some))?;
+                if elements.len() < 2 && !(elements.len() == 1 && trailing) {
+                    return Err(ParserError::tuple_pattern_without_comma(span).into());
+                }
+                Ok(Pattern::Tuple(TuplePattern { elements, span }))
+            }
+            Token::Identifier(_) => {
+                let name = self.expect_identifier()?;
+                if !self.check(&Token::LeftCurly) {
+                    return Ok(Pattern::Binding(name));
+                }
+                let (members, _, end) = self.parse_list(Delimiter::Brace, Some(Token::Comma), |p| {
+                    let identifier = p.expect_identifier()?;
+                    let pattern = match p.eat(&Token::Colon) {
+                        true => Some(p.parse_pattern()?),
+                        false => None,
+                    };
+                    Ok(Some(StructMemberPattern { identifier, pattern }))
+                })?;
+                Ok(Pattern::Struct(StructPattern {
+                    span: name.span + end,
+                    name,
+                    members,
+                }))
+            }
+            Token::Integer(_) | Token::True | Token::False | Token::AddressLit(_) | Token::StaticString(_) => {
+                match self.parse_unary_expression()? {
+                    Expression::Literal(literal) => Ok(Pattern::Literal(literal)),
+                    expression => Err(ParserError::unexpected(&expression, "a literal", expression.span()).into()),
+                }
+            }
+            _ => self.unexpected("a pattern"),
+        }
+    }
+
     /// Returns an [`IterationStatement`] AST node if the next tokens represent an iteration statement.
     fn parse_loop_statement(&mut self) -> Result<IterationStatement> {
         let start_span = self.expect(&Token::For)?;
@@ -226,6 +340,7 @@ impl ParserContext<'_> {
             stop_value: Default::default(),
             inclusive: false,
             block,
+            id: self.node_builder.next_id(),
         })
     }
 
@@ -238,24 +353,30 @@ impl ParserContext<'_> {
             sym::assert => {
                 self.expect(&Token::LeftParen)?;
                 let expr = self.parse_expression()?;
+                let message = self.parse_assert_message()?;
                 self.expect(&Token::RightParen)?;
-                (keyword + expr.span(), ConsoleFunction::Assert(expr))
+                let end = message.as_ref().map_or(expr.span(), |message| message.span);
+                (keyword + end, ConsoleFunction::Assert(expr, message))
             }
             sym::assert_eq => {
                 self.expect(&Token::LeftParen)?;
                 let left = self.parse_expression()?;
                 self.expect(&Token::Comma)?;
                 let right = self.parse_expression()?;
+                let message = self.parse_assert_message()?;
                 self.expect(&Token::RightParen)?;
-                (left.span() + right.span(), ConsoleFunction::AssertEq(left, right))
+                let end = message.as_ref().map_or(right.span(), |message| message.span);
+                (left.span() + end, ConsoleFunction::AssertEq(left, right, message))
             }
             sym::assert_neq => {
                 self.expect(&Token::LeftParen)?;
                 let left = self.parse_expression()?;
                 self.expect(&Token::Comma)?;
                 let right = self.parse_expression()?;
+                let message = self.parse_assert_message()?;
                 self.expect(&Token::RightParen)?;
-                (left.span() + right.span(), ConsoleFunction::AssertNeq(left, right))
+                let end = message.as_ref().map_or(right.span(), |message| message.span);
+                (left.span() + end, ConsoleFunction::AssertNeq(left, right, message))
             }
             symbol => {
                 // Not sure what it is, assume it's `log`.
@@ -266,9 +387,13 @@ impl ParserContext<'_> {
                 ));
                 (
                     Default::default(),
-                    ConsoleFunction::Assert(Expression::Err(ErrExpression {
-                        span: Default::default(),
-                    })),
+                    ConsoleFunction::Assert(
+                        Expression::Err(ErrExpression {
+                            span: Default::default(),
+                            id: self.node_builder.next_id(),
+                        }),
+                        None,
+                    ),
                 )
             }
         };
@@ -277,9 +402,38 @@ impl ParserContext<'_> {
         Ok(ConsoleStatement {
             span: keyword + span,
             function,
+            id: self.node_builder.next_id(),
         })
     }
 
+    /// Returns an optional [`AssertMessage`] AST node if the next tokens represent
+    /// the `, "format string", args...` suffix of an assertion.
+    fn parse_assert_message(&mut self) -> Result<Option<AssertMessage>> {
+        if !self.eat(&Token::Comma) {
+            return Ok(None);
+        }
+
+        let start = self.token.span;
+        let string = match &self.token.token {
+            Token::StaticString(string) => string.clone(),
+            _ => return Err(ParserError::unexpected_str(&self.token.token, "string", start).into()),
+        };
+        self.bump();
+
+        let mut parameters = Vec::new();
+        while self.eat(&Token::Comma) {
+            parameters.push(self.parse_expression()?);
+        }
+        let span = parameters.last().map_or(start, |parameter| start + parameter.span());
+        self.check_feature(Feature::AssertMessages, span);
+
+        Ok(Some(AssertMessage {
+            string,
+            parameters,
+            span,
+        }))
+    }
+
     /// Returns a [`DefinitionStatement`] AST node if the next tokens represent a definition statement.
     pub(super) fn parse_definition_statement(&mut self) -> Result<DefinitionStatement> {
         self.expect_any(&[Token::Let, Token::Const])?;
@@ -290,8 +444,16 @@ impl ParserContext<'_> {
             _ => unreachable!("parse_definition_statement_ shouldn't produce this"),
         };
 
+        // A definition without a type, e.g. `let x = 1u8;`, is an error that can be fixed when the value is a literal.
+        if matches!(self.token.token, Token::Identifier(_)) && self.look_ahead(1, |next| next.token == Token::Assign) {
+            return Err(self.missing_type_annotation().into());
+        }
+
         // Parse variable name and type.
-        let (variable_name, type_) = self.parse_typed_ident()?;
+        // The type may be a tuple, e.g. `(u64, Future)` for the result of a call to a transition of another program.
+        let variable_name = self.expect_identifier()?;
+        self.expect(&Token::Colon)?;
+        let type_ = self.parse_definition_type()?;
 
         self.expect(&Token::Assign)?;
         let value = self.parse_expression()?;
@@ -303,6 +465,47 @@ impl ParserContext<'_> {
             variable_name,
             type_,
             value,
+            id: self.node_builder.next_id(),
         })
     }
+
+    /// Returns the type of a definition, which is a tuple type if the next tokens are types in parentheses.
+    fn parse_definition_type(&mut self) -> Result<Type> {
+        if !self.check(&Token::LeftParen) {
+            return Ok(self.parse_type()?.0);
+        }
+
+        let (types, _, span) = self.parse_paren_comma_list(|p| p.parse_type().map(|(type_, _)| Some(type_)))?;
+        if types.len() < 2 {
+            return Err(ParserError::tuple_type_too_short(span).into());
+        }
+        Ok(Type::Tuple(Tuple(types)))
+    }
+
+    /// Returns the error for a definition whose name, the current token, is followed by `=` instead of its type.
+    /// If the value is a literal, the type of the literal is suggested.
+    fn missing_type_annotation(&self) -> ParserError {
+        let (assign, name_span) = self.look_ahead(1, |assign| (assign.clone(), self.token.span));
+        let err = ParserError::unexpected(&assign.token, Token::Colon, assign.span);
+
+        let tokens: Vec<Token> = (2..=4).map(|dist| self.look_ahead(dist, |t| t.token.clone())).collect();
+        let type_ = match tokens.as_slice() {
+            [Token::Integer(_), suffix, Token::Semicolon]
+                if Self::token_to_int_type(suffix).is_some()
+                    || matches!(suffix, Token::Field | Token::Group | Token::Scalar) =>
+            {
+                suffix.to_string()
+            }
+            [Token::True | Token::False, Token::Semicolon, ..] => Token::Bool.to_string(),
+            [Token::AddressLit(_), Token::Semicolon, ..] => Token::Address.to_string(),
+            [Token::SignatureLit(_), Token::Semicolon, ..] => Token::Signature.to_string(),
+            _ => return err,
+        };
+
+        err.with_suggestion(Suggestion::new(
+            Span::new(name_span.hi, name_span.hi),
+            format!(": {type_}"),
+            format!("Add the type `{type_}`"),
+        ))
+    }
 }
@@ -17,6 +17,7 @@
 use super::*;
 
 use leo_errors::Result;
+use leo_span::sym;
 
 pub(super) const TYPE_TOKENS: &[Token] = &[
     Token::Address,
@@ -24,6 +25,7 @@ pub(super) const TYPE_TOKENS: &[Token] = &[
     Token::Field,
     Token::Group,
     Token::Scalar,
+    Token::Signature,
     Token::String,
     Token::I8,
     Token::I16,
@@ -39,7 +41,7 @@ pub(super) const TYPE_TOKENS: &[Token] = &[
 
 impl ParserContext<'_> {
     /// Returns a [`IntegerType`] AST node if the given token is a supported integer type, or [`None`].
-    pub(super) fn token_to_int_type(token: &Token) -> Option<IntegerType> {
+    pub(crate) fn token_to_int_type(token: &Token) -> Option<IntegerType> {
         Some(match token {
             Token::I8 => IntegerType::I8,
             Token::I16 => IntegerType::I16,
@@ -66,6 +68,10 @@ impl ParserContext<'_> {
                 Token::Field => Type::Field,
                 Token::Group => Type::Group,
                 Token::Scalar => Type::Scalar,
+                Token::Signature => {
+                    self.check_feature(Feature::Signatures, span);
+                    Type::Signature
+                }
                 Token::String => Type::String,
                 x => Type::Integer(Self::token_to_int_type(x).expect("invalid int type")),
             },
@@ -77,6 +83,9 @@ impl ParserContext<'_> {
     /// Also returns the span of the parsed token.
     pub fn parse_type(&mut self) -> Result<(Type, Span)> {
         if let Some(ident) = self.eat_identifier() {
+            if ident.name == sym::Future {
+                self.check_feature(Feature::AsyncFunctions, ident.span);
+            }
             Ok((Type::Identifier(ident), ident.span))
         } else {
             self.parse_primitive_type()
@@ -0,0 +1,359 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Stubs of deployed Aleo programs, which are imported with `import foo.aleo;`.
+//! A stub is a Leo program with the signatures of the Aleo program, generated from its bytecode,
+//! so that the type checker can check calls into the program, e.g. `foo.aleo/bar(1u8)`.
+
+use leo_ast::Identifier;
+use leo_errors::{ParserError, Result};
+
+use std::fmt::Write as _;
+
+/// A struct or record declared by an Aleo program.
+struct Composite {
+    name: String,
+    is_record: bool,
+    members: Vec<(String, String)>,
+}
+
+/// A mapping declared by an Aleo program.
+struct Mapping {
+    name: String,
+    key: String,
+    value: String,
+}
+
+/// The inputs and outputs of a function of an Aleo program, with their types and visibilities.
+struct Function {
+    name: String,
+    inputs: Vec<(String, String)>,
+    outputs: Vec<(String, String)>,
+}
+
+/// The block of an Aleo program that a line belongs to.
+enum Block {
+    None,
+    Composite,
+    Mapping,
+    Function,
+    /// A closure or finalize block, whose lines are not part of the signatures.
+    Other,
+}
+
+/// Returns the source of a Leo program with the structs, records, mappings and transitions of `bytecode`,
+/// the Aleo program imported as `name`. Errors are reported at `name`.
+/// The transitions return placeholder values, since only their signatures are used.
+/// Closures and finalize blocks are left out, since other programs cannot call them,
+/// and so are functions that take or return a record of another program, which Leo cannot declare.
+pub fn aleo_stub(name: &Identifier, bytecode: &str) -> Result<String> {
+    let mut program_id = None;
+    let mut composites: Vec<Composite> = Vec::new();
+    let mut mappings: Vec<Mapping> = Vec::new();
+    let mut functions: Vec<Function> = Vec::new();
+    let mut block = Block::None;
+
+    for (index, line) in bytecode.lines().enumerate() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if line.is_empty() || line.starts_with("import ") {
+            continue;
+        }
+        let invalid = || ParserError::invalid_aleo_import(name, index + 1, line, name.span);
+
+        if let Some(id) = line.strip_prefix("program ") {
+            program_id = Some(id.trim_end_matches(';').trim().to_string());
+            continue;
+        }
+        if let Some(header) = line.strip_suffix(':') {
+            let (keyword, item) = header.split_once(' ').ok_or_else(invalid)?;
+            let item = item.trim().to_string();
+            block = match keyword {
+                "struct" | "interface" | "record" => {
+                    let is_record = keyword == "record";
+                    composites.push(Composite {
+                        name: item,
+                        is_record,
+                        members: Vec::new(),
+                    });
+                    Block::Composite
+                }
+                "mapping" => {
+                    mappings.push(Mapping {
+                        name: item,
+                        key: String::new(),
+                        value: String::new(),
+                    });
+                    Block::Mapping
+                }
+                "function" => {
+                    functions.push(Function {
+                        name: item,
+                        inputs: Vec::new(),
+                        outputs: Vec::new(),
+                    });
+                    Block::Function
+                }
+                "closure" | "finalize" => Block::Other,
+                _ => return Err(invalid().into()),
+            };
+            continue;
+        }
+
+        let words = line.trim_end_matches(';').split_whitespace().collect::<Vec<_>>();
+        match (&block, words.as_slice()) {
+            (Block::Composite, [member, "as", type_]) => {
+                // Note that this unwrap is safe since a composite block is only entered after pushing a composite.
+                let (type_, _) = split_visibility(type_);
+                composites.last_mut().unwrap().members.push((member.to_string(), type_));
+            }
+            (Block::Mapping, ["key", _, "as", type_]) => mappings.last_mut().unwrap().key = split_visibility(type_).0,
+            (Block::Mapping, ["value", _, "as", type_]) => {
+                mappings.last_mut().unwrap().value = split_visibility(type_).0
+            }
+            (Block::Function, ["input", register, "as", type_]) => {
+                let (type_, visibility) = split_visibility(type_);
+                let input = format!("{}{}: {}", visibility, register, type_);
+                functions.last_mut().unwrap().inputs.push((input, type_));
+            }
+            (Block::Function, ["output", _, "as", type_]) => {
+                let (type_, visibility) = split_visibility(type_);
+                functions
+                    .last_mut()
+                    .unwrap()
+                    .outputs
+                    .push((format!("{}{}", visibility, type_), type_));
+            }
+            // The instructions of functions, closures and finalize blocks are not part of the signatures.
+            (Block::Function | Block::Other, _) => {}
+            _ => return Err(invalid().into()),
+        }
+    }
+
+    let program_id =
+        program_id.ok_or_else(|| ParserError::invalid_aleo_import(name, 0, "no `program` declaration", name.span))?;
+
+    let mut leo = String::new();
+    writeln!(leo, "// The stub of `{}.aleo`, generated from its bytecode.", name).unwrap();
+    writeln!(leo, "program {} {{", program_id).unwrap();
+    for composite in composites.iter() {
+        let keyword = if composite.is_record { "record" } else { "struct" };
+        writeln!(leo, "    {} {} {{", keyword, composite.name).unwrap();
+        for (member, type_) in composite.members.iter() {
+            writeln!(leo, "        {}: {},", member, type_).unwrap();
+        }
+        writeln!(leo, "    }}\n").unwrap();
+    }
+    for mapping in mappings.iter() {
+        writeln!(
+            leo,
+            "    mapping {}: {} => {};\n",
+            mapping.name, mapping.key, mapping.value
+        )
+        .unwrap();
+    }
+    for function in functions.iter() {
+        let types = function.inputs.iter().chain(function.outputs.iter());
+        if types.clone().any(|(_, type_)| type_.contains('/')) {
+            writeln!(
+                leo,
+                "    // `{}` is left out, since it takes or returns a record of another program.\n",
+                function.name
+            )
+            .unwrap();
+            continue;
+        }
+
+        let inputs = function
+            .inputs
+            .iter()
+            .map(|(input, _)| input.as_str())
+            .collect::<Vec<_>>();
+        let outputs = function
+            .outputs
+            .iter()
+            .map(|(output, _)| output.as_str())
+            .collect::<Vec<_>>();
+        let (outputs, values) = match outputs.len() {
+            0 => (String::new(), String::new()),
+            1 => (
+                format!(" -> {}", outputs[0]),
+                placeholder(&function.outputs[0].1, &composites),
+            ),
+            _ => (
+                format!(" -> ({})", outputs.join(", ")),
+                format!(
+                    "({})",
+                    function
+                        .outputs
+                        .iter()
+                        .map(|(_, type_)| placeholder(type_, &composites))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ),
+        };
+        // The deployed program checks the owners of the records it consumes, so its stub does not.
+        if function.inputs.iter().any(|(_, type_)| {
+            composites
+                .iter()
+                .any(|composite| composite.is_record && &composite.name == type_)
+        }) {
+            writeln!(leo, "    @no_owner_check").unwrap();
+        }
+        write!(
+            leo,
+            "    transition {}({}){} ",
+            function.name,
+            inputs.join(", "),
+            outputs
+        )
+        .unwrap();
+        match values.is_empty() {
+            true => writeln!(leo, "{{}}\n").unwrap(),
+            false => writeln!(leo, "{{\n        return {};\n    }}\n", values).unwrap(),
+        }
+    }
+    // Remove the blank line after the last item.
+    if leo.ends_with("\n\n") {
+        leo.pop();
+    }
+    leo.push_str("}\n");
+
+    Ok(leo)
+}
+
+/// Splits an Aleo type with a visibility, e.g. `u64.public`, into the Leo type and the mode of an input, e.g. `public `.
+/// Records, e.g. `token.record`, are named without their suffix, and `boolean` is written `bool`.
+fn split_visibility(type_: &str) -> (String, &'static str) {
+    let (type_, visibility) = match type_.rsplit_once('.') {
+        Some((type_, "public")) => (type_, "public "),
+        Some((type_, "constant")) => (type_, "constant "),
+        Some((type_, "private" | "record")) => (type_, ""),
+        _ => (type_, ""),
+    };
+    let type_ = match type_ {
+        "boolean" => "bool",
+        type_ => type_,
+    };
+    (type_.to_string(), visibility)
+}
+
+/// Returns a value of a Leo type, for the placeholder bodies of transitions.
+fn placeholder(type_: &str, composites: &[Composite]) -> String {
+    match type_ {
+        "address" => "self.caller".to_string(),
+        "bool" => "false".to_string(),
+        "field" | "group" | "scalar" | "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64"
+        | "i128" => {
+            format!("0{}", type_)
+        }
+        name => match composites.iter().find(|composite| composite.name == name) {
+            Some(composite) => {
+                let members = composite
+                    .members
+                    .iter()
+                    .map(|(member, type_)| format!("{}: {}", member, placeholder(type_, composites)))
+                    .collect::<Vec<_>>();
+                format!("{} {{ {} }}", name, members.join(", "))
+            }
+            None => name.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use leo_span::{symbol::create_session_if_not_set_then, Symbol};
+
+    /// Returns the stub of `bytecode`, imported as `name`.
+    fn stub(name: &str, bytecode: &str) -> Result<String> {
+        create_session_if_not_set_then(|_| {
+            aleo_stub(&Identifier::new(Symbol::intern(name), Default::default()), bytecode)
+        })
+    }
+
+    #[test]
+    fn test_aleo_stub() {
+        let bytecode = "program token.aleo;
+
+record token:
+    owner as address.private;
+    gates as u64.private;
+    amount as u64.private;
+
+mapping account:
+    key left as address.public;
+    value right as u64.public;
+
+function mint_public:
+    input r0 as address.public;
+    input r1 as u64.public;
+    finalize r0 r1;
+
+finalize mint_public:
+    input r0 as address.public;
+    input r1 as u64.public;
+    increment account[r0] by r1;
+
+function split:
+    input r0 as token.record;
+    input r1 as boolean.private;
+    cast r0.owner r0.gates r0.amount into r2 as token.record;
+    output r2 as token.record;
+    output r1 as boolean.public;
+
+closure helper:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+";
+        let expected = "// The stub of `token.aleo`, generated from its bytecode.
+program token.aleo {
+    record token {
+        owner: address,
+        gates: u64,
+        amount: u64,
+    }
+
+    mapping account: address => u64;
+
+    transition mint_public(public r0: address, public r1: u64) {}
+
+    @no_owner_check
+    transition split(r0: token, r1: bool) -> (token, public bool) {
+        return (token { owner: self.caller, gates: 0u64, amount: 0u64 }, false);
+    }
+}
+";
+        assert_eq!(stub("token", bytecode).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_aleo_stub_with_external_record() {
+        let bytecode = "import token.aleo;
+program swap.aleo;
+
+function swap:
+    input r0 as token.aleo/token.record;
+    output r0.amount as u64.private;
+";
+        let stub_source = stub("swap", bytecode).unwrap();
+        assert!(stub_source.contains("`swap` is left out"));
+        assert!(stub("swap", "program swap.aleo;\nfoo bar:\n").is_err());
+    }
+}
@@ -90,7 +90,10 @@ fn all_are_comments(tokens: &[SpannedToken]) -> bool {
 }
 
 fn yaml_or_fail<T: Serialize>(value: T) -> Value {
-    serde_yaml::to_value(value).expect("serialization failed")
+    // Node ids depend on parse order, so they are not part of the expectations.
+    let mut json = serde_json::to_value(value).expect("serialization failed");
+    remove_key_from_json(&mut json, "id");
+    serde_json::from_value(json).expect("serialization failed")
 }
 
 struct ParseExpressionNamespace;
@@ -122,7 +125,7 @@ impl Namespace for ParseStatementNamespace {
         create_session_if_not_set_then(|s| {
             let tokenizer = tokenize(test, s)?;
             if all_are_comments(&tokenizer) {
-                return Ok(yaml_or_fail(Statement::dummy(Span::default())));
+                return Ok(yaml_or_fail(Statement::dummy(Span::default(), 0)));
             }
             with_handler(tokenizer, |p| p.parse_statement()).map(yaml_or_fail)
         })
@@ -199,6 +202,7 @@ impl Namespace for SerializeNamespace {
 
             let mut json = serde_json::to_value(parsed).expect("failed to convert to json value");
             remove_key_from_json(&mut json, "span");
+            remove_key_from_json(&mut json, "id");
             json = normalize_json_value(json);
 
             Ok(serde_json::from_value::<serde_yaml::Value>(json).expect("failed serialization"))
@@ -24,9 +24,10 @@ use std::{
     iter::{from_fn, Peekable},
 };
 
-/// Eat an identifier, that is, a string matching '[a-zA-Z][a-zA-Z\d_]*', if any.
+/// Eat an identifier, that is, a string matching '[a-zA-Z_][a-zA-Z\d_]*', if any.
+/// A lone `_` is lexed as [`Token::Underscore`] before this is reached.
 fn eat_identifier(input: &mut Peekable<impl Iterator<Item = char>>) -> Option<String> {
-    input.peek().filter(|c| c.is_ascii_alphabetic())?;
+    input.peek().filter(|c| c.is_ascii_alphabetic() || **c == '_')?;
     Some(from_fn(|| input.next_if(|c| c.is_ascii_alphanumeric() || c == &'_')).collect())
 }
 
@@ -281,7 +282,10 @@ impl Token {
             }
             '(' => return match_one(&mut input, Token::LeftParen),
             ')' => return match_one(&mut input, Token::RightParen),
-            '_' => return match_one(&mut input, Token::Underscore),
+            // An underscore followed by an identifier character begins an identifier, e.g. `_unused`.
+            '_' if !input_str[1..].starts_with(|c: char| c.is_ascii_alphanumeric()) => {
+                return match_one(&mut input, Token::Underscore)
+            }
             '*' => {
                 return match_four(
                     &mut input,
@@ -393,6 +397,7 @@ impl Token {
                 // todo: match on symbols instead of hard-coded &str's
                 match &*identifier {
                     x if x.starts_with("aleo1") => Token::AddressLit(identifier),
+                    x if x.starts_with("sign1") => Token::SignatureLit(identifier),
                     "address" => Token::Address,
                     "async" => Token::Async,
                     "bool" => Token::Bool,
@@ -420,12 +425,14 @@ impl Token {
                     "let" => Token::Let,
                     "leo" => Token::Leo,
                     "mapping" => Token::Mapping,
+                    "match" => Token::Match,
                     "program" => Token::Program,
                     "public" => Token::Public,
                     "record" => Token::Record,
                     "return" => Token::Return,
                     "scalar" => Token::Scalar,
                     "self" => Token::SelfLower,
+                    "signature" => Token::Signature,
                     "string" => Token::String,
                     "struct" => Token::Struct,
                     "transition" => Token::Transition,
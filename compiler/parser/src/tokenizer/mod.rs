@@ -27,6 +27,9 @@ pub(crate) use self::token::*;
 pub(crate) mod lexer;
 pub(crate) use self::lexer::*;
 
+pub(crate) mod trivia;
+pub use self::trivia::*;
+
 use leo_errors::Result;
 use leo_span::span::{BytePos, Pos, Span};
 use std::iter;
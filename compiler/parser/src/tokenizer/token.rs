@@ -32,6 +32,7 @@ pub enum Token {
     True,
     False,
     AddressLit(String),
+    SignatureLit(String),
     WhiteSpace,
 
     // Symbols
@@ -94,6 +95,7 @@ pub enum Token {
     Field,
     Group,
     Scalar,
+    Signature,
     String,
     I8,
     I16,
@@ -126,6 +128,7 @@ pub enum Token {
     Increment,
     Let,
     Mapping,
+    Match,
     Program,
     // For public inputs.
     Public,
@@ -171,12 +174,14 @@ pub const KEYWORD_TOKENS: &[Token] = &[
     Token::Increment,
     Token::Let,
     Token::Mapping,
+    Token::Match,
     Token::Program,
     Token::Public,
     Token::Record,
     Token::Return,
     Token::SelfLower,
     Token::Scalar,
+    Token::Signature,
     Token::Static,
     Token::String,
     Token::Struct,
@@ -224,11 +229,13 @@ impl Token {
             Token::Let => sym::Let,
             Token::Leo => sym::leo,
             Token::Mapping => sym::mapping,
+            Token::Match => sym::Match,
             Token::Program => sym::program,
             Token::Public => sym::Public,
             Token::Record => sym::record,
             Token::Return => sym::Return,
             Token::Scalar => sym::scalar,
+            Token::Signature => sym::signature,
             Token::SelfLower => sym::SelfLower,
             Token::Static => sym::Static,
             Token::String => sym::string,
@@ -257,6 +264,7 @@ impl fmt::Display for Token {
             True => write!(f, "true"),
             False => write!(f, "false"),
             AddressLit(s) => write!(f, "{}", s),
+            SignatureLit(s) => write!(f, "{}", s),
             WhiteSpace => write!(f, "whitespace"),
 
             Not => write!(f, "!"),
@@ -316,6 +324,7 @@ impl fmt::Display for Token {
             Field => write!(f, "field"),
             Group => write!(f, "group"),
             Scalar => write!(f, "scalar"),
+            Signature => write!(f, "signature"),
             String => write!(f, "string"),
             I8 => write!(f, "i8"),
             I16 => write!(f, "i16"),
@@ -345,6 +354,7 @@ impl fmt::Display for Token {
             Increment => write!(f, "increment"),
             Let => write!(f, "let"),
             Mapping => write!(f, "mapping"),
+            Match => write!(f, "match"),
             Program => write!(f, "program"),
             Public => write!(f, "public"),
             Return => write!(f, "return"),
@@ -0,0 +1,100 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::Token;
+
+pub use leo_ast::{Comment, Trivia};
+use leo_errors::Result;
+use leo_span::span::{BytePos, Pos, Span};
+
+/// Collects the comments and blank lines of the given source code text.
+///
+/// Each comment is attached either to the code before it on the same line, or to the code after it,
+/// so that it can be written back in the same place when the AST is printed.
+pub fn tokenize_trivia(mut input: &str, mut lo: BytePos) -> Result<Trivia> {
+    let mut trivia = Trivia::default();
+
+    // The number of line breaks since the last token, if there was one.
+    let mut line_breaks = None;
+
+    while !input.is_empty() {
+        let (token_len, token) = Token::eat(input)?;
+        let text = &input[..token_len];
+        let span = Span::new(lo, lo + BytePos::from_usize(token_len));
+        input = &input[token_len..];
+        lo = span.hi;
+
+        match token {
+            Token::WhiteSpace => {
+                if text.contains('\n') {
+                    line_breaks = line_breaks.map(|count| count + 1);
+                    if line_breaks == Some(2) {
+                        trivia.blank_lines.push(span.lo);
+                    }
+                }
+                continue;
+            }
+            Token::CommentLine(comment) => {
+                trivia.comments.push(Comment {
+                    text: comment.trim_end().to_string(),
+                    span,
+                    trailing: line_breaks == Some(0),
+                });
+                // A line comment includes the line break that ends it.
+                line_breaks = Some(comment.ends_with('\n') as usize);
+            }
+            Token::CommentBlock(comment) => {
+                trivia.comments.push(Comment {
+                    text: comment,
+                    span,
+                    trailing: line_breaks == Some(0),
+                });
+                line_breaks = Some(0);
+            }
+            _ => line_breaks = Some(0),
+        }
+    }
+
+    Ok(trivia)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    #[test]
+    fn comments_and_blank_lines() {
+        create_session_if_not_set_then(|_| {
+            let source = "// Header.\n\nprogram test.aleo { // Trailing.\n\n\n    /* Leading. */\n}\n";
+            let trivia = tokenize_trivia(source, BytePos(0)).unwrap();
+
+            let comments: Vec<_> = trivia
+                .comments
+                .iter()
+                .map(|comment| (comment.text.as_str(), comment.trailing))
+                .collect();
+            assert_eq!(
+                comments,
+                [("// Header.", false), ("// Trailing.", true), ("/* Leading. */", false)]
+            );
+
+            // The line comments end with their line breaks, so each blank line is the first whitespace after them.
+            assert_eq!(trivia.blank_lines, [BytePos(11), BytePos(45)]);
+        })
+    }
+}
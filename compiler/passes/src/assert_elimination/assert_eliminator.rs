@@ -0,0 +1,67 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::ProvenAssert;
+
+use leo_ast::{Block, ExpressionReconstructor, ProgramReconstructor, Statement, StatementReconstructor};
+
+pub struct AssertEliminator<'a> {
+    /// The assertions to remove.
+    proven: &'a [ProvenAssert],
+}
+
+impl<'a> AssertEliminator<'a> {
+    /// Returns a new assert eliminator, which removes each of the `proven` assertions.
+    pub fn new(proven: &'a [ProvenAssert]) -> Self {
+        Self { proven }
+    }
+
+    /// Returns `true` if `statement` is a proven assertion.
+    fn is_proven(&self, statement: &Statement) -> bool {
+        match statement {
+            Statement::Console(console) => self.proven.iter().any(|assert| assert.span == console.span),
+            _ => false,
+        }
+    }
+}
+
+impl ExpressionReconstructor for AssertEliminator<'_> {
+    type AdditionalOutput = ();
+}
+
+impl StatementReconstructor for AssertEliminator<'_> {
+    /// Removes the proven assertions of the block.
+    /// An assertion in an unrolled loop shares its span with its other iterations, which are all proven.
+    fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
+        (
+            Block {
+                statements: input
+                    .statements
+                    .into_iter()
+                    .filter_map(|statement| match self.is_proven(&statement) {
+                        true => None,
+                        false => Some(self.reconstruct_statement(statement).0),
+                    })
+                    .collect(),
+                span: input.span,
+                id: input.id,
+            },
+            Default::default(),
+        )
+    }
+}
+
+impl ProgramReconstructor for AssertEliminator<'_> {}
@@ -0,0 +1,48 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The assert elimination pass removes the assertions that the range analysis proved to always hold,
+//! so that no instructions are generated for them.
+//! The pass runs on the AST in static single assignment form, after the range analysis.
+//!
+//! Consider the following Leo code, in which `$var$0` is `3u8` in an iteration of an unrolled loop.
+//! ```leo
+//! let $var$1: bool = $var$0 < 10u8;
+//! console.assert($var$1);
+//! ```
+//!
+//! If the assertion holds in every iteration, the assert elimination pass produces the following code,
+//! and the proof of each iteration, e.g. `3u8 < 10u8`, is reported.
+//! ```leo
+//! let $var$1: bool = $var$0 < 10u8;
+//! ```
+
+pub mod assert_eliminator;
+pub use assert_eliminator::*;
+
+use crate::{Pass, ProvenAssert};
+
+use leo_ast::{Ast, ProgramReconstructor};
+
+impl<'a> Pass for AssertEliminator<'a> {
+    type Input = (Ast, &'a [ProvenAssert]);
+    type Output = Ast;
+
+    fn do_pass((ast, proven): Self::Input) -> Self::Output {
+        let mut reconstructor = AssertEliminator::new(proven);
+        Ast::new(reconstructor.reconstruct_program(ast.into_repr()))
+    }
+}
@@ -0,0 +1,667 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::*;
+use leo_errors::{emitter::Handler, TypeCheckerError};
+use leo_span::{sym, Symbol};
+
+use indexmap::IndexMap;
+use std::fmt;
+
+/// A future bound in an async transition, by a variable `f` or as the last element `r.1` of a tuple `r`.
+struct BoundFuture {
+    /// The variable that binds the future.
+    variable: Identifier,
+    /// The index of the future in the tuple bound by the variable, which is the number of the other outputs, if any.
+    index: Option<usize>,
+}
+
+impl fmt::Display for BoundFuture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "{}.{}", self.variable, index),
+            None => write!(f, "{}", self.variable),
+        }
+    }
+}
+
+pub struct AsyncDesugarer<'a> {
+    /// The handler that errors are emitted to.
+    handler: &'a Handler,
+    /// The async functions of the program scope being desugared, with their bodies desugared.
+    async_functions: IndexMap<Symbol, Function>,
+    /// The indices of the inputs that each async function awaits, in the order that it awaits them.
+    awaits: IndexMap<Symbol, Vec<usize>>,
+    /// The transitions of the imported programs that have finalize blocks, keyed by the names of the program and the transition,
+    /// with their numbers of outputs. Called from an async transition, they return a future after their outputs.
+    external_async_transitions: IndexMap<(Symbol, Symbol), usize>,
+    /// The name of the async transition being desugared, if any.
+    async_transition: Option<Symbol>,
+    /// The futures bound in the async transition being desugared, in the order that their transitions are called.
+    futures: Vec<BoundFuture>,
+}
+
+impl<'a> AsyncDesugarer<'a> {
+    /// Returns a new `AsyncDesugarer` that emits errors to `handler`.
+    pub fn new(handler: &'a Handler) -> Self {
+        Self {
+            handler,
+            async_functions: IndexMap::new(),
+            awaits: IndexMap::new(),
+            external_async_transitions: IndexMap::new(),
+            async_transition: None,
+            futures: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if `type_` is the type of the futures returned by async transitions.
+    fn is_future(type_: &Type) -> bool {
+        matches!(type_, Type::Identifier(identifier) if identifier.name == sym::Future)
+    }
+
+    /// Reports the inputs and outputs of `function` of type `Future`, except for the last output of an async transition.
+    fn check_signature(&self, function: &Function) {
+        let finalize = function.finalize.iter();
+        let inputs = function
+            .input
+            .iter()
+            .chain(finalize.clone().flat_map(|finalize| finalize.input.iter()));
+        // The inputs of an async function may be the futures of the transitions called by its async transition.
+        let inputs = inputs.skip(match function.call_type {
+            CallType::AsyncFunction => function.input.len(),
+            _ => 0,
+        });
+        for input in inputs {
+            if let Input::Internal(input) = input {
+                if Self::is_future(&input.type_) {
+                    self.handler.emit_err(TypeCheckerError::invalid_future_type(input.span));
+                }
+            }
+        }
+
+        let last = function.output.len().saturating_sub(1);
+        let outputs = function
+            .output
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| function.call_type != CallType::AsyncTransition || *index != last)
+            .map(|(_, output)| output)
+            .chain(finalize.flat_map(|finalize| finalize.output.iter()));
+        for output in outputs {
+            if let Output::Internal(output) = output {
+                if Self::is_future(&output.type_) {
+                    self.handler
+                        .emit_err(TypeCheckerError::invalid_future_type(output.span));
+                }
+            }
+        }
+    }
+
+    /// Returns the async function that `call` calls, if it calls one.
+    fn async_callee(&self, call: &CallExpression) -> Option<Symbol> {
+        match &*call.function {
+            Expression::Identifier(identifier)
+                if call.external.is_none() && self.async_functions.contains_key(&identifier.name) =>
+            {
+                Some(identifier.name)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the number of outputs of the transition of another program that `call` calls, if it has a finalize block.
+    fn external_async_transition(&self, call: &CallExpression) -> Option<usize> {
+        match (call.external.as_deref(), &*call.function) {
+            (Some(Expression::Identifier(program)), Expression::Identifier(function)) => self
+                .external_async_transitions
+                .get(&(program.name, function.name))
+                .copied(),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the bound future that `expression` refers to, if it refers to one.
+    fn future(&self, expression: &Expression) -> Option<usize> {
+        let (variable, index) = match expression {
+            Expression::Identifier(variable) => (variable, None),
+            Expression::Access(AccessExpression::Tuple(access)) => match &*access.tuple {
+                Expression::Identifier(variable) => (variable, Some(access.index.to_usize())),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        self.futures
+            .iter()
+            .position(|future| future.variable.name == variable.name && future.index == index)
+    }
+
+    /// Reconstructs the arguments of `call`.
+    fn reconstruct_arguments(&mut self, call: CallExpression) -> CallExpression {
+        CallExpression {
+            arguments: call
+                .arguments
+                .into_iter()
+                .map(|argument| self.reconstruct_expression(argument).0)
+                .collect(),
+            ..call
+        }
+    }
+
+    /// Removes the awaits that start the body of the async function `function`,
+    /// returning the indices of the inputs they await, in the order that they await them.
+    /// Reports the inputs of type `Future` that are not awaited exactly once.
+    fn take_awaits(&self, function: &Function, block: &mut Block) -> Vec<usize> {
+        let futures: Vec<(usize, Identifier)> = function
+            .input
+            .iter()
+            .enumerate()
+            .filter(|(_, input)| Self::is_future(&input.type_()))
+            .map(|(index, input)| (index, input.identifier()))
+            .collect();
+
+        let count = block
+            .statements
+            .iter()
+            .take_while(|statement| {
+                matches!(
+                    statement,
+                    Statement::Expression(ExpressionStatement {
+                        expression: Expression::Unary(UnaryExpression {
+                            op: UnaryOperation::Await,
+                            ..
+                        }),
+                        ..
+                    })
+                )
+            })
+            .count();
+
+        let mut awaits = Vec::new();
+        for statement in block.statements.drain(..count) {
+            if let Statement::Expression(ExpressionStatement {
+                expression: Expression::Unary(await_),
+                ..
+            }) = statement
+            {
+                let future = futures.iter().find(|(index, input)| {
+                    matches!(&*await_.receiver, Expression::Identifier(receiver) if receiver.name == input.name)
+                        && !awaits.contains(index)
+                });
+                match future {
+                    Some((index, _)) => awaits.push(*index),
+                    None => self
+                        .handler
+                        .emit_err(TypeCheckerError::cannot_await_future(await_.span)),
+                }
+            }
+        }
+
+        for (index, input) in futures {
+            if !awaits.contains(&index) {
+                self.handler
+                    .emit_err(TypeCheckerError::future_not_awaited(input, input.span));
+            }
+        }
+
+        awaits
+    }
+
+    /// Returns the async function whose future is returned by the last statement of `function`, if it returns one.
+    fn returned_future(&self, function: &Function) -> Option<Symbol> {
+        let returns_future =
+            matches!(function.output.last(), Some(Output::Internal(output)) if Self::is_future(&output.type_));
+        match function.block.statements.last() {
+            Some(Statement::Return(statement)) if returns_future => {
+                match (&statement.expression, function.output.len()) {
+                    (Expression::Call(call), 1) => self.async_callee(call),
+                    (Expression::Tuple(tuple), outputs) if tuple.elements.len() == outputs => {
+                        match tuple.elements.last() {
+                            Some(Expression::Call(call)) => self.async_callee(call),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Rewrites an async transition into a transition, whose finalize block is the async function it returns the future of.
+    fn desugar_async_transition(&mut self, function: Function) -> Function {
+        let name = function.identifier.name;
+        let callee = self.returned_future(&function);
+        if callee.is_none() {
+            self.handler
+                .emit_err(TypeCheckerError::async_transition_must_return_future(
+                    name,
+                    function.span,
+                ));
+        }
+        if let Some(finalize) = &function.finalize {
+            self.handler
+                .emit_err(TypeCheckerError::async_transition_with_finalize(name, finalize.span));
+        }
+
+        let mut output = function.output;
+        let mut statements = function.block.statements;
+
+        // Split the last statement into the call to the async function and the values of the other outputs.
+        let returned = match callee {
+            Some(_) => match statements.pop() {
+                Some(Statement::Return(statement)) => {
+                    output.pop();
+                    match (statement.expression, output.len()) {
+                        (Expression::Call(call), 0) => Some((call, None)),
+                        (Expression::Tuple(mut tuple), outputs) => match tuple.elements.pop() {
+                            Some(Expression::Call(call)) => {
+                                let values = match outputs {
+                                    1 => tuple.elements.pop().expect("the tuple has an element for each output"),
+                                    _ => Expression::Tuple(tuple),
+                                };
+                                Some((call, Some((values, statement.span, statement.id))))
+                            }
+                            _ => unreachable!("The future is returned by a call to an async function."),
+                        },
+                        _ => unreachable!("The future is returned by a call to an async function."),
+                    }
+                }
+                _ => unreachable!("The future is returned by the last statement."),
+            },
+            None => None,
+        };
+
+        self.async_transition = Some(name);
+        let mut statements: Vec<Statement> = statements
+            .into_iter()
+            .map(|statement| self.reconstruct_statement(statement).0)
+            .collect();
+        if let Some((call, values)) = returned {
+            // The futures passed to the async function are awaited by it, so they are not arguments of its finalize block.
+            let callee = callee.expect("the future is returned by a call to an async function");
+            let inputs = self.async_functions[&callee].input.clone();
+            let mut passed = IndexMap::new();
+            let mut arguments = Vec::with_capacity(call.arguments.len());
+            for (index, argument) in call.arguments.into_iter().enumerate() {
+                match inputs.get(index) {
+                    Some(input) if Self::is_future(&input.type_()) => match self.future(&argument) {
+                        Some(future) => {
+                            passed.insert(index, future);
+                        }
+                        None => self
+                            .handler
+                            .emit_err(TypeCheckerError::expected_future(input.identifier(), argument.span())),
+                    },
+                    _ => arguments.push(self.reconstruct_expression(argument).0),
+                }
+            }
+
+            for (index, future) in self.futures.iter().enumerate() {
+                if !passed.values().any(|passed| *passed == index) {
+                    self.handler
+                        .emit_err(TypeCheckerError::future_not_awaited(future, future.variable.span));
+                }
+            }
+
+            // The finalize blocks of the called transitions run in the order that they are called.
+            let order: Vec<usize> = self.awaits[&callee]
+                .iter()
+                .filter_map(|input| passed.get(input).copied())
+                .collect();
+            if order.windows(2).any(|pair| pair[0] > pair[1]) {
+                self.handler
+                    .emit_err(TypeCheckerError::futures_awaited_out_of_order(callee, call.span));
+            }
+
+            statements.push(Statement::Finalize(FinalizeStatement {
+                arguments,
+                span: call.span,
+                id: call.id,
+            }));
+            if let Some((values, span, id)) = values {
+                statements.push(Statement::Return(ReturnStatement {
+                    expression: self.reconstruct_expression(values).0,
+                    span,
+                    id,
+                }));
+            }
+        }
+        self.async_transition = None;
+        self.futures.clear();
+
+        // The body of the async function becomes the finalize block, named after the transition.
+        let finalize = callee.map(|callee| {
+            let async_function = self.async_functions[&callee].clone();
+            Finalize::new(
+                Identifier {
+                    name,
+                    span: async_function.identifier.span,
+                    id: async_function.identifier.id,
+                },
+                async_function
+                    .input
+                    .into_iter()
+                    .filter(|input| !Self::is_future(&input.type_()))
+                    .collect(),
+                async_function.output,
+                async_function.block,
+                async_function.span,
+            )
+        });
+
+        Function::new(
+            function.documentation,
+            function.annotations,
+            CallType::Transition,
+            function.identifier,
+            function.input,
+            output,
+            Block {
+                statements,
+                span: function.block.span,
+                id: function.block.id,
+            },
+            finalize,
+            function.span,
+        )
+    }
+}
+
+impl ExpressionReconstructor for AsyncDesugarer<'_> {
+    type AdditionalOutput = ();
+
+    fn reconstruct_access(&mut self, input: AccessExpression) -> (Expression, Self::AdditionalOutput) {
+        let access = match input {
+            AccessExpression::AssociatedFunction(function) => {
+                AccessExpression::AssociatedFunction(AssociatedFunction {
+                    args: function
+                        .args
+                        .into_iter()
+                        .map(|arg| self.reconstruct_expression(arg).0)
+                        .collect(),
+                    ..function
+                })
+            }
+            AccessExpression::Member(member) => AccessExpression::Member(MemberAccess {
+                inner: Box::new(self.reconstruct_expression(*member.inner).0),
+                ..member
+            }),
+            AccessExpression::Tuple(access) => {
+                let tuple = match &*access.tuple {
+                    Expression::Identifier(variable) => self
+                        .futures
+                        .iter()
+                        .find(|future| future.variable.name == variable.name)
+                        .and_then(|future| future.index),
+                    _ => None,
+                };
+                match tuple {
+                    // The future is the last element of the tuple, which is removed from its type.
+                    Some(index) if access.index.to_usize() == index => {
+                        self.handler
+                            .emit_err(TypeCheckerError::future_not_passed_to_async_function(
+                                &access,
+                                access.span,
+                            ));
+                        AccessExpression::Tuple(access)
+                    }
+                    // A tuple of one other output and the future becomes that output.
+                    Some(1) => return (*access.tuple, Default::default()),
+                    Some(_) => AccessExpression::Tuple(access),
+                    None => AccessExpression::Tuple(TupleAccess {
+                        tuple: Box::new(self.reconstruct_expression(*access.tuple).0),
+                        ..access
+                    }),
+                }
+            }
+            access => access,
+        };
+        (Expression::Access(access), Default::default())
+    }
+
+    fn reconstruct_call(&mut self, input: CallExpression) -> (Expression, Self::AdditionalOutput) {
+        if let Some(callee) = self.async_callee(&input) {
+            self.handler
+                .emit_err(TypeCheckerError::async_function_call_outside_return(callee, input.span));
+        }
+        if self.async_transition.is_some() && self.external_async_transition(&input).is_some() {
+            self.handler.emit_err(TypeCheckerError::unbound_future(
+                format!("{}.leo/{}", input.external.as_ref().unwrap(), input.function),
+                input.span,
+            ));
+        }
+
+        (
+            Expression::Call(CallExpression {
+                function: Box::new(self.reconstruct_expression(*input.function).0),
+                arguments: input
+                    .arguments
+                    .into_iter()
+                    .map(|argument| self.reconstruct_expression(argument).0)
+                    .collect(),
+                external: input.external,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_identifier(&mut self, input: Identifier) -> (Expression, Self::AdditionalOutput) {
+        if self.futures.iter().any(|future| future.variable.name == input.name) {
+            self.handler
+                .emit_err(TypeCheckerError::future_not_passed_to_async_function(input, input.span));
+        }
+        (Expression::Identifier(input), Default::default())
+    }
+
+    fn reconstruct_unary(&mut self, input: UnaryExpression) -> (Expression, Self::AdditionalOutput) {
+        if input.op == UnaryOperation::Await {
+            self.handler.emit_err(TypeCheckerError::cannot_await_future(input.span));
+        }
+
+        (
+            Expression::Unary(UnaryExpression {
+                receiver: Box::new(self.reconstruct_expression(*input.receiver).0),
+                op: input.op,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+}
+
+impl StatementReconstructor for AsyncDesugarer<'_> {
+    fn reconstruct_definition(&mut self, input: DefinitionStatement) -> (Statement, Self::AdditionalOutput) {
+        // In an async transition, the future of a call to a transition of another program is bound by the definition
+        // of its result, as its last element. The future is awaited by the async function instead of being a value.
+        let outputs = match (&input.value, self.async_transition) {
+            (Expression::Call(call), Some(_)) => self.external_async_transition(call),
+            _ => None,
+        };
+        if let (Some(outputs), Expression::Call(call)) = (outputs, &input.value) {
+            let type_ = match &input.type_ {
+                type_ if outputs == 0 && Self::is_future(type_) => Some(None),
+                Type::Tuple(tuple) if tuple.0.len() == outputs + 1 && tuple.0.last().map_or(false, Self::is_future) => {
+                    Some(Some(match outputs {
+                        1 => tuple.0[0].clone(),
+                        _ => Type::Tuple(Tuple(tuple.0[..outputs].to_vec())),
+                    }))
+                }
+                _ => None,
+            };
+            match type_ {
+                // A transition without outputs is called for its future alone.
+                Some(None) => {
+                    let call = self.reconstruct_arguments(call.clone());
+                    self.futures.push(BoundFuture {
+                        variable: input.variable_name,
+                        index: None,
+                    });
+                    return (
+                        Statement::Expression(ExpressionStatement {
+                            expression: Expression::Call(call),
+                            span: input.span,
+                            id: input.id,
+                        }),
+                        Default::default(),
+                    );
+                }
+                Some(Some(type_)) => {
+                    let call = self.reconstruct_arguments(call.clone());
+                    self.futures.push(BoundFuture {
+                        variable: input.variable_name,
+                        index: Some(outputs),
+                    });
+                    return (
+                        Statement::Definition(DefinitionStatement {
+                            type_,
+                            value: Expression::Call(call),
+                            ..input
+                        }),
+                        Default::default(),
+                    );
+                }
+                None => {}
+            }
+        }
+
+        (
+            Statement::Definition(DefinitionStatement {
+                value: self.reconstruct_expression(input.value.clone()).0,
+                ..input
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_finalize(&mut self, input: FinalizeStatement) -> (Statement, Self::AdditionalOutput) {
+        if let Some(name) = self.async_transition {
+            self.handler
+                .emit_err(TypeCheckerError::async_transition_with_finalize(name, input.span));
+        }
+
+        (
+            Statement::Finalize(FinalizeStatement {
+                arguments: input
+                    .arguments
+                    .into_iter()
+                    .map(|argument| self.reconstruct_expression(argument).0)
+                    .collect(),
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+}
+
+impl ProgramReconstructor for AsyncDesugarer<'_> {
+    fn reconstruct_program(&mut self, input: Program) -> Program {
+        let imports: IndexMap<Identifier, Program> = input
+            .imports
+            .into_iter()
+            .map(|(id, import)| (id, self.reconstruct_import(import)))
+            .collect();
+
+        // The imports are desugared first, so that their async transitions are transitions with finalize blocks.
+        let external_async_transitions = imports
+            .iter()
+            .flat_map(|(id, import)| {
+                import
+                    .program_scopes
+                    .values()
+                    .flat_map(|scope| scope.functions.values())
+                    .filter(|function| function.call_type == CallType::Transition && function.finalize.is_some())
+                    .map(move |function| ((id.name, function.name()), function.output.len()))
+            })
+            .collect();
+        let external_async_transitions =
+            std::mem::replace(&mut self.external_async_transitions, external_async_transitions);
+
+        let program_scopes = input
+            .program_scopes
+            .into_iter()
+            .map(|(id, scope)| (id, self.reconstruct_program_scope(scope)))
+            .collect();
+        self.external_async_transitions = external_async_transitions;
+
+        Program {
+            imports,
+            program_scopes,
+            import_source: input.import_source,
+        }
+    }
+
+    fn reconstruct_program_scope(&mut self, input: ProgramScope) -> ProgramScope {
+        input
+            .functions
+            .values()
+            .for_each(|function| self.check_signature(function));
+
+        // Async functions only exist as the finalize blocks of the async transitions that return their futures.
+        let (async_functions, functions): (Vec<_>, Vec<_>) = input
+            .functions
+            .into_iter()
+            .partition(|(_, function)| function.call_type == CallType::AsyncFunction);
+        self.async_functions = async_functions
+            .into_iter()
+            .map(|(identifier, function)| (identifier.name, function))
+            .collect();
+
+        let names: Vec<Symbol> = self.async_functions.keys().copied().collect();
+        for name in names {
+            let async_function = self.async_functions[&name].clone();
+            if let Some(finalize) = &async_function.finalize {
+                self.handler
+                    .emit_err(TypeCheckerError::only_transition_functions_can_have_finalize(
+                        finalize.span,
+                    ));
+            }
+            let mut block = async_function.block.clone();
+            let awaits = self.take_awaits(&async_function, &mut block);
+            self.awaits.insert(name, awaits);
+            self.async_functions[&name].block = self.reconstruct_block(block).0;
+        }
+
+        ProgramScope {
+            program_id: input.program_id,
+            structs: input.structs,
+            mappings: input.mappings,
+            functions: functions
+                .into_iter()
+                .map(|(identifier, function)| (identifier, self.reconstruct_function(function)))
+                .collect(),
+            span: input.span,
+        }
+    }
+
+    fn reconstruct_function(&mut self, input: Function) -> Function {
+        if input.call_type == CallType::AsyncTransition {
+            return self.desugar_async_transition(input);
+        }
+
+        Function {
+            block: self.reconstruct_block(input.block).0,
+            finalize: input.finalize.map(|finalize| Finalize {
+                block: self.reconstruct_block(finalize.block).0,
+                ..finalize
+            }),
+            ..input
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The async desugaring pass rewrites async transitions and async functions into transitions with finalize blocks.
+//!
+//! An async transition declares `Future` as its last output, and returns the future of a call to an async function
+//! in its last statement. The async function is the on-chain part of the transition, so it becomes its finalize block,
+//! and the call becomes an `async finalize` statement. The pass runs right after parsing, so that the later passes
+//! only see transitions with finalize blocks.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! async transition mint_public(public receiver: address, public amount: u64) -> (u64, Future) {
+//!     return (amount, finalize_mint_public(receiver, amount));
+//! }
+//!
+//! async function finalize_mint_public(public receiver: address, public amount: u64) {
+//!     increment(account, receiver, amount);
+//! }
+//! ```
+//!
+//! The async desugaring pass produces the following code.
+//! ```leo
+//! transition mint_public(public receiver: address, public amount: u64) -> u64 {
+//!     async finalize(receiver, amount);
+//!     return amount;
+//! } finalize mint_public(public receiver: address, public amount: u64) {
+//!     increment(account, receiver, amount);
+//! }
+//! ```
+//!
+//! An async transition may call the transitions of other programs that have finalize blocks, and bind the future of
+//! each call as the last element of its result. The futures are passed to the async function, which awaits them
+//! in its first statements, in the order that the transitions are called. Since snarkVM runs the finalize blocks
+//! of the called transitions before that of the caller, the futures and awaits are removed by the pass.
+//!
+//! ```leo
+//! async transition transfer(public receiver: address, public amount: u64) -> Future {
+//!     let f: Future = token.leo/burn_public(self.caller, amount);
+//!     return finalize_transfer(f, receiver, amount);
+//! }
+//!
+//! async function finalize_transfer(f: Future, public receiver: address, public amount: u64) {
+//!     f.await();
+//!     increment(account, receiver, amount);
+//! }
+//! ```
+//!
+//! The async desugaring pass produces the following code.
+//! ```leo
+//! transition transfer(public receiver: address, public amount: u64) {
+//!     token.leo/burn_public(self.caller, amount);
+//!     async finalize(receiver, amount);
+//! } finalize transfer(public receiver: address, public amount: u64) {
+//!     increment(account, receiver, amount);
+//! }
+//! ```
+
+pub mod async_desugarer;
+pub use async_desugarer::*;
+
+use crate::Pass;
+
+use leo_ast::{Ast, ProgramReconstructor};
+use leo_errors::{emitter::Handler, Result};
+
+impl<'a> Pass for AsyncDesugarer<'a> {
+    type Input = (Ast, &'a Handler);
+    type Output = Result<Ast>;
+
+    fn do_pass((ast, handler): Self::Input) -> Self::Output {
+        let mut reconstructor = AsyncDesugarer::new(handler);
+        let program = reconstructor.reconstruct_program(ast.into_repr());
+        handler.last_err()?;
+
+        Ok(Ast::new(program))
+    }
+}
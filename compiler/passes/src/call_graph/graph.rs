@@ -0,0 +1,175 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::CallType;
+use leo_span::{Span, Symbol};
+
+use indexmap::{IndexMap, IndexSet};
+use std::fmt::{self, Write};
+
+/// A function in the call graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CallNode {
+    /// A function or transition of the program.
+    Function(Symbol),
+    /// The finalize block of a transition of the program, which runs on-chain.
+    Finalize(Symbol),
+    /// A transition of another program, given by the name of the program and of the transition.
+    External(Symbol, Symbol),
+}
+
+impl fmt::Display for CallNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Function(name) => write!(f, "{name}"),
+            Self::Finalize(name) => write!(f, "{name}/finalize"),
+            Self::External(program, name) => write!(f, "{program}.leo/{name}"),
+        }
+    }
+}
+
+/// The calls between the functions of a program.
+/// Besides calls, each transition has an edge to its finalize block for every `async finalize` in it,
+/// which marks the boundary between the off-chain and on-chain parts of the transition.
+#[derive(Clone, Debug, Default)]
+pub struct CallGraph {
+    /// The functions of the program, with whether each is a transition, inlined, or a regular function.
+    functions: IndexMap<Symbol, CallType>,
+    /// The functions called by each function, with the span of the first call to each.
+    edges: IndexMap<CallNode, IndexMap<CallNode, Span>>,
+}
+
+impl CallGraph {
+    /// Adds a function of the program, which may or may not call or be called by other functions.
+    pub fn add_function(&mut self, name: Symbol, call_type: CallType) {
+        self.functions.insert(name, call_type);
+        self.edges.entry(CallNode::Function(name)).or_default();
+    }
+
+    /// Adds a call from `caller` to `callee` at `span`. Only the first call between two functions is kept.
+    pub fn add_call(&mut self, caller: CallNode, callee: CallNode, span: Span) {
+        self.edges.entry(caller).or_default().entry(callee).or_insert(span);
+        self.edges.entry(callee).or_default();
+    }
+
+    /// Returns the functions of the program, in the order they were added.
+    pub fn functions(&self) -> impl Iterator<Item = (Symbol, CallType)> + '_ {
+        self.functions.iter().map(|(name, call_type)| (*name, *call_type))
+    }
+
+    /// Returns the functions called by `caller`, with the span of the first call to each.
+    pub fn callees(&self, caller: CallNode) -> impl Iterator<Item = (CallNode, Span)> + '_ {
+        self.edges
+            .get(&caller)
+            .into_iter()
+            .flat_map(|callees| callees.iter().map(|(callee, span)| (*callee, *span)))
+    }
+
+    /// Returns the span of the first call from `caller` to `callee`, if there is one.
+    pub fn call_span(&self, caller: CallNode, callee: CallNode) -> Option<Span> {
+        self.edges
+            .get(&caller)
+            .and_then(|callees| callees.get(&callee))
+            .copied()
+    }
+
+    /// Returns the functions that call `callee`.
+    pub fn callers(&self, callee: CallNode) -> impl Iterator<Item = CallNode> + '_ {
+        self.edges
+            .iter()
+            .filter(move |(_, callees)| callees.contains_key(&callee))
+            .map(|(caller, _)| *caller)
+    }
+
+    /// Returns the functions reachable from `roots`, including the roots, in the order they are reached.
+    pub fn reachable_from(&self, roots: impl IntoIterator<Item = CallNode>) -> IndexSet<CallNode> {
+        let mut reachable: IndexSet<CallNode> = roots.into_iter().collect();
+        let mut index = 0;
+        while let Some(caller) = reachable.get_index(index).copied() {
+            reachable.extend(self.callees(caller).map(|(callee, _)| callee));
+            index += 1;
+        }
+        reachable
+    }
+
+    /// Returns a cycle of calls for each call that leads back to a function that is still being called,
+    /// each starting and ending at the same function, e.g. `[a, b, a]`.
+    /// The graph has no recursion if there are none.
+    pub fn cycles(&self) -> Vec<Vec<CallNode>> {
+        let mut cycles = Vec::new();
+        let mut finished = IndexSet::new();
+        for root in self.edges.keys() {
+            if !finished.contains(root) {
+                self.find_cycles(*root, &mut Vec::new(), &mut finished, &mut cycles);
+            }
+        }
+        cycles
+    }
+
+    /// Searches the calls of `node` depth-first, where `path` holds the calls that lead to it.
+    fn find_cycles(
+        &self,
+        node: CallNode,
+        path: &mut Vec<CallNode>,
+        finished: &mut IndexSet<CallNode>,
+        cycles: &mut Vec<Vec<CallNode>>,
+    ) {
+        path.push(node);
+        for (callee, _) in self.callees(node) {
+            if let Some(start) = path.iter().position(|caller| *caller == callee) {
+                let mut cycle = path[start..].to_vec();
+                cycle.push(callee);
+                cycles.push(cycle);
+            } else if !finished.contains(&callee) {
+                self.find_cycles(callee, path, finished, cycles);
+            }
+        }
+        path.pop();
+        finished.insert(node);
+    }
+
+    /// Returns the graph in the DOT format of Graphviz, as a digraph named `name`.
+    /// Transitions are drawn as boxes, finalize blocks and their edges as dashed, and other programs in grey.
+    pub fn to_dot(&self, name: &str) -> String {
+        let mut dot = String::new();
+        let _ = writeln!(dot, "digraph \"{name}\" {{");
+        for node in self.edges.keys() {
+            let attributes = match node {
+                CallNode::Function(name) => match self.functions.get(name) {
+                    Some(CallType::Transition | CallType::AsyncTransition) => "shape=box",
+                    _ => "shape=ellipse",
+                },
+                CallNode::Finalize(_) => "shape=box, style=dashed",
+                CallNode::External(..) => "shape=box, color=grey, fontcolor=grey",
+            };
+            let _ = writeln!(dot, "    \"{node}\" [{attributes}];");
+        }
+        for (caller, callees) in self.edges.iter() {
+            for callee in callees.keys() {
+                match callee {
+                    CallNode::Finalize(_) => {
+                        let _ = writeln!(dot, "    \"{caller}\" -> \"{callee}\" [style=dashed];");
+                    }
+                    _ => {
+                        let _ = writeln!(dot, "    \"{caller}\" -> \"{callee}\";");
+                    }
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
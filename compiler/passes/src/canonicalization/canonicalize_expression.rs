@@ -0,0 +1,60 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Canonicalizer;
+
+use leo_ast::{
+    Expression, ExpressionReconstructor, StatementReconstructor, StructExpression, StructVariableInitializer,
+};
+
+impl ExpressionReconstructor for Canonicalizer {
+    type AdditionalOutput = ();
+
+    /// Orders the members of a struct initializer as they are declared in the struct.
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        let mut members: Vec<StructVariableInitializer> = input
+            .members
+            .into_iter()
+            .map(|member| StructVariableInitializer {
+                identifier: member.identifier,
+                expression: member
+                    .expression
+                    .map(|expression| self.reconstruct_expression(expression).0),
+            })
+            .collect();
+
+        if let Some(declared) = self.struct_members.get(&input.name.name) {
+            members.sort_by_key(|member| {
+                declared
+                    .iter()
+                    .position(|name| *name == member.identifier.name)
+                    .unwrap_or(declared.len())
+            });
+        }
+
+        (
+            Expression::Struct(StructExpression {
+                name: input.name,
+                members,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+}
+
+impl StatementReconstructor for Canonicalizer {}
@@ -0,0 +1,72 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{sort_by_dependencies, struct_dependencies, CallCollector, Canonicalizer};
+
+use leo_ast::{Program, ProgramReconstructor, ProgramScope, ProgramVisitor};
+
+impl ProgramReconstructor for Canonicalizer {
+    fn reconstruct_program(&mut self, input: Program) -> Program {
+        let mut imports: Vec<_> = input
+            .imports
+            .into_iter()
+            .map(|(id, import)| (id, self.reconstruct_import(import)))
+            .collect();
+        imports.sort_by_cached_key(|(id, _)| id.name.to_string());
+
+        let mut program_scopes: Vec<_> = input
+            .program_scopes
+            .into_iter()
+            .map(|(id, scope)| (id, self.reconstruct_program_scope(scope)))
+            .collect();
+        program_scopes.sort_by_cached_key(|(id, _)| id.to_string());
+
+        Program {
+            imports: imports.into_iter().collect(),
+            program_scopes: program_scopes.into_iter().collect(),
+            import_source: input.import_source,
+        }
+    }
+
+    fn reconstruct_program_scope(&mut self, input: ProgramScope) -> ProgramScope {
+        let mut mappings: Vec<_> = input
+            .mappings
+            .into_iter()
+            .map(|(id, mapping)| (id, self.reconstruct_mapping(mapping)))
+            .collect();
+        mappings.sort_by_cached_key(|(id, _)| id.name.to_string());
+
+        let functions = input
+            .functions
+            .into_iter()
+            .map(|(id, function)| (id, self.reconstruct_function(function)))
+            .collect();
+
+        ProgramScope {
+            program_id: input.program_id,
+            // A struct must be declared after the structs that its members refer to.
+            structs: sort_by_dependencies(input.structs, struct_dependencies),
+            mappings: mappings.into_iter().collect(),
+            // A function must be declared after the functions that it calls.
+            functions: sort_by_dependencies(functions, |function| {
+                let mut collector = CallCollector::default();
+                collector.visit_function(function);
+                collector.callees
+            }),
+            span: input.span,
+        }
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    AstVisitor, CallExpression, Expression, ExpressionVisitor, Identifier, Program, Struct, StructExpression, Type,
+};
+use leo_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+
+pub struct Canonicalizer {
+    /// A map from the name of each struct and record in the program and its imports to the names of its members,
+    /// in the order they are declared.
+    pub(crate) struct_members: IndexMap<Symbol, Vec<Symbol>>,
+}
+
+impl Canonicalizer {
+    pub(crate) fn new(program: &Program) -> Self {
+        let mut canonicalizer = Self {
+            struct_members: IndexMap::new(),
+        };
+        canonicalizer.collect_struct_members(program);
+        canonicalizer
+    }
+
+    /// Records the declared member order of each struct in `program` and its imports.
+    fn collect_struct_members(&mut self, program: &Program) {
+        program
+            .imports
+            .values()
+            .for_each(|import| self.collect_struct_members(import));
+        for scope in program.program_scopes.values() {
+            for struct_ in scope.structs.values() {
+                let members = struct_.members.iter().map(|member| member.identifier.name).collect();
+                self.struct_members.insert(struct_.identifier.name, members);
+            }
+        }
+    }
+}
+
+/// Orders `items` by name, except that each item is placed after the items it depends on, as given by `dependencies`.
+/// Items in a dependency cycle, which are rejected by other passes, are placed by name alone.
+pub(crate) fn sort_by_dependencies<T>(
+    items: IndexMap<Identifier, T>,
+    dependencies: impl Fn(&T) -> IndexSet<Symbol>,
+) -> IndexMap<Identifier, T> {
+    let mut remaining: Vec<(Identifier, T)> = items.into_iter().collect();
+    remaining.sort_by_cached_key(|(identifier, _)| identifier.name.to_string());
+
+    let mut sorted = IndexMap::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        // Pick the first item whose dependencies are all placed, or the first item if there is a cycle.
+        let index = remaining
+            .iter()
+            .position(|(identifier, item)| {
+                dependencies(item).iter().all(|dependency| {
+                    *dependency == identifier.name
+                        || !remaining.iter().any(|(candidate, _)| candidate.name == *dependency)
+                })
+            })
+            .unwrap_or(0);
+        let (identifier, item) = remaining.remove(index);
+        sorted.insert(identifier, item);
+    }
+    sorted
+}
+
+/// Returns the names of the structs and records that the members of `struct_` refer to.
+pub(crate) fn struct_dependencies(struct_: &Struct) -> IndexSet<Symbol> {
+    fn collect(type_: &Type, names: &mut IndexSet<Symbol>) {
+        match type_ {
+            Type::Identifier(identifier) => {
+                names.insert(identifier.name);
+            }
+            Type::Tuple(tuple) => tuple.0.iter().for_each(|type_| collect(type_, names)),
+            _ => {}
+        }
+    }
+
+    let mut names = IndexSet::new();
+    struct_
+        .members
+        .iter()
+        .for_each(|member| collect(&member.type_, &mut names));
+    names
+}
+
+/// Collects the names of the local functions called by a function.
+#[derive(Default, AstVisitor)]
+#[visitor(visit_call, visit_struct_init)]
+pub(crate) struct CallCollector {
+    pub(crate) callees: IndexSet<Symbol>,
+}
+
+impl CallCollector {
+    fn visit_call(&mut self, input: &CallExpression, additional: &()) {
+        if let (Expression::Identifier(function), None) = (&*input.function, &input.external) {
+            self.callees.insert(function.name);
+        }
+        input.arguments.iter().for_each(|argument| {
+            self.visit_expression(argument, additional);
+        });
+    }
+
+    fn visit_struct_init(&mut self, input: &StructExpression, additional: &()) {
+        input
+            .members
+            .iter()
+            .filter_map(|member| member.expression.as_ref())
+            .for_each(|expression| self.visit_expression(expression, additional));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use leo_ast::ProgramVisitor;
+    use leo_errors::emitter::Handler;
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    #[test]
+    fn call_collector_walks_the_nodes_it_does_not_override() {
+        let source = "program test.aleo {
+    struct Point {
+        x: u8,
+    }
+
+    transition main(a: u8) -> u8 {
+        let p: Point = Point { x: in_struct(a) };
+        if in_condition(a) == 1u8 {
+            for i: u8 in 0u8..2u8 {
+                console.assert_eq(in_loop(i), 1u8);
+            }
+        }
+        let b: u8 = other.leo/external(a);
+        return nested(in_argument(a)) + p.x;
+    }
+}";
+        let callees = create_session_if_not_set_then(|_| {
+            let ast = leo_parser::parse_ast(&Handler::default(), source, Default::default()).unwrap();
+            let function = ast
+                .as_repr()
+                .program_scopes
+                .values()
+                .next()
+                .unwrap()
+                .functions
+                .values()
+                .next()
+                .unwrap();
+            let mut collector = CallCollector::default();
+            collector.visit_function(function);
+            collector
+                .callees
+                .iter()
+                .map(|callee| callee.to_string())
+                .collect::<Vec<_>>()
+        });
+
+        // External calls are not local functions.
+        assert_eq!(
+            callees,
+            ["in_struct", "in_condition", "in_loop", "nested", "in_argument"]
+        );
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The canonicalization pass rewrites the AST into a canonical form before code generation,
+//! so that semantically identical programs produce identical artifacts.
+//!
+//! The pass orders the members of each struct initializer as they are declared in the struct.
+//! It sorts imports and mappings by name, and sorts structs and functions by name after the items they depend on,
+//! since an Aleo program must declare an interface or closure before it is used.
+//! Parentheses need no rewriting, as the parser discards them and the AST is printed with only those that are needed.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! struct Point {
+//!     y: u8,
+//!     x: u8,
+//! }
+//!
+//! transition main(a: u8) -> Point {
+//!     return make(a);
+//! }
+//!
+//! function make(a: u8) -> Point {
+//!     return Point { x: a, y: (a + 1u8) };
+//! }
+//! ```
+//!
+//! The canonicalization pass produces the following code.
+//! ```leo
+//! struct Point {
+//!     y: u8,
+//!     x: u8,
+//! }
+//!
+//! function make(a: u8) -> Point {
+//!     return Point { y: a + 1u8, x: a };
+//! }
+//!
+//! transition main(a: u8) -> Point {
+//!     return make(a);
+//! }
+//! ```
+
+mod canonicalize_expression;
+
+mod canonicalize_program;
+
+pub mod canonicalizer;
+pub use canonicalizer::*;
+
+use crate::Pass;
+
+use leo_ast::{Ast, ProgramReconstructor};
+
+impl Pass for Canonicalizer {
+    type Input = Ast;
+    type Output = Ast;
+
+    fn do_pass(ast: Self::Input) -> Self::Output {
+        let mut reconstructor = Canonicalizer::new(ast.as_repr());
+        let program = reconstructor.reconstruct_program(ast.into_repr());
+
+        Ast::new(program)
+    }
+}
@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::{FunctionInstructions, GeneratedHeader};
+
 use leo_ast::Function;
 use leo_errors::emitter::Handler;
 use leo_span::Symbol;
@@ -22,8 +24,12 @@ use indexmap::IndexMap;
 
 pub struct CodeGenerator<'a> {
     _handler: &'a Handler,
+    /// The header written at the top of the generated program, if any.
+    pub(crate) header: Option<&'a GeneratedHeader>,
     /// A counter to track the next available register.
     pub(crate) next_register: u64,
+    /// A counter to track the next available label of a finalize block.
+    pub(crate) next_label: u64,
     /// Reference to the current function.
     pub(crate) current_function: Option<&'a Function>,
     /// Mapping of variables to registers.
@@ -36,20 +42,37 @@ pub struct CodeGenerator<'a> {
     pub(crate) is_transition_function: bool,
     /// Are we traversing a finalize block?
     pub(crate) in_finalize: bool,
+    /// The number of instructions generated for each statement of the function bodies visited so far.
+    pub(crate) instruction_counts: Vec<FunctionInstructions>,
+    /// Whether the functions of the current program make a tagged commitment, which needs the `TAGGED_DIGEST` interface.
+    pub(crate) uses_tagged_digest: bool,
 }
 
+/// The name of the interface that a tagged commitment commits to, holding the tag and the digest of the value.
+pub(crate) const TAGGED_DIGEST: &str = "tagged_digest";
+
 impl<'a> CodeGenerator<'a> {
     /// Initializes a new `CodeGenerator`.
     pub fn new(handler: &'a Handler) -> Self {
         // Initialize variable mapping.
         Self {
             _handler: handler,
+            header: None,
             next_register: 0,
+            next_label: 0,
             current_function: None,
             variable_mapping: IndexMap::new(),
             composite_mapping: IndexMap::new(),
             is_transition_function: false,
             in_finalize: false,
+            instruction_counts: Vec::new(),
+            uses_tagged_digest: false,
         }
     }
+
+    /// Writes `header` as comments at the top of the generated program, e.g. a license and the provenance of the code.
+    pub fn with_header(mut self, header: &'a GeneratedHeader) -> Self {
+        self.header = Some(header);
+        self
+    }
 }
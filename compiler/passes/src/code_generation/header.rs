@@ -0,0 +1,101 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::FormatConfig;
+
+use serde::Deserialize;
+use std::fmt::Write as _;
+
+/// The header of generated code, as configured under `generated` in `program.json`,
+/// so that generated files can be checked into repositories that require license headers.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HeaderConfig {
+    /// The SPDX identifier of the license of the generated code, e.g. `MIT` or `Apache-2.0 OR MIT`.
+    pub license: Option<String>,
+    /// A notice, such as a copyright line, that is written before the provenance of the code.
+    pub notice: Option<String>,
+    /// Whether to record the version of Leo and the hash of the source that the code was generated from.
+    pub provenance: bool,
+}
+
+/// The header written as comments at the top of generated code.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GeneratedHeader {
+    /// The SPDX identifier of the license of the generated code.
+    pub license: Option<String>,
+    /// A notice, which may span several lines.
+    pub notice: Option<String>,
+    /// The version of Leo and the SHA-256 hash of the source, if provenance is recorded.
+    pub provenance: Option<(String, String)>,
+    /// The layout of the comments, whose lines of the notice are wrapped at the maximum line length.
+    pub format: FormatConfig,
+}
+
+impl GeneratedHeader {
+    /// Returns the header configured by `config` for code generated from a source with the hash `source_hash`,
+    /// written in the layout of `format`.
+    pub fn new(config: &HeaderConfig, format: &FormatConfig, tool_version: &str, source_hash: &str) -> Self {
+        Self {
+            license: config.license.clone(),
+            notice: config.notice.clone(),
+            provenance: config
+                .provenance
+                .then(|| (tool_version.to_string(), source_hash.to_string())),
+            format: format.clone(),
+        }
+    }
+
+    /// Returns whether the header has no lines.
+    pub fn is_empty(&self) -> bool {
+        self.license.is_none() && self.notice.is_none() && self.provenance.is_none()
+    }
+
+    /// Renders the header as `//` comments followed by an empty line, or as nothing if it is empty.
+    /// The comment syntax is shared by Leo and Aleo, so the same header is used for both.
+    pub fn render(&self) -> String {
+        let mut header = String::new();
+        if self.is_empty() {
+            return header;
+        }
+
+        if let Some(license) = &self.license {
+            writeln!(header, "// SPDX-License-Identifier: {}", license).expect("Failed to write header to string.");
+        }
+        if let Some(notice) = &self.notice {
+            for line in notice.lines() {
+                let lines = match self.format.fits(3, line) {
+                    true => vec![line.to_string()],
+                    false => self.format.wrap("// ", line),
+                };
+                for line in lines {
+                    writeln!(header, "// {}", line).expect("Failed to write header to string.");
+                }
+            }
+        }
+        if let Some((tool_version, source_hash)) = &self.provenance {
+            writeln!(
+                header,
+                "// Generated by Leo {} from a source with SHA-256 {}.",
+                tool_version, source_hash
+            )
+            .expect("Failed to write header to string.");
+        }
+        header.push('\n');
+
+        header
+    }
+}
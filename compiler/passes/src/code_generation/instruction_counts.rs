@@ -0,0 +1,46 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_span::{Span, Symbol};
+
+/// The number of Aleo instructions generated for a statement.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StatementInstructions {
+    /// The span of the statement, or the default span if the compiler generated it, e.g. to merge the branches of a conditional.
+    pub span: Span,
+    /// The number of instructions generated for the statement.
+    pub count: usize,
+}
+
+/// The Aleo instructions generated for the body of a function or of its finalize block, by statement.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FunctionInstructions {
+    /// The function.
+    pub function: Symbol,
+    /// The span of the function.
+    pub span: Span,
+    /// Whether the instructions are those of the finalize block, which is executed on-chain instead of proven.
+    pub finalize: bool,
+    /// The instructions generated for each statement of the body, in order.
+    pub statements: Vec<StatementInstructions>,
+}
+
+impl FunctionInstructions {
+    /// Returns the number of instructions generated for the whole body.
+    pub fn total(&self) -> usize {
+        self.statements.iter().map(|statement| statement.count).sum()
+    }
+}
@@ -17,6 +17,15 @@
 pub mod generator;
 pub use generator::*;
 
+pub mod header;
+pub use header::*;
+
+pub mod instruction_counts;
+pub use instruction_counts::*;
+
+pub mod source_map;
+pub use source_map::*;
+
 mod visit_expressions;
 
 mod visit_program;
@@ -32,14 +41,14 @@ use leo_errors::emitter::Handler;
 use leo_errors::Result;
 
 impl<'a> Pass for CodeGenerator<'a> {
-    type Input = (&'a Ast, &'a Handler);
-    type Output = Result<String>;
+    type Input = (&'a Ast, &'a Handler, &'a GeneratedHeader);
+    type Output = Result<(String, Vec<FunctionInstructions>)>;
 
-    fn do_pass((ast, handler): Self::Input) -> Self::Output {
-        let mut generator = Self::new(handler);
+    fn do_pass((ast, handler, header): Self::Input) -> Self::Output {
+        let mut generator = Self::new(handler).with_header(header);
         let bytecode = generator.visit_program(ast.as_repr());
         handler.last_err()?;
 
-        Ok(bytecode)
+        Ok((bytecode, generator.instruction_counts))
     }
 }
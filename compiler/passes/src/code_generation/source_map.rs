@@ -0,0 +1,106 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::FunctionInstructions;
+
+use leo_span::{Span, Symbol};
+
+/// The Leo statement that an Aleo instruction was generated from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InstructionMapping {
+    /// The line of the instruction in the generated Aleo program, starting at 1.
+    pub line: usize,
+    /// The function whose body contains the instruction.
+    pub function: Symbol,
+    /// Whether the instruction is part of the finalize block of the function.
+    pub finalize: bool,
+    /// The span of the statement, or the default span if the compiler generated it.
+    pub span: Span,
+}
+
+/// Associates every instruction of a generated Aleo program with the Leo statement it came from,
+/// so that errors reported against the Aleo program can be traced back to the Leo source.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InstructionSourceMap {
+    /// The mappings, ordered by line.
+    pub mappings: Vec<InstructionMapping>,
+}
+
+impl InstructionSourceMap {
+    /// Builds the source map of `bytecode` from the instructions generated for each statement of its functions.
+    /// Input declarations are not generated from statements and have no mapping.
+    pub fn new(bytecode: &str, functions: &[FunctionInstructions]) -> Self {
+        let mut mappings = Vec::new();
+        let mut lines = bytecode.lines().enumerate().peekable();
+        let mut current_function = None;
+
+        while let Some((_, line)) = lines.next() {
+            // Find the body that follows the header of a closure, function or finalize block.
+            // A finalize block follows the function it belongs to.
+            let body = if let Some(name) = line.strip_prefix("function ").or_else(|| line.strip_prefix("closure ")) {
+                current_function = Some(name.trim_end_matches(':'));
+                Self::body(functions, current_function, false)
+            } else if line.starts_with("finalize ") {
+                Self::body(functions, current_function, true)
+            } else {
+                None
+            };
+
+            if let Some(body) = body {
+                // Skip the input declarations.
+                while matches!(lines.peek(), Some((_, line)) if line.trim_start().starts_with("input ")) {
+                    lines.next();
+                }
+
+                for statement in body.statements.iter() {
+                    mappings.extend(
+                        lines
+                            .by_ref()
+                            .take(statement.count)
+                            .map(|(index, _)| InstructionMapping {
+                                line: index + 1,
+                                function: body.function,
+                                finalize: body.finalize,
+                                span: statement.span,
+                            }),
+                    );
+                }
+            }
+        }
+
+        Self { mappings }
+    }
+
+    /// Returns the mapping of the instruction on `line` of the Aleo program, if it was generated from a statement.
+    pub fn get(&self, line: usize) -> Option<&InstructionMapping> {
+        self.mappings
+            .binary_search_by_key(&line, |mapping| mapping.line)
+            .ok()
+            .map(|index| &self.mappings[index])
+    }
+
+    fn body<'a>(
+        functions: &'a [FunctionInstructions],
+        name: Option<&str>,
+        finalize: bool,
+    ) -> Option<&'a FunctionInstructions> {
+        // Identifiers are interned, so the name is compared as a symbol rather than each function's name as a string.
+        let name = Symbol::intern(name?);
+        functions
+            .iter()
+            .find(|function| function.finalize == finalize && function.function == name)
+    }
+}
@@ -14,12 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::CodeGenerator;
+use crate::{CodeGenerator, TAGGED_DIGEST};
 use leo_ast::{
-    AccessExpression, AssociatedFunction, BinaryExpression, BinaryOperation, CallExpression, ErrExpression, Expression,
-    Identifier, Literal, MemberAccess, StructExpression, TernaryExpression, TupleExpression, Type, UnaryExpression,
-    UnaryOperation,
+    AccessExpression, AssociatedFunction, BinaryExpression, BinaryOperation, CallExpression, CastExpression,
+    ErrExpression, Expression, Identifier, Literal, MemberAccess, StructExpression, TernaryExpression, TupleExpression,
+    Type, UnaryExpression, UnaryOperation,
 };
+use leo_core::MappingOperation;
 use leo_span::sym;
 
 use std::fmt::Write as _;
@@ -34,6 +35,7 @@ impl<'a> CodeGenerator<'a> {
             Expression::Access(expr) => self.visit_access(expr),
             Expression::Binary(expr) => self.visit_binary(expr),
             Expression::Call(expr) => self.visit_call(expr),
+            Expression::Cast(expr) => self.visit_cast(expr),
             Expression::Struct(expr) => self.visit_struct_init(expr),
             Expression::Err(expr) => self.visit_err(expr),
             Expression::Identifier(expr) => self.visit_identifier(expr),
@@ -122,6 +124,7 @@ impl<'a> CodeGenerator<'a> {
             UnaryOperation::Negate => String::from("neg"),
             UnaryOperation::Square => String::from("square"),
             UnaryOperation::SquareRoot => String::from("sqrt"),
+            UnaryOperation::Await => unreachable!("Awaits are removed when async functions are desugared."),
         };
 
         let destination_register = format!("r{}", self.next_register);
@@ -160,6 +163,25 @@ impl<'a> CodeGenerator<'a> {
         (destination_register, instructions)
     }
 
+    fn visit_cast(&mut self, input: &'a CastExpression) -> (String, String) {
+        let (expression_operand, expression_instructions) = self.visit_expression(&input.expression);
+
+        let destination_register = format!("r{}", self.next_register);
+        let cast_instruction = format!(
+            "    cast {} into {} as {};\n",
+            expression_operand, destination_register, input.type_
+        );
+
+        // Increment the register counter.
+        self.next_register += 1;
+
+        // Concatenate the instructions.
+        let mut instructions = expression_instructions;
+        instructions.push_str(&cast_instruction);
+
+        (destination_register, instructions)
+    }
+
     fn visit_struct_init(&mut self, input: &'a StructExpression) -> (String, String) {
         // Lookup struct or record.
         let name = if let Some((is_record, type_)) = self.composite_mapping.get(&input.name.name) {
@@ -227,6 +249,9 @@ impl<'a> CodeGenerator<'a> {
     fn visit_associated_function(&mut self, input: &'a AssociatedFunction) -> (String, String) {
         // Write identifier as opcode. `Pedersen64` -> `ped64`.
         let symbol: &str = if let Type::Identifier(identifier) = input.ty {
+            if let Some(operation) = MappingOperation::from_symbols(identifier.name, input.name.name) {
+                return self.visit_mapping_operation(operation, input);
+            }
             match identifier.name {
                 sym::BHP256 => "bhp256",
                 sym::BHP512 => "bhp512",
@@ -237,14 +262,53 @@ impl<'a> CodeGenerator<'a> {
                 sym::Poseidon2 => "psd2",
                 sym::Poseidon4 => "psd4",
                 sym::Poseidon8 => "psd8",
+                // `signature::verify` -> `sign.verify`
+                sym::signature => "sign",
                 _ => unreachable!("All core function calls should be known at this time."),
             }
         } else {
             unreachable!("All core function should be known at this time.")
         };
 
-        // Construct associated function call.
-        let mut associated_function_call = format!("    {}.{} ", input.name, symbol);
+        // A tagged commitment hashes the value, and commits to the tag and the digest together,
+        // so that commitments under different tags can never be equal.
+        if input.name.name == sym::commit_tagged {
+            let mut instructions = String::new();
+            let mut args = Vec::with_capacity(input.args.len());
+            for arg in input.args.iter() {
+                let (arg_string, arg_instructions) = self.visit_expression(arg);
+                args.push(arg_string);
+                instructions.push_str(&arg_instructions);
+            }
+
+            let digest = format!("r{}", self.next_register);
+            let tagged = format!("r{}", self.next_register + 1);
+            let destination_register = format!("r{}", self.next_register + 2);
+            writeln!(instructions, "    hash.{} {} into {};", symbol, args[1], digest)
+                .expect("failed to write to string");
+            writeln!(
+                instructions,
+                "    cast {} {} into {} as {};",
+                args[0], digest, tagged, TAGGED_DIGEST
+            )
+            .expect("failed to write to string");
+            writeln!(
+                instructions,
+                "    commit.{} {} {} into {};",
+                symbol, tagged, args[2], destination_register
+            )
+            .expect("failed to write to string");
+            self.next_register += 3;
+            self.uses_tagged_digest = true;
+
+            return (destination_register, instructions);
+        }
+
+        // Construct associated function call. The signature opcode is written module first, i.e. `sign.verify`.
+        let mut associated_function_call = match input.name.name {
+            sym::verify => format!("    {}.{} ", symbol, input.name),
+            _ => format!("    {}.{} ", input.name, symbol),
+        };
         let mut instructions = String::new();
 
         // Visit each function argument and accumulate instructions from expressions.
@@ -266,6 +330,56 @@ impl<'a> CodeGenerator<'a> {
         (destination_register, instructions)
     }
 
+    /// Writes an operation on a mapping, e.g. `Mapping::get(balances, addr)` as `get balances[r0] into r1;`.
+    fn visit_mapping_operation(
+        &mut self,
+        operation: MappingOperation,
+        input: &'a AssociatedFunction,
+    ) -> (String, String) {
+        let mapping = match &input.args[0] {
+            Expression::Identifier(mapping) => mapping.name,
+            _ => unreachable!("Type checking guarantees that the first argument is a mapping."),
+        };
+
+        let mut instructions = String::new();
+        let mut args = Vec::with_capacity(input.args.len() - 1);
+        for arg in input.args.iter().skip(1) {
+            let (arg_string, arg_instructions) = self.visit_expression(arg);
+            args.push(arg_string);
+            instructions.push_str(&arg_instructions);
+        }
+
+        let opcode = operation.opcode();
+        match operation {
+            MappingOperation::Set => {
+                writeln!(
+                    instructions,
+                    "    {} {} into {}[{}];",
+                    opcode, args[1], mapping, args[0]
+                )
+                .expect("failed to write to string");
+                (String::new(), instructions)
+            }
+            MappingOperation::Remove => {
+                writeln!(instructions, "    {} {}[{}];", opcode, mapping, args[0]).expect("failed to write to string");
+                (String::new(), instructions)
+            }
+            MappingOperation::Get | MappingOperation::GetOrUse | MappingOperation::Contains => {
+                let destination_register = format!("r{}", self.next_register);
+                let default = args.get(1).map(|default| format!(" {}", default)).unwrap_or_default();
+                writeln!(
+                    instructions,
+                    "    {} {}[{}]{} into {};",
+                    opcode, mapping, args[0], default, destination_register
+                )
+                .expect("failed to write to string");
+                self.next_register += 1;
+
+                (destination_register, instructions)
+            }
+        }
+    }
+
     fn visit_access(&mut self, input: &'a AccessExpression) -> (String, String) {
         match input {
             AccessExpression::Member(access) => self.visit_member_access(access),
@@ -275,7 +389,8 @@ impl<'a> CodeGenerator<'a> {
         }
     }
 
-    fn visit_call(&mut self, input: &'a CallExpression) -> (String, String) {
+    /// Returns the call instruction of `input` without its destination register, and the instructions that compute its arguments.
+    pub(crate) fn visit_call_operands(&mut self, input: &'a CallExpression) -> (String, String) {
         let mut call_instruction = match &input.external {
             Some(external) => format!("    call {}.aleo/{} ", external, input.function),
             None => format!("    call {} ", input.function),
@@ -288,6 +403,12 @@ impl<'a> CodeGenerator<'a> {
             instructions.push_str(&argument_instructions);
         }
 
+        (call_instruction, instructions)
+    }
+
+    fn visit_call(&mut self, input: &'a CallExpression) -> (String, String) {
+        let (mut call_instruction, mut instructions) = self.visit_call_operands(input);
+
         // Push destination register to call instruction.
         let destination_register = format!("r{}", self.next_register);
         writeln!(call_instruction, "into {};", destination_register).expect("failed to write to string");
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::CodeGenerator;
+use crate::{CodeGenerator, GeneratedHeader, TAGGED_DIGEST};
 
 use leo_ast::{functions, CallType, Function, Identifier, Mapping, Mode, Program, ProgramScope, Struct, Type};
 
@@ -26,7 +26,7 @@ use std::fmt::Write as _;
 impl<'a> CodeGenerator<'a> {
     pub(crate) fn visit_program(&mut self, input: &'a Program) -> String {
         // Accumulate instructions into a program string.
-        let mut program_string = String::new();
+        let mut program_string = self.header.map(GeneratedHeader::render).unwrap_or_default();
 
         if !input.imports.is_empty() {
             // Visit each import statement and produce a Aleo import instruction.
@@ -65,6 +65,9 @@ impl<'a> CodeGenerator<'a> {
         // Newline separator.
         program_string.push('\n');
 
+        // The interface of tagged commitments is declared after the structs, if a function makes one.
+        let tagged_digest_position = program_string.len();
+
         // Visit each mapping in the Leo AST and produce an Aleo mapping declaration.
         program_string.push_str(
             &program_scope
@@ -79,7 +82,13 @@ impl<'a> CodeGenerator<'a> {
         let mut functions = String::new();
 
         // Visit each `Function` in the Leo AST and produce Aleo instructions.
+        self.uses_tagged_digest = false;
         program_scope.functions.values().for_each(|function| {
+            // Tests are only run by the interpreter, so they are not part of the Aleo program.
+            if function.is_test() {
+                return;
+            }
+
             self.is_transition_function = matches!(function.call_type, CallType::Transition);
 
             let function_string = self.visit_function(function);
@@ -96,6 +105,13 @@ impl<'a> CodeGenerator<'a> {
             self.is_transition_function = false;
         });
 
+        if self.uses_tagged_digest {
+            program_string.insert_str(
+                tagged_digest_position,
+                &format!("interface {TAGGED_DIGEST}:\n    tag as field;\n    digest as field;\n\n"),
+            );
+        }
+
         // Closures must precede functions in the Aleo program.
         program_string.push_str(&closures);
         program_string.push('\n');
@@ -106,9 +122,13 @@ impl<'a> CodeGenerator<'a> {
 
     fn visit_import(&mut self, import_name: &'a Identifier, import_program: &'a Program) -> String {
         // Load symbols into composite mapping.
+        let instruction_counts = self.instruction_counts.len();
         let _import_program_string = self.visit_program(import_program);
         // todo: We do not need the import program string because we generate instructions for imports separately during leo build.
 
+        // The instructions of the imported program are not part of this one.
+        self.instruction_counts.truncate(instruction_counts);
+
         // Generate string for import statement.
         format!("import {}.aleo;", import_name)
     }
@@ -180,8 +200,9 @@ impl<'a> CodeGenerator<'a> {
                 functions::Input::Internal(input) => {
                     self.variable_mapping
                         .insert(&input.identifier.name, register_string.clone());
+                    // Witnesses are private inputs, which the type checker ensures are the last inputs.
                     let visibility = match (self.is_transition_function, input.mode) {
-                        (true, Mode::None) => Mode::Private,
+                        (true, Mode::None | Mode::Witness) => Mode::Private,
                         _ => input.mode,
                     };
                     self.visit_type_with_visibility(&input.type_, visibility)
@@ -198,13 +219,14 @@ impl<'a> CodeGenerator<'a> {
         }
 
         //  Construct and append the function body.
-        let block_string = self.visit_block(&function.block);
+        let block_string = self.visit_body(function, &function.block);
         function_string.push_str(&block_string);
 
         // If the finalize block exists, generate the appropriate bytecode.
         if let Some(finalize) = &function.finalize {
-            // Clear the register count.
+            // Clear the register and label counts.
             self.next_register = 0;
+            self.next_label = 0;
             self.in_finalize = true;
 
             // Clear the variable mapping.
@@ -243,7 +265,7 @@ impl<'a> CodeGenerator<'a> {
             }
 
             // Construct and append the finalize block body.
-            function_string.push_str(&self.visit_block(&finalize.block));
+            function_string.push_str(&self.visit_body(function, &finalize.block));
 
             self.in_finalize = false;
         }
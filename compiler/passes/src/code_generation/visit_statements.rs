@@ -14,12 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::CodeGenerator;
+use crate::{CodeGenerator, FunctionInstructions, StatementInstructions};
 
 use leo_ast::{
     AssignStatement, Block, ConditionalStatement, ConsoleFunction, ConsoleStatement, DecrementStatement,
-    DefinitionStatement, Expression, FinalizeStatement, IncrementStatement, IterationStatement, Mode, Output,
-    ReturnStatement, Statement,
+    DefinitionStatement, Expression, ExpressionStatement, FinalizeStatement, Function, IncrementStatement,
+    IterationStatement, MatchStatement, Mode, Node, Output, ReturnStatement, Statement,
 };
 
 use itertools::Itertools;
@@ -34,9 +34,11 @@ impl<'a> CodeGenerator<'a> {
             Statement::Console(stmt) => self.visit_console(stmt),
             Statement::Decrement(stmt) => self.visit_decrement(stmt),
             Statement::Definition(stmt) => self.visit_definition(stmt),
+            Statement::Expression(stmt) => self.visit_expression_statement(stmt),
             Statement::Finalize(stmt) => self.visit_finalize(stmt),
             Statement::Increment(stmt) => self.visit_increment(stmt),
             Statement::Iteration(stmt) => self.visit_iteration(stmt),
+            Statement::Match(stmt) => self.visit_match(stmt),
             Statement::Return(stmt) => self.visit_return(stmt),
         }
     }
@@ -47,10 +49,16 @@ impl<'a> CodeGenerator<'a> {
             Expression::Tuple(ref tuple) if tuple.elements.is_empty() => String::new(),
             _ => {
                 let (operand, mut expression_instructions) = self.visit_expression(&input.expression);
+                // The outputs of the finalize block are returned from it, rather than those of the function.
+                let function = self.current_function.unwrap();
+                let outputs = match (self.in_finalize, &function.finalize) {
+                    (true, Some(finalize)) => &finalize.output,
+                    _ => &function.output,
+                };
                 let instructions = operand
                     .split('\n')
                     .into_iter()
-                    .zip(self.current_function.unwrap().output.iter())
+                    .zip(outputs.iter())
                     .map(|(operand, output)| {
                         match output {
                             Output::Internal(output) => {
@@ -120,6 +128,19 @@ impl<'a> CodeGenerator<'a> {
         instructions
     }
 
+    fn visit_expression_statement(&mut self, input: &'a ExpressionStatement) -> String {
+        match &input.expression {
+            // The call is to a transition of another program without outputs, made for the future of its finalize block.
+            Expression::Call(call) => {
+                let (call_instruction, mut instructions) = self.visit_call_operands(call);
+                writeln!(instructions, "{};", call_instruction.trim_end()).expect("failed to write to string");
+                instructions
+            }
+            // The expression is an update of a mapping, which has no output.
+            expression => self.visit_expression(expression).1,
+        }
+    }
+
     fn visit_finalize(&mut self, input: &'a FinalizeStatement) -> String {
         let mut instructions = String::new();
         let mut finalize_instruction = "    finalize".to_string();
@@ -147,9 +168,35 @@ impl<'a> CodeGenerator<'a> {
         }
     }
 
-    fn visit_conditional(&mut self, _input: &'a ConditionalStatement) -> String {
+    fn visit_conditional(&mut self, input: &'a ConditionalStatement) -> String {
         // TODO: Once SSA is made optional, create a Leo error informing the user to enable the SSA pass.
-        unreachable!("`ConditionalStatement`s should not be in the AST at this phase of compilation.")
+        if !self.in_finalize {
+            unreachable!("`ConditionalStatement`s should only be in the finalize blocks at this phase of compilation.")
+        }
+
+        // Branch past the then-block if the condition is false, and past the otherwise-block at the end of the then-block.
+        let label = self.next_label;
+        self.next_label += 1;
+
+        let (condition, mut instructions) = self.visit_expression(&input.condition);
+        writeln!(instructions, "    branch.eq {} false to end_then_{};", condition, label)
+            .expect("failed to write to string");
+        instructions.push_str(&self.visit_block(&input.then));
+
+        match &input.otherwise {
+            None => {
+                writeln!(instructions, "    position end_then_{};", label).expect("failed to write to string");
+            }
+            Some(otherwise) => {
+                writeln!(instructions, "    branch.eq true true to end_otherwise_{};", label)
+                    .expect("failed to write to string");
+                writeln!(instructions, "    position end_then_{};", label).expect("failed to write to string");
+                instructions.push_str(&self.visit_statement(otherwise));
+                writeln!(instructions, "    position end_otherwise_{};", label).expect("failed to write to string");
+            }
+        }
+
+        instructions
     }
 
     fn visit_iteration(&mut self, _input: &'a IterationStatement) -> String {
@@ -157,6 +204,10 @@ impl<'a> CodeGenerator<'a> {
         unreachable!("`IterationStatement`s should not be in the AST at this phase of compilation.");
     }
 
+    fn visit_match(&mut self, _input: &'a MatchStatement) -> String {
+        unreachable!("`MatchStatement`s should not be in the AST at this phase of compilation.");
+    }
+
     fn visit_console(&mut self, input: &'a ConsoleStatement) -> String {
         let mut generate_assert_instruction = |name: &str, left: &'a Expression, right: &'a Expression| {
             let (left_operand, left_instructions) = self.visit_expression(left);
@@ -171,15 +222,15 @@ impl<'a> CodeGenerator<'a> {
             instructions
         };
         match &input.function {
-            ConsoleFunction::Assert(expr) => {
+            ConsoleFunction::Assert(expr, _) => {
                 let (operand, mut instructions) = self.visit_expression(expr);
                 let assert_instruction = format!("    assert.eq {} true;\n", operand);
 
                 instructions.push_str(&assert_instruction);
                 instructions
             }
-            ConsoleFunction::AssertEq(left, right) => generate_assert_instruction("assert.eq", left, right),
-            ConsoleFunction::AssertNeq(left, right) => generate_assert_instruction("assert.neq", left, right),
+            ConsoleFunction::AssertEq(left, right, _) => generate_assert_instruction("assert.eq", left, right),
+            ConsoleFunction::AssertNeq(left, right, _) => generate_assert_instruction("assert.neq", left, right),
         }
     }
 
@@ -187,4 +238,29 @@ impl<'a> CodeGenerator<'a> {
         // For each statement in the block, visit it and add its instructions to the list.
         input.statements.iter().map(|stmt| self.visit_statement(stmt)).join("")
     }
+
+    /// Visits the body of `function`, or of its finalize block, recording the number of instructions of each statement.
+    pub(crate) fn visit_body(&mut self, function: &'a Function, input: &'a Block) -> String {
+        let mut counts = FunctionInstructions {
+            function: function.name(),
+            span: function.span,
+            finalize: self.in_finalize,
+            statements: Vec::with_capacity(input.statements.len()),
+        };
+        let instructions = input
+            .statements
+            .iter()
+            .map(|stmt| {
+                let instructions = self.visit_statement(stmt);
+                counts.statements.push(StatementInstructions {
+                    span: stmt.span(),
+                    count: instructions.lines().count(),
+                });
+                instructions
+            })
+            .join("");
+        self.instruction_counts.push(counts);
+
+        instructions
+    }
 }
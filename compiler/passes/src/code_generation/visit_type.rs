@@ -26,6 +26,7 @@ impl<'a> CodeGenerator<'a> {
             | Type::Field
             | Type::Group
             | Type::Scalar
+            | Type::Signature
             | Type::String
             | Type::Integer(..) => format!("{}", input),
             Type::Identifier(ident) => format!("{}", ident),
@@ -49,6 +50,8 @@ impl<'a> CodeGenerator<'a> {
             }
             _ => match visibility {
                 Mode::None => self.visit_type(type_),
+                // Aleo spells out the constant visibility.
+                Mode::Const => format!("{}.constant", self.visit_type(type_)),
                 _ => format!("{}.{}", self.visit_type(type_), visibility),
             },
         }
@@ -0,0 +1,28 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_span::{Span, Symbol};
+
+/// The estimated number of constraints of a function, including those of the functions it calls.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CostEstimate {
+    /// The function.
+    pub function: Symbol,
+    /// The span of the function.
+    pub span: Span,
+    /// The estimated number of constraints.
+    pub constraints: u64,
+}
@@ -0,0 +1,433 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CostEstimate, SymbolTable};
+
+use leo_ast::*;
+use leo_core::CoreInstruction;
+use leo_span::{sym, Symbol};
+
+use indexmap::IndexMap;
+
+/// The number of bits of a field element.
+const FIELD_BITS: u64 = 253;
+/// The number of bits of a scalar.
+const SCALAR_BITS: u64 = 251;
+/// The weight of adding two group elements.
+const GROUP_ADD: u64 = 6;
+
+/// Estimates the number of constraints of each function from static weights of its operations.
+///
+/// The weights approximate the constraints that an operation is synthesized into, e.g. one for a field multiplication
+/// and one per bit for an integer addition. They are coarse, so that the estimates are meant to compare implementations
+/// rather than to predict the size of a circuit. Both branches of a conditional are counted, since a circuit evaluates
+/// both, and the body of a loop is counted once per iteration. Calls count the estimate of the function they call,
+/// while finalize blocks are executed on-chain instead of being proven, so they count nothing.
+///
+/// This must be run on a type checked AST, since the types of the operands decide the weights.
+/// Since the AST does not record them, they are inferred again here.
+pub struct CostEstimator<'a> {
+    /// The estimates of the functions visited so far.
+    pub(crate) estimates: IndexMap<Symbol, CostEstimate>,
+    /// The symbol table, which provides the types of struct members and function outputs.
+    symbol_table: &'a SymbolTable,
+    /// The functions of the current program scope.
+    functions: IndexMap<Symbol, &'a Function>,
+    /// The types of the variables in the function being visited.
+    variables: IndexMap<Symbol, Type>,
+    /// The constraints counted so far in the function being visited.
+    constraints: u64,
+}
+
+impl<'a> CostEstimator<'a> {
+    /// Returns a new cost estimator.
+    pub fn new(symbol_table: &'a SymbolTable) -> Self {
+        Self {
+            estimates: IndexMap::new(),
+            symbol_table,
+            functions: IndexMap::new(),
+            variables: IndexMap::new(),
+            constraints: 0,
+        }
+    }
+
+    /// Returns the estimate of the function `name` of the current program scope, estimating it first if needed.
+    /// Functions of other programs are proven on their own, so they count nothing.
+    fn estimate(&mut self, name: Symbol) -> u64 {
+        if let Some(estimate) = self.estimates.get(&name) {
+            return estimate.constraints;
+        }
+        let function = match self.functions.get(&name) {
+            Some(function) => *function,
+            None => return 0,
+        };
+
+        // Record an empty estimate first, so that a recursive call does not recurse here.
+        self.estimates.insert(
+            name,
+            CostEstimate {
+                function: name,
+                span: function.span,
+                constraints: 0,
+            },
+        );
+        let variables = std::mem::take(&mut self.variables);
+        let constraints = std::mem::take(&mut self.constraints);
+        self.visit_function(function);
+        self.variables = variables;
+        self.constraints = constraints;
+
+        self.estimates[&name].constraints
+    }
+
+    /// Returns the number of bits of an integer type.
+    fn bits(integer_type: IntegerType) -> u64 {
+        match integer_type {
+            IntegerType::U8 | IntegerType::I8 => 8,
+            IntegerType::U16 | IntegerType::I16 => 16,
+            IntegerType::U32 | IntegerType::I32 => 32,
+            IntegerType::U64 | IntegerType::I64 => 64,
+            IntegerType::U128 | IntegerType::I128 => 128,
+        }
+    }
+
+    /// Returns the weight of selecting between two values of the given type, which is that of each of their bits
+    /// for integers, and of each of their members for structs and tuples.
+    fn select_weight(&self, type_: &Type) -> u64 {
+        match type_ {
+            Type::Integer(integer_type) => Self::bits(*integer_type),
+            Type::Identifier(struct_) => self.symbol_table.lookup_struct(struct_.name).map_or(1, |struct_| {
+                struct_
+                    .members
+                    .iter()
+                    .map(|member| self.select_weight(&member.type_))
+                    .sum()
+            }),
+            Type::Tuple(tuple) => tuple.iter().map(|type_| self.select_weight(type_)).sum(),
+            _ => 1,
+        }
+    }
+
+    /// Returns the weight of a binary operation on operands of the given types.
+    fn binary_weight(op: BinaryOperation, left: &Type, right: Option<&Type>) -> u64 {
+        use BinaryOperation::*;
+        match (left, right) {
+            (Type::Group, _) | (Type::Scalar, Some(Type::Group)) if op == Mul => SCALAR_BITS * GROUP_ADD,
+            (Type::Field, _) => match op {
+                Add | Sub => 0,
+                Mul => 1,
+                Div | Eq | Neq => 2,
+                Pow | Lt | Lte | Gt | Gte => 2 * FIELD_BITS,
+                _ => 1,
+            },
+            (Type::Group, _) => match op {
+                Add | Sub => GROUP_ADD,
+                _ => 2,
+            },
+            (Type::Integer(integer_type), _) => {
+                let bits = Self::bits(*integer_type);
+                match op {
+                    Add | AddWrapped | Sub | SubWrapped | Lt | Lte | Gt | Gte => bits + 1,
+                    Mul | MulWrapped | Shl | ShlWrapped | Shr | ShrWrapped => 2 * bits,
+                    Div | DivWrapped | Rem | RemWrapped | Mod => 4 * bits,
+                    // Exponentiation squares and multiplies once for each bit of the exponent.
+                    Pow | PowWrapped => match right {
+                        Some(Type::Integer(exponent_type)) => Self::bits(*exponent_type) * 4 * bits,
+                        _ => 8 * 4 * bits,
+                    },
+                    Eq | Neq => 2,
+                    _ => bits,
+                }
+            }
+            (Type::Address | Type::Scalar, _) if matches!(op, Eq | Neq) => 2,
+            _ => 1,
+        }
+    }
+
+    /// Returns the weight of a unary operation on an operand of the given type.
+    fn unary_weight(op: UnaryOperation, type_: &Type) -> u64 {
+        use UnaryOperation::*;
+        match (type_, op) {
+            (Type::Field | Type::Group | Type::Boolean, Negate | Not) => 0,
+            (Type::Field, Square) => 1,
+            (Type::Field, Inverse) => 2,
+            (Type::Field, SquareRoot) => 2 * FIELD_BITS,
+            (Type::Group, Double) => GROUP_ADD - 1,
+            (Type::Integer(integer_type), _) => Self::bits(*integer_type) + 1,
+            _ => 1,
+        }
+    }
+
+    /// Returns the weight of a call to a core instruction, such as a hash.
+    fn core_instruction_weight(instruction: &CoreInstruction) -> u64 {
+        use CoreInstruction::*;
+        match instruction {
+            BHP256Hash => 800,
+            BHP512Hash => 1_400,
+            BHP768Hash => 2_000,
+            BHP1024Hash => 2_600,
+            // A commitment also adds a randomizer, which is a scalar multiplication.
+            BHP256Commit => 800 + SCALAR_BITS * GROUP_ADD,
+            BHP512Commit => 1_400 + SCALAR_BITS * GROUP_ADD,
+            BHP768Commit => 2_000 + SCALAR_BITS * GROUP_ADD,
+            BHP1024Commit => 2_600 + SCALAR_BITS * GROUP_ADD,
+            // A tagged commitment first hashes the value, then commits to the tag and the digest.
+            BHP256CommitTagged => 800 + 800 + SCALAR_BITS * GROUP_ADD,
+            BHP512CommitTagged => 1_400 + 1_400 + SCALAR_BITS * GROUP_ADD,
+            BHP768CommitTagged => 2_000 + 2_000 + SCALAR_BITS * GROUP_ADD,
+            BHP1024CommitTagged => 2_600 + 2_600 + SCALAR_BITS * GROUP_ADD,
+            Pedersen64Hash => 64 * GROUP_ADD,
+            Pedersen128Hash => 128 * GROUP_ADD,
+            Pedersen64Commit => 64 * GROUP_ADD + SCALAR_BITS * GROUP_ADD,
+            Pedersen128Commit => 128 * GROUP_ADD + SCALAR_BITS * GROUP_ADD,
+            Poseidon2Hash => 250,
+            Poseidon4Hash => 300,
+            Poseidon8Hash => 400,
+            // A verification recomputes the challenge with a Poseidon hash, after two scalar multiplications.
+            SignatureVerify => 2 * SCALAR_BITS * GROUP_ADD + 300,
+        }
+    }
+
+    /// Declares the inputs of a function as variables.
+    fn declare_inputs(&mut self, input: &[Input]) {
+        for input in input {
+            self.variables.insert(input.identifier().name, input.type_());
+        }
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for CostEstimator<'a> {
+    type AdditionalInput = ();
+    /// The type of the expression, if it is known.
+    type Output = Option<Type>;
+
+    fn visit_access(&mut self, input: &'a AccessExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        match input {
+            AccessExpression::AssociatedConstant(constant) => Some(constant.ty.clone()),
+            AccessExpression::AssociatedFunction(function) => {
+                function.args.iter().for_each(|arg| {
+                    self.visit_expression(arg, additional);
+                });
+                match &function.ty {
+                    Type::Identifier(module) => {
+                        CoreInstruction::from_symbols(module.name, function.name.name).map(|instruction| {
+                            self.constraints += Self::core_instruction_weight(&instruction);
+                            instruction.return_type()
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            AccessExpression::Member(member) => match &*member.inner {
+                Expression::Identifier(identifier) if identifier.name == sym::SelfLower => match member.name.name {
+                    sym::caller => Some(Type::Address),
+                    _ => None,
+                },
+                _ => match self.visit_expression(&member.inner, additional)? {
+                    Type::Identifier(struct_) => self
+                        .symbol_table
+                        .lookup_struct(struct_.name)?
+                        .members
+                        .iter()
+                        .find(|struct_member| struct_member.identifier.name == member.name.name)
+                        .map(|struct_member| struct_member.type_.clone()),
+                    _ => None,
+                },
+            },
+            AccessExpression::Tuple(access) => match self.visit_expression(&access.tuple, additional)? {
+                Type::Tuple(tuple) => tuple.get(access.index.to_usize()).cloned(),
+                _ => None,
+            },
+        }
+    }
+
+    fn visit_binary(&mut self, input: &'a BinaryExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        let left = self.visit_expression(&input.left, additional);
+        let right = self.visit_expression(&input.right, additional);
+
+        self.constraints += match &left {
+            Some(type_) => Self::binary_weight(input.op, type_, right.as_ref()),
+            None => 1,
+        };
+
+        match input.op {
+            BinaryOperation::And
+            | BinaryOperation::Or
+            | BinaryOperation::Nand
+            | BinaryOperation::Nor
+            | BinaryOperation::Eq
+            | BinaryOperation::Neq
+            | BinaryOperation::Lt
+            | BinaryOperation::Lte
+            | BinaryOperation::Gt
+            | BinaryOperation::Gte => Some(Type::Boolean),
+            // A scalar can multiply a group element from either side.
+            BinaryOperation::Mul if matches!(right, Some(Type::Group)) => right,
+            _ => left.or(right),
+        }
+    }
+
+    fn visit_call(&mut self, input: &'a CallExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        input.arguments.iter().for_each(|argument| {
+            self.visit_expression(argument, additional);
+        });
+        match &*input.function {
+            Expression::Identifier(function) => {
+                self.constraints += self.estimate(function.name);
+                self.symbol_table
+                    .lookup_fn_symbol(function.name)
+                    .map(|function| function.output_type.clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        input.members.iter().for_each(|member| {
+            if let Some(expression) = &member.expression {
+                self.visit_expression(expression, additional);
+            }
+        });
+        Some(Type::Identifier(input.name))
+    }
+
+    fn visit_identifier(&mut self, input: &'a Identifier, _additional: &Self::AdditionalInput) -> Self::Output {
+        self.variables.get(&input.name).cloned()
+    }
+
+    fn visit_literal(&mut self, input: &'a Literal, _additional: &Self::AdditionalInput) -> Self::Output {
+        Some(match input {
+            Literal::Address(..) => Type::Address,
+            Literal::Boolean(..) => Type::Boolean,
+            Literal::Field(..) => Type::Field,
+            Literal::Group(..) => Type::Group,
+            Literal::Integer(integer_type, ..) => Type::Integer(*integer_type),
+            Literal::Scalar(..) => Type::Scalar,
+            Literal::Signature(..) => Type::Signature,
+            Literal::String(..) => Type::String,
+        })
+    }
+
+    fn visit_ternary(&mut self, input: &'a TernaryExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        self.visit_expression(&input.condition, additional);
+        let if_true = self.visit_expression(&input.if_true, additional);
+        let if_false = self.visit_expression(&input.if_false, additional);
+        let type_ = if_true.or(if_false);
+        self.constraints += type_.as_ref().map_or(1, |type_| self.select_weight(type_));
+        type_
+    }
+
+    fn visit_tuple(&mut self, input: &'a TupleExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        let elements: Vec<_> = input
+            .elements
+            .iter()
+            .map(|element| self.visit_expression(element, additional))
+            .collect();
+        elements
+            .into_iter()
+            .collect::<Option<_>>()
+            .map(|elements| Type::Tuple(Tuple(elements)))
+    }
+
+    fn visit_unary(&mut self, input: &'a UnaryExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        let receiver = self.visit_expression(&input.receiver, additional);
+        self.constraints += match &receiver {
+            Some(type_) => Self::unary_weight(input.op, type_),
+            None => 1,
+        };
+        receiver
+    }
+}
+
+impl<'a> StatementVisitor<'a> for CostEstimator<'a> {
+    fn visit_console(&mut self, input: &'a ConsoleStatement) {
+        match &input.function {
+            ConsoleFunction::Assert(expression, _) => {
+                self.visit_expression(expression, &());
+                self.constraints += 1;
+            }
+            ConsoleFunction::AssertEq(left, right, _) | ConsoleFunction::AssertNeq(left, right, _) => {
+                let type_ = self.visit_expression(left, &());
+                self.visit_expression(right, &());
+                self.constraints += match &type_ {
+                    Some(type_) => Self::binary_weight(BinaryOperation::Eq, type_, Some(type_)),
+                    None => 1,
+                };
+            }
+        }
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        self.visit_expression(&input.value, &());
+        self.variables.insert(input.variable_name.name, input.type_.clone());
+    }
+
+    fn visit_iteration(&mut self, input: &'a IterationStatement) {
+        self.visit_expression(&input.start, &());
+        self.visit_expression(&input.stop, &());
+        self.variables.insert(input.variable.name, input.type_.clone());
+
+        // The type checker evaluates the bounds, which are constants. The body is counted once if they are unknown.
+        let bound = |value: &Option<Value>| value.as_ref().and_then(|value| i128::try_from(value).ok());
+        let iterations = match (bound(&input.start_value.borrow()), bound(&input.stop_value.borrow())) {
+            (Some(start), Some(stop)) => {
+                let iterations = stop.saturating_sub(start) + input.inclusive as i128;
+                u64::try_from(iterations.max(0)).unwrap_or(u64::MAX)
+            }
+            _ => 1,
+        };
+
+        let before = self.constraints;
+        self.visit_block(&input.block);
+        let body = self.constraints - before;
+        self.constraints = before.saturating_add(body.saturating_mul(iterations));
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for CostEstimator<'a> {
+    // Imported programs are compiled, and estimated, on their own.
+    fn visit_import(&mut self, _input: &'a Program) {}
+
+    fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        // Tests are only run by the interpreter, so they are not part of the circuits.
+        self.functions = input
+            .functions
+            .values()
+            .filter(|function| !function.is_test())
+            .map(|function| (function.name(), function))
+            .collect();
+        let names: Vec<_> = self.functions.keys().copied().collect();
+        names.into_iter().for_each(|name| {
+            self.estimate(name);
+        });
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        self.variables.clear();
+        self.constraints = 0;
+        self.declare_inputs(&input.input);
+        self.visit_block(&input.block);
+
+        self.estimates.insert(
+            input.name(),
+            CostEstimate {
+                function: input.name(),
+                span: input.span,
+                constraints: self.constraints,
+            },
+        );
+    }
+}
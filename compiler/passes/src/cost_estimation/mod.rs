@@ -0,0 +1,42 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Estimates the number of constraints that each function of a program is synthesized into,
+//! from static weights of its operations, so that implementations can be compared without running the prover.
+
+pub mod cost_estimate;
+pub use cost_estimate::*;
+
+pub mod cost_estimator;
+pub use cost_estimator::*;
+
+use crate::{Pass, SymbolTable};
+
+use leo_ast::{Ast, ProgramVisitor};
+
+impl<'a> Pass for CostEstimator<'a> {
+    type Input = (&'a Ast, &'a SymbolTable);
+    type Output = Vec<CostEstimate>;
+
+    fn do_pass((ast, symbol_table): Self::Input) -> Self::Output {
+        let mut visitor = CostEstimator::new(symbol_table);
+        visitor.visit_program(ast.as_repr());
+        // Called functions are estimated first, so the estimates are sorted back into the order of the functions.
+        let mut estimates: Vec<_> = visitor.estimates.into_values().collect();
+        estimates.sort_by_key(|estimate| estimate.span.lo);
+        estimates
+    }
+}
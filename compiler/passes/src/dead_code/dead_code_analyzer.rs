@@ -0,0 +1,281 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CallGraph, CallNode, UnusedCode, UnusedCodeKind};
+
+use leo_ast::*;
+use leo_interpreter::assertion_always_fails;
+use leo_span::{Span, Symbol};
+
+/// Finds code that can never be executed.
+///
+/// This reports functions that are not reachable from a transition or a test, branches whose
+/// condition is a constant that never selects them, and statements that follow a
+/// `return` or an assertion that always fails.
+#[derive(Default)]
+pub struct DeadCodeAnalyzer<'a> {
+    /// The unused code found so far.
+    pub(crate) unused: Vec<UnusedCode>,
+    /// The calls between the functions of the current program scope.
+    call_graph: CallGraph,
+    /// The name of the function being visited.
+    function: Option<Symbol>,
+    /// The functions of the current program scope.
+    functions: Vec<&'a Function>,
+}
+
+impl<'a> DeadCodeAnalyzer<'a> {
+    /// Records a range of unused code.
+    fn report(&mut self, span: Span, kind: UnusedCodeKind) {
+        self.unused.push(UnusedCode { span, kind });
+    }
+
+    /// Returns `true` if control never continues past the statement.
+    fn diverges(statement: &Statement) -> bool {
+        match statement {
+            Statement::Return(_) => true,
+            Statement::Console(console) => assertion_always_fails(&console.function),
+            _ => false,
+        }
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for DeadCodeAnalyzer<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_call(&mut self, input: &'a CallExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        // Only calls to functions in the same program can make them reachable.
+        if let (Some(caller), Expression::Identifier(function), None) =
+            (self.function, &*input.function, &input.external)
+        {
+            self.call_graph.add_call(
+                CallNode::Function(caller),
+                CallNode::Function(function.name),
+                input.span,
+            );
+        }
+        input.arguments.iter().for_each(|argument| {
+            self.visit_expression(argument, additional);
+        });
+    }
+}
+
+impl<'a> StatementVisitor<'a> for DeadCodeAnalyzer<'a> {
+    fn visit_block(&mut self, input: &'a Block) {
+        let mut statements = input.statements.iter();
+        for statement in statements.by_ref() {
+            self.visit_statement(statement);
+            if Self::diverges(statement) {
+                break;
+            }
+        }
+
+        // Everything after a diverging statement is reported as a single range.
+        let rest: Vec<_> = statements.collect();
+        if let (Some(first), Some(last)) = (rest.first(), rest.last()) {
+            self.report(first.span() + last.span(), UnusedCodeKind::Statements);
+        }
+    }
+
+    fn visit_conditional(&mut self, input: &'a ConditionalStatement) {
+        self.visit_expression(&input.condition, &());
+        match &input.condition {
+            Expression::Literal(Literal::Boolean(false, ..)) => {
+                self.report(input.then.span, UnusedCodeKind::Branch);
+                if let Some(otherwise) = &input.otherwise {
+                    self.visit_statement(otherwise);
+                }
+            }
+            Expression::Literal(Literal::Boolean(true, ..)) => {
+                self.visit_block(&input.then);
+                if let Some(otherwise) = &input.otherwise {
+                    self.report(otherwise.span(), UnusedCodeKind::Branch);
+                }
+            }
+            _ => {
+                self.visit_block(&input.then);
+                if let Some(otherwise) = &input.otherwise {
+                    self.visit_statement(otherwise);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for DeadCodeAnalyzer<'a> {
+    fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        self.call_graph = CallGraph::default();
+        self.functions.clear();
+        input
+            .functions
+            .values()
+            .for_each(|function| self.visit_function(function));
+
+        // Walk the call graph from the transitions, which are the program's entry points, and from the tests.
+        let reachable = self.call_graph.reachable_from(
+            self.functions
+                .iter()
+                .filter(|function| {
+                    matches!(function.call_type, CallType::Transition | CallType::AsyncTransition) || function.is_test()
+                })
+                .map(|function| CallNode::Function(function.name())),
+        );
+
+        for function in std::mem::take(&mut self.functions) {
+            if !reachable.contains(&CallNode::Function(function.name())) {
+                self.report(function.span, UnusedCodeKind::Function);
+            }
+        }
+    }
+
+    fn visit_import(&mut self, _input: &'a Program) {
+        // Imported programs live in other files, so their ranges are not reported.
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        self.function = Some(input.name());
+        self.call_graph.add_function(input.name(), input.call_type);
+        self.visit_block(&input.block);
+        if let Some(finalize) = &input.finalize {
+            self.visit_block(&finalize.block);
+        }
+        self.functions.push(input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pass;
+
+    use leo_errors::emitter::Handler;
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    /// Returns the kind and source text of each range of unused code in `source`.
+    fn dead_code(source: &str) -> Vec<(UnusedCodeKind, String)> {
+        create_session_if_not_set_then(|_| {
+            let ast = leo_parser::parse_ast(&Handler::default(), source, Default::default()).unwrap();
+            DeadCodeAnalyzer::do_pass(&ast)
+                .into_iter()
+                .map(|unused| {
+                    let range = unused.span.lo.0 as usize..unused.span.hi.0 as usize;
+                    (unused.kind, source[range].to_string())
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn functions_unreachable_from_transitions_and_tests() {
+        let source = "program test.aleo {
+    function used(a: u8) -> u8 {
+        return a;
+    }
+
+    function unused(a: u8) -> u8 {
+        return used(a);
+    }
+
+    function tested(a: u8) -> u8 {
+        return a;
+    }
+
+    transition main(a: u8) -> u8 {
+        return used(a);
+    }
+
+    @test
+    transition test_tested(a: u8) -> u8 {
+        return tested(a);
+    }
+}
+";
+        let unused = dead_code(source);
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].0, UnusedCodeKind::Function);
+        assert!(unused[0].1.starts_with("function unused"));
+    }
+
+    #[test]
+    fn functions_called_by_async_transitions() {
+        let source = "program test.aleo {
+    mapping counts: u8 => u8;
+
+    async transition main(a: u8) -> Future {
+        return finalize_main(a);
+    }
+
+    async function finalize_main(a: u8) {
+        increment(counts, a, 1u8);
+    }
+}
+";
+        assert_eq!(dead_code(source), Vec::new());
+    }
+
+    #[test]
+    fn branches_with_constant_conditions() {
+        let source = "program test.aleo {
+    transition main(a: u8) -> u8 {
+        if false {
+            return 0u8;
+        }
+        if true {
+            a = a + 1u8;
+        } else {
+            a = 0u8;
+        }
+        return a;
+    }
+}
+";
+        assert_eq!(
+            dead_code(source),
+            vec![
+                (
+                    UnusedCodeKind::Branch,
+                    "{\n            return 0u8;\n        }".to_string()
+                ),
+                (UnusedCodeKind::Branch, "{\n            a = 0u8;\n        }".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn statements_after_diverging_statements() {
+        // The span of a statement ends before its semicolon.
+        let source = "program test.aleo {
+    transition main(a: u8) -> u8 {
+        if a == 0u8 {
+            console.assert(false);
+            a = 1u8;
+        }
+        return a;
+        a = 2u8;
+        return a;
+    }
+}
+";
+        assert_eq!(
+            dead_code(source),
+            vec![
+                (UnusedCodeKind::Statements, "a = 1u8".to_string()),
+                (UnusedCodeKind::Statements, "a = 2u8;\n        return a".to_string()),
+            ]
+        );
+    }
+}
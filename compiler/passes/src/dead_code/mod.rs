@@ -0,0 +1,40 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Reachability analysis whose results are reported as source ranges,
+//! so that editors can render code that will never run as dimmed.
+
+pub mod dead_code_analyzer;
+pub use dead_code_analyzer::*;
+
+pub mod unused_code;
+pub use unused_code::*;
+
+use crate::Pass;
+
+use leo_ast::{Ast, ProgramVisitor};
+
+impl<'a> Pass for DeadCodeAnalyzer<'a> {
+    type Input = &'a Ast;
+    type Output = Vec<UnusedCode>;
+
+    fn do_pass(ast: Self::Input) -> Self::Output {
+        let mut visitor = DeadCodeAnalyzer::default();
+        visitor.visit_program(ast.as_repr());
+        visitor.unused.sort_by_key(|unused| unused.span.lo);
+        visitor.unused
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_span::Span;
+
+use std::fmt;
+
+/// The reason a range of code is reported as unused.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnusedCodeKind {
+    /// A function that is not reachable from any transition.
+    Function,
+    /// A branch of a conditional whose condition is a constant that never selects it.
+    Branch,
+    /// Statements that follow a `return` or an assertion that always fails.
+    Statements,
+}
+
+impl fmt::Display for UnusedCodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Function => write!(f, "unused function"),
+            Self::Branch => write!(f, "unreachable branch"),
+            Self::Statements => write!(f, "unreachable statements"),
+        }
+    }
+}
+
+/// A range of source code that can never be executed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UnusedCode {
+    /// The span of the unused code.
+    pub span: Span,
+    /// Why the code is unused.
+    pub kind: UnusedCodeKind,
+}
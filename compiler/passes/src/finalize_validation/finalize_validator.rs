@@ -0,0 +1,154 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::*;
+use leo_errors::{emitter::Handler, TypeCheckerError};
+use leo_span::Symbol;
+
+use indexmap::IndexSet;
+
+/// Checks that the finalize blocks only use operations that can be executed on chain.
+///
+/// Only one branch of a conditional or match statement in a finalize block is executed, so a branch cannot
+/// assign to the variables declared outside of it, nor return. Finalize blocks cannot call functions.
+pub struct FinalizeValidator<'a> {
+    /// The handler that errors are emitted to.
+    handler: &'a Handler,
+    /// A stack of the variables declared in each enclosing scope of the finalize block being visited.
+    scopes: Vec<IndexSet<Symbol>>,
+    /// The number of scopes outside of the innermost branch being visited, if any.
+    branch: Option<usize>,
+}
+
+impl<'a> FinalizeValidator<'a> {
+    /// Returns a new `FinalizeValidator` that emits errors to `handler`.
+    pub fn new(handler: &'a Handler) -> Self {
+        Self {
+            handler,
+            scopes: Vec::new(),
+            branch: None,
+        }
+    }
+
+    /// Visits `block` as a branch, which only the variables declared within it may be assigned from.
+    fn visit_branch(&mut self, block: &'a Block) {
+        let branch = self.branch.replace(self.scopes.len());
+        self.visit_block(block);
+        self.branch = branch;
+    }
+
+    /// Returns the variable that an assignment to `place` writes, e.g. `p` for `p.x`.
+    fn assigned_variable(place: &Expression) -> Option<&Identifier> {
+        match place {
+            Expression::Identifier(identifier) => Some(identifier),
+            Expression::Access(AccessExpression::Member(access)) => Self::assigned_variable(&access.inner),
+            Expression::Access(AccessExpression::Tuple(access)) => Self::assigned_variable(&access.tuple),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for FinalizeValidator<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_call(&mut self, input: &'a CallExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        let function = match &input.external {
+            Some(program) => format!("{}.leo/{}", program, input.function),
+            None => input.function.to_string(),
+        };
+        self.handler
+            .emit_err(TypeCheckerError::call_in_finalize(function, input.span));
+        input.arguments.iter().for_each(|argument| {
+            self.visit_expression(argument, additional);
+        });
+    }
+}
+
+impl<'a> StatementVisitor<'a> for FinalizeValidator<'a> {
+    fn visit_assign(&mut self, input: &'a AssignStatement) {
+        if let (Some(variable), Some(branch)) = (Self::assigned_variable(&input.place), self.branch) {
+            // A variable that is not found is declared by a pattern, within the branch of its match arm.
+            let scope = self.scopes.iter().rposition(|scope| scope.contains(&variable.name));
+            if matches!(scope, Some(scope) if scope < branch) {
+                self.handler
+                    .emit_err(TypeCheckerError::assign_outside_branch_in_finalize(
+                        variable, input.span,
+                    ));
+            }
+        }
+        self.visit_expression(&input.value, &());
+    }
+
+    fn visit_block(&mut self, input: &'a Block) {
+        self.scopes.push(IndexSet::new());
+        input
+            .statements
+            .iter()
+            .for_each(|statement| self.visit_statement(statement));
+        self.scopes.pop();
+    }
+
+    fn visit_conditional(&mut self, input: &'a ConditionalStatement) {
+        self.visit_expression(&input.condition, &());
+        self.visit_branch(&input.then);
+        match input.otherwise.as_deref() {
+            Some(Statement::Block(block)) => self.visit_branch(block),
+            Some(statement) => self.visit_statement(statement),
+            None => {}
+        }
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        self.visit_expression(&input.value, &());
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(input.variable_name.name);
+        }
+    }
+
+    fn visit_iteration(&mut self, input: &'a IterationStatement) {
+        self.visit_expression(&input.start, &());
+        self.visit_expression(&input.stop, &());
+        self.scopes.push(IndexSet::from([input.variable.name]));
+        self.visit_block(&input.block);
+        self.scopes.pop();
+    }
+
+    fn visit_match(&mut self, input: &'a MatchStatement) {
+        self.visit_expression(&input.expression, &());
+        input.arms.iter().for_each(|arm| self.visit_branch(&arm.block));
+    }
+
+    fn visit_return(&mut self, input: &'a ReturnStatement) {
+        if self.branch.is_some() {
+            self.handler
+                .emit_err(TypeCheckerError::return_in_finalize_branch(input.span));
+        }
+        self.visit_expression(&input.expression, &());
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for FinalizeValidator<'a> {
+    // Imported programs are validated on their own.
+    fn visit_import(&mut self, _input: &'a Program) {}
+
+    fn visit_function(&mut self, input: &'a Function) {
+        if let Some(finalize) = &input.finalize {
+            self.scopes = vec![finalize.input.iter().map(|input| input.identifier().name).collect()];
+            self.visit_block(&finalize.block);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The finalize validation pass checks that the finalize blocks of a type checked program can be executed on chain.
+//!
+//! The conditionals and match statements of a finalize block are not flattened like those of a function,
+//! but compiled into `branch` and `position` commands, so that only the branch that is taken is executed.
+//! Its assignments, updates of mappings, and assertions are therefore only executed if the branch is taken,
+//! and an assertion that always fails aborts the finalize block when its branch is taken.
+//! Since the variables assigned in a branch are not defined outside of it, the pass rejects assignments from a branch
+//! to a variable declared outside of it, as well as returns from a branch and calls to functions.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! finalize withdraw(addr: address, amount: u64) {
+//!     let balance: u64 = Mapping::get_or_use(balances, addr, 0u64);
+//!     if balance < amount {
+//!         console.assert(false);
+//!     }
+//!     Mapping::set(balances, addr, balance - amount);
+//! }
+//! ```
+//!
+//! The pass accepts it, while it rejects the following code, which assigns to `balance` from a branch.
+//! ```leo
+//! finalize withdraw(addr: address, amount: u64) {
+//!     let balance: u64 = 0u64;
+//!     if Mapping::contains(balances, addr) {
+//!         balance = Mapping::get(balances, addr);
+//!     }
+//!     Mapping::set(balances, addr, balance - amount);
+//! }
+//! ```
+
+pub mod finalize_validator;
+pub use finalize_validator::*;
+
+use crate::Pass;
+
+use leo_ast::{Ast, ProgramVisitor};
+use leo_errors::{emitter::Handler, Result};
+
+impl<'a> Pass for FinalizeValidator<'a> {
+    type Input = (&'a Ast, &'a Handler);
+    type Output = Result<()>;
+
+    fn do_pass((ast, handler): Self::Input) -> Self::Output {
+        let mut visitor = FinalizeValidator::new(handler);
+        visitor.visit_program(ast.as_repr());
+        handler.last_err()
+    }
+}
@@ -68,6 +68,7 @@ impl ExpressionReconstructor for Flattener<'_> {
                                 if_true: Box::new(if_true),
                                 if_false: Box::new(if_false),
                                 span: input.span,
+                                id: self.assigner.node_builder.next_id(),
                             });
 
                             // Accumulate any statements generated.
@@ -82,6 +83,7 @@ impl ExpressionReconstructor for Flattener<'_> {
                         })
                         .collect(),
                     span: Default::default(),
+                    id: self.assigner.node_builder.next_id(),
                 });
                 (tuple, statements)
             }
@@ -116,13 +118,16 @@ impl ExpressionReconstructor for Flattener<'_> {
                                         inner: Box::new(Expression::Access(AccessExpression::Member(first.clone()))),
                                         name: *identifier,
                                         span: Default::default(),
+                                        id: self.assigner.node_builder.next_id(),
                                     }))),
                                     if_false: Box::new(Expression::Access(AccessExpression::Member(MemberAccess {
                                         inner: Box::new(Expression::Access(AccessExpression::Member(second.clone()))),
                                         name: *identifier,
                                         span: Default::default(),
+                                        id: self.assigner.node_builder.next_id(),
                                     }))),
                                     span: Default::default(),
+                                    id: self.assigner.node_builder.next_id(),
                                 });
 
                                 // Accumulate any statements generated.
@@ -143,6 +148,7 @@ impl ExpressionReconstructor for Flattener<'_> {
                             name: first_member_struct.identifier,
                             members,
                             span: Default::default(),
+                            id: self.assigner.node_builder.next_id(),
                         });
 
                         // Accumulate any statements generated.
@@ -176,6 +182,7 @@ impl ExpressionReconstructor for Flattener<'_> {
                                 if_true: Box::new(if_true),
                                 if_false: Box::new(if_false),
                                 span: input.span,
+                                id: input.id,
                             }));
 
                         // Accumulate the new assignment statement.
@@ -212,13 +219,16 @@ impl ExpressionReconstructor for Flattener<'_> {
                                 inner: Box::new(Expression::Identifier(first)),
                                 name: *identifier,
                                 span: Default::default(),
+                                id: self.assigner.node_builder.next_id(),
                             }))),
                             if_false: Box::new(Expression::Access(AccessExpression::Member(MemberAccess {
                                 inner: Box::new(Expression::Identifier(second)),
                                 name: *identifier,
                                 span: Default::default(),
+                                id: self.assigner.node_builder.next_id(),
                             }))),
                             span: Default::default(),
+                            id: self.assigner.node_builder.next_id(),
                         });
 
                         // Accumulate any statements generated.
@@ -239,6 +249,7 @@ impl ExpressionReconstructor for Flattener<'_> {
                     name: first_struct.identifier,
                     members,
                     span: Default::default(),
+                    id: self.assigner.node_builder.next_id(),
                 });
 
                 // Accumulate any statements generated.
@@ -271,6 +282,7 @@ impl ExpressionReconstructor for Flattener<'_> {
                         if_true: Box::new(if_true),
                         if_false: Box::new(if_false),
                         span: input.span,
+                        id: input.id,
                     }));
 
                 // Accumulate the new assignment statement.
@@ -36,7 +36,9 @@ impl ProgramReconstructor for Flattener<'_> {
             }
 
             // Flatten the finalize block.
+            self.in_finalize = true;
             let mut block = self.reconstruct_block(finalize.block).0;
+            self.in_finalize = false;
 
             // Get all of the guards and return expression.
             let returns = self.clear_early_returns();
@@ -52,6 +54,7 @@ impl ProgramReconstructor for Flattener<'_> {
                 block.statements.push(Statement::Return(ReturnStatement {
                     expression,
                     span: Default::default(),
+                    id: self.assigner.node_builder.next_id(),
                 }));
             }
 
@@ -93,6 +96,7 @@ impl ProgramReconstructor for Flattener<'_> {
             block.statements.push(Statement::Return(ReturnStatement {
                 expression,
                 span: Default::default(),
+                id: self.assigner.node_builder.next_id(),
             }));
         }
 
@@ -126,10 +130,12 @@ impl ProgramReconstructor for Flattener<'_> {
             block.statements.push(Statement::Finalize(FinalizeStatement {
                 arguments,
                 span: Default::default(),
+                id: self.assigner.node_builder.next_id(),
             }));
         }
 
         Function {
+            documentation: function.documentation,
             annotations: function.annotations,
             call_type: function.call_type,
             identifier: function.identifier,
@@ -18,7 +18,7 @@ use crate::Flattener;
 
 use leo_ast::{
     AssignStatement, BinaryExpression, BinaryOperation, Block, ConditionalStatement, DefinitionStatement, Expression,
-    ExpressionReconstructor, FinalizeStatement, IterationStatement, Node, ReturnStatement, Statement,
+    ExpressionReconstructor, FinalizeStatement, IterationStatement, MatchStatement, Node, ReturnStatement, Statement,
     StatementReconstructor, UnaryExpression, UnaryOperation,
 };
 
@@ -48,6 +48,7 @@ impl StatementReconstructor for Flattener<'_> {
                 place: Expression::Identifier(lhs),
                 value,
                 span: assign.span,
+                id: assign.id,
             })),
             statements,
         )
@@ -69,6 +70,7 @@ impl StatementReconstructor for Flattener<'_> {
         (
             Block {
                 span: block.span,
+                id: block.id,
                 statements,
             },
             Default::default(),
@@ -76,7 +78,26 @@ impl StatementReconstructor for Flattener<'_> {
     }
 
     /// Flatten a conditional statement into a list of statements.
+    /// The conditionals of a finalize block are kept, since they are compiled into branches.
     fn reconstruct_conditional(&mut self, conditional: ConditionalStatement) -> (Statement, Self::AdditionalOutput) {
+        if self.in_finalize {
+            let then = self.reconstruct_block(conditional.then).0;
+            let otherwise = conditional.otherwise.map(|statement| match *statement {
+                Statement::Block(block) => Box::new(Statement::Block(self.reconstruct_block(block).0)),
+                _ => unreachable!("SSA guarantees that the `otherwise` is always a `Block`"),
+            });
+            return (
+                Statement::Conditional(ConditionalStatement {
+                    condition: conditional.condition,
+                    then,
+                    otherwise,
+                    span: conditional.span,
+                    id: conditional.id,
+                }),
+                Default::default(),
+            );
+        }
+
         let mut statements = Vec::with_capacity(conditional.then.statements.len());
 
         // Add condition to the condition stack.
@@ -95,6 +116,7 @@ impl StatementReconstructor for Flattener<'_> {
                 op: UnaryOperation::Not,
                 receiver: Box::new(conditional.condition.clone()),
                 span: conditional.condition.span(),
+                id: self.assigner.node_builder.next_id(),
             }));
 
             // Reconstruct the otherwise-block and accumulate it constituent statements.
@@ -107,7 +129,10 @@ impl StatementReconstructor for Flattener<'_> {
             self.condition_stack.pop();
         };
 
-        (Statement::dummy(Default::default()), statements)
+        (
+            Statement::dummy(Default::default(), self.assigner.node_builder.next_id()),
+            statements,
+        )
     }
 
     /// Static single assignment converts definition statements into assignment statements.
@@ -129,6 +154,7 @@ impl StatementReconstructor for Flattener<'_> {
                         left: Box::new(acc),
                         right: Box::new(condition),
                         span: Default::default(),
+                        id: self.assigner.node_builder.next_id(),
                     })
                 }))
             }
@@ -141,7 +167,10 @@ impl StatementReconstructor for Flattener<'_> {
             self.finalizes.get_mut(i).unwrap().push((guard.clone(), argument));
         }
 
-        (Statement::dummy(Default::default()), Default::default())
+        (
+            Statement::dummy(Default::default(), self.assigner.node_builder.next_id()),
+            Default::default(),
+        )
     }
 
     // TODO: Error message requesting the user to enable loop-unrolling.
@@ -149,6 +178,10 @@ impl StatementReconstructor for Flattener<'_> {
         unreachable!("`IterationStatement`s should not be in the AST at this phase of compilation.");
     }
 
+    fn reconstruct_match(&mut self, _input: MatchStatement) -> (Statement, Self::AdditionalOutput) {
+        unreachable!("`MatchStatement`s should not be in the AST at this phase of compilation.");
+    }
+
     /// Transforms a return statement into an empty block statement.
     /// Stores the arguments to the return statement, which are later folded into a single return statement at the end of the function.
     fn reconstruct_return(&mut self, input: ReturnStatement) -> (Statement, Self::AdditionalOutput) {
@@ -163,6 +196,7 @@ impl StatementReconstructor for Flattener<'_> {
                         left: Box::new(acc),
                         right: Box::new(condition),
                         span: Default::default(),
+                        id: self.assigner.node_builder.next_id(),
                     })
                 }))
             }
@@ -171,6 +205,9 @@ impl StatementReconstructor for Flattener<'_> {
         // Add it to the list of return statements.
         self.returns.push((guard, input.expression));
 
-        (Statement::dummy(Default::default()), Default::default())
+        (
+            Statement::dummy(Default::default(), self.assigner.node_builder.next_id()),
+            Default::default(),
+        )
     }
 }
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Assigner, SymbolTable};
+use crate::{Assigner, SymbolTable, TypeTable};
 
 use leo_ast::{
     AccessExpression, Expression, ExpressionReconstructor, Identifier, Member, Statement, TernaryExpression, Type,
@@ -27,6 +27,8 @@ pub struct Flattener<'a> {
     /// The symbol table associated with the program.
     /// This table is used to lookup struct definitions, when they are folded.
     pub(crate) symbol_table: &'a SymbolTable,
+    /// The types of the expressions recorded by the type checker.
+    pub(crate) type_table: &'a TypeTable,
     /// An struct used to construct (unique) assignment statements.
     pub(crate) assigner: Assigner,
     /// The set of variables that are structs.
@@ -43,17 +45,21 @@ pub struct Flattener<'a> {
     /// Note that finalizes are inserted in the order they are encountered during a pre-order traversal of the AST.
     /// Note that type checking guarantees that there is at most one finalize in a basic block.
     pub(crate) finalizes: Vec<Vec<(Option<Expression>, Expression)>>,
+    /// Whether or not we are currently traversing a finalize block, whose conditionals are kept as branches.
+    pub(crate) in_finalize: bool,
 }
 
 impl<'a> Flattener<'a> {
-    pub(crate) fn new(symbol_table: &'a SymbolTable, assigner: Assigner) -> Self {
+    pub(crate) fn new(symbol_table: &'a SymbolTable, type_table: &'a TypeTable, assigner: Assigner) -> Self {
         Self {
             symbol_table,
+            type_table,
             assigner,
             structs: IndexMap::new(),
             condition_stack: Vec::new(),
             returns: Vec::new(),
             finalizes: Vec::new(),
+            in_finalize: false,
         }
     }
 
@@ -85,12 +91,14 @@ impl<'a> Flattener<'a> {
             let place = Identifier {
                 name: self.assigner.unique_symbol(prefix),
                 span: Default::default(),
+                id: self.assigner.node_builder.next_id(),
             };
             let (value, stmts) = self.reconstruct_ternary(TernaryExpression {
                 condition: Box::new(guard),
                 if_true: Box::new(if_true),
                 if_false: Box::new(if_false),
                 span: Default::default(),
+                id: self.assigner.node_builder.next_id(),
             });
             statements.extend(stmts);
 
@@ -119,7 +127,16 @@ impl<'a> Flattener<'a> {
     }
 
     /// Looks up the name of the struct associated with an identifier or access expression, if it exists.
+    /// The type recorded by the type checker is used if there is one, since nodes created by earlier passes have none.
     pub(crate) fn lookup_struct_symbol(&self, expression: &Expression) -> Option<Symbol> {
+        if let Some(type_) = self.type_table.get(&expression.id()) {
+            return match type_ {
+                Type::Identifier(identifier) if self.symbol_table.lookup_struct(identifier.name).is_some() => {
+                    Some(identifier.name)
+                }
+                _ => None,
+            };
+        }
         match expression {
             Expression::Identifier(identifier) => self.structs.get(&identifier.name).copied(),
             Expression::Access(AccessExpression::Member(access)) => {
@@ -152,6 +169,15 @@ impl<'a> Flattener<'a> {
                 // Note that this unwrap is safe because we just checked that the key exists.
                 self.structs.insert(lhs.name, *self.structs.get(&rhs.name).unwrap());
             }
+            // If the rhs of the assignment is an access expression that is a struct, e.g. a member that is a struct,
+            // add it to `self.structs`. Static single assignment stores each access in a variable like this.
+            Expression::Access(_) => {
+                if let Some(Type::Identifier(identifier)) = self.type_table.get(&rhs.id()) {
+                    if self.symbol_table.lookup_struct(identifier.name).is_some() {
+                        self.structs.insert(lhs.name, identifier.name);
+                    }
+                }
+            }
             // Otherwise, do nothing.
             _ => (),
         }
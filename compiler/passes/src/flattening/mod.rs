@@ -16,6 +16,7 @@
 
 //! The flattening pass traverses the AST after the SSA pass and converts into a sequential code.
 //! The pass flattens `ConditionalStatement`s into a sequence of `AssignStatement`s.
+//! The `ConditionalStatement`s of finalize blocks are kept, since they are compiled into `branch` and `position` commands.
 //! The pass rewrites `ReturnStatement`s into `AssignStatement`s and consolidates the returned values as a single `ReturnStatement` at the end of the function.
 //! The pass rewrites ternary expressions over composite data types, into ternary expressions over the individual fields of the composite data type, followed by an expression constructing the composite data type.
 //!
@@ -59,17 +60,17 @@ mod flatten_statement;
 pub mod flattener;
 pub use flattener::*;
 
-use crate::{Assigner, Pass, SymbolTable};
+use crate::{Assigner, Pass, SymbolTable, TypeTable};
 
 use leo_ast::{Ast, ProgramReconstructor};
 use leo_errors::Result;
 
 impl<'a> Pass for Flattener<'a> {
-    type Input = (Ast, &'a SymbolTable, Assigner);
+    type Input = (Ast, &'a SymbolTable, &'a TypeTable, Assigner);
     type Output = Result<Ast>;
 
-    fn do_pass((ast, st, assigner): Self::Input) -> Self::Output {
-        let mut reconstructor = Flattener::new(st, assigner);
+    fn do_pass((ast, st, type_table, assigner): Self::Input) -> Self::Output {
+        let mut reconstructor = Flattener::new(st, type_table, assigner);
         let program = reconstructor.reconstruct_program(ast.into_repr());
 
         Ok(Ast::new(program))
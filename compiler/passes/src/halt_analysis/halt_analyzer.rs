@@ -0,0 +1,345 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{HaltPoint, HaltReason, SymbolTable};
+
+use leo_ast::*;
+use leo_core::{CoreInstruction, MappingOperation};
+use leo_span::{sym, Span, Symbol};
+
+use indexmap::IndexMap;
+
+/// Finds the operations that can halt the program at runtime.
+///
+/// This must be run on a type checked AST, so that the types of the operands, which decide whether an
+/// operation can halt, are known. Since the AST does not record them, they are inferred again here.
+pub struct HaltAnalyzer<'a> {
+    /// The operations found so far.
+    pub(crate) halt_points: Vec<HaltPoint>,
+    /// The symbol table, which provides the types of struct members and function outputs.
+    symbol_table: &'a SymbolTable,
+    /// The types of the mapping values in the current program scope.
+    mappings: IndexMap<Symbol, Type>,
+    /// The types of the variables in the function being visited.
+    variables: IndexMap<Symbol, Type>,
+    /// The function being visited.
+    function: Symbol,
+}
+
+impl<'a> HaltAnalyzer<'a> {
+    /// Returns a new halt analyzer.
+    pub fn new(symbol_table: &'a SymbolTable) -> Self {
+        Self {
+            halt_points: Vec::new(),
+            symbol_table,
+            mappings: IndexMap::new(),
+            variables: IndexMap::new(),
+            function: Symbol::intern(""),
+        }
+    }
+
+    /// Records an operation that can halt.
+    fn report(&mut self, operation: String, reason: HaltReason, span: Span) {
+        self.halt_points.push(HaltPoint {
+            function: self.function,
+            operation,
+            reason,
+            span,
+        });
+    }
+
+    /// Declares the inputs of a function or finalize block as variables.
+    fn declare_inputs(&mut self, input: &[Input]) {
+        for input in input {
+            self.variables.insert(input.identifier().name, input.type_());
+        }
+    }
+
+    /// Reports a `Mapping::get` as a halt point, and returns the type of the result of a mapping operation.
+    fn mapping_operation(&mut self, operation: MappingOperation, function: &AssociatedFunction) -> Option<Type> {
+        let value_type = match function.args.first() {
+            Some(Expression::Identifier(mapping)) => self.mappings.get(&mapping.name).cloned(),
+            _ => None,
+        };
+        match operation {
+            MappingOperation::Get => {
+                self.report("`Mapping::get`".to_string(), HaltReason::MissingKey, function.span);
+                value_type
+            }
+            MappingOperation::GetOrUse => value_type,
+            MappingOperation::Contains => Some(Type::Boolean),
+            MappingOperation::Set | MappingOperation::Remove => Some(Type::Unit),
+        }
+    }
+
+    /// Returns why a binary operation on operands of the given type can halt, if it can.
+    fn binary_halt_reason(op: BinaryOperation, type_: &Type) -> Option<HaltReason> {
+        match (op, type_) {
+            (
+                BinaryOperation::Add | BinaryOperation::Sub | BinaryOperation::Mul | BinaryOperation::Pow,
+                Type::Integer(_),
+            ) => Some(HaltReason::Overflow),
+            (
+                BinaryOperation::Div
+                | BinaryOperation::DivWrapped
+                | BinaryOperation::Rem
+                | BinaryOperation::RemWrapped
+                | BinaryOperation::Mod,
+                Type::Integer(_),
+            ) => Some(HaltReason::DivisionByZero),
+            (BinaryOperation::Div, Type::Field) => Some(HaltReason::DivisionByZero),
+            (BinaryOperation::Shl | BinaryOperation::Shr, Type::Integer(_)) => Some(HaltReason::ShiftOutOfRange),
+            _ => None,
+        }
+    }
+
+    /// Returns why a unary operation on an operand of the given type can halt, if it can.
+    fn unary_halt_reason(op: UnaryOperation, type_: &Type) -> Option<HaltReason> {
+        match (op, type_) {
+            (UnaryOperation::Abs | UnaryOperation::Negate, Type::Integer(integer_type)) if integer_type.is_signed() => {
+                Some(HaltReason::Overflow)
+            }
+            (UnaryOperation::Inverse, Type::Field) => Some(HaltReason::NoInverse),
+            (UnaryOperation::SquareRoot, Type::Field) => Some(HaltReason::NoSquareRoot),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for HaltAnalyzer<'a> {
+    type AdditionalInput = ();
+    /// The type of the expression, if it is known.
+    type Output = Option<Type>;
+
+    fn visit_access(&mut self, input: &'a AccessExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        match input {
+            AccessExpression::AssociatedConstant(constant) => Some(constant.ty.clone()),
+            AccessExpression::AssociatedFunction(function) => {
+                function.args.iter().for_each(|arg| {
+                    self.visit_expression(arg, additional);
+                });
+                let module = match &function.ty {
+                    Type::Identifier(module) => module.name,
+                    _ => return None,
+                };
+                match MappingOperation::from_symbols(module, function.name.name) {
+                    Some(operation) => self.mapping_operation(operation, function),
+                    None => CoreInstruction::from_symbols(module, function.name.name)
+                        .map(|instruction| instruction.return_type()),
+                }
+            }
+            AccessExpression::Member(member) => match &*member.inner {
+                Expression::Identifier(identifier) if identifier.name == sym::SelfLower => match member.name.name {
+                    sym::caller => Some(Type::Address),
+                    _ => None,
+                },
+                _ => match self.visit_expression(&member.inner, additional)? {
+                    Type::Identifier(struct_) => self
+                        .symbol_table
+                        .lookup_struct(struct_.name)?
+                        .members
+                        .iter()
+                        .find(|struct_member| struct_member.identifier.name == member.name.name)
+                        .map(|struct_member| struct_member.type_.clone()),
+                    _ => None,
+                },
+            },
+            AccessExpression::Tuple(access) => match self.visit_expression(&access.tuple, additional)? {
+                Type::Tuple(tuple) => tuple.get(access.index.to_usize()).cloned(),
+                _ => None,
+            },
+        }
+    }
+
+    fn visit_binary(&mut self, input: &'a BinaryExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        let left = self.visit_expression(&input.left, additional);
+        let right = self.visit_expression(&input.right, additional);
+
+        if let Some(type_) = &left {
+            if let Some(reason) = Self::binary_halt_reason(input.op, type_) {
+                self.report(format!("`{}` on {}", input.op, type_), reason, input.span);
+            }
+        }
+
+        match input.op {
+            BinaryOperation::And
+            | BinaryOperation::Or
+            | BinaryOperation::Nand
+            | BinaryOperation::Nor
+            | BinaryOperation::Eq
+            | BinaryOperation::Neq
+            | BinaryOperation::Lt
+            | BinaryOperation::Lte
+            | BinaryOperation::Gt
+            | BinaryOperation::Gte => Some(Type::Boolean),
+            // A scalar can multiply a group element from either side.
+            BinaryOperation::Mul if matches!(right, Some(Type::Group)) => right,
+            _ => left.or(right),
+        }
+    }
+
+    fn visit_call(&mut self, input: &'a CallExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        input.arguments.iter().for_each(|argument| {
+            self.visit_expression(argument, additional);
+        });
+        match &*input.function {
+            Expression::Identifier(function) => self
+                .symbol_table
+                .lookup_fn_symbol(function.name)
+                .map(|function| function.output_type.clone()),
+            _ => None,
+        }
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        input.members.iter().for_each(|member| {
+            if let Some(expression) = &member.expression {
+                self.visit_expression(expression, additional);
+            }
+        });
+        Some(Type::Identifier(input.name))
+    }
+
+    fn visit_identifier(&mut self, input: &'a Identifier, _additional: &Self::AdditionalInput) -> Self::Output {
+        self.variables.get(&input.name).cloned()
+    }
+
+    fn visit_literal(&mut self, input: &'a Literal, _additional: &Self::AdditionalInput) -> Self::Output {
+        Some(match input {
+            Literal::Address(..) => Type::Address,
+            Literal::Boolean(..) => Type::Boolean,
+            Literal::Field(..) => Type::Field,
+            Literal::Group(..) => Type::Group,
+            Literal::Integer(integer_type, ..) => Type::Integer(*integer_type),
+            Literal::Scalar(..) => Type::Scalar,
+            Literal::Signature(..) => Type::Signature,
+            Literal::String(..) => Type::String,
+        })
+    }
+
+    fn visit_ternary(&mut self, input: &'a TernaryExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        self.visit_expression(&input.condition, additional);
+        let if_true = self.visit_expression(&input.if_true, additional);
+        let if_false = self.visit_expression(&input.if_false, additional);
+        if_true.or(if_false)
+    }
+
+    fn visit_tuple(&mut self, input: &'a TupleExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        let elements: Vec<_> = input
+            .elements
+            .iter()
+            .map(|element| self.visit_expression(element, additional))
+            .collect();
+        elements
+            .into_iter()
+            .collect::<Option<_>>()
+            .map(|elements| Type::Tuple(Tuple(elements)))
+    }
+
+    fn visit_unary(&mut self, input: &'a UnaryExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        let receiver = self.visit_expression(&input.receiver, additional);
+
+        if let Some(type_) = &receiver {
+            if let Some(reason) = Self::unary_halt_reason(input.op, type_) {
+                self.report(format!("`{}` on {}", input.op.as_str(), type_), reason, input.span);
+            }
+        }
+
+        receiver
+    }
+}
+
+impl<'a> StatementVisitor<'a> for HaltAnalyzer<'a> {
+    fn visit_console(&mut self, input: &'a ConsoleStatement) {
+        let operation = match &input.function {
+            ConsoleFunction::Assert(expression, _) => {
+                self.visit_expression(expression, &());
+                "console.assert"
+            }
+            ConsoleFunction::AssertEq(left, right, _) => {
+                self.visit_expression(left, &());
+                self.visit_expression(right, &());
+                "console.assert_eq"
+            }
+            ConsoleFunction::AssertNeq(left, right, _) => {
+                self.visit_expression(left, &());
+                self.visit_expression(right, &());
+                "console.assert_neq"
+            }
+        };
+        self.report(format!("`{}`", operation), HaltReason::Assertion, input.span);
+    }
+
+    fn visit_decrement(&mut self, input: &'a DecrementStatement) {
+        self.visit_expression(&input.index, &());
+        self.visit_expression(&input.amount, &());
+        if let Some(type_ @ Type::Integer(_)) = self.mappings.get(&input.mapping.name).cloned() {
+            self.report(format!("`decrement` on {}", type_), HaltReason::Overflow, input.span);
+        }
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        self.visit_expression(&input.value, &());
+        self.variables.insert(input.variable_name.name, input.type_.clone());
+    }
+
+    fn visit_increment(&mut self, input: &'a IncrementStatement) {
+        self.visit_expression(&input.index, &());
+        self.visit_expression(&input.amount, &());
+        if let Some(type_ @ Type::Integer(_)) = self.mappings.get(&input.mapping.name).cloned() {
+            self.report(format!("`increment` on {}", type_), HaltReason::Overflow, input.span);
+        }
+    }
+
+    fn visit_iteration(&mut self, input: &'a IterationStatement) {
+        self.visit_expression(&input.start, &());
+        self.visit_expression(&input.stop, &());
+        self.variables.insert(input.variable.name, input.type_.clone());
+        self.visit_block(&input.block);
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for HaltAnalyzer<'a> {
+    // Imported programs are compiled, and reported, on their own.
+    fn visit_import(&mut self, _input: &'a Program) {}
+
+    fn visit_mapping(&mut self, input: &'a Mapping) {
+        self.mappings.insert(input.identifier.name, input.value_type.clone());
+    }
+
+    fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        self.mappings.clear();
+        input.mappings.values().for_each(|mapping| self.visit_mapping(mapping));
+        input
+            .functions
+            .values()
+            .for_each(|function| self.visit_function(function));
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        self.function = input.name();
+
+        self.variables.clear();
+        self.declare_inputs(&input.input);
+        self.visit_block(&input.block);
+
+        if let Some(finalize) = &input.finalize {
+            self.variables.clear();
+            self.declare_inputs(&finalize.input);
+            self.visit_block(&finalize.block);
+        }
+    }
+}
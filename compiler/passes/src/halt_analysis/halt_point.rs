@@ -0,0 +1,71 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_span::{Span, Symbol};
+
+use std::fmt;
+
+/// The reason an operation can halt the program at runtime.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HaltReason {
+    /// Checked integer arithmetic whose result does not fit in its type, e.g. `a + b` or `-a`.
+    Overflow,
+    /// A division or remainder by zero, or of the smallest signed integer by `-1`, e.g. `a / b`.
+    DivisionByZero,
+    /// A shift by at least the number of bits of its type, e.g. `a >> b`.
+    ShiftOutOfRange,
+    /// The inverse of a field element that is zero, e.g. `a.inv()`.
+    NoInverse,
+    /// The square root of a field element that is not a square, e.g. `a.square_root()`.
+    NoSquareRoot,
+    /// An assertion whose condition does not hold, e.g. `console.assert(a)`.
+    Assertion,
+    /// A read of a key that is not in the mapping, e.g. `Mapping::get(balances, addr)`.
+    MissingKey,
+}
+
+impl fmt::Display for HaltReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Overflow => write!(f, "halts on overflow"),
+            Self::DivisionByZero => write!(f, "halts on division by zero"),
+            Self::ShiftOutOfRange => write!(f, "halts on an out of range shift"),
+            Self::NoInverse => write!(f, "halts on zero"),
+            Self::NoSquareRoot => write!(f, "halts on a non-square"),
+            Self::Assertion => write!(f, "halts if the assertion fails"),
+            Self::MissingKey => write!(f, "halts if the key is not in the mapping"),
+        }
+    }
+}
+
+/// An operation that can halt the program at runtime.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HaltPoint {
+    /// The function containing the operation, or whose finalize block contains it.
+    pub function: Symbol,
+    /// A description of the operation, e.g. `` `+` on u8 ``.
+    pub operation: String,
+    /// Why the operation can halt.
+    pub reason: HaltReason,
+    /// The span of the operation.
+    pub span: Span,
+}
+
+impl fmt::Display for HaltPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.operation, self.reason)
+    }
+}
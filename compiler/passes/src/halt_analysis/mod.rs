@@ -0,0 +1,40 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Finds the operations of a program that can halt at runtime, such as checked arithmetic,
+//! division, and assertions, so that they can be reviewed before deployment.
+
+pub mod halt_analyzer;
+pub use halt_analyzer::*;
+
+pub mod halt_point;
+pub use halt_point::*;
+
+use crate::{Pass, SymbolTable};
+
+use leo_ast::{Ast, ProgramVisitor};
+
+impl<'a> Pass for HaltAnalyzer<'a> {
+    type Input = (&'a Ast, &'a SymbolTable);
+    type Output = Vec<HaltPoint>;
+
+    fn do_pass((ast, symbol_table): Self::Input) -> Self::Output {
+        let mut visitor = HaltAnalyzer::new(symbol_table);
+        visitor.visit_program(ast.as_repr());
+        visitor.halt_points.sort_by_key(|point| point.span.lo);
+        visitor.halt_points
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The implicit widening pass inserts a cast for each integer that the type checker implicitly widened.
+//!
+//! An integer is implicitly widened to a wider integer type where that type is expected, e.g. in a definition, an assignment,
+//! a call, or a return, and where it is an operand of a binary operation whose other operand has a wider type.
+//! Only conversions that keep every value are implicit: from an integer type to a wider integer type of the same signedness,
+//! and from an unsigned integer type to a wider signed integer type.
+//! The pass runs on the type checked AST, so that later passes and code generation see explicit casts.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! function sum(a: u8, b: u64) -> u64 {
+//!     return a + b;
+//! }
+//! ```
+//!
+//! The implicit widening pass produces the following code, which is generated as a `cast` instruction.
+//! ```leo
+//! function sum(a: u8, b: u64) -> u64 {
+//!     return (a as u64) + b;
+//! }
+//! ```
+
+pub mod widening_inserter;
+pub use widening_inserter::*;
+
+use crate::Pass;
+
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
+
+impl Pass for WideningInserter {
+    type Input = (Ast, ImplicitWidenings);
+    type Output = Ast;
+
+    fn do_pass((ast, widenings): Self::Input) -> Self::Output {
+        let mut reconstructor = WideningInserter::new(widenings, NodeBuilder::for_program(ast.as_repr()));
+        Ast::new(reconstructor.reconstruct_program(ast.into_repr()))
+    }
+}
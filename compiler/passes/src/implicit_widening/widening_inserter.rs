@@ -0,0 +1,120 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    CastExpression, Expression, ExpressionReconstructor, IntegerType, NodeBuilder, NodeID, ProgramReconstructor,
+    StatementReconstructor, StructExpression, StructVariableInitializer,
+};
+use leo_span::Span;
+
+use indexmap::IndexMap;
+
+/// An integer that the type checker implicitly widened to a wider integer type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImplicitWidening {
+    /// The type of the integer.
+    pub from: IntegerType,
+    /// The type it is widened to.
+    pub to: IntegerType,
+    /// The span of the widened expression.
+    pub span: Span,
+}
+
+/// The implicitly widened integers of a program, keyed by the id of their expression.
+pub type ImplicitWidenings = IndexMap<NodeID, ImplicitWidening>;
+
+pub struct WideningInserter {
+    /// The widenings that remain to be inserted.
+    pub(crate) widenings: ImplicitWidenings,
+    /// Allocates the ids of the inserted casts.
+    pub(crate) node_builder: NodeBuilder,
+}
+
+impl WideningInserter {
+    /// Returns a new widening inserter, which inserts a cast for each of the given `widenings`.
+    /// The ids of the casts are allocated with `node_builder`.
+    pub fn new(widenings: ImplicitWidenings, node_builder: NodeBuilder) -> Self {
+        Self {
+            widenings,
+            node_builder,
+        }
+    }
+
+    /// Wraps `expression` in a cast if the type checker widened it.
+    fn cast(&mut self, expression: Expression) -> Expression {
+        match self.widenings.remove(&expression.id()) {
+            Some(widening) => Expression::Cast(CastExpression {
+                expression: Box::new(expression),
+                type_: widening.to,
+                span: widening.span,
+                id: self.node_builder.next_id(),
+            }),
+            None => expression,
+        }
+    }
+}
+
+impl ExpressionReconstructor for WideningInserter {
+    type AdditionalOutput = ();
+
+    fn reconstruct_expression(&mut self, input: Expression) -> (Expression, Self::AdditionalOutput) {
+        let expression = match input {
+            Expression::Access(access) => self.reconstruct_access(access).0,
+            Expression::Binary(binary) => self.reconstruct_binary(binary).0,
+            Expression::Call(call) => self.reconstruct_call(call).0,
+            Expression::Cast(cast) => self.reconstruct_cast(cast).0,
+            Expression::Struct(struct_) => self.reconstruct_struct_init(struct_).0,
+            Expression::Err(err) => self.reconstruct_err(err).0,
+            Expression::Identifier(identifier) => self.reconstruct_identifier(identifier).0,
+            Expression::Literal(value) => self.reconstruct_literal(value).0,
+            Expression::Ternary(ternary) => self.reconstruct_ternary(ternary).0,
+            Expression::Tuple(tuple) => self.reconstruct_tuple(tuple).0,
+            Expression::Unary(unary) => self.reconstruct_unary(unary).0,
+        };
+        (self.cast(expression), Default::default())
+    }
+
+    /// Reconstructs the members of a struct initialization, which may be widened to the types of the members.
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Struct(StructExpression {
+                name: input.name,
+                members: input
+                    .members
+                    .into_iter()
+                    .map(|member| StructVariableInitializer {
+                        expression: match member.expression {
+                            Some(expression) => Some(self.reconstruct_expression(expression).0),
+                            // A widened shorthand member, e.g. `Foo { a }`, is given its variable as an explicit value.
+                            None => match self.widenings.contains_key(&member.identifier.id) {
+                                true => Some(self.cast(Expression::Identifier(member.identifier))),
+                                false => None,
+                            },
+                        },
+                        identifier: member.identifier,
+                    })
+                    .collect(),
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+}
+
+impl StatementReconstructor for WideningInserter {}
+
+impl ProgramReconstructor for WideningInserter {}
@@ -0,0 +1,152 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::*;
+use leo_errors::{emitter::Handler, LintWarning};
+use leo_span::Symbol;
+
+use indexmap::IndexSet;
+
+/// Records the imported programs and the type names that a program refers to, so that its unused imports can be removed.
+pub struct ImportPruner<'a> {
+    /// The handler that warnings are emitted to.
+    handler: &'a Handler,
+    /// The programs named by external calls, inputs, and outputs.
+    programs: IndexSet<Symbol>,
+    /// The struct and record types referred to by name.
+    types: IndexSet<Symbol>,
+}
+
+impl<'a> ImportPruner<'a> {
+    /// Returns a new `ImportPruner` that emits warnings to `handler`.
+    pub fn new(handler: &'a Handler) -> Self {
+        Self {
+            handler,
+            programs: IndexSet::new(),
+            types: IndexSet::new(),
+        }
+    }
+
+    /// Removes the imports of `program` that were not used by the visited program, warning about each of them.
+    pub fn prune(self, mut program: Program) -> Program {
+        program.imports.retain(|name, import| {
+            let used = self.programs.contains(&name.name)
+                || import
+                    .program_scopes
+                    .values()
+                    .flat_map(|scope| scope.structs.keys())
+                    .any(|struct_| self.types.contains(&struct_.name));
+            if !used {
+                self.handler
+                    .emit_warning(LintWarning::unused_import(name.name, name.span));
+            }
+            used
+        });
+        program
+    }
+
+    /// Records the struct and record types that `type_` refers to.
+    fn use_type(&mut self, type_: &Type) {
+        match type_ {
+            Type::Identifier(identifier) => {
+                self.types.insert(identifier.name);
+            }
+            Type::Mapping(mapping) => {
+                self.use_type(&mapping.key);
+                self.use_type(&mapping.value);
+            }
+            Type::Tuple(tuple) => tuple.0.iter().for_each(|element| self.use_type(element)),
+            _ => {}
+        }
+    }
+
+    /// Records the program and types of a function or finalize input.
+    fn use_input(&mut self, input: &Input) {
+        match input {
+            Input::Internal(input) => self.use_type(&input.type_),
+            Input::External(input) => {
+                self.programs.insert(input.program_name.name);
+            }
+        }
+    }
+
+    /// Records the program and types of a function or finalize output.
+    fn use_output(&mut self, output: &Output) {
+        match output {
+            Output::Internal(output) => self.use_type(&output.type_),
+            Output::External(output) => {
+                self.programs.insert(output.program_name.name);
+            }
+        }
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for ImportPruner<'_> {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_call(&mut self, input: &'a CallExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        if let Some(Expression::Identifier(program)) = input.external.as_deref() {
+            self.programs.insert(program.name);
+        }
+        input.arguments.iter().for_each(|argument| {
+            self.visit_expression(argument, additional);
+        });
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        self.types.insert(input.name.name);
+        input
+            .members
+            .iter()
+            .filter_map(|member| member.expression.as_ref())
+            .for_each(|expression| self.visit_expression(expression, additional));
+    }
+}
+
+impl<'a> StatementVisitor<'a> for ImportPruner<'_> {
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        self.use_type(&input.type_);
+        self.visit_expression(&input.value, &());
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for ImportPruner<'_> {
+    fn visit_import(&mut self, _input: &'a Program) {
+        // Only the uses in the program being generated decide whether an import is kept.
+    }
+
+    fn visit_struct(&mut self, input: &'a Struct) {
+        input.members.iter().for_each(|member| self.use_type(&member.type_));
+    }
+
+    fn visit_mapping(&mut self, input: &'a Mapping) {
+        self.use_type(&input.key_type);
+        self.use_type(&input.value_type);
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        input.input.iter().for_each(|input| self.use_input(input));
+        input.output.iter().for_each(|output| self.use_output(output));
+        self.visit_block(&input.block);
+
+        if let Some(finalize) = &input.finalize {
+            finalize.input.iter().for_each(|input| self.use_input(input));
+            finalize.output.iter().for_each(|output| self.use_output(output));
+            self.visit_block(&finalize.block);
+        }
+    }
+}
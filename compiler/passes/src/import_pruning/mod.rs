@@ -0,0 +1,63 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The import pruning pass removes imports that the generated program does not use, and warns about each of them.
+//!
+//! An import is used if the program calls one of its functions, takes or returns one of its records,
+//! or refers to one of its structs or records by name.
+//! The pass runs on the AST that instructions are generated from, so code removed by earlier passes does not keep an import alive.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! import token.leo;
+//! import math.leo;
+//!
+//! program swap.aleo {
+//!     transition send(t: token.leo/token.record, to: address) -> token.leo/token.record {
+//!         return token.leo/transfer(t, to);
+//!     }
+//! }
+//! ```
+//!
+//! The import pruning pass produces the following code, and warns that `math.leo` is unused.
+//! ```leo
+//! import token.leo;
+//!
+//! program swap.aleo {
+//!     transition send(t: token.leo/token.record, to: address) -> token.leo/token.record {
+//!         return token.leo/transfer(t, to);
+//!     }
+//! }
+//! ```
+
+pub mod import_pruner;
+pub use import_pruner::*;
+
+use crate::Pass;
+
+use leo_ast::{Ast, ProgramVisitor};
+use leo_errors::emitter::Handler;
+
+impl<'a> Pass for ImportPruner<'a> {
+    type Input = (Ast, &'a Handler);
+    type Output = Ast;
+
+    fn do_pass((ast, handler): Self::Input) -> Self::Output {
+        let mut visitor = ImportPruner::new(handler);
+        visitor.visit_program(ast.as_repr());
+        Ast::new(visitor.prune(ast.into_repr()))
+    }
+}
@@ -17,18 +17,60 @@
 #![forbid(unsafe_code)]
 #![doc = include_str!("../README.md")]
 
+pub mod assert_elimination;
+pub use assert_elimination::*;
+
+pub mod async_desugaring;
+pub use async_desugaring::*;
+
+pub mod call_graph;
+pub use call_graph::*;
+
+pub mod canonicalization;
+pub use canonicalization::*;
+
 pub mod code_generation;
 pub use code_generation::*;
 
+pub mod cost_estimation;
+pub use cost_estimation::*;
+
+pub mod dead_code;
+pub use dead_code::*;
+
+pub mod finalize_validation;
+pub use finalize_validation::*;
+
 pub mod flattening;
 pub use flattening::*;
 
+pub mod halt_analysis;
+pub use halt_analysis::*;
+
+pub mod implicit_widening;
+pub use implicit_widening::*;
+
+pub mod import_pruning;
+pub use import_pruning::*;
+
+pub mod linting;
+pub use linting::*;
+
 pub mod loop_unrolling;
 pub use self::loop_unrolling::*;
 
+pub mod match_lowering;
+pub use match_lowering::*;
+
 pub mod pass;
 pub use self::pass::*;
 
+pub mod range_analysis;
+pub use range_analysis::*;
+
+pub mod refactoring;
+pub use refactoring::*;
+
 pub mod static_single_assignment;
 pub use static_single_assignment::*;
 
@@ -0,0 +1,153 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::*;
+use leo_core::CoreInstruction;
+use leo_errors::{emitter::Handler, LintWarning};
+use leo_span::{Span, Symbol};
+
+use indexmap::IndexMap;
+
+/// Reports hashes of values with few possible values that are made public, i.e. used as commitments to the values.
+/// Such a hash does not hide the value, since every possible value can be hashed and compared with it.
+///
+/// A value is made public if it is a public output of a transition, or an argument of its finalize block.
+/// A hash is only reported if it is of a variable that is a `bool` or an integer of at most 64 bits.
+pub struct CommitmentChecker<'a> {
+    /// The handler that warnings are emitted to.
+    handler: &'a Handler,
+    /// The types of the variables of the current function.
+    types: IndexMap<Symbol, Type>,
+    /// The variables of the current function whose value is a hash of a value with few possible values,
+    /// along with the span of the hash and the type of the value.
+    hashes: IndexMap<Symbol, (Span, Type)>,
+    /// The modes of the outputs of the current function, if it is a transition.
+    outputs: Option<Vec<Mode>>,
+}
+
+impl<'a> CommitmentChecker<'a> {
+    /// Returns a new `CommitmentChecker` that emits warnings to `handler`.
+    pub fn new(handler: &'a Handler) -> Self {
+        Self {
+            handler,
+            types: IndexMap::new(),
+            hashes: IndexMap::new(),
+            outputs: None,
+        }
+    }
+
+    /// Returns the span of `expression` and the type of the hashed value,
+    /// if it is a hash of a value with few possible values, or a variable whose value is one.
+    fn weak_hash(&self, expression: &Expression) -> Option<(Span, Type)> {
+        match expression {
+            Expression::Access(AccessExpression::AssociatedFunction(function)) => {
+                let Type::Identifier(module) = &function.ty else {
+                    return None;
+                };
+                let is_hash = CoreInstruction::from_symbols(module.name, function.name.name)
+                    .map_or(false, |instruction| instruction.is_hash());
+                let type_ = match function.args.first() {
+                    Some(Expression::Identifier(value)) if is_hash => self.types.get(&value.name)?,
+                    _ => return None,
+                };
+                let few_values = match type_ {
+                    Type::Boolean => true,
+                    Type::Integer(integer_type) => integer_type.bits() <= 64,
+                    _ => false,
+                };
+                few_values.then(|| (function.span, type_.clone()))
+            }
+            Expression::Identifier(variable) => self.hashes.get(&variable.name).cloned(),
+            _ => None,
+        }
+    }
+
+    /// Reports `expression` if it is a hash of a value with few possible values, since it is made public.
+    fn check_public(&self, expression: &Expression) {
+        if let Some((span, type_)) = self.weak_hash(expression) {
+            self.handler
+                .emit_warning(LintWarning::hash_used_as_commitment(type_, span));
+        }
+    }
+
+    /// Records whether `variable` is assigned a hash of a value with few possible values.
+    fn assign(&mut self, variable: &Identifier, value: &Expression) {
+        match self.weak_hash(value) {
+            Some(hash) => self.hashes.insert(variable.name, hash),
+            None => self.hashes.remove(&variable.name),
+        };
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for CommitmentChecker<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+}
+
+impl<'a> StatementVisitor<'a> for CommitmentChecker<'a> {
+    fn visit_assign(&mut self, input: &'a AssignStatement) {
+        if let Expression::Identifier(variable) = &input.place {
+            self.assign(variable, &input.value);
+        }
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        self.types.insert(input.variable_name.name, input.type_.clone());
+        self.assign(&input.variable_name, &input.value);
+    }
+
+    fn visit_finalize(&mut self, input: &'a FinalizeStatement) {
+        // The arguments of a finalize block are public.
+        input.arguments.iter().for_each(|argument| self.check_public(argument));
+    }
+
+    fn visit_iteration(&mut self, input: &'a IterationStatement) {
+        self.types.insert(input.variable.name, input.type_.clone());
+        self.visit_block(&input.block);
+    }
+
+    fn visit_return(&mut self, input: &'a ReturnStatement) {
+        let Some(outputs) = &self.outputs else {
+            return;
+        };
+        let values = match &input.expression {
+            Expression::Tuple(tuple) => tuple.elements.iter().collect(),
+            value => vec![value],
+        };
+        values
+            .into_iter()
+            .zip(outputs.iter())
+            .filter(|(_, mode)| **mode == Mode::Public)
+            .for_each(|(value, _)| self.check_public(value));
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for CommitmentChecker<'a> {
+    fn visit_import(&mut self, _input: &'a Program) {
+        // Imported programs are linted when they are compiled themselves.
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        self.types.clear();
+        self.hashes.clear();
+        self.types
+            .extend(input.input.iter().map(|input| (input.identifier().name, input.type_())));
+        self.outputs = matches!(input.call_type, CallType::Transition | CallType::AsyncTransition)
+            .then(|| input.output.iter().map(Output::mode).collect());
+        self.visit_block(&input.block);
+        // The finalize block runs on chain, where every value is public, and its hashes are not reported.
+    }
+}
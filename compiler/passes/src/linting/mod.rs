@@ -0,0 +1,63 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Lints that report code which is valid but likely a mistake, or which does not follow the configured style.
+//! Lints only emit warnings, so they never stop compilation unless the warnings are denied.
+
+pub mod commitment_checker;
+pub use commitment_checker::*;
+
+pub mod naming_checker;
+pub use naming_checker::*;
+
+pub mod unused_checker;
+pub use unused_checker::*;
+
+use crate::Pass;
+
+use leo_ast::{Ast, ProgramVisitor};
+use leo_errors::emitter::Handler;
+
+impl<'a> Pass for UnusedChecker<'a> {
+    type Input = (&'a Ast, &'a Handler);
+    type Output = ();
+
+    fn do_pass((ast, handler): Self::Input) -> Self::Output {
+        let mut visitor = UnusedChecker::new(handler);
+        visitor.visit_program(ast.as_repr());
+    }
+}
+
+impl<'a> Pass for CommitmentChecker<'a> {
+    type Input = (&'a Ast, &'a Handler);
+    type Output = ();
+
+    fn do_pass((ast, handler): Self::Input) -> Self::Output {
+        let mut visitor = CommitmentChecker::new(handler);
+        visitor.visit_program(ast.as_repr());
+    }
+}
+
+impl<'a> Pass for NamingChecker<'a> {
+    type Input = (&'a Ast, &'a NamingConfig);
+    type Output = Vec<NamingViolation>;
+
+    fn do_pass((ast, config): Self::Input) -> Self::Output {
+        let mut visitor = NamingChecker::new(config);
+        visitor.visit_program(ast.as_repr());
+        visitor.violations
+    }
+}
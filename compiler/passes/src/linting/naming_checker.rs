@@ -0,0 +1,366 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::*;
+use leo_errors::LintWarning;
+use leo_span::{Span, Symbol};
+
+use serde::Deserialize;
+use std::fmt;
+
+/// A convention for the case of names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum NamingConvention {
+    /// Lowercase words separated by underscores, e.g. `transfer_public`.
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    /// Capitalized words without separators, e.g. `TokenInfo`.
+    #[serde(rename = "UpperCamelCase")]
+    UpperCamelCase,
+    /// Uppercase words separated by underscores, e.g. `MAX_SUPPLY`.
+    #[serde(rename = "SCREAMING_SNAKE_CASE")]
+    ScreamingSnakeCase,
+    /// Any name is accepted.
+    #[serde(rename = "any")]
+    Any,
+}
+
+impl NamingConvention {
+    /// Returns `true` if `name` follows the convention.
+    /// Leading underscores are ignored.
+    pub fn is_followed_by(self, name: &str) -> bool {
+        let name = name.trim_start_matches('_');
+        match self {
+            Self::SnakeCase => !name.chars().any(char::is_uppercase),
+            Self::UpperCamelCase => name.starts_with(char::is_uppercase) && !name.contains('_'),
+            Self::ScreamingSnakeCase => !name.chars().any(char::is_lowercase),
+            Self::Any => true,
+        }
+    }
+
+    /// Converts `name` to follow the convention, keeping any leading underscores.
+    pub fn apply(self, name: &str) -> String {
+        let body = name.trim_start_matches('_');
+        let prefix = &name[..name.len() - body.len()];
+        let words = Self::words(body);
+        let body = match self {
+            Self::SnakeCase => words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Self::UpperCamelCase => words
+                .iter()
+                .map(|word| {
+                    let mut chars = word.chars();
+                    chars
+                        .next()
+                        .map(|first| first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect())
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<String>>()
+                .concat(),
+            Self::ScreamingSnakeCase => words
+                .iter()
+                .map(|word| word.to_uppercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Self::Any => body.to_string(),
+        };
+        format!("{prefix}{body}")
+    }
+
+    /// Splits a name into its words, at underscores and at changes of case.
+    /// An acronym is kept as one word, e.g. `HTTPServer` is split into `HTTP` and `Server`.
+    fn words(name: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        for part in name.split('_').filter(|part| !part.is_empty()) {
+            let chars = part.chars().collect::<Vec<_>>();
+            let mut word = String::new();
+            for (i, c) in chars.iter().enumerate() {
+                let starts_word = c.is_uppercase()
+                    && i > 0
+                    && (!chars[i - 1].is_uppercase() || chars.get(i + 1).map_or(false, |next| next.is_lowercase()));
+                if starts_word && !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                word.push(*c);
+            }
+            words.push(word);
+        }
+        words
+    }
+}
+
+impl fmt::Display for NamingConvention {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::SnakeCase => write!(f, "snake_case"),
+            Self::UpperCamelCase => write!(f, "UpperCamelCase"),
+            Self::ScreamingSnakeCase => write!(f, "SCREAMING_SNAKE_CASE"),
+            Self::Any => write!(f, "any"),
+        }
+    }
+}
+
+/// The naming convention of each kind of item, as configured under `lints.naming` in `program.json`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NamingConfig {
+    /// The convention for function and transition names.
+    pub functions: NamingConvention,
+    /// The convention for `let` bindings, loop variables, and parameters.
+    pub variables: NamingConvention,
+    /// The convention for struct and record names.
+    pub structs: NamingConvention,
+    /// The convention for `const` bindings.
+    pub constants: NamingConvention,
+    /// The convention for program names.
+    pub programs: NamingConvention,
+}
+
+impl Default for NamingConfig {
+    fn default() -> Self {
+        Self {
+            functions: NamingConvention::SnakeCase,
+            variables: NamingConvention::SnakeCase,
+            structs: NamingConvention::UpperCamelCase,
+            constants: NamingConvention::ScreamingSnakeCase,
+            programs: NamingConvention::SnakeCase,
+        }
+    }
+}
+
+/// The kinds of named items that naming conventions apply to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NamedItem {
+    Function,
+    Variable,
+    Struct,
+    Record,
+    Constant,
+    Program,
+}
+
+impl fmt::Display for NamedItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Function => write!(f, "Function"),
+            Self::Variable => write!(f, "Variable"),
+            Self::Struct => write!(f, "Struct"),
+            Self::Record => write!(f, "Record"),
+            Self::Constant => write!(f, "Constant"),
+            Self::Program => write!(f, "Program"),
+        }
+    }
+}
+
+/// A name that does not follow the naming convention for its kind of item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NamingViolation {
+    /// The kind of item that is named.
+    pub kind: NamedItem,
+    /// The name of the item.
+    pub name: Symbol,
+    /// The convention that the name does not follow.
+    pub convention: NamingConvention,
+    /// The name converted to follow the convention.
+    pub suggestion: Symbol,
+    /// The span of the name where the item is declared.
+    pub span: Span,
+}
+
+impl NamingViolation {
+    /// Returns `true` if the suggestion can be applied with the rename refactoring.
+    /// Programs are renamed through `program.json`, and suggestions that are not identifiers, such as keywords, cannot be applied.
+    pub fn is_machine_applicable(&self) -> bool {
+        self.kind != NamedItem::Program && leo_parser::is_identifier(&self.suggestion.to_string())
+    }
+
+    /// Returns the warning that reports the violation.
+    pub fn to_warning(&self) -> LintWarning {
+        LintWarning::naming_convention(self.kind, self.name, self.convention, self.suggestion, self.span)
+    }
+}
+
+/// Finds the names that do not follow the configured naming conventions.
+pub struct NamingChecker<'a> {
+    /// The configured naming conventions.
+    config: &'a NamingConfig,
+    /// The names found so far that do not follow their convention.
+    pub(crate) violations: Vec<NamingViolation>,
+}
+
+impl<'a> NamingChecker<'a> {
+    /// Returns a new `NamingChecker` that checks names against `config`.
+    pub fn new(config: &'a NamingConfig) -> Self {
+        Self {
+            config,
+            violations: Vec::new(),
+        }
+    }
+
+    /// Records a violation if the name `identifier` of an item of the given `kind` does not follow its convention.
+    fn check(&mut self, kind: NamedItem, identifier: &Identifier) {
+        let convention = match kind {
+            NamedItem::Function => self.config.functions,
+            NamedItem::Variable => self.config.variables,
+            NamedItem::Struct | NamedItem::Record => self.config.structs,
+            NamedItem::Constant => self.config.constants,
+            NamedItem::Program => self.config.programs,
+        };
+        let name = identifier.name.to_string();
+        if convention.is_followed_by(&name) {
+            return;
+        }
+        let suggestion = convention.apply(&name);
+        if suggestion != name {
+            self.violations.push(NamingViolation {
+                kind,
+                name: identifier.name,
+                convention,
+                suggestion: Symbol::intern(&suggestion),
+                span: identifier.span,
+            });
+        }
+    }
+
+    /// Checks the names of the parameters `inputs`.
+    fn check_inputs(&mut self, inputs: &[Input]) {
+        inputs
+            .iter()
+            .filter(|input| matches!(input, Input::Internal(_)))
+            .for_each(|input| self.check(NamedItem::Variable, &input.identifier()));
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for NamingChecker<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+}
+
+impl<'a> StatementVisitor<'a> for NamingChecker<'a> {
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        let kind = match input.declaration_type {
+            DeclarationType::Const => NamedItem::Constant,
+            DeclarationType::Let => NamedItem::Variable,
+        };
+        self.check(kind, &input.variable_name);
+    }
+
+    fn visit_iteration(&mut self, input: &'a IterationStatement) {
+        self.check(NamedItem::Variable, &input.variable);
+        self.visit_block(&input.block);
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for NamingChecker<'a> {
+    fn visit_import(&mut self, _input: &'a Program) {
+        // Imported programs follow the conventions of their own packages.
+    }
+
+    fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        self.check(NamedItem::Program, &input.program_id.name);
+        input.structs.values().for_each(|struct_| self.visit_struct(struct_));
+        input
+            .functions
+            .values()
+            .for_each(|function| self.visit_function(function));
+    }
+
+    fn visit_struct(&mut self, input: &'a Struct) {
+        let kind = match input.is_record {
+            true => NamedItem::Record,
+            false => NamedItem::Struct,
+        };
+        self.check(kind, &input.identifier);
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        self.check(NamedItem::Function, &input.identifier);
+        self.check_inputs(&input.input);
+        self.visit_block(&input.block);
+        if let Some(finalize) = &input.finalize {
+            self.check_inputs(&finalize.input);
+            self.visit_block(&finalize.block);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pass;
+
+    use leo_errors::emitter::Handler;
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    #[test]
+    fn conventions() {
+        assert!(NamingConvention::SnakeCase.is_followed_by("transfer_public"));
+        assert!(NamingConvention::SnakeCase.is_followed_by("_unused"));
+        assert!(!NamingConvention::SnakeCase.is_followed_by("transferPublic"));
+        assert!(NamingConvention::UpperCamelCase.is_followed_by("HTTPServer"));
+        assert!(!NamingConvention::UpperCamelCase.is_followed_by("token_info"));
+        assert!(NamingConvention::ScreamingSnakeCase.is_followed_by("MAX_SUPPLY"));
+        assert!(!NamingConvention::ScreamingSnakeCase.is_followed_by("maxSupply"));
+        assert!(NamingConvention::Any.is_followed_by("aNy_Name"));
+    }
+
+    #[test]
+    fn suggestions_keep_acronyms_and_leading_underscores() {
+        assert_eq!(NamingConvention::SnakeCase.apply("HTTPServer"), "http_server");
+        assert_eq!(NamingConvention::SnakeCase.apply("_firstInput"), "_first_input");
+        assert_eq!(NamingConvention::UpperCamelCase.apply("token_info"), "TokenInfo");
+        assert_eq!(NamingConvention::UpperCamelCase.apply("HTTP_server"), "HttpServer");
+        assert_eq!(NamingConvention::ScreamingSnakeCase.apply("maxSupply"), "MAX_SUPPLY");
+    }
+
+    #[test]
+    fn program_names_are_not_renamed() {
+        create_session_if_not_set_then(|_| {
+            let source = "program tokenVault.aleo {
+    transition main(a: u8) -> u8 {
+        return a;
+    }
+}
+";
+            let ast = leo_parser::parse_ast(&Handler::default(), source, Default::default()).unwrap();
+            let violations = NamingChecker::do_pass((&ast, &NamingConfig::default()));
+            assert_eq!(violations.len(), 1);
+            assert_eq!(violations[0].kind, NamedItem::Program);
+            assert_eq!(violations[0].suggestion.to_string(), "token_vault");
+            assert!(!violations[0].is_machine_applicable());
+        })
+    }
+
+    #[test]
+    fn keywords_are_not_machine_applicable() {
+        create_session_if_not_set_then(|_| {
+            let source = "program test.aleo {
+    transition main(Self: u8) -> u8 {
+        return Self;
+    }
+}
+";
+            let ast = leo_parser::parse_ast(&Handler::default(), source, Default::default()).unwrap();
+            let violations = NamingChecker::do_pass((&ast, &NamingConfig::default()));
+            assert_eq!(violations.len(), 1);
+            assert_eq!(violations[0].suggestion.to_string(), "self");
+            assert!(!violations[0].is_machine_applicable());
+        })
+    }
+}
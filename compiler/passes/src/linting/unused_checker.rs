@@ -0,0 +1,155 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::*;
+use leo_errors::{emitter::Handler, LintWarning};
+use leo_span::{Span, Symbol};
+
+use indexmap::{IndexMap, IndexSet};
+
+/// Reports variables that are defined but never read, and functions that are never called.
+///
+/// Variables whose names begin with an underscore are assumed to be unused on purpose.
+/// Transitions are the entry points of a program, and tests are run on their own, so neither is ever reported.
+pub struct UnusedChecker<'a> {
+    /// The handler that warnings are emitted to.
+    handler: &'a Handler,
+    /// A stack of the variables defined in each enclosing scope, along with whether they have been read.
+    scopes: Vec<IndexMap<Symbol, (Span, bool)>>,
+    /// The functions called within the current program scope.
+    called_functions: IndexSet<Symbol>,
+}
+
+impl<'a> UnusedChecker<'a> {
+    /// Returns a new `UnusedChecker` that emits warnings to `handler`.
+    pub fn new(handler: &'a Handler) -> Self {
+        Self {
+            handler,
+            scopes: Vec::new(),
+            called_functions: IndexSet::new(),
+        }
+    }
+
+    /// Enters a new scope, whose variables are checked when it is exited.
+    fn enter_scope(&mut self) {
+        self.scopes.push(IndexMap::new());
+    }
+
+    /// Exits the current scope, reporting each of its variables that was never read.
+    fn exit_scope(&mut self) {
+        let scope = self.scopes.pop().unwrap_or_default();
+        for (name, (span, used)) in scope {
+            if !used && !name.to_string().starts_with('_') {
+                self.handler.emit_warning(LintWarning::unused_variable(name, span));
+            }
+        }
+    }
+
+    /// Records that a variable has been defined in the current scope.
+    fn define(&mut self, variable: &Identifier) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(variable.name, (variable.span, false));
+        }
+    }
+
+    /// Records that a variable has been read, marking its innermost definition as used.
+    fn read(&mut self, name: Symbol) {
+        if let Some((_, used)) = self.scopes.iter_mut().rev().find_map(|scope| scope.get_mut(&name)) {
+            *used = true;
+        }
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for UnusedChecker<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_call(&mut self, input: &'a CallExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        if let (Expression::Identifier(function), None) = (&*input.function, &input.external) {
+            self.called_functions.insert(function.name);
+        }
+        input.arguments.iter().for_each(|argument| {
+            self.visit_expression(argument, additional);
+        });
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        for member in input.members.iter() {
+            match &member.expression {
+                Some(expression) => self.visit_expression(expression, additional),
+                // The shorthand `Foo { x }` reads the variable `x`.
+                None => self.read(member.identifier.name),
+            }
+        }
+    }
+
+    fn visit_identifier(&mut self, input: &'a Identifier, _additional: &Self::AdditionalInput) -> Self::Output {
+        self.read(input.name);
+    }
+}
+
+impl<'a> StatementVisitor<'a> for UnusedChecker<'a> {
+    fn visit_block(&mut self, input: &'a Block) {
+        self.enter_scope();
+        input
+            .statements
+            .iter()
+            .for_each(|statement| self.visit_statement(statement));
+        self.exit_scope();
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        self.visit_expression(&input.value, &());
+        self.define(&input.variable_name);
+    }
+
+    fn visit_iteration(&mut self, input: &'a IterationStatement) {
+        self.visit_expression(&input.start, &());
+        self.visit_expression(&input.stop, &());
+
+        // The loop variable is scoped to the body of the loop.
+        self.enter_scope();
+        self.define(&input.variable);
+        self.visit_block(&input.block);
+        self.exit_scope();
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for UnusedChecker<'a> {
+    fn visit_import(&mut self, _input: &'a Program) {
+        // Imported programs are linted when they are compiled themselves.
+    }
+
+    fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        self.called_functions.clear();
+        input
+            .functions
+            .values()
+            .for_each(|function| self.visit_function(function));
+
+        input
+            .functions
+            .values()
+            .filter(|function| {
+                !matches!(function.call_type, CallType::Transition | CallType::AsyncTransition) && !function.is_test()
+            })
+            .filter(|function| !self.called_functions.contains(&function.name()))
+            .for_each(|function| {
+                self.handler
+                    .emit_warning(LintWarning::unused_function(function.name(), function.identifier.span))
+            });
+    }
+}
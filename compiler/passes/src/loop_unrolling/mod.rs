@@ -17,6 +17,9 @@
 mod range_iterator;
 pub(crate) use range_iterator::*;
 
+mod renumberer;
+pub(crate) use renumberer::*;
+
 pub mod unroller;
 pub use unroller::*;
 
@@ -29,18 +32,19 @@ pub use unroll_program::*;
 pub mod unroll_statement;
 pub use unroll_statement::*;
 
-use crate::{Pass, SymbolTable};
+use crate::{Pass, SymbolTable, TypeTable};
 
-use leo_ast::{Ast, ProgramReconstructor};
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
 use leo_errors::{emitter::Handler, Result};
 
 impl<'a> Pass for Unroller<'a> {
-    type Input = (Ast, &'a Handler, SymbolTable);
+    type Input = (Ast, &'a Handler, SymbolTable, &'a mut TypeTable);
     type Output = Result<(Ast, SymbolTable)>;
 
-    fn do_pass((ast, handler, st): Self::Input) -> Self::Output {
+    fn do_pass((ast, handler, st, type_table): Self::Input) -> Self::Output {
         // Reconstructs the AST based off any flattening work that is done.
-        let mut reconstructor = Self::new(st, handler);
+        let node_builder = NodeBuilder::for_program(ast.as_repr());
+        let mut reconstructor = Self::new(st, handler, node_builder, type_table);
         let program = reconstructor.reconstruct_program(ast.into_repr());
         handler.last_err()?;
 
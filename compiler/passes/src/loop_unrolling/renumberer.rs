@@ -0,0 +1,444 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::*;
+
+use crate::TypeTable;
+
+/// Gives fresh ids to the nodes of a copy of a statement, e.g. of an unrolled iteration of a loop,
+/// so that no two nodes of the program share an id.
+/// The type recorded for a node is also recorded for its copy.
+pub(crate) struct Renumberer<'a> {
+    /// Allocates the fresh ids.
+    node_builder: &'a NodeBuilder,
+    /// The types of the nodes, keyed by their id.
+    type_table: &'a mut TypeTable,
+}
+
+impl<'a> Renumberer<'a> {
+    pub(crate) fn new(node_builder: &'a NodeBuilder, type_table: &'a mut TypeTable) -> Self {
+        Self {
+            node_builder,
+            type_table,
+        }
+    }
+
+    /// Returns a fresh id for the copy of the node `id`.
+    fn renumber(&mut self, id: NodeID) -> NodeID {
+        let new_id = self.node_builder.next_id();
+        if let Some(type_) = self.type_table.get(&id).cloned() {
+            self.type_table.insert(new_id, type_);
+        }
+        new_id
+    }
+
+    fn renumber_identifier(&mut self, identifier: Identifier) -> Identifier {
+        Identifier {
+            id: self.renumber(identifier.id),
+            ..identifier
+        }
+    }
+
+    fn renumber_type(&mut self, type_: Type) -> Type {
+        match type_ {
+            Type::Identifier(identifier) => Type::Identifier(self.renumber_identifier(identifier)),
+            Type::Mapping(mapping) => Type::Mapping(MappingType {
+                key: Box::new(self.renumber_type(*mapping.key)),
+                value: Box::new(self.renumber_type(*mapping.value)),
+            }),
+            Type::Tuple(tuple) => Type::Tuple(Tuple(
+                tuple.0.into_iter().map(|type_| self.renumber_type(type_)).collect(),
+            )),
+            type_ => type_,
+        }
+    }
+
+    fn renumber_pattern(&mut self, pattern: Pattern) -> Pattern {
+        match pattern {
+            Pattern::Wildcard(span) => Pattern::Wildcard(span),
+            Pattern::Binding(identifier) => Pattern::Binding(self.renumber_identifier(identifier)),
+            Pattern::Literal(mut literal) => {
+                literal.set_id(self.renumber(literal.id()));
+                Pattern::Literal(literal)
+            }
+            Pattern::Tuple(tuple) => Pattern::Tuple(TuplePattern {
+                elements: tuple
+                    .elements
+                    .into_iter()
+                    .map(|pattern| self.renumber_pattern(pattern))
+                    .collect(),
+                span: tuple.span,
+            }),
+            Pattern::Struct(struct_) => Pattern::Struct(StructPattern {
+                name: self.renumber_identifier(struct_.name),
+                members: struct_
+                    .members
+                    .into_iter()
+                    .map(|member| StructMemberPattern {
+                        identifier: self.renumber_identifier(member.identifier),
+                        pattern: member.pattern.map(|pattern| self.renumber_pattern(pattern)),
+                    })
+                    .collect(),
+                span: struct_.span,
+            }),
+        }
+    }
+}
+
+impl ExpressionReconstructor for Renumberer<'_> {
+    type AdditionalOutput = ();
+
+    fn reconstruct_access(&mut self, input: AccessExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Access(match input {
+                AccessExpression::AssociatedConstant(constant) => {
+                    AccessExpression::AssociatedConstant(AssociatedConstant {
+                        ty: self.renumber_type(constant.ty),
+                        name: self.renumber_identifier(constant.name),
+                        span: constant.span,
+                        id: self.renumber(constant.id),
+                    })
+                }
+                AccessExpression::AssociatedFunction(function) => {
+                    AccessExpression::AssociatedFunction(AssociatedFunction {
+                        ty: self.renumber_type(function.ty),
+                        name: self.renumber_identifier(function.name),
+                        args: function
+                            .args
+                            .into_iter()
+                            .map(|arg| self.reconstruct_expression(arg).0)
+                            .collect(),
+                        span: function.span,
+                        id: self.renumber(function.id),
+                    })
+                }
+                AccessExpression::Member(member) => AccessExpression::Member(MemberAccess {
+                    inner: Box::new(self.reconstruct_expression(*member.inner).0),
+                    name: self.renumber_identifier(member.name),
+                    span: member.span,
+                    id: self.renumber(member.id),
+                }),
+                AccessExpression::Tuple(tuple) => AccessExpression::Tuple(TupleAccess {
+                    tuple: Box::new(self.reconstruct_expression(*tuple.tuple).0),
+                    index: tuple.index,
+                    span: tuple.span,
+                    id: self.renumber(tuple.id),
+                }),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_binary(&mut self, input: BinaryExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Binary(BinaryExpression {
+                left: Box::new(self.reconstruct_expression(*input.left).0),
+                right: Box::new(self.reconstruct_expression(*input.right).0),
+                op: input.op,
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_call(&mut self, input: CallExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Call(CallExpression {
+                function: Box::new(self.reconstruct_expression(*input.function).0),
+                arguments: input
+                    .arguments
+                    .into_iter()
+                    .map(|arg| self.reconstruct_expression(arg).0)
+                    .collect(),
+                external: input
+                    .external
+                    .map(|external| Box::new(self.reconstruct_expression(*external).0)),
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_cast(&mut self, input: CastExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Cast(CastExpression {
+                expression: Box::new(self.reconstruct_expression(*input.expression).0),
+                type_: input.type_,
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Struct(StructExpression {
+                name: self.renumber_identifier(input.name),
+                members: input
+                    .members
+                    .into_iter()
+                    .map(|member| StructVariableInitializer {
+                        identifier: self.renumber_identifier(member.identifier),
+                        expression: member
+                            .expression
+                            .map(|expression| self.reconstruct_expression(expression).0),
+                    })
+                    .collect(),
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_identifier(&mut self, input: Identifier) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Identifier(self.renumber_identifier(input)),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_literal(&mut self, mut input: Literal) -> (Expression, Self::AdditionalOutput) {
+        input.set_id(self.renumber(input.id()));
+        (Expression::Literal(input), Default::default())
+    }
+
+    fn reconstruct_ternary(&mut self, input: TernaryExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Ternary(TernaryExpression {
+                condition: Box::new(self.reconstruct_expression(*input.condition).0),
+                if_true: Box::new(self.reconstruct_expression(*input.if_true).0),
+                if_false: Box::new(self.reconstruct_expression(*input.if_false).0),
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_tuple(&mut self, input: TupleExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Tuple(TupleExpression {
+                elements: input
+                    .elements
+                    .into_iter()
+                    .map(|element| self.reconstruct_expression(element).0)
+                    .collect(),
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_unary(&mut self, input: UnaryExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Unary(UnaryExpression {
+                receiver: Box::new(self.reconstruct_expression(*input.receiver).0),
+                op: input.op,
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+}
+
+impl StatementReconstructor for Renumberer<'_> {
+    fn reconstruct_assign(&mut self, input: AssignStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Assign(Box::new(AssignStatement {
+                place: self.reconstruct_expression(input.place).0,
+                value: self.reconstruct_expression(input.value).0,
+                span: input.span,
+                id: self.renumber(input.id),
+            })),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
+        (
+            Block {
+                statements: input
+                    .statements
+                    .into_iter()
+                    .map(|s| self.reconstruct_statement(s).0)
+                    .collect(),
+                span: input.span,
+                id: self.renumber(input.id),
+            },
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_conditional(&mut self, input: ConditionalStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Conditional(ConditionalStatement {
+                condition: self.reconstruct_expression(input.condition).0,
+                then: self.reconstruct_block(input.then).0,
+                otherwise: input.otherwise.map(|n| Box::new(self.reconstruct_statement(*n).0)),
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_console(&mut self, input: ConsoleStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Console(ConsoleStatement {
+                function: match input.function {
+                    ConsoleFunction::Assert(expr, message) => ConsoleFunction::Assert(
+                        self.reconstruct_expression(expr).0,
+                        message.map(|message| self.reconstruct_assert_message(message)),
+                    ),
+                    ConsoleFunction::AssertEq(left, right, message) => ConsoleFunction::AssertEq(
+                        self.reconstruct_expression(left).0,
+                        self.reconstruct_expression(right).0,
+                        message.map(|message| self.reconstruct_assert_message(message)),
+                    ),
+                    ConsoleFunction::AssertNeq(left, right, message) => ConsoleFunction::AssertNeq(
+                        self.reconstruct_expression(left).0,
+                        self.reconstruct_expression(right).0,
+                        message.map(|message| self.reconstruct_assert_message(message)),
+                    ),
+                },
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_decrement(&mut self, input: DecrementStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Decrement(DecrementStatement {
+                mapping: self.renumber_identifier(input.mapping),
+                index: self.reconstruct_expression(input.index).0,
+                amount: self.reconstruct_expression(input.amount).0,
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_definition(&mut self, input: DefinitionStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Definition(DefinitionStatement {
+                declaration_type: input.declaration_type,
+                variable_name: self.renumber_identifier(input.variable_name),
+                type_: self.renumber_type(input.type_),
+                value: self.reconstruct_expression(input.value).0,
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_expression_statement(&mut self, input: ExpressionStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Expression(ExpressionStatement {
+                expression: self.reconstruct_expression(input.expression).0,
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_finalize(&mut self, input: FinalizeStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Finalize(FinalizeStatement {
+                arguments: input
+                    .arguments
+                    .into_iter()
+                    .map(|arg| self.reconstruct_expression(arg).0)
+                    .collect(),
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_increment(&mut self, input: IncrementStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Increment(IncrementStatement {
+                mapping: self.renumber_identifier(input.mapping),
+                index: self.reconstruct_expression(input.index).0,
+                amount: self.reconstruct_expression(input.amount).0,
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_iteration(&mut self, input: IterationStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Iteration(Box::new(IterationStatement {
+                variable: self.renumber_identifier(input.variable),
+                type_: self.renumber_type(input.type_),
+                start: self.reconstruct_expression(input.start).0,
+                start_value: input.start_value,
+                stop: self.reconstruct_expression(input.stop).0,
+                stop_value: input.stop_value,
+                block: self.reconstruct_block(input.block).0,
+                inclusive: input.inclusive,
+                span: input.span,
+                id: self.renumber(input.id),
+            })),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_match(&mut self, input: MatchStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Match(MatchStatement {
+                expression: self.reconstruct_expression(input.expression).0,
+                arms: input
+                    .arms
+                    .into_iter()
+                    .map(|arm| MatchArm {
+                        pattern: self.renumber_pattern(arm.pattern),
+                        block: self.reconstruct_block(arm.block).0,
+                        span: arm.span,
+                    })
+                    .collect(),
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_return(&mut self, input: ReturnStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Return(ReturnStatement {
+                expression: self.reconstruct_expression(input.expression).0,
+                span: input.span,
+                id: self.renumber(input.id),
+            }),
+            Default::default(),
+        )
+    }
+}
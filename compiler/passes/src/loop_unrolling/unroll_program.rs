@@ -57,6 +57,7 @@ impl ProgramReconstructor for Unroller<'_> {
 
         // Reconstruct the function block.
         let reconstructed_function = Function {
+            documentation: function.documentation,
             annotations: function.annotations,
             call_type: function.call_type,
             identifier: function.identifier,
@@ -33,6 +33,7 @@ impl StatementReconstructor for Unroller<'_> {
                 .map(|s| self.reconstruct_statement(s).0)
                 .collect(),
             span: input.span,
+            id: input.id,
         };
 
         // Exit the block scope.
@@ -15,14 +15,14 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use leo_ast::{
-    Block, DeclarationType, DefinitionStatement, Expression, IntegerType, IterationStatement, Literal, Statement,
-    StatementReconstructor, Type, Value,
+    Block, DeclarationType, DefinitionStatement, Expression, Identifier, IntegerType, IterationStatement, Literal,
+    NodeBuilder, Statement, StatementReconstructor, Type, Value,
 };
 use std::cell::RefCell;
 
 use leo_errors::emitter::Handler;
 
-use crate::{Clusivity, LoopBound, RangeIterator, SymbolTable};
+use crate::{Clusivity, LoopBound, RangeIterator, Renumberer, SymbolTable, TypeTable};
 
 pub struct Unroller<'a> {
     /// The symbol table for the function being processed.
@@ -33,15 +33,26 @@ pub struct Unroller<'a> {
     pub(crate) handler: &'a Handler,
     /// Are we in the midst of unrolling a loop?
     pub(crate) is_unrolling: bool,
+    /// Allocates the ids of the nodes created during unrolling, including those of each unrolled copy of a loop body.
+    pub(crate) node_builder: NodeBuilder,
+    /// The types of the nodes, which are also recorded for their unrolled copies.
+    pub(crate) type_table: &'a mut TypeTable,
 }
 
 impl<'a> Unroller<'a> {
-    pub(crate) fn new(symbol_table: SymbolTable, handler: &'a Handler) -> Self {
+    pub(crate) fn new(
+        symbol_table: SymbolTable,
+        handler: &'a Handler,
+        node_builder: NodeBuilder,
+        type_table: &'a mut TypeTable,
+    ) -> Self {
         Self {
             symbol_table: RefCell::new(symbol_table),
             scope_index: 0,
             handler,
             is_unrolling: false,
+            node_builder,
+            type_table,
         }
     }
 
@@ -86,7 +97,7 @@ impl<'a> Unroller<'a> {
                 Ok(val_as_u128) => Ok(val_as_u128),
                 Err(err) => {
                     self.handler.emit_err(err);
-                    Err(Statement::dummy(input.span))
+                    Err(Statement::dummy(input.span, self.node_builder.next_id()))
                 }
             }
         };
@@ -118,6 +129,7 @@ impl<'a> Unroller<'a> {
         // Creates a new block per iteration inside the outer block statement.
         let iter_blocks = Statement::Block(Block {
             span: input.span,
+            id: input.id,
             statements: match input.inclusive {
                 true => {
                     let iter = RangeIterator::new(start, stop, Clusivity::Inclusive);
@@ -148,36 +160,38 @@ impl<'a> Unroller<'a> {
         self.is_unrolling = true;
 
         // Reconstruct `iteration_count` as a `Literal`.
+        let id = self.node_builder.next_id();
+        self.type_table.insert(id, input.type_.clone());
         let value = match input.type_ {
             Type::Integer(IntegerType::I8) => {
-                Literal::Integer(IntegerType::I8, iteration_count.to_string(), Default::default())
+                Literal::Integer(IntegerType::I8, iteration_count.to_string(), Default::default(), id)
             }
             Type::Integer(IntegerType::I16) => {
-                Literal::Integer(IntegerType::I16, iteration_count.to_string(), Default::default())
+                Literal::Integer(IntegerType::I16, iteration_count.to_string(), Default::default(), id)
             }
             Type::Integer(IntegerType::I32) => {
-                Literal::Integer(IntegerType::I32, iteration_count.to_string(), Default::default())
+                Literal::Integer(IntegerType::I32, iteration_count.to_string(), Default::default(), id)
             }
             Type::Integer(IntegerType::I64) => {
-                Literal::Integer(IntegerType::I64, iteration_count.to_string(), Default::default())
+                Literal::Integer(IntegerType::I64, iteration_count.to_string(), Default::default(), id)
             }
             Type::Integer(IntegerType::I128) => {
-                Literal::Integer(IntegerType::I128, iteration_count.to_string(), Default::default())
+                Literal::Integer(IntegerType::I128, iteration_count.to_string(), Default::default(), id)
             }
             Type::Integer(IntegerType::U8) => {
-                Literal::Integer(IntegerType::U8, iteration_count.to_string(), Default::default())
+                Literal::Integer(IntegerType::U8, iteration_count.to_string(), Default::default(), id)
             }
             Type::Integer(IntegerType::U16) => {
-                Literal::Integer(IntegerType::U16, iteration_count.to_string(), Default::default())
+                Literal::Integer(IntegerType::U16, iteration_count.to_string(), Default::default(), id)
             }
             Type::Integer(IntegerType::U32) => {
-                Literal::Integer(IntegerType::U32, iteration_count.to_string(), Default::default())
+                Literal::Integer(IntegerType::U32, iteration_count.to_string(), Default::default(), id)
             }
             Type::Integer(IntegerType::U64) => {
-                Literal::Integer(IntegerType::U64, iteration_count.to_string(), Default::default())
+                Literal::Integer(IntegerType::U64, iteration_count.to_string(), Default::default(), id)
             }
             Type::Integer(IntegerType::U128) => {
-                Literal::Integer(IntegerType::U128, iteration_count.to_string(), Default::default())
+                Literal::Integer(IntegerType::U128, iteration_count.to_string(), Default::default(), id)
             }
             _ => unreachable!(
                 "The iteration variable must be an integer type. This should be enforced by type checking."
@@ -191,19 +205,27 @@ impl<'a> Unroller<'a> {
                 type_: input.type_.clone(),
                 value: Expression::Literal(value),
                 span: Default::default(),
-                variable_name: input.variable,
+                id: self.node_builder.next_id(),
+                variable_name: Identifier {
+                    id: self.node_builder.next_id(),
+                    ..input.variable
+                },
             })
             .0,
         ];
 
-        // Reconstruct the statements in the loop body.
-        input.block.statements.clone().into_iter().for_each(|s| {
-            statements.push(self.reconstruct_statement(s).0);
-        });
+        // Reconstruct the statements in the loop body, giving their copies fresh ids.
+        for statement in input.block.statements.iter().cloned() {
+            let statement = Renumberer::new(&self.node_builder, self.type_table)
+                .reconstruct_statement(statement)
+                .0;
+            statements.push(self.reconstruct_statement(statement).0);
+        }
 
         let block = Statement::Block(Block {
             statements,
             span: input.block.span,
+            id: self.node_builder.next_id(),
         });
 
         self.is_unrolling = prior_is_unrolling;
@@ -0,0 +1,217 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    AccessExpression, BinaryExpression, BinaryOperation, Block, ConditionalStatement, DeclarationType,
+    DefinitionStatement, Expression, ExpressionReconstructor, Identifier, MatchStatement, MemberAccess, Node,
+    NodeBuilder, Pattern, PositiveNumber, ProgramReconstructor, Statement, StatementReconstructor, TupleAccess, Type,
+};
+use leo_span::{Span, Symbol};
+
+use indexmap::IndexMap;
+
+/// The types of the values that match statements match, keyed by the span of the statement,
+/// and of the variables that their patterns bind, keyed by the span of the variable.
+pub type MatchTypes = IndexMap<Span, Type>;
+
+pub struct MatchLowerer {
+    /// The types recorded by the type checker.
+    pub(crate) types: MatchTypes,
+    /// The number of variables introduced to hold matched values.
+    pub(crate) counter: usize,
+    /// Allocates the ids of the nodes created during lowering.
+    pub(crate) node_builder: NodeBuilder,
+}
+
+impl MatchLowerer {
+    /// Returns a new match lowerer, which lowers the match statements whose types are in `types`.
+    pub fn new(types: MatchTypes, node_builder: NodeBuilder) -> Self {
+        Self {
+            types,
+            counter: 0,
+            node_builder,
+        }
+    }
+
+    /// Lowers a match statement into definitions of the values it matches, followed by a conditional statement.
+    /// Note that type checking guarantees that the arms are exhaustive, and that only the last arm can match every value.
+    fn lower_match(&mut self, input: MatchStatement, statements: &mut Vec<Statement>) {
+        let type_ = self.types.get(&input.span).cloned().unwrap_or(Type::Err);
+
+        // The matched value is defined once, so that each arm compares its parts without evaluating it again.
+        // The elements of a tuple expression are defined separately, so that no tuple is constructed.
+        let value = match (input.expression, type_) {
+            (Expression::Tuple(mut tuple), Type::Tuple(types)) => {
+                tuple.elements = tuple
+                    .elements
+                    .into_iter()
+                    .zip(types.0)
+                    .map(|(element, type_)| self.define(element, type_, statements))
+                    .collect();
+                Expression::Tuple(tuple)
+            }
+            (expression, type_) => self.define(expression, type_, statements),
+        };
+
+        // Each arm becomes a branch of the conditional statement, in order, and the last arm becomes its `else` block.
+        let mut otherwise: Option<Statement> = None;
+        for arm in input.arms.into_iter().rev() {
+            let mut conditions = Vec::new();
+            let mut bindings = Vec::new();
+            self.lower_pattern(arm.pattern, value.clone(), &mut conditions, &mut bindings);
+
+            let mut block = self.reconstruct_block(arm.block).0;
+            bindings.append(&mut block.statements);
+            block.statements = bindings;
+
+            let condition = conditions.into_iter().reduce(|left, right| {
+                Expression::Binary(BinaryExpression {
+                    span: left.span() + right.span(),
+                    id: self.node_builder.next_id(),
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    op: BinaryOperation::And,
+                })
+            });
+            otherwise = Some(match (condition, otherwise) {
+                (Some(condition), Some(otherwise)) => Statement::Conditional(ConditionalStatement {
+                    condition,
+                    then: block,
+                    otherwise: Some(Box::new(otherwise)),
+                    span: arm.span,
+                    id: self.node_builder.next_id(),
+                }),
+                _ => Statement::Block(block),
+            });
+        }
+        statements.extend(otherwise);
+    }
+
+    /// Defines a variable holding `expression`, unless it is a variable or a literal, and returns the variable.
+    fn define(&mut self, expression: Expression, type_: Type, statements: &mut Vec<Statement>) -> Expression {
+        if matches!(expression, Expression::Identifier(_) | Expression::Literal(_)) {
+            return expression;
+        }
+
+        let span = expression.span();
+        let variable_name = Identifier {
+            name: Symbol::intern(&format!("$match${}", self.counter)),
+            span,
+            id: self.node_builder.next_id(),
+        };
+        self.counter += 1;
+        statements.push(Statement::Definition(DefinitionStatement {
+            declaration_type: DeclarationType::Let,
+            variable_name,
+            type_,
+            value: expression,
+            span,
+            id: self.node_builder.next_id(),
+        }));
+        Expression::Identifier(variable_name)
+    }
+
+    /// Collects the conditions under which `pattern` matches `value`, and the definitions of the variables it binds.
+    fn lower_pattern(
+        &self,
+        pattern: Pattern,
+        value: Expression,
+        conditions: &mut Vec<Expression>,
+        bindings: &mut Vec<Statement>,
+    ) {
+        match pattern {
+            Pattern::Wildcard(_) => {}
+            Pattern::Binding(variable_name) => bindings.push(self.bind(variable_name, value)),
+            Pattern::Literal(literal) => conditions.push(Expression::Binary(BinaryExpression {
+                span: literal.span(),
+                id: self.node_builder.next_id(),
+                left: Box::new(value),
+                right: Box::new(Expression::Literal(literal)),
+                op: BinaryOperation::Eq,
+            })),
+            Pattern::Tuple(tuple) => {
+                for (index, element) in tuple.elements.into_iter().enumerate() {
+                    let element_value = match &value {
+                        Expression::Tuple(tuple) => tuple.elements[index].clone(),
+                        _ => Expression::Access(AccessExpression::Tuple(TupleAccess {
+                            tuple: Box::new(value.clone()),
+                            index: PositiveNumber {
+                                value: index.to_string(),
+                            },
+                            span: element.span(),
+                            id: self.node_builder.next_id(),
+                        })),
+                    };
+                    self.lower_pattern(element, element_value, conditions, bindings);
+                }
+            }
+            Pattern::Struct(struct_) => {
+                for member in struct_.members {
+                    let member_value = Expression::Access(AccessExpression::Member(MemberAccess {
+                        inner: Box::new(value.clone()),
+                        name: member.identifier,
+                        span: member.identifier.span,
+                        id: self.node_builder.next_id(),
+                    }));
+                    match member.pattern {
+                        Some(pattern) => self.lower_pattern(pattern, member_value, conditions, bindings),
+                        None => bindings.push(self.bind(member.identifier, member_value)),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the definition of a variable bound by a pattern.
+    fn bind(&self, variable_name: Identifier, value: Expression) -> Statement {
+        Statement::Definition(DefinitionStatement {
+            declaration_type: DeclarationType::Let,
+            variable_name,
+            type_: self.types.get(&variable_name.span).cloned().unwrap_or(Type::Err),
+            value,
+            span: variable_name.span,
+            id: self.node_builder.next_id(),
+        })
+    }
+}
+
+impl ExpressionReconstructor for MatchLowerer {
+    type AdditionalOutput = ();
+}
+
+impl StatementReconstructor for MatchLowerer {
+    /// Reconstructs the statements of a block, lowering each match statement into several statements.
+    fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
+        let mut statements = Vec::with_capacity(input.statements.len());
+        for statement in input.statements {
+            match statement {
+                Statement::Match(match_) => self.lower_match(match_, &mut statements),
+                statement => statements.push(self.reconstruct_statement(statement).0),
+            }
+        }
+
+        (
+            Block {
+                statements,
+                span: input.span,
+                id: input.id,
+            },
+            Default::default(),
+        )
+    }
+}
+
+impl ProgramReconstructor for MatchLowerer {}
@@ -0,0 +1,61 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The match lowering pass lowers each match statement into a conditional statement, with a branch for each arm.
+//! The condition of a branch compares the parts of the matched value with the literals in the pattern of its arm,
+//! and the block of a branch starts with the definitions of the variables that the pattern binds.
+//! The pass runs on the type checked AST, which guarantees that the arms are exhaustive,
+//! so the last arm becomes the `else` block of the conditional statement.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! match get_point() {
+//!     Point { x: 0u32, y } => {
+//!         return y;
+//!     }
+//!     _ => {
+//!         return 0u32;
+//!     }
+//! }
+//! ```
+//!
+//! The match lowering pass produces the following code.
+//! ```leo
+//! let $match$0: Point = get_point();
+//! if $match$0.x == 0u32 {
+//!     let y: u32 = $match$0.y;
+//!     return y;
+//! } else {
+//!     return 0u32;
+//! }
+//! ```
+
+pub mod match_lowerer;
+pub use match_lowerer::*;
+
+use crate::Pass;
+
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
+
+impl Pass for MatchLowerer {
+    type Input = (Ast, MatchTypes);
+    type Output = Ast;
+
+    fn do_pass((ast, types): Self::Input) -> Self::Output {
+        let mut reconstructor = MatchLowerer::new(types, NodeBuilder::for_program(ast.as_repr()));
+        Ast::new(reconstructor.reconstruct_program(ast.into_repr()))
+    }
+}
@@ -0,0 +1,347 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::IntegerType;
+
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+/// A bound of an interval, between `-u128::MAX` and `u128::MAX`, so that it holds every value of every integer type.
+/// Arithmetic returns `None` when a bound leaves that range, which no value of any integer type does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bound {
+    /// Whether the bound is below zero, which is never the case for a zero magnitude.
+    negative: bool,
+    /// The distance of the bound from zero.
+    magnitude: u128,
+}
+
+impl Bound {
+    /// Returns the bound `-magnitude` if `negative` is `true`, and `magnitude` otherwise.
+    pub fn new(negative: bool, magnitude: u128) -> Self {
+        Self {
+            negative: negative && magnitude != 0,
+            magnitude,
+        }
+    }
+
+    /// Returns the bound as a `u32`, if it is one.
+    pub fn to_u32(self) -> Option<u32> {
+        match self.negative {
+            true => None,
+            false => u32::try_from(self.magnitude).ok(),
+        }
+    }
+
+    /// Returns `self + other`.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        if self.negative == other.negative {
+            Some(Self::new(self.negative, self.magnitude.checked_add(other.magnitude)?))
+        } else if self.magnitude >= other.magnitude {
+            Some(Self::new(self.negative, self.magnitude - other.magnitude))
+        } else {
+            Some(Self::new(other.negative, other.magnitude - self.magnitude))
+        }
+    }
+
+    /// Returns `self - other`.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(-other)
+    }
+
+    /// Returns `self * other`.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.negative != other.negative,
+            self.magnitude.checked_mul(other.magnitude)?,
+        ))
+    }
+
+    /// Returns `self ** exponent`.
+    pub fn checked_pow(self, exponent: u32) -> Option<Self> {
+        Some(Self::new(
+            self.negative && exponent % 2 == 1,
+            self.magnitude.checked_pow(exponent)?,
+        ))
+    }
+}
+
+impl From<i128> for Bound {
+    fn from(value: i128) -> Self {
+        Self::new(value < 0, value.unsigned_abs())
+    }
+}
+
+impl From<u128> for Bound {
+    fn from(value: u128) -> Self {
+        Self::new(false, value)
+    }
+}
+
+impl std::ops::Neg for Bound {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(!self.negative, self.magnitude)
+    }
+}
+
+impl Ord for Bound {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for Bound {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for Bound {
+    type Err = std::num::ParseIntError;
+
+    /// Parses a bound from its decimal digits, with a leading `-` if it is negative.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('-') {
+            Some(magnitude) => Ok(Self::new(true, magnitude.parse()?)),
+            None => Ok(Self::new(false, s.parse()?)),
+        }
+    }
+}
+
+impl fmt::Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.negative {
+            true => write!(f, "-{}", self.magnitude),
+            false => write!(f, "{}", self.magnitude),
+        }
+    }
+}
+
+/// The values an integer expression can take, as the closed range `min..=max` of its type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Interval {
+    /// The type of the values.
+    pub type_: IntegerType,
+    /// The smallest value.
+    pub min: Bound,
+    /// The largest value.
+    pub max: Bound,
+}
+
+impl Interval {
+    /// Returns the interval containing only `value`.
+    pub fn point(type_: IntegerType, value: Bound) -> Self {
+        Self {
+            type_,
+            min: value,
+            max: value,
+        }
+    }
+
+    /// Returns the bounds of `type_`.
+    fn type_bounds(type_: IntegerType) -> (Bound, Bound) {
+        match type_ {
+            IntegerType::U8 => (0u128.into(), (u8::MAX as u128).into()),
+            IntegerType::U16 => (0u128.into(), (u16::MAX as u128).into()),
+            IntegerType::U32 => (0u128.into(), (u32::MAX as u128).into()),
+            IntegerType::U64 => (0u128.into(), (u64::MAX as u128).into()),
+            IntegerType::U128 => (0u128.into(), u128::MAX.into()),
+            IntegerType::I8 => ((i8::MIN as i128).into(), (i8::MAX as i128).into()),
+            IntegerType::I16 => ((i16::MIN as i128).into(), (i16::MAX as i128).into()),
+            IntegerType::I32 => ((i32::MIN as i128).into(), (i32::MAX as i128).into()),
+            IntegerType::I64 => ((i64::MIN as i128).into(), (i64::MAX as i128).into()),
+            IntegerType::I128 => (i128::MIN.into(), i128::MAX.into()),
+        }
+    }
+
+    /// Returns `true` if every value in the interval is a value of its type.
+    pub fn fits(&self) -> bool {
+        let (min, max) = Self::type_bounds(self.type_);
+        min <= self.min && self.max <= max
+    }
+
+    /// Returns the smallest interval of `type_` containing each of `values`.
+    fn hull(type_: IntegerType, values: impl IntoIterator<Item = Option<Bound>>) -> Option<Self> {
+        let values = values.into_iter().collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            type_,
+            min: *values.iter().min()?,
+            max: *values.iter().max()?,
+        })
+    }
+
+    /// Returns the smallest interval containing both `self` and `other`.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            type_: self.type_,
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Returns the values of `self + other`.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        Self::hull(
+            self.type_,
+            [self.min.checked_add(other.min), self.max.checked_add(other.max)],
+        )
+    }
+
+    /// Returns the values of `self - other`.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        Self::hull(
+            self.type_,
+            [self.min.checked_sub(other.max), self.max.checked_sub(other.min)],
+        )
+    }
+
+    /// Returns the values of `self * other`.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        Self::hull(
+            self.type_,
+            [
+                self.min.checked_mul(other.min),
+                self.min.checked_mul(other.max),
+                self.max.checked_mul(other.min),
+                self.max.checked_mul(other.max),
+            ],
+        )
+    }
+
+    /// Returns the values of `self ** exponent`.
+    pub fn checked_pow(self, exponent: Self) -> Option<Self> {
+        let min_exponent = exponent.min.to_u32()?;
+        let max_exponent = exponent.max.to_u32()?;
+
+        // The extremes are at the bounds of the base, or at zero, raised to the largest exponents of either parity.
+        // The smallest exponent is included for bases whose powers shrink, i.e. zero.
+        let zero = Bound::from(0u128);
+        let mut bases = vec![self.min, self.max];
+        if self.min < zero && self.max > zero {
+            bases.push(zero);
+        }
+        let mut exponents = vec![min_exponent, max_exponent];
+        if max_exponent > min_exponent {
+            exponents.push(max_exponent - 1);
+        }
+
+        Self::hull(
+            self.type_,
+            bases
+                .iter()
+                .flat_map(|base| exponents.iter().map(move |exponent| base.checked_pow(*exponent))),
+        )
+    }
+
+    /// Returns the values of `-self`.
+    pub fn checked_neg(self) -> Option<Self> {
+        Self::hull(self.type_, [Some(-self.max), Some(-self.min)])
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.min == self.max {
+            true => write!(f, "{}{}", self.min, self.type_),
+            false => write!(f, "{}{}..={}{}", self.min, self.type_, self.max, self.type_),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(type_: IntegerType, min: &str, max: &str) -> Interval {
+        Interval {
+            type_,
+            min: min.parse().unwrap(),
+            max: max.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn bounds_are_ordered_across_zero() {
+        let values = [
+            "-340282366920938463463374607431768211455",
+            "-1",
+            "0",
+            "1",
+            "340282366920938463463374607431768211455",
+        ];
+        let bounds: Vec<Bound> = values.iter().map(|value| value.parse().unwrap()).collect();
+        assert!(bounds.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!("-0".parse::<Bound>().unwrap(), Bound::from(0u128));
+        assert_eq!(Bound::from(i128::MIN).to_string(), i128::MIN.to_string());
+    }
+
+    #[test]
+    fn u128_values_above_i128_max_fit() {
+        let large = interval(IntegerType::U128, "0", &u128::MAX.to_string());
+        assert!(large.fits());
+        assert!(!interval(IntegerType::I128, "0", &(i128::MAX as u128 + 1).to_string()).fits());
+    }
+
+    #[test]
+    fn products_that_overflow_do_not_fit() {
+        let half = interval(IntegerType::U128, "0", &(u128::MAX / 2).to_string());
+        let two = interval(IntegerType::U128, "2", "2");
+        assert!(half.checked_mul(two).unwrap().fits());
+
+        // A product of `u128`s that overflows exceeds every bound, which no type holds.
+        let three = interval(IntegerType::U128, "3", "3");
+        assert_eq!(half.checked_mul(three), None);
+
+        let large = interval(IntegerType::U64, "0", &u64::MAX.to_string());
+        let two = interval(IntegerType::U64, "2", "2");
+        assert!(!large.checked_mul(two).unwrap().fits());
+    }
+
+    #[test]
+    fn subtraction_can_underflow_unsigned_types() {
+        let left = interval(IntegerType::U8, "0", "10");
+        let right = interval(IntegerType::U8, "1", "1");
+        assert_eq!(left.checked_sub(right), Some(interval(IntegerType::U8, "-1", "9")));
+        assert!(!left.checked_sub(right).unwrap().fits());
+    }
+
+    #[test]
+    fn multiplication_of_signed_intervals() {
+        let left = interval(IntegerType::I8, "-3", "2");
+        let right = interval(IntegerType::I8, "-4", "5");
+        assert_eq!(left.checked_mul(right), Some(interval(IntegerType::I8, "-15", "12")));
+    }
+
+    #[test]
+    fn powers_of_signed_intervals() {
+        let base = interval(IntegerType::I16, "-3", "2");
+        let exponent = interval(IntegerType::U8, "2", "3");
+        assert_eq!(base.checked_pow(exponent), Some(interval(IntegerType::I16, "-27", "9")));
+    }
+
+    #[test]
+    fn negation_of_the_smallest_value_does_not_fit() {
+        let smallest = interval(IntegerType::I128, &i128::MIN.to_string(), &i128::MIN.to_string());
+        assert!(!smallest.checked_neg().unwrap().fits());
+        assert_eq!(interval(IntegerType::I8, "-3", "2").to_string(), "-3i8..=2i8");
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! A range analysis over programs in static single assignment form,
+//! which reports arithmetic that can overflow on operands with known bounds,
+//! and proves the assertions that always hold on them.
+
+pub mod interval;
+pub use interval::*;
+
+pub mod proven_assert;
+pub use proven_assert::*;
+
+pub mod range_analyzer;
+pub use range_analyzer::*;
+
+use crate::Pass;
+
+use leo_ast::{Ast, ProgramVisitor};
+use leo_errors::emitter::Handler;
+
+impl<'a> Pass for RangeAnalyzer<'a> {
+    type Input = (&'a Ast, &'a Handler);
+    type Output = Vec<ProvenAssert>;
+
+    fn do_pass((ast, handler): Self::Input) -> Self::Output {
+        let mut visitor = RangeAnalyzer::new(handler);
+        visitor.visit_program(ast.as_repr());
+        visitor.emit_warnings();
+        visitor.proven_asserts()
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_span::{Span, Symbol};
+
+use std::fmt;
+
+/// An assertion that the range analysis proved to always hold.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProvenAssert {
+    /// The function containing the assertion, or whose finalize block contains it.
+    pub function: Symbol,
+    /// Why the assertion always holds, e.g. `3u8 < 10u8`, or why it holds in each iteration of an unrolled loop.
+    pub reason: String,
+    /// The span of the assertion.
+    pub span: Span,
+}
+
+impl fmt::Display for ProvenAssert {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "always holds, since {}", self.reason)
+    }
+}
@@ -0,0 +1,281 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Interval, ProvenAssert};
+
+use leo_ast::*;
+use leo_errors::{emitter::Handler, LintWarning};
+use leo_span::{Span, Symbol};
+
+use indexmap::IndexMap;
+
+/// An operation that can overflow.
+struct Overflow {
+    /// The name of the operation, e.g. `addition`.
+    operation: &'static str,
+    /// The method that performs the operation with wrapping, without the `_wrapped` suffix.
+    method: &'static str,
+    /// The values of the left operand.
+    left: Interval,
+    /// The values of the right operand.
+    right: Interval,
+}
+
+/// Tracks the intervals of integer variables through a program in static single assignment form,
+/// and warns when an addition, multiplication, or exponentiation of bounded operands can overflow.
+///
+/// A variable is bounded if its value is derived from literals, e.g. through loop unrolling or the phi functions of conditionals.
+/// Operations on parameters and other unbounded values are not reported, since any such operation could overflow.
+///
+/// The analyzer also proves assertions that always hold, i.e. comparisons of bounded operands whose intervals decide them.
+pub struct RangeAnalyzer<'a> {
+    /// The handler that warnings are emitted to.
+    handler: &'a Handler,
+    /// The function being analyzed.
+    function: Symbol,
+    /// The intervals of the bounded variables in the current function.
+    intervals: IndexMap<Symbol, Interval>,
+    /// The boolean variables in the current function that are always `true`, with the reason they are.
+    facts: IndexMap<Symbol, String>,
+    /// The operations that can overflow, keyed by their span.
+    /// An operation in an unrolled loop is analyzed once per iteration, but reported once.
+    overflows: IndexMap<Span, Overflow>,
+    /// The assertions, keyed by their span, with their proof if they hold in every analysis of them.
+    asserts: IndexMap<Span, Option<ProvenAssert>>,
+    /// Whether the current function is annotated `@optimize(none)`, so that its assertions are kept.
+    keep_asserts: bool,
+}
+
+impl<'a> RangeAnalyzer<'a> {
+    /// Returns a new `RangeAnalyzer` that emits warnings to `handler`.
+    pub fn new(handler: &'a Handler) -> Self {
+        Self {
+            handler,
+            function: Symbol::intern(""),
+            intervals: IndexMap::new(),
+            facts: IndexMap::new(),
+            overflows: IndexMap::new(),
+            asserts: IndexMap::new(),
+            keep_asserts: false,
+        }
+    }
+
+    /// Returns the assertions that are proven to always hold.
+    pub fn proven_asserts(&self) -> Vec<ProvenAssert> {
+        self.asserts.values().flatten().cloned().collect()
+    }
+
+    /// Reports each operation that can overflow, with the values of its operands across all of its analyses.
+    pub fn emit_warnings(&self) {
+        for (span, overflow) in self.overflows.iter() {
+            self.handler.emit_warning(LintWarning::possible_overflow(
+                overflow.operation,
+                overflow.left.type_,
+                overflow.left,
+                overflow.right,
+                overflow.method,
+                *span,
+            ));
+        }
+    }
+
+    /// Returns the interval of `input`, or `None` if it is not a bounded integer.
+    /// Records the operations in `input` that can overflow.
+    fn evaluate(&mut self, input: &Expression) -> Option<Interval> {
+        match input {
+            Expression::Literal(Literal::Integer(type_, value, ..)) => {
+                value.parse().ok().map(|value| Interval::point(*type_, value))
+            }
+            Expression::Identifier(identifier) => self.intervals.get(&identifier.name).copied(),
+            Expression::Binary(binary) => {
+                let left = self.evaluate(&binary.left);
+                let right = self.evaluate(&binary.right);
+                let (left, right) = (left?, right?);
+                match binary.op {
+                    BinaryOperation::Add => self.check(binary, "addition", "add", left, right, left.checked_add(right)),
+                    BinaryOperation::Mul => {
+                        self.check(binary, "multiplication", "mul", left, right, left.checked_mul(right))
+                    }
+                    BinaryOperation::Pow => {
+                        self.check(binary, "exponentiation", "pow", left, right, left.checked_pow(right))
+                    }
+                    BinaryOperation::Sub => left.checked_sub(right).filter(Interval::fits),
+                    _ => None,
+                }
+            }
+            Expression::Unary(unary) => match unary.op {
+                UnaryOperation::Negate => self.evaluate(&unary.receiver)?.checked_neg().filter(Interval::fits),
+                _ => None,
+            },
+            // Widening keeps the values, only changing their type.
+            Expression::Cast(cast) => self.evaluate(&cast.expression).map(|interval| Interval {
+                type_: cast.type_,
+                ..interval
+            }),
+            Expression::Ternary(ternary) => {
+                let if_true = self.evaluate(&ternary.if_true);
+                let if_false = self.evaluate(&ternary.if_false);
+                Some(if_true?.union(if_false?))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the reason `input` is always `true`, or `None` if it is not proven to be.
+    fn prove(&mut self, input: &Expression) -> Option<String> {
+        match input {
+            Expression::Literal(Literal::Boolean(true, ..)) => Some("the condition is `true`".to_string()),
+            Expression::Identifier(identifier) => self.facts.get(&identifier.name).cloned(),
+            Expression::Binary(binary) => self.prove_comparison(binary.op, &binary.left, &binary.right),
+            _ => None,
+        }
+    }
+
+    /// Returns the reason `left op right` is always `true`, or `None` if the intervals of its operands do not decide it.
+    fn prove_comparison(&mut self, op: BinaryOperation, left: &Expression, right: &Expression) -> Option<String> {
+        let left = self.evaluate(left);
+        let right = self.evaluate(right);
+        let (left, right) = (left?, right?);
+        let holds = match op {
+            BinaryOperation::Lt => left.max < right.min,
+            BinaryOperation::Lte => left.max <= right.min,
+            BinaryOperation::Gt => left.min > right.max,
+            BinaryOperation::Gte => left.min >= right.max,
+            BinaryOperation::Eq => left.min == left.max && left == right,
+            BinaryOperation::Neq => left.max < right.min || right.max < left.min,
+            _ => false,
+        };
+        holds.then(|| format!("{} {} {}", left, op, right))
+    }
+
+    /// Records the proof of the assertion at `span`, which only stands if it holds in every analysis of the assertion.
+    /// The proof of an assertion in an unrolled loop lists the reason it holds in each iteration.
+    /// The assertions of a function annotated `@optimize(none)` are recorded without a proof, so that they are kept.
+    fn record_assert(&mut self, span: Span, reason: Option<String>) {
+        let reason = reason.filter(|_| !self.keep_asserts);
+        let proof = reason.map(|reason| ProvenAssert {
+            function: self.function,
+            reason,
+            span,
+        });
+        self.asserts
+            .entry(span)
+            .and_modify(|existing| match (existing.as_mut(), &proof) {
+                (Some(existing), Some(proof)) => {
+                    if !existing.reason.split(", ").any(|reason| reason == proof.reason) {
+                        existing.reason = format!("{}, {}", existing.reason, proof.reason);
+                    }
+                }
+                _ => *existing = None,
+            })
+            .or_insert(proof);
+    }
+
+    /// Returns `result` if it fits in its type, and otherwise records that `binary` can overflow.
+    fn check(
+        &mut self,
+        binary: &BinaryExpression,
+        operation: &'static str,
+        method: &'static str,
+        left: Interval,
+        right: Interval,
+        result: Option<Interval>,
+    ) -> Option<Interval> {
+        let overflows = match result {
+            Some(result) => !result.fits(),
+            // A bound of the result exceeds the values of every integer type.
+            None => true,
+        };
+        if overflows {
+            self.overflows
+                .entry(binary.span)
+                .and_modify(|overflow| {
+                    overflow.left = overflow.left.union(left);
+                    overflow.right = overflow.right.union(right);
+                })
+                .or_insert(Overflow {
+                    operation,
+                    method,
+                    left,
+                    right,
+                });
+        }
+        result.filter(Interval::fits)
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for RangeAnalyzer<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+}
+
+impl<'a> StatementVisitor<'a> for RangeAnalyzer<'a> {
+    fn visit_assign(&mut self, input: &'a AssignStatement) {
+        let interval = self.evaluate(&input.value);
+        // In static single assignment form, each variable is assigned exactly once.
+        if let Expression::Identifier(place) = &input.place {
+            if let Some(interval) = interval {
+                self.intervals.insert(place.name, interval);
+            }
+            // The conditions of assertions are assigned to variables, so comparisons are proven when they are assigned.
+            if let Some(reason) = self.prove(&input.value) {
+                self.facts.insert(place.name, reason);
+            }
+        }
+    }
+
+    fn visit_conditional(&mut self, input: &'a ConditionalStatement) {
+        self.visit_block(&input.then);
+        if let Some(otherwise) = &input.otherwise {
+            self.visit_statement(otherwise);
+        }
+    }
+
+    fn visit_console(&mut self, input: &'a ConsoleStatement) {
+        let reason = match &input.function {
+            ConsoleFunction::Assert(expression, _) => {
+                self.evaluate(expression);
+                self.prove(expression)
+            }
+            ConsoleFunction::AssertEq(left, right, _) => self.prove_comparison(BinaryOperation::Eq, left, right),
+            ConsoleFunction::AssertNeq(left, right, _) => self.prove_comparison(BinaryOperation::Neq, left, right),
+        };
+        self.record_assert(input.span, reason);
+    }
+
+    fn visit_return(&mut self, input: &'a ReturnStatement) {
+        self.evaluate(&input.expression);
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for RangeAnalyzer<'a> {
+    fn visit_import(&mut self, _input: &'a Program) {
+        // Imported programs are analyzed when they are compiled themselves.
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        self.function = input.identifier.name;
+        self.keep_asserts = input.is_unoptimized();
+        self.intervals.clear();
+        self.facts.clear();
+        self.visit_block(&input.block);
+        if let Some(finalize) = &input.finalize {
+            self.intervals.clear();
+            self.facts.clear();
+            self.visit_block(&finalize.block);
+        }
+    }
+}
@@ -0,0 +1,373 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Pass, UseCollector};
+
+use leo_ast::*;
+use leo_errors::{RefactorError, Result};
+use leo_span::{Span, Symbol};
+
+use indexmap::{IndexMap, IndexSet};
+
+/// Extracts a range of statements into a new function and replaces them with a call to it.
+///
+/// The statements are selected by a span, which must cover whole statements of a single block.
+/// Variables read by the selection but declared before it become the parameters of the new function,
+/// and the single variable declared by the selection and used after it becomes its return value.
+pub struct FunctionExtractor {
+    /// The span covering the statements to extract.
+    selection: Span,
+    /// The name of the function to create.
+    name: Symbol,
+    /// The types of the variables declared in the function being traversed.
+    declarations: IndexMap<Symbol, Type>,
+    /// Whether the function being traversed is a transition function.
+    is_transition_function: bool,
+    /// Whether the traversal is inside a finalize block.
+    is_finalize: bool,
+    /// Whether the selected statements have been found.
+    found: bool,
+    /// The extracted function, if it has not yet been added to the program scope.
+    extracted: Option<Function>,
+    /// The first error encountered while extracting.
+    error: Option<RefactorError>,
+    /// Allocates the ids of the new function and of the call to it.
+    node_builder: NodeBuilder,
+}
+
+impl FunctionExtractor {
+    /// Returns a new `FunctionExtractor` that extracts the statements in `selection` into a function named `name`.
+    /// The ids of the new nodes are allocated with `node_builder`.
+    pub fn new(selection: Span, name: Symbol, node_builder: NodeBuilder) -> Self {
+        Self {
+            selection,
+            name,
+            declarations: IndexMap::new(),
+            is_transition_function: false,
+            is_finalize: false,
+            found: false,
+            extracted: None,
+            error: None,
+            node_builder,
+        }
+    }
+
+    /// Records the types of all variables declared in `statements`, including those in nested blocks.
+    fn collect_declarations<'a>(
+        declarations: &mut IndexMap<Symbol, Type>,
+        statements: impl Iterator<Item = &'a Statement>,
+    ) {
+        for statement in statements {
+            match statement {
+                Statement::Block(block) => Self::collect_declarations(declarations, block.statements.iter()),
+                Statement::Conditional(conditional) => {
+                    Self::collect_declarations(declarations, conditional.then.statements.iter());
+                    if let Some(otherwise) = &conditional.otherwise {
+                        Self::collect_declarations(declarations, std::iter::once(otherwise.as_ref()));
+                    }
+                }
+                Statement::Definition(definition) => {
+                    declarations.insert(definition.variable_name.name, definition.type_.clone());
+                }
+                Statement::Iteration(iteration) => {
+                    declarations.insert(iteration.variable.name, iteration.type_.clone());
+                    Self::collect_declarations(declarations, iteration.block.statements.iter());
+                }
+                // The types of the variables bound by patterns are not written, so only the arm blocks are collected.
+                Statement::Match(match_) => match_
+                    .arms
+                    .iter()
+                    .for_each(|arm| Self::collect_declarations(declarations, arm.block.statements.iter())),
+                _ => {}
+            }
+        }
+    }
+
+    /// Extracts `selected` into a new function, returning the statement that replaces it.
+    /// `rest` holds the statements that follow the selection in its block.
+    fn extract(
+        &mut self,
+        selected: Vec<Statement>,
+        rest: &[Statement],
+        span: Span,
+    ) -> Result<Statement, RefactorError> {
+        if self.is_finalize || !self.is_transition_function {
+            return Err(RefactorError::cannot_extract_function(
+                "only statements in a transition function can be extracted, since other functions cannot make calls",
+                span,
+            ));
+        }
+
+        let mut uses = UseCollector::default();
+        selected.iter().for_each(|statement| uses.visit_statement(statement));
+        if uses.has_call {
+            return Err(RefactorError::cannot_extract_function(
+                "the selection contains a function call, which a standard function cannot make",
+                span,
+            ));
+        }
+        if uses.has_control_flow {
+            return Err(RefactorError::cannot_extract_function(
+                "the selection contains a `return`, `finalize`, `increment`, or `decrement` statement, or an operation on a mapping",
+                span,
+            ));
+        }
+
+        // The variables declared anywhere in the selection.
+        let mut locals = IndexMap::new();
+        Self::collect_declarations(&mut locals, selected.iter());
+        if let Some(write) = uses.writes.iter().find(|write| !locals.contains_key(*write)) {
+            return Err(RefactorError::cannot_extract_function(
+                format!("the selection assigns to `{write}`, which is declared outside of it"),
+                span,
+            ));
+        }
+
+        // The variables declared by the selection and read after it.
+        let mut later_uses = UseCollector::default();
+        rest.iter().for_each(|statement| later_uses.visit_statement(statement));
+        let outputs: IndexSet<Symbol> = selected
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::Definition(definition) => Some(definition.variable_name.name),
+                _ => None,
+            })
+            .filter(|name| later_uses.reads.contains(name) || later_uses.writes.contains(name))
+            .collect();
+        let output = match outputs.len() {
+            1 => outputs[0],
+            0 => {
+                return Err(RefactorError::cannot_extract_function(
+                    "the selection does not declare a variable that is used after it",
+                    span,
+                ))
+            }
+            _ => {
+                return Err(RefactorError::cannot_extract_function(
+                    format!(
+                        "the selection declares more than one variable that is used after it: {}",
+                        outputs
+                            .iter()
+                            .map(|name| format!("`{name}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    span,
+                ))
+            }
+        };
+        let output_type = locals[&output].clone();
+
+        // The variables read by the selection but declared before it become parameters.
+        let parameters: Vec<(Symbol, Type)> = uses
+            .reads
+            .iter()
+            .filter(|read| !locals.contains_key(*read))
+            .filter_map(|read| self.declarations.get(read).map(|type_| (*read, type_.clone())))
+            .collect();
+
+        let function = parameters
+            .iter()
+            .fold(
+                FunctionBuilder::function(&self.node_builder, self.name),
+                |function, (name, type_)| function.input(*name, type_.clone()),
+            )
+            .output(output_type.clone());
+        self.extracted = Some(
+            selected
+                .into_iter()
+                .fold(function, FunctionBuilder::statement)
+                .returns(ExprBuilder::identifier(&self.node_builder, output))
+                .build(),
+        );
+
+        let mut call = ExprBuilder::call(
+            &self.node_builder,
+            self.name,
+            parameters
+                .into_iter()
+                .map(|(name, _)| ExprBuilder::identifier(&self.node_builder, name)),
+        )
+        .build();
+        call.set_span(span);
+        Ok(Statement::Definition(DefinitionStatement {
+            declaration_type: DeclarationType::Let,
+            variable_name: Identifier::new(output, self.node_builder.next_id()),
+            type_: output_type,
+            value: call,
+            span,
+            id: self.node_builder.next_id(),
+        }))
+    }
+}
+
+impl ExpressionReconstructor for FunctionExtractor {
+    type AdditionalOutput = ();
+}
+
+impl StatementReconstructor for FunctionExtractor {
+    fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
+        let overlaps = |span: Span| span.lo < self.selection.hi && self.selection.lo < span.hi;
+        let selected: Vec<usize> = input
+            .statements
+            .iter()
+            .enumerate()
+            .filter(|(_, statement)| self.selection.contains(statement.span()))
+            .map(|(i, _)| i)
+            .collect();
+
+        // If this block does not contain the selection, look for it in the nested blocks.
+        if self.found || selected.is_empty() {
+            return (
+                Block {
+                    statements: input
+                        .statements
+                        .into_iter()
+                        .map(|statement| self.reconstruct_statement(statement).0)
+                        .collect(),
+                    span: input.span,
+                    id: input.id,
+                },
+                Default::default(),
+            );
+        }
+
+        self.found = true;
+        let (start, end) = (selected[0], selected[selected.len() - 1] + 1);
+        let partial = input
+            .statements
+            .iter()
+            .any(|statement| overlaps(statement.span()) && !self.selection.contains(statement.span()));
+        if partial || end - start != selected.len() {
+            self.error = Some(RefactorError::cannot_extract_function(
+                "the selection must cover whole statements of a single block",
+                self.selection,
+            ));
+            return (input, Default::default());
+        }
+
+        let mut statements = input.statements;
+        let rest = statements.split_off(end);
+        let extracted: Vec<Statement> = statements.drain(start..).collect();
+        let span = extracted
+            .iter()
+            .map(|statement| statement.span())
+            .reduce(|a, b| a + b)
+            .unwrap_or(self.selection);
+
+        match self.extract(extracted.clone(), &rest, span) {
+            Ok(call) => statements.push(call),
+            Err(error) => {
+                self.error = Some(error);
+                statements.extend(extracted);
+            }
+        }
+        statements.extend(rest);
+
+        (
+            Block {
+                statements,
+                span: input.span,
+                id: input.id,
+            },
+            Default::default(),
+        )
+    }
+}
+
+impl ProgramReconstructor for FunctionExtractor {
+    fn reconstruct_program_scope(&mut self, input: ProgramScope) -> ProgramScope {
+        let name_taken = input.functions.keys().any(|id| id.name == self.name)
+            || input.structs.keys().any(|id| id.name == self.name)
+            || input.mappings.keys().any(|id| id.name == self.name);
+        if name_taken && input.span.contains(self.selection) {
+            self.error = Some(RefactorError::cannot_extract_function(
+                format!("`{}` is already defined in the program", self.name),
+                self.selection,
+            ));
+            return input;
+        }
+
+        let mut functions = IndexMap::with_capacity(input.functions.len() + 1);
+        for (id, function) in input.functions {
+            functions.insert(id, self.reconstruct_function(function));
+            // Place the extracted function directly after the function it was extracted from.
+            if let Some(extracted) = self.extracted.take() {
+                functions.insert(extracted.identifier, extracted);
+            }
+        }
+
+        ProgramScope {
+            program_id: input.program_id,
+            structs: input.structs,
+            mappings: input.mappings,
+            functions,
+            span: input.span,
+        }
+    }
+
+    fn reconstruct_function(&mut self, input: Function) -> Function {
+        self.declarations.clear();
+        input.input.iter().for_each(|input| {
+            self.declarations.insert(input.identifier().name, input.type_());
+        });
+        Self::collect_declarations(&mut self.declarations, input.block.statements.iter());
+        self.is_transition_function = matches!(input.call_type, CallType::Transition);
+
+        self.is_finalize = false;
+        let block = self.reconstruct_block(input.block).0;
+        self.is_finalize = true;
+        let finalize = input.finalize.map(|finalize| Finalize {
+            identifier: finalize.identifier,
+            input: finalize.input,
+            output: finalize.output,
+            output_type: finalize.output_type,
+            block: self.reconstruct_block(finalize.block).0,
+            span: finalize.span,
+        });
+        self.is_finalize = false;
+
+        Function {
+            documentation: input.documentation,
+            annotations: input.annotations,
+            call_type: input.call_type,
+            identifier: input.identifier,
+            input: input.input,
+            output: input.output,
+            output_type: input.output_type,
+            block,
+            finalize,
+            span: input.span,
+        }
+    }
+}
+
+impl Pass for FunctionExtractor {
+    type Input = (Ast, Span, Symbol);
+    type Output = Result<Ast>;
+
+    fn do_pass((ast, selection, name): Self::Input) -> Self::Output {
+        let node_builder = NodeBuilder::for_program(ast.as_repr());
+        let mut extractor = Self::new(selection, name, node_builder);
+        let program = extractor.reconstruct_program(ast.into_repr());
+
+        match (extractor.found, extractor.error) {
+            (_, Some(error)) => Err(error.into()),
+            (false, None) => Err(RefactorError::no_refactoring_target("statement", selection).into()),
+            (true, None) => Ok(Ast::new(program)),
+        }
+    }
+}
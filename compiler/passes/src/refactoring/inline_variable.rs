@@ -0,0 +1,182 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Pass, UseCollector};
+
+use leo_ast::*;
+use leo_errors::{RefactorError, Result};
+use leo_span::{Span, Symbol};
+
+/// Inlines a `let` or `const` binding into each of its uses and removes the binding.
+///
+/// The binding is selected by a span that lies within its definition statement.
+/// Inlining is rejected if the variable, or any variable its value depends on,
+/// is reassigned after the binding, since the substituted value would then differ.
+pub struct VariableInliner {
+    /// A span within the definition to inline.
+    target: Span,
+    /// Whether the target definition has been found.
+    found: bool,
+    /// The first error encountered while inlining.
+    error: Option<RefactorError>,
+}
+
+impl VariableInliner {
+    /// Returns a new `VariableInliner` for the definition enclosing `target`.
+    pub fn new(target: Span) -> Self {
+        Self {
+            target,
+            found: false,
+            error: None,
+        }
+    }
+
+    /// Substitutes the value of `definition` into `statements`, the statements that follow it in its block.
+    fn inline(
+        &self,
+        definition: DefinitionStatement,
+        statements: Vec<Statement>,
+    ) -> Result<Vec<Statement>, RefactorError> {
+        let name = definition.variable_name.name;
+
+        // Find the variables that the value depends on.
+        let mut dependencies = UseCollector::default();
+        dependencies.visit_expression(&definition.value, &());
+
+        // Find the variables that are reassigned after the definition.
+        let mut uses = UseCollector::default();
+        statements.iter().for_each(|statement| uses.visit_statement(statement));
+
+        if uses.writes.contains(&name) {
+            return Err(RefactorError::cannot_inline_variable(
+                name,
+                "it is reassigned after its definition",
+                definition.span,
+            ));
+        }
+        if let Some(dependency) = dependencies.reads.iter().find(|read| uses.writes.contains(*read)) {
+            return Err(RefactorError::cannot_inline_variable(
+                name,
+                format!("its value depends on `{dependency}`, which is reassigned after the definition"),
+                definition.span,
+            ));
+        }
+
+        let mut substitutor = Substitutor {
+            name,
+            value: definition.value,
+        };
+        Ok(statements
+            .into_iter()
+            .map(|statement| substitutor.reconstruct_statement(statement).0)
+            .collect())
+    }
+}
+
+impl ExpressionReconstructor for VariableInliner {
+    type AdditionalOutput = ();
+}
+
+impl StatementReconstructor for VariableInliner {
+    fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
+        let mut statements = Vec::with_capacity(input.statements.len());
+        let mut iter = input.statements.into_iter();
+        while let Some(statement) = iter.next() {
+            match statement {
+                Statement::Definition(definition) if !self.found && definition.span.contains(self.target) => {
+                    self.found = true;
+                    match self.inline(definition, iter.by_ref().collect()) {
+                        Ok(rest) => statements.extend(rest),
+                        Err(error) => self.error = Some(error),
+                    }
+                }
+                statement => statements.push(self.reconstruct_statement(statement).0),
+            }
+        }
+
+        (
+            Block {
+                statements,
+                span: input.span,
+                id: input.id,
+            },
+            Default::default(),
+        )
+    }
+}
+
+impl ProgramReconstructor for VariableInliner {}
+
+impl Pass for VariableInliner {
+    type Input = (Ast, Span);
+    type Output = Result<Ast>;
+
+    fn do_pass((ast, target): Self::Input) -> Self::Output {
+        let mut inliner = Self::new(target);
+        let program = inliner.reconstruct_program(ast.into_repr());
+
+        match (inliner.found, inliner.error) {
+            (_, Some(error)) => Err(error.into()),
+            (false, None) => Err(RefactorError::no_refactoring_target("variable definition", target).into()),
+            (true, None) => Ok(Ast::new(program)),
+        }
+    }
+}
+
+/// Replaces each use of a variable with an expression.
+struct Substitutor {
+    /// The variable to replace.
+    name: Symbol,
+    /// The expression to substitute for the variable.
+    value: Expression,
+}
+
+impl ExpressionReconstructor for Substitutor {
+    type AdditionalOutput = ();
+
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Struct(StructExpression {
+                name: input.name,
+                members: input
+                    .members
+                    .into_iter()
+                    .map(|member| StructVariableInitializer {
+                        expression: match member.expression {
+                            Some(expression) => Some(self.reconstruct_expression(expression).0),
+                            // Expand the shorthand `Foo { x }` if `x` is the variable being replaced.
+                            None if member.identifier.name == self.name => Some(self.value.clone()),
+                            None => None,
+                        },
+                        identifier: member.identifier,
+                    })
+                    .collect(),
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_identifier(&mut self, input: Identifier) -> (Expression, Self::AdditionalOutput) {
+        match input.name == self.name {
+            true => (self.value.clone(), Default::default()),
+            false => (Expression::Identifier(input), Default::default()),
+        }
+    }
+}
+
+impl StatementReconstructor for Substitutor {}
@@ -0,0 +1,31 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Semantic refactorings over the AST, used by editor tooling.
+//! Each refactoring is a reconstructor that locates its target by span and
+//! rejects the transformation when it would change the meaning of the program.
+
+pub mod extract_function;
+pub use extract_function::*;
+
+pub mod inline_variable;
+pub use inline_variable::*;
+
+pub mod rename;
+pub use rename::*;
+
+mod use_collector;
+pub(crate) use use_collector::*;
@@ -0,0 +1,609 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Pass;
+
+use leo_ast::*;
+use leo_errors::{RefactorError, Result};
+use leo_span::{Span, Symbol};
+
+use indexmap::IndexSet;
+
+/// The kind of item being renamed, along with its current name.
+#[derive(Clone, Copy)]
+enum Renaming {
+    Function(Symbol),
+    Struct(Symbol),
+    Variable(Symbol),
+}
+
+/// Renames a function, struct, record, or variable, along with each of its uses.
+///
+/// The item is selected by a span that lies within the name at its declaration.
+/// A variable is renamed only within its scope, so that variables of the same name in sibling scopes are unchanged.
+/// Imported programs are left unchanged.
+pub struct Renamer {
+    /// A span within the name to rename.
+    target: Span,
+    /// The new name.
+    name: Symbol,
+    /// The item being renamed, once it has been found.
+    renaming: Option<Renaming>,
+    /// Whether the renamed variable is in scope.
+    in_scope: bool,
+}
+
+impl Renamer {
+    /// Returns a new `Renamer` that renames the item declared at `target` to `name`.
+    pub fn new(target: Span, name: Symbol) -> Self {
+        Self {
+            target,
+            name,
+            renaming: None,
+            in_scope: false,
+        }
+    }
+
+    /// Returns `identifier` with the new name.
+    fn renamed(&self, identifier: Identifier) -> Identifier {
+        Identifier {
+            name: self.name,
+            span: identifier.span,
+            id: identifier.id,
+        }
+    }
+
+    /// Returns `true` if `identifier` refers to the item being renamed when used as an expression.
+    fn refers_to_target(&self, identifier: &Identifier) -> bool {
+        match self.renaming {
+            Some(Renaming::Function(name)) => identifier.name == name,
+            Some(Renaming::Variable(name)) => self.in_scope && identifier.name == name,
+            _ => false,
+        }
+    }
+
+    /// Renames `identifier` and brings the variable into scope if it declares the target variable.
+    fn declare(&mut self, identifier: Identifier) -> Identifier {
+        if self.renaming.is_none() && identifier.span.contains(self.target) {
+            self.renaming = Some(Renaming::Variable(identifier.name));
+            self.in_scope = true;
+            self.renamed(identifier)
+        } else {
+            identifier
+        }
+    }
+
+    /// Renames the variables bound by `pattern` and the struct it matches, bringing the variable into scope if it is bound.
+    fn reconstruct_pattern(&mut self, pattern: Pattern) -> Pattern {
+        match pattern {
+            Pattern::Binding(identifier) => Pattern::Binding(self.declare(identifier)),
+            Pattern::Tuple(tuple) => Pattern::Tuple(TuplePattern {
+                elements: tuple
+                    .elements
+                    .into_iter()
+                    .map(|element| self.reconstruct_pattern(element))
+                    .collect(),
+                span: tuple.span,
+            }),
+            Pattern::Struct(struct_) => Pattern::Struct(StructPattern {
+                name: self.reconstruct_item_name(struct_.name),
+                members: struct_
+                    .members
+                    .into_iter()
+                    .map(|member| StructMemberPattern {
+                        pattern: match member.pattern {
+                            Some(pattern) => Some(self.reconstruct_pattern(pattern)),
+                            // Expand the shorthand `Foo { x }` if it binds the variable being renamed.
+                            None => {
+                                let binding = self.declare(member.identifier);
+                                (binding.name != member.identifier.name).then(|| Pattern::Binding(binding))
+                            }
+                        },
+                        identifier: member.identifier,
+                    })
+                    .collect(),
+                span: struct_.span,
+            }),
+            pattern => pattern,
+        }
+    }
+
+    /// Renames the struct in `type_`, if it is being renamed.
+    fn reconstruct_type(&self, type_: Type) -> Type {
+        match type_ {
+            Type::Identifier(identifier) => match self.renaming {
+                Some(Renaming::Struct(name)) if identifier.name == name => Type::Identifier(self.renamed(identifier)),
+                _ => Type::Identifier(identifier),
+            },
+            Type::Mapping(mapping) => Type::Mapping(MappingType {
+                key: Box::new(self.reconstruct_type(*mapping.key)),
+                value: Box::new(self.reconstruct_type(*mapping.value)),
+            }),
+            Type::Tuple(tuple) => Type::Tuple(Tuple(
+                tuple.0.into_iter().map(|type_| self.reconstruct_type(type_)).collect(),
+            )),
+            type_ => type_,
+        }
+    }
+
+    /// Renames the parameters and parameter types in `inputs`.
+    fn reconstruct_inputs(&mut self, inputs: Vec<Input>) -> Vec<Input> {
+        inputs
+            .into_iter()
+            .map(|input| match input {
+                Input::Internal(input) => Input::Internal(FunctionInput {
+                    identifier: self.declare(input.identifier),
+                    mode: input.mode,
+                    type_: self.reconstruct_type(input.type_),
+                    span: input.span,
+                }),
+                input => input,
+            })
+            .collect()
+    }
+
+    /// Renames the output types in `outputs`.
+    fn reconstruct_outputs(&self, outputs: Vec<Output>) -> Vec<Output> {
+        outputs
+            .into_iter()
+            .map(|output| match output {
+                Output::Internal(output) => Output::Internal(FunctionOutput {
+                    mode: output.mode,
+                    type_: self.reconstruct_type(output.type_),
+                    span: output.span,
+                }),
+                output => output,
+            })
+            .collect()
+    }
+
+    /// Renames the declaration of a function or struct if it encloses the target.
+    fn find_item(&mut self, input: &ProgramScope) {
+        if let Some(struct_) = input.structs.values().find(|s| s.identifier.span.contains(self.target)) {
+            self.renaming = Some(Renaming::Struct(struct_.identifier.name));
+        } else if let Some(function) = input
+            .functions
+            .values()
+            .find(|f| f.identifier.span.contains(self.target))
+        {
+            self.renaming = Some(Renaming::Function(function.identifier.name));
+        }
+    }
+
+    /// Renames `identifier` if it names the function or struct being renamed.
+    fn reconstruct_item_name(&self, identifier: Identifier) -> Identifier {
+        match self.renaming {
+            Some(Renaming::Function(name) | Renaming::Struct(name)) if identifier.name == name => {
+                self.renamed(identifier)
+            }
+            _ => identifier,
+        }
+    }
+}
+
+impl ExpressionReconstructor for Renamer {
+    type AdditionalOutput = ();
+
+    fn reconstruct_call(&mut self, input: CallExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Call(CallExpression {
+                function: match input.external {
+                    // Calls to other programs are not renamed.
+                    Some(_) => input.function,
+                    None => Box::new(self.reconstruct_expression(*input.function).0),
+                },
+                arguments: input
+                    .arguments
+                    .into_iter()
+                    .map(|argument| self.reconstruct_expression(argument).0)
+                    .collect(),
+                external: input.external,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Struct(StructExpression {
+                name: self.reconstruct_item_name(input.name),
+                members: input
+                    .members
+                    .into_iter()
+                    .map(|member| StructVariableInitializer {
+                        expression: match member.expression {
+                            Some(expression) => Some(self.reconstruct_expression(expression).0),
+                            // Expand the shorthand `Foo { x }` if `x` is the variable being renamed.
+                            None if self.refers_to_target(&member.identifier) => {
+                                Some(Expression::Identifier(self.renamed(member.identifier)))
+                            }
+                            None => None,
+                        },
+                        identifier: member.identifier,
+                    })
+                    .collect(),
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_identifier(&mut self, input: Identifier) -> (Expression, Self::AdditionalOutput) {
+        match self.refers_to_target(&input) {
+            true => (Expression::Identifier(self.renamed(input)), Default::default()),
+            false => (Expression::Identifier(input), Default::default()),
+        }
+    }
+}
+
+impl StatementReconstructor for Renamer {
+    fn reconstruct_assign(&mut self, input: AssignStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Assign(Box::new(AssignStatement {
+                place: self.reconstruct_expression(input.place).0,
+                value: self.reconstruct_expression(input.value).0,
+                span: input.span,
+                id: input.id,
+            })),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
+        // A variable declared in this block goes out of scope at the end of the block.
+        let in_scope = self.in_scope;
+        let statements = input
+            .statements
+            .into_iter()
+            .map(|statement| self.reconstruct_statement(statement).0)
+            .collect();
+        self.in_scope = in_scope;
+
+        (
+            Block {
+                statements,
+                span: input.span,
+                id: input.id,
+            },
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_decrement(&mut self, input: DecrementStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Decrement(DecrementStatement {
+                mapping: input.mapping,
+                index: self.reconstruct_expression(input.index).0,
+                amount: self.reconstruct_expression(input.amount).0,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_match(&mut self, input: MatchStatement) -> (Statement, Self::AdditionalOutput) {
+        let expression = self.reconstruct_expression(input.expression).0;
+        let arms = input
+            .arms
+            .into_iter()
+            .map(|arm| {
+                // The variables bound by the pattern are scoped to the block of the arm.
+                let in_scope = self.in_scope;
+                let pattern = self.reconstruct_pattern(arm.pattern);
+                let block = self.reconstruct_block(arm.block).0;
+                self.in_scope = in_scope;
+                MatchArm {
+                    pattern,
+                    block,
+                    span: arm.span,
+                }
+            })
+            .collect();
+
+        (
+            Statement::Match(MatchStatement {
+                expression,
+                arms,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_definition(&mut self, input: DefinitionStatement) -> (Statement, Self::AdditionalOutput) {
+        // The value is reconstructed first, since the variable is not in scope within its own definition.
+        let value = self.reconstruct_expression(input.value).0;
+        (
+            Statement::Definition(DefinitionStatement {
+                declaration_type: input.declaration_type,
+                variable_name: self.declare(input.variable_name),
+                type_: self.reconstruct_type(input.type_),
+                value,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_increment(&mut self, input: IncrementStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Increment(IncrementStatement {
+                mapping: input.mapping,
+                index: self.reconstruct_expression(input.index).0,
+                amount: self.reconstruct_expression(input.amount).0,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_iteration(&mut self, input: IterationStatement) -> (Statement, Self::AdditionalOutput) {
+        let start = self.reconstruct_expression(input.start).0;
+        let stop = self.reconstruct_expression(input.stop).0;
+
+        // The loop variable is scoped to the body of the loop.
+        let in_scope = self.in_scope;
+        let variable = self.declare(input.variable);
+        let block = self.reconstruct_block(input.block).0;
+        self.in_scope = in_scope;
+
+        (
+            Statement::Iteration(Box::new(IterationStatement {
+                variable,
+                type_: self.reconstruct_type(input.type_),
+                start,
+                start_value: input.start_value,
+                stop,
+                stop_value: input.stop_value,
+                block,
+                inclusive: input.inclusive,
+                span: input.span,
+                id: input.id,
+            })),
+            Default::default(),
+        )
+    }
+}
+
+impl ProgramReconstructor for Renamer {
+    fn reconstruct_program_scope(&mut self, input: ProgramScope) -> ProgramScope {
+        self.find_item(&input);
+
+        ProgramScope {
+            program_id: input.program_id,
+            structs: input
+                .structs
+                .into_iter()
+                .map(|(identifier, struct_)| (self.reconstruct_item_name(identifier), self.reconstruct_struct(struct_)))
+                .collect(),
+            mappings: input
+                .mappings
+                .into_iter()
+                .map(|(identifier, mapping)| (identifier, self.reconstruct_mapping(mapping)))
+                .collect(),
+            functions: input
+                .functions
+                .into_iter()
+                .map(|(identifier, function)| {
+                    (
+                        self.reconstruct_item_name(identifier),
+                        self.reconstruct_function(function),
+                    )
+                })
+                .collect(),
+            span: input.span,
+        }
+    }
+
+    fn reconstruct_function(&mut self, input: Function) -> Function {
+        // The parameters of a function are in scope throughout its body, but not in its finalize block.
+        let input_ = self.reconstruct_inputs(input.input);
+        let block = self.reconstruct_block(input.block).0;
+        self.in_scope = false;
+
+        let finalize = input.finalize.map(|finalize| {
+            let input = self.reconstruct_inputs(finalize.input);
+            let block = self.reconstruct_block(finalize.block).0;
+            self.in_scope = false;
+            Finalize {
+                identifier: self.reconstruct_item_name(finalize.identifier),
+                input,
+                output: self.reconstruct_outputs(finalize.output),
+                output_type: self.reconstruct_type(finalize.output_type),
+                block,
+                span: finalize.span,
+            }
+        });
+
+        Function {
+            documentation: input.documentation,
+            annotations: input.annotations,
+            call_type: input.call_type,
+            identifier: self.reconstruct_item_name(input.identifier),
+            input: input_,
+            output: self.reconstruct_outputs(input.output),
+            output_type: self.reconstruct_type(input.output_type),
+            block,
+            finalize,
+            span: input.span,
+        }
+    }
+
+    fn reconstruct_struct(&mut self, input: Struct) -> Struct {
+        Struct {
+            documentation: input.documentation,
+            identifier: self.reconstruct_item_name(input.identifier),
+            members: input
+                .members
+                .into_iter()
+                .map(|member| Member {
+                    identifier: member.identifier,
+                    type_: self.reconstruct_type(member.type_),
+                })
+                .collect(),
+            is_record: input.is_record,
+            span: input.span,
+        }
+    }
+
+    fn reconstruct_import(&mut self, input: Program) -> Program {
+        // Imported programs cannot refer to items in the importing program.
+        input
+    }
+
+    fn reconstruct_mapping(&mut self, input: Mapping) -> Mapping {
+        Mapping {
+            documentation: input.documentation,
+            identifier: input.identifier,
+            key_type: self.reconstruct_type(input.key_type),
+            value_type: self.reconstruct_type(input.value_type),
+            span: input.span,
+        }
+    }
+}
+
+impl Pass for Renamer {
+    type Input = (Ast, Span, Symbol);
+    type Output = Result<Ast>;
+
+    fn do_pass((ast, target, name): Self::Input) -> Self::Output {
+        // The names declared at the top level of the program, which the new name must not clash with.
+        let items: IndexSet<Symbol> = ast
+            .as_repr()
+            .program_scopes
+            .values()
+            .flat_map(|scope| {
+                scope
+                    .structs
+                    .keys()
+                    .chain(scope.mappings.keys())
+                    .chain(scope.functions.keys())
+                    .map(|identifier| identifier.name)
+            })
+            .collect();
+
+        let mut renamer = Self::new(target, name);
+        let program = renamer.reconstruct_program(ast.into_repr());
+
+        let old_name = match renamer.renaming {
+            None => {
+                return Err(RefactorError::no_refactoring_target("function, struct, or variable name", target).into())
+            }
+            Some(Renaming::Function(name) | Renaming::Struct(name) | Renaming::Variable(name)) => name,
+        };
+        if !leo_parser::is_identifier(&name.to_string()) {
+            return Err(
+                RefactorError::cannot_rename(old_name, name, "the new name is not an identifier", target).into(),
+            );
+        }
+        if name != old_name && items.contains(&name) {
+            return Err(RefactorError::cannot_rename(
+                old_name,
+                name,
+                "a function, struct, or mapping with the new name already exists",
+                target,
+            )
+            .into());
+        }
+
+        Ok(Ast::new(program))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use leo_errors::emitter::Handler;
+    use leo_span::{span::BytePos, symbol::create_session_if_not_set_then};
+
+    const PROGRAM: &str = "program test.aleo {
+    struct point {
+        x: u8,
+    }
+
+    function double(a: u8) -> u8 {
+        return a + a;
+    }
+
+    transition main(a: u8) -> u8 {
+        let p: point = point { x: a };
+        if a > 1u8 {
+            let sum: u8 = double(p.x);
+            return sum;
+        }
+        let sum: u8 = a;
+        return sum;
+    }
+}
+";
+
+    /// Renames the item declared at the `occurrence`th match of `target` in `PROGRAM` to `name`,
+    /// returning the printed program or the message of the error.
+    fn rename(target: &str, occurrence: usize, name: &str) -> std::result::Result<String, String> {
+        create_session_if_not_set_then(|_| {
+            let ast = leo_parser::parse_ast(&Handler::default(), PROGRAM, Default::default()).unwrap();
+            let (lo, _) = PROGRAM.match_indices(target).nth(occurrence).unwrap();
+            let span = Span::new(BytePos(lo as u32), BytePos((lo + target.len()) as u32));
+            Renamer::do_pass((ast, span, Symbol::intern(name)))
+                .map(|ast| leo_ast::pretty::program(ast.as_repr()))
+                .map_err(|err| err.to_string())
+        })
+    }
+
+    #[test]
+    fn variable_is_renamed_in_its_scope_only() {
+        let program = rename("sum", 0, "total").unwrap();
+        assert!(program.contains("let total: u8 = double(p.x);"));
+        assert!(program.contains("return total;"));
+        assert!(program.contains("let sum: u8 = a;"));
+        assert!(program.contains("return sum;"));
+    }
+
+    #[test]
+    fn function_is_renamed_with_its_calls() {
+        let program = rename("double", 0, "twice").unwrap();
+        assert!(program.contains("function twice(a: u8) -> u8"));
+        assert!(program.contains("let sum: u8 = twice(p.x);"));
+        assert!(!program.contains("double"));
+    }
+
+    #[test]
+    fn struct_is_renamed_with_its_types_and_values() {
+        let program = rename("point", 0, "Point").unwrap();
+        assert!(program.contains("struct Point {"));
+        assert!(program.contains("let p: Point = Point { x: a };"));
+        assert!(!program.contains("point"));
+    }
+
+    #[test]
+    fn invalid_names_are_rejected() {
+        assert!(rename("sum", 0, "1sum")
+            .unwrap_err()
+            .contains("the new name is not an identifier"));
+        assert!(rename("sum", 0, "double")
+            .unwrap_err()
+            .contains("a function, struct, or mapping with the new name already exists"));
+        assert!(rename("return", 0, "result").is_err());
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::*;
+use leo_span::{sym, Symbol};
+
+use indexmap::IndexSet;
+
+/// Collects the variables read and written by a sequence of statements.
+#[derive(Default)]
+pub(crate) struct UseCollector {
+    /// The variables read, in order of first use.
+    pub(crate) reads: IndexSet<Symbol>,
+    /// The variables assigned to.
+    pub(crate) writes: IndexSet<Symbol>,
+    /// Whether a function call was encountered.
+    pub(crate) has_call: bool,
+    /// Whether a `return`, `finalize`, `increment`, or `decrement` statement, or an operation on a mapping, was encountered.
+    pub(crate) has_control_flow: bool,
+}
+
+impl UseCollector {
+    /// Returns the variable at the root of an assignment place, e.g. `a` in `a.b.c`.
+    fn place_root(place: &Expression) -> Option<Symbol> {
+        match place {
+            Expression::Identifier(identifier) => Some(identifier.name),
+            Expression::Access(AccessExpression::Member(member)) => Self::place_root(&member.inner),
+            Expression::Access(AccessExpression::Tuple(tuple)) => Self::place_root(&tuple.tuple),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for UseCollector {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_access(&mut self, input: &'a AccessExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        match input {
+            AccessExpression::AssociatedFunction(function) => {
+                // Mappings are only accessed in finalize blocks, so an operation on one cannot be moved out of it.
+                if matches!(&function.ty, Type::Identifier(module) if module.name == sym::Mapping) {
+                    self.has_control_flow = true;
+                }
+                function.args.iter().for_each(|arg| {
+                    self.visit_expression(arg, additional);
+                });
+            }
+            AccessExpression::Member(member) => self.visit_expression(&member.inner, additional),
+            AccessExpression::Tuple(tuple) => self.visit_expression(&tuple.tuple, additional),
+            AccessExpression::AssociatedConstant(_) => {}
+        }
+    }
+
+    fn visit_call(&mut self, input: &'a CallExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        self.has_call = true;
+        input.arguments.iter().for_each(|argument| {
+            self.visit_expression(argument, additional);
+        });
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        for member in input.members.iter() {
+            match &member.expression {
+                Some(expression) => self.visit_expression(expression, additional),
+                // The shorthand `Foo { x }` reads the variable `x`.
+                None => {
+                    self.reads.insert(member.identifier.name);
+                }
+            }
+        }
+    }
+
+    fn visit_identifier(&mut self, input: &'a Identifier, _additional: &Self::AdditionalInput) -> Self::Output {
+        self.reads.insert(input.name);
+    }
+}
+
+impl<'a> StatementVisitor<'a> for UseCollector {
+    fn visit_assign(&mut self, input: &'a AssignStatement) {
+        if let Some(root) = Self::place_root(&input.place) {
+            self.writes.insert(root);
+        }
+        self.visit_expression(&input.value, &Default::default());
+    }
+
+    fn visit_decrement(&mut self, input: &'a DecrementStatement) {
+        self.has_control_flow = true;
+        self.visit_expression(&input.amount, &Default::default());
+        self.visit_expression(&input.index, &Default::default());
+    }
+
+    fn visit_finalize(&mut self, input: &'a FinalizeStatement) {
+        self.has_control_flow = true;
+        input.arguments.iter().for_each(|argument| {
+            self.visit_expression(argument, &Default::default());
+        });
+    }
+
+    fn visit_increment(&mut self, input: &'a IncrementStatement) {
+        self.has_control_flow = true;
+        self.visit_expression(&input.amount, &Default::default());
+        self.visit_expression(&input.index, &Default::default());
+    }
+
+    fn visit_return(&mut self, input: &'a ReturnStatement) {
+        self.has_control_flow = true;
+        self.visit_expression(&input.expression, &Default::default());
+    }
+}
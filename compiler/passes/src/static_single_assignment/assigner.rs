@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use leo_ast::{AssignStatement, Expression, Identifier, Statement};
+use leo_ast::{AssignStatement, Expression, Identifier, NodeBuilder, Statement};
 use leo_span::Symbol;
 use std::fmt::Display;
 
@@ -23,9 +23,19 @@ use std::fmt::Display;
 pub struct Assigner {
     /// A strictly increasing counter, used to ensure that new variable names are unique.
     pub(crate) counter: usize,
+    /// Allocates the ids of the nodes created by the passes that use the assigner.
+    pub(crate) node_builder: NodeBuilder,
 }
 
 impl Assigner {
+    /// Returns a new assigner, which allocates the ids of new nodes with `node_builder`.
+    pub(crate) fn new(node_builder: NodeBuilder) -> Self {
+        Self {
+            counter: 0,
+            node_builder,
+        }
+    }
+
     /// Return a new unique `Symbol` from a `&str`.
     pub(crate) fn unique_symbol(&mut self, arg: impl Display) -> Symbol {
         self.counter += 1;
@@ -39,6 +49,7 @@ impl Assigner {
             place: Expression::Identifier(identifier),
             value,
             span: Default::default(),
+            id: self.node_builder.next_id(),
         }))
     }
 
@@ -51,6 +62,7 @@ impl Assigner {
         let place = Identifier {
             name,
             span: Default::default(),
+            id: self.node_builder.next_id(),
         };
 
         (place, self.simple_assign_statement(place, expr))
@@ -67,7 +67,7 @@ pub use static_single_assigner::*;
 
 use crate::{Pass, SymbolTable};
 
-use leo_ast::{Ast, ProgramConsumer};
+use leo_ast::{Ast, NodeBuilder, ProgramConsumer};
 use leo_errors::Result;
 
 impl<'a> Pass for StaticSingleAssigner<'a> {
@@ -75,7 +75,7 @@ impl<'a> Pass for StaticSingleAssigner<'a> {
     type Output = Result<(Ast, Assigner)>;
 
     fn do_pass((ast, symbol_table): Self::Input) -> Self::Output {
-        let mut consumer = StaticSingleAssigner::new(symbol_table);
+        let mut consumer = StaticSingleAssigner::new(symbol_table, NodeBuilder::for_program(ast.as_repr()));
         let program = consumer.consume_program(ast.into_repr());
 
         Ok((Ast::new(program), consumer.assigner))
@@ -19,9 +19,9 @@ use indexmap::IndexMap;
 use std::borrow::Borrow;
 
 use leo_ast::{
-    AccessExpression, AssociatedFunction, BinaryExpression, CallExpression, Expression, ExpressionConsumer, Identifier,
-    Literal, MemberAccess, Statement, Struct, StructExpression, StructVariableInitializer, TernaryExpression,
-    TupleAccess, TupleExpression, UnaryExpression,
+    AccessExpression, AssociatedFunction, BinaryExpression, CallExpression, CastExpression, Expression,
+    ExpressionConsumer, Identifier, Literal, MemberAccess, Statement, Struct, StructExpression,
+    StructVariableInitializer, TernaryExpression, TupleAccess, TupleExpression, UnaryExpression,
 };
 use leo_span::{sym, Symbol};
 
@@ -47,6 +47,7 @@ impl ExpressionConsumer for StaticSingleAssigner<'_> {
                             })
                             .collect(),
                         span: function.span,
+                        id: function.id,
                     }),
                     statements,
                 )
@@ -66,6 +67,7 @@ impl ExpressionConsumer for StaticSingleAssigner<'_> {
                         inner: Box::new(expr),
                         name: member.name,
                         span: member.span,
+                        id: member.id,
                     }),
                     statements,
                 )
@@ -77,6 +79,7 @@ impl ExpressionConsumer for StaticSingleAssigner<'_> {
                         tuple: Box::new(expr),
                         index: tuple.index,
                         span: tuple.span,
+                        id: tuple.id,
                     }),
                     statements,
                 )
@@ -106,6 +109,7 @@ impl ExpressionConsumer for StaticSingleAssigner<'_> {
                 right: Box::new(right_expression),
                 op: input.op,
                 span: input.span,
+                id: input.id,
             }));
         statements.push(statement);
 
@@ -137,6 +141,26 @@ impl ExpressionConsumer for StaticSingleAssigner<'_> {
                 arguments,
                 external: input.external,
                 span: input.span,
+                id: input.id,
+            }));
+        statements.push(statement);
+
+        (Expression::Identifier(place), statements)
+    }
+
+    /// Consumes a cast expression, accumulating any statements that are generated.
+    fn consume_cast(&mut self, input: CastExpression) -> Self::Output {
+        // Reconstruct the converted expression.
+        let (expression, mut statements) = self.consume_expression(*input.expression);
+
+        // Construct and accumulate a new assignment statement for the cast expression.
+        let (place, statement) = self
+            .assigner
+            .unique_simple_assign_statement(Expression::Cast(CastExpression {
+                expression: Box::new(expression),
+                type_: input.type_,
+                span: input.span,
+                id: input.id,
             }));
         statements.push(statement);
 
@@ -213,6 +237,7 @@ impl ExpressionConsumer for StaticSingleAssigner<'_> {
             .unique_simple_assign_statement(Expression::Struct(StructExpression {
                 name: input.name,
                 span: input.span,
+                id: input.id,
                 members: reordered_members,
             }));
         statements.push(statement);
@@ -240,6 +265,7 @@ impl ExpressionConsumer for StaticSingleAssigner<'_> {
             Expression::Identifier(Identifier {
                 name,
                 span: identifier.span,
+                id: identifier.id,
             }),
             Default::default(),
         )
@@ -271,6 +297,7 @@ impl ExpressionConsumer for StaticSingleAssigner<'_> {
                 if_true: Box::new(if_true_expr),
                 if_false: Box::new(if_false_expr),
                 span: input.span,
+                id: input.id,
             }));
         statements.push(statement);
 
@@ -298,6 +325,7 @@ impl ExpressionConsumer for StaticSingleAssigner<'_> {
             Expression::Tuple(TupleExpression {
                 elements,
                 span: input.span,
+                id: input.id,
             }),
             statements,
         )
@@ -315,6 +343,7 @@ impl ExpressionConsumer for StaticSingleAssigner<'_> {
                 op: input.op,
                 receiver: Box::new(receiver),
                 span: input.span,
+                id: input.id,
             }));
         statements.push(statement);
 
@@ -73,6 +73,7 @@ impl FunctionConsumer for StaticSingleAssigner<'_> {
 
         let block = Block {
             span: function.block.span,
+            id: function.block.id,
             statements: self.consume_block(function.block),
         };
 
@@ -92,6 +93,7 @@ impl FunctionConsumer for StaticSingleAssigner<'_> {
 
             let block = Block {
                 span: finalize.block.span,
+                id: finalize.block.id,
                 statements: self.consume_block(finalize.block),
             };
 
@@ -109,6 +111,7 @@ impl FunctionConsumer for StaticSingleAssigner<'_> {
         });
 
         Function {
+            documentation: function.documentation,
             annotations: function.annotations,
             call_type: function.call_type,
             identifier: function.identifier,
@@ -159,6 +162,7 @@ impl ProgramConsumer for StaticSingleAssigner<'_> {
                 .into_iter()
                 .map(|(name, scope)| (name, self.consume_program_scope(scope)))
                 .collect(),
+            import_source: input.import_source,
         }
     }
 }
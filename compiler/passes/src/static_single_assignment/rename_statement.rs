@@ -17,9 +17,10 @@
 use crate::{RenameTable, StaticSingleAssigner};
 
 use leo_ast::{
-    AssignStatement, Block, ConditionalStatement, ConsoleFunction, ConsoleStatement, DecrementStatement,
-    DefinitionStatement, Expression, ExpressionConsumer, FinalizeStatement, Identifier, IncrementStatement,
-    IterationStatement, ReturnStatement, Statement, StatementConsumer, TernaryExpression,
+    AccessExpression, AssignStatement, AssociatedFunction, Block, CallExpression, ConditionalStatement,
+    ConsoleFunction, ConsoleStatement, DecrementStatement, DefinitionStatement, Expression, ExpressionConsumer,
+    ExpressionStatement, FinalizeStatement, Identifier, IncrementStatement, IterationStatement, MatchStatement, NodeID,
+    ReturnStatement, Statement, StatementConsumer, TernaryExpression,
 };
 use leo_span::Symbol;
 
@@ -73,6 +74,7 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
         // Consume the then-block.
         let then = Block {
             span: conditional.then.span,
+            id: conditional.then.id,
             statements: self.consume_block(conditional.then),
         };
 
@@ -86,10 +88,12 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
         let otherwise = conditional.otherwise.map(|otherwise| Box::new(Statement::Block(match *otherwise {
             Statement::Block(block) => Block {
                 span: block.span,
+                id: block.id,
                 statements: self.consume_block(block),
             },
             Statement::Conditional(conditional) => Block {
                 span: conditional.span,
+                id: self.assigner.node_builder.next_id(),
                 statements: self.consume_conditional(conditional),
             },
             _ => unreachable!("Type checking guarantees that the otherwise-block of a conditional statement is a block or another conditional statement."),
@@ -101,6 +105,7 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
         // Add reconstructed conditional statement to the list of produced statements.
         statements.push(Statement::Conditional(ConditionalStatement {
             span: conditional.span,
+            id: conditional.id,
             condition: condition.clone(),
             then,
             otherwise,
@@ -116,13 +121,14 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
             // Note that phi functions only need to be instantiated if the variable exists before the `ConditionalStatement`.
             if self.rename_table.lookup(**symbol).is_some() {
                 // Helper to lookup a symbol and create an argument for the phi function.
-                let create_phi_argument = |table: &RenameTable, symbol: Symbol| {
+                let create_phi_argument = |table: &RenameTable, symbol: Symbol, id: NodeID| {
                     let name = *table
                         .lookup(symbol)
                         .unwrap_or_else(|| panic!("Symbol {} should exist in the program.", symbol));
                     Box::new(Expression::Identifier(Identifier {
                         name,
                         span: Default::default(),
+                        id,
                     }))
                 };
 
@@ -131,9 +137,10 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
 
                 let (value, stmts) = self.consume_ternary(TernaryExpression {
                     condition: Box::new(condition.clone()),
-                    if_true: create_phi_argument(&if_table, **symbol),
-                    if_false: create_phi_argument(&else_table, **symbol),
+                    if_true: create_phi_argument(&if_table, **symbol, self.assigner.node_builder.next_id()),
+                    if_false: create_phi_argument(&else_table, **symbol, self.assigner.node_builder.next_id()),
                     span: Default::default(),
+                    id: self.assigner.node_builder.next_id(),
                 });
 
                 statements.extend(stmts);
@@ -143,6 +150,7 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
                     Identifier {
                         name: new_name,
                         span: Default::default(),
+                        id: self.assigner.node_builder.next_id(),
                     },
                     value,
                 );
@@ -161,29 +169,38 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
     /// Consumes the expressions in a `ConsoleStatement`, returning the list of simplified statements.
     fn consume_console(&mut self, input: ConsoleStatement) -> Self::Output {
         let (function, mut statements) = match input.function {
-            ConsoleFunction::Assert(expr) => {
-                let (expr, statements) = self.consume_expression(expr);
-                (ConsoleFunction::Assert(expr), statements)
+            ConsoleFunction::Assert(expr, message) => {
+                let (expr, mut statements) = self.consume_expression(expr);
+                let (message, message_statements) = self.consume_assert_message(message);
+                statements.extend(message_statements);
+
+                (ConsoleFunction::Assert(expr, message), statements)
             }
-            ConsoleFunction::AssertEq(left, right) => {
+            ConsoleFunction::AssertEq(left, right, message) => {
                 // Reconstruct the lhs of the binary expression.
                 let (left, mut statements) = self.consume_expression(left);
                 // Reconstruct the rhs of the binary expression.
                 let (right, right_statements) = self.consume_expression(right);
+                // Reconstruct the parameters of the message.
+                let (message, message_statements) = self.consume_assert_message(message);
                 // Accumulate any statements produced.
                 statements.extend(right_statements);
+                statements.extend(message_statements);
 
-                (ConsoleFunction::AssertEq(left, right), statements)
+                (ConsoleFunction::AssertEq(left, right, message), statements)
             }
-            ConsoleFunction::AssertNeq(left, right) => {
+            ConsoleFunction::AssertNeq(left, right, message) => {
                 // Reconstruct the lhs of the binary expression.
                 let (left, mut statements) = self.consume_expression(left);
                 // Reconstruct the rhs of the binary expression.
                 let (right, right_statements) = self.consume_expression(right);
+                // Reconstruct the parameters of the message.
+                let (message, message_statements) = self.consume_assert_message(message);
                 // Accumulate any statements produced.
                 statements.extend(right_statements);
+                statements.extend(message_statements);
 
-                (ConsoleFunction::AssertNeq(left, right), statements)
+                (ConsoleFunction::AssertNeq(left, right, message), statements)
             }
         };
 
@@ -191,6 +208,7 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
         statements.push(Statement::Console(ConsoleStatement {
             function,
             span: input.span,
+            id: input.id,
         }));
 
         statements
@@ -210,6 +228,7 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
             index,
             amount,
             span: input.span,
+            id: input.id,
         }));
 
         statements
@@ -234,6 +253,53 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
         statements
     }
 
+    /// Consumes the arguments of the call in the `ExpressionStatement`, returning the simplified `ExpressionStatement`.
+    fn consume_expression_statement(&mut self, input: ExpressionStatement) -> Self::Output {
+        let mut statements = Vec::new();
+
+        // The call itself has no output, so only its arguments are assigned to unique names.
+        let expression = match input.expression {
+            Expression::Access(AccessExpression::AssociatedFunction(function)) => {
+                let args = function
+                    .args
+                    .into_iter()
+                    .map(|arg| {
+                        let (arg, stmts) = self.consume_expression(arg);
+                        statements.extend(stmts);
+                        arg
+                    })
+                    .collect();
+                Expression::Access(AccessExpression::AssociatedFunction(AssociatedFunction {
+                    args,
+                    ..function
+                }))
+            }
+            Expression::Call(call) => {
+                let arguments = call
+                    .arguments
+                    .into_iter()
+                    .map(|argument| {
+                        let (argument, stmts) = self.consume_expression(argument);
+                        statements.extend(stmts);
+                        argument
+                    })
+                    .collect();
+                Expression::Call(CallExpression { arguments, ..call })
+            }
+            _ => {
+                unreachable!("Expression statements only call associated functions, or transitions for their futures.")
+            }
+        };
+
+        statements.push(Statement::Expression(ExpressionStatement {
+            expression,
+            span: input.span,
+            id: input.id,
+        }));
+
+        statements
+    }
+
     /// Consumes the expressions associated with the `FinalizeStatement`, returning the simplified `FinalizeStatement`.
     fn consume_finalize(&mut self, input: FinalizeStatement) -> Self::Output {
         let mut statements = Vec::new();
@@ -253,6 +319,7 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
         statements.push(Statement::Finalize(FinalizeStatement {
             arguments,
             span: input.span,
+            id: input.id,
         }));
 
         statements
@@ -272,6 +339,7 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
             index,
             amount,
             span: input.span,
+            id: input.id,
         }));
 
         statements
@@ -282,6 +350,10 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
         unreachable!("`IterationStatement`s should not be in the AST at this phase of compilation.");
     }
 
+    fn consume_match(&mut self, _input: MatchStatement) -> Self::Output {
+        unreachable!("`MatchStatement`s should not be in the AST at this phase of compilation.");
+    }
+
     /// Reconstructs the expression associated with the return statement, returning a simplified `ReturnStatement`.
     /// Note that type checking guarantees that there is at most one `ReturnStatement` in a block.
     fn consume_return(&mut self, input: ReturnStatement) -> Self::Output {
@@ -292,6 +364,7 @@ impl StatementConsumer for StaticSingleAssigner<'_> {
         statements.push(Statement::Return(ReturnStatement {
             expression,
             span: input.span,
+            id: input.id,
         }));
 
         statements
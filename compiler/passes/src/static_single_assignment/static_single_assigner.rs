@@ -16,6 +16,11 @@
 
 use crate::{Assigner, RenameTable, SymbolTable};
 
+use leo_ast::{
+    AssertMessage, CallExpression, Expression, ExpressionReconstructor, Identifier, NodeBuilder, Statement,
+    StructExpression, StructVariableInitializer,
+};
+
 pub struct StaticSingleAssigner<'a> {
     /// The `SymbolTable` of the program.
     pub(crate) symbol_table: &'a SymbolTable,
@@ -29,12 +34,12 @@ pub struct StaticSingleAssigner<'a> {
 
 impl<'a> StaticSingleAssigner<'a> {
     /// Initializes a new `StaticSingleAssigner` with an empty `RenameTable`.
-    pub(crate) fn new(symbol_table: &'a SymbolTable) -> Self {
+    pub(crate) fn new(symbol_table: &'a SymbolTable, node_builder: NodeBuilder) -> Self {
         Self {
             symbol_table,
             rename_table: RenameTable::new(None),
             is_lhs: false,
-            assigner: Assigner::default(),
+            assigner: Assigner::new(node_builder),
         }
     }
 
@@ -49,4 +54,77 @@ impl<'a> StaticSingleAssigner<'a> {
         let parent = self.rename_table.parent.clone().unwrap_or_default();
         core::mem::replace(&mut self.rename_table, *parent)
     }
+
+    /// Renames the variables in the parameters of an optional `AssertMessage`, returning the renamed message and the list of simplified statements.
+    ///
+    /// The parameters are only evaluated if the assertion fails, to render the message, and code generation drops them,
+    /// so they are not lowered into statements, which would compute them on every run and could halt a run whose assertion holds.
+    pub(crate) fn consume_assert_message(
+        &mut self,
+        message: Option<AssertMessage>,
+    ) -> (Option<AssertMessage>, Vec<Statement>) {
+        let message = message.map(|message| {
+            let mut renamer = MessageRenamer {
+                rename_table: &self.rename_table,
+            };
+            AssertMessage {
+                string: message.string,
+                parameters: message
+                    .parameters
+                    .into_iter()
+                    .map(|parameter| renamer.reconstruct_expression(parameter).0)
+                    .collect(),
+                span: message.span,
+            }
+        });
+        (message, Vec::new())
+    }
+}
+
+/// Renames the variables of an expression to their current names in the `RenameTable`, without simplifying it.
+struct MessageRenamer<'a> {
+    rename_table: &'a RenameTable,
+}
+
+impl ExpressionReconstructor for MessageRenamer<'_> {
+    type AdditionalOutput = ();
+
+    fn reconstruct_call(&mut self, input: CallExpression) -> (Expression, Self::AdditionalOutput) {
+        // The name of the called function is not a variable.
+        (
+            Expression::Call(CallExpression {
+                arguments: input
+                    .arguments
+                    .into_iter()
+                    .map(|argument| self.reconstruct_expression(argument).0)
+                    .collect(),
+                ..input
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        // A member without an expression, e.g. `Point { x }`, is the variable with its name.
+        let members = input
+            .members
+            .into_iter()
+            .map(|member| {
+                let expression = member.expression.unwrap_or(Expression::Identifier(member.identifier));
+                StructVariableInitializer {
+                    identifier: member.identifier,
+                    expression: Some(self.reconstruct_expression(expression).0),
+                }
+            })
+            .collect();
+        (
+            Expression::Struct(StructExpression { members, ..input }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_identifier(&mut self, input: Identifier) -> (Expression, Self::AdditionalOutput) {
+        let name = *self.rename_table.lookup(input.name).unwrap_or(&input.name);
+        (Expression::Identifier(Identifier { name, ..input }), Default::default())
+    }
 }
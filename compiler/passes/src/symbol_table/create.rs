@@ -16,6 +16,7 @@
 
 use leo_ast::*;
 use leo_errors::emitter::Handler;
+use leo_span::Symbol;
 
 use crate::{SymbolTable, VariableSymbol, VariableType};
 
@@ -27,6 +28,8 @@ pub struct CreateSymbolTable<'a> {
     pub(crate) symbol_table: SymbolTable,
     /// The error handler.
     handler: &'a Handler,
+    /// The imported program being visited, if any.
+    program: Option<Symbol>,
 }
 
 impl<'a> CreateSymbolTable<'a> {
@@ -34,6 +37,7 @@ impl<'a> CreateSymbolTable<'a> {
         Self {
             symbol_table: Default::default(),
             handler,
+            program: None,
         }
     }
 }
@@ -47,7 +51,15 @@ impl<'a> StatementVisitor<'a> for CreateSymbolTable<'a> {}
 
 impl<'a> ProgramVisitor<'a> for CreateSymbolTable<'a> {
     fn visit_import(&mut self, input: &'a Program) {
-        self.visit_program(input)
+        // Note that parsing enforces that there is exactly one program scope in a file.
+        let program = input
+            .program_scopes
+            .keys()
+            .next()
+            .map(|program_id| program_id.name.name);
+        let importer = std::mem::replace(&mut self.program, program);
+        self.visit_program(input);
+        self.program = importer;
     }
 
     fn visit_struct(&mut self, input: &'a Struct) {
@@ -74,7 +86,7 @@ impl<'a> ProgramVisitor<'a> for CreateSymbolTable<'a> {
     }
 
     fn visit_function(&mut self, input: &'a Function) {
-        if let Err(err) = self.symbol_table.insert_fn(input.name(), input) {
+        if let Err(err) = self.symbol_table.insert_fn_from(self.program, input.name(), input) {
             self.handler.emit_err(err);
         }
     }
@@ -15,12 +15,14 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use leo_ast::{CallType, Function, Input, Type};
-use leo_span::Span;
+use leo_span::{Span, Symbol};
+
+use serde::{Deserialize, Serialize};
 
 use crate::SymbolTable;
 
 /// Metadata associated with the finalize block.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FinalizeData {
     /// The inputs to the finalize block.
     pub(crate) input: Vec<Input>,
@@ -29,24 +31,26 @@ pub struct FinalizeData {
 }
 
 /// An entry for a function in the symbol table.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FunctionSymbol {
     /// The index associated with the scope in the parent symbol table.
     pub(crate) id: usize,
     /// The output type of the function.
-    pub(crate) output_type: Type,
+    pub output_type: Type,
     /// Is this function a transition, inlined, or a regular function?.
     pub call_type: CallType,
     /// The `Span` associated with the function.
-    pub(crate) span: Span,
+    pub span: Span,
     /// The inputs to the function.
-    pub(crate) input: Vec<Input>,
+    pub input: Vec<Input>,
     /// Metadata associated with the finalize block.
     pub(crate) finalize: Option<FinalizeData>,
+    /// The imported program that declares the function, if it is not declared by the program being compiled.
+    pub program: Option<Symbol>,
 }
 
 impl SymbolTable {
-    pub(crate) fn new_function_symbol(id: usize, func: &Function) -> FunctionSymbol {
+    pub(crate) fn new_function_symbol(id: usize, func: &Function, program: Option<Symbol>) -> FunctionSymbol {
         FunctionSymbol {
             id,
             output_type: func.output_type.clone(),
@@ -57,6 +61,7 @@ impl SymbolTable {
                 input: finalize.input.clone(),
                 output_type: finalize.output_type.clone(),
             }),
+            program,
         }
     }
 }
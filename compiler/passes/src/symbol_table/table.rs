@@ -21,10 +21,11 @@ use leo_errors::{AstError, Result};
 use leo_span::{Span, Symbol};
 
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 
 use crate::{FunctionSymbol, VariableSymbol};
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct SymbolTable {
     /// The parent scope if it exists.
     /// For example, the parent scope of a then-block is the scope containing the associated ConditionalStatement.
@@ -42,6 +43,18 @@ pub struct SymbolTable {
     pub(crate) scope_index: usize,
     /// The sub-scopes of this scope.
     pub(crate) scopes: Vec<RefCell<SymbolTable>>,
+    /// The source covered by this scope, e.g. the body of a function or block.
+    /// The span of the global scope is the dummy span.
+    #[serde(default)]
+    pub(crate) span: Span,
+}
+
+/// A named entry of the symbol table, as returned to tools that query it by position.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ScopeSymbol {
+    Function(FunctionSymbol),
+    Struct(Struct),
+    Variable(VariableSymbol),
 }
 
 impl SymbolTable {
@@ -74,13 +87,39 @@ impl SymbolTable {
 
     /// Inserts a function into the symbol table.
     pub fn insert_fn(&mut self, symbol: Symbol, insert: &Function) -> Result<()> {
+        self.insert_fn_from(None, symbol, insert)
+    }
+
+    /// Inserts a function declared by the imported `program`, or by the program being compiled if it is `None`.
+    pub fn insert_fn_from(&mut self, program: Option<Symbol>, symbol: Symbol, insert: &Function) -> Result<()> {
         self.check_shadowing(symbol, insert.span)?;
         let id = self.scope_index();
-        self.functions.insert(symbol, Self::new_function_symbol(id, insert));
-        self.scopes.push(Default::default());
+        self.functions
+            .insert(symbol, Self::new_function_symbol(id, insert, program));
+        self.scopes.push(RefCell::new(SymbolTable {
+            span: insert.span,
+            ..Default::default()
+        }));
         Ok(())
     }
 
+    /// Replaces the signature of a function already in the symbol table, e.g. after the function has been edited.
+    /// The function's scope is cleared. Returns `false` if there is no such function.
+    pub fn replace_fn(&mut self, symbol: Symbol, insert: &Function) -> bool {
+        match self.functions.get(&symbol).map(|func| (func.id, func.program)) {
+            Some((id, program)) => {
+                self.functions
+                    .insert(symbol, Self::new_function_symbol(id, insert, program));
+                self.scopes[id] = RefCell::new(SymbolTable {
+                    span: insert.span,
+                    ..Default::default()
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Inserts a struct into the symbol table.
     pub fn insert_struct(&mut self, symbol: Symbol, insert: &Struct) -> Result<()> {
         self.check_shadowing(symbol, insert.span)?;
@@ -134,6 +173,43 @@ impl SymbolTable {
         }
     }
 
+    /// Returns the variable in scope whose name is most similar to `symbol`, e.g. the intended name of a misspelled variable.
+    pub fn similar_variable(&self, symbol: Symbol) -> Option<Symbol> {
+        self.similar_name(symbol, |table| table.variables.keys().copied().collect())
+    }
+
+    /// Returns the function whose name is most similar to `symbol`, e.g. the intended name of a misspelled function.
+    pub fn similar_fn(&self, symbol: Symbol) -> Option<Symbol> {
+        self.similar_name(symbol, |table| table.functions.keys().copied().collect())
+    }
+
+    /// Returns the struct whose name is most similar to `symbol`, e.g. the intended name of a misspelled struct.
+    pub fn similar_struct(&self, symbol: Symbol) -> Option<Symbol> {
+        self.similar_name(symbol, |table| table.structs.keys().copied().collect())
+    }
+
+    /// Returns the name most similar to `symbol` among the `names` of this scope and its parents.
+    /// Names that differ in more than a third of their characters are not considered similar.
+    fn similar_name(&self, symbol: Symbol, names: impl Fn(&Self) -> Vec<Symbol>) -> Option<Symbol> {
+        let name = symbol.to_string();
+        let max_distance = std::cmp::max(1, name.chars().count() / 3);
+
+        let mut candidates = Vec::new();
+        let mut table = Some(self);
+        while let Some(scope) = table {
+            candidates.extend(names(scope));
+            table = scope.parent.as_deref();
+        }
+
+        candidates
+            .into_iter()
+            .filter(|candidate| *candidate != symbol)
+            .map(|candidate| (edit_distance(&name, &candidate.to_string()), candidate))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, candidate)| candidate)
+    }
+
     /// Returns true if the variable exists in the local scope
     pub fn variable_in_local_scope(&self, symbol: Symbol) -> bool {
         self.variables.contains_key(&symbol)
@@ -172,4 +248,87 @@ impl SymbolTable {
     pub fn lookup_scope_by_index(&self, index: usize) -> Option<&RefCell<Self>> {
         self.scopes.get(index)
     }
+
+    /// Returns the functions of the program, in the order they are declared.
+    pub fn all_functions(&self) -> impl Iterator<Item = (Symbol, &FunctionSymbol)> {
+        self.functions.iter().map(|(name, function)| (*name, function))
+    }
+
+    /// Resolves `name` as it would be resolved at `span`, i.e. in the innermost scope that contains `span` and its parents.
+    /// Variables declared after `span` are not visible at `span`.
+    /// The table must have been filled in by the type checker, which records the variables and the span of each scope.
+    pub fn lookup_at(&self, span: Span, name: Symbol) -> Option<ScopeSymbol> {
+        self.find_at(span, &mut |scope| {
+            if let Some(variable) = scope
+                .variables
+                .get(&name)
+                .filter(|variable| variable.span.lo <= span.lo)
+            {
+                Some(ScopeSymbol::Variable(variable.clone()))
+            } else if let Some(function) = scope.functions.get(&name) {
+                Some(ScopeSymbol::Function(function.clone()))
+            } else {
+                scope
+                    .structs
+                    .get(&name)
+                    .map(|struct_| ScopeSymbol::Struct(struct_.clone()))
+            }
+        })
+    }
+
+    /// Returns the names visible at `span`, from the innermost scope that contains `span` outwards.
+    /// Variables declared after `span` are not visible at `span`.
+    pub fn symbols_in_scope(&self, span: Span) -> Vec<(Symbol, ScopeSymbol)> {
+        let mut symbols = Vec::new();
+        self.find_at(span, &mut |scope| {
+            symbols.extend(
+                scope
+                    .variables
+                    .iter()
+                    .filter(|(_, variable)| variable.span.lo <= span.lo)
+                    .map(|(name, variable)| (*name, ScopeSymbol::Variable(variable.clone()))),
+            );
+            symbols.extend(
+                scope
+                    .functions
+                    .iter()
+                    .map(|(name, function)| (*name, ScopeSymbol::Function(function.clone()))),
+            );
+            symbols.extend(
+                scope
+                    .structs
+                    .iter()
+                    .map(|(name, struct_)| (*name, ScopeSymbol::Struct(struct_.clone()))),
+            );
+            None::<()>
+        });
+        symbols
+    }
+
+    /// Calls `visit` on each scope that contains `span`, from the innermost outwards, until it returns a value.
+    fn find_at<T>(&self, span: Span, visit: &mut impl FnMut(&Self) -> Option<T>) -> Option<T> {
+        if let Some(scope) = self.scopes.iter().find(|scope| scope.borrow().span.contains(span)) {
+            if let Some(found) = scope.borrow().find_at(span, visit) {
+                return Some(found);
+            }
+        }
+        visit(self)
+    }
+}
+
+/// Returns the Levenshtein distance between `a` and `b`, i.e. the least number of inserted, removed, or replaced characters that turn one into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // The distances from the prefix of `a` read so far to each prefix of `b`.
+    let mut distances: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut previous = distances[0];
+        distances[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous + usize::from(a_char != *b_char);
+            previous = distances[j + 1];
+            distances[j + 1] = substitution.min(distances[j] + 1).min(previous + 1);
+        }
+    }
+    distances[b.len()]
 }
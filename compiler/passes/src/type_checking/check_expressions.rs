@@ -15,12 +15,27 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use leo_ast::*;
-use leo_errors::emitter::Handler;
-use leo_errors::TypeCheckerError;
+use leo_core::MappingOperation;
+use leo_errors::{Suggestion, TypeCheckerError};
 use leo_span::{sym, Span};
-use std::str::FromStr;
 
-use crate::TypeChecker;
+use crate::{CallNode, TypeChecker};
+
+/// Returns `true` if the integer literal `string` is a valid value of `integer_type`.
+fn integer_literal_fits(string: &str, integer_type: &IntegerType) -> bool {
+    match integer_type {
+        IntegerType::U8 => string.parse::<u8>().is_ok(),
+        IntegerType::U16 => string.parse::<u16>().is_ok(),
+        IntegerType::U32 => string.parse::<u32>().is_ok(),
+        IntegerType::U64 => string.parse::<u64>().is_ok(),
+        IntegerType::U128 => string.parse::<u128>().is_ok(),
+        IntegerType::I8 => string.parse::<i8>().is_ok(),
+        IntegerType::I16 => string.parse::<i16>().is_ok(),
+        IntegerType::I32 => string.parse::<i32>().is_ok(),
+        IntegerType::I64 => string.parse::<i64>().is_ok(),
+        IntegerType::I128 => string.parse::<i128>().is_ok(),
+    }
+}
 
 fn return_incorrect_type(t1: Option<Type>, t2: Option<Type>, expected: &Option<Type>) -> Option<Type> {
     match (t1, t2) {
@@ -44,9 +59,44 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
     type AdditionalInput = Option<Type>;
     type Output = Option<Type>;
 
+    /// Checks `input`, recording its type in the type table if it has one, or the error type if errors are recorded.
+    fn visit_expression(&mut self, input: &'a Expression, additional: &Self::AdditionalInput) -> Self::Output {
+        let type_ = match input {
+            Expression::Access(access) => self.visit_access(access, additional),
+            Expression::Binary(binary) => self.visit_binary(binary, additional),
+            Expression::Call(call) => self.visit_call(call, additional),
+            Expression::Cast(cast) => self.visit_cast(cast, additional),
+            Expression::Struct(struct_) => self.visit_struct_init(struct_, additional),
+            Expression::Err(err) => self.visit_err(err, additional),
+            Expression::Identifier(identifier) => self.visit_identifier(identifier, additional),
+            Expression::Literal(literal) => self.visit_literal(literal, additional),
+            Expression::Ternary(ternary) => self.visit_ternary(ternary, additional),
+            Expression::Tuple(tuple) => self.visit_tuple(tuple, additional),
+            Expression::Unary(unary) => self.visit_unary(unary, additional),
+        };
+        match &type_ {
+            Some(type_) => {
+                self.type_table.insert(input.id(), type_.clone());
+            }
+            None if self.record_error_types => {
+                self.type_table.insert(input.id(), Type::Err);
+            }
+            _ => {}
+        }
+        type_
+    }
+
     fn visit_access(&mut self, input: &'a AccessExpression, expected: &Self::AdditionalInput) -> Self::Output {
         match input {
             AccessExpression::AssociatedFunction(access) => {
+                // Operations on mappings are typed by the mapping they are given.
+                if let Type::Identifier(module) = &access.ty {
+                    if let Some(operation) = MappingOperation::from_symbols(module.name, access.name.name) {
+                        self.check_feature(Feature::MappingOperations, module.span() + access.name.span());
+                        return self.check_mapping_operation(operation, access, expected);
+                    }
+                }
+
                 // Check core struct name and function.
                 if let Some(core_instruction) = self.check_core_function_call(&access.ty, &access.name) {
                     // Check num input arguments.
@@ -85,8 +135,28 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                         }
                     }
 
+                    // Check third argument type.
+                    if core_instruction.num_args() >= 3 {
+                        if let Some(third_arg) = access.args.get(2usize) {
+                            if let Some(third_arg_type) = self.visit_expression(third_arg, &None) {
+                                if !core_instruction.third_arg_is_allowed_type(&third_arg_type) {
+                                    // TODO: Better error messages.
+                                    self.emit_err(TypeCheckerError::invalid_type(&third_arg_type, third_arg.span()));
+                                }
+                            }
+                        }
+                    }
+
+                    self.check_commitment_randomness(&core_instruction, &access.args);
+
                     // Check return type.
-                    return Some(self.assert_and_return_type(core_instruction.return_type(), expected, access.span()));
+                    return Some(self.assert_or_widen_type(
+                        core_instruction.return_type(),
+                        expected,
+                        access.id,
+                        access.span(),
+                        access.span(),
+                    ));
                 } else {
                     self.emit_err(TypeCheckerError::invalid_core_function_call(access, access.span()));
                 }
@@ -143,7 +213,12 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                                     // Check that `access.name` is a member of the struct.
                                     match struct_.members.iter().find(|member| member.name() == access.name.name) {
                                         // Case where `access.name` is a member of the struct.
-                                        Some(Member { type_, .. }) => return Some(type_.clone()),
+                                        Some(Member { type_, .. }) => {
+                                            return Some(
+                                                self.widen(type_, expected, input.id(), input.span())
+                                                    .unwrap_or_else(|| type_.clone()),
+                                            )
+                                        }
                                         // Case where `access.name` is not a member of the struct.
                                         None => {
                                             self.emit_err(TypeCheckerError::invalid_struct_variable(
@@ -194,6 +269,9 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 let t1 = self.visit_expression(&input.left, destination);
                 let t2 = self.visit_expression(&input.right, destination);
 
+                // Widen the operand of the narrower integer type, if no value is lost.
+                let (t1, t2) = self.widen_operands(&input.left, t1, &input.right, t2);
+
                 // Check that both operands have the same type.
                 self.check_eq_types(&t1, &t2, input.span());
 
@@ -205,6 +283,9 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 let t1 = self.visit_expression(&input.left, destination);
                 let t2 = self.visit_expression(&input.right, destination);
 
+                // Widen the operand of the narrower integer type, if no value is lost.
+                let (t1, t2) = self.widen_operands(&input.left, t1, &input.right, t2);
+
                 // Check that both operands have the same type.
                 self.check_eq_types(&t1, &t2, input.span());
 
@@ -216,6 +297,9 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 let t1 = self.visit_expression(&input.left, destination);
                 let t2 = self.visit_expression(&input.right, destination);
 
+                // Widen the operand of the narrower integer type, if no value is lost.
+                let (t1, t2) = self.widen_operands(&input.left, t1, &input.right, t2);
+
                 // Check that both operands have the same type.
                 self.check_eq_types(&t1, &t2, input.span());
 
@@ -225,8 +309,17 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 // Operation returns field, group or integer types.
                 self.assert_field_group_int_type(destination, input.span());
 
-                let t1 = self.visit_expression(&input.left, &None);
-                let t2 = self.visit_expression(&input.right, &None);
+                // Integer operands are widened to an integer destination, as for addition,
+                // so that the product is computed in the wider type.
+                let operand_destination = match destination {
+                    Some(Type::Integer(_)) => destination.clone(),
+                    _ => None,
+                };
+                let t1 = self.visit_expression(&input.left, &operand_destination);
+                let t2 = self.visit_expression(&input.right, &operand_destination);
+
+                // Widen the operand of the narrower integer type, if no value is lost.
+                let (t1, t2) = self.widen_operands(&input.left, t1, &input.right, t2);
 
                 // Allow group * scalar multiplication.
                 match (t1, input.left.span(), t2, input.right.span()) {
@@ -253,10 +346,14 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                         // Other type must be the same integer type.
                         self.assert_type(&other, &Type::Integer(integer_type), other_span);
 
-                        // Operation returns the same integer type.
-                        self.assert_type(destination, &Type::Integer(integer_type), input.span());
-
-                        Some(Type::Integer(integer_type))
+                        // Operation returns the same integer type, which may be widened to the destination type.
+                        Some(self.assert_or_widen_type(
+                            Type::Integer(integer_type),
+                            destination,
+                            input.id,
+                            input.span,
+                            input.span,
+                        ))
                     }
                     (left_type, left_span, right_type, right_span) => {
                         let check_type = |type_: Option<Type>, expression: &Expression, span: Span| match type_ {
@@ -284,18 +381,24 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 let t1 = self.visit_expression(&input.left, destination);
                 let t2 = self.visit_expression(&input.right, destination);
 
+                // Widen the operand of the narrower integer type, if no value is lost.
+                let (t1, t2) = self.widen_operands(&input.left, t1, &input.right, t2);
+
                 // Check that both operands have the same type.
                 self.check_eq_types(&t1, &t2, input.span());
 
                 return_incorrect_type(t1, t2, destination)
             }
-            BinaryOperation::Rem | BinaryOperation::RemWrapped => {
+            BinaryOperation::Rem => {
                 // Only integer types.
                 self.assert_int_type(destination, input.span());
 
                 let t1 = self.visit_expression(&input.left, destination);
                 let t2 = self.visit_expression(&input.right, destination);
 
+                // Widen the operand of the narrower integer type, if no value is lost.
+                let (t1, t2) = self.widen_operands(&input.left, t1, &input.right, t2);
+
                 // Check that both operands have the same type.
                 self.check_eq_types(&t1, &t2, input.span());
 
@@ -308,6 +411,9 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 let t1 = self.visit_expression(&input.left, destination);
                 let t2 = self.visit_expression(&input.right, destination);
 
+                // Widen the operand of the narrower integer type, if no value is lost.
+                let (t1, t2) = self.widen_operands(&input.left, t1, &input.right, t2);
+
                 // Check that both operands have the same type.
                 self.check_eq_types(&t1, &t2, input.span());
 
@@ -317,7 +423,12 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 // Operation returns field or integer types.
                 self.assert_field_int_type(destination, input.span());
 
-                let t1 = self.visit_expression(&input.left, &None);
+                // An integer base is widened to an integer destination, as for multiplication, but the exponent is not.
+                let base_destination = match destination {
+                    Some(Type::Integer(_)) => destination.clone(),
+                    _ => None,
+                };
+                let t1 = self.visit_expression(&input.left, &base_destination);
                 let t2 = self.visit_expression(&input.right, &None);
 
                 // Allow field ^ field.
@@ -345,10 +456,8 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                         // Right type must be magnitude (u8, u16, u32).
                         self.assert_magnitude_type(&right, input.right.span());
 
-                        // Operation returns left type.
-                        self.assert_type(destination, &left, input.span());
-
-                        Some(left)
+                        // Operation returns left type, which may be widened to the destination type.
+                        Some(self.assert_or_widen_type(left, destination, input.id, input.span, input.span))
                     }
                     (None, right) => {
                         // Lhs type is checked to be an integer by above.
@@ -363,11 +472,17 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 let t1 = self.visit_expression(&input.left, &None);
                 let t2 = self.visit_expression(&input.right, &None);
 
+                // Widen the operand of the narrower integer type, if no value is lost.
+                let (t1, t2) = self.widen_operands(&input.left, t1, &input.right, t2);
+
                 // Check that the types of the operands are equal.
                 self.check_eq_types(&t1, &t2, input.span());
 
+                // Note whether the comparison checks the owner of a record.
+                self.record_owner_check(&input.left, &input.right);
+
                 // Operation returns a boolean.
-                self.assert_bool_type(destination, input.span());
+                self.assert_bool_result(destination, input.span());
 
                 Some(Type::Boolean)
             }
@@ -376,6 +491,9 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 let t1 = self.visit_expression(&input.left, &None);
                 let t2 = self.visit_expression(&input.right, &None);
 
+                // Widen the operand of the narrower integer type, if no value is lost.
+                let (t1, t2) = self.widen_operands(&input.left, t1, &input.right, t2);
+
                 match (&t1, &t2) {
                     (Some(Type::Address), _) | (_, Some(Type::Address)) => {
                         // Emit an error for address comparison.
@@ -391,29 +509,46 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 self.check_eq_types(&t1, &t2, input.span());
 
                 // Operation returns a boolean.
-                self.assert_bool_type(destination, input.span());
+                self.assert_bool_result(destination, input.span());
 
                 Some(Type::Boolean)
             }
             BinaryOperation::AddWrapped
             | BinaryOperation::SubWrapped
             | BinaryOperation::DivWrapped
-            | BinaryOperation::MulWrapped => {
+            | BinaryOperation::MulWrapped
+            | BinaryOperation::RemWrapped => {
+                // The result wraps around at the bounds of the type of the operands, so they are not widened.
+                let t1 = self.visit_expression(&input.left, &None);
+                let t2 = self.visit_expression(&input.right, &None);
+
                 // Only integer types.
-                self.assert_int_type(destination, input.span);
-                let t1 = self.visit_expression(&input.left, destination);
-                let t2 = self.visit_expression(&input.right, destination);
+                self.assert_int_type(&t1, input.left.span());
 
                 // Check that both operands have the same type.
                 self.check_eq_types(&t1, &t2, input.span());
 
-                return_incorrect_type(t1, t2, destination)
+                // Only the result may be widened to the destination type.
+                t1.map(|t1| self.assert_or_widen_type(t1, destination, input.id, input.span, input.span))
             }
             BinaryOperation::Shl
             | BinaryOperation::ShlWrapped
-            | BinaryOperation::Shr
             | BinaryOperation::ShrWrapped
             | BinaryOperation::PowWrapped => {
+                // The bits shifted out of, or wrapped around, the type of the left operand are lost, so it is not widened.
+                let t1 = self.visit_expression(&input.left, &None);
+                let t2 = self.visit_expression(&input.right, &None);
+
+                // Assert left is an integer type.
+                self.assert_int_type(&t1, input.left.span());
+
+                // Assert right type is a magnitude (u8, u16, u32).
+                self.assert_magnitude_type(&t2, input.right.span());
+
+                // Only the result may be widened to the destination type.
+                t1.map(|t1| self.assert_or_widen_type(t1, destination, input.id, input.span, input.span))
+            }
+            BinaryOperation::Shr => {
                 let t1 = self.visit_expression(&input.left, destination);
                 let t2 = self.visit_expression(&input.right, &None);
 
@@ -438,14 +573,38 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 let func = self.symbol_table.borrow().lookup_fn_symbol(ident.name).cloned();
 
                 if let Some(func) = func {
+                    // Check that an external call is to a transition of the program it names,
+                    // and that the functions of other programs are only called externally.
+                    match (input.external.as_deref(), func.program) {
+                        (Some(Expression::Identifier(program)), declared) => {
+                            let is_transition =
+                                matches!(func.call_type, CallType::Transition | CallType::AsyncTransition);
+                            if declared != Some(program.name) || !is_transition {
+                                self.emit_err(TypeCheckerError::unknown_external_transition(
+                                    program.name,
+                                    ident.name,
+                                    input.span,
+                                ));
+                            }
+                        }
+                        (None, Some(program)) if self.program != Some(program) => {
+                            self.emit_err(TypeCheckerError::imported_function_called_locally(
+                                program, ident.name, input.span,
+                            ));
+                        }
+                        _ => {}
+                    }
+
                     // Check that the call is valid.
-                    match self.is_transition_function {
+                    // A call outside of any function, e.g. at a REPL prompt, is made by the user, who can call any function.
+                    match (self.function.is_some(), self.is_transition_function) {
+                        (false, _) => {}
                         // If the function is not a transition function, it cannot call any other functions.
-                        false => {
+                        (true, false) => {
                             self.emit_err(TypeCheckerError::cannot_invoke_call_from_standard_function(input.span));
                         }
                         // If the function is a transition function, then check that the call is not to another local transition function.
-                        true => {
+                        (true, true) => {
                             if matches!(func.call_type, CallType::Transition) && input.external.is_none() {
                                 self.emit_err(TypeCheckerError::cannot_invoke_call_to_local_transition_function(
                                     input.span,
@@ -454,7 +613,27 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                         }
                     }
 
-                    let ret = self.assert_and_return_type(func.output_type, expected, func.span);
+                    // Record the call in the call graph, even if it is invalid, so that recursion is still reported as such.
+                    if let Some(function) = self.function {
+                        let caller = match self.is_finalize {
+                            true => CallNode::Finalize(function),
+                            false => CallNode::Function(function),
+                        };
+                        let callee = match input.external.as_deref() {
+                            Some(Expression::Identifier(program)) => CallNode::External(program.name, ident.name),
+                            _ => CallNode::Function(ident.name),
+                        };
+                        self.call_graph.add_call(caller, callee, input.span);
+                    }
+
+                    // Check that the function does not have witnesses, which only its prover can supply, unless the user calls it.
+                    if self.function.is_some() && func.input.iter().any(|input| input.mode() == Mode::Witness) {
+                        self.emit_err(TypeCheckerError::cannot_call_function_with_witnesses(
+                            ident.name, input.span,
+                        ));
+                    }
+
+                    let ret = self.assert_or_widen_type(func.output_type, expected, input.id, input.span, func.span);
 
                     // Check number of function arguments.
                     if func.input.len() != input.arguments.len() {
@@ -475,7 +654,8 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
 
                     Some(ret)
                 } else {
-                    self.emit_err(TypeCheckerError::unknown_sym("function", ident.name, ident.span()));
+                    let similar = self.symbol_table.borrow().similar_fn(ident.name);
+                    self.emit_unknown_sym("function", ident, similar);
                     None
                 }
             }
@@ -489,6 +669,20 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
             // Check struct type name.
             let ret = self.check_expected_struct(struct_.identifier, additional, input.name.span());
 
+            // Check that a record is not created on-chain, where it would not be private.
+            if struct_.is_record && self.is_finalize {
+                self.emit_err(TypeCheckerError::record_in_finalize(input.span()));
+            }
+
+            // Check that the owner of a new record is one that can spend it.
+            if struct_.is_record {
+                if let Some(owner) = input.members.iter().find(|member| member.identifier.name == sym::owner) {
+                    if let Some(expression) = &owner.expression {
+                        self.check_record_owner(struct_.identifier.name, expression);
+                    }
+                }
+            }
+
             // Check number of struct members.
             if struct_.members.len() != input.members.len() {
                 self.emit_err(TypeCheckerError::incorrect_num_struct_members(
@@ -523,11 +717,8 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
 
             Some(ret)
         } else {
-            self.emit_err(TypeCheckerError::unknown_sym(
-                "struct",
-                input.name.name,
-                input.name.span(),
-            ));
+            let similar = self.symbol_table.borrow().similar_struct(input.name.name);
+            self.emit_unknown_sym("struct", &input.name, similar);
             None
         }
     }
@@ -538,70 +729,65 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
     }
 
     fn visit_identifier(&mut self, var: &'a Identifier, expected: &Self::AdditionalInput) -> Self::Output {
-        if let Some(var) = self.symbol_table.borrow().lookup_variable(var.name) {
-            Some(self.assert_and_return_type(var.type_.clone(), expected, var.span))
+        if let Some(variable) = self.symbol_table.borrow().lookup_variable(var.name) {
+            // The type of a variable bound by an ill-typed pattern is unknown, and its errors are already reported.
+            if variable.type_ == Type::Err {
+                return None;
+            }
+            Some(self.assert_or_widen_type(variable.type_.clone(), expected, var.id, var.span, variable.span))
         } else {
-            self.emit_err(TypeCheckerError::unknown_sym("variable", var.name, var.span()));
+            let similar = self.symbol_table.borrow().similar_variable(var.name);
+            self.emit_unknown_sym("variable", var, similar);
             None
         }
     }
 
     fn visit_literal(&mut self, input: &'a Literal, expected: &Self::AdditionalInput) -> Self::Output {
-        fn parse_integer_literal<I: FromStr>(handler: &Handler, string: &String, span: Span, type_string: &str) {
-            if string.parse::<I>().is_err() {
-                handler.emit_err(TypeCheckerError::invalid_int_value(string, type_string, span));
-            }
-        }
-
         Some(match input {
-            Literal::Address(_, _) => self.assert_and_return_type(Type::Address, expected, input.span()),
-            Literal::Boolean(_, _) => self.assert_and_return_type(Type::Boolean, expected, input.span()),
-            Literal::Field(_, _) => self.assert_and_return_type(Type::Field, expected, input.span()),
-            Literal::Integer(integer_type, string, _) => match integer_type {
-                IntegerType::U8 => {
-                    parse_integer_literal::<u8>(self.handler, string, input.span(), "u8");
-                    self.assert_and_return_type(Type::Integer(IntegerType::U8), expected, input.span())
-                }
-                IntegerType::U16 => {
-                    parse_integer_literal::<u16>(self.handler, string, input.span(), "u16");
-                    self.assert_and_return_type(Type::Integer(IntegerType::U16), expected, input.span())
-                }
-                IntegerType::U32 => {
-                    parse_integer_literal::<u32>(self.handler, string, input.span(), "u32");
-                    self.assert_and_return_type(Type::Integer(IntegerType::U32), expected, input.span())
-                }
-                IntegerType::U64 => {
-                    parse_integer_literal::<u64>(self.handler, string, input.span(), "u64");
-                    self.assert_and_return_type(Type::Integer(IntegerType::U64), expected, input.span())
-                }
-                IntegerType::U128 => {
-                    parse_integer_literal::<u128>(self.handler, string, input.span(), "u128");
-                    self.assert_and_return_type(Type::Integer(IntegerType::U128), expected, input.span())
-                }
-                IntegerType::I8 => {
-                    parse_integer_literal::<i8>(self.handler, string, input.span(), "i8");
-                    self.assert_and_return_type(Type::Integer(IntegerType::I8), expected, input.span())
-                }
-                IntegerType::I16 => {
-                    parse_integer_literal::<i16>(self.handler, string, input.span(), "i16");
-                    self.assert_and_return_type(Type::Integer(IntegerType::I16), expected, input.span())
-                }
-                IntegerType::I32 => {
-                    parse_integer_literal::<i32>(self.handler, string, input.span(), "i32");
-                    self.assert_and_return_type(Type::Integer(IntegerType::I32), expected, input.span())
-                }
-                IntegerType::I64 => {
-                    parse_integer_literal::<i64>(self.handler, string, input.span(), "i64");
-                    self.assert_and_return_type(Type::Integer(IntegerType::I64), expected, input.span())
+            Literal::Address(..) => self.assert_and_return_type(Type::Address, expected, input.span()),
+            Literal::Boolean(..) => {
+                self.assert_or_widen_type(Type::Boolean, expected, input.id(), input.span(), input.span())
+            }
+            Literal::Field(..) => {
+                self.assert_or_widen_type(Type::Field, expected, input.id(), input.span(), input.span())
+            }
+            Literal::Integer(integer_type, string, ..) => {
+                if !integer_literal_fits(string, integer_type) {
+                    self.emit_err(TypeCheckerError::invalid_int_value(string, integer_type, input.span()));
                 }
-                IntegerType::I128 => {
-                    parse_integer_literal::<i128>(self.handler, string, input.span(), "i128");
-                    self.assert_and_return_type(Type::Integer(IntegerType::I128), expected, input.span())
+
+                match expected {
+                    // If the value also fits in the expected integer type, suggest changing the suffix.
+                    // An integer of a narrower type is implicitly widened instead, below.
+                    Some(Type::Integer(expected_type))
+                        if expected_type != integer_type
+                            && !integer_type.widens_to(expected_type)
+                            && integer_literal_fits(string, expected_type) =>
+                    {
+                        let suggestion = Suggestion::new(
+                            input.span(),
+                            format!("{string}{expected_type}"),
+                            format!("Change the type suffix to `{expected_type}`"),
+                        );
+                        self.emit_err(
+                            self.type_mismatch(integer_type, expected_type, input.span())
+                                .with_suggestion(suggestion),
+                        );
+                        Type::Integer(*integer_type)
+                    }
+                    _ => self.assert_or_widen_type(
+                        Type::Integer(*integer_type),
+                        expected,
+                        input.id(),
+                        input.span(),
+                        input.span(),
+                    ),
                 }
-            },
+            }
             Literal::Group(_) => self.assert_and_return_type(Type::Group, expected, input.span()),
-            Literal::Scalar(_, _) => self.assert_and_return_type(Type::Scalar, expected, input.span()),
-            Literal::String(_, _) => self.assert_and_return_type(Type::String, expected, input.span()),
+            Literal::Scalar(..) => self.assert_and_return_type(Type::Scalar, expected, input.span()),
+            Literal::Signature(..) => self.assert_and_return_type(Type::Signature, expected, input.span()),
+            Literal::String(..) => self.assert_and_return_type(Type::String, expected, input.span()),
         })
     }
 
@@ -611,6 +797,9 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
         let t1 = self.visit_expression(&input.if_true, expected);
         let t2 = self.visit_expression(&input.if_false, expected);
 
+        // Widen the branch of the narrower integer type, if no value is lost.
+        let (t1, t2) = self.widen_operands(&input.if_true, t1, &input.if_false, t2);
+
         return_incorrect_type(t1, t2, expected)
     }
 
@@ -657,9 +846,14 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 self.visit_expression(&input.receiver, destination)
             }
             UnaryOperation::AbsWrapped => {
+                // The result wraps around at the bounds of the type of the receiver, so it is not widened.
+                let type_ = self.visit_expression(&input.receiver, &None);
+
                 // Only signed integer types.
-                self.assert_signed_int_type(destination, input.span());
-                self.visit_expression(&input.receiver, destination)
+                self.assert_signed_int_type(&type_, input.receiver.span());
+
+                // Only the result may be widened to the destination type.
+                type_.map(|type_| self.assert_or_widen_type(type_, destination, input.id, input.span, input.span))
             }
             UnaryOperation::Double => {
                 // Only field or group types.
@@ -679,9 +873,14 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 type_
             }
             UnaryOperation::Not => {
+                // The bits of the receiver are flipped within its type, so it is not widened.
+                let type_ = self.visit_expression(&input.receiver, &None);
+
                 // Only boolean or integer types.
-                self.assert_bool_int_type(destination, input.span());
-                self.visit_expression(&input.receiver, destination)
+                self.assert_bool_int_type(&type_, input.receiver.span());
+
+                // Only the result may be widened to the destination type.
+                type_.map(|type_| self.assert_or_widen_type(type_, destination, input.id, input.span, input.span))
             }
             UnaryOperation::Square => {
                 // Only field type.
@@ -693,6 +892,8 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 self.assert_field_type(destination, input.span());
                 self.visit_expression(&input.receiver, destination)
             }
+            // Awaits are removed when async functions are desugared.
+            UnaryOperation::Await => None,
         }
     }
 }
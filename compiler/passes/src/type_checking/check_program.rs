@@ -14,18 +14,54 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{TypeChecker, VariableSymbol, VariableType};
+use crate::{CallGraph, TypeChecker, VariableSymbol, VariableType};
 
 use leo_ast::*;
-use leo_errors::TypeCheckerError;
+use leo_errors::{TypeCheckerError, TypeCheckerWarning};
 
-use leo_span::sym;
+use leo_span::{sym, Span};
 
 use std::collections::HashSet;
 
 // TODO: Generally, cleanup tyc logic.
 
 impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
+    fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        // Imports are checked first, so the call graph that is kept is the one of the main program.
+        self.call_graph = CallGraph::default();
+        self.program = Some(input.program_id.name.name);
+
+        input.structs.values().for_each(|struct_| self.visit_struct(struct_));
+        self.check_struct_cycles(&input.structs);
+        input.mappings.values().for_each(|mapping| self.visit_mapping(mapping));
+        input
+            .functions
+            .values()
+            .for_each(|function| self.visit_function(function));
+
+        // Each cycle is reported at its first call, with a label at each of the calls that close it.
+        for cycle in self.call_graph.cycles() {
+            let calls = cycle
+                .windows(2)
+                .filter_map(|pair| Some((pair[0], pair[1], self.call_graph.call_span(pair[0], pair[1])?)))
+                .collect::<Vec<_>>();
+            if let Some((caller, _, span)) = calls.first().copied() {
+                let path = cycle
+                    .iter()
+                    .map(|node| node.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                let error = calls.iter().skip(1).fold(
+                    TypeCheckerError::recursive_call(caller, path, span),
+                    |error, (caller, callee, span)| {
+                        error.with_label(*span, format!("`{caller}` calls `{callee}` here"))
+                    },
+                );
+                self.emit_err(error);
+            }
+        }
+    }
+
     fn visit_struct(&mut self, input: &'a Struct) {
         // Check for conflicting struct/record member names.
         let mut used = HashSet::new();
@@ -101,13 +137,45 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
 
     fn visit_function(&mut self, function: &'a Function) {
         // Check that the function's annotations are valid.
-        // Note that Leo does not natively support any specific annotations.
+        // Note that Leo supports `@test`, whose arguments are the number of cases to run a test with random inputs
+        // on, e.g. `@test(cases = 256)`, and fixed values of its inputs, e.g. `@test(token = token { .. })`,
+        // `@no_owner_check` on transitions, which takes no arguments, and `@optimize(none)`.
         for annotation in function.annotations.iter() {
-            self.emit_err(TypeCheckerError::unknown_annotation(annotation, annotation.span))
+            if annotation.identifier.name == sym::optimize {
+                match annotation.arguments.as_slice() {
+                    [(level, value)] if level.name == sym::none && value.is_empty() => {}
+                    // A level such as `size` is well-formed, but the compiler has no optimizations to tune for it.
+                    [(level, value)] if value.is_empty() => {
+                        self.emit_err(TypeCheckerError::unsupported_optimize_mode(level, annotation.span))
+                    }
+                    _ => self.emit_err(TypeCheckerError::invalid_optimize_annotation(
+                        annotation,
+                        annotation.span,
+                    )),
+                }
+                continue;
+            } else if annotation.identifier.name == sym::no_owner_check {
+                if !matches!(function.call_type, CallType::Transition) {
+                    self.emit_err(TypeCheckerError::no_owner_check_outside_transition(annotation.span))
+                }
+            } else if annotation.identifier.name != sym::test {
+                self.emit_err(TypeCheckerError::unknown_annotation(annotation, annotation.span))
+            }
+            for (name, value) in annotation.arguments.iter() {
+                let is_cases = name.name == sym::cases && matches!(value.parse::<usize>(), Ok(cases) if cases > 0);
+                let is_input = function.input.iter().any(|input| input.identifier().name == name.name);
+                if annotation.identifier.name != sym::test || !(is_cases || is_input) {
+                    self.emit_err(TypeCheckerError::invalid_annotation_argument(
+                        name, value, annotation, name.span,
+                    ))
+                }
+            }
         }
 
         self.is_transition_function = matches!(function.call_type, CallType::Transition);
 
+        self.call_graph.add_function(function.name(), function.call_type);
+
         // Lookup function metadata in the symbol table.
         // Note that this unwrap is safe since function metadata is stored in a prior pass.
         let function_index = self
@@ -129,8 +197,21 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
         // Store the name of the function.
         self.function = Some(function.name());
 
+        // Store the span of the function's output type, or of its name if it does not declare one.
+        self.output_span = output_span(&function.output).unwrap_or(function.identifier.span);
+
         // Create a new child scope for the function's parameters and body.
-        let scope_index = self.create_child_scope();
+        let scope_index = self.create_child_scope(function.span);
+
+        // Check that the witnesses follow the other inputs, so that they are the last inputs of the generated function.
+        if let Some(input_var) = function
+            .input
+            .iter()
+            .skip_while(|input_var| input_var.mode() != Mode::Witness)
+            .find(|input_var| input_var.mode() != Mode::Witness)
+        {
+            self.emit_err(TypeCheckerError::witness_must_follow_inputs(input_var.span()));
+        }
 
         // Type check the function's parameters.
         function.input.iter().for_each(|input_var| {
@@ -163,6 +244,23 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
             }
         });
 
+        // Check that only transitions take or return records, as functions are compiled to closures, which cannot.
+        // Note that test functions are not compiled.
+        if function.call_type == CallType::Standard && !function.is_test() {
+            // The inputs and outputs of another program are its records.
+            let inputs = function.input.iter().map(|input_var| match input_var {
+                Input::External(external) => (true, external.span),
+                Input::Internal(input_var) => (self.contains_record(&input_var.type_), input_var.span),
+            });
+            let outputs = function.output.iter().map(|output_type| match output_type {
+                Output::External(external) => (true, external.span),
+                Output::Internal(output_type) => (self.contains_record(&output_type.type_), output_type.span),
+            });
+            for (_, span) in inputs.chain(outputs).filter(|(is_record, _)| *is_record) {
+                self.emit_err(TypeCheckerError::record_outside_transition(function.name(), span));
+            }
+        }
+
         // Type check the function's return type.
         function.output.iter().for_each(|output_type| {
             match output_type {
@@ -172,15 +270,51 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
                     self.assert_type_is_valid(output_type.span, &output_type.type_);
 
                     // Check that the mode of the output is valid.
-                    if output_type.mode == Mode::Const {
-                        self.emit_err(TypeCheckerError::cannot_have_constant_output_mode(output_type.span));
+                    match output_type.mode {
+                        Mode::Const => {
+                            self.emit_err(TypeCheckerError::cannot_have_constant_output_mode(output_type.span))
+                        }
+                        Mode::Witness => self.emit_err(TypeCheckerError::invalid_witness_mode(output_type.span)),
+                        // Only the outputs of transitions are visible outside of the program, so only they have visibilities.
+                        Mode::Private | Mode::Public if !self.is_transition_function => self.emit_err(
+                            TypeCheckerError::regular_function_outputs_cannot_have_modes(output_type.span),
+                        ),
+                        _ => {}
                     }
                 }
             }
         });
 
+        // The function has not checked the owner of any record yet.
+        self.owner_checks.clear();
+
         self.visit_block(&function.block);
 
+        // Check that a transition checks the owner of each record that it consumes, unless it is annotated otherwise.
+        let no_owner_check = function
+            .annotations
+            .iter()
+            .any(|annotation| annotation.identifier.name == sym::no_owner_check);
+        if self.is_transition_function && !no_owner_check {
+            for input_var in function.input.iter() {
+                if let Type::Identifier(type_) = input_var.type_() {
+                    let is_record = self
+                        .symbol_table
+                        .borrow()
+                        .lookup_struct(type_.name)
+                        .map_or(false, |struct_| struct_.is_record);
+                    let record = input_var.identifier().name;
+                    if is_record && !self.owner_checks.contains(&record) {
+                        self.emit_warning(TypeCheckerWarning::unchecked_record_owner(
+                            record,
+                            function.name(),
+                            input_var.span(),
+                        ));
+                    }
+                }
+            }
+        }
+
         // Check that the return type is valid.
         self.assert_type_is_valid(function.span, &function.output_type);
 
@@ -200,6 +334,7 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
         // Traverse and check the finalize block if it exists.
         if let Some(finalize) = &function.finalize {
             self.is_finalize = true;
+            self.output_span = output_span(&finalize.output).unwrap_or(finalize.identifier.span);
             // The function's finalize block does not have a return statement.
             self.has_return = false;
             // The function;s finalize block does not have a finalize statement.
@@ -222,16 +357,23 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
             }
 
             // Create a new child scope for the finalize block.
-            let scope_index = self.create_child_scope();
+            let scope_index = self.create_child_scope(finalize.span);
 
             finalize.input.iter().for_each(|input_var| {
                 // Check that the type of input parameter is valid.
                 self.assert_type_is_valid(input_var.span(), &input_var.type_());
                 self.assert_not_tuple(input_var.span(), &input_var.type_());
+                if matches!(input_var, Input::External(_)) || self.contains_record(&input_var.type_()) {
+                    self.emit_err(TypeCheckerError::record_in_finalize(input_var.span()));
+                }
 
-                // Check that the input parameter is not constant or private.
-                if input_var.mode() == Mode::Const || input_var.mode() == Mode::Private {
-                    self.emit_err(TypeCheckerError::finalize_input_mode_must_be_public(input_var.span()));
+                // Check that the input parameter is not constant, private, or a witness.
+                match input_var.mode() {
+                    Mode::Const | Mode::Private => {
+                        self.emit_err(TypeCheckerError::finalize_input_mode_must_be_public(input_var.span()))
+                    }
+                    Mode::Witness => self.emit_err(TypeCheckerError::invalid_witness_mode(input_var.span())),
+                    Mode::None | Mode::Public => {}
                 }
 
                 // Check for conflicting variable names.
@@ -251,10 +393,17 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
             finalize.output.iter().for_each(|output_type| {
                 // Check that the type of output is valid.
                 self.assert_type_is_valid(output_type.span(), &output_type.type_());
+                if matches!(output_type, Output::External(_)) || self.contains_record(&output_type.type_()) {
+                    self.emit_err(TypeCheckerError::record_in_finalize(output_type.span()));
+                }
 
-                // Check that the mode of the output is valid.
-                if output_type.mode() == Mode::Const {
-                    self.emit_err(TypeCheckerError::finalize_input_mode_must_be_public(output_type.span()));
+                // Check that the output is not constant, private, or a witness.
+                match output_type.mode() {
+                    Mode::Const | Mode::Private => self.emit_err(
+                        TypeCheckerError::finalize_output_mode_must_be_public(output_type.span()),
+                    ),
+                    Mode::Witness => self.emit_err(TypeCheckerError::invalid_witness_mode(output_type.span())),
+                    _ => {}
                 }
             });
 
@@ -288,3 +437,8 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
         self.is_transition_function = false;
     }
 }
+
+/// Returns the span of the declared `outputs`, from the first to the last, if there are any.
+fn output_span(outputs: &[Output]) -> Option<Span> {
+    Some(outputs.first()?.span() + outputs.last()?.span())
+}
@@ -14,19 +14,16 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{TypeChecker, VariableSymbol, VariableType};
+use crate::{is_useful, CallNode, PatternShape, TypeChecker, VariableSymbol, VariableType};
 
 use leo_ast::*;
-use leo_errors::TypeCheckerError;
+use leo_errors::{TypeCheckerError, TypeCheckerWarning};
+use leo_interpreter::assertion_always_fails;
+
+use indexmap::IndexMap;
 
 impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
     fn visit_statement(&mut self, input: &'a Statement) {
-        // No statements can follow a return statement.
-        if self.has_return {
-            self.emit_err(TypeCheckerError::unreachable_code_after_return(input.span()));
-            return;
-        }
-
         match input {
             Statement::Assign(stmt) => self.visit_assign(stmt),
             Statement::Block(stmt) => self.visit_block(stmt),
@@ -34,9 +31,11 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
             Statement::Console(stmt) => self.visit_console(stmt),
             Statement::Decrement(stmt) => self.visit_decrement(stmt),
             Statement::Definition(stmt) => self.visit_definition(stmt),
+            Statement::Expression(stmt) => self.visit_expression_statement(stmt),
             Statement::Finalize(stmt) => self.visit_finalize(stmt),
             Statement::Increment(stmt) => self.visit_increment(stmt),
             Statement::Iteration(stmt) => self.visit_iteration(stmt),
+            Statement::Match(stmt) => self.visit_match(stmt),
             Statement::Return(stmt) => self.visit_return(stmt),
         }
     }
@@ -50,7 +49,7 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
             }
         };
 
-        let var_type = if let Some(var) = self.symbol_table.borrow_mut().lookup_variable(var_name.name) {
+        let var_type = if let Some(var) = self.symbol_table.borrow().lookup_variable(var_name.name) {
             match &var.declaration {
                 VariableType::Const => self.emit_err(TypeCheckerError::cannot_assign_to_const_var(var_name, var.span)),
                 VariableType::Input(Mode::Const) => {
@@ -61,7 +60,8 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
 
             Some(var.type_.clone())
         } else {
-            self.emit_err(TypeCheckerError::unknown_sym("variable", var_name.name, var_name.span));
+            let similar = self.symbol_table.borrow().similar_variable(var_name.name);
+            self.emit_unknown_sym("variable", &var_name, similar);
 
             None
         };
@@ -73,9 +73,9 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
 
     fn visit_block(&mut self, input: &'a Block) {
         // Create a new scope for the then-block.
-        let scope_index = self.create_child_scope();
+        let scope_index = self.create_child_scope(input.span);
 
-        input.statements.iter().for_each(|stmt| self.visit_statement(stmt));
+        self.check_block_statements(input);
 
         // Exit the scope for the then-block.
         self.exit_scope(scope_index);
@@ -84,6 +84,27 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
     fn visit_conditional(&mut self, input: &'a ConditionalStatement) {
         self.visit_expression(&input.condition, &Some(Type::Boolean));
 
+        // Warn about branches that can never be taken.
+        match &input.condition {
+            Expression::Literal(Literal::Boolean(false, ..)) if !input.then.statements.is_empty() => {
+                self.emit_warning(TypeCheckerWarning::unreachable_code(
+                    "the condition is always false",
+                    input.then.span,
+                ));
+            }
+            Expression::Literal(Literal::Boolean(true, ..)) => {
+                if let Some(otherwise) = &input.otherwise {
+                    self.emit_warning(TypeCheckerWarning::unreachable_code(
+                        "the condition is always true",
+                        otherwise.span(),
+                    ));
+                }
+            }
+            _ => {}
+        }
+
+        let previous_in_branch = core::mem::replace(&mut self.in_branch, true);
+
         let mut then_block_has_return = false;
         let mut otherwise_block_has_return = false;
 
@@ -127,22 +148,59 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
         self.has_return = previous_has_return || (then_block_has_return && otherwise_block_has_return);
         // Restore the previous `has_finalize` flag.
         self.has_finalize = previous_has_finalize || (then_block_has_finalize && otherwise_block_has_finalize);
+        self.in_branch = previous_in_branch;
     }
 
     fn visit_console(&mut self, input: &'a ConsoleStatement) {
+        // The errors emitted so far, to tell whether checking this assertion emits any.
+        let errors = self.handler.err_count() + self.handler.suppressed_err_count();
+
         match &input.function {
-            ConsoleFunction::Assert(expr) => {
+            ConsoleFunction::Assert(expr, _) => {
                 let type_ = self.visit_expression(expr, &Some(Type::Boolean));
                 self.assert_bool_type(&type_, expr.span());
             }
-            ConsoleFunction::AssertEq(left, right) | ConsoleFunction::AssertNeq(left, right) => {
+            ConsoleFunction::AssertEq(left, right, _) | ConsoleFunction::AssertNeq(left, right, _) => {
                 let t1 = self.visit_expression(left, &None);
                 let t2 = self.visit_expression(right, &None);
 
+                // Widen the operand of the narrower integer type, if no value is lost.
+                let (t1, t2) = self.widen_operands(left, t1, right, t2);
+
                 // Check that the types are equal.
                 self.check_eq_types(&t1, &t2, input.span());
+
+                // Note whether the assertion checks the owner of a record.
+                self.record_owner_check(left, right);
             }
         }
+
+        if let Some(message) = input.function.message() {
+            // Check that each placeholder in the message has a corresponding parameter.
+            if message.placeholder_count() != message.parameters.len() {
+                self.emit_err(TypeCheckerError::assert_message_parameter_mismatch(
+                    message.placeholder_count(),
+                    message.parameters.len(),
+                    message.span,
+                ));
+            }
+            message.parameters.iter().for_each(|parameter| {
+                self.visit_expression(parameter, &None);
+            });
+        }
+
+        // If the assertion can be proven to fail at compile time, report it along with the user-defined message.
+        // In a branch of a finalize block, it aborts the finalize block when the branch is taken.
+        // An assertion whose operands or message have errors is not evaluated, since it is already reported.
+        let aborts_finalize = self.is_finalize && self.in_branch;
+        let has_errors = self.handler.err_count() + self.handler.suppressed_err_count() > errors;
+        if !aborts_finalize && !has_errors && assertion_always_fails(&input.function) {
+            self.emit_err(TypeCheckerError::assertion_always_fails(
+                &input.function,
+                input.function.message().map(|message| message.render()),
+                input.span(),
+            ));
+        }
     }
 
     fn visit_decrement(&mut self, input: &'a DecrementStatement) {
@@ -203,6 +261,18 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
         }
     }
 
+    fn visit_expression_statement(&mut self, input: &'a ExpressionStatement) {
+        // Only the calls that have no output, i.e. the updates of mappings and the calls to transitions of other programs
+        // made for their futures, may be evaluated for their effect alone.
+        match self.visit_expression(&input.expression, &None) {
+            None | Some(Type::Unit) => {}
+            Some(_) => self.emit_err(TypeCheckerError::unused_expression_value(
+                &input.expression,
+                input.span(),
+            )),
+        }
+    }
+
     fn visit_finalize(&mut self, input: &'a FinalizeStatement) {
         if self.is_finalize {
             self.emit_err(TypeCheckerError::finalize_in_finalize(input.span()));
@@ -224,6 +294,11 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
         match finalize {
             None => self.emit_err(TypeCheckerError::finalize_without_finalize_block(input.span())),
             Some(finalize) => {
+                // Record the boundary between the transition and its finalize block in the call graph.
+                let function = self.function.unwrap();
+                self.call_graph
+                    .add_call(CallNode::Function(function), CallNode::Finalize(function), input.span());
+
                 // Check number of function arguments.
                 if finalize.input.len() != input.arguments.len() {
                     self.emit_err(TypeCheckerError::incorrect_num_args_to_finalize(
@@ -284,7 +359,7 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
         self.assert_int_type(iter_type, input.variable.span);
 
         // Create a new scope for the loop body.
-        let scope_index = self.create_child_scope();
+        let scope_index = self.create_child_scope(input.span);
 
         // Add the loop variable to the scope of the loop body.
         if let Err(err) = self.symbol_table.borrow_mut().insert_variable(
@@ -332,6 +407,75 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
         }
     }
 
+    fn visit_match(&mut self, input: &'a MatchStatement) {
+        let type_ = match &input.expression {
+            // A matched tuple is not explicitly typed, so its type is that of its elements.
+            Expression::Tuple(tuple) if tuple.elements.len() > 1 => {
+                let types: Vec<Option<Type>> = tuple
+                    .elements
+                    .iter()
+                    .map(|element| self.visit_expression(element, &None))
+                    .collect();
+                let type_ = types
+                    .into_iter()
+                    .collect::<Option<Vec<Type>>>()
+                    .map(|types| Type::Tuple(Tuple(types)));
+                if let Some(type_) = &type_ {
+                    self.type_table.insert(input.expression.id(), type_.clone());
+                }
+                type_
+            }
+            expression => self.visit_expression(expression, &None),
+        };
+        if let Some(type_) = &type_ {
+            self.match_types.insert(input.span, type_.clone());
+        }
+
+        let previous_has_return = self.has_return;
+        let previous_has_finalize = self.has_finalize;
+        let previous_in_branch = core::mem::replace(&mut self.in_branch, true);
+        let mut arms_have_return = true;
+        let mut arms_have_finalize = true;
+
+        // The shapes of the patterns of the previous arms that fit the type of the matched value.
+        let mut rows: Vec<Vec<PatternShape>> = Vec::new();
+        for arm in input.arms.iter() {
+            // The variables bound by the pattern are added to the scope of the block of the arm.
+            let scope_index = self.create_child_scope(arm.span);
+            let shape = self.check_pattern(&arm.pattern, &type_);
+
+            // Reachability is only checked once the type of the matched value is known.
+            if let (Some(_), Some(shape)) = (&type_, shape) {
+                let row = vec![shape];
+                if !is_useful(&rows, &row) {
+                    self.emit_err(TypeCheckerError::unreachable_match_arm(arm.span));
+                }
+                rows.push(row);
+            }
+
+            self.has_return = false;
+            self.has_finalize = false;
+            self.check_block_statements(&arm.block);
+            arms_have_return &= self.has_return;
+            arms_have_finalize &= self.has_finalize;
+
+            self.exit_scope(scope_index);
+        }
+
+        // A pattern that does not fit the type of the matched value matches nothing, so it is left out of the rows.
+        let is_exhaustive = type_.is_none() || !is_useful(&rows, &[PatternShape::Any]);
+
+        self.in_branch = previous_in_branch;
+
+        if !is_exhaustive {
+            self.emit_err(TypeCheckerError::non_exhaustive_match(input.span));
+        }
+
+        // The match statement returns on every path if it is exhaustive and each arm returns.
+        self.has_return = previous_has_return || (is_exhaustive && arms_have_return);
+        self.has_finalize = previous_has_finalize || (is_exhaustive && arms_have_finalize);
+    }
+
     fn visit_return(&mut self, input: &'a ReturnStatement) {
         // we can safely unwrap all self.parent instances because
         // statements should always have some parent block
@@ -349,6 +493,146 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
 
         self.has_return = true;
 
+        // Mismatches of the whole returned value are reported against the declared output type.
+        self.return_span = Some(input.expression.span());
         self.visit_expression(&input.expression, return_type);
+        self.return_span = None;
+    }
+}
+
+impl<'a> TypeChecker<'a> {
+    /// Checks the statements of a block in the current scope.
+    fn check_block_statements(&mut self, input: &'a Block) {
+        let mut statements = input.statements.iter();
+        for stmt in statements.by_ref() {
+            self.visit_statement(stmt);
+            // Stop at a return statement, or at a conditional that returns on every path.
+            if self.has_return {
+                break;
+            }
+        }
+
+        // No statements can follow a return statement, so report the rest of the block as a single range.
+        let unreachable: Vec<_> = statements.collect();
+        if let (Some(first), Some(last)) = (unreachable.first(), unreachable.last()) {
+            self.emit_err(TypeCheckerError::unreachable_code_after_return(
+                first.span() + last.span(),
+            ));
+        }
+    }
+
+    /// Checks that `pattern` can match a value of the given type, if it is known,
+    /// and adds the variables that the pattern binds to the current scope.
+    /// Returns the shape of the pattern, if it fits the type without errors.
+    fn check_pattern(&mut self, pattern: &'a Pattern, type_: &Option<Type>) -> Option<PatternShape> {
+        match pattern {
+            Pattern::Wildcard(_) => Some(PatternShape::Any),
+            Pattern::Binding(identifier) => {
+                self.bind_pattern_variable(identifier, type_);
+                Some(PatternShape::Any)
+            }
+            Pattern::Literal(literal) => {
+                let errors = self.handler.err_count();
+                self.visit_literal(literal, type_);
+                (self.handler.err_count() == errors).then(|| PatternShape::literal(literal))
+            }
+            Pattern::Tuple(tuple) => {
+                let types = match type_ {
+                    Some(Type::Tuple(types)) if types.len() == tuple.elements.len() => {
+                        types.iter().cloned().map(Some).collect()
+                    }
+                    Some(type_) => {
+                        self.emit_err(TypeCheckerError::invalid_match_pattern(pattern, type_, tuple.span));
+                        // The elements are still checked, to bind their variables.
+                        for element in tuple.elements.iter() {
+                            self.check_pattern(element, &None);
+                        }
+                        return None;
+                    }
+                    None => vec![None; tuple.elements.len()],
+                };
+                // Every element is checked, even after one that does not fit its type.
+                let shapes: Vec<Option<PatternShape>> = tuple
+                    .elements
+                    .iter()
+                    .zip(types)
+                    .map(|(element, type_)| self.check_pattern(element, &type_))
+                    .collect();
+                shapes.into_iter().collect::<Option<Vec<_>>>().map(PatternShape::Fields)
+            }
+            Pattern::Struct(struct_pattern) => {
+                let struct_ = match type_ {
+                    Some(Type::Identifier(name)) if name.name == struct_pattern.name.name => {
+                        self.symbol_table.borrow().lookup_struct(name.name).cloned()
+                    }
+                    Some(type_) => {
+                        self.emit_err(TypeCheckerError::invalid_match_pattern(
+                            pattern,
+                            type_,
+                            struct_pattern.span,
+                        ));
+                        None
+                    }
+                    None => None,
+                };
+                let mut is_well_typed = struct_.is_some();
+                let mut member_shapes = IndexMap::new();
+                for member in struct_pattern.members.iter() {
+                    // Check that the member is a member of the struct, and look up its type.
+                    let member_type = struct_.as_ref().and_then(|struct_| {
+                        let found = struct_.members.iter().find(|m| m.name() == member.identifier.name);
+                        if found.is_none() {
+                            self.emit_err(TypeCheckerError::invalid_struct_variable(
+                                member.identifier,
+                                struct_,
+                                member.identifier.span,
+                            ));
+                        }
+                        found.map(|member| member.type_.clone())
+                    });
+                    is_well_typed &= member_type.is_some();
+                    let shape = match &member.pattern {
+                        Some(pattern) => self.check_pattern(pattern, &member_type),
+                        None => {
+                            self.bind_pattern_variable(&member.identifier, &member_type);
+                            Some(PatternShape::Any)
+                        }
+                    };
+                    match shape {
+                        Some(shape) => {
+                            member_shapes.insert(member.identifier.name, shape);
+                        }
+                        None => is_well_typed = false,
+                    }
+                }
+                // The members that are not listed match any value.
+                match struct_ {
+                    Some(struct_) if is_well_typed => Some(PatternShape::Fields(
+                        struct_
+                            .members
+                            .iter()
+                            .map(|member| member_shapes.remove(&member.name()).unwrap_or(PatternShape::Any))
+                            .collect(),
+                    )),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Adds a variable bound by a pattern to the current scope, recording its type for the match lowering pass.
+    fn bind_pattern_variable(&mut self, identifier: &Identifier, type_: &Option<Type>) {
+        let type_ = type_.clone().unwrap_or(Type::Err);
+        self.match_types.insert(identifier.span, type_.clone());
+        if let Err(err) = self.symbol_table.borrow_mut().insert_variable(
+            identifier.name,
+            VariableSymbol {
+                type_,
+                span: identifier.span,
+                declaration: VariableType::Const,
+            },
+        ) {
+            self.handler.emit_err(err);
+        }
     }
 }
@@ -14,21 +14,31 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::SymbolTable;
+use crate::{CallGraph, ImplicitWidening, ImplicitWidenings, MatchTypes, SymbolTable};
 
-use leo_ast::{Identifier, IntegerType, Node, Type};
+use leo_ast::{
+    AccessExpression, AssociatedFunction, Expression, ExpressionVisitor, Feature, Identifier, IntegerType,
+    LanguageVersion, Member, Node, NodeID, Struct, Type,
+};
 use leo_core::*;
-use leo_errors::{emitter::Handler, TypeCheckerError};
-use leo_span::{Span, Symbol};
+use leo_errors::{emitter::Handler, LintWarning, Suggestion, TypeCheckerError, TypeCheckerWarning};
+use leo_span::{sym, symbol::with_session_globals, Span, Symbol};
 
+use indexmap::{IndexMap, IndexSet};
 use itertools::Itertools;
-use std::cell::RefCell;
+use std::{cell::RefCell, fmt::Display};
+
+/// The types of the expressions of a type checked program, keyed by the id of their node.
+/// Identifiers and literals have no id: the types of variables are in the symbol table, and literals carry theirs.
+pub type TypeTable = IndexMap<NodeID, Type>;
 
 pub struct TypeChecker<'a> {
     /// The symbol table for the program.
     pub(crate) symbol_table: RefCell<SymbolTable>,
     /// The error handler.
     pub(crate) handler: &'a Handler,
+    /// The name of the program scope that we are currently traversing.
+    pub(crate) program: Option<Symbol>,
     /// The name of the function that we are currently traversing.
     pub(crate) function: Option<Symbol>,
     /// Whether or not the function that we are currently traversing has a return statement.
@@ -39,6 +49,27 @@ pub struct TypeChecker<'a> {
     pub(crate) is_transition_function: bool,
     /// Whether or not we are currently traversing a finalize block.
     pub(crate) is_finalize: bool,
+    /// Whether or not we are currently traversing a branch of a conditional or match statement.
+    pub(crate) in_branch: bool,
+    /// The span of the output type of the function or finalize block that we are currently traversing.
+    pub(crate) output_span: Span,
+    /// The span of the expression of the return statement that we are currently traversing, if any.
+    pub(crate) return_span: Option<Span>,
+    /// The version of Leo that the program is written for, which determines the features it may use.
+    pub(crate) version: LanguageVersion,
+    /// The integers that were implicitly widened to a wider integer type, keyed by their expression.
+    pub(crate) widenings: RefCell<ImplicitWidenings>,
+    /// The types of the values matched by match statements and of the variables bound by their patterns.
+    pub(crate) match_types: MatchTypes,
+    /// The types of the expressions that have been checked.
+    pub(crate) type_table: TypeTable,
+    /// The calls between the functions of the program scope that we are currently traversing.
+    pub(crate) call_graph: CallGraph,
+    /// The records whose owner is compared to `self.caller` in the function that we are currently traversing.
+    pub(crate) owner_checks: IndexSet<Symbol>,
+    /// Whether expressions whose type cannot be determined are recorded with the error type,
+    /// so that tools can tell them apart from expressions that were not checked.
+    pub(crate) record_error_types: bool,
 }
 
 const BOOLEAN_TYPE: Type = Type::Boolean;
@@ -85,16 +116,27 @@ const MAGNITUDE_TYPES: [Type; 3] = [
 ];
 
 impl<'a> TypeChecker<'a> {
-    /// Returns a new type checker given a symbol table and error handler.
-    pub fn new(symbol_table: SymbolTable, handler: &'a Handler) -> Self {
+    /// Returns a new type checker given a symbol table, error handler, and the version of Leo that the program is written for.
+    pub fn new(symbol_table: SymbolTable, handler: &'a Handler, version: LanguageVersion) -> Self {
         Self {
             is_transition_function: false,
             symbol_table: RefCell::new(symbol_table),
             handler,
+            program: None,
             function: None,
             has_return: false,
             has_finalize: false,
             is_finalize: false,
+            in_branch: false,
+            output_span: Span::default(),
+            return_span: None,
+            version,
+            widenings: Default::default(),
+            match_types: Default::default(),
+            type_table: Default::default(),
+            call_graph: Default::default(),
+            owner_checks: Default::default(),
+            record_error_types: false,
         }
     }
 
@@ -106,12 +148,14 @@ impl<'a> TypeChecker<'a> {
         self.symbol_table.borrow_mut().parent = Some(Box::new(previous_symbol_table.into_inner()));
     }
 
-    /// Creates a new child scope.
-    pub(crate) fn create_child_scope(&mut self) -> usize {
+    /// Creates a new child scope, covering the source at `span`.
+    pub(crate) fn create_child_scope(&mut self, span: Span) -> usize {
         // Creates a new child scope.
         let scope_index = self.symbol_table.borrow_mut().insert_block();
         // Enter the new scope.
         self.enter_scope(scope_index);
+        // Record the source it covers, so that tools can find it by position.
+        self.symbol_table.borrow_mut().span = span;
         // Return the index of the new scope.
         scope_index
     }
@@ -129,6 +173,37 @@ impl<'a> TypeChecker<'a> {
         self.handler.emit_err(err);
     }
 
+    /// Emits an error if `feature`, used at `span`, is not available in the version of Leo that the program is written for.
+    pub(crate) fn check_feature(&self, feature: Feature, span: Span) {
+        if !feature.is_available_in(self.version) {
+            self.emit_err(TypeCheckerError::feature_requires_version(
+                feature,
+                feature.minimum_version(),
+                self.version,
+                span,
+            ));
+        }
+    }
+
+    /// Emits an error for the unknown `kind` of symbol `name`.
+    /// If a symbol with a `similar` name exists, it is suggested as the intended name.
+    pub(crate) fn emit_unknown_sym(&self, kind: &str, name: &Identifier, similar: Option<Symbol>) {
+        let mut err = TypeCheckerError::unknown_sym(kind, name.name, name.span);
+        if let Some(similar) = similar {
+            err = err.with_suggestion(Suggestion::new(
+                name.span,
+                similar.to_string(),
+                format!("A {kind} with a similar name exists: `{similar}`"),
+            ));
+        }
+        self.emit_err(err);
+    }
+
+    /// Emits a type checker warning.
+    pub(crate) fn emit_warning(&self, warning: TypeCheckerWarning) {
+        self.handler.emit_warning(warning);
+    }
+
     /// Emits an error to the handler if the given type is invalid.
     fn check_type(&self, is_valid: impl Fn(&Type) -> bool, error_string: String, type_: &Option<Type>, span: Span) {
         if let Some(type_) = type_ {
@@ -151,18 +226,149 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
+    /// Returns an error for an expression of type `actual` where an expression of type `expected` was expected.
+    /// If the expression is returned by a return statement, the error also points to the declared output type.
+    pub(crate) fn type_mismatch(&self, actual: impl Display, expected: impl Display, span: Span) -> TypeCheckerError {
+        match self.return_span == Some(span) {
+            true => TypeCheckerError::return_type_mismatch(&expected, actual, span).with_label(
+                self.output_span,
+                format!("expected `{expected}` because of the return type"),
+            ),
+            false => TypeCheckerError::type_should_be(actual, expected, span),
+        }
+    }
+
     /// Use this method when you know the actual type.
     /// Emits an error to the handler if the `actual` type is not equal to the `expected` type.
     pub(crate) fn assert_and_return_type(&self, actual: Type, expected: &Option<Type>, span: Span) -> Type {
         if let Some(expected) = expected {
             if !actual.eq_flat(expected) {
-                self.emit_err(TypeCheckerError::type_should_be(actual.clone(), expected, span));
+                self.emit_err(self.type_mismatch(&actual, expected, span));
             }
         }
 
         actual
     }
 
+    /// Returns the `expected` type if it is an integer type that the integer type `actual` widens to without losing any value,
+    /// recording that the expression `id`, whose span is `span`, is implicitly widened.
+    pub(crate) fn widen(&self, actual: &Type, expected: &Option<Type>, id: NodeID, span: Span) -> Option<Type> {
+        match (actual, expected) {
+            (Type::Integer(from), Some(Type::Integer(to))) if from.widens_to(to) => {
+                self.check_feature(Feature::ImplicitWidening, span);
+                let widening = ImplicitWidening {
+                    from: *from,
+                    to: *to,
+                    span,
+                };
+                self.widenings.borrow_mut().insert(id, widening);
+                Some(Type::Integer(*to))
+            }
+            _ => None,
+        }
+    }
+
+    /// Use this method for the expression `id`, whose span is `expression_span` and whose type is `actual`.
+    /// Returns the `expected` type if `actual` is implicitly widened to it, and otherwise behaves as `assert_and_return_type`.
+    pub(crate) fn assert_or_widen_type(
+        &self,
+        actual: Type,
+        expected: &Option<Type>,
+        id: NodeID,
+        expression_span: Span,
+        span: Span,
+    ) -> Type {
+        let conversion = expected.as_ref().and_then(|expected| {
+            Some((
+                expected,
+                self.conversion_suggestion(&actual, expected, expression_span)?,
+            ))
+        });
+        match (self.widen(&actual, expected, id, expression_span), conversion) {
+            (Some(widened), _) => widened,
+            // The conversion is reported where it is written, e.g. at the use of a variable rather than its declaration.
+            (None, Some((expected, suggestion))) => {
+                self.emit_err(
+                    self.type_mismatch(&actual, expected, expression_span)
+                        .with_suggestion(suggestion),
+                );
+                actual
+            }
+            (None, None) => self.assert_and_return_type(actual, expected, span),
+        }
+    }
+
+    /// Returns a suggestion to convert the expression at `span`, of type `actual`, to the type `expected`,
+    /// if one of them is a boolean and the other an integer or a field.
+    /// Leo never converts between them implicitly, e.g. there is no truthiness, so the conversion must be written out:
+    /// a number is compared with zero, and a boolean selects one or zero with a ternary.
+    pub(crate) fn conversion_suggestion(&self, actual: &Type, expected: &Type, span: Span) -> Option<Suggestion> {
+        let (zero, one) = match (actual, expected) {
+            (Type::Integer(integer_type), Type::Boolean) | (Type::Boolean, Type::Integer(integer_type)) => {
+                (format!("0{integer_type}"), format!("1{integer_type}"))
+            }
+            (Type::Field, Type::Boolean) | (Type::Boolean, Type::Field) => ("0field".to_string(), "1field".to_string()),
+            _ => return None,
+        };
+        let source = with_session_globals(|s| s.source_map.contents_of_span(span))?;
+
+        Some(match expected {
+            Type::Boolean => {
+                // Operands other than names, e.g. `a + b`, are parenthesized, since `!=` may bind tighter.
+                let operand = match source.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.') {
+                    true => source,
+                    false => format!("({source})"),
+                };
+                Suggestion::new(
+                    span,
+                    format!("{operand} != {zero}"),
+                    format!("Compare with `{zero}` to get a boolean"),
+                )
+            }
+            _ => Suggestion::new(
+                span,
+                format!("({source} ? {one} : {zero})"),
+                format!("Select `{one}` or `{zero}` with a ternary"),
+            ),
+        })
+    }
+
+    /// Emits an error if the boolean result of an operation at `span` is used where the type `destination` is expected,
+    /// with a suggestion to convert it if a number is expected.
+    pub(crate) fn assert_bool_result(&self, destination: &Option<Type>, span: Span) {
+        let conversion = destination
+            .as_ref()
+            .and_then(|expected| Some((expected, self.conversion_suggestion(&Type::Boolean, expected, span)?)));
+        match conversion {
+            Some((expected, suggestion)) => self.emit_err(
+                self.type_mismatch(Type::Boolean, expected, span)
+                    .with_suggestion(suggestion),
+            ),
+            None => self.assert_bool_type(destination, span),
+        }
+    }
+
+    /// Widens the operand of the narrower integer type to the integer type of the other operand,
+    /// so that the operands of a binary operation have the same type if no value is lost.
+    /// Returns the types of the operands after widening.
+    pub(crate) fn widen_operands(
+        &self,
+        left: &Expression,
+        t1: Option<Type>,
+        right: &Expression,
+        t2: Option<Type>,
+    ) -> (Option<Type>, Option<Type>) {
+        if let (Some(left_type), Some(right_type)) = (&t1, &t2) {
+            if let Some(widened) = self.widen(left_type, &t2, left.id(), left.span()) {
+                return (Some(widened.clone()), Some(widened));
+            }
+            if let Some(widened) = self.widen(right_type, &t1, right.id(), right.span()) {
+                return (Some(widened.clone()), Some(widened));
+            }
+        }
+        (t1, t2)
+    }
+
     /// Emits an error to the error handler if the `actual` type is not equal to the `expected` type.
     pub(crate) fn assert_type(&self, actual: &Option<Type>, expected: &Type, span: Span) {
         self.check_type(
@@ -335,12 +541,107 @@ impl<'a> TypeChecker<'a> {
                         ident.span(),
                     ));
                 }
-                Some(core_instruction) => return Some(core_instruction),
+                Some(core_instruction) => {
+                    if let Some(feature) = core_instruction.feature() {
+                        self.check_feature(feature, ident.span() + function.span());
+                    }
+                    return Some(core_instruction);
+                }
             }
         }
         None
     }
 
+    /// Checks a call to an operation on a mapping, e.g. `Mapping::get(balances, addr)`, returning its output type.
+    /// Emits an error if the call is outside a finalize block, or if its arguments do not match the mapping.
+    pub(crate) fn check_mapping_operation(
+        &mut self,
+        operation: MappingOperation,
+        access: &'a AssociatedFunction,
+        expected: &Option<Type>,
+    ) -> Option<Type> {
+        if !self.is_finalize {
+            self.emit_err(TypeCheckerError::mapping_operation_outside_finalize(
+                access,
+                access.span(),
+            ));
+        }
+
+        if operation.num_args() != access.args.len() {
+            self.emit_err(TypeCheckerError::incorrect_num_args_to_call(
+                operation.num_args(),
+                access.args.len(),
+                access.span(),
+            ));
+            return None;
+        }
+
+        // The first argument is the mapping, whose key and value types the other arguments must have.
+        let mapping_type = match self.visit_expression(&access.args[0], &None)? {
+            Type::Mapping(mapping_type) => mapping_type,
+            type_ => {
+                self.emit_err(TypeCheckerError::expected_one_type_of(
+                    "mapping",
+                    type_,
+                    access.args[0].span(),
+                ));
+                return None;
+            }
+        };
+        self.visit_expression(&access.args[1], &Some(*mapping_type.key.clone()));
+        if let Some(value) = access.args.get(2) {
+            self.visit_expression(value, &Some(*mapping_type.value.clone()));
+        }
+
+        let output = match operation {
+            MappingOperation::Get | MappingOperation::GetOrUse => *mapping_type.value,
+            MappingOperation::Contains => Type::Boolean,
+            MappingOperation::Set | MappingOperation::Remove => Type::Unit,
+        };
+        Some(self.assert_or_widen_type(output, expected, access.id, access.span(), access.span()))
+    }
+
+    /// Warns about a commitment whose randomness is a literal, since it does not hide the committed value.
+    pub(crate) fn check_commitment_randomness(&self, core_instruction: &CoreInstruction, args: &[Expression]) {
+        if let Some(randomness) = core_instruction.randomness_index().and_then(|index| args.get(index)) {
+            if matches!(randomness, Expression::Literal(_)) {
+                self.handler
+                    .emit_warning(LintWarning::constant_commitment_randomness(randomness.span()));
+            }
+        }
+    }
+
+    /// Records that the owner of a record is checked, if `left` and `right` compare `record.owner` to `self.caller`.
+    pub(crate) fn record_owner_check(&mut self, left: &Expression, right: &Expression) {
+        let record = match (owner_of(left), owner_of(right)) {
+            (Some(record), _) if is_caller(right) => record,
+            (_, Some(record)) if is_caller(left) => record,
+            _ => return,
+        };
+        self.owner_checks.insert(record);
+    }
+
+    /// Emits a warning if the `owner` of a new `record` is a constant or computed address,
+    /// rather than the caller, an input, or the owner of another record.
+    pub(crate) fn check_record_owner(&self, record: Symbol, owner: &Expression) {
+        match owner {
+            Expression::Literal(_) => {
+                self.emit_warning(TypeCheckerWarning::constant_record_owner(record, owner.span()))
+            }
+            Expression::Binary(_)
+            | Expression::Cast(_)
+            | Expression::Unary(_)
+            | Expression::Access(AccessExpression::AssociatedFunction(_)) => {
+                self.emit_warning(TypeCheckerWarning::computed_record_owner(record, owner.span()))
+            }
+            Expression::Ternary(ternary) => {
+                self.check_record_owner(record, &ternary.if_true);
+                self.check_record_owner(record, &ternary.if_false);
+            }
+            _ => {}
+        }
+    }
+
     /// Returns the `struct` type and emits an error if the `expected` type does not match.
     pub(crate) fn check_expected_struct(&mut self, struct_: Identifier, expected: &Option<Type>, span: Span) -> Type {
         if let Some(Type::Identifier(expected)) = expected {
@@ -359,6 +660,19 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
+    /// Returns `true` if the type is a record, or a tuple that contains one.
+    pub(crate) fn contains_record(&self, type_: &Type) -> bool {
+        match type_ {
+            Type::Identifier(identifier) => self
+                .symbol_table
+                .borrow()
+                .lookup_struct(identifier.name)
+                .map_or(false, |struct_| struct_.is_record),
+            Type::Tuple(tuple_type) => tuple_type.iter().any(|type_| self.contains_record(type_)),
+            _ => false,
+        }
+    }
+
     /// Emits an error if the struct member is a record type.
     pub(crate) fn assert_member_is_not_record(&self, span: Span, parent: Symbol, type_: &Type) {
         match type_ {
@@ -384,6 +698,53 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
+    /// Emits an error for each cycle of structs that contain themselves, directly or through other structs.
+    /// Each cycle is reported at its first member, with a label at each of the members that close it.
+    pub(crate) fn check_struct_cycles(&self, structs: &IndexMap<Identifier, Struct>) {
+        // The members of each struct whose type is a struct, with the name of that struct.
+        let members: IndexMap<Symbol, Vec<(Symbol, &Member)>> = structs
+            .values()
+            .map(|struct_| {
+                let members = struct_
+                    .members
+                    .iter()
+                    .filter_map(|member| match &member.type_ {
+                        Type::Identifier(identifier) => Some((identifier.name, member)),
+                        _ => None,
+                    })
+                    .collect();
+                (struct_.identifier.name, members)
+            })
+            .collect();
+
+        let mut cycles = Vec::new();
+        let mut finished = IndexSet::new();
+        for name in members.keys() {
+            if !finished.contains(name) {
+                find_struct_cycles(*name, &members, &mut Vec::new(), &mut finished, &mut cycles);
+            }
+        }
+
+        for cycle in cycles {
+            let (struct_, member) = cycle[0];
+            let path = cycle
+                .iter()
+                .map(|(struct_, _)| struct_.to_string())
+                .chain(std::iter::once(struct_.to_string()))
+                .join(" -> ");
+            let error = cycle.iter().skip(1).fold(
+                TypeCheckerError::cyclic_struct(struct_, path, member.identifier.span),
+                |error, (struct_, member)| {
+                    error.with_label(
+                        member.identifier.span,
+                        format!("`{struct_}` has a member of type `{}` here", member.type_),
+                    )
+                },
+            );
+            self.emit_err(error);
+        }
+    }
+
     /// Emits an error if the type is not valid.
     pub(crate) fn assert_type_is_valid(&self, span: Span, type_: &Type) {
         match type_ {
@@ -420,3 +781,48 @@ impl<'a> TypeChecker<'a> {
 fn types_to_string(types: &[Type]) -> String {
     types.iter().map(|type_| type_.to_string()).join(", ")
 }
+
+/// Returns the variable `record` if `expression` is `record.owner`.
+fn owner_of(expression: &Expression) -> Option<Symbol> {
+    match expression {
+        Expression::Access(AccessExpression::Member(access)) if access.name.name == sym::owner => {
+            match &*access.inner {
+                Expression::Identifier(record) => Some(record.name),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether `expression` is `self.caller`.
+fn is_caller(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::Access(AccessExpression::Member(access))
+            if access.name.name == sym::caller
+                && matches!(&*access.inner, Expression::Identifier(inner) if inner.name == sym::SelfLower)
+    )
+}
+
+/// Searches the members of the struct `name` depth-first for a struct that leads back to one in `path`,
+/// which holds the structs that lead to `name`, each with its member of the type of the next.
+/// Each cycle that is found is added to `cycles` as the structs in it, each with its member of the type of the next.
+fn find_struct_cycles<'a>(
+    name: Symbol,
+    members: &IndexMap<Symbol, Vec<(Symbol, &'a Member)>>,
+    path: &mut Vec<(Symbol, &'a Member)>,
+    finished: &mut IndexSet<Symbol>,
+    cycles: &mut Vec<Vec<(Symbol, &'a Member)>>,
+) {
+    for (type_, member) in members.get(&name).into_iter().flatten() {
+        path.push((name, *member));
+        if let Some(start) = path.iter().position(|(struct_, _)| struct_ == type_) {
+            cycles.push(path[start..].to_vec());
+        } else if !finished.contains(type_) {
+            find_struct_cycles(*type_, members, path, finished, cycles);
+        }
+        path.pop();
+    }
+    finished.insert(name);
+}
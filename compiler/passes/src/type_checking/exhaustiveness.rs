@@ -0,0 +1,110 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::Literal;
+
+/// The values that a pattern fitting the type of the matched value matches, regardless of the variables it binds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum PatternShape {
+    /// A wildcard or a binding, which matches any value.
+    Any,
+    /// A boolean literal.
+    Boolean(bool),
+    /// Any other literal, one of too many values to list, identified by its text.
+    Value(String),
+    /// A tuple, or a struct with its members in declaration order.
+    Fields(Vec<PatternShape>),
+}
+
+impl PatternShape {
+    /// Returns the shape of a literal pattern.
+    pub(crate) fn literal(literal: &Literal) -> Self {
+        match literal {
+            Literal::Boolean(value, ..) => PatternShape::Boolean(*value),
+            // An integer literal may be widened to the type of the matched value, so only its value is compared.
+            Literal::Integer(_, value, ..) => PatternShape::Value(value.clone()),
+            literal => PatternShape::Value(literal.to_string()),
+        }
+    }
+}
+
+/// Returns `true` if some value matched by the patterns of `row` is matched by none of the rows of `matrix`.
+/// A match statement is exhaustive if a wildcard is not useful after its arms,
+/// and an arm is reachable if its pattern is useful after the patterns of the arms before it.
+pub(crate) fn is_useful(matrix: &[Vec<PatternShape>], row: &[PatternShape]) -> bool {
+    let (head, tail) = match row.split_first() {
+        Some(split) => split,
+        None => return matrix.is_empty(),
+    };
+    match head {
+        PatternShape::Fields(fields) => is_useful(
+            &specialize_fields(matrix, fields.len()),
+            &[fields.as_slice(), tail].concat(),
+        ),
+        PatternShape::Any => {
+            // Tuples and structs have a single shape, so any value of them is matched by a wildcard for each field.
+            let arity = matrix.iter().find_map(|row| match &row[0] {
+                PatternShape::Fields(fields) => Some(fields.len()),
+                _ => None,
+            });
+            if let Some(arity) = arity {
+                let fields = vec![PatternShape::Any; arity];
+                return is_useful(&specialize_fields(matrix, arity), &[fields.as_slice(), tail].concat());
+            }
+
+            // If both booleans are listed, the value is one of them.
+            let booleans = [PatternShape::Boolean(false), PatternShape::Boolean(true)];
+            if booleans
+                .iter()
+                .all(|boolean| matrix.iter().any(|row| &row[0] == boolean))
+            {
+                return booleans
+                    .iter()
+                    .any(|boolean| is_useful(&specialize_value(matrix, boolean), tail));
+            }
+
+            // Otherwise, some value is matched only by the rows that start with a wildcard.
+            let rest: Vec<_> = matrix
+                .iter()
+                .filter(|row| row[0] == PatternShape::Any)
+                .map(|row| row[1..].to_vec())
+                .collect();
+            is_useful(&rest, tail)
+        }
+        value => is_useful(&specialize_value(matrix, value), tail),
+    }
+}
+
+/// Returns the rows of `matrix` that match a tuple or struct, with their first pattern replaced by its `arity` fields.
+fn specialize_fields(matrix: &[Vec<PatternShape>], arity: usize) -> Vec<Vec<PatternShape>> {
+    matrix
+        .iter()
+        .filter_map(|row| match &row[0] {
+            PatternShape::Fields(fields) => Some([fields.as_slice(), &row[1..]].concat()),
+            PatternShape::Any => Some([vec![PatternShape::Any; arity].as_slice(), &row[1..]].concat()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the rows of `matrix` that match the literal `value`, without their first pattern.
+fn specialize_value(matrix: &[Vec<PatternShape>], value: &PatternShape) -> Vec<Vec<PatternShape>> {
+    matrix
+        .iter()
+        .filter(|row| row[0] == PatternShape::Any || &row[0] == value)
+        .map(|row| row[1..].to_vec())
+        .collect()
+}
@@ -27,20 +27,116 @@ pub use check_statements::*;
 pub mod checker;
 pub use checker::*;
 
-use crate::{Pass, SymbolTable};
+pub(crate) mod exhaustiveness;
+pub(crate) use exhaustiveness::*;
 
-use leo_ast::{Ast, ProgramVisitor};
+use crate::{CallGraph, ImplicitWidenings, MatchTypes, Pass, SymbolTable};
+
+use leo_ast::{
+    Ast, DefinitionStatement, Expression, ExpressionVisitor, Function, LanguageVersion, ProgramVisitor,
+    StatementVisitor, Type,
+};
 use leo_errors::{emitter::Handler, Result};
 
 impl<'a> Pass for TypeChecker<'a> {
-    type Input = (&'a Ast, &'a Handler, SymbolTable);
-    type Output = Result<SymbolTable>;
+    type Input = (&'a Ast, &'a Handler, SymbolTable, LanguageVersion);
+    type Output = Result<(SymbolTable, ImplicitWidenings, MatchTypes, TypeTable, CallGraph)>;
+
+    fn do_pass((ast, handler, st, version): Self::Input) -> Self::Output {
+        let mut visitor = TypeChecker::new(st, handler, version);
+        visitor.visit_program(ast.as_repr());
+        handler.last_err()?;
+
+        Ok((
+            visitor.symbol_table.take(),
+            visitor.widenings.take(),
+            visitor.match_types,
+            visitor.type_table,
+            visitor.call_graph,
+        ))
+    }
+}
 
-    fn do_pass((ast, handler, st): Self::Input) -> Self::Output {
-        let mut visitor = TypeChecker::new(st, handler);
+/// The results of type checking a program that may not type check.
+#[derive(Default)]
+pub struct PartialTypeCheck {
+    /// The symbol table, with the variables of the functions that were checked.
+    pub symbol_table: SymbolTable,
+    /// The types of the checked expressions.
+    /// Expressions whose type cannot be determined because of an error have the error type.
+    pub type_table: TypeTable,
+    /// The integers that are implicitly widened.
+    pub widenings: ImplicitWidenings,
+    /// Whether any errors were emitted while checking the program.
+    pub has_errors: bool,
+}
+
+impl<'a> TypeChecker<'a> {
+    /// Type checks a program like the type checking pass, but returns what was checked even if the program has errors,
+    /// e.g. so that an editor can show the types in a file that is being edited.
+    /// Every function is checked, since an error in one function does not stop the others from being checked.
+    /// Errors are emitted to the handler.
+    pub fn check_partially(
+        ast: &'a Ast,
+        handler: &'a Handler,
+        st: SymbolTable,
+        version: LanguageVersion,
+    ) -> PartialTypeCheck {
+        let errors = handler.err_count();
+        let mut visitor = TypeChecker::new(st, handler, version);
+        visitor.record_error_types = true;
         visitor.visit_program(ast.as_repr());
+
+        PartialTypeCheck {
+            symbol_table: visitor.symbol_table.take(),
+            type_table: visitor.type_table,
+            widenings: visitor.widenings.take(),
+            has_errors: handler.err_count() > errors,
+        }
+    }
+
+    /// Type checks a single function, whose signature must already be in the symbol table.
+    /// This allows a function to be re-checked after an edit without checking the rest of the program.
+    pub fn check_function(
+        function: &'a Function,
+        handler: &'a Handler,
+        st: SymbolTable,
+        version: LanguageVersion,
+    ) -> Result<SymbolTable> {
+        let mut visitor = TypeChecker::new(st, handler, version);
+        visitor.visit_function(function);
         handler.last_err()?;
 
         Ok(visitor.symbol_table.take())
     }
+
+    /// Type checks a single expression outside of any function, against the variables in the symbol table.
+    /// Returns the type of the expression, if it has one, and the integers in it that are implicitly widened.
+    pub fn check_expression(
+        expression: &'a Expression,
+        handler: &'a Handler,
+        st: SymbolTable,
+        version: LanguageVersion,
+    ) -> Result<(Option<Type>, ImplicitWidenings)> {
+        let mut visitor = TypeChecker::new(st, handler, version);
+        let type_ = visitor.visit_expression(expression, &None);
+        handler.last_err()?;
+
+        Ok((type_, visitor.widenings.take()))
+    }
+
+    /// Type checks a single definition outside of any function, adding the variable it defines to the symbol table.
+    /// Also returns the integers in the definition that are implicitly widened.
+    pub fn check_definition(
+        definition: &'a DefinitionStatement,
+        handler: &'a Handler,
+        st: SymbolTable,
+        version: LanguageVersion,
+    ) -> Result<(SymbolTable, ImplicitWidenings)> {
+        let mut visitor = TypeChecker::new(st, handler, version);
+        visitor.visit_definition(definition);
+        handler.last_err()?;
+
+        Ok((visitor.symbol_table.take(), visitor.widenings.take()))
+    }
 }
@@ -15,6 +15,8 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 #![forbid(unsafe_code)]
+// The `symbols!` macro recurses once per symbol.
+#![recursion_limit = "256"]
 
 pub mod symbol;
 pub use symbol::{sym, Symbol};
@@ -25,3 +27,6 @@ pub use span::Span;
 pub mod span_json;
 
 pub mod source_map;
+
+pub mod snippet;
+pub use snippet::SnippetConfig;
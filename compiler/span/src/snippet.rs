@@ -0,0 +1,32 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The configuration of how the source code that diagnostics point to is rendered.
+
+/// How the source lines of a diagnostic are rendered.
+/// By default, only the lines of the span are shown, in full and with their tabs as they are.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SnippetConfig {
+    /// The number of source lines that are shown before the lines of the span.
+    pub context_before: usize,
+    /// The number of source lines that are shown after the lines of the span.
+    pub context_after: usize,
+    /// The maximum width of a rendered line, including its line number, if there is one.
+    /// Longer lines are cut to the columns around the start of the span.
+    pub max_width: Option<usize>,
+    /// The number of columns that each tab is expanded to, if tabs are expanded.
+    pub tab_width: Option<usize>,
+}
@@ -52,6 +52,11 @@ impl Span {
     pub fn is_dummy(&self) -> bool {
         self == &Self::dummy()
     }
+
+    /// Does the span fully enclose `other`?
+    pub fn contains(&self, other: Span) -> bool {
+        self.lo <= other.lo && other.hi <= self.hi
+    }
 }
 
 impl fmt::Display for Span {
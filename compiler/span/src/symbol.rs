@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::snippet::SnippetConfig;
 use crate::source_map::SourceMap;
 
 use core::borrow::Borrow;
@@ -25,7 +26,7 @@ use core::{fmt, str};
 use fxhash::FxBuildHasher;
 use indexmap::IndexSet;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::cell::RefCell;
+use std::sync::{Arc, RwLock};
 
 /// A helper for `symbols` defined below.
 /// The macro's job is to bind conveniently  usable `const` items to the symbol names provided.
@@ -144,12 +145,20 @@ symbols! {
     BHP768,
     BHP1024,
     commit,
+    commit_tagged,
+    contains,
+    get,
+    get_or_use,
     hash,
+    Mapping,
     Pedersen64,
     Pedersen128,
     Poseidon2,
     Poseidon4,
     Poseidon8,
+    remove,
+    set,
+    verify,
 
     // types
     address,
@@ -159,6 +168,7 @@ symbols! {
     Constant,
     constants,
     field,
+    Future,
     group,
     i8,
     i16,
@@ -167,6 +177,7 @@ symbols! {
     i128,
     record,
     scalar,
+    signature,
     string,
     u8,
     u16,
@@ -182,7 +193,9 @@ symbols! {
     AlwaysConst,
     assert,
     Async: "async",
+    Await: "await",
     caller,
+    cases,
     circuit,
     Class: "class",
     context,
@@ -204,7 +217,11 @@ symbols! {
     assert_neq,
     main,
     mapping,
+    Match: "match",
     Mut: "mut",
+    no_owner_check,
+    none,
+    optimize,
     prelude,
     Public,
     Return: "return",
@@ -221,6 +238,7 @@ symbols! {
     aleo,
     public,
     private,
+    witness,
     owner,
     gates,
     _nonce,
@@ -290,11 +308,14 @@ impl fmt::Display for Symbol {
 }
 
 /// All the globals for a compiler sessions.
+/// The globals can be shared by several threads, e.g. to compile independent programs in parallel.
 pub struct SessionGlobals {
     /// The interner for `Symbol`s used in the compiler.
     symbol_interner: Interner,
     /// The source map used in the compiler.
     pub source_map: SourceMap,
+    /// How the source lines of diagnostics are rendered.
+    snippet_config: RwLock<SnippetConfig>,
 }
 
 impl Default for SessionGlobals {
@@ -302,10 +323,23 @@ impl Default for SessionGlobals {
         Self {
             symbol_interner: Interner::prefilled(),
             source_map: SourceMap::default(),
+            snippet_config: RwLock::default(),
         }
     }
 }
 
+impl SessionGlobals {
+    /// Returns how the source lines of diagnostics are rendered.
+    pub fn snippet_config(&self) -> SnippetConfig {
+        *self.snippet_config.read().unwrap()
+    }
+
+    /// Sets how the source lines of diagnostics are rendered.
+    pub fn set_snippet_config(&self, config: SnippetConfig) {
+        *self.snippet_config.write().unwrap() = config;
+    }
+}
+
 scoped_tls::scoped_thread_local!(pub static SESSION_GLOBALS: SessionGlobals);
 
 /// Creates the session globals and then runs the closure `f`.
@@ -325,15 +359,23 @@ pub fn with_session_globals<R>(f: impl FnOnce(&SessionGlobals) -> R) -> R {
     SESSION_GLOBALS.with(f)
 }
 
+/// Runs the closure `f` with the given session globals,
+/// e.g. on a thread other than the one that created them.
+#[inline]
+pub fn set_session_globals_then<R>(session_globals: &SessionGlobals, f: impl FnOnce() -> R) -> R {
+    SESSION_GLOBALS.set(session_globals, f)
+}
+
 /// An interned string,
 /// either prefilled "at compile time" (`Static`),
 /// or created at runtime (`Owned`).
-#[derive(Eq)]
+#[derive(Clone, Eq)]
 enum InternedStr {
     /// String is stored "at compile time", i.e. prefilled.
     Static(&'static str),
     /// String is constructed and stored during runtime.
-    Owned(Box<str>),
+    /// It is reference counted, so that it can be read after the lock on the interner is released.
+    Owned(Arc<str>),
 }
 
 impl Borrow<str> for InternedStr {
@@ -376,7 +418,7 @@ struct InnerInterner {
 
 /// A symbol-to-string interner.
 struct Interner {
-    inner: RefCell<InnerInterner>,
+    inner: RwLock<InnerInterner>,
 }
 
 impl Interner {
@@ -392,25 +434,34 @@ impl Interner {
             set: init.iter().copied().map(InternedStr::Static).collect(),
         };
         Self {
-            inner: RefCell::new(inner),
+            inner: RwLock::new(inner),
         }
     }
 
     /// Interns `string`, returning a `Symbol` corresponding to it.
     fn intern(&self, string: &str) -> Symbol {
-        let InnerInterner { set } = &mut *self.inner.borrow_mut();
-
-        if let Some(sym) = set.get_index_of(string) {
+        if let Some(sym) = self.inner.read().unwrap().set.get_index_of(string) {
             // Already interned, return that symbol.
             return Symbol::new(sym as u32);
         }
 
+        // Another thread may have interned the string since the read lock was released,
+        // in which case its symbol is returned.
+        let InnerInterner { set } = &mut *self.inner.write().unwrap();
         Symbol::new(set.insert_full(InternedStr::Owned(string.into())).0 as u32)
     }
 
     /// Returns the corresponding string for the given symbol.
     fn get<R>(&self, symbol: Symbol, with: impl FnOnce(&str) -> R) -> R {
-        let set = &self.inner.borrow().set;
-        with(set.get_index(symbol.as_u32() as usize).unwrap())
+        // The lock is released before calling `with`, which may use the interner itself.
+        let string = self
+            .inner
+            .read()
+            .unwrap()
+            .set
+            .get_index(symbol.as_u32() as usize)
+            .unwrap()
+            .clone();
+        with(&string)
     }
 }
@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::{Field, Suggestion};
+
 use std::fmt;
 
 use backtrace::Backtrace;
@@ -44,6 +46,12 @@ pub struct Backtraced {
     pub type_: String,
     /// Is this Backtrace a warning or error?
     pub error: bool,
+    /// The edits suggested to fix the error.
+    pub suggestions: Vec<Suggestion>,
+    /// The name of the function that created the error, e.g. `type_should_be`, which identifies the kind of error.
+    pub kind: &'static str,
+    /// The arguments that the message was created from.
+    pub fields: Vec<Field>,
     #[derivative(PartialEq = "ignore")]
     #[derivative(Hash = "ignore")]
     /// The backtrace representing where the error occured in Leo.
@@ -71,6 +79,9 @@ impl Backtraced {
             code_identifier,
             type_,
             error,
+            suggestions: Vec::new(),
+            kind: "",
+            fields: Vec::new(),
             backtrace,
         }
     }
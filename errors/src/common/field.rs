@@ -0,0 +1,83 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Display};
+
+/// An argument of a diagnostic, e.g. the expected type of a type mismatch,
+/// so that tools can inspect a diagnostic without parsing its message.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Field {
+    /// The name of the argument in the definition of the diagnostic, e.g. `expected`.
+    pub name: String,
+    /// The value of the argument, rendered as in the message.
+    pub value: String,
+}
+
+impl Field {
+    /// Returns a field with the given name and value.
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// Renders an argument of a diagnostic as the value of a field.
+/// The arguments are rendered with `Display` where possible, as a list if they are a slice, and with `Debug` otherwise.
+///
+/// The rendering is chosen by the method that `(&&&FieldValue(&arg)).render()` resolves to,
+/// which prefers the implementations for the most references, since the arguments have no common trait.
+#[doc(hidden)]
+pub struct FieldValue<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait RenderDisplay {
+    fn render(&self) -> String;
+}
+
+impl<T: Display + ?Sized> RenderDisplay for &&FieldValue<'_, T> {
+    fn render(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[doc(hidden)]
+pub trait RenderSlice {
+    fn render(&self) -> String;
+}
+
+impl<T: Display> RenderSlice for &FieldValue<'_, &[T]> {
+    fn render(&self) -> String {
+        self.0
+            .iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[doc(hidden)]
+pub trait RenderDebug {
+    fn render(&self) -> String;
+}
+
+impl<T: Debug + ?Sized> RenderDebug for FieldValue<'_, T> {
+    fn render(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
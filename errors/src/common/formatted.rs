@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Backtraced, INDENT};
+use crate::{write_snippet, Backtraced, Label, INDENT};
 
 use leo_span::{source_map::SpanLocation, symbol::with_session_globals, Span};
 
@@ -36,6 +36,8 @@ use std::fmt;
 pub struct Formatted {
     /// The formatted error span information.
     pub span: Span,
+    /// The secondary locations of the error, each with a message explaining its relevance.
+    pub labels: Vec<Label>,
     /// The backtrace to track where the Leo error originated.
     pub backtrace: Backtraced,
 }
@@ -58,6 +60,7 @@ impl Formatted {
     {
         Self {
             span,
+            labels: Vec::new(),
             backtrace: Backtraced::new_from_backtrace(
                 message.to_string(),
                 help,
@@ -88,26 +91,7 @@ impl Formatted {
 
 impl fmt::Display for Formatted {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let underline = |mut start: usize, mut end: usize| -> String {
-            if start > end {
-                std::mem::swap(&mut start, &mut end)
-            }
-
-            let mut underline = String::new();
-
-            for _ in 0..start {
-                underline.push(' ');
-                end -= 1;
-            }
-
-            for _ in 0..end {
-                underline.push('^');
-            }
-
-            underline
-        };
-
-        let (loc, contents) = with_session_globals(|s| {
+        let (loc, contents, config) = with_session_globals(|s| {
             (
                 s.source_map
                     .span_to_location(self.span)
@@ -115,11 +99,10 @@ impl fmt::Display for Formatted {
                 s.source_map
                     .line_contents_of_span(self.span)
                     .unwrap_or_else(|| "<contents unavailable>".to_owned()),
+                s.snippet_config(),
             )
         });
 
-        let underlined = underline(loc.col_start, loc.col_stop);
-
         let (kind, code) = if self.backtrace.error {
             ("Error", self.error_code())
         } else {
@@ -147,29 +130,18 @@ impl fmt::Display for Formatted {
         write!(
             f,
             "\n{indent     }--> {path}:{line_start}:{start}\n\
-            {indent     } |\n",
+            {indent     } |",
             indent = INDENT,
             path = &loc.source_file.name,
             line_start = loc.line_start,
             start = loc.col_start,
         )?;
 
-        for (line_no, line) in contents.lines().enumerate() {
-            writeln!(
-                f,
-                "{line_no:width$} | {text}",
-                width = INDENT.len(),
-                line_no = loc.line_start + line_no,
-                text = line,
-            )?;
-        }
+        write_snippet(f, &loc, &contents, None, config)?;
 
-        write!(
-            f,
-            "{indent     } |{underlined}",
-            indent = INDENT,
-            underlined = underlined,
-        )?;
+        for label in &self.labels {
+            write!(f, "\n{indent     } |\n{label}", indent = INDENT, label = label)?;
+        }
 
         if let Some(help) = &self.backtrace.help {
             write!(
@@ -0,0 +1,81 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{write_snippet, INDENT};
+
+use leo_span::{symbol::with_session_globals, Span};
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A secondary location of a diagnostic, with a message explaining its relevance.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Label {
+    /// The source text that the label points to.
+    pub span: Span,
+    /// The message shown next to the source text, e.g. "expected because of this".
+    pub message: String,
+}
+
+impl Label {
+    /// Returns a new label that points to `span`.
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// Renders the label as the location and source lines it points to, underlined and followed by its message, e.g.
+///     --> main.leo:1:30
+///      |
+///    1 | function foo(a: u8) -> u16 {
+///      |                        ^^^ expected because of this
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location = with_session_globals(|s| {
+            Some((
+                s.source_map.span_to_location(self.span)?,
+                s.source_map.line_contents_of_span(self.span)?,
+                s.snippet_config(),
+            ))
+        });
+        let (loc, contents, config) = match location {
+            Some(location) => location,
+            None => {
+                return write!(
+                    f,
+                    "{indent     } = note: {message}",
+                    indent = INDENT,
+                    message = self.message
+                )
+            }
+        };
+
+        write!(
+            f,
+            "{indent     }--> {path}:{line_start}:{start}\n\
+            {indent     } |",
+            indent = INDENT,
+            path = &loc.source_file.name,
+            line_start = loc.line_start,
+            start = loc.col_start,
+        )?;
+
+        write_snippet(f, &loc, &contents, Some(&self.message), config)
+    }
+}
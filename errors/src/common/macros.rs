@@ -29,6 +29,8 @@ macro_rules! create_messages {
     ($(#[$error_type_docs:meta])* $type_:ident, code_mask: $code_mask:expr, code_prefix: $code_prefix:expr, $($(#[$docs:meta])* @$formatted_or_backtraced_list:ident $names:ident { args: ($($arg_names:ident: $arg_types:ty$(,)?)*), msg: $messages:expr, help: $helps:expr, })*) => {
         #[allow(unused_imports)] // Allow unused for errors that only use formatted or backtraced errors.
         use $crate::{Backtraced, Formatted, LeoMessageCode};
+        #[allow(unused_imports)] // Allow unused for errors whose messages have no arguments of some kinds.
+        use $crate::{FieldValue, RenderDebug, RenderDisplay, RenderSlice};
 
         use backtrace::Backtrace;
 
@@ -88,6 +90,86 @@ macro_rules! create_messages {
 
         // Steps over the list of functions with an initial code of 0.
         impl $type_ {
+            /// Returns the message, without its location or help text.
+            pub fn message(&self) -> &str {
+                match self {
+                    Self::Formatted(formatted) => &formatted.backtrace.message,
+                    Self::Backtraced(backtraced) => &backtraced.message,
+                }
+            }
+
+            /// Returns the help text of the message, if it has one.
+            pub fn help(&self) -> Option<&str> {
+                match self {
+                    Self::Formatted(formatted) => formatted.backtrace.help.as_deref(),
+                    Self::Backtraced(backtraced) => backtraced.help.as_deref(),
+                }
+            }
+
+            /// Returns the edits suggested to fix the message.
+            pub fn suggestions(&self) -> &[$crate::Suggestion] {
+                match self {
+                    Self::Formatted(formatted) => &formatted.backtrace.suggestions,
+                    Self::Backtraced(backtraced) => &backtraced.suggestions,
+                }
+            }
+
+            /// Attaches `suggestion` to the message as an edit that fixes it.
+            pub fn with_suggestion(mut self, suggestion: $crate::Suggestion) -> Self {
+                match &mut self {
+                    Self::Formatted(formatted) => formatted.backtrace.suggestions.push(suggestion),
+                    Self::Backtraced(backtraced) => backtraced.suggestions.push(suggestion),
+                }
+                self
+            }
+
+            /// Returns the secondary locations that the message points to.
+            pub fn labels(&self) -> &[$crate::Label] {
+                match self {
+                    Self::Formatted(formatted) => &formatted.labels,
+                    Self::Backtraced(_) => &[],
+                }
+            }
+
+            /// Attaches a secondary location to the message, with a message explaining its relevance.
+            /// Backtraced messages have no location, so the label is only attached to formatted messages.
+            pub fn with_label(mut self, span: leo_span::Span, message: impl ToString) -> Self {
+                if let Self::Formatted(formatted) = &mut self {
+                    formatted.labels.push($crate::Label::new(span, message.to_string()));
+                }
+                self
+            }
+
+            /// Returns the name of the function that created the message, e.g. `type_should_be`.
+            /// The names are stable, so tools can match on them rather than on the text of the message.
+            pub fn kind(&self) -> &'static str {
+                match self {
+                    Self::Formatted(formatted) => formatted.backtrace.kind,
+                    Self::Backtraced(backtraced) => backtraced.kind,
+                }
+            }
+
+            /// Returns the arguments that the message was created from, in the order of the function that created it.
+            pub fn fields(&self) -> &[$crate::Field] {
+                match self {
+                    Self::Formatted(formatted) => &formatted.backtrace.fields,
+                    Self::Backtraced(backtraced) => &backtraced.fields,
+                }
+            }
+
+            /// Returns the value of the argument `name` that the message was created from, if it has one.
+            pub fn field(&self, name: &str) -> Option<&str> {
+                self.fields().iter().find(|field| field.name == name).map(|field| field.value.as_str())
+            }
+
+            /// Returns the span that the message points to, if it has one.
+            pub fn span(&self) -> Option<leo_span::Span> {
+                match self {
+                    Self::Formatted(formatted) => Some(formatted.span),
+                    Self::Backtraced(_) => None,
+                }
+            }
+
             create_messages!(@step 0i32, $(($(#[$docs])* $formatted_or_backtraced_list, $names($($arg_names: $arg_types,)*), $messages, $helps),)*);
         }
     };
@@ -97,19 +179,23 @@ macro_rules! create_messages {
         $(#[$error_func_docs])*
         // Expands additional arguments for the error defining function.
         pub fn $name($($arg_names: $arg_types,)* span: leo_span::Span) -> Self {
-            Self::Formatted(
-                Formatted::new_from_span(
-                    $message,
-                    $help,
-                    $code + Self::code_mask(),
-                    Self::code_identifier(),
-                    Self::message_type(),
-                    Self::is_error(),
-                    span,
-                    // Each function always generates its own backtrace for backtrace clarity to originate from the error function.
-                    Backtrace::new(),
-                )
-            )
+            // The fields are rendered first, since the message may consume the arguments.
+            #[allow(clippy::needless_borrow)] // The borrows choose how each argument is rendered.
+            let fields = vec![$($crate::Field::new(stringify!($arg_names), (&&&FieldValue(&$arg_names)).render()),)*];
+            let mut formatted = Formatted::new_from_span(
+                $message,
+                $help,
+                $code + Self::code_mask(),
+                Self::code_identifier(),
+                Self::message_type(),
+                Self::is_error(),
+                span,
+                // Each function always generates its own backtrace for backtrace clarity to originate from the error function.
+                Backtrace::new(),
+            );
+            formatted.backtrace.kind = stringify!($name);
+            formatted.backtrace.fields = fields;
+            Self::Formatted(formatted)
         }
 
         // Steps the code value by one and calls on the rest of the functions.
@@ -120,18 +206,22 @@ macro_rules! create_messages {
         $(#[$error_func_docs])*
         // Expands additional arguments for the error defining function.
         pub fn $name($($arg_names: $arg_types,)*) -> Self {
-            Self::Backtraced(
-                Backtraced::new_from_backtrace(
-                    $message,
-                    $help,
-                    $code + Self::code_mask(),
-                    Self::code_identifier(),
-                    Self::message_type(),
-                    Self::is_error(),
-                    // Each function always generates its own backtrace for backtrace clarity to originate from the error function.
-                    Backtrace::new(),
-                )
-            )
+            // The fields are rendered first, since the message may consume the arguments.
+            #[allow(clippy::needless_borrow)] // The borrows choose how each argument is rendered.
+            let fields = vec![$($crate::Field::new(stringify!($arg_names), (&&&FieldValue(&$arg_names)).render()),)*];
+            let mut backtraced = Backtraced::new_from_backtrace(
+                $message,
+                $help,
+                $code + Self::code_mask(),
+                Self::code_identifier(),
+                Self::message_type(),
+                Self::is_error(),
+                // Each function always generates its own backtrace for backtrace clarity to originate from the error function.
+                Backtrace::new(),
+            );
+            backtraced.kind = stringify!($name);
+            backtraced.fields = fields;
+            Self::Backtraced(backtraced)
         }
 
         // Steps the code value by one and calls on the rest of the functions.
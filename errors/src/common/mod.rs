@@ -18,15 +18,31 @@
 pub mod backtraced;
 pub use self::backtraced::*;
 
+/// This module contains the arguments of diagnostics.
+pub mod field;
+pub use self::field::*;
+
 /// This module contains a formatted error and its methods.
 pub mod formatted;
 pub use self::formatted::*;
 
+/// This module contains labeled secondary locations of diagnostics.
+pub mod label;
+pub use self::label::*;
+
 /// This module contains the macros for making errors easily.
 #[macro_use]
 pub mod macros;
 pub use self::macros::*;
 
+/// This module renders the source lines that diagnostics point to.
+pub(crate) mod snippet;
+pub(crate) use self::snippet::*;
+
+/// This module contains suggested edits that fix diagnostics.
+pub mod suggestion;
+pub use self::suggestion::*;
+
 /// This module contains traits for making errors easily.
 pub mod traits;
 pub use self::traits::*;
@@ -0,0 +1,142 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::INDENT;
+
+use leo_span::{source_map::SpanLocation, SnippetConfig};
+
+use std::fmt;
+
+/// The width of the line number and the separator that precede each rendered line, e.g. `   2 | `.
+const GUTTER_WIDTH: usize = INDENT.len() + 3;
+
+/// The width of the `...` that marks where a line was cut.
+const ELLIPSIS_WIDTH: usize = 3;
+
+/// Writes the source lines of a span with their line numbers, followed by carets under the span, e.g.
+///    2 | let a = x;
+///      |         ^
+/// `contents` are the lines of the span at `loc`, and `message`, if any, is written after the carets.
+/// Each line, including the first, is preceded by a newline.
+/// The lines shown around the span, the width of the lines, and the expansion of tabs are set by `config`.
+pub(crate) fn write_snippet(
+    f: &mut fmt::Formatter,
+    loc: &SpanLocation,
+    contents: &str,
+    message: Option<&str>,
+    config: SnippetConfig,
+) -> fmt::Result {
+    let expand = |line: &str| match config.tab_width {
+        Some(width) => line.replace('\t', &" ".repeat(width)),
+        None => line.to_string(),
+    };
+    // Returns the column that the 1-based character column `col` of `line` is rendered at, once tabs are expanded.
+    let column = |line: &str, col: usize| match config.tab_width {
+        Some(width) => {
+            let before: usize = line
+                .chars()
+                .take(col.saturating_sub(1))
+                .map(|c| if c == '\t' { width } else { 1 })
+                .sum();
+            before + col.min(1)
+        }
+        None => col,
+    };
+
+    let span_lines: Vec<&str> = contents.lines().collect();
+    let mut start = column(span_lines.first().copied().unwrap_or_default(), loc.col_start);
+    let mut end = column(span_lines.last().copied().unwrap_or_default(), loc.col_stop);
+    if start > end {
+        std::mem::swap(&mut start, &mut end);
+    }
+
+    // The lines of the file around the span. A dummy location, whose line is 0, has none.
+    let source: Vec<&str> = loc.source_file.src.lines().collect();
+    let context = |lines: std::ops::Range<usize>| -> Vec<(usize, String)> {
+        lines
+            .filter(|_| loc.line_start > 0)
+            .filter_map(|line_no| Some((line_no, expand(source.get(line_no.checked_sub(1)?)?))))
+            .collect()
+    };
+    let before = context(loc.line_start.saturating_sub(config.context_before).max(1)..loc.line_start);
+    let after = context(loc.line_stop + 1..loc.line_stop + 1 + config.context_after);
+    let span_lines: Vec<(usize, String)> = span_lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| (loc.line_start + i, expand(line)))
+        .collect();
+
+    // Lines that are too wide are cut to the same columns, which start a little before the span if it would not fit.
+    let width = config
+        .max_width
+        .map(|width| width.saturating_sub(GUTTER_WIDTH).max(2 * ELLIPSIS_WIDTH + 1));
+    let offset = match width {
+        Some(width) if end > width + 1 => start.saturating_sub(1).saturating_sub(width / 3),
+        _ => 0,
+    };
+    let cut = |line: &str| -> String {
+        let width = match width {
+            Some(width) => width,
+            None => return line.to_string(),
+        };
+        let chars: Vec<char> = line.chars().collect();
+        let mut visible: Vec<char> = chars.iter().copied().skip(offset).take(width).collect();
+        if visible.len() >= ELLIPSIS_WIDTH {
+            if offset > 0 {
+                visible[..ELLIPSIS_WIDTH].fill('.');
+            }
+            if chars.len() > offset + width {
+                let len = visible.len();
+                visible[len - ELLIPSIS_WIDTH..].fill('.');
+            }
+        }
+        visible.into_iter().collect()
+    };
+
+    let write_lines = |f: &mut fmt::Formatter, lines: &[(usize, String)]| -> fmt::Result {
+        for (line_no, line) in lines {
+            write!(
+                f,
+                "\n{line_no:width$} | {text}",
+                width = INDENT.len(),
+                line_no = line_no,
+                text = cut(line),
+            )?;
+        }
+        Ok(())
+    };
+
+    write_lines(f, &before)?;
+    write_lines(f, &span_lines)?;
+
+    // Only the carets of the columns that are shown are written.
+    let visible_end = match width {
+        Some(width) => end.min(offset + width + 1),
+        None => end,
+    };
+    write!(
+        f,
+        "\n{indent     } |{spaces}{carets}",
+        indent = INDENT,
+        spaces = " ".repeat(start - offset),
+        carets = "^".repeat(visible_end.saturating_sub(start)),
+    )?;
+    if let Some(message) = message {
+        write!(f, " {}", message)?;
+    }
+
+    write_lines(f, &after)
+}
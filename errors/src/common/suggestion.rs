@@ -0,0 +1,126 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::INDENT;
+
+use leo_span::{
+    span::{BytePos, Pos},
+    symbol::with_session_globals,
+    Span,
+};
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A machine-applicable edit that replaces the source text covered by `span` with `replacement`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Suggestion {
+    /// The source text to replace.
+    pub span: Span,
+    /// The text to replace it with.
+    pub replacement: String,
+    /// A description of the edit, e.g. "Add the type suffix `u8`".
+    pub message: String,
+}
+
+impl Suggestion {
+    /// Returns a new suggestion that replaces the text covered by `span` with `replacement`.
+    pub fn new(span: Span, replacement: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            replacement: replacement.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Applies `suggestions` to `source`, which starts at `start_pos` in the source map.
+    /// Suggestions that overlap an earlier suggestion, or that lie outside of `source`, are skipped.
+    pub fn apply_all(source: &str, start_pos: BytePos, suggestions: &[Suggestion]) -> String {
+        let mut suggestions = suggestions.iter().collect::<Vec<_>>();
+        suggestions.sort_by_key(|suggestion| suggestion.span.lo);
+
+        let mut fixed = String::with_capacity(source.len());
+        // The offset into `source` up to which text has been copied.
+        let mut copied = 0;
+        for suggestion in suggestions {
+            let (lo, hi) = match (
+                suggestion.span.lo.to_usize().checked_sub(start_pos.to_usize()),
+                suggestion.span.hi.to_usize().checked_sub(start_pos.to_usize()),
+            ) {
+                (Some(lo), Some(hi)) => (lo, hi),
+                _ => continue,
+            };
+            if lo < copied || hi > source.len() || !source.is_char_boundary(lo) || !source.is_char_boundary(hi) {
+                continue;
+            }
+            fixed.push_str(&source[copied..lo]);
+            fixed.push_str(&suggestion.replacement);
+            copied = hi;
+        }
+        fixed.push_str(&source[copied..]);
+        fixed
+    }
+}
+
+/// Renders the suggestion as a note followed by the source lines with the edit applied, e.g.
+///     = suggestion: Change the type suffix to `u64`
+///     |
+///   2 | let a: u64 = 1u64;
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{indent     } = suggestion: {message}",
+            indent = INDENT,
+            message = self.message
+        )?;
+
+        let location = with_session_globals(|s| {
+            Some((
+                s.source_map.span_to_location(self.span)?,
+                s.source_map.line_contents_of_span(self.span)?,
+            ))
+        });
+        let (loc, contents) = match location {
+            Some(location) => location,
+            None => return Ok(()),
+        };
+
+        // The columns of the location count characters, so find the byte offsets of the span in its lines.
+        let start = contents
+            .char_indices()
+            .nth(loc.col_start - 1)
+            .map_or(contents.len(), |(i, _)| i);
+        let end = start + (self.span.hi.to_usize() - self.span.lo.to_usize());
+        if end > contents.len() || !contents.is_char_boundary(end) {
+            return Ok(());
+        }
+        let fixed = format!("{}{}{}", &contents[..start], self.replacement, &contents[end..]);
+
+        write!(f, "\n{indent     } |", indent = INDENT)?;
+        for (line_no, line) in fixed.lines().enumerate() {
+            write!(
+                f,
+                "\n{line_no:width$} | {text}",
+                width = INDENT.len(),
+                line_no = loc.line_start + line_no,
+                text = line,
+            )?;
+        }
+
+        Ok(())
+    }
+}
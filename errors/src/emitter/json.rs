@@ -0,0 +1,217 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::Emitter;
+use crate::{Field, Label, LeoError, LeoWarning, Suggestion};
+
+use leo_span::{span::Pos, symbol::with_session_globals, Span};
+
+use serde::{Deserialize, Serialize};
+
+/// Whether a diagnostic is an error or a warning.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The diagnostic is an error.
+    Error,
+    /// The diagnostic is a warning.
+    Warning,
+}
+
+/// The location of a diagnostic in a source file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosticSpan {
+    /// The name of the source file.
+    pub file: String,
+    /// The byte offset of the start of the span in the file.
+    pub byte_start: usize,
+    /// The byte offset of the end of the span in the file.
+    pub byte_end: usize,
+    /// The line on which the span starts, starting from 1.
+    pub line_start: usize,
+    /// The line on which the span ends, starting from 1.
+    pub line_end: usize,
+    /// The column at which the span starts, starting from 1.
+    pub column_start: usize,
+    /// The column at which the span ends, starting from 1.
+    pub column_end: usize,
+}
+
+impl DiagnosticSpan {
+    /// Returns the location of `span`, if it points into the source map.
+    pub fn new(span: Span) -> Option<Self> {
+        with_session_globals(|s| {
+            let location = s.source_map.span_to_location(span)?;
+            let start_pos = location.source_file.start_pos.to_usize();
+            Some(Self {
+                file: location.source_file.name.to_string(),
+                byte_start: span.lo.to_usize() - start_pos,
+                byte_end: span.hi.to_usize() - start_pos,
+                line_start: location.line_start,
+                line_end: location.line_stop,
+                column_start: location.col_start,
+                column_end: location.col_stop,
+            })
+        })
+    }
+}
+
+/// A secondary location of a diagnostic, with a message explaining its relevance.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosticLabel {
+    /// The location that the label points to.
+    pub span: DiagnosticSpan,
+    /// The message shown next to the location.
+    pub message: String,
+}
+
+impl DiagnosticLabel {
+    /// Returns the machine-readable form of `label`, if its span points into the source map.
+    pub fn new(label: &Label) -> Option<Self> {
+        Some(Self {
+            span: DiagnosticSpan::new(label.span)?,
+            message: label.message.clone(),
+        })
+    }
+}
+
+/// An edit suggested to fix a diagnostic.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosticSuggestion {
+    /// The location of the source text to replace.
+    pub span: DiagnosticSpan,
+    /// The text to replace it with.
+    pub replacement: String,
+    /// A description of the edit.
+    pub message: String,
+}
+
+impl DiagnosticSuggestion {
+    /// Returns the machine-readable form of `suggestion`, if its span points into the source map.
+    pub fn new(suggestion: &Suggestion) -> Option<Self> {
+        Some(Self {
+            span: DiagnosticSpan::new(suggestion.span)?,
+            replacement: suggestion.replacement.clone(),
+            message: suggestion.message.clone(),
+        })
+    }
+}
+
+/// A machine-readable representation of an error or warning.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Whether the diagnostic is an error or a warning.
+    pub severity: Severity,
+    /// The code of the diagnostic, e.g. `EPAR0370005`, if it has one.
+    pub code: Option<String>,
+    /// The name of the function that created the diagnostic, e.g. `type_should_be`, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// The message of the diagnostic, without its location or help text.
+    pub message: String,
+    /// The arguments that the message was created from.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<Field>,
+    /// The locations that the diagnostic points to.
+    pub spans: Vec<DiagnosticSpan>,
+    /// The secondary locations that the diagnostic points to.
+    pub labels: Vec<DiagnosticLabel>,
+    /// Additional help text.
+    pub notes: Vec<String>,
+    /// The edits suggested to fix the diagnostic.
+    pub suggestions: Vec<DiagnosticSuggestion>,
+}
+
+impl Diagnostic {
+    /// Returns the diagnostic that describes `err`.
+    pub fn from_error(err: &LeoError) -> Self {
+        // Errors from snarkVM have neither a code nor a span.
+        if let LeoError::Anyhow(_) = err {
+            return Self {
+                severity: Severity::Error,
+                code: None,
+                kind: None,
+                message: err.message().into_owned(),
+                fields: Vec::new(),
+                spans: Vec::new(),
+                labels: Vec::new(),
+                notes: Vec::new(),
+                suggestions: Vec::new(),
+            };
+        }
+
+        Self {
+            severity: Severity::Error,
+            code: Some(err.error_code()),
+            kind: Some(err.kind()).filter(|kind| !kind.is_empty()).map(str::to_string),
+            message: err.message().to_string(),
+            fields: err.fields().to_vec(),
+            spans: err.span().and_then(DiagnosticSpan::new).into_iter().collect(),
+            labels: err.labels().iter().filter_map(DiagnosticLabel::new).collect(),
+            notes: err.help().map(str::to_string).into_iter().collect(),
+            suggestions: err.suggestions().iter().filter_map(DiagnosticSuggestion::new).collect(),
+        }
+    }
+
+    /// Returns the diagnostic that describes `warning`.
+    pub fn from_warning(warning: &LeoWarning) -> Self {
+        Self {
+            severity: Severity::Warning,
+            code: Some(warning.error_code()),
+            kind: Some(warning.kind().to_string()),
+            message: warning.message().to_string(),
+            fields: warning.fields().to_vec(),
+            spans: warning.span().and_then(DiagnosticSpan::new).into_iter().collect(),
+            labels: warning.labels().iter().filter_map(DiagnosticLabel::new).collect(),
+            notes: warning.help().map(str::to_string).into_iter().collect(),
+            suggestions: warning
+                .suggestions()
+                .iter()
+                .filter_map(DiagnosticSuggestion::new)
+                .collect(),
+        }
+    }
+
+    /// Returns the diagnostic serialized as a single line of JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("diagnostics are always serializable")
+    }
+}
+
+/// An `Emitter` that prints each diagnostic to the standard error as a line of JSON.
+#[derive(Default)]
+pub struct JsonEmitter {
+    /// Exit code of the last emitted error.
+    last_error_code: Option<i32>,
+}
+
+impl Emitter for JsonEmitter {
+    fn emit_err(&mut self, err: LeoError) {
+        self.last_error_code = Some(err.exit_code());
+        // The error has already been reported, and only carries its exit code.
+        if !matches!(err, LeoError::LastErrorCode(_)) {
+            eprintln!("{}", Diagnostic::from_error(&err).to_json());
+        }
+    }
+
+    fn last_emitted_err_code(&self) -> Option<i32> {
+        self.last_error_code
+    }
+
+    fn emit_warning(&mut self, warning: LeoWarning) {
+        eprintln!("{}", Diagnostic::from_warning(&warning).to_json());
+    }
+}
@@ -14,13 +14,20 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::LeoWarning;
+use crate::{CompilerError, LeoWarning};
 
 use super::LeoError;
 use core::default::Default;
 use core::fmt;
+use leo_span::Span;
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::str::FromStr;
+
+/// Contains the emitter for machine-readable diagnostics.
+pub mod json;
+pub use json::*;
 
 /// Types that are sinks for compiler errors.
 pub trait Emitter {
@@ -44,6 +51,9 @@ impl Emitter for StderrEmitter {
     fn emit_err(&mut self, err: LeoError) {
         self.last_error_code = Some(err.exit_code());
         eprintln!("{}", err);
+        for suggestion in err.suggestions() {
+            eprintln!("{suggestion}");
+        }
     }
 
     fn last_emitted_err_code(&self) -> Option<i32> {
@@ -52,6 +62,41 @@ impl Emitter for StderrEmitter {
 
     fn emit_warning(&mut self, warning: LeoWarning) {
         eprintln!("{warning}");
+        for suggestion in warning.suggestions() {
+            eprintln!("{suggestion}");
+        }
+    }
+}
+
+/// The format in which diagnostics are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// Diagnostics are rendered for humans, with source snippets.
+    #[default]
+    Human,
+    /// Each diagnostic is printed as a line of JSON.
+    Json,
+}
+
+impl ErrorFormat {
+    /// Returns an `Emitter` that prints diagnostics to the standard error in this format.
+    pub fn emitter(self) -> Box<dyn Emitter> {
+        match self {
+            Self::Human => Box::new(StderrEmitter { last_error_code: None }),
+            Self::Json => Box::new(JsonEmitter::default()),
+        }
+    }
+}
+
+impl FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown error format `{s}`, expected `human` or `json`")),
+        }
     }
 }
 
@@ -136,6 +181,39 @@ impl Emitter for BufferEmitter {
     }
 }
 
+/// How a warning is reported when it is emitted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WarningLevel {
+    /// The warning is silently dropped.
+    Allow,
+    /// The warning is reported.
+    #[default]
+    Warn,
+    /// The warning is reported and causes compilation to fail.
+    Deny,
+}
+
+/// Configures the level at which each warning is reported.
+#[derive(Clone, Debug, Default)]
+pub struct WarningConfig {
+    /// The level of warnings without an explicit level.
+    pub default_level: WarningLevel,
+    /// The levels of individual warnings, keyed by warning code, e.g. `WTYC0372000`.
+    pub levels: HashMap<String, WarningLevel>,
+}
+
+impl WarningConfig {
+    /// Sets the level of the warning with the given `code`.
+    pub fn set_level(&mut self, code: impl Into<String>, level: WarningLevel) {
+        self.levels.insert(code.into(), level);
+    }
+
+    /// Returns the level of the warning with the given `code`.
+    pub fn level(&self, code: &str) -> WarningLevel {
+        self.levels.get(code).copied().unwrap_or(self.default_level)
+    }
+}
+
 /// Contains the actual data for `Handler`.
 /// Modelled this way to afford an API using interior mutability.
 struct HandlerInner {
@@ -143,13 +221,46 @@ struct HandlerInner {
     err_count: usize,
     /// Number of warnings emitted thus far.
     warn_count: usize,
+    /// Number of emitted warnings whose level is `Deny`.
+    denied_count: usize,
+    /// Number of errors that were not emitted because the error limit was reached.
+    suppressed_count: usize,
+    /// The codes and spans of the errors emitted thus far.
+    emitted: HashSet<(String, Span)>,
+    /// The number of errors after which further errors are suppressed, if any.
+    error_limit: Option<usize>,
+    /// The levels at which warnings are reported.
+    warning_config: WarningConfig,
     /// The sink through which errors will be emitted.
     emitter: Box<dyn Emitter>,
 }
 
 impl HandlerInner {
     /// Emit the error `err`.
+    /// An error with the same code and span as an earlier error is dropped.
+    /// Once the error limit is reached, a summary is emitted in place of any further errors.
     fn emit_err(&mut self, err: LeoError) {
+        // Errors that only carry an exit code have already been reported.
+        if !matches!(err, LeoError::LastErrorCode(_)) {
+            // The same error is often emitted repeatedly, e.g. for each iteration of an unrolled loop.
+            if let Some(span) = err.span() {
+                if !self.emitted.insert((err.error_code(), span)) {
+                    return;
+                }
+            }
+
+            if let Some(limit) = self.error_limit {
+                if self.err_count >= limit {
+                    if self.suppressed_count == 0 {
+                        self.emitter
+                            .emit_err(CompilerError::error_limit_reached(self.err_count).into());
+                    }
+                    self.suppressed_count = self.suppressed_count.saturating_add(1);
+                    return;
+                }
+            }
+        }
+
         self.err_count = self.err_count.saturating_add(1);
         self.emitter.emit_err(err);
     }
@@ -159,8 +270,13 @@ impl HandlerInner {
         self.emitter.last_emitted_err_code()
     }
 
-    /// Emit the error `err`.
+    /// Emit the warning `warning` according to its configured level.
     fn emit_warning(&mut self, warning: LeoWarning) {
+        match self.warning_config.level(&warning.error_code()) {
+            WarningLevel::Allow => return,
+            WarningLevel::Warn => {}
+            WarningLevel::Deny => self.denied_count = self.denied_count.saturating_add(1),
+        }
         self.warn_count = self.warn_count.saturating_add(1);
         self.emitter.emit_warning(warning);
     }
@@ -175,7 +291,7 @@ pub struct Handler {
 
 impl Default for Handler {
     fn default() -> Self {
-        Self::new(Box::new(StderrEmitter { last_error_code: None }))
+        Self::new(ErrorFormat::default().emitter())
     }
 }
 
@@ -185,6 +301,11 @@ impl Handler {
         let inner = RefCell::new(HandlerInner {
             err_count: 0,
             warn_count: 0,
+            denied_count: 0,
+            suppressed_count: 0,
+            emitted: HashSet::new(),
+            error_limit: None,
+            warning_config: WarningConfig::default(),
             emitter,
         });
         Self { inner }
@@ -209,9 +330,20 @@ impl Handler {
         self.inner.borrow_mut().emit_err(err.into());
     }
 
-    /// Emit the error `err`.
-    pub fn emit_warning(&self, warning: LeoWarning) {
-        self.inner.borrow_mut().emit_warning(warning);
+    /// Emit the warning `warning`.
+    pub fn emit_warning<W: Into<LeoWarning>>(&self, warning: W) {
+        self.inner.borrow_mut().emit_warning(warning.into());
+    }
+
+    /// Sets the levels at which subsequent warnings are reported.
+    pub fn set_warning_config(&self, config: WarningConfig) {
+        self.inner.borrow_mut().warning_config = config;
+    }
+
+    /// Sets the number of errors after which further errors are suppressed.
+    /// `None` means that every error is emitted.
+    pub fn set_error_limit(&self, limit: Option<usize>) {
+        self.inner.borrow_mut().error_limit = limit;
     }
 
     /// Emits the error `err`.
@@ -227,11 +359,26 @@ impl Handler {
         self.inner.borrow().err_count
     }
 
+    /// The number of errors thus far that were suppressed because the error limit was reached.
+    pub fn suppressed_err_count(&self) -> usize {
+        self.inner.borrow().suppressed_count
+    }
+
+    /// Whether errors are being suppressed because the error limit was reached.
+    pub fn error_limit_reached(&self) -> bool {
+        self.suppressed_err_count() > 0
+    }
+
     /// The number of warnings thus far.
     pub fn warning_count(&self) -> usize {
         self.inner.borrow().warn_count
     }
 
+    /// The number of warnings thus far whose level is `Deny`.
+    pub fn denied_warning_count(&self) -> usize {
+        self.inner.borrow().denied_count
+    }
+
     /// Did we have any errors thus far?
     pub fn had_errors(&self) -> bool {
         self.err_count() > 0
@@ -264,8 +411,13 @@ impl Handler {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ParserError;
-    use leo_span::{symbol::create_session_if_not_set_then, Span};
+    use crate::{Field, LintWarning, ParserError, ParserWarning, Suggestion, TypeCheckerError};
+    use leo_span::{
+        source_map::FileName,
+        span::BytePos,
+        symbol::{create_session_if_not_set_then, with_session_globals},
+        SnippetConfig, Span,
+    };
 
     #[test]
     fn fresh_no_errors() {
@@ -302,4 +454,245 @@ mod tests {
             Handler::with(|_| Ok(())).unwrap();
         })
     }
+
+    #[test]
+    fn snippet_config() {
+        create_session_if_not_set_then(|_| {
+            let source = "let a: u8 = 1u8;\n\tlet b: u8 = y;\nlet c: u8 = 2u8;\n";
+            let sf = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom("test".into())));
+            let span = Span::new(sf.start_pos + BytePos(30), sf.start_pos + BytePos(31));
+            let render = |config: SnippetConfig| {
+                with_session_globals(|s| s.set_snippet_config(config));
+                LeoError::from(ParserError::unexpected_eof(span)).to_string()
+            };
+
+            // By default, only the line of the span is shown, as it is.
+            assert!(render(SnippetConfig::default()).contains("\n   2 | \tlet b: u8 = y;\n     |              ^"));
+
+            let config = SnippetConfig {
+                context_before: 1,
+                context_after: 1,
+                max_width: None,
+                tab_width: Some(4),
+            };
+            assert!(render(config).contains(
+                "\n   1 | let a: u8 = 1u8;\n   2 |     let b: u8 = y;\n     |                 ^\n   3 | let c: u8 = 2u8;"
+            ));
+
+            // The lines are cut to the columns around the span.
+            let config = SnippetConfig {
+                max_width: Some(17),
+                ..config
+            };
+            assert!(render(config).contains("\n   2 | ...y;\n     |    ^\n"));
+        })
+    }
+
+    #[test]
+    fn deduplication() {
+        create_session_if_not_set_then(|_| {
+            let source = "let x: u8 = y;\nlet z: u8 = y;";
+            let sf = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom("test".into())));
+            let span =
+                |lo: usize, hi: usize| Span::new(sf.start_pos + BytePos(lo as u32), sf.start_pos + BytePos(hi as u32));
+
+            let (handler, buf) = Handler::new_with_buf();
+            handler.emit_err(ParserError::unexpected_eof(span(12, 13)));
+            handler.emit_err(ParserError::unexpected_eof(span(12, 13)));
+            assert_eq!(handler.err_count(), 1);
+
+            // The same error at another location, or another error at the same location, is still emitted.
+            handler.emit_err(ParserError::unexpected_eof(span(27, 28)));
+            handler.emit_err(ParserError::invalid_import_list(span(12, 13)));
+            assert_eq!(handler.err_count(), 3);
+            assert_eq!(buf.extract_errs().into_inner().len(), 3);
+        })
+    }
+
+    #[test]
+    fn error_limit() {
+        create_session_if_not_set_then(|_| {
+            let source = "let a: u8 = b;";
+            let sf = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom("test".into())));
+            let span = |lo: usize| Span::new(sf.start_pos + BytePos(lo as u32), sf.start_pos + BytePos(lo as u32 + 1));
+
+            let (handler, buf) = Handler::new_with_buf();
+            handler.set_error_limit(Some(2));
+            for lo in 0..5 {
+                handler.emit_err(ParserError::unexpected_eof(span(lo)));
+            }
+            assert_eq!(handler.err_count(), 2);
+            assert_eq!(handler.suppressed_err_count(), 3);
+            assert!(handler.error_limit_reached());
+
+            // The two errors are followed by a single summary.
+            let errs = buf.extract_errs().into_inner();
+            assert_eq!(errs.len(), 3);
+            assert_eq!(
+                errs[2].error_code(),
+                LeoError::from(CompilerError::error_limit_reached(2)).error_code()
+            );
+        })
+    }
+
+    #[test]
+    fn warning_levels() {
+        create_session_if_not_set_then(|_| {
+            let (handler, buf) = Handler::new_with_buf();
+            let code = LeoWarning::from(ParserWarning::const_parameter_or_input(Span::default())).error_code();
+
+            let mut config = WarningConfig::default();
+            config.set_level(code.clone(), WarningLevel::Allow);
+            handler.set_warning_config(config.clone());
+            handler.emit_warning(ParserWarning::const_parameter_or_input(Span::default()));
+            assert_eq!(handler.warning_count(), 0);
+
+            config.set_level(code, WarningLevel::Deny);
+            handler.set_warning_config(config);
+            handler.emit_warning(ParserWarning::const_parameter_or_input(Span::default()));
+            assert_eq!(handler.warning_count(), 1);
+            assert_eq!(handler.denied_warning_count(), 1);
+            assert!(!handler.had_errors());
+
+            handler.set_warning_config(WarningConfig::default());
+            handler.emit_warning(ParserWarning::const_parameter_or_input(Span::default()));
+            assert_eq!(handler.warning_count(), 2);
+            assert_eq!(handler.denied_warning_count(), 1);
+            assert_eq!(buf.extract_warnings().into_inner().len(), 2);
+        })
+    }
+
+    #[test]
+    fn fingerprint_ignores_position() {
+        create_session_if_not_set_then(|_| {
+            let source = "let x: u8 = 1u8;\n\n    let x: u8 = 1u8;\nlet y: u8 = 1u8;";
+            let sf = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom("test".into())));
+            let span =
+                |lo: usize, hi: usize| Span::new(sf.start_pos + BytePos(lo as u32), sf.start_pos + BytePos(hi as u32));
+            let fingerprint = |span| LeoWarning::from(LintWarning::unused_variable("x", span)).fingerprint();
+
+            // The same warning on the same code is recognized after the code moves.
+            assert_eq!(fingerprint(span(0, 16)), fingerprint(span(22, 38)));
+            // A warning on different code is not.
+            assert_ne!(fingerprint(span(0, 16)), fingerprint(span(39, 55)));
+        })
+    }
+
+    #[test]
+    fn json_diagnostics() {
+        create_session_if_not_set_then(|_| {
+            let source = "function main() {\n    let x: u8 = 1u8;\n}";
+            let sf = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom("test".into())));
+            let span = Span::new(sf.start_pos + BytePos(26), sf.start_pos + BytePos(27));
+
+            let warning = LeoWarning::from(LintWarning::unused_variable("x", span));
+            let json = Diagnostic::from_warning(&warning).to_json();
+            let diagnostic: Diagnostic = serde_json::from_str(&json).unwrap();
+            assert!(!json.contains('\n'));
+            assert_eq!(diagnostic.severity, Severity::Warning);
+            assert_eq!(diagnostic.code, Some(warning.error_code()));
+            assert_eq!(diagnostic.message, warning.message());
+            assert_eq!(
+                diagnostic.spans,
+                vec![DiagnosticSpan {
+                    file: "test".to_string(),
+                    byte_start: 26,
+                    byte_end: 27,
+                    line_start: 2,
+                    line_end: 2,
+                    column_start: 9,
+                    column_end: 10,
+                }]
+            );
+
+            let error = LeoError::from(ParserError::unexpected_eof(span));
+            let diagnostic = Diagnostic::from_error(&error);
+            assert_eq!(diagnostic.severity, Severity::Error);
+            assert_eq!(diagnostic.code, Some(error.error_code()));
+            assert_eq!(diagnostic.spans.len(), 1);
+
+            // Errors from snarkVM are reported with their text, but without a code or a span.
+            let error = LeoError::from(anyhow::anyhow!("failed to parse the program"));
+            let diagnostic = Diagnostic::from_error(&error);
+            assert_eq!(error.message(), "failed to parse the program");
+            assert_eq!(diagnostic.message, "failed to parse the program");
+            assert_eq!(diagnostic.code, None);
+            assert!(diagnostic.spans.is_empty());
+            let mut emitter = JsonEmitter::default();
+            emitter.emit_err(error);
+            assert_eq!(emitter.last_emitted_err_code(), Some(1));
+        })
+    }
+
+    #[test]
+    fn fields() {
+        create_session_if_not_set_then(|_| {
+            let error = LeoError::from(TypeCheckerError::type_should_be("u8", "u16", Span::default()));
+            assert_eq!(error.kind(), "type_should_be");
+            assert_eq!(
+                error.fields(),
+                &[Field::new("type_", "u8"), Field::new("expected", "u16")]
+            );
+
+            // Slices are rendered as lists, and other arguments without `Display` with `Debug`.
+            let error = ParserError::unexpected_ident("x", &["y", "z"], Span::default());
+            assert_eq!(error.field("expected"), Some("y, z"));
+            let error = TypeCheckerError::assertion_always_fails("false", None, Span::default());
+            assert_eq!(error.field("message"), Some("None"));
+
+            let diagnostic = Diagnostic::from_error(&LeoError::from(error));
+            assert_eq!(diagnostic.kind.as_deref(), Some("assertion_always_fails"));
+            assert_eq!(diagnostic.fields.len(), 2);
+        })
+    }
+
+    #[test]
+    fn suggestions() {
+        create_session_if_not_set_then(|_| {
+            let source = "function main() {\n    let x: u64 = 1u8;\n}";
+            let sf = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom("test".into())));
+            let span = Span::new(sf.start_pos + BytePos(35), sf.start_pos + BytePos(38));
+
+            let suggestion = Suggestion::new(span, "1u64", "Change the type suffix to `u64`");
+            let error = LeoError::from(ParserError::unexpected_eof(span).with_suggestion(suggestion.clone()));
+            assert_eq!(error.suggestions(), &[suggestion.clone()]);
+            assert!(suggestion.to_string().ends_with("2 |     let x: u64 = 1u64;"));
+
+            let diagnostic = Diagnostic::from_error(&error);
+            assert_eq!(diagnostic.suggestions.len(), 1);
+            assert_eq!(diagnostic.suggestions[0].replacement, "1u64");
+            assert_eq!(diagnostic.suggestions[0].span.byte_start, 35);
+        })
+    }
+
+    #[test]
+    fn labels() {
+        create_session_if_not_set_then(|_| {
+            let source = "function foo() -> u16 {\n    return 1u8;\n}";
+            let sf = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom("test".into())));
+            let span =
+                |lo: usize, hi: usize| Span::new(sf.start_pos + BytePos(lo as u32), sf.start_pos + BytePos(hi as u32));
+
+            let error = LeoError::from(
+                TypeCheckerError::return_type_mismatch("u16", "u8", span(35, 38))
+                    .with_label(span(18, 21), "expected `u16` because of the return type"),
+            );
+            assert_eq!(error.span(), Some(span(35, 38)));
+            assert_eq!(error.labels().len(), 1);
+            let rendered = error.to_string();
+            assert!(rendered.contains("--> test:2:12"));
+            assert!(rendered.contains("--> test:1:19"));
+            assert!(rendered.contains("1 | function foo() -> u16 {"));
+            assert!(rendered.ends_with("^^^ expected `u16` because of the return type"));
+
+            let diagnostic = Diagnostic::from_error(&error);
+            assert_eq!(diagnostic.spans.len(), 1);
+            assert_eq!(diagnostic.labels.len(), 1);
+            assert_eq!(diagnostic.labels[0].span.byte_start, 18);
+            assert_eq!(
+                diagnostic.labels[0].message,
+                "expected `u16` because of the return type"
+            );
+        })
+    }
 }
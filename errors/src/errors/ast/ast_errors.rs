@@ -121,4 +121,44 @@ create_messages!(
         msg: format!("variable `{var}` shadowed by"),
         help: None,
     }
+
+    /// For when the AST fails to be encoded in the binary format.
+    @backtraced
+    failed_to_convert_ast_to_binary {
+        args: (error: impl Display),
+        msg: format!("failed to convert ast to the binary format {}", error),
+        help: None,
+    }
+
+    /// For when data in the binary format fails to be decoded.
+    @backtraced
+    failed_to_read_binary_ast {
+        args: (error: impl Display),
+        msg: format!("failed to read a binary ast: {}", error),
+        help: None,
+    }
+
+    /// For when data in the binary format was written by another version of the compiler.
+    @backtraced
+    incompatible_binary_ast {
+        args: (version: impl Display),
+        msg: format!("the binary ast was not written by Leo {}", version),
+        help: Some("Binary ASTs can only be read by the version of Leo that wrote them. Write the AST again, or use JSON to exchange it.".to_string()),
+    }
+
+    /// For when the AST fails to be written to a binary file.
+    @backtraced
+    failed_to_write_ast_to_binary_file {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("failed to write ast to a binary file `{:?}` {}", path, error),
+        help: None,
+    }
+
+    /// For when a value that is not a literal, such as a tuple, is converted to a literal.
+    @backtraced
+    value_is_not_a_literal {
+        args: (value: impl Display),
+        msg: format!("the value `{}` cannot be written as a literal", value),
+        help: None,
+    }
 );
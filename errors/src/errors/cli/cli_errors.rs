@@ -150,4 +150,249 @@ create_messages!(
         msg: format!("Failed to parse the `aleo deploy` command.\nSnarkVM Error: {}", error),
         help: None,
     }
+
+    @backtraced
+    language_server_failed {
+        args: (error: impl Display),
+        msg: format!("The language server failed: {}", error),
+        help: None,
+    }
+
+    @backtraced
+    files_not_formatted {
+        args: (count: impl Display),
+        msg: format!("{} file(s) are not formatted.", count),
+        help: Some("Run `leo fmt` to format them.".to_string()),
+    }
+
+    @backtraced
+    failed_to_read_execution {
+        args: (path: impl Display, error: impl Display),
+        msg: format!("Failed to read the execution in `{}`.\nSnarkVM Error: {}", path, error),
+        help: Some("The execution must be a JSON file, as written by snarkVM.".to_string()),
+    }
+
+    @backtraced
+    failed_to_verify_execution {
+        args: (error: impl Display),
+        msg: format!("Failed to verify the execution.\nSnarkVM Error: {}", error),
+        help: None,
+    }
+
+    @backtraced
+    debug_adapter_failed {
+        args: (error: impl Display),
+        msg: format!("The debug adapter failed: {}", error),
+        help: None,
+    }
+
+    @backtraced
+    invalid_breakpoint {
+        args: (breakpoint: impl Display),
+        msg: format!("Invalid breakpoint `{}`.", breakpoint),
+        help: Some("Breakpoints are written as `FILE:LINE` or `LINE`, e.g. `main.leo:3`.".to_string()),
+    }
+
+    @backtraced
+    failed_to_load_signer {
+        args: (source: impl Display, error: impl Display),
+        msg: format!("Failed to load the signer from `{}`: {}", source, error),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_sign {
+        args: (error: impl Display),
+        msg: format!("The signer failed to sign: {}", error),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_encrypt_keystore {
+        args: (),
+        msg: "Failed to encrypt the private key of the keystore.",
+        help: None,
+    }
+
+    @backtraced
+    failed_to_decrypt_keystore {
+        args: (),
+        msg: "Failed to decrypt the private key of the keystore.",
+        help: Some("Check that the password is correct, and that the keystore file has not been modified.".to_string()),
+    }
+
+    @backtraced
+    failed_to_write_keystore {
+        args: (path: impl Display, error: impl Display),
+        msg: format!("Failed to write the keystore `{}`: {}", path, error),
+        help: None,
+    }
+
+    @backtraced
+    keystore_passwords_differ {
+        args: (),
+        msg: "The passwords do not match.",
+        help: None,
+    }
+
+    @backtraced
+    tests_failed {
+        args: (count: impl Display),
+        msg: format!("{} test(s) failed.", count),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_write_instruction_counts {
+        args: (path: impl Display, error: impl Display),
+        msg: format!("Failed to write the instruction counts to `{}`: {}", path, error),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_run_script {
+        args: (hook: impl Display, command: impl Display, error: impl Display),
+        msg: format!("Failed to run the {} script `{}`: {}", hook, command, error),
+        help: None,
+    }
+
+    @backtraced
+    script_failed {
+        args: (hook: impl Display, command: impl Display, status: impl Display),
+        msg: format!("The {} script `{}` failed with {}.", hook, command, status),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_write_source_map {
+        args: (path: impl Display, error: impl Display),
+        msg: format!("Failed to write the source map to `{}`: {}", path, error),
+        help: None,
+    }
+
+    @backtraced
+    unknown_command {
+        args: (name: impl Display),
+        msg: format!("`{}` is not a Leo command, and no plugin `leo-{}` was found on PATH.", name, name),
+        help: Some("Run `leo --help` for the list of commands, or `leo plugins` for the list of plugins.".to_string()),
+    }
+
+    @backtraced
+    failed_to_run_plugin {
+        args: (name: impl Display, error: impl Display),
+        msg: format!("Failed to run the plugin `leo-{}`: {}", name, error),
+        help: None,
+    }
+
+    @backtraced
+    plugin_failed {
+        args: (name: impl Display, status: impl Display),
+        msg: format!("The plugin `leo-{}` failed with {}.", name, status),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_write_assert_report {
+        args: (path: impl Display, error: impl Display),
+        msg: format!("Failed to write the report of removed assertions to `{}`: {}", path, error),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_write_call_graph {
+        args: (path: impl Display, error: impl Display),
+        msg: format!("Failed to write the call graph to `{}`: {}", path, error),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_prove {
+        args: (prover: impl Display, error: impl Display),
+        msg: format!("The proving service at {} failed to prove the execution: {}", prover, error),
+        help: None,
+    }
+
+    @backtraced
+    invalid_program_id {
+        args: (program: impl Display),
+        msg: format!("`{}` is not the id of a program, which ends with `.aleo`.", program),
+        help: Some("Name the program with its network suffix, e.g. `leo add token.aleo`.".to_string()),
+    }
+
+    @backtraced
+    unknown_home_directory {
+        args: (),
+        msg: "The home directory is not known, so there is nowhere to store fetched programs and keystores.",
+        help: Some("Set the `HOME` environment variable.".to_string()),
+    }
+
+    @backtraced
+    failed_to_deploy {
+        args: (error: impl Display),
+        msg: format!("Failed to deploy the program: {}", error),
+        help: Some("Run `leo deploy --dry-run` to check the program and its estimated fee without deploying it.".to_string()),
+    }
+
+    @backtraced
+    failed_to_execute {
+        args: (error: impl Display),
+        msg: format!("Failed to execute the transition: {}", error),
+        help: Some("Run `leo execute --local` to produce the transaction without broadcasting it.".to_string()),
+    }
+
+    @backtraced
+    execution_needs_private_key {
+        args: (),
+        msg: "Executing a transition needs the private key of the account, and none is chosen.",
+        help: Some("Sign with a keystore or with the private key in `ALEO_PRIVATE_KEY`.".to_string()),
+    }
+
+    @backtraced
+    prover_returned_no_execution {
+        args: (prover: impl Display),
+        msg: format!("The proving service at {} did not return the execution.", prover),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_broadcast {
+        args: (endpoint: impl Display, error: impl Display),
+        msg: format!("Failed to broadcast the transaction to {}: {}", endpoint, error),
+        help: None,
+    }
+
+    @backtraced
+    no_signer {
+        args: (),
+        msg: "No account is chosen to sign with.",
+        help: Some("Pass `--keystore` or `--external-signer`, or set `ALEO_PRIVATE_KEY`.".to_string()),
+    }
+
+    @backtraced
+    unknown_network_profile {
+        args: (name: impl Display),
+        msg: format!("There is no network profile `{}`.", name),
+        help: Some("Use `devnet` or `testnet`, or configure the profile under `networks` in `program.json`.".to_string()),
+    }
+
+    @backtraced
+    unset_variable {
+        args: (name: impl Display),
+        msg: format!("The variable `{}` is set neither in the environment nor in `.env`.", name),
+        help: None,
+    }
+
+    @backtraced
+    deployment_needs_private_key {
+        args: (),
+        msg: "Paying the deployment fee needs the private key of the account, and none is chosen.",
+        help: Some("Sign with a keystore or with the private key in `ALEO_PRIVATE_KEY`.".to_string()),
+    }
+
+    @backtraced
+    private_key_in_profile {
+        args: (name: impl Display),
+        msg: format!("The network profile `{}` in `program.json` holds a private key, which would be committed with the program.", name),
+        help: Some("Set `private_key` to `$VAR`, and the private key in the variable `VAR` of the environment or of `.env`.".to_string()),
+    }
 );
@@ -70,4 +70,91 @@ create_messages!(
         msg: format!("The program scope name `{program_scope_name}` must match `{file_name}`."),
         help: None,
     }
+
+    /// For when a function is checked under a different name than the one it is defined with.
+    @formatted
+    function_name_does_not_match {
+        args: (found: impl Display, expected: impl Display),
+        msg: format!("Expected a definition of the function `{expected}`, but found `{found}`."),
+        help: None,
+    }
+
+    /// For when a function is checked that does not exist in the program.
+    @formatted
+    unknown_function {
+        args: (name: impl Display),
+        msg: format!("The program does not contain a function named `{name}`."),
+        help: None,
+    }
+
+    /// For when compilation fails because warnings were denied.
+    @backtraced
+    warnings_denied {
+        args: (count: impl Display),
+        msg: format!("Compilation failed due to {count} denied warning(s)."),
+        help: Some("Warnings are denied by `--deny-warnings` or `--deny <CODE>`.".to_string()),
+    }
+
+    @backtraced
+    error_limit_reached {
+        args: (count: impl Display),
+        msg: format!("Aborting compilation due to {count} previous error(s); further errors are not reported."),
+        help: Some("The number of reported errors is limited by `--error-limit`.".to_string()),
+    }
+
+    /// For when the program has no program scope, e.g. because it failed to parse.
+    @backtraced
+    no_program_scope {
+        args: (),
+        msg: "The program has no program scope.",
+        help: None,
+    }
+
+    /// For when the artifacts of a compilation cannot be written to the compilation cache.
+    @backtraced
+    failed_to_write_compilation_cache {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot write the compilation cache to '{:?}': {}", path, error),
+        help: None,
+    }
+
+    /// For when the snapshot of the AST after a pass cannot be written.
+    @backtraced
+    failed_to_write_pass_snapshot {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot write the snapshot of the AST after a pass to '{:?}': {}", path, error),
+        help: None,
+    }
+
+    /// For when the program imports, directly or through another import, a program that the package may not import.
+    @formatted
+    import_not_allowed {
+        args: (import: impl Display, through: impl Display, reason: impl Display),
+        msg: format!("`{import}.leo` may not be imported by this package{through}: {reason}."),
+        help: Some("The programs a package may import are configured by `allowed_imports` and `denied_imports` in `program.json`.".to_string()),
+    }
+
+    /// For when a saved REPL session cannot be restored.
+    @backtraced
+    invalid_repl_session {
+        args: (error: impl Display),
+        msg: format!("Cannot restore the REPL session: {error}"),
+        help: Some("Sessions are saved with `:save` and can only be restored into the program they were saved with.".to_string()),
+    }
+
+    /// For when the Leo file of an imported program is not in any directory of the import search path.
+    @formatted
+    import_not_in_search_path {
+        args: (file: impl Display, directories: impl Display),
+        msg: format!("Cannot find `{file}` to import. It was looked for in {directories}."),
+        help: Some("Imports are looked for in the `imports/` directory of the package, the directories given with `--import-path`, and then `~/.leo/registry/`.".to_string()),
+    }
+
+    /// For when the program in the Leo file of an import is not the program that the import names.
+    @formatted
+    imported_program_name_mismatch {
+        args: (import: impl Display, program: impl Display, source: impl Display),
+        msg: format!("`{import}.leo` is imported from `{source}`, but that file declares the program `{program}`."),
+        help: Some("The Leo file of an imported program must be named after the program it declares.".to_string()),
+    }
 );
@@ -58,4 +58,124 @@ create_messages!(
         ),
         help: None,
     }
+
+    /// For when an input has a struct or record type that is not declared.
+    @formatted
+    unknown_input_struct {
+        args: (name: impl Display),
+        msg: format!("a struct or record declaration does not exist for '{}'", name),
+        help: None,
+    }
+
+    /// For when the input of a record type is not a record.
+    @formatted
+    expected_record_value {
+        args: (name: impl Display, value: impl Display),
+        msg: format!("expected a record for the input of type '{}', found '{}'", name, value),
+        help: None,
+    }
+
+    /// For when an input given on the command line is not a single value.
+    @backtraced
+    invalid_input_value {
+        args: (name: impl Display, value: impl Display),
+        msg: format!("the value `{}` given for input `{}` is not a single value", value, name),
+        help: Some("Each input is given as one value, e.g. `1u32` or `Point { x: 1u32, y: 2u32 }`.".to_string()),
+    }
+
+    /// For when a TOML or JSON input file cannot be parsed.
+    @backtraced
+    failed_to_parse_input_data {
+        args: (file: impl Display, error: impl Display),
+        msg: format!("failed to parse the input file `{}`: {}", file, error),
+        help: None,
+    }
+
+    /// For when a TOML or JSON input file has a section for a function that the program does not declare.
+    @backtraced
+    unknown_input_function {
+        args: (file: impl Display, key: impl Display),
+        msg: format!("{}: `{}`: the program has no function `{}`", file, key, key),
+        help: None,
+    }
+
+    /// For when a TOML or JSON input file gives a value for an input or member that is not declared.
+    @backtraced
+    unknown_input_key {
+        args: (file: impl Display, key: impl Display),
+        msg: format!("{}: `{}`: there is no input or member of this name", file, key),
+        help: None,
+    }
+
+    /// For when a TOML or JSON input file does not give a value for an input or member.
+    @backtraced
+    missing_input_key {
+        args: (file: impl Display, key: impl Display),
+        msg: format!("{}: `{}`: the value is missing", file, key),
+        help: None,
+    }
+
+    /// For when a value of a TOML or JSON input file does not have the declared type.
+    @backtraced
+    invalid_input_data {
+        args: (file: impl Display, key: impl Display, expected: impl Display, found: impl Display),
+        msg: format!("{}: `{}`: expected a value of type `{}`, found `{}`", file, key, expected, found),
+        help: Some("Give integers and booleans as such, structs and records as tables of their members, and other values as strings, e.g. `\"1field\"`.".to_string()),
+    }
+
+    /// For when the value of an input does not have the type of the input of the function.
+    @formatted
+    input_type_mismatch {
+        args: (name: impl Display, expected: impl Display, found: impl Display),
+        msg: format!("input `{}` expects {}, found {}", name, expected, found),
+        help: None,
+    }
+
+    /// For when an input file gives a value for an input that the function does not declare.
+    @formatted
+    unknown_function_input {
+        args: (function: impl Display, name: impl Display),
+        msg: format!("the function `{}` has no input `{}`", function, name),
+        help: None,
+    }
+
+    /// For when an input file does not give a value for an input of the function.
+    @formatted
+    missing_function_input {
+        args: (function: impl Display, name: impl Display),
+        msg: format!("no value is given for the input `{}` of `{}`", name, function),
+        help: None,
+    }
+
+    /// For when the value of a struct or record input has a member that the struct does not declare.
+    @formatted
+    unknown_member_input {
+        args: (name: impl Display, struct_: impl Display),
+        msg: format!("input `{}` is not a member of `{}`", name, struct_),
+        help: None,
+    }
+
+    /// For when the value of a struct or record input does not give a member.
+    @formatted
+    missing_member_input {
+        args: (name: impl Display, member: impl Display),
+        msg: format!("input `{}` is missing the member `{}`", name, member),
+        help: None,
+    }
+
+    /// For when an input file gives an input a mode other than that of the input of the function.
+    @formatted
+    input_mode_mismatch {
+        args: (name: impl Display, expected: impl Display, found: impl Display),
+        msg: format!("input `{}` is {}, but is given as {}", name, expected, found),
+        help: Some("Give the input the mode of the function's input, or leave the mode out.".to_string()),
+    }
+
+    /// For when a record value does not have the members of its record type.
+    @formatted
+    invalid_record_value {
+        args: (record: impl Display, reason: impl Display),
+        msg: format!("invalid value of the record `{}`: {}", record, reason),
+        help: Some("Give a record as its type with its members, e.g. `token { owner: aleo1..., gates: 0u64, amount: 1u64 }`, and optionally a `_nonce` group.".to_string()),
+    }
 );
@@ -0,0 +1,156 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::create_messages;
+use std::fmt::Display;
+
+create_messages!(
+    /// InterpreterError enum that represents all the errors for the `leo-interpreter` crate.
+    InterpreterError,
+    code_mask: 9000i32,
+    code_prefix: "INT",
+
+    /// For when the interpreter does not define the semantics of an operation on the given type.
+    @formatted
+    unsupported_operation {
+        args: (operation: impl Display, type_: impl Display),
+        msg: format!("The interpreter does not support `{operation}` on values of type `{type_}`."),
+        help: Some("Operations on fields, groups, and scalars are only evaluated by snarkVM.".to_string()),
+    }
+
+    /// For when the interpreter cannot evaluate a statement or an expression.
+    @formatted
+    unsupported_construct {
+        args: (construct: impl Display),
+        msg: format!("The interpreter does not support {construct}."),
+        help: None,
+    }
+
+    /// For when the divisor of a division or a remainder is zero.
+    @formatted
+    division_by_zero {
+        args: (operation: impl Display),
+        msg: format!("Division by zero in `{operation}`."),
+        help: None,
+    }
+
+    /// For when an assertion does not hold.
+    @formatted
+    assertion_failed {
+        args: (assertion: impl Display, message: Option<String>),
+        msg: match message {
+            Some(message) => format!("`{assertion}` failed: {message}"),
+            None => format!("`{assertion}` failed."),
+        },
+        help: None,
+    }
+
+    /// For when a variable is used before it is defined.
+    @formatted
+    unknown_variable {
+        args: (name: impl Display),
+        msg: format!("Unknown variable `{name}`."),
+        help: None,
+    }
+
+    /// For when a function that is not defined in the program is called.
+    @formatted
+    unknown_function {
+        args: (name: impl Display),
+        msg: format!("Unknown function `{name}`."),
+        help: None,
+    }
+
+    /// For when a function is called with the wrong number of arguments.
+    @formatted
+    incorrect_num_args {
+        args: (name: impl Display, expected: impl Display, received: impl Display),
+        msg: format!("Function `{name}` expects {expected} argument(s) but received {received}."),
+        help: None,
+    }
+
+    /// For when a function with an output finishes without returning a value.
+    @formatted
+    missing_return {
+        args: (name: impl Display),
+        msg: format!("Function `{name}` finished without returning a value."),
+        help: None,
+    }
+
+    /// For when a value does not have the type that its use requires.
+    @formatted
+    type_mismatch {
+        args: (expected: impl Display, value: impl Display),
+        msg: format!("Expected a value of type `{expected}` but found `{value}`."),
+        help: None,
+    }
+
+    /// For when a function is interpreted without inputs and the input file has no section for it.
+    @backtraced
+    missing_input_section {
+        args: (function: impl Display),
+        msg: format!("The input file has no `[{function}]` section with the inputs of function `{function}`."),
+        help: Some("Pass the inputs on the command line, or add the section to the input file.".to_string()),
+    }
+
+    /// For when an input of an interpreted function is not a constant value, e.g. `a: u8 = b;`.
+    @formatted
+    input_not_constant {
+        args: (name: impl Display),
+        msg: format!("The input `{name}` is not a constant value."),
+        help: None,
+    }
+
+    /// For when the store of the mapping entries cannot be read or written.
+    @backtraced
+    failed_to_access_state {
+        args: (error: impl Display),
+        msg: format!("Failed to access the state of the mappings: {error}"),
+        help: None,
+    }
+
+    /// For when the store of the mapping entries holds an entry that is not the source code of a value.
+    @backtraced
+    invalid_stored_entry {
+        args: (entry: impl Display),
+        msg: format!("The state of the mappings holds an invalid entry `{entry}`."),
+        help: Some("The state may have been written by another version of Leo. Remove it to start over.".to_string()),
+    }
+
+    /// For when a debugger stops the run of the interpreter.
+    @formatted
+    stopped_by_debugger {
+        args: (),
+        msg: "The run was stopped by the debugger.",
+        help: None,
+    }
+
+    /// For when a test with inputs has a parameter of a type that no random values are generated for.
+    @formatted
+    cannot_generate_input {
+        args: (type_: impl Display),
+        msg: format!("Cannot generate random inputs of type `{type_}` for the test."),
+        help: Some("Random inputs are generated for addresses, booleans, fields, integers, structs, records and tuples of them.".to_string()),
+    }
+
+    /// For when `Mapping::get` is given a key that is not in the mapping.
+    @formatted
+    missing_mapping_key {
+        args: (mapping: impl Display, key: impl Display),
+        msg: format!("`{mapping}` has no entry for `{key}`."),
+        help: Some("Use `Mapping::get_or_use` to fall back to a default value, or `Mapping::contains` to check for the key first.".to_string()),
+    }
+);
@@ -15,7 +15,11 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 /// Contains the ASG error definitions.
-use crate::LeoMessageCode;
+use crate::{Field, Label, LeoMessageCode, Suggestion};
+
+use leo_span::Span;
+
+use std::borrow::Cow;
 
 /// Contains the AST error definitions.
 pub mod ast;
@@ -37,6 +41,10 @@ pub use self::flattener::*;
 pub mod input;
 pub use self::input::*;
 
+/// Contains the Interpreter error definitions.
+pub mod interpreter;
+pub use self::interpreter::*;
+
 /// Contains the Package error definitions.
 pub mod package;
 pub use self::package::*;
@@ -45,6 +53,10 @@ pub use self::package::*;
 pub mod parser;
 pub use self::parser::*;
 
+/// Contains the Refactor error definitions.
+pub mod refactor;
+pub use self::refactor::*;
+
 /// Contains the Type Checker error definitions.
 pub mod type_checker;
 pub use self::type_checker::*;
@@ -65,6 +77,9 @@ pub enum LeoError {
     /// Represents an Input Error in a Leo Error.
     #[error(transparent)]
     InputError(#[from] InputError),
+    /// Represents an Interpreter Error in a Leo Error.
+    #[error(transparent)]
+    InterpreterError(#[from] InterpreterError),
     /// Represents an Package Error in a Leo Error.
     #[error(transparent)]
     PackageError(#[from] PackageError),
@@ -77,6 +92,9 @@ pub enum LeoError {
     /// Represents a Flatten Error in a Leo Error.
     #[error(transparent)]
     FlattenError(#[from] FlattenError),
+    /// Represents a Refactor Error in a Leo Error.
+    #[error(transparent)]
+    RefactorError(#[from] RefactorError),
     /// Purely for just exiting with the correct status code and
     /// not re-displaying an error.
     #[error("")]
@@ -96,10 +114,12 @@ impl LeoError {
             CompilerError(error) => error.error_code(),
             CliError(error) => error.error_code(),
             InputError(error) => error.error_code(),
+            InterpreterError(error) => error.error_code(),
             ParserError(error) => error.error_code(),
             PackageError(error) => error.error_code(),
             TypeCheckerError(error) => error.error_code(),
             FlattenError(error) => error.error_code(),
+            RefactorError(error) => error.error_code(),
             LastErrorCode(_) => unreachable!(),
             Anyhow(_) => unimplemented!(), // todo: implement error codes for snarkvm errors.
         }
@@ -114,12 +134,150 @@ impl LeoError {
             CompilerError(error) => error.exit_code(),
             CliError(error) => error.exit_code(),
             InputError(error) => error.exit_code(),
+            InterpreterError(error) => error.exit_code(),
             ParserError(error) => error.exit_code(),
             PackageError(error) => error.exit_code(),
             TypeCheckerError(error) => error.exit_code(),
             FlattenError(error) => error.exit_code(),
+            RefactorError(error) => error.exit_code(),
             LastErrorCode(code) => *code,
-            Anyhow(_) => unimplemented!(), // todo: implement exit codes for snarkvm errors.
+            Anyhow(_) => 1, // todo: implement exit codes for snarkvm errors.
+        }
+    }
+
+    /// Returns the error message, without its location or help text.
+    pub fn message(&self) -> Cow<'_, str> {
+        use LeoError::*;
+
+        match self {
+            AstError(error) => Cow::Borrowed(error.message()),
+            CompilerError(error) => Cow::Borrowed(error.message()),
+            CliError(error) => Cow::Borrowed(error.message()),
+            InputError(error) => Cow::Borrowed(error.message()),
+            InterpreterError(error) => Cow::Borrowed(error.message()),
+            ParserError(error) => Cow::Borrowed(error.message()),
+            PackageError(error) => Cow::Borrowed(error.message()),
+            TypeCheckerError(error) => Cow::Borrowed(error.message()),
+            FlattenError(error) => Cow::Borrowed(error.message()),
+            RefactorError(error) => Cow::Borrowed(error.message()),
+            LastErrorCode(_) => Cow::Borrowed(""),
+            // Errors from snarkVM only have their text.
+            Anyhow(error) => Cow::Owned(format!("{error}")),
+        }
+    }
+
+    /// Returns the help text of the error, if it has one.
+    pub fn help(&self) -> Option<&str> {
+        use LeoError::*;
+
+        match self {
+            AstError(error) => error.help(),
+            CompilerError(error) => error.help(),
+            CliError(error) => error.help(),
+            InputError(error) => error.help(),
+            InterpreterError(error) => error.help(),
+            ParserError(error) => error.help(),
+            PackageError(error) => error.help(),
+            TypeCheckerError(error) => error.help(),
+            FlattenError(error) => error.help(),
+            RefactorError(error) => error.help(),
+            LastErrorCode(_) | Anyhow(_) => None,
+        }
+    }
+
+    /// Returns the edits suggested to fix the error.
+    pub fn suggestions(&self) -> &[Suggestion] {
+        use LeoError::*;
+
+        match self {
+            AstError(error) => error.suggestions(),
+            CompilerError(error) => error.suggestions(),
+            CliError(error) => error.suggestions(),
+            InputError(error) => error.suggestions(),
+            InterpreterError(error) => error.suggestions(),
+            ParserError(error) => error.suggestions(),
+            PackageError(error) => error.suggestions(),
+            TypeCheckerError(error) => error.suggestions(),
+            FlattenError(error) => error.suggestions(),
+            RefactorError(error) => error.suggestions(),
+            LastErrorCode(_) | Anyhow(_) => &[],
+        }
+    }
+
+    /// Returns the secondary locations that the error points to.
+    pub fn labels(&self) -> &[Label] {
+        use LeoError::*;
+
+        match self {
+            AstError(error) => error.labels(),
+            CompilerError(error) => error.labels(),
+            CliError(error) => error.labels(),
+            InputError(error) => error.labels(),
+            InterpreterError(error) => error.labels(),
+            ParserError(error) => error.labels(),
+            PackageError(error) => error.labels(),
+            TypeCheckerError(error) => error.labels(),
+            FlattenError(error) => error.labels(),
+            RefactorError(error) => error.labels(),
+            LastErrorCode(_) | Anyhow(_) => &[],
+        }
+    }
+
+    /// Returns the name of the function that created the error, e.g. `type_should_be`, or an empty string
+    /// for errors that were not created by Leo.
+    pub fn kind(&self) -> &'static str {
+        use LeoError::*;
+
+        match self {
+            AstError(error) => error.kind(),
+            CompilerError(error) => error.kind(),
+            CliError(error) => error.kind(),
+            InputError(error) => error.kind(),
+            InterpreterError(error) => error.kind(),
+            ParserError(error) => error.kind(),
+            PackageError(error) => error.kind(),
+            TypeCheckerError(error) => error.kind(),
+            FlattenError(error) => error.kind(),
+            RefactorError(error) => error.kind(),
+            LastErrorCode(_) | Anyhow(_) => "",
+        }
+    }
+
+    /// Returns the arguments that the error was created from.
+    pub fn fields(&self) -> &[Field] {
+        use LeoError::*;
+
+        match self {
+            AstError(error) => error.fields(),
+            CompilerError(error) => error.fields(),
+            CliError(error) => error.fields(),
+            InputError(error) => error.fields(),
+            InterpreterError(error) => error.fields(),
+            ParserError(error) => error.fields(),
+            PackageError(error) => error.fields(),
+            TypeCheckerError(error) => error.fields(),
+            FlattenError(error) => error.fields(),
+            RefactorError(error) => error.fields(),
+            LastErrorCode(_) | Anyhow(_) => &[],
+        }
+    }
+
+    /// Returns the span that the error points to, if it has one.
+    pub fn span(&self) -> Option<Span> {
+        use LeoError::*;
+
+        match self {
+            AstError(error) => error.span(),
+            CompilerError(error) => error.span(),
+            CliError(error) => error.span(),
+            InputError(error) => error.span(),
+            InterpreterError(error) => error.span(),
+            ParserError(error) => error.span(),
+            PackageError(error) => error.span(),
+            TypeCheckerError(error) => error.span(),
+            FlattenError(error) => error.span(),
+            RefactorError(error) => error.span(),
+            LastErrorCode(_) | Anyhow(_) => None,
         }
     }
 }
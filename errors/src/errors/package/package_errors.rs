@@ -115,6 +115,22 @@ create_messages!(
         help: None,
     }
 
+    /// For when reading the diagnostics file failed.
+    @backtraced
+    failed_to_read_diagnostics_file {
+        args: (path: impl Debug),
+        msg: format!("Cannot read diagnostics file from the provided file path - {:?}", path),
+        help: None,
+    }
+
+    /// For when the diagnostics file has an IO error.
+    @backtraced
+    io_error_diagnostics_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error diagnostics file from the provided file path - {}", error),
+        help: None,
+    }
+
     /// For when the main file has an IO error.
     @backtraced
     io_error_main_file {
@@ -305,4 +321,187 @@ create_messages!(
         msg: "The `src/` directory can contain only one file and must be named `main.leo`.".to_string(),
         help: None,
     }
+
+    @backtraced
+    invalid_naming_config {
+        args: (error: impl Display),
+        msg: format!("Invalid naming conventions in `lints.naming` of `program.json`: {}.", error),
+        help: Some("Each of `functions`, `variables`, `structs`, `constants`, and `programs` may be set to `snake_case`, `UpperCamelCase`, `SCREAMING_SNAKE_CASE`, or `any`.".to_string()),
+    }
+
+    /// For when a file could not be written.
+    @backtraced
+    failed_to_write_file {
+        args: (path: impl Display, error: impl ErrorArg),
+        msg: format!("failed to write file: {}, error: {}", path, error),
+        help: None,
+    }
+
+    /// For when the `leo` field of the manifest is not a valid version.
+    @backtraced
+    invalid_language_version {
+        args: (version: impl Display),
+        msg: format!("Invalid Leo version in the `leo` field of `program.json`: `{}`.", version),
+        help: Some("Write the version as `major.minor`, e.g. `\"leo\": \"1.5\"`.".to_string()),
+    }
+
+    /// For when the manifest declares a version of Leo newer than the compiler.
+    @backtraced
+    unsupported_language_version {
+        args: (version: impl Display, current: impl Display),
+        msg: format!("The package is written for Leo {}, but this compiler only supports Leo {} and older.", version, current),
+        help: Some("Update the Leo compiler to build this package.".to_string()),
+    }
+
+    /// For when reading the metrics file failed.
+    @backtraced
+    failed_to_read_metrics_file {
+        args: (path: impl Debug),
+        msg: format!("Cannot read metrics file from the provided file path - {:?}", path),
+        help: None,
+    }
+
+    /// For when the metrics file has an IO error.
+    @backtraced
+    io_error_metrics_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error metrics file from the provided file path - {}", error),
+        help: None,
+    }
+
+    /// For when a line of the metrics file is not the metrics of a build.
+    @backtraced
+    invalid_metrics_record {
+        args: (error: impl ErrorArg),
+        msg: format!("The metrics file has an invalid record: {}", error),
+        help: Some("The file may have been written by another version of Leo. Remove `outputs/metrics.ndjson` to start over.".to_string()),
+    }
+
+    /// For when the `scripts` of the manifest are not a map from hooks to commands.
+    @backtraced
+    invalid_scripts {
+        args: (error: impl Display),
+        msg: format!("Invalid `scripts` in `program.json`: {}.", error),
+        help: Some("Map each hook to a command or a list of commands, e.g. `\"pre-build\": \"./generate.sh\"`.".to_string()),
+    }
+
+    /// For when the `scripts` of the manifest declare a hook that does not exist.
+    @backtraced
+    unknown_script_hook {
+        args: (hook: impl Display),
+        msg: format!("Unknown hook `{}` in the `scripts` of `program.json`.", hook),
+        help: Some("The hooks are `pre-build`, `post-build`, `pre-run`, `post-run`, `pre-deploy`, and `post-deploy`.".to_string()),
+    }
+
+    /// For when the `generated` field of the manifest is not a valid header configuration.
+    @backtraced
+    invalid_header_config {
+        args: (error: impl Display),
+        msg: format!("Invalid `generated` header configuration in `program.json`: {}.", error),
+        help: Some("`license` is an SPDX license identifier, `notice` is a string, and `provenance` is a boolean.".to_string()),
+    }
+
+    /// For when the `allowed_imports` or `denied_imports` field of the manifest is not a list of program names.
+    @backtraced
+    invalid_import_policy {
+        args: (field: impl Display),
+        msg: format!("Invalid `{}` in `program.json`: expected a list of program names.", field),
+        help: Some("List the programs by name, e.g. `[\"token.aleo\"]`.".to_string()),
+    }
+
+    /// For when the `format` field of the manifest is not a valid formatting configuration.
+    @backtraced
+    invalid_format_config {
+        args: (error: impl Display),
+        msg: format!("Invalid `format` configuration in `program.json`: {}.", error),
+        help: Some("`indent_width` and `max_line_length` are numbers, and `literal_style` is `\"preserve\"` or `\"normalize\"`.".to_string()),
+    }
+
+    /// For when the `dependencies` field of the manifest is not a map of dependency declarations.
+    @backtraced
+    invalid_dependencies {
+        args: (error: impl Display),
+        msg: format!("Invalid `dependencies` in `program.json`: {}.", error),
+        help: Some("Declare each dependency by name, e.g. `\"token\": { \"version\": \"0.1.0\", \"network\": \"testnet3\" }`.".to_string()),
+    }
+
+    /// For when a dependency does not declare exactly one source.
+    @backtraced
+    invalid_dependency_source {
+        args: (name: impl Display),
+        msg: format!("The dependency `{}` in `program.json` must declare exactly one of `network`, `git` and `path`.", name),
+        help: Some("`rev` may only be declared along with `git`.".to_string()),
+    }
+
+    /// For when the Leo file of a dependency cannot be found.
+    @backtraced
+    dependency_not_found {
+        args: (name: impl Display, source: impl Display),
+        msg: format!("Could not find the dependency `{}` from `{}`.", name, source),
+        help: Some("Programs from a network are fetched into `~/.leo/registry` by `leo add <name>.aleo`, checkouts of git repositories in `~/.leo/git/<name>`, and paths are relative to the package.".to_string()),
+    }
+
+    /// For when `leo.lock` cannot be parsed or written.
+    @backtraced
+    invalid_lockfile {
+        args: (path: impl Display, error: impl Display),
+        msg: format!("Invalid lockfile `{}`: {}.", path, error),
+        help: Some("Delete the lockfile to pin the dependencies again.".to_string()),
+    }
+
+    /// For when a dependency no longer has the checksum pinned in `leo.lock`.
+    @backtraced
+    lockfile_drift {
+        args: (name: impl Display, locked: impl Display, current: impl Display),
+        msg: format!("The dependency `{}` changed since it was pinned in `leo.lock`: its checksum was {} and is now {}.", name, locked, current),
+        help: Some("If the change is expected, remove the dependency from `leo.lock` to pin it again.".to_string()),
+    }
+
+    /// For when a program cannot be downloaded from an Aleo node.
+    @backtraced
+    failed_to_fetch_program {
+        args: (program: impl Display, url: impl Display, error: impl Display),
+        msg: format!("Failed to fetch `{}` from {}: {}.", program, url, error),
+        help: Some("Check that the program is deployed, or fetch it from another node with `--endpoint`.".to_string()),
+    }
+
+    /// For when the workspace manifest cannot be parsed.
+    @backtraced
+    invalid_workspace {
+        args: (path: impl Display, error: impl Display),
+        msg: format!("Invalid workspace manifest `{}`: {}.", path, error),
+        help: Some("List the directories of the packages of the workspace, e.g. `{ \"members\": [\"token\", \"dex\"] }`.".to_string()),
+    }
+
+    /// For when members of a workspace depend on each other in a cycle.
+    @backtraced
+    cyclic_workspace_dependencies {
+        args: (cycle: impl Display),
+        msg: format!("The members of the workspace depend on each other in a cycle: {}.", cycle),
+        help: Some("Aleo programs cannot import each other cyclically. Remove a `path` dependency from the cycle.".to_string()),
+    }
+
+    /// For when a package imports another package that has not been built yet.
+    @backtraced
+    dependency_not_built {
+        args: (name: impl Display, path: impl Display),
+        msg: format!("The dependency `{}` at `{}` has not been built.", name, path),
+        help: Some("Build the dependency first, or build the whole workspace with `leo build --workspace`.".to_string()),
+    }
+
+    /// For when the `networks` of the manifest are not a map from names to profiles.
+    @backtraced
+    invalid_network_profiles {
+        args: (error: impl Display),
+        msg: format!("Invalid `networks` in `program.json`: {}.", error),
+        help: Some("Give each profile an `endpoint`, and optionally a `network`, a `private_key` and a `prover`.".to_string()),
+    }
+
+    /// For when a line of the `.env` file is not an assignment.
+    @backtraced
+    invalid_env_file {
+        args: (line: impl Display),
+        msg: format!("Line {} of `.env` is not an assignment `KEY=VALUE`.", line),
+        help: None,
+    }
 );
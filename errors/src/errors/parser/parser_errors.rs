@@ -227,7 +227,7 @@ create_messages!(
     leo_imports_only {
         args: (),
         msg: "Invalid import call to non-leo file.",
-        help: Some("Only imports of Leo `.leo` files are currently supported.".to_string()),
+        help: Some("Import the Leo file of a program with `import foo.leo;`, or a deployed program with `import foo.aleo;`.".to_string()),
     }
 
     @formatted
@@ -271,4 +271,63 @@ create_messages!(
         msg: "Invalid network identifier. The only supported identifier is `aleo`.",
         help: None,
     }
+
+    @formatted
+    item_name_starts_with_underscore {
+        args: (name: impl Display),
+        msg: format!("The name `{name}` cannot begin with an underscore."),
+        help: Some("Only the names of variables and function inputs may begin with an underscore.".to_string()),
+    }
+
+    @backtraced
+    invalid_grammar {
+        args: (line: usize, reason: impl Display),
+        msg: format!("Invalid ABNF grammar on line {line}: {reason}."),
+        help: None,
+    }
+
+    @formatted
+    invalid_language_version {
+        args: (version: impl Display),
+        msg: format!("`{version}` is not a valid Leo version."),
+        help: Some("Write the version as `major.minor`, e.g. `// leo:version 1.5`.".to_string()),
+    }
+
+    @formatted
+    unsupported_language_version {
+        args: (version: impl Display, current: impl Display),
+        msg: format!("The program is written for Leo {version}, but this compiler only supports Leo {current} and older."),
+        help: Some("Update the Leo compiler to compile this program.".to_string()),
+    }
+
+    @formatted
+    feature_requires_version {
+        args: (feature: impl Display, minimum: impl Display, version: impl Display),
+        msg: format!("{feature} require Leo {minimum}, but the program is written for Leo {version}."),
+        help: Some(format!("Declare at least `// leo:version {minimum}` at the top of the file, or `\"leo\": \"{minimum}\"` in `program.json`.")),
+    }
+
+    /// For when a tuple pattern has fewer than two elements and no trailing comma, e.g. `(a)`.
+    @formatted
+    tuple_pattern_without_comma {
+        args: (),
+        msg: "A tuple pattern must contain a comma.",
+        help: Some("A tuple pattern with one element is written with a trailing comma, e.g. `(a,)`.".to_string()),
+    }
+
+    /// For when the bytecode of a program imported with `import foo.aleo;` cannot be read.
+    @formatted
+    invalid_aleo_import {
+        args: (program: impl Display, line: impl Display, text: impl Display),
+        msg: format!("Could not read the signatures of `{}.aleo` at line {} of its bytecode: `{}`.", program, line, text),
+        help: Some("Only the structs, records, mappings and functions of the program are read, to generate its stub.".to_string()),
+    }
+
+    /// For when the tuple type of a definition has fewer than two types, e.g. `let a: (u8) = 1u8;`.
+    @formatted
+    tuple_type_too_short {
+        args: (),
+        msg: "A tuple type must contain at least two types.",
+        help: Some("Declare a variable with one type without parentheses, e.g. `let a: u8 = 1u8;`.".to_string()),
+    }
 );
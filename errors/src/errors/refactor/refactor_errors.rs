@@ -0,0 +1,57 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::create_messages;
+use std::fmt::Display;
+
+create_messages!(
+    /// RefactorError enum that represents all the errors for the refactorings in the `leo-passes` crate.
+    RefactorError,
+    code_mask: 8000i32,
+    code_prefix: "REF",
+
+    /// For when no refactoring target could be found at the requested location.
+    @formatted
+    no_refactoring_target {
+        args: (target: impl Display),
+        msg: format!("No {target} found at the requested location."),
+        help: None,
+    }
+
+    /// For when a variable cannot be inlined.
+    @formatted
+    cannot_inline_variable {
+        args: (name: impl Display, reason: impl Display),
+        msg: format!("Cannot inline variable `{name}`: {reason}."),
+        help: None,
+    }
+
+    /// For when a range of statements cannot be extracted into a function.
+    @formatted
+    cannot_extract_function {
+        args: (reason: impl Display),
+        msg: format!("Cannot extract the selected statements into a function: {reason}."),
+        help: None,
+    }
+
+    /// For when an item cannot be renamed.
+    @formatted
+    cannot_rename {
+        args: (name: impl Display, new_name: impl Display, reason: impl Display),
+        msg: format!("Cannot rename `{name}` to `{new_name}`: {reason}."),
+        help: None,
+    }
+);
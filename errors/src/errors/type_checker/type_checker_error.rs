@@ -263,7 +263,7 @@ create_messages!(
     @formatted
     unreachable_code_after_return {
         args: (),
-        msg: format!("Cannot reach the following statement."),
+        msg: format!("Cannot reach the following statement(s)."),
         help: Some("Remove the unreachable code.".to_string()),
     }
 
@@ -423,4 +423,266 @@ create_messages!(
         msg: format!("Cannot call a local transition function from a transition function."),
         help: None,
     }
+
+    @formatted
+    assert_message_parameter_mismatch {
+        args: (placeholders: impl Display, parameters: impl Display),
+        msg: format!("Assertion message has {placeholders} placeholder(s) but {parameters} parameter(s) were provided."),
+        help: Some("Provide one parameter for each `{}` in the message.".to_string()),
+    }
+
+    @formatted
+    assertion_always_fails {
+        args: (assertion: impl Display, message: Option<String>),
+        msg: match message {
+            Some(message) => format!("`{assertion}` always fails: {message}"),
+            None => format!("`{assertion}` always fails."),
+        },
+        help: None,
+    }
+
+    @formatted
+    return_type_mismatch {
+        args: (expected: impl Display, actual: impl Display),
+        msg: format!("Expected a return value of type `{expected}` but type `{actual}` was found"),
+        help: None,
+    }
+
+    @formatted
+    feature_requires_version {
+        args: (feature: impl Display, minimum: impl Display, version: impl Display),
+        msg: format!("{feature} require Leo {minimum}, but the program is written for Leo {version}."),
+        help: Some(format!("Declare at least `// leo:version {minimum}` at the top of the file, or `\"leo\": \"{minimum}\"` in `program.json`.")),
+    }
+
+    @formatted
+    invalid_annotation_argument {
+        args: (name: impl Display, value: impl Display, annotation: impl Display),
+        msg: format!("Invalid argument `{name} = {value}` of annotation `{annotation}`."),
+        help: Some("The arguments of `@test` are `cases`, the positive number of random cases to run the test on, and the values of its inputs.".to_string()),
+    }
+
+    @formatted
+    async_transition_must_return_future {
+        args: (name: impl Display),
+        msg: format!("The async transition `{name}` must return the future of a call to an async function, as its last output and in its last statement."),
+        help: Some(format!("Declare `Future` as the last output of `{name}`, and end it with e.g. `return finalize_{name}(a, b);`.")),
+    }
+
+    @formatted
+    async_transition_with_finalize {
+        args: (name: impl Display),
+        msg: format!("The async transition `{name}` cannot also have a `finalize` block or an `async finalize` statement."),
+        help: Some("Move the `finalize` block into an async function, and return its future.".to_string()),
+    }
+
+    @formatted
+    async_function_call_outside_return {
+        args: (name: impl Display),
+        msg: format!("The async function `{name}` can only be called in the last statement of an async transition, which returns its future."),
+        help: None,
+    }
+
+    @formatted
+    invalid_future_type {
+        args: (),
+        msg: format!("The type `Future` can only be the last output of an async transition, or an input of an async function."),
+        help: None,
+    }
+
+    @formatted
+    cannot_await_future {
+        args: (),
+        msg: format!("A future can only be awaited by the first statements of the async function it is passed to."),
+        help: Some("Pass the future to the async function returned by the transition, and await it there with e.g. `f.await();`.".to_string()),
+    }
+
+    @formatted
+    invalid_match_pattern {
+        args: (pattern: impl Display, type_: impl Display),
+        msg: format!("The pattern `{pattern}` cannot match a value of type `{type_}`."),
+        help: None,
+    }
+
+    @formatted
+    non_exhaustive_match {
+        args: (),
+        msg: format!("The arms of this match statement do not match every value."),
+        help: Some("Add a last arm with the pattern `_`, which matches any value.".to_string()),
+    }
+
+    @formatted
+    unreachable_match_arm {
+        args: (),
+        msg: format!("This arm is unreachable, because the previous arms match every value that it matches."),
+        help: Some("Remove the arm, or move it before the arms that match its values.".to_string()),
+    }
+
+    @formatted
+    invalid_optimize_annotation {
+        args: (annotation: impl Display),
+        msg: format!("Invalid annotation `{annotation}`."),
+        help: Some("The only level of optimization of a function is `@optimize(none)`, which keeps the assertions of the function that always hold, and the unused imports of its program.".to_string()),
+    }
+
+    @formatted
+    witness_must_follow_inputs {
+        args: (),
+        msg: format!("The witnesses of a transition must follow its other inputs."),
+        help: Some("Move the inputs declared with `witness` to the end of the parameters.".to_string()),
+    }
+
+    @formatted
+    invalid_witness_mode {
+        args: (),
+        msg: format!("Only the inputs of a transition can be witnesses."),
+        help: Some("Remove the `witness` mode.".to_string()),
+    }
+
+    @formatted
+    cannot_call_function_with_witnesses {
+        args: (function: impl Display),
+        msg: format!("`{function}` has witnesses, so it can only be executed directly rather than called."),
+        help: Some("Witnesses are supplied by the prover of the transition, not by its callers. Pass the values as regular inputs instead.".to_string()),
+    }
+
+    @formatted
+    recursive_call {
+        args: (function: impl Display, cycle: impl Display),
+        msg: format!("`{function}` is called recursively: {cycle}."),
+        help: Some("Aleo programs cannot call functions recursively. Rewrite the recursion as a bounded loop.".to_string()),
+    }
+
+    @formatted
+    cyclic_struct {
+        args: (struct_: impl Display, cycle: impl Display),
+        msg: format!("`{struct_}` contains itself: {cycle}."),
+        help: Some("A struct cannot contain itself, directly or through other structs, since its values would be infinitely large. Remove a member from the cycle.".to_string()),
+    }
+
+    @formatted
+    unknown_external_transition {
+        args: (program: impl Display, function: impl Display),
+        msg: format!("`{program}.aleo` has no transition `{function}`."),
+        help: Some("Only the transitions of an imported program can be called from another program.".to_string()),
+    }
+
+    @formatted
+    imported_function_called_locally {
+        args: (program: impl Display, function: impl Display),
+        msg: format!("`{function}` is declared by the imported program `{program}.aleo`."),
+        help: Some(format!("Call it as `{program}.aleo/{function}(...)`.")),
+    }
+
+    @formatted
+    no_owner_check_outside_transition {
+        args: (),
+        msg: format!("Only transitions can be annotated with `@no_owner_check`."),
+        help: Some("Only transitions consume records, so only their owner checks can be waived. Remove the annotation.".to_string()),
+    }
+
+    @formatted
+    mapping_operation_outside_finalize {
+        args: (operation: impl Display),
+        msg: format!("`{operation}` must be inside a finalize block."),
+        help: Some("Mappings are only read and updated on chain, by the finalize block of a transition.".to_string()),
+    }
+
+    @formatted
+    unused_expression_value {
+        args: (expression: impl Display),
+        msg: format!("The value of `{expression}` is unused."),
+        help: Some("Only `Mapping::set` and `Mapping::remove` can be called as statements. Assign the value to a variable with `let`.".to_string()),
+    }
+
+    @formatted
+    call_in_finalize {
+        args: (function: impl Display),
+        msg: format!("A finalize block cannot call `{function}`."),
+        help: Some("Finalize blocks are executed on chain, where they cannot call functions. Write the body of the function into the finalize block instead.".to_string()),
+    }
+
+    @formatted
+    assign_outside_branch_in_finalize {
+        args: (variable: impl Display),
+        msg: format!("Cannot assign to `{variable}` from a branch of a finalize block, since it is declared outside of the branch."),
+        help: Some(format!("Only one branch of a finalize block is executed. Compute the value outside of the branch instead, e.g. `{variable} = condition ? value : {variable};`.")),
+    }
+
+    @formatted
+    return_in_finalize_branch {
+        args: (),
+        msg: format!("A finalize block cannot return from a branch."),
+        help: Some("Only one branch of a finalize block is executed. Compute the returned value with a ternary expression, and return it at the end of the finalize block.".to_string()),
+    }
+
+    @formatted
+    unbound_future {
+        args: (function: impl Display),
+        msg: format!("The future returned by `{function}` must be bound by a definition, as its last element."),
+        help: Some(format!("Bind it with e.g. `let f: Future = {function}(...);`, or `let r: (u64, Future) = {function}(...);` if the transition has outputs.")),
+    }
+
+    @formatted
+    future_not_passed_to_async_function {
+        args: (future: impl Display),
+        msg: format!("The future `{future}` can only be passed to the async function returned by the transition."),
+        help: None,
+    }
+
+    @formatted
+    expected_future {
+        args: (input: impl Display),
+        msg: format!("The input `{input}` of the async function is a `Future`, so it must be passed a future."),
+        help: None,
+    }
+
+    @formatted
+    future_not_awaited {
+        args: (future: impl Display),
+        msg: format!("The future `{future}` is never awaited."),
+        help: Some("The finalize block of a called transition only runs once its future is awaited by the async function of the caller.".to_string()),
+    }
+
+    @formatted
+    futures_awaited_out_of_order {
+        args: (function: impl Display),
+        msg: format!("`{function}` awaits the futures it is passed in a different order than their transitions are called."),
+        help: Some("The finalize blocks of the called transitions run in the order that the transitions are called, so await their futures in that order.".to_string()),
+    }
+
+    @formatted
+    record_outside_transition {
+        args: (function: impl Display),
+        msg: format!("Only transitions can take or return records, but `{function}` is not a transition."),
+        help: Some(format!("Make `{function}` a transition, or pass it the members of the record instead.")),
+    }
+
+    @formatted
+    record_in_finalize {
+        args: (),
+        msg: format!("A finalize block cannot use records."),
+        help: Some("A finalize block runs on-chain, where records are not visible. Pass it the members of the record instead.".to_string()),
+    }
+
+    @formatted
+    regular_function_outputs_cannot_have_modes {
+        args: (),
+        msg: format!("Standard functions cannot have modes associated with their outputs."),
+        help: Some("Consider removing the mode or using the keyword `transition` instead of `function`.".to_string()),
+    }
+
+    @formatted
+    finalize_output_mode_must_be_public {
+        args: (),
+        msg: format!("An output of a finalize block must be public."),
+        help: Some("Add a `public` modifier to the output type or remove the visibility modifier entirely.".to_string()),
+    }
+
+    @formatted
+    unsupported_optimize_mode {
+        args: (mode: impl Display),
+        msg: format!("The optimization mode `{mode}` is not supported."),
+        help: Some("The compiler has no optimizations to tune for a mode, so the only mode of `@optimize` is `none`, which keeps the assertions of the function that always hold, and the unused imports of its program.".to_string()),
+    }
 );
@@ -0,0 +1,90 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::create_messages;
+
+use std::fmt::Display;
+
+create_messages!(
+    /// LintWarning enum that represents all the warnings for the lint passes in the `leo-passes` crate.
+    LintWarning,
+    code_mask: 3000i32,
+    code_prefix: "LNT",
+
+    /// For when a variable is defined but never read.
+    @formatted
+    unused_variable {
+        args: (name: impl Display),
+        msg: format!("Variable `{name}` is never used."),
+        help: Some(format!("If this is intentional, prefix it with an underscore: `_{name}`.")),
+    }
+
+    /// For when a function is defined but never called.
+    @formatted
+    unused_function {
+        args: (name: impl Display),
+        msg: format!("Function `{name}` is never called."),
+        help: Some("Remove the function, or call it from a transition.".to_string()),
+    }
+
+    /// For when a name does not follow the naming convention configured for its kind of item.
+    @formatted
+    naming_convention {
+        args: (kind: impl Display, name: impl Display, convention: impl Display, suggestion: impl Display),
+        msg: format!("{kind} `{name}` does not follow the {convention} naming convention."),
+        help: Some(format!("Rename it to `{suggestion}`.")),
+    }
+
+    /// For when an arithmetic operation on bounded operands can overflow.
+    @formatted
+    possible_overflow {
+        args: (operation: impl Display, type_: impl Display, left: impl Display, right: impl Display, method: impl Display),
+        msg: format!("This {operation} can overflow `{type_}`, since its operands range over {left} and {right}."),
+        help: Some(format!("If the result should wrap around on overflow, use `{method}_wrapped` instead.")),
+    }
+
+    /// For when an imported program is not used by the generated program.
+    @formatted
+    unused_import {
+        args: (name: impl Display),
+        msg: format!("Import `{name}.leo` is never used, so it is removed from the generated program."),
+        help: Some("Remove the import, or build with `--keep-unused-imports` to keep it.".to_string()),
+    }
+
+    /// For when an integer is implicitly widened to a wider integer type.
+    @formatted
+    implicit_widening {
+        args: (from: impl Display, to: impl Display),
+        msg: format!("This `{from}` is implicitly widened to `{to}`."),
+        help: Some(format!("Declare the value with the type `{to}` to avoid the conversion.")),
+    }
+
+    /// For when a commitment is made with randomness that is a constant.
+    @formatted
+    constant_commitment_randomness {
+        args: (),
+        msg: "This commitment uses constant randomness, so it does not hide the committed value.".to_string(),
+        help: Some("Pass randomness that is secret and chosen uniformly at random, such as a `scalar` program input.".to_string()),
+    }
+
+    /// For when a hash of a value with few possible values is made public, so the hash can be inverted by brute force.
+    @formatted
+    hash_used_as_commitment {
+        args: (type_: impl Display),
+        msg: format!("This hash of a `{type_}` is made public, but does not hide it, since every possible value can be hashed and compared."),
+        help: Some("Use `BHP256::commit_tagged(tag, value, randomness)` with a domain-separation tag and secret randomness instead.".to_string()),
+    }
+);
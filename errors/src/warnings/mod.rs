@@ -16,19 +16,35 @@
 
 /// The LeoError type that contains all sub error types.
 /// This allows a unified error type throughout the Leo crates.
-use crate::LeoMessageCode;
+use crate::{Field, Label, LeoMessageCode, Suggestion};
+
+use leo_span::{symbol::with_session_globals, Span};
+
+/// Contains the Lint warning definitions.
+pub mod lint;
+pub use self::lint::*;
 
 /// Contains the Parser warning definitions.
 pub mod parser;
 pub use self::parser::*;
 
+/// Contains the Type Checker warning definitions.
+pub mod type_checker;
+pub use self::type_checker::*;
+
 /// The LeoWarning type that contains all sub error types.
 /// This allows a unified error type throughout the Leo crates.
 #[derive(Debug, Error)]
 pub enum LeoWarning {
+    /// Represents a Lint Warning in a Leo Warning.
+    #[error(transparent)]
+    LintWarning(#[from] LintWarning),
     /// Represents an Parser Error in a Leo Error.
     #[error(transparent)]
     ParserWarning(#[from] ParserWarning),
+    /// Represents a Type Checker Warning in a Leo Warning.
+    #[error(transparent)]
+    TypeCheckerWarning(#[from] TypeCheckerWarning),
 }
 
 impl LeoWarning {
@@ -37,7 +53,107 @@ impl LeoWarning {
         use LeoWarning::*;
 
         match self {
+            LintWarning(warning) => warning.warning_code(),
             ParserWarning(warning) => warning.warning_code(),
+            TypeCheckerWarning(warning) => warning.warning_code(),
+        }
+    }
+
+    /// Returns the warning message, without its location or help text.
+    pub fn message(&self) -> &str {
+        use LeoWarning::*;
+
+        match self {
+            LintWarning(warning) => warning.message(),
+            ParserWarning(warning) => warning.message(),
+            TypeCheckerWarning(warning) => warning.message(),
+        }
+    }
+
+    /// Returns the help text of the warning, if it has one.
+    pub fn help(&self) -> Option<&str> {
+        use LeoWarning::*;
+
+        match self {
+            LintWarning(warning) => warning.help(),
+            ParserWarning(warning) => warning.help(),
+            TypeCheckerWarning(warning) => warning.help(),
+        }
+    }
+
+    /// Returns the edits suggested to fix the warning.
+    pub fn suggestions(&self) -> &[Suggestion] {
+        use LeoWarning::*;
+
+        match self {
+            LintWarning(warning) => warning.suggestions(),
+            ParserWarning(warning) => warning.suggestions(),
+            TypeCheckerWarning(warning) => warning.suggestions(),
+        }
+    }
+
+    /// Returns the secondary locations that the warning points to.
+    pub fn labels(&self) -> &[Label] {
+        use LeoWarning::*;
+
+        match self {
+            LintWarning(warning) => warning.labels(),
+            ParserWarning(warning) => warning.labels(),
+            TypeCheckerWarning(warning) => warning.labels(),
         }
     }
+
+    /// Returns the name of the function that created the warning, e.g. `unused_variable`.
+    pub fn kind(&self) -> &'static str {
+        use LeoWarning::*;
+
+        match self {
+            LintWarning(warning) => warning.kind(),
+            ParserWarning(warning) => warning.kind(),
+            TypeCheckerWarning(warning) => warning.kind(),
+        }
+    }
+
+    /// Returns the arguments that the warning was created from.
+    pub fn fields(&self) -> &[Field] {
+        use LeoWarning::*;
+
+        match self {
+            LintWarning(warning) => warning.fields(),
+            ParserWarning(warning) => warning.fields(),
+            TypeCheckerWarning(warning) => warning.fields(),
+        }
+    }
+
+    /// Returns the span that the warning points to, if it has one.
+    pub fn span(&self) -> Option<Span> {
+        use LeoWarning::*;
+
+        match self {
+            LintWarning(warning) => warning.span(),
+            ParserWarning(warning) => warning.span(),
+            TypeCheckerWarning(warning) => warning.span(),
+        }
+    }
+
+    /// Returns a key that identifies the warning by its code, file, message, and source text, but not its position.
+    /// This allows the same warning to be recognized across builds after unrelated lines have been edited.
+    pub fn fingerprint(&self) -> String {
+        let (file, source) = self
+            .span()
+            .and_then(|span| {
+                with_session_globals(|s| {
+                    let location = s.source_map.span_to_location(span)?;
+                    Some((
+                        location.source_file.name.to_string(),
+                        s.source_map.contents_of_span(span)?,
+                    ))
+                })
+            })
+            .unwrap_or_default();
+        // Collapse whitespace so that reformatting does not change the key, and so that it fits on one line.
+        let source = source.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        format!("{} {} {} {}", self.error_code(), file, self.message(), source)
+    }
 }
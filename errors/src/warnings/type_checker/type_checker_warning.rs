@@ -0,0 +1,58 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::create_messages;
+
+use std::fmt::Display;
+
+create_messages!(
+    /// TypeCheckerWarning enum that represents all the warnings for the type checker in the `leo-passes` crate.
+    TypeCheckerWarning,
+    code_mask: 2000i32,
+    code_prefix: "TYC",
+
+    /// For when a block of code can never be executed.
+    @formatted
+    unreachable_code {
+        args: (reason: impl Display),
+        msg: format!("Unreachable code: {reason}."),
+        help: None,
+    }
+
+    /// For when a transition consumes a record without checking that its caller owns it.
+    @formatted
+    unchecked_record_owner {
+        args: (record: impl Display, function: impl Display),
+        msg: format!("`{function}` consumes the record `{record}` without checking its owner."),
+        help: Some(format!("Check `{record}.owner == self.caller`, or annotate the transition with `@no_owner_check` if any caller may consume it.")),
+    }
+
+    /// For when a record is created with a constant owner.
+    @formatted
+    constant_record_owner {
+        args: (record: impl Display),
+        msg: format!("This `{record}` record is owned by a constant address, so only that account can ever spend it."),
+        help: Some("If the record is meant for the caller or a recipient, assign `self.caller` or an `address` input as its owner.".to_string()),
+    }
+
+    /// For when a record is created with an owner that is computed from other values.
+    @formatted
+    computed_record_owner {
+        args: (record: impl Display),
+        msg: format!("This `{record}` record is owned by a computed address, which may have no private key to spend it with."),
+        help: Some("Assign `self.caller`, an `address` input, or the owner of another record as its owner.".to_string()),
+    }
+);
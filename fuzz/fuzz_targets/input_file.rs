@@ -0,0 +1,37 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Parses input files, e.g. `inputs/main.in`, and converts them for `leo run`.
+
+#![no_main]
+
+use leo_ast::Struct;
+use leo_errors::emitter::Handler;
+use leo_span::{source_map::FileName, symbol::create_session_if_not_set_then};
+
+use indexmap::IndexMap;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|source: &str| {
+    create_session_if_not_set_then(|s| {
+        let sf = s.source_map.new_source(source, FileName::Custom("fuzz".into()));
+        let (handler, _) = Handler::new_with_buf();
+        if let Ok(input_ast) = leo_parser::parse_input(&handler, &sf.src, sf.start_pos) {
+            let _ = input_ast.program_inputs("main", IndexMap::<_, Struct>::new());
+        }
+        let _ = leo_parser::parse_program_inputs(&handler, &sf.src, sf.start_pos);
+    })
+});
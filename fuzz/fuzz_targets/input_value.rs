@@ -0,0 +1,34 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Parses and evaluates the values given as inputs on the command line, e.g. `leo run main 1u32 "{ x: 2u8 }"`.
+
+#![no_main]
+
+use leo_errors::emitter::Handler;
+use leo_span::{source_map::FileName, symbol::create_session_if_not_set_then};
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|value: &str| {
+    create_session_if_not_set_then(|s| {
+        let sf = s.source_map.new_source(value, FileName::Custom("fuzz".into()));
+        let (handler, _) = Handler::new_with_buf();
+        if let Ok(expression) = leo_parser::parse_expression(&handler, &sf.src, sf.start_pos) {
+            let _ = leo_interpreter::evaluate_constant(&expression);
+        }
+    })
+});
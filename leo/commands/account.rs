@@ -0,0 +1,147 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    commands::{Command, Network},
+    context::Context,
+    signer::{keystore_password, Keystore, SignerOptions},
+};
+use leo_errors::{CliError, Result};
+
+use clap::StructOpt;
+use rand::thread_rng;
+use snarkvm::prelude::{Address, PrivateKey, ViewKey};
+use std::{path::PathBuf, str::FromStr};
+use tracing::span::Span;
+
+/// Commands to manage the accounts that sign deployments.
+#[derive(StructOpt, Debug)]
+pub enum Account {
+    /// Generates a new account, printing its private key, view key and address
+    New {
+        #[structopt(
+            long,
+            help = "Encrypts the private key into a keystore in `~/.leo/accounts` instead of printing it."
+        )]
+        save: bool,
+    },
+    /// Encrypts a private key with a password into a keystore, which `leo deploy --keystore` signs with
+    Import {
+        #[structopt(
+            name = "PATH",
+            help = "The path of the keystore file to create. Defaults to `~/.leo/accounts/ADDRESS.json`."
+        )]
+        path: Option<PathBuf>,
+    },
+    /// Prints the address and the view key of the account of a signer
+    Show {
+        #[structopt(flatten)]
+        signer: SignerOptions,
+    },
+    /// Signs a message with the account of a signer, printing the signature
+    Sign {
+        #[structopt(name = "MESSAGE", help = "The message to sign.")]
+        message: String,
+
+        #[structopt(flatten)]
+        signer: SignerOptions,
+    },
+}
+
+impl Command for Account {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, _: Context, _: Self::Input) -> Result<Self::Output> {
+        match self {
+            Account::New { save } => {
+                let private_key = PrivateKey::<Network>::new(&mut thread_rng()).map_err(CliError::failed_to_sign)?;
+                match save {
+                    true => {
+                        let password = new_keystore_password()?;
+                        let keystore = Keystore::encrypt(&private_key, &password)?;
+                        write_keystore(&keystore, None)?;
+                    }
+                    false => tracing::info!("Private key: {}", private_key),
+                }
+                print_keys(&private_key)?;
+            }
+            Account::Import { path } => {
+                // The private key is read without echoing it, so that it does not end up in the shell history.
+                let term = console::Term::stderr();
+                term.write_str("Private key: ").map_err(CliError::cli_io_error)?;
+                let private_key = term.read_secure_line().map_err(CliError::cli_io_error)?;
+                let private_key = PrivateKey::<Network>::from_str(private_key.trim())
+                    .map_err(|err| CliError::failed_to_load_signer("the prompt", err))?;
+
+                let password = new_keystore_password()?;
+                let keystore = Keystore::encrypt(&private_key, &password)?;
+                write_keystore(&keystore, path)?;
+            }
+            Account::Show { signer } => {
+                let signer = signer.open()?.ok_or_else(CliError::no_signer)?;
+                match signer.private_key() {
+                    Some(private_key) => print_keys(private_key)?,
+                    // An external signer only tells the address of its account.
+                    None => tracing::info!("Address: {}", signer.address()?),
+                }
+            }
+            Account::Sign { message, signer } => {
+                let signer = signer.open()?.ok_or_else(CliError::no_signer)?;
+                tracing::info!("Address: {}", signer.address()?);
+                tracing::info!("Signature: {}", signer.sign(message.as_bytes())?);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Asks for the password of a new keystore twice, failing if the two differ.
+fn new_keystore_password() -> Result<String> {
+    let password = keystore_password("Password: ")?;
+    if keystore_password("Confirm the password: ")? != password {
+        return Err(CliError::keystore_passwords_differ().into());
+    }
+    Ok(password)
+}
+
+/// Writes `keystore` to `path`, or to its default path in the home directory.
+fn write_keystore(keystore: &Keystore, path: Option<PathBuf>) -> Result<()> {
+    let path = match path {
+        Some(path) => path,
+        None => keystore.default_path().ok_or_else(CliError::unknown_home_directory)?,
+    };
+    keystore.write(&path)?;
+    tracing::info!("Wrote the keystore of {} to {}", keystore.address, path.display());
+    Ok(())
+}
+
+/// Prints the view key and the address derived from `private_key`.
+fn print_keys(private_key: &PrivateKey<Network>) -> Result<()> {
+    let view_key = ViewKey::try_from(private_key).map_err(CliError::failed_to_sign)?;
+    let address = Address::try_from(private_key).map_err(CliError::failed_to_sign)?;
+    tracing::info!("View key: {}", view_key);
+    tracing::info!("Address: {}", address);
+    Ok(())
+}
@@ -0,0 +1,77 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+use leo_errors::{CliError, Result};
+use leo_package::dependencies::{home_directory, Fetcher, Registry, DEFAULT_ENDPOINT};
+
+use clap::StructOpt;
+use serde_json::json;
+use tracing::span::Span;
+
+/// Fetch a deployed program from the Aleo network and add it to the dependencies of the package.
+#[derive(StructOpt, Debug)]
+pub struct Add {
+    #[structopt(name = "PROGRAM", help = "The id of the deployed program, e.g. `token.aleo`.")]
+    program: String,
+
+    #[structopt(
+        long,
+        value_name = "URL",
+        default_value = DEFAULT_ENDPOINT,
+        env = "LEO_ENDPOINT",
+        help = "The REST endpoint of the Aleo node to fetch the program from."
+    )]
+    endpoint: String,
+
+    #[structopt(
+        long,
+        default_value = "testnet3",
+        help = "The network that the program is deployed on."
+    )]
+    network: String,
+}
+
+impl Command for Add {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Adding")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let name = self
+            .program
+            .strip_suffix(".aleo")
+            .ok_or_else(|| CliError::invalid_program_id(&self.program))?;
+        let home = home_directory().ok_or_else(CliError::unknown_home_directory)?;
+
+        // Cache the bytecode of the program in the registry, where the imports of any package are found.
+        let bytecode = Fetcher::new(&self.endpoint, &self.network).fetch(&self.program)?;
+        Registry::at_home(&home).store(name, &bytecode)?;
+
+        // Declare the dependency, so that it is pinned in `leo.lock` and its bytecode is built along with the package.
+        context.add_dependency(name, json!({ "network": self.network }))?;
+
+        tracing::info!("Added {0}, which is imported with `import {0};`", self.program);
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+use leo_ast::Ast;
+use leo_errors::{emitter::Handler, PackageError, Result};
+use leo_span::{source_map::FileName, symbol::with_session_globals};
+
+use clap::StructOpt;
+use serde_json::json;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::span::Span;
+
+/// Compare the items and statements of two versions of a program.
+#[derive(StructOpt, Debug)]
+pub struct AstDiff {
+    #[structopt(
+        name = "OLD",
+        parse(from_os_str),
+        help = "The old program, as a Leo source file, a JSON AST, or a binary AST written with `--ast-format binary`."
+    )]
+    pub(crate) old: PathBuf,
+
+    #[structopt(
+        name = "NEW",
+        parse(from_os_str),
+        help = "The new program, in any of the formats of OLD."
+    )]
+    pub(crate) new: PathBuf,
+
+    #[structopt(long, help = "Prints the differences as JSON.")]
+    pub(crate) json: bool,
+}
+
+impl Command for AstDiff {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let handler = context.handler();
+        let old = read_ast(&handler, &self.old)?;
+        let new = read_ast(&handler, &self.new)?;
+
+        let diff = leo_ast::diff(old.as_repr(), new.as_repr());
+        match self.json {
+            true => println!("{}", json!(diff)),
+            false => print!("{}", diff),
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the AST of a program from a Leo source file, or from a JSON or binary AST by their extensions.
+fn read_ast(handler: &Handler, path: &Path) -> Result<Ast> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => Ast::from_json_file(path.to_path_buf()),
+        Some("bin") => {
+            let bytes = fs::read(path).map_err(|err| PackageError::failed_to_read_file(path.display(), err))?;
+            Ast::from_binary(&bytes)
+        }
+        _ => {
+            let source =
+                fs::read_to_string(path).map_err(|err| PackageError::failed_to_read_file(path.display(), err))?;
+            let file = with_session_globals(|s| s.source_map.new_source(&source, FileName::Real(path.to_path_buf())));
+            leo_parser::parse_ast(handler, &file.src, file.start_pos)
+        }
+    }
+}
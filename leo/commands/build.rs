@@ -15,26 +15,43 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::commands::ALEO_CLI_COMMAND;
-use crate::{commands::Command, context::Context};
+use crate::{
+    commands::{Command, Network},
+    context::Context,
+    scripts::Hook,
+};
 
-use leo_ast::Struct;
-use leo_compiler::{Compiler, InputAst, OutputOptions};
-use leo_errors::{CliError, CompilerError, PackageError, Result};
-use leo_package::source::SourceDirectory;
-use leo_package::{inputs::InputFile, outputs::OutputsDirectory};
-use leo_span::symbol::with_session_globals;
+use leo_ast::{FormatConfig, Struct};
+use leo_compiler::{
+    check_input, AstFormat, CompilationCache, Compiler, CostEstimate, DumpFormat, FunctionInstructions, HaltPoint,
+    HeaderConfig, ImportPolicy, ImportSearchPath, InputAst, InstructionSourceMap, LanguageVersion, NamingConfig,
+    OutputOptions, ProvenAssert,
+};
+use leo_errors::{CliError, CompilerError, LeoError, LeoWarning, PackageError, Result};
+use leo_package::source::{SourceDirectory, SOURCE_DIRECTORY_NAME};
+use leo_package::{
+    dependencies::{home_directory, DependencySource, Lockfile, Registry},
+    inputs::{InputFile, INPUTS_DIRECTORY_NAME},
+    outputs::{BuildMetrics, ChecksumFile, FileMetrics, MetricsFile, OutputsDirectory, MAIN_ALEO_FILE_NAME},
+};
+use leo_span::symbol::{set_session_globals_then, with_session_globals};
 
 use aleo::commands::Build as AleoBuild;
 
 use clap::StructOpt;
 use indexmap::IndexMap;
+use rayon::prelude::*;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use snarkvm::file::Manifest;
 use snarkvm::prelude::{ProgramID, Testnet3};
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use leo_errors::emitter::Handler;
-use leo_package::build::BuildDirectory;
-use leo_package::imports::ImportsDirectory;
+use leo_errors::emitter::{Emitter, Handler, WarningLevel};
+use leo_package::build::{BuildDirectory, BUILD_DIRECTORY_NAME};
+use leo_package::imports::{ImportsDirectory, IMPORTS_DIRECTORY_NAME};
 use leo_span::Symbol;
 use tracing::span::Span;
 
@@ -46,49 +63,237 @@ pub struct BuildOptions {
     pub offline: bool,
     #[structopt(long, help = "Enable spans in AST snapshots.")]
     pub enable_spans: bool,
+    #[structopt(
+        long,
+        default_value = "json",
+        possible_values = &["json", "binary"],
+        help = "Writes AST snapshots as JSON, or in a compact binary format to `<stage>_ast.bin`."
+    )]
+    pub ast_format: AstFormat,
     #[structopt(long, help = "Writes all AST snapshots for the different compiler phases.")]
     pub enable_all_ast_snapshots: bool,
     #[structopt(long, help = "Writes Input AST snapshot of the initial parse.")]
     pub enable_initial_input_ast_snapshot: bool,
     #[structopt(long, help = "Writes AST snapshot of the initial parse.")]
     pub enable_initial_ast_snapshot: bool,
+    #[structopt(
+        long,
+        help = "Writes AST snapshot of the type checked AST, along with the types of its expressions."
+    )]
+    pub enable_type_checked_ast_snapshot: bool,
     #[structopt(long, help = "Writes AST snapshot of the unrolled AST.")]
     pub enable_unrolled_ast_snapshot: bool,
     #[structopt(long, help = "Writes AST snapshot of the SSA AST.")]
     pub enable_ssa_ast_snapshot: bool,
     #[structopt(long, help = "Writes AST snapshot of the flattened AST.")]
     pub enable_flattened_ast_snapshot: bool,
+    #[structopt(long, help = "Writes AST snapshot of the canonicalized AST.")]
+    pub enable_canonicalized_ast_snapshot: bool,
+    #[structopt(
+        long,
+        help = "Writes the AST after every compiler pass to `outputs/<program>/NN_<pass>.json`, to bisect miscompilations."
+    )]
+    pub dump_all: bool,
+    #[structopt(
+        long,
+        default_value = "json",
+        possible_values = &["json", "leo"],
+        help = "Writes the AST of `--dump-all` as JSON, or the program as Leo source to `outputs/<program>/NN_<pass>.leo`."
+    )]
+    pub dump_format: DumpFormat,
+    #[structopt(long, help = "Fails the build if any warnings are emitted.")]
+    pub deny_warnings: bool,
+    #[structopt(
+        long = "allow",
+        value_name = "CODE",
+        help = "Silences the warning with the given code."
+    )]
+    pub allow_warnings: Vec<String>,
+    #[structopt(
+        long = "warn",
+        value_name = "CODE",
+        help = "Reports the warning with the given code."
+    )]
+    pub warn_warnings: Vec<String>,
+    #[structopt(
+        long = "deny",
+        value_name = "CODE",
+        help = "Fails the build if the warning with the given code is emitted."
+    )]
+    pub denied_warnings: Vec<String>,
+    #[structopt(
+        long,
+        value_name = "N",
+        help = "Aborts compilation after the given number of errors have been reported."
+    )]
+    pub error_limit: Option<usize>,
+    #[structopt(long, help = "Lists the operations that can halt at runtime, grouped by function.")]
+    pub list_halt_points: bool,
+    #[structopt(
+        long,
+        help = "Estimates the number of constraints of each function from static weights of its operations."
+    )]
+    pub estimate_costs: bool,
+    #[structopt(long, help = "Keeps imports in the generated program even if they are never used.")]
+    pub keep_unused_imports: bool,
+    #[structopt(
+        long,
+        help = "Keeps assertions in the generated program even if they are proven to always hold."
+    )]
+    pub keep_proven_asserts: bool,
+    #[structopt(
+        long,
+        help = "Warns about each integer that is implicitly widened to a wider integer type."
+    )]
+    pub warn_implicit_widening: bool,
+    #[structopt(
+        long,
+        help = "Reports the number of Aleo instructions, which the constraints of a circuit are synthesized from, generated by each function and statement."
+    )]
+    pub print_constraints: bool,
+    #[structopt(
+        long,
+        requires = "print-constraints",
+        help = "Also writes the reported instruction counts as JSON to `outputs/<program>.constraints.json`."
+    )]
+    pub constraints_json: bool,
+    #[structopt(
+        long,
+        value_name = "ARTIFACT",
+        possible_values = &["call-graph"],
+        help = "Writes an extra artifact to `outputs/`: `call-graph` writes the calls between functions as DOT to `outputs/<program>.call_graph.dot`."
+    )]
+    pub emit: Vec<EmitArtifact>,
+    #[structopt(
+        long = "import-path",
+        value_name = "DIR",
+        help = "Also looks for imported programs in DIR, after the `imports/` directory of the package and before the registry in `~/.leo/registry/`."
+    )]
+    pub import_path: Vec<PathBuf>,
+    /// The naming conventions configured in `program.json`.
+    #[structopt(skip)]
+    pub naming: NamingConfig,
+    /// The header of generated code configured in `program.json`.
+    #[structopt(skip)]
+    pub header: HeaderConfig,
+    /// The layout of printed source and generated comments configured in `program.json`.
+    #[structopt(skip)]
+    pub format: FormatConfig,
+    /// The programs that the package may import, configured in `program.json`.
+    #[structopt(skip)]
+    pub import_policy: ImportPolicy,
+    /// The version of Leo configured in `program.json`.
+    #[structopt(skip)]
+    pub language_version: Option<LanguageVersion>,
+    /// The Leo files of the programs of other packages that the package imports, by name.
+    #[structopt(skip)]
+    pub import_programs: IndexMap<String, PathBuf>,
+    /// The build directory whose compilation cache is used instead of that of the package, e.g. that of a workspace.
+    #[structopt(skip)]
+    pub cache_directory: Option<PathBuf>,
 }
 
 impl From<BuildOptions> for OutputOptions {
     fn from(options: BuildOptions) -> Self {
         let mut out_options = Self {
             spans_enabled: options.enable_spans,
+            ast_format: options.ast_format,
             initial_input_ast: options.enable_initial_input_ast_snapshot,
             initial_ast: options.enable_initial_ast_snapshot,
+            type_checked_ast: options.enable_type_checked_ast_snapshot,
             unrolled_ast: options.enable_unrolled_ast_snapshot,
             ssa_ast: options.enable_ssa_ast_snapshot,
             flattened_ast: options.enable_flattened_ast_snapshot,
+            canonicalized_ast: options.enable_canonicalized_ast_snapshot,
+            dump_all: options.dump_all,
+            dump_format: options.dump_format,
+            warnings: Default::default(),
+            error_limit: options.error_limit,
+            naming: options.naming,
+            header: options.header,
+            format: options.format,
+            import_policy: options.import_policy,
+            import_search_path: options.import_programs.into_iter().fold(
+                ImportSearchPath::for_package(options.import_path),
+                |search_path, (name, file)| search_path.with_program(Symbol::intern(&name), file),
+            ),
+            language_version: options.language_version,
+            list_halt_points: options.list_halt_points,
+            estimate_costs: options.estimate_costs,
+            keep_unused_imports: options.keep_unused_imports,
+            keep_proven_asserts: options.keep_proven_asserts,
+            warn_implicit_widening: options.warn_implicit_widening,
         };
+        if options.deny_warnings {
+            out_options.warnings.default_level = WarningLevel::Deny;
+        }
+        for (codes, level) in [
+            (options.allow_warnings, WarningLevel::Allow),
+            (options.warn_warnings, WarningLevel::Warn),
+            (options.denied_warnings, WarningLevel::Deny),
+        ] {
+            codes
+                .into_iter()
+                .for_each(|code| out_options.warnings.set_level(code, level));
+        }
         if options.enable_all_ast_snapshots {
             out_options.initial_input_ast = true;
             out_options.initial_ast = true;
+            out_options.type_checked_ast = true;
             out_options.unrolled_ast = true;
             out_options.ssa_ast = true;
             out_options.flattened_ast = true;
+            out_options.canonicalized_ast = true;
         }
 
         out_options
     }
 }
 
+/// An extra artifact written by the build.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmitArtifact {
+    /// The calls between the functions of the program, in the DOT format of Graphviz.
+    CallGraph,
+}
+
+impl std::str::FromStr for EmitArtifact {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "call-graph" => Ok(Self::CallGraph),
+            _ => Err(format!("unknown artifact `{s}`, expected `call-graph`")),
+        }
+    }
+}
+
 /// Compile and build program command.
 #[derive(StructOpt, Debug)]
 pub struct Build {
     #[structopt(flatten)]
     pub(crate) compiler_options: BuildOptions,
+
+    #[structopt(
+        long,
+        help = "Rebuilds the package whenever a Leo file in `src/` or `imports/`, or a file in `inputs/`, changes."
+    )]
+    pub(crate) watch: bool,
+
+    #[structopt(
+        long,
+        conflicts_with = "watch",
+        help = "Builds each member of the workspace in `leo.workspace.json`, after the members that it imports."
+    )]
+    pub(crate) workspace: bool,
 }
 
+/// How often the files of the package are checked for changes in watch mode.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The structs declared by the imports compiled by previous builds in watch mode, with the checksum of each file.
+type ImportCache = IndexMap<PathBuf, (String, IndexMap<Symbol, Struct>)>;
+
 impl Command for Build {
     type Input = ();
     type Output = (Option<InputAst>, IndexMap<Symbol, Struct>);
@@ -102,27 +307,173 @@ impl Command for Build {
     }
 
     fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        match (self.workspace, self.watch) {
+            (true, _) => self.build_workspace(&context),
+            (false, true) => self.rebuild_on_changes(&context),
+            (false, false) => self.run_build(&context, &mut ImportCache::new()),
+        }
+    }
+}
+
+impl Build {
+    /// Builds the members of the workspace in dependency order, sharing the compilation cache of the workspace,
+    /// and returns the output of the last member built.
+    fn build_workspace(&self, context: &Context) -> Result<<Self as Command>::Output> {
+        let root = std::env::current_dir()
+            .map_err(CliError::cli_io_error)?
+            .join(context.dir()?);
+        let members = context.open_workspace()?;
+
+        let mut compiler_options = self.compiler_options.clone();
+        compiler_options.cache_directory = Some(BuildDirectory::create(&root)?);
+
+        let mut output = (None, IndexMap::new());
+        for member in members {
+            let member_path = member.dir()?;
+            tracing::info!(
+                "Building {}",
+                member_path.strip_prefix(&root).unwrap_or(&member_path).display()
+            );
+
+            // Imports are resolved relative to the package, and each build changes the current directory.
+            std::env::set_current_dir(&member_path)
+                .map_err(|err| PackageError::failed_to_set_cwd(member_path.display(), err))?;
+            let build = Build {
+                compiler_options: compiler_options.clone(),
+                watch: false,
+                workspace: false,
+            };
+            output = build.run_build(&member, &mut ImportCache::new())?;
+        }
+        Ok(output)
+    }
+
+    /// Builds the package whenever one of its files changes, until the process is interrupted.
+    /// The imports that did not change since the previous build are not compiled again.
+    fn rebuild_on_changes(&self, context: &Context) -> Result<<Self as Command>::Output> {
+        let package_path = context.dir()?;
+        let mut imports = ImportCache::new();
+
+        loop {
+            match self.run_build(context, &mut imports) {
+                Ok(_) => tracing::info!("Watching for changes..."),
+                Err(error) => {
+                    context.error_format.emitter().emit_err(error);
+                    tracing::info!("Build failed. Watching for changes...");
+                }
+            }
+
+            // Files changed by the build itself, e.g. by its scripts, do not trigger another build.
+            let files = watched_files(&package_path);
+            loop {
+                std::thread::sleep(WATCH_INTERVAL);
+                let current = watched_files(&package_path);
+                let changed = changed_files(&files, &current);
+                if !changed.is_empty() {
+                    for path in changed {
+                        tracing::info!("Changed {}", path.strip_prefix(&package_path).unwrap_or(path).display());
+                    }
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Builds the package once, running its scripts and recording the metrics of the build.
+    fn run_build(&self, context: &Context, imports: &mut ImportCache) -> Result<<Self as Command>::Output> {
         // Get the package path.
         let package_path = context.dir()?;
 
+        // Run the scripts declared to run before building.
+        let scripts = context.open_scripts()?;
+        scripts.run(Hook::PreBuild)?;
+
+        // Initialize error handler
+        let handler = context.handler();
+
+        // Record the metrics of the build, whether it succeeds or not.
+        let start = Instant::now();
+        let mut metrics = BuildMetrics {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|since_epoch| since_epoch.as_secs())
+                .unwrap_or_default(),
+            leo_version: env!("CARGO_PKG_VERSION").to_string(),
+            ..Default::default()
+        };
+        let result = self.build(context, &package_path, &handler, &mut metrics, imports);
+        metrics.success = result.is_ok();
+        metrics.duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+        // A failing build returns its last error, which may or may not have been reported to the handler.
+        metrics.errors = handler.err_count().max(result.is_err() as usize);
+        metrics.warnings = handler.warning_count();
+
+        // The metrics are only written locally to track trends, so failing to write them does not fail the build.
+        if let Err(error) = MetricsFile::append_to(&package_path, &metrics) {
+            tracing::warn!("Failed to write the metrics of the build: {}", error);
+        }
+
+        let output = result?;
+        scripts.run(Hook::PostBuild)?;
+
+        Ok(output)
+    }
+
+    /// Compiles the Leo files of the package and builds the Aleo program, recording the metrics of the build.
+    /// Imports whose checksum is in `imports` are not compiled again, and those that are compiled are added to it.
+    fn build(
+        &self,
+        context: &Context,
+        package_path: &Path,
+        handler: &Handler,
+        metrics: &mut BuildMetrics,
+        imports: &mut ImportCache,
+    ) -> Result<<Self as Command>::Output> {
         // Get the program id.
         let manifest = context.open_manifest()?;
         let program_id = manifest.program_id();
 
+        // Resolve the dependencies, and check that they did not change since they were pinned in `leo.lock`.
+        let dependencies = context.open_dependencies()?;
+        Lockfile::update(package_path, &dependencies, home_directory().as_deref())?;
+
         // Create the outputs directory.
-        let outputs_directory = OutputsDirectory::create(&package_path)?;
+        let outputs_directory = OutputsDirectory::create(package_path)?;
 
         // Open the build directory.
-        let build_directory = BuildDirectory::open(&package_path)?;
+        let build_directory = BuildDirectory::open(package_path)?;
 
-        // Initialize error handler
-        let handler = Handler::default();
+        // Read the naming conventions, the header and layout of generated code and the version of Leo configured in `program.json`.
+        let mut compiler_options = self.compiler_options.clone();
+        compiler_options.naming = context.open_naming_config()?;
+        compiler_options.header = context.open_header_config()?;
+        compiler_options.format = context.open_format_config()?;
+        compiler_options.import_policy = context.open_import_policy()?;
+        compiler_options.language_version = context.open_language_version()?;
+
+        // Programs of other packages, such as the members of a workspace, are imported from their Leo files,
+        // and built from the instructions that their own builds generated.
+        let packages = dependencies
+            .iter()
+            .filter_map(|dependency| match &dependency.source {
+                DependencySource::Path(path) => Some((dependency, package_path.join(path))),
+                _ => None,
+            })
+            .filter(|(_, path)| path.join(Manifest::<Network>::file_name()).is_file())
+            .collect::<Vec<_>>();
+        for (dependency, _) in packages.iter() {
+            let resolved = dependency.resolve(package_path, None)?;
+            compiler_options
+                .import_programs
+                .insert(dependency.name.clone(), resolved.file);
+        }
 
         // Fetch paths to all .leo files in the source directory.
-        let source_files = SourceDirectory::files(&package_path)?;
+        let source_files = SourceDirectory::files(package_path)?;
 
         // Check the source files.
         SourceDirectory::check_files(&source_files)?;
+        let main_file_path = source_files[0].clone();
 
         // Store all struct declarations made in the source files.
         let mut structs = IndexMap::new();
@@ -131,40 +482,139 @@ impl Command for Build {
         for file_path in source_files.into_iter() {
             structs.extend(compile_leo_file(
                 file_path,
-                &package_path,
+                package_path,
                 program_id,
                 &outputs_directory,
                 &build_directory,
-                &handler,
-                self.compiler_options.clone(),
+                handler,
+                compiler_options.clone(),
                 false,
+                None,
+                &mut metrics.files,
             )?);
         }
 
-        if !ImportsDirectory::is_empty(&package_path)? {
+        if !ImportsDirectory::is_empty(package_path)? {
             // Create Aleo build/imports/ directory.
             let build_imports_directory = ImportsDirectory::create(&build_directory)?;
 
             // Fetch paths to all .leo files in the imports directory.
-            let import_files = ImportsDirectory::files(&package_path)?;
+            let import_files = ImportsDirectory::files(package_path)?;
 
-            // Compile all .leo files into .aleo files.
+            // Imports that did not change since they were last compiled, even by another build, are not compiled again.
+            let cache = CompilationCache::new(compiler_options.cache_directory.as_deref().unwrap_or(&build_directory));
+
+            // Reuse the structs of the imports that did not change since the previous build.
+            let mut pending = Vec::new();
             for file_path in import_files.into_iter() {
-                structs.extend(compile_leo_file(
-                    file_path,
-                    &package_path,
-                    program_id,
-                    &outputs_directory,
-                    &build_imports_directory,
-                    &handler,
-                    self.compiler_options.clone(),
-                    true,
-                )?);
+                let contents = std::fs::read(&file_path).map_err(|e| CompilerError::file_read_error(&file_path, e))?;
+                let checksum = format!("{:x}", Sha256::digest(&contents));
+                let reused = imports
+                    .get(&file_path)
+                    .filter(|(cached, _)| *cached == checksum)
+                    .map(|(_, import_structs)| import_structs.clone());
+                pending.push((file_path, checksum, reused));
+            }
+
+            // The other imports do not depend on each other, so they are compiled into .aleo files in parallel.
+            let compiled: Vec<Option<CompiledImport>> = with_session_globals(|session_globals| {
+                pending
+                    .par_iter()
+                    .map(|(file_path, _, reused)| {
+                        reused.is_none().then(|| {
+                            set_session_globals_then(session_globals, || {
+                                compile_import(
+                                    file_path.clone(),
+                                    package_path,
+                                    program_id,
+                                    &outputs_directory,
+                                    &build_imports_directory,
+                                    compiler_options.clone(),
+                                    &cache,
+                                )
+                            })
+                        })
+                    })
+                    .collect()
+            });
+
+            // Report the diagnostics and merge the structs of the imports in order,
+            // so that the output does not depend on which import was compiled first.
+            let mut first_error = None;
+            for ((file_path, checksum, reused), compiled) in pending.into_iter().zip(compiled) {
+                let import_structs = match (reused, compiled) {
+                    (Some(import_structs), _) => {
+                        let file_name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                        tracing::info!("Reusing '{}' from the previous build", file_name);
+                        metrics.files.push(FileMetrics {
+                            file: file_name,
+                            cache_hit: true,
+                            passes: IndexMap::new(),
+                        });
+                        import_structs
+                    }
+                    (None, Some(compiled)) => {
+                        compiled.errors.into_iter().for_each(|error| handler.emit_err(error));
+                        compiled
+                            .warnings
+                            .into_iter()
+                            .for_each(|warning| handler.emit_warning(warning));
+                        metrics.files.extend(compiled.metrics);
+                        match compiled.structs {
+                            Ok(import_structs) => {
+                                imports.insert(file_path, (checksum, import_structs.clone()));
+                                import_structs
+                            }
+                            Err(error) => {
+                                first_error.get_or_insert(error);
+                                continue;
+                            }
+                        }
+                    }
+                    (None, None) => unreachable!("An import that is not reused is compiled."),
+                };
+                structs.extend(import_structs);
+            }
+            if let Some(error) = first_error {
+                return Err(error);
+            }
+        }
+
+        // Programs fetched from the network are built from their bytecode in the registry, rather than compiled.
+        let fetched = dependencies
+            .iter()
+            .filter(|dependency| matches!(dependency.source, DependencySource::Network(_)))
+            .collect::<Vec<_>>();
+        if let (false, Some(home)) = (fetched.is_empty(), home_directory()) {
+            let registry = Registry::at_home(&home);
+            let build_imports_directory = ImportsDirectory::create(&build_directory)?;
+            for dependency in fetched {
+                let bytecode_path = registry.bytecode_path(&dependency.name);
+                std::fs::copy(
+                    &bytecode_path,
+                    build_imports_directory.join(format!("{}.aleo", dependency.name)),
+                )
+                .map_err(|err| PackageError::failed_to_read_file(bytecode_path.display(), err))?;
             }
         }
 
-        // Load the input file at `package_name.in`
-        let input_file_path = InputFile::new(&manifest.program_id().name().to_string()).setup_file_path(&package_path);
+        if !packages.is_empty() {
+            let build_imports_directory = ImportsDirectory::create(&build_directory)?;
+            for (dependency, path) in packages {
+                let instructions = path.join(BUILD_DIRECTORY_NAME).join(MAIN_ALEO_FILE_NAME);
+                if !instructions.is_file() {
+                    return Err(PackageError::dependency_not_built(&dependency.name, path.display()).into());
+                }
+                std::fs::copy(
+                    &instructions,
+                    build_imports_directory.join(format!("{}.aleo", dependency.name)),
+                )
+                .map_err(|err| PackageError::failed_to_read_file(instructions.display(), err))?;
+            }
+        }
+
+        // Load the input file at `package_name.in`, or else at `package_name.toml` or `package_name.json`.
+        let input_file_path = InputFile::new(&manifest.program_id().name().to_string()).find_file_path(package_path);
 
         // Parse the input file.
         let input_ast = if input_file_path.exists() {
@@ -172,10 +622,29 @@ impl Command for Build {
             let input_sf = with_session_globals(|s| s.source_map.load_file(&input_file_path))
                 .map_err(|e| CompilerError::file_read_error(&input_file_path, e))?;
 
+            // The inputs are checked against the signatures of the main program,
+            // and those of a TOML or JSON file take their types from them.
+            let main_sf = with_session_globals(|s| s.source_map.load_file(&main_file_path))
+                .map_err(|e| CompilerError::file_read_error(&main_file_path, e))?;
+            let program = leo_parser::parse_ast(handler, &main_sf.src, main_sf.start_pos)?;
+
             // TODO: This is a hack to notify the user that something is wrong with the input file. Redesign.
-            leo_parser::parse_input(&handler, &input_sf.src, input_sf.start_pos)
-                .map_err(|_e| println!("Warning: Failed to parse input file"))
-                .ok()
+            let input_ast = match input_file_path.extension().and_then(|extension| extension.to_str()) {
+                Some("toml" | "json") => {
+                    leo_parser::parse_input_data(handler, &input_file_path, &input_sf.src, program.as_repr())
+                }
+                _ => leo_parser::parse_input(handler, &input_sf.src, input_sf.start_pos),
+            };
+            match input_ast {
+                Ok(input_ast) => {
+                    check_input(&input_ast, program.as_repr())?;
+                    Some(input_ast)
+                }
+                Err(e) => {
+                    println!("Warning: Failed to parse input file: {}", e);
+                    None
+                }
+            }
         } else {
             None
         };
@@ -195,11 +664,82 @@ impl Command for Build {
         // Log the result of the build
         tracing::info!("{}", result);
 
+        // Record the size of each artifact.
+        artifact_sizes(&build_directory, &build_directory, &mut metrics.artifacts);
+
         Ok((input_ast, structs))
     }
 }
 
+/// Records the size in bytes of each file under `directory`, by its path relative to `root`.
+fn artifact_sizes(root: &Path, directory: &Path, sizes: &mut IndexMap<String, u64>) {
+    let mut paths: Vec<PathBuf> = match std::fs::read_dir(directory) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect(),
+        Err(_) => return,
+    };
+    paths.sort();
+
+    for path in paths {
+        if path.is_dir() {
+            artifact_sizes(root, &path, sizes);
+        } else if let Ok(metadata) = path.metadata() {
+            let name = path.strip_prefix(root).unwrap_or(&path).display().to_string();
+            sizes.insert(name, metadata.len());
+        }
+    }
+}
+
+/// An import compiled on a worker thread, along with the diagnostics and metrics of its compilation.
+struct CompiledImport {
+    /// The structs declared in the import, or the error that its compilation failed with.
+    structs: Result<IndexMap<Symbol, Struct>>,
+    /// The errors emitted while compiling the import.
+    errors: Vec<LeoError>,
+    /// The warnings emitted while compiling the import.
+    warnings: Vec<LeoWarning>,
+    /// The metrics of the compilation.
+    metrics: Vec<FileMetrics>,
+}
+
+/// Compiles a Leo file in the `imports/` directory, reusing its artifacts in `cache` if it did not change.
+/// A handler cannot be shared between threads, so the import is compiled with a handler of its own,
+/// and its diagnostics are returned to be reported by the caller.
+fn compile_import(
+    file_path: PathBuf,
+    package_path: &Path,
+    program_id: &ProgramID<Testnet3>,
+    outputs: &Path,
+    build: &Path,
+    options: BuildOptions,
+    cache: &CompilationCache,
+) -> CompiledImport {
+    let (handler, buffer) = Handler::new_with_buf();
+    let mut metrics = Vec::new();
+    let structs = compile_leo_file(
+        file_path,
+        package_path,
+        program_id,
+        outputs,
+        build,
+        &handler,
+        options,
+        true,
+        Some(cache),
+        &mut metrics,
+    );
+
+    CompiledImport {
+        structs,
+        errors: buffer.extract_errs().into_inner(),
+        warnings: buffer.extract_warnings().into_inner(),
+        metrics,
+    }
+}
+
 /// Compiles a Leo file in the `src/` directory.
+/// If a `cache` is given, the artifacts of a file that did not change since they were cached are reused.
 #[allow(clippy::too_many_arguments)]
 fn compile_leo_file(
     file_path: PathBuf,
@@ -210,6 +750,8 @@ fn compile_leo_file(
     handler: &Handler,
     options: BuildOptions,
     is_import: bool,
+    cache: Option<&CompilationCache>,
+    metrics: &mut Vec<FileMetrics>,
 ) -> Result<IndexMap<Symbol, Struct>> {
     // Construct the Leo file name with extension `foo.leo`.
     let file_name = file_path
@@ -234,6 +776,17 @@ fn compile_leo_file(
         false => format!("main.{}", program_id.network()),
     });
 
+    let list_halt_points = options.list_halt_points;
+    let keep_proven_asserts = options.keep_proven_asserts;
+    let estimate_costs = options.estimate_costs;
+    let print_constraints = options.print_constraints;
+    let constraints_json = options.constraints_json;
+    let emit_call_graph = options.emit.contains(&EmitArtifact::CallGraph);
+    let checksum_file = ChecksumFile::new(&program_name);
+
+    // The reports are made by the passes, which do not run if the artifacts are reused, so the cache is not used for them.
+    let cache = cache.filter(|_| !(list_halt_points || estimate_costs || print_constraints || constraints_json));
+
     // Create a new instance of the Leo compiler.
     let mut compiler = Compiler::new(
         program_name,
@@ -244,8 +797,28 @@ fn compile_leo_file(
         Some(options.into()),
     );
 
-    // Compile the Leo program into Aleo instructions.
-    let (symbol_table, instructions) = compiler.compile_and_generate_instructions()?;
+    // Compare the checksum of the file with that of its last build.
+    let checksum = compiler.checksum()?;
+    let unchanged = checksum_file.exists_at(outputs) && checksum_file.read_from(outputs)? == checksum;
+
+    // Compile the Leo program into Aleo instructions, recording how long each pass took.
+    let result = match cache {
+        Some(cache) => compiler.compile_with_cache(cache),
+        None => compiler
+            .compile_and_generate_instructions()
+            .map(|(symbol_table, instructions)| (symbol_table, instructions, false)),
+    };
+    let reused = matches!(result, Ok((_, _, true)));
+    metrics.push(FileMetrics {
+        file: file_name.to_string(),
+        cache_hit: unchanged || reused,
+        passes: compiler
+            .pass_durations
+            .iter()
+            .map(|(pass, duration)| (pass.to_string(), duration.as_secs_f64() * 1000.0))
+            .collect(),
+    });
+    let (symbol_table, instructions, _) = result?;
 
     // Write the instructions.
     std::fs::File::create(&aleo_file_path)
@@ -253,11 +826,262 @@ fn compile_leo_file(
         .write_all(instructions.as_bytes())
         .map_err(CliError::failed_to_load_instructions)?;
 
+    // Write the source map, locating the Leo statement each instruction came from.
+    // The source map of reused artifacts was written when they were compiled.
+    if !reused {
+        let source_map_path = outputs.join(format!("{}.map.json", compiler.program_name));
+        let source_map = source_map_to_json(&compiler.program_name, file_name, &compiler.instruction_source_map);
+        std::fs::write(&source_map_path, source_map.to_string())
+            .map_err(|e| CliError::failed_to_write_source_map(source_map_path.display(), e))?;
+    }
+
+    // Write the report of the assertions that were removed, with the proof of each, so that they can be audited.
+    // The report of reused artifacts was written when they were compiled.
+    if !reused && !keep_proven_asserts {
+        let report_path = outputs.join(format!("{}.asserts.json", compiler.program_name));
+        let report = compiler.proven_asserts_report();
+        std::fs::write(&report_path, report.to_string())
+            .map_err(|e| CliError::failed_to_write_assert_report(report_path.display(), e))?;
+    }
+
+    // Write the call graph, which is only known if the program was type checked rather than reused from the cache.
+    if !reused && emit_call_graph {
+        let path = outputs.join(format!("{}.call_graph.dot", compiler.program_name));
+        let dot = compiler.call_graph.to_dot(&compiler.program_name);
+        std::fs::write(&path, dot).map_err(|e| CliError::failed_to_write_call_graph(path.display(), e))?;
+    }
+
+    // Record the checksum of the built file.
+    checksum_file.write_to(outputs, checksum)?;
+
     // Prepare the path string.
     let _path_string = format!("(in \"{}\")", aleo_file_path.display());
 
     // Log the build as successful.
-    tracing::info!("Compiled '{}' into Aleo instructions", file_name,);
+    match reused {
+        true => tracing::info!(
+            "Reusing the Aleo instructions of '{}' from the compilation cache",
+            file_name
+        ),
+        false => tracing::info!("Compiled '{}' into Aleo instructions", file_name,),
+    }
+
+    if list_halt_points {
+        log_halt_points(file_name, &compiler.halt_points);
+    }
+
+    if !reused && !keep_proven_asserts {
+        log_proven_asserts(file_name, &compiler.proven_asserts);
+    }
+
+    if estimate_costs {
+        log_cost_estimates(file_name, &compiler.cost_estimates);
+    }
+
+    if print_constraints {
+        log_instruction_counts(file_name, &compiler.instruction_counts);
+    }
+
+    if constraints_json {
+        let path = outputs.join(format!("{}.constraints.json", compiler.program_name));
+        let json = instruction_counts_to_json(&compiler.program_name, &compiler.instruction_counts);
+        std::fs::write(&path, json.to_string())
+            .map_err(|e| CliError::failed_to_write_instruction_counts(path.display(), e))?;
+    }
 
     Ok(symbol_table.structs)
 }
+
+/// Logs the operations of a compiled Leo file that can halt at runtime, grouped by function.
+fn log_halt_points(file_name: &str, halt_points: &[HaltPoint]) {
+    let mut functions: IndexMap<Symbol, Vec<&HaltPoint>> = IndexMap::new();
+    for halt_point in halt_points {
+        functions.entry(halt_point.function).or_default().push(halt_point);
+    }
+
+    for (function, halt_points) in functions.iter() {
+        tracing::info!("'{}' has {} halt point(s):", function, halt_points.len());
+        for halt_point in halt_points {
+            let location = with_session_globals(|s| s.source_map.span_to_string(halt_point.span));
+            tracing::info!("    {}:{} {}", file_name, location, halt_point);
+        }
+    }
+
+    tracing::info!(
+        "'{}' has {} halt point(s) in {} function(s)",
+        file_name,
+        halt_points.len(),
+        functions.len()
+    );
+}
+
+/// Logs the assertions of a compiled Leo file that were removed, since they are proven to always hold.
+fn log_proven_asserts(file_name: &str, proven_asserts: &[ProvenAssert]) {
+    for proven_assert in proven_asserts {
+        let location = with_session_globals(|s| s.source_map.span_to_string(proven_assert.span));
+        tracing::info!(
+            "Removed the assertion in '{}' at {}:{}, which {}",
+            proven_assert.function,
+            file_name,
+            location,
+            proven_assert
+        );
+    }
+}
+
+/// Logs the estimated number of constraints of each function of a compiled Leo file.
+fn log_cost_estimates(file_name: &str, estimates: &[CostEstimate]) {
+    for estimate in estimates {
+        let location = with_session_globals(|s| s.source_map.span_to_string(estimate.span));
+        tracing::info!(
+            "'{}' is estimated at {} constraint(s) ({}:{})",
+            estimate.function,
+            estimate.constraints,
+            file_name,
+            location
+        );
+    }
+}
+
+/// Returns the name of a function body for the instruction counts, e.g. `'main'` or `'main' (finalize)`.
+fn body_name(function: &FunctionInstructions) -> String {
+    match function.finalize {
+        true => format!("'{}' (finalize)", function.function),
+        false => format!("'{}'", function.function),
+    }
+}
+
+/// Logs the number of instructions generated by each function of a compiled Leo file, and by each of its statements.
+/// Statements that the compiler generated, e.g. to merge the branches of a conditional, have no location.
+fn log_instruction_counts(file_name: &str, functions: &[FunctionInstructions]) {
+    for function in functions {
+        tracing::info!("{} generates {} instruction(s):", body_name(function), function.total());
+        for statement in function.statements.iter().filter(|statement| statement.count > 0) {
+            let location = match statement.span == Default::default() {
+                true => "(generated)".to_string(),
+                false => format!(
+                    "{}:{}",
+                    file_name,
+                    with_session_globals(|s| s.source_map.span_to_string(statement.span))
+                ),
+            };
+            tracing::info!("    {:<32} {:>6}", location, statement.count);
+        }
+    }
+
+    tracing::info!(
+        "'{}' generates {} instruction(s) in {} function(s)",
+        file_name,
+        functions.iter().map(FunctionInstructions::total).sum::<usize>(),
+        functions.iter().filter(|function| !function.finalize).count()
+    );
+}
+
+/// Returns the instruction counts of a compiled program as JSON, with the location of each statement.
+fn instruction_counts_to_json(program_name: &str, functions: &[FunctionInstructions]) -> serde_json::Value {
+    let location = |span: leo_span::Span| {
+        with_session_globals(|s| s.source_map.span_to_location(span))
+            .filter(|_| span != Default::default())
+            .map(|location| json!({ "line": location.line_start, "column": location.col_start }))
+    };
+    let functions: Vec<_> = functions
+        .iter()
+        .map(|function| {
+            let statements: Vec<_> = function
+                .statements
+                .iter()
+                .map(|statement| json!({ "location": location(statement.span), "instructions": statement.count }))
+                .collect();
+            json!({
+                "function": function.function.to_string(),
+                "finalize": function.finalize,
+                "location": location(function.span),
+                "instructions": function.total(),
+                "statements": statements,
+            })
+        })
+        .collect();
+
+    json!({ "program": program_name, "functions": functions })
+}
+
+/// Returns the source map of a compiled program as JSON.
+/// Each instruction is identified by its line in the Aleo program, and has no location if the compiler generated it.
+fn source_map_to_json(program_name: &str, file_name: &str, source_map: &InstructionSourceMap) -> serde_json::Value {
+    let mappings: Vec<_> = source_map
+        .mappings
+        .iter()
+        .map(|mapping| {
+            let location = with_session_globals(|s| s.source_map.span_to_location(mapping.span))
+                .filter(|_| mapping.span != Default::default())
+                .map(|location| {
+                    json!({
+                        "line_start": location.line_start,
+                        "column_start": location.col_start,
+                        "line_stop": location.line_stop,
+                        "column_stop": location.col_stop,
+                    })
+                });
+            json!({
+                "line": mapping.line,
+                "function": mapping.function.to_string(),
+                "finalize": mapping.finalize,
+                "location": location,
+            })
+        })
+        .collect();
+
+    json!({ "version": 1, "program": program_name, "source": file_name, "mappings": mappings })
+}
+
+/// Returns the modification time of each file whose changes trigger a rebuild in watch mode.
+fn watched_files(package_path: &Path) -> IndexMap<PathBuf, SystemTime> {
+    let mut files = IndexMap::new();
+    collect_files(&package_path.join(SOURCE_DIRECTORY_NAME), true, Some("leo"), &mut files);
+    collect_files(
+        &package_path.join(IMPORTS_DIRECTORY_NAME),
+        false,
+        Some("leo"),
+        &mut files,
+    );
+    collect_files(&package_path.join(INPUTS_DIRECTORY_NAME), false, None, &mut files);
+    files
+}
+
+/// Records the modification time of the files in `directory` with the given extension, if any.
+fn collect_files(
+    directory: &Path,
+    recursive: bool,
+    extension: Option<&str>,
+    files: &mut IndexMap<PathBuf, SystemTime>,
+) {
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+        if path.is_dir() {
+            if recursive {
+                collect_files(&path, recursive, extension, files);
+            }
+        } else if extension.map_or(true, |extension| path.extension().map_or(false, |e| e == extension)) {
+            if let Ok(modified) = path.metadata().and_then(|metadata| metadata.modified()) {
+                files.insert(path, modified);
+            }
+        }
+    }
+}
+
+/// Returns the files that were added, removed or modified between two snapshots of the watched files.
+fn changed_files<'a>(
+    before: &'a IndexMap<PathBuf, SystemTime>,
+    after: &'a IndexMap<PathBuf, SystemTime>,
+) -> Vec<&'a PathBuf> {
+    let mut changed: Vec<&PathBuf> = after
+        .iter()
+        .filter(|(path, modified)| before.get(*path) != Some(*modified))
+        .map(|(path, _)| path)
+        .collect();
+    changed.extend(before.keys().filter(|path| !after.contains_key(*path)));
+    changed
+}
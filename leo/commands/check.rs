@@ -0,0 +1,158 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::build::BuildOptions;
+use crate::{commands::Command, context::Context};
+
+use leo_compiler::Compiler;
+use leo_errors::emitter::{Emitter, Handler};
+use leo_errors::{LeoError, LeoWarning, PackageError, Result};
+use leo_package::imports::ImportsDirectory;
+use leo_package::outputs::{DiagnosticsFile, OutputsDirectory};
+use leo_package::source::SourceDirectory;
+
+use clap::StructOpt;
+use indexmap::IndexMap;
+use tracing::span::Span;
+
+/// Check the package for errors and warnings without generating Aleo instructions.
+#[derive(StructOpt, Debug)]
+pub struct Check {
+    #[structopt(long, help = "Only report warnings that were not reported by the previous check.")]
+    pub(crate) only_new: bool,
+    #[structopt(flatten)]
+    pub(crate) compiler_options: BuildOptions,
+}
+
+impl Command for Check {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        // Get the package path.
+        let package_path = context.dir()?;
+
+        // Get the program id.
+        let manifest = context.open_manifest()?;
+        let program_id = manifest.program_id();
+
+        // Create the outputs directory.
+        let outputs_directory = OutputsDirectory::create(&package_path)?;
+
+        // Read the naming conventions and the version of Leo configured in `program.json`.
+        let mut compiler_options = self.compiler_options.clone();
+        compiler_options.naming = context.open_naming_config()?;
+        compiler_options.import_policy = context.open_import_policy()?;
+        compiler_options.language_version = context.open_language_version()?;
+
+        // Collect the diagnostics instead of printing them, so that warnings can be compared with the last check.
+        let (handler, buffer) = Handler::new_with_buf();
+
+        // Fetch paths to all .leo files in the source directory, named after the program.
+        let source_files = SourceDirectory::files(&package_path)?;
+        SourceDirectory::check_files(&source_files)?;
+        let mut files: Vec<_> = source_files
+            .into_iter()
+            .map(|file_path| (file_path, program_id.name().to_string()))
+            .collect();
+
+        // Fetch paths to all .leo files in the imports directory, named after the file.
+        if !ImportsDirectory::is_empty(&package_path)? {
+            for file_path in ImportsDirectory::files(&package_path)? {
+                let program_name = file_path
+                    .file_stem()
+                    .and_then(|name| name.to_str())
+                    .ok_or_else(PackageError::failed_to_get_file_name)?
+                    .to_string();
+                files.push((file_path, program_name));
+            }
+        }
+
+        // Check every file, even if an earlier one has errors.
+        for (file_path, program_name) in files {
+            let mut compiler = Compiler::new(
+                program_name,
+                program_id.network().to_string(),
+                &handler,
+                file_path,
+                outputs_directory.clone(),
+                Some(compiler_options.clone().into()),
+            );
+            let _ = handler.extend_if_error(compiler.check());
+        }
+
+        // Print the errors in the configured format. Errors that have already been reported only carry an exit code.
+        let mut emitter = context.error_format.emitter();
+        for error in buffer.extract_errs().into_inner() {
+            if !matches!(error, LeoError::LastErrorCode(_)) {
+                emitter.emit_err(error);
+            }
+        }
+
+        // Count the warnings reported by the last check.
+        let diagnostics_file = DiagnosticsFile::new(&program_id.name().to_string());
+        let mut previous: IndexMap<String, usize> = IndexMap::new();
+        if diagnostics_file.exists_at(&package_path) {
+            for fingerprint in diagnostics_file.read_from(&package_path)? {
+                *previous.entry(fingerprint).or_default() += 1;
+            }
+        }
+
+        // A warning is new if it was reported fewer times by the last check.
+        let warnings = buffer.extract_warnings().into_inner();
+        let fingerprints: Vec<_> = warnings.iter().map(LeoWarning::fingerprint).collect();
+        let mut new_warnings = 0;
+        let total_warnings = warnings.len();
+        for (warning, fingerprint) in warnings.into_iter().zip(fingerprints.iter()) {
+            let is_new = match previous.get_mut(fingerprint) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            };
+            if is_new {
+                new_warnings += 1;
+            }
+            if is_new || !self.only_new {
+                emitter.emit_warning(warning);
+            }
+        }
+
+        // The warnings from the last check that were not matched have been fixed.
+        let fixed_warnings: usize = previous.values().sum();
+        tracing::info!(
+            "{} new warning(s), {} pre-existing, {} fixed since the last check",
+            new_warnings,
+            total_warnings - new_warnings,
+            fixed_warnings
+        );
+
+        // Errors can hide warnings, so only a check without errors is recorded.
+        handler.last_err()?;
+        diagnostics_file.write_to(&package_path, &fingerprints)?;
+
+        Ok(())
+    }
+}
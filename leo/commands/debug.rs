@@ -0,0 +1,484 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::build::BuildOptions;
+use crate::{commands::Command, context::Context};
+
+use leo_compiler::{
+    value_to_source, Breakpoint, DebugFrontend, DebugStep, Debugger, Execution, PauseReason, StackFrame,
+};
+use leo_errors::{CliError, LeoError, Result};
+use leo_package::inputs::InputFile;
+use leo_span::{source_map::FileName, Symbol};
+
+use clap::StructOpt;
+use serde_json::{json, Value as Json};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use tracing::span::Span;
+
+/// Debug a function of the package with the reference interpreter, pausing at breakpoints and stepping through it.
+#[derive(StructOpt, Debug)]
+pub struct Debug {
+    #[structopt(name = "NAME", help = "The name of the function to debug.", default_value = "main")]
+    pub(crate) name: String,
+
+    #[structopt(
+        name = "INPUTS",
+        help = "The inputs to the function. If none are provided, the input file is used."
+    )]
+    pub(crate) inputs: Vec<String>,
+
+    #[structopt(
+        short,
+        long = "break",
+        value_name = "[FILE:]LINE",
+        help = "Pauses before the statements on LINE of FILE, or of any file. Can be given several times."
+    )]
+    pub(crate) breakpoints: Vec<String>,
+
+    #[structopt(
+        long,
+        value_name = "PORT",
+        help = "Serves the Debug Adapter Protocol to an editor on PORT of localhost, instead of prompting for commands."
+    )]
+    pub(crate) dap: Option<u16>,
+
+    #[structopt(flatten)]
+    pub(crate) compiler_options: BuildOptions,
+}
+
+impl Command for Debug {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Debugging")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let handler = context.handler();
+        let mut compiler = context.open_compiler(&handler, self.compiler_options.clone())?;
+        compiler.check()?;
+        compiler.parse_input(InputFile::new(&compiler.program_name).find_file_path(&context.dir()?))?;
+        // The input file is only used, and so only checked, if no inputs are given.
+        if self.inputs.is_empty() {
+            compiler.check_input()?;
+        }
+
+        let breakpoints = self
+            .breakpoints
+            .iter()
+            .map(|breakpoint| {
+                Breakpoint::parse(breakpoint).ok_or_else(|| CliError::invalid_breakpoint(breakpoint).into())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let function = Symbol::intern(&self.name);
+
+        match self.dap {
+            // The editor sets its breakpoints and starts the run once it is configured.
+            Some(port) => {
+                let mut server = DapServer::accept(port, breakpoints)?;
+                if !server.configure()? {
+                    return Ok(());
+                }
+                let breakpoints = std::mem::take(&mut server.breakpoints);
+                let stop_on_entry = server.stop_on_entry;
+                let execution = compiler.debug(
+                    function,
+                    &self.inputs,
+                    Debugger::new(&mut server, breakpoints, stop_on_entry),
+                );
+                server.finish(&execution)
+            }
+            // Without breakpoints, the prompt starts before the first statement.
+            None => {
+                let stop_on_entry = breakpoints.is_empty();
+                let execution = compiler.debug(
+                    function,
+                    &self.inputs,
+                    Debugger::new(&mut Prompt, breakpoints, stop_on_entry),
+                )?;
+                tracing::info!("'{}' returned {}", self.name, value_to_source(&execution.output));
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Returns the function and the location of the statement that `frame` is evaluating, e.g. `main at src/main.leo:3`.
+fn describe(frame: &StackFrame) -> String {
+    match frame.location() {
+        Some(location) => format!(
+            "{} at {}:{}",
+            frame.function, location.source_file.name, location.line_start
+        ),
+        None => frame.function.to_string(),
+    }
+}
+
+/// A debugger frontend that asks for commands at a prompt on the standard input.
+struct Prompt;
+
+/// The commands of the prompt.
+const PROMPT_HELP: &str = "\
+continue, c          Run until the next breakpoint
+step, s              Step to the next statement, into the functions it calls
+next, n              Step to the next statement of this function
+finish, f            Step out of this function
+break, b [FILE:]LINE Pause before the statements on LINE
+delete, d [FILE:]LINE Remove the breakpoint on LINE
+print, p [NAME]      Print the variable NAME, or every variable in scope
+backtrace, bt        Print the calls that are being evaluated
+quit, q              Stop the run";
+
+impl DebugFrontend for Prompt {
+    fn pause(
+        &mut self,
+        reason: PauseReason,
+        stack: &[StackFrame],
+        breakpoints: &mut Vec<Breakpoint>,
+    ) -> Result<DebugStep> {
+        // Note that this unwrap is safe since the interpreter only pauses while it evaluates a call.
+        let frame = stack.last().unwrap();
+        let reason = match reason {
+            PauseReason::Entry => "Paused",
+            PauseReason::Breakpoint => "Breakpoint",
+            PauseReason::Step => "Stepped",
+        };
+        println!("{} in {}", reason, describe(frame));
+        if let Some(location) = frame.location() {
+            let line = location.line_start.checked_sub(1);
+            if let Some(source) = line.and_then(|line| location.source_file.src.lines().nth(line)) {
+                println!("{:>5} | {}", location.line_start, source.trim_end());
+            }
+        }
+
+        let mut lines = io::stdin().lock().lines();
+        loop {
+            print!("(debug) ");
+            io::stdout().flush().map_err(CliError::cli_io_error)?;
+
+            // Stop the run at the end of the input, e.g. when Ctrl-D is pressed.
+            let line = match lines.next() {
+                Some(line) => line.map_err(CliError::cli_io_error)?,
+                None => return Ok(DebugStep::Stop),
+            };
+            let (command, argument) = match line.trim().split_once(' ') {
+                Some((command, argument)) => (command, argument.trim()),
+                None => (line.trim(), ""),
+            };
+
+            match command {
+                "continue" | "c" => return Ok(DebugStep::Continue),
+                "step" | "s" => return Ok(DebugStep::StepIn),
+                "next" | "n" => return Ok(DebugStep::Next),
+                "finish" | "f" => return Ok(DebugStep::StepOut),
+                "quit" | "q" => return Ok(DebugStep::Stop),
+                "break" | "b" => match Breakpoint::parse(argument) {
+                    Some(breakpoint) => {
+                        println!("Breakpoint on line {}", breakpoint.line);
+                        breakpoints.push(breakpoint);
+                    }
+                    None => println!("Expected a breakpoint like `main.leo:3` or `3`."),
+                },
+                "delete" | "d" => match Breakpoint::parse(argument) {
+                    Some(breakpoint) => breakpoints.retain(|b| *b != breakpoint),
+                    None => println!("Expected a breakpoint like `main.leo:3` or `3`."),
+                },
+                "print" | "p" if argument.is_empty() => {
+                    for (name, value) in frame.variables.iter() {
+                        println!("{} = {}", name, value_to_source(value));
+                    }
+                }
+                "print" | "p" => match frame.variables.get(&Symbol::intern(argument)) {
+                    Some(value) => println!("{} = {}", argument, value_to_source(value)),
+                    None => println!("There is no variable `{}` in scope.", argument),
+                },
+                "backtrace" | "bt" => {
+                    for (i, frame) in stack.iter().rev().enumerate() {
+                        println!("#{} {}", i, describe(frame));
+                    }
+                }
+                "help" | "h" => println!("{}", PROMPT_HELP),
+                "" => {}
+                command => println!("Unknown command `{}`. Type `help` for the list of commands.", command),
+            }
+        }
+    }
+}
+
+/// A debugger frontend that serves the Debug Adapter Protocol to an editor over TCP.
+/// The interpreter runs on a single thread, which the protocol calls `main`.
+struct DapServer {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    /// The sequence number of the last message that was sent.
+    seq: u64,
+    /// The breakpoints that the editor sets before the run starts.
+    breakpoints: Vec<Breakpoint>,
+    /// Whether the editor asked to pause before the first statement.
+    stop_on_entry: bool,
+}
+
+impl DapServer {
+    /// Waits for an editor to connect on `port` of localhost.
+    fn accept(port: u16, breakpoints: Vec<Breakpoint>) -> Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port)).map_err(CliError::debug_adapter_failed)?;
+        tracing::info!("Waiting for an editor to connect on port {}", port);
+        let (stream, _) = listener.accept().map_err(CliError::debug_adapter_failed)?;
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone().map_err(CliError::debug_adapter_failed)?),
+            writer: stream,
+            seq: 0,
+            breakpoints,
+            stop_on_entry: false,
+        })
+    }
+
+    /// Answers the requests of the editor until it is done configuring the run,
+    /// returning `false` if it disconnects instead.
+    fn configure(&mut self) -> Result<bool> {
+        let mut breakpoints = std::mem::take(&mut self.breakpoints);
+        let step = self.serve(&[], &mut breakpoints)?;
+        self.breakpoints = breakpoints;
+        Ok(step != DebugStep::Stop)
+    }
+
+    /// Tells the editor how the run ended, and answers its requests until it disconnects.
+    fn finish(&mut self, execution: &Result<Execution>) -> Result<()> {
+        let output = match execution {
+            Ok(execution) => format!("Returned {}\n", value_to_source(&execution.output)),
+            // Errors that have already been reported only carry an exit code.
+            Err(LeoError::LastErrorCode(_)) => "The run failed.\n".to_string(),
+            Err(error) => format!("{}\n", error),
+        };
+        self.event("output", json!({ "category": "console", "output": output }))?;
+        self.event("terminated", json!({}))?;
+        let exit_code = match execution {
+            Ok(_) => 0,
+            Err(_) => 1,
+        };
+        self.event("exited", json!({ "exitCode": exit_code }))?;
+        while self.serve(&[], &mut Vec::new())? != DebugStep::Stop {}
+        Ok(())
+    }
+
+    /// Answers the requests of the editor about `stack` until it asks the run to start or to resume.
+    fn serve(&mut self, stack: &[StackFrame], breakpoints: &mut Vec<Breakpoint>) -> Result<DebugStep> {
+        loop {
+            // A closed connection stops the run.
+            let request = match self.read()? {
+                Some(request) => request,
+                None => return Ok(DebugStep::Stop),
+            };
+            let arguments = &request["arguments"];
+            let (body, step) = match request["command"].as_str().unwrap_or_default() {
+                "initialize" => {
+                    self.respond(&request, json!({ "supportsConfigurationDoneRequest": true }))?;
+                    self.event("initialized", json!({}))?;
+                    continue;
+                }
+                "launch" | "attach" => {
+                    self.stop_on_entry = arguments["stopOnEntry"].as_bool().unwrap_or(false);
+                    (json!({}), None)
+                }
+                "setBreakpoints" => (set_breakpoints(arguments, breakpoints), None),
+                "configurationDone" => (json!({}), Some(DebugStep::Continue)),
+                "threads" => (json!({ "threads": [{ "id": 1, "name": "main" }] }), None),
+                "stackTrace" => (stack_trace(stack), None),
+                // Each call has a single scope, whose reference is one more than the index of the call.
+                "scopes" => {
+                    let frame = arguments["frameId"].as_u64().unwrap_or_default();
+                    let scope = json!({ "name": "Locals", "variablesReference": frame + 1, "expensive": false });
+                    (json!({ "scopes": [scope] }), None)
+                }
+                "variables" => {
+                    let reference = arguments["variablesReference"].as_u64().unwrap_or_default() as usize;
+                    let variables: Vec<_> = reference
+                        .checked_sub(1)
+                        .and_then(|frame| stack.get(frame))
+                        .map(|frame| {
+                            frame
+                                .variables
+                                .iter()
+                                .map(|(name, value)| {
+                                    json!({
+                                        "name": name.to_string(),
+                                        "value": value_to_source(value),
+                                        "variablesReference": 0,
+                                    })
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    (json!({ "variables": variables }), None)
+                }
+                "continue" => (json!({ "allThreadsContinued": true }), Some(DebugStep::Continue)),
+                "next" => (json!({}), Some(DebugStep::Next)),
+                "stepIn" => (json!({}), Some(DebugStep::StepIn)),
+                "stepOut" => (json!({}), Some(DebugStep::StepOut)),
+                "disconnect" | "terminate" => (json!({}), Some(DebugStep::Stop)),
+                command => {
+                    let message = format!("Unsupported request `{}`.", command);
+                    self.send(json!({
+                        "type": "response",
+                        "request_seq": request["seq"],
+                        "command": command,
+                        "success": false,
+                        "message": message,
+                    }))?;
+                    continue;
+                }
+            };
+            self.respond(&request, body)?;
+            if let Some(step) = step {
+                return Ok(step);
+            }
+        }
+    }
+
+    /// Reads the next message from the editor, or `None` if it has closed the connection.
+    fn read(&mut self) -> Result<Option<Json>> {
+        // Each message has a header with its length, followed by an empty line and the JSON content.
+        let mut length = None;
+        loop {
+            let mut line = String::new();
+            if self
+                .reader
+                .read_line(&mut line)
+                .map_err(CliError::debug_adapter_failed)?
+                == 0
+            {
+                return Ok(None);
+            }
+            match line.trim_end() {
+                "" => break,
+                header => {
+                    if let Some(value) = header.strip_prefix("Content-Length:") {
+                        length = value.trim().parse().ok();
+                    }
+                }
+            }
+        }
+        let length =
+            length.ok_or_else(|| CliError::debug_adapter_failed("a message has no `Content-Length` header"))?;
+        let mut content = vec![0; length];
+        self.reader
+            .read_exact(&mut content)
+            .map_err(CliError::debug_adapter_failed)?;
+        serde_json::from_slice(&content)
+            .map(Some)
+            .map_err(|error| CliError::debug_adapter_failed(error).into())
+    }
+
+    /// Sends `message` to the editor, numbering it after the last one.
+    fn send(&mut self, mut message: Json) -> Result<()> {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+        let content = message.to_string();
+        write!(self.writer, "Content-Length: {}\r\n\r\n{}", content.len(), content)
+            .and_then(|_| self.writer.flush())
+            .map_err(|error| CliError::debug_adapter_failed(error).into())
+    }
+
+    /// Answers `request` successfully with `body`.
+    fn respond(&mut self, request: &Json, body: Json) -> Result<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": true,
+            "body": body,
+        }))
+    }
+
+    /// Sends the event `event` with `body`.
+    fn event(&mut self, event: &str, body: Json) -> Result<()> {
+        self.send(json!({ "type": "event", "event": event, "body": body }))
+    }
+}
+
+impl DebugFrontend for DapServer {
+    fn pause(
+        &mut self,
+        reason: PauseReason,
+        stack: &[StackFrame],
+        breakpoints: &mut Vec<Breakpoint>,
+    ) -> Result<DebugStep> {
+        let reason = match reason {
+            PauseReason::Entry => "entry",
+            PauseReason::Breakpoint => "breakpoint",
+            PauseReason::Step => "step",
+        };
+        self.event(
+            "stopped",
+            json!({ "reason": reason, "threadId": 1, "allThreadsStopped": true }),
+        )?;
+        self.serve(stack, breakpoints)
+    }
+}
+
+/// Replaces the breakpoints of the source file of a `setBreakpoints` request, returning the body of its response.
+fn set_breakpoints(arguments: &Json, breakpoints: &mut Vec<Breakpoint>) -> Json {
+    let file = arguments["source"]["path"].as_str().map(Into::into);
+    breakpoints.retain(|breakpoint| breakpoint.file != file);
+    let lines: Vec<_> = arguments["breakpoints"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|breakpoint| breakpoint["line"].as_u64())
+        .collect();
+    breakpoints.extend(lines.iter().map(|line| Breakpoint {
+        file: file.clone(),
+        line: *line as usize,
+    }));
+    let verified: Vec<_> = lines
+        .iter()
+        .map(|line| json!({ "verified": true, "line": line }))
+        .collect();
+    json!({ "breakpoints": verified })
+}
+
+/// Returns the body of the response to a `stackTrace` request, with the innermost call first.
+/// Each call is identified by its index in `stack`.
+fn stack_trace(stack: &[StackFrame]) -> Json {
+    let frames: Vec<_> = stack
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(id, frame)| {
+            let location = frame.location();
+            let source = match location.as_ref().map(|location| &location.source_file.name) {
+                Some(FileName::Real(path)) => json!({ "path": path.canonicalize().unwrap_or_else(|_| path.clone()) }),
+                Some(FileName::Custom(name)) => json!({ "name": name }),
+                None => Json::Null,
+            };
+            json!({
+                "id": id,
+                "name": frame.function.to_string(),
+                "source": source,
+                "line": location.as_ref().map_or(0, |location| location.line_start),
+                "column": location.as_ref().map_or(0, |location| location.col_start),
+            })
+        })
+        .collect();
+    json!({ "stackFrames": frames, "totalFrames": stack.len() })
+}
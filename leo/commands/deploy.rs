@@ -14,19 +14,83 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::commands::ALEO_CLI_COMMAND;
-use crate::{commands::Command, context::Context};
-use leo_errors::{CliError, PackageError, Result};
-use leo_package::build::BuildDirectory;
+use super::{build::BuildOptions, Network};
+use crate::{commands::Command, context::Context, scripts::Hook, signer::PrivateKeyOptions};
+use leo_compiler::Compiler;
+use leo_errors::{emitter::Handler, CliError, PackageError, Result};
+use leo_package::{
+    build::BuildDirectory,
+    outputs::{MAIN_ALEO_FILE_NAME, OUTPUTS_DIRECTORY_NAME},
+    source::SourceDirectory,
+};
 
-use aleo::commands::Deploy as AleoDeploy;
+use snarkvm::circuit::AleoV0;
+use snarkvm::package::Package;
+use snarkvm::prelude::{Address, Plaintext, Record, Transaction};
 
 use clap::StructOpt;
+use rand::thread_rng;
+use std::{fmt, str::FromStr};
 use tracing::span::Span;
 
+// The rates below only approximate the fee that a node charges, which may differ:
+// the snarkVM that Leo is built against does not price deployments, so they cannot be derived from it.
+
+/// The fee per byte of the deployed program, in microcredits.
+const STORAGE_FEE_PER_BYTE: u64 = 1_000;
+
+/// The fee per constraint of the circuits synthesized for the functions of the program, in microcredits.
+const SYNTHESIS_FEE_PER_CONSTRAINT: u64 = 25;
+
+/// The names shorter than this many characters pay a namespace fee, which grows tenfold with each character less.
+const NAMESPACE_LENGTH: u32 = 10;
+
+/// The number of microcredits in a credit.
+const MICROCREDITS_PER_CREDIT: u64 = 1_000_000;
+
 /// Deploys an Aleo program.
 #[derive(StructOpt, Debug)]
-pub struct Deploy;
+pub struct Deploy {
+    #[structopt(flatten)]
+    key: PrivateKeyOptions,
+
+    #[structopt(
+        long,
+        value_name = "URL",
+        env = "LEO_ENDPOINT",
+        help = "The REST endpoint of the Aleo node to broadcast the deployment to, instead of the one of the network profile."
+    )]
+    endpoint: Option<String>,
+
+    #[structopt(
+        long,
+        value_name = "PROFILE",
+        help = "The network profile to use: `devnet`, `testnet` or one under `networks` in `program.json`. Defaults to `LEO_NETWORK`, or else `testnet`."
+    )]
+    network: Option<String>,
+
+    #[structopt(
+        long,
+        value_name = "MICROCREDITS",
+        required_unless_present = "dry-run",
+        help = "The fee paid for the deployment, in microcredits. Run with `--dry-run` for a rough estimate of it."
+    )]
+    fee: Option<u64>,
+
+    #[structopt(
+        long,
+        value_name = "RECORD",
+        required_unless_present = "dry-run",
+        help = "The credits record that pays the deployment fee."
+    )]
+    fee_record: Option<String>,
+
+    #[structopt(
+        long,
+        help = "Prints a rough estimate of the deployment fee without deploying the program."
+    )]
+    dry_run: bool,
+}
 
 impl Command for Deploy {
     type Input = ();
@@ -45,18 +109,153 @@ impl Command for Deploy {
         let path = context.dir()?;
         let build_directory = BuildDirectory::open(&path).map_err(|_| CliError::needs_leo_build())?;
 
-        // Change the cwd to the Leo build/ directory to deploy aleo files.
-        std::env::set_current_dir(&build_directory)
-            .map_err(|err| PackageError::failed_to_set_cwd(build_directory.display(), err))?;
+        // Roughly estimate the fee from the size of the built program and the constraints of its functions.
+        // The estimate is only printed, since a deployment pays the fee given with `--fee` rather than an estimate.
+        if self.dry_run {
+            let program_path = build_directory.join(MAIN_ALEO_FILE_NAME);
+            let program = std::fs::read(&program_path)
+                .map_err(|err| PackageError::failed_to_read_file(program_path.display(), err))?;
+            let manifest = context.open_manifest()?;
+            let program_name = manifest.program_id().name().to_string();
+            let fee = DeploymentFee::estimate(&program_name, program.len() as u64, estimate_constraints(&context)?);
+            tracing::info!("Rough estimate of the deployment fee: {}", fee);
+            return Ok(());
+        }
 
-        // Call the `aleo node` command from the Aleo SDK.
-        println!();
-        let command = AleoDeploy::try_parse_from([ALEO_CLI_COMMAND]).map_err(CliError::failed_to_parse_aleo_node)?;
-        let res = command.parse().map_err(CliError::failed_to_execute_aleo_node)?;
+        // Run the scripts declared to run before deploying.
+        let scripts = context.open_scripts()?;
+        scripts.run(Hook::PreDeploy)?;
 
-        // Log the output of the `aleo node` command.
-        tracing::info!("{}", res);
+        // snarkVM signs the fee with the private key of the deploying account, so it cannot come from an external signer.
+        let profile = context.open_profile(self.network.as_deref())?;
+        let private_key = self
+            .key
+            .open_in(&profile)?
+            .ok_or_else(CliError::deployment_needs_private_key)?;
+        let address = Address::try_from(&private_key).map_err(CliError::failed_to_sign)?;
+        tracing::info!("Deploying as {}", address);
 
-        Ok(())
+        // Synthesize the keys of the functions into a deployment.
+        let package = Package::<Network>::open(&build_directory).map_err(CliError::failed_to_deploy)?;
+        let deployment = package.deploy::<AleoV0>(None).map_err(CliError::failed_to_deploy)?;
+
+        // Prove the fee, which is paid from a credits record of the account, and assemble the transaction.
+        // Note that these unwraps are safe since the fee and its record are required unless the deployment is a dry run.
+        let record = Record::<Network, Plaintext<Network>>::from_str(self.fee_record.as_ref().unwrap())
+            .map_err(CliError::failed_to_deploy)?;
+        let process = package.get_process().map_err(CliError::failed_to_deploy)?;
+        let (_, fee) = process
+            .execute_additional_fee::<AleoV0, _>(&private_key, record, self.fee.unwrap(), &mut thread_rng())
+            .map_err(CliError::failed_to_deploy)?;
+        let transaction = Transaction::from_deployment(deployment, fee).map_err(CliError::failed_to_deploy)?;
+
+        // Broadcast the transaction to the node.
+        let endpoint = self.endpoint.unwrap_or(profile.endpoint);
+        let endpoint = format!(
+            "{}/{}/transaction/broadcast",
+            endpoint.trim_end_matches('/'),
+            profile.network
+        );
+        reqwest::blocking::Client::new()
+            .post(&endpoint)
+            .header("Content-Type", "application/json")
+            .body(transaction.to_string())
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| CliError::failed_to_broadcast(&endpoint, err))?;
+        tracing::info!(
+            "Deployed '{}' in transaction {} to {}",
+            package.program_id(),
+            transaction.id(),
+            endpoint
+        );
+
+        // Run the scripts declared to run after deploying.
+        scripts.run(Hook::PostDeploy)
+    }
+}
+
+/// The fee to deploy a program, in microcredits, as roughly estimated by Leo rather than charged by a node.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DeploymentFee {
+    /// The fee for storing the program.
+    pub storage: u64,
+    /// The fee for synthesizing the circuits of its functions.
+    pub synthesis: u64,
+    /// The fee for claiming a short program name.
+    pub namespace: u64,
+}
+
+impl DeploymentFee {
+    /// Estimates the fee to deploy the program `name`, which is `size` bytes long
+    /// and whose functions have `constraints` constraints in total.
+    pub fn estimate(name: &str, size: u64, constraints: u64) -> Self {
+        let length = name.chars().count() as u32;
+        let namespace = match length < NAMESPACE_LENGTH {
+            true => 10u64.pow(NAMESPACE_LENGTH - length) * MICROCREDITS_PER_CREDIT,
+            false => 0,
+        };
+        Self {
+            storage: size * STORAGE_FEE_PER_BYTE,
+            synthesis: constraints * SYNTHESIS_FEE_PER_CONSTRAINT,
+            namespace,
+        }
+    }
+
+    /// Returns the total fee.
+    pub fn total(&self) -> u64 {
+        self.storage + self.synthesis + self.namespace
+    }
+}
+
+impl fmt::Display for DeploymentFee {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let credits = |microcredits: u64| microcredits as f64 / MICROCREDITS_PER_CREDIT as f64;
+        write!(
+            f,
+            "~{:.6} credits (storage {:.6}, synthesis {:.6}, namespace {:.6})",
+            credits(self.total()),
+            credits(self.storage),
+            credits(self.synthesis),
+            credits(self.namespace)
+        )
+    }
+}
+
+/// Returns the number of constraints of the functions of the package, as estimated by the compiler.
+fn estimate_constraints(context: &Context) -> Result<u64> {
+    let package_path = context.dir()?;
+    let manifest = context.open_manifest()?;
+    let program_id = manifest.program_id();
+    // The program is only checked, which writes no outputs, so the outputs directory is not created.
+    let outputs_directory = package_path.join(OUTPUTS_DIRECTORY_NAME);
+
+    let mut options = BuildOptions {
+        estimate_costs: true,
+        ..Default::default()
+    };
+    options.import_policy = context.open_import_policy()?;
+    options.language_version = context.open_language_version()?;
+
+    // The program was already checked when it was built, so its diagnostics are not reported again.
+    let (handler, _) = Handler::new_with_buf();
+    let mut constraints = 0;
+    for file_path in SourceDirectory::files(&package_path)? {
+        let mut compiler = Compiler::new(
+            program_id.name().to_string(),
+            program_id.network().to_string(),
+            &handler,
+            file_path,
+            outputs_directory.clone(),
+            Some(options.clone().into()),
+        );
+        let symbol_table = compiler.check()?;
+        compiler.cost_estimation_pass(&symbol_table);
+        constraints += compiler
+            .cost_estimates
+            .iter()
+            .map(|estimate| estimate.constraints)
+            .sum::<u64>();
     }
+    Ok(constraints)
 }
@@ -0,0 +1,246 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{build::BuildOptions, Network};
+use crate::{
+    commands::{Build, Command},
+    context::Context,
+    prover::{HttpProver, Prover, ProvingRequest},
+    signer::PrivateKeyOptions,
+};
+use leo_errors::{CliError, PackageError, Result};
+use leo_package::{build::BuildDirectory, outputs::OutputsDirectory};
+
+use snarkvm::circuit::AleoV0;
+use snarkvm::package::Package;
+use snarkvm::prelude::{Address, Execution, Identifier, Plaintext, Record, Request, Transaction, Value};
+
+use clap::StructOpt;
+use rand::thread_rng;
+use std::str::FromStr;
+use tracing::span::Span;
+
+/// Builds the program, proves the execution of one of its transitions
+/// and broadcasts it to the Aleo network as a transaction.
+#[derive(StructOpt, Debug)]
+pub struct Execute {
+    #[structopt(
+        name = "NAME",
+        help = "The name of the transition to execute.",
+        default_value = "main"
+    )]
+    name: String,
+
+    #[structopt(
+        name = "INPUTS",
+        help = "The inputs to the transition. If none are provided, the input file is used."
+    )]
+    inputs: Vec<String>,
+
+    #[structopt(flatten)]
+    key: PrivateKeyOptions,
+
+    #[structopt(
+        long,
+        value_name = "URL",
+        help = "Offloads the proof of the execution, which is authorized locally, to the proving service at URL, instead of the one of the network profile, if any."
+    )]
+    prover: Option<String>,
+
+    #[structopt(
+        long,
+        value_name = "MICROCREDITS",
+        default_value = "0",
+        requires = "fee-record",
+        help = "The fee paid for the transaction on top of its execution, in microcredits."
+    )]
+    fee: u64,
+
+    #[structopt(long, value_name = "RECORD", help = "The credits record that pays the fee.")]
+    fee_record: Option<String>,
+
+    #[structopt(
+        long,
+        value_name = "URL",
+        env = "LEO_ENDPOINT",
+        help = "The REST endpoint of the Aleo node to broadcast the transaction to, instead of the one of the network profile."
+    )]
+    endpoint: Option<String>,
+
+    #[structopt(
+        long,
+        value_name = "PROFILE",
+        help = "The network profile to use: `devnet`, `testnet` or one under `networks` in `program.json`. Defaults to `LEO_NETWORK`, or else `testnet`."
+    )]
+    network: Option<String>,
+
+    #[structopt(
+        long,
+        help = "Only writes the transaction to `outputs/NAME.transaction.json`, without broadcasting it."
+    )]
+    local: bool,
+
+    #[structopt(flatten)]
+    pub(crate) compiler_options: BuildOptions,
+}
+
+impl Command for Execute {
+    type Input = <Build as Command>::Output;
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Executing")
+    }
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        (Build {
+            compiler_options: self.compiler_options.clone(),
+            watch: false,
+            workspace: false,
+        })
+        .execute(context)
+    }
+
+    fn apply(self, context: Context, input: Self::Input) -> Result<Self::Output> {
+        // If input values are provided, then execute the transition with those inputs.
+        // Otherwise, use the input file.
+        let inputs = match self.inputs.is_empty() {
+            true => match input {
+                (Some(input_ast), circuits) => input_ast.program_inputs(&self.name, circuits)?,
+                _ => Vec::new(),
+            },
+            false => self.inputs.clone(),
+        };
+
+        // snarkVM signs the transition and the fee with the private key of the executing account,
+        // so it cannot come from an external signer.
+        let profile = context.open_profile(self.network.as_deref())?;
+        let private_key = self
+            .key
+            .open_in(&profile)?
+            .ok_or_else(CliError::execution_needs_private_key)?;
+        let address = Address::try_from(&private_key).map_err(CliError::failed_to_sign)?;
+        tracing::info!("Executing as {}", address);
+
+        // Open the Leo build/ directory, which is the Aleo package of the program.
+        let path = context.dir()?;
+        let build_directory = BuildDirectory::open(&path).map_err(|_| CliError::needs_leo_build())?;
+        let package = Package::<Network>::open(&build_directory).map_err(CliError::failed_to_execute)?;
+        let process = package.get_process().map_err(CliError::failed_to_execute)?;
+        let rng = &mut thread_rng();
+
+        // Authorize the transition with the private key, so that the caller is bound to the inputs by their signature.
+        let function = Identifier::<Network>::from_str(&self.name).map_err(CliError::failed_to_execute)?;
+        let inputs = inputs
+            .iter()
+            .map(|input| Value::<Network>::from_str(input))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(CliError::failed_to_execute)?;
+        let authorization = process
+            .authorize::<AleoV0, _>(&private_key, package.program_id(), function, &inputs, rng)
+            .map_err(CliError::failed_to_execute)?;
+
+        // Execute the transition and prove the execution, locally or with a proving service.
+        let execution = match self.prover.as_ref().or(profile.prover.as_ref()) {
+            Some(url) => {
+                let requests = Vec::from(authorization.to_vec_deque());
+                prove_authorized(
+                    &HttpProver::new(url.clone()),
+                    url,
+                    package.program_id().to_string(),
+                    self.name.clone(),
+                    &requests,
+                )?
+            }
+            None => {
+                let (response, execution) = process
+                    .execute::<AleoV0, _>(authorization, rng)
+                    .map_err(CliError::failed_to_execute)?;
+                for output in response.outputs().iter() {
+                    tracing::info!("• {}", output);
+                }
+                execution
+            }
+        };
+
+        // Prove the fee, which is paid from a credits record of the account, and assemble the transaction.
+        let fee = match &self.fee_record {
+            Some(record) => {
+                let record =
+                    Record::<Network, Plaintext<Network>>::from_str(record).map_err(CliError::failed_to_execute)?;
+                let (_, fee) = process
+                    .execute_additional_fee::<AleoV0, _>(&private_key, record, self.fee, rng)
+                    .map_err(CliError::failed_to_execute)?;
+                Some(fee)
+            }
+            None => None,
+        };
+        let transaction = Transaction::from_execution(execution, fee).map_err(CliError::failed_to_execute)?;
+
+        // Write the transaction to the outputs/ directory, so that it can be inspected or broadcast later.
+        let outputs_directory = OutputsDirectory::create(&path)?;
+        let transaction_path = outputs_directory.join(format!("{}.transaction.json", self.name));
+        std::fs::write(&transaction_path, transaction.to_string())
+            .map_err(|err| PackageError::failed_to_write_file(transaction_path.display(), err))?;
+        tracing::info!(
+            "Wrote transaction {} to {}",
+            transaction.id(),
+            transaction_path.display()
+        );
+
+        if self.local {
+            return Ok(());
+        }
+
+        // Broadcast the transaction to the node.
+        let endpoint = self.endpoint.unwrap_or(profile.endpoint);
+        let endpoint = format!(
+            "{}/{}/transaction/broadcast",
+            endpoint.trim_end_matches('/'),
+            profile.network
+        );
+        reqwest::blocking::Client::new()
+            .post(&endpoint)
+            .header("Content-Type", "application/json")
+            .body(transaction.to_string())
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| CliError::failed_to_broadcast(&endpoint, err))?;
+        tracing::info!("Broadcast transaction {} to {}", transaction.id(), endpoint);
+
+        Ok(())
+    }
+}
+
+/// Proves the execution of `function` of the deployed program `program_id` with `prover`, which is named `prover_name`,
+/// sending it only the `requests` of the authorization that the caller signed.
+pub(crate) fn prove_authorized(
+    prover: &dyn Prover,
+    prover_name: &str,
+    program_id: String,
+    function: String,
+    requests: &[Request<Network>],
+) -> Result<Execution<Network>> {
+    let request = ProvingRequest::authorized(program_id, function, requests);
+    let response = prover.prove(&request)?;
+    for output in response.outputs.iter() {
+        tracing::info!("• {}", output);
+    }
+    let execution = response
+        .execution
+        .ok_or_else(|| CliError::prover_returned_no_execution(prover_name))?;
+    Execution::<Network>::from_str(&execution).map_err(|err| CliError::failed_to_execute(err).into())
+}
@@ -0,0 +1,76 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+
+use leo_errors::{PackageError, Result, Suggestion};
+use leo_package::imports::ImportsDirectory;
+use leo_package::source::SourceDirectory;
+use leo_span::span::BytePos;
+
+use clap::StructOpt;
+use colored::Colorize;
+use std::fs;
+use tracing::span::Span;
+
+/// Apply automatic style fixes to the package's source files.
+#[derive(StructOpt, Debug)]
+pub struct Fix {}
+
+impl Command for Fix {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Fixing")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        // Get the package path.
+        let package_path = context.dir()?;
+
+        // Fetch paths to all .leo files in the source and imports directories.
+        let mut files = SourceDirectory::files(&package_path)?;
+        if !ImportsDirectory::is_empty(&package_path)? {
+            files.extend(ImportsDirectory::files(&package_path)?);
+        }
+
+        for file_path in files {
+            let source = fs::read_to_string(&file_path)
+                .map_err(|err| PackageError::failed_to_read_file(file_path.display(), err))?;
+
+            // Normalize the literals of the file.
+            let fixes = leo_parser::literal_fixes(&source, BytePos(0))?;
+            if fixes.is_empty() {
+                continue;
+            }
+            let fixed = Suggestion::apply_all(&source, BytePos(0), &fixes);
+            fs::write(&file_path, fixed).map_err(|err| PackageError::failed_to_write_file(file_path.display(), err))?;
+
+            tracing::info!(
+                "applied {} fix(es) to {}",
+                fixes.len(),
+                file_path.display().to_string().dimmed()
+            );
+        }
+
+        Ok(())
+    }
+}
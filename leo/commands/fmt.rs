@@ -0,0 +1,87 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+
+use leo_errors::{CliError, PackageError, Result};
+use leo_package::imports::ImportsDirectory;
+use leo_package::source::SourceDirectory;
+use leo_span::{source_map::FileName, symbol::with_session_globals};
+
+use clap::StructOpt;
+use colored::Colorize;
+use std::fs;
+use tracing::span::Span;
+
+/// Format the package's source files.
+#[derive(StructOpt, Debug)]
+pub struct Fmt {
+    #[structopt(long, help = "Check that the files are formatted, without writing them.")]
+    pub check: bool,
+}
+
+impl Command for Fmt {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Formatting")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        // Get the package path.
+        let package_path = context.dir()?;
+        let handler = context.handler();
+        let config = context.open_format_config()?;
+
+        // Fetch paths to all .leo files in the source and imports directories.
+        let mut files = SourceDirectory::files(&package_path)?;
+        if !ImportsDirectory::is_empty(&package_path)? {
+            files.extend(ImportsDirectory::files(&package_path)?);
+        }
+
+        let mut unformatted = 0;
+        for file_path in files {
+            let source = fs::read_to_string(&file_path)
+                .map_err(|err| PackageError::failed_to_read_file(file_path.display(), err))?;
+
+            // Register the source in the source map, so that syntax errors point into it.
+            let file = with_session_globals(|s| s.source_map.new_source(&source, FileName::Real(file_path.clone())));
+            let formatted = leo_fmt::format_with_config(&handler, &file.src, file.start_pos, &config)?;
+            if formatted == source {
+                continue;
+            }
+
+            if self.check {
+                unformatted += 1;
+                tracing::info!("{} is not formatted", file_path.display().to_string().dimmed());
+            } else {
+                fs::write(&file_path, formatted)
+                    .map_err(|err| PackageError::failed_to_write_file(file_path.display(), err))?;
+                tracing::info!("formatted {}", file_path.display().to_string().dimmed());
+            }
+        }
+
+        match unformatted {
+            0 => Ok(()),
+            _ => Err(CliError::files_not_formatted(unformatted).into()),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+use leo_errors::{PackageError, Result};
+use leo_parser::LEO_GRAMMAR;
+
+use clap::StructOpt;
+use colored::Colorize;
+use std::path::PathBuf;
+use tracing::span::Span;
+
+/// Commands to inspect the grammar of the Leo language.
+#[derive(StructOpt, Debug)]
+pub enum Grammar {
+    /// Prints the grammar of Leo in ABNF
+    Dump {
+        /// Writes the grammar to the given file instead of printing it.
+        #[structopt(long, parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
+}
+
+impl Command for Grammar {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, _: Context, _: Self::Input) -> Result<Self::Output> {
+        match self {
+            Grammar::Dump { output: Some(path) } => {
+                std::fs::write(&path, LEO_GRAMMAR)
+                    .map_err(|err| PackageError::failed_to_write_file(path.display(), err))?;
+                tracing::info!("wrote the grammar to {}", path.display().to_string().dimmed());
+            }
+            Grammar::Dump { output: None } => print!("{LEO_GRAMMAR}"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{build::BuildOptions, state::StateOptions};
+use crate::{commands::Command, context::Context};
+
+use leo_compiler::value_to_source;
+use leo_errors::{PackageError, Result};
+use leo_package::inputs::InputFile;
+use leo_span::Symbol;
+
+use clap::StructOpt;
+use colored::Colorize;
+use std::fs;
+use tracing::span::Span;
+
+/// Run a function of the package with the reference interpreter, without proving it.
+#[derive(StructOpt, Debug)]
+pub struct Interpret {
+    #[structopt(name = "NAME", help = "The name of the function to run.", default_value = "main")]
+    pub(crate) name: String,
+
+    #[structopt(
+        name = "INPUTS",
+        help = "The inputs to the function. If none are provided, the input file is used."
+    )]
+    pub(crate) inputs: Vec<String>,
+
+    #[structopt(
+        long,
+        value_name = "TEST",
+        help = "Exports the inputs and the output of the run as a compiler test named TEST in the `tests/` directory."
+    )]
+    pub(crate) export: Option<String>,
+
+    #[structopt(flatten)]
+    pub(crate) state: StateOptions,
+
+    #[structopt(flatten)]
+    pub(crate) compiler_options: BuildOptions,
+}
+
+impl Command for Interpret {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Interpreting")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let handler = context.handler();
+        let mut compiler = context.open_compiler(&handler, self.compiler_options.clone())?;
+        compiler.check()?;
+        compiler.parse_input(InputFile::new(&compiler.program_name).find_file_path(&context.dir()?))?;
+        // The input file is only used, and so only checked, if no inputs are given.
+        if self.inputs.is_empty() {
+            compiler.check_input()?;
+        }
+
+        // The mappings are kept in memory, unless a file or a database is given for their entries to persist in.
+        let storage = self.state.open(&context)?;
+
+        // Run the function.
+        let execution = compiler.interpret_with_storage(Symbol::intern(&self.name), &self.inputs, storage)?;
+        tracing::info!("'{}' returned {}", self.name, value_to_source(&execution.output));
+        for (mapping, key, value) in execution.mappings.entries() {
+            tracing::info!("{}[{}] = {}", mapping, value_to_source(key), value_to_source(value));
+        }
+
+        // Export the run as a test of the package's program that expects the same output on the same inputs.
+        if let Some(test) = self.export {
+            let tests_directory = context.dir()?.join("tests");
+            let inputs_directory = tests_directory.join("inputs");
+            fs::create_dir_all(&inputs_directory)
+                .map_err(|err| PackageError::failed_to_create_directory(inputs_directory.display(), err))?;
+
+            let input_file = format!("inputs/{}.in", test);
+            let input_file_path = tests_directory.join(&input_file);
+            fs::write(&input_file_path, execution.input_file())
+                .map_err(|err| PackageError::failed_to_write_file(input_file_path.display(), err))?;
+
+            // The test framework compiles every test as the program `test.aleo`.
+            let main_file_path = context.main_file()?;
+            let source = fs::read_to_string(&main_file_path)
+                .map_err(|err| PackageError::failed_to_read_file(main_file_path.display(), err))?
+                .replacen(&format!("program {}.", compiler.program_name), "program test.", 1);
+            let test_file_path = tests_directory.join(format!("{}.leo", test));
+            fs::write(&test_file_path, execution.test_header(&input_file) + "\n" + &source)
+                .map_err(|err| PackageError::failed_to_write_file(test_file_path.display(), err))?;
+
+            tracing::info!("exported the run to {}", test_file_path.display().to_string().dimmed());
+        }
+
+        Ok(())
+    }
+}
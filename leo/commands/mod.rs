@@ -15,24 +15,75 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 // local program commands
+pub mod account;
+pub use account::Account;
+
+pub mod add;
+pub use add::Add;
+
+pub mod ast_diff;
+pub use ast_diff::AstDiff;
+
 pub mod build;
 pub use build::Build;
 
+pub mod check;
+pub use check::Check;
+
 pub mod clean;
 pub use clean::Clean;
 
+pub mod debug;
+pub use debug::Debug;
+
 pub mod deploy;
 pub use deploy::Deploy;
 
+pub mod execute;
+pub use execute::Execute;
+
+pub mod fix;
+pub use fix::Fix;
+
+pub mod fmt;
+pub use fmt::Fmt;
+
+pub mod grammar;
+pub use grammar::Grammar;
+
+pub mod interpret;
+pub use interpret::Interpret;
+
+pub mod lsp;
+pub use lsp::Lsp;
+
 pub mod new;
 pub use new::New;
 
 pub mod node;
 pub use node::Node;
 
+pub mod plugins;
+pub use plugins::Plugins;
+
+pub mod repl;
+pub use repl::Repl;
+
 pub mod run;
 pub use run::Run;
 
+pub mod state;
+pub use state::{State, StateOptions};
+
+pub mod stats;
+pub use stats::Stats;
+
+pub mod test;
+pub use test::Test;
+
+pub mod verify;
+pub use verify::Verify;
+
 use crate::context::*;
 use leo_errors::Result;
 
@@ -0,0 +1,51 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context, plugins::Plugin};
+use leo_errors::Result;
+
+use clap::StructOpt;
+use colored::Colorize;
+use tracing::span::Span;
+
+/// List the plugins on `PATH`, which are run as subcommands of the CLI.
+#[derive(StructOpt, Debug)]
+pub struct Plugins {}
+
+impl Command for Plugins {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Plugins")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, _: Context, _: Self::Input) -> Result<Self::Output> {
+        let plugins = Plugin::discover();
+        if plugins.is_empty() {
+            tracing::info!("No plugins found. Executables named `leo-<name>` on PATH are run by `leo <name>`.");
+        }
+        for plugin in plugins {
+            tracing::info!("{:<16} {}", plugin.name, plugin.path.display().to_string().dimmed());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::build::BuildOptions;
+use crate::{commands::Command, context::Context};
+
+use leo_compiler::{value_to_source, LanguageVersion};
+use leo_errors::{emitter::Handler, CliError, LeoError, PackageError, Result};
+use leo_span::Symbol;
+
+use clap::StructOpt;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use tracing::span::Span;
+
+/// Evaluate Leo expressions and definitions at a prompt, optionally against the program of the package.
+#[derive(StructOpt, Debug)]
+pub struct Repl {}
+
+/// The commands of the prompt, which are told apart from Leo code by their leading colon.
+const PROMPT_HELP: &str = "\
+:load           Load the program of the package, so that its functions can be called
+:state MAPPING  Print the entries of MAPPING
+:save FILE      Save the variables and the mapping entries to FILE
+:restore FILE   Replace the variables and the mapping entries with those saved in FILE
+:help           Print this help";
+
+impl Command for Repl {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Repl")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let mut repl = leo_compiler::Repl::new(LanguageVersion::CURRENT);
+        let mut lines = io::stdin().lock().lines();
+
+        loop {
+            print!("> ");
+            io::stdout().flush().map_err(CliError::cli_io_error)?;
+
+            // Stop at the end of the input, e.g. when Ctrl-D is pressed.
+            let line = match lines.next() {
+                Some(line) => line.map_err(CliError::cli_io_error)?,
+                None => break,
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // Each line has its own handler, so that an error does not fail the lines after it.
+            let handler = context.handler();
+            let result = match line.trim().strip_prefix(':') {
+                Some(command) => run_command(&context, &handler, &mut repl, command),
+                None => repl
+                    .evaluate(&handler, &line)
+                    .map(|value| println!("{}", value_to_source(&value))),
+            };
+            match result {
+                Ok(()) => {}
+                // Errors that have already been reported only carry an exit code.
+                Err(LeoError::LastErrorCode(_)) => {}
+                Err(error) => handler.emit_err(error),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs a command of the prompt, e.g. `state balances`, written without its leading colon.
+fn run_command(context: &Context, handler: &Handler, repl: &mut leo_compiler::Repl, command: &str) -> Result<()> {
+    let (command, argument) = match command.split_once(' ') {
+        Some((command, argument)) => (command, argument.trim()),
+        None => (command, ""),
+    };
+
+    match command {
+        "load" => {
+            *repl = load_package(context, handler)?;
+            println!("Loaded {}", repl.program_id());
+        }
+        "state" => match repl.mapping_entries(Symbol::intern(argument)) {
+            Some(entries) => {
+                for (key, value) in entries {
+                    println!("{}[{}] = {}", argument, value_to_source(key), value_to_source(value));
+                }
+            }
+            None => println!("There is no mapping `{}` in {}.", argument, repl.program_id()),
+        },
+        "save" if !argument.is_empty() => {
+            fs::write(argument, repl.save()).map_err(|err| PackageError::failed_to_write_file(argument, err))?;
+            println!("Saved the session to {}", argument);
+        }
+        "restore" if !argument.is_empty() => {
+            let session =
+                fs::read_to_string(argument).map_err(|err| PackageError::failed_to_read_file(argument, err))?;
+            repl.restore(handler, &session)?;
+            println!("Restored the session from {}", argument);
+        }
+        "save" | "restore" => println!("Expected a file, e.g. `:{} session.json`.", command),
+        "help" => println!("{}", PROMPT_HELP),
+        command => println!("Unknown command `:{}`. Type `:help` for the list of commands.", command),
+    }
+    Ok(())
+}
+
+/// Checks the program of the package in the current directory, and returns a `Repl` whose lines can call its functions.
+/// Its variables and mapping entries start out empty.
+fn load_package(context: &Context, handler: &Handler) -> Result<leo_compiler::Repl> {
+    let mut compiler = context.open_compiler(handler, BuildOptions::default())?;
+    let symbol_table = compiler.check()?;
+
+    // Note that parsing enforces that there is exactly one program scope in a file.
+    let scope = compiler
+        .ast
+        .as_repr()
+        .program_scopes
+        .values()
+        .next()
+        .cloned()
+        .expect("a checked program has a program scope");
+    Ok(leo_compiler::Repl::with_program(
+        compiler.language_version,
+        scope,
+        symbol_table,
+    ))
+}
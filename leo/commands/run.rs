@@ -15,17 +15,19 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use super::build::BuildOptions;
-use crate::commands::ALEO_CLI_COMMAND;
 use crate::{
-    commands::{Build, Command},
+    commands::{Build, Command, Interpret, StateOptions},
     context::Context,
+    prover::{ProverOptions, ProvingRequest},
+    scripts::Hook,
 };
-use leo_errors::{CliError, PackageError, Result};
+use leo_errors::Result;
 use leo_package::build::BuildDirectory;
 
-use aleo::commands::Run as AleoRun;
-
 use clap::StructOpt;
+use indexmap::IndexMap;
+#[cfg(feature = "rocksdb")]
+use std::path::PathBuf;
 use tracing::span::Span;
 
 /// Build, Prove and Run Leo program with inputs
@@ -40,6 +42,39 @@ pub struct Run {
     )]
     inputs: Vec<String>,
 
+    #[structopt(
+        long,
+        help = "Runs the program with the interpreter instead of building and proving it."
+    )]
+    no_snark: bool,
+
+    #[structopt(
+        long,
+        requires = "no-snark",
+        help = "Keeps the mappings in `outputs/state.json`, so that their entries persist across runs."
+    )]
+    persist: bool,
+
+    #[cfg(feature = "rocksdb")]
+    #[structopt(
+        long,
+        value_name = "DIR",
+        requires = "no-snark",
+        conflicts_with = "persist",
+        help = "Keeps the mappings in a RocksDB database in DIR, so that their entries persist across runs."
+    )]
+    state: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    prover: ProverOptions,
+
+    #[structopt(
+        long,
+        value_name = "PROFILE",
+        help = "The network profile whose proving service is used if `--prover` is not given. Defaults to `LEO_NETWORK`, or else `testnet`."
+    )]
+    network: Option<String>,
+
     #[structopt(flatten)]
     pub(crate) compiler_options: BuildOptions,
 }
@@ -53,46 +88,71 @@ impl Command for Run {
     }
 
     fn prelude(&self, context: Context) -> Result<Self::Input> {
+        // Run the scripts declared to run before running, which may e.g. generate the source to build.
+        context.open_scripts()?.run(Hook::PreRun)?;
+
+        // The interpreter runs the checked program, so nothing is built.
+        if self.no_snark {
+            return Ok((None, IndexMap::new()));
+        }
+
         (Build {
             compiler_options: self.compiler_options.clone(),
+            watch: false,
+            workspace: false,
         })
         .execute(context)
     }
 
     fn apply(self, context: Context, input: Self::Input) -> Result<Self::Output> {
+        if self.no_snark {
+            Interpret {
+                name: self.name,
+                inputs: self.inputs,
+                export: None,
+                state: StateOptions {
+                    persist: self.persist,
+                    #[cfg(feature = "rocksdb")]
+                    state: self.state,
+                },
+                compiler_options: self.compiler_options,
+            }
+            .apply(context.clone(), ())?;
+            return context.open_scripts()?.run(Hook::PostRun);
+        }
+
         // If input values are provided, then run the program with those inputs.
         // Otherwise, use the input file.
-        let mut inputs = match self.inputs.is_empty() {
+        let inputs = match self.inputs.is_empty() {
             true => match input {
-                (Some(input_ast), circuits) => input_ast.program_inputs(&self.name, circuits),
+                (Some(input_ast), circuits) => input_ast.program_inputs(&self.name, circuits)?,
                 _ => Vec::new(),
             },
             false => self.inputs,
         };
 
-        // Compose the `aleo run` command.
-        let mut arguments = vec![ALEO_CLI_COMMAND.to_string(), self.name];
-        arguments.append(&mut inputs);
-
         // Open the Leo build/ directory
         let path = context.dir()?;
         let build_directory = BuildDirectory::open(&path)?;
 
-        // Change the cwd to the Leo build/ directory to compile aleo files.
-        std::env::set_current_dir(&build_directory)
-            .map_err(|err| PackageError::failed_to_set_cwd(build_directory.display(), err))?;
-
-        // Call the `aleo run` command from the Aleo SDK.
-        if self.compiler_options.offline {
-            arguments.push(String::from("--offline"));
+        // Execute the function and prove the execution, locally or with a proving service.
+        let program_id = context.open_manifest()?.program_id().to_string();
+        let mut request = ProvingRequest::new(program_id, self.name, inputs);
+        let mut prover = self.prover;
+        if prover.prover.is_none() {
+            prover.prover = context.open_profile(self.network.as_deref())?.prover;
+        }
+        // Only a proving service is sent the program and the keys, since a local prover reads them from the build directory.
+        if prover.prover.is_some() {
+            request = request.with_program(&build_directory)?;
+        }
+        let prover = prover.open(build_directory, self.compiler_options.offline);
+        let response = prover.prove(&request)?;
+        for output in response.outputs.iter() {
+            tracing::info!("• {}", output);
         }
-        println!();
-        let command = AleoRun::try_parse_from(&arguments).map_err(CliError::failed_to_parse_aleo_run)?;
-        let res = command.parse().map_err(CliError::failed_to_execute_aleo_run)?;
-
-        // Log the output of the `aleo run` command.
-        tracing::info!("{}", res);
 
-        Ok(())
+        // Run the scripts declared to run after running.
+        context.open_scripts()?.run(Hook::PostRun)
     }
 }
@@ -0,0 +1,151 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+#[cfg(feature = "rocksdb")]
+use leo_compiler::RocksDbStorage;
+use leo_compiler::{value_to_source, JsonStorage, Mappings, StateStorage};
+use leo_errors::Result;
+use leo_package::outputs::OutputsDirectory;
+
+use clap::StructOpt;
+use std::path::PathBuf;
+use tracing::span::Span;
+
+/// The name of the file in the outputs directory that keeps the entries of the mappings across runs.
+pub const STATE_FILE_NAME: &str = "state.json";
+
+/// Where the interpreter keeps the entries of the program's mappings.
+#[derive(StructOpt, Clone, Debug, Default)]
+pub struct StateOptions {
+    #[structopt(
+        long,
+        help = "Keeps the mappings in `outputs/state.json`, so that their entries persist across runs."
+    )]
+    pub(crate) persist: bool,
+
+    #[cfg(feature = "rocksdb")]
+    #[structopt(
+        long,
+        value_name = "DIR",
+        conflicts_with = "persist",
+        help = "Keeps the mappings in a RocksDB database in DIR, so that their entries persist across runs."
+    )]
+    pub(crate) state: Option<PathBuf>,
+}
+
+impl StateOptions {
+    /// Opens the store of the mapping entries, which is kept in memory unless a file or a database is given.
+    pub(crate) fn open(&self, context: &Context) -> Result<Box<dyn StateStorage>> {
+        #[cfg(feature = "rocksdb")]
+        if let Some(path) = &self.state {
+            return Ok(Box::new(RocksDbStorage::open(path)?));
+        }
+
+        match self.persist {
+            true => Ok(Box::new(JsonStorage::open(&state_file_path(context)?)?)),
+            false => Ok(Box::new(Mappings::default())),
+        }
+    }
+}
+
+/// Returns the path of the file that keeps the mapping entries of the package, creating the outputs directory.
+fn state_file_path(context: &Context) -> Result<PathBuf> {
+    Ok(OutputsDirectory::create(&context.dir()?)?.join(STATE_FILE_NAME))
+}
+
+/// Commands to inspect and reset the entries of the mappings kept by `leo run --no-snark --persist`.
+#[derive(StructOpt, Debug)]
+pub enum State {
+    /// Prints the entries of the mappings
+    Inspect {
+        #[structopt(
+            name = "MAPPING",
+            help = "The mapping to print the entries of. Defaults to every mapping."
+        )]
+        mapping: Option<String>,
+
+        #[cfg(feature = "rocksdb")]
+        #[structopt(
+            long,
+            value_name = "DIR",
+            help = "Reads the entries from the RocksDB database in DIR instead of `outputs/state.json`."
+        )]
+        state: Option<PathBuf>,
+    },
+    /// Removes every entry of the mappings
+    Reset {
+        #[cfg(feature = "rocksdb")]
+        #[structopt(
+            long,
+            value_name = "DIR",
+            help = "Removes the entries from the RocksDB database in DIR instead of `outputs/state.json`."
+        )]
+        state: Option<PathBuf>,
+    },
+}
+
+impl State {
+    /// Returns the options that open the store the command reads or resets.
+    fn options(&self) -> StateOptions {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            State::Inspect { state, .. } | State::Reset { state } => StateOptions {
+                persist: true,
+                state: state.clone(),
+            },
+            #[cfg(not(feature = "rocksdb"))]
+            State::Inspect { .. } | State::Reset { .. } => StateOptions { persist: true },
+        }
+    }
+}
+
+impl Command for State {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        match &self {
+            State::Inspect { mapping, .. } => {
+                let entries = self.options().open(&context)?.entries()?;
+                let entries: Vec<_> = entries
+                    .into_iter()
+                    .filter(|(name, _, _)| mapping.as_ref().map_or(true, |mapping| name.to_string() == *mapping))
+                    .collect();
+                if entries.is_empty() {
+                    tracing::info!("No entries");
+                }
+                for (mapping, key, value) in entries {
+                    tracing::info!("{}[{}] = {}", mapping, value_to_source(&key), value_to_source(&value));
+                }
+            }
+            State::Reset { .. } => {
+                self.options().open(&context)?.clear()?;
+                tracing::info!("Removed every entry");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+use leo_errors::Result;
+use leo_package::outputs::{BuildMetrics, MetricsFile};
+
+use clap::StructOpt;
+use colored::Colorize;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::span::Span;
+
+/// Show the metrics recorded by the builds of the package in `outputs/metrics.ndjson`.
+#[derive(StructOpt, Debug)]
+pub struct Stats {
+    #[structopt(
+        long,
+        help = "Shows a summary of each recorded build, instead of the details of the last one."
+    )]
+    pub(crate) history: bool,
+
+    #[structopt(
+        long,
+        value_name = "N",
+        help = "Only shows the last N builds of the history.",
+        requires = "history"
+    )]
+    pub(crate) limit: Option<usize>,
+}
+
+impl Command for Stats {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Stats")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let path = context.dir()?;
+        if !MetricsFile::exists_at(&path) {
+            tracing::info!("No builds have been recorded yet. Run `leo build` to record one.");
+            return Ok(());
+        }
+        let builds = MetricsFile::read_from(&path)?;
+
+        match (self.history, builds.last()) {
+            (_, None) => tracing::info!("No builds have been recorded yet. Run `leo build` to record one."),
+            (false, Some(last)) => log_build(last),
+            (true, Some(_)) => {
+                let shown = &builds[builds.len().saturating_sub(self.limit.unwrap_or(builds.len()))..];
+                for build in shown {
+                    tracing::info!(
+                        "{:>10}  {}  {:>10.1} ms  {} error(s), {} warning(s)  {}/{} cached  {} bytes",
+                        format_age(build.timestamp),
+                        format_result(build),
+                        build.duration_ms,
+                        build.errors,
+                        build.warnings,
+                        build.files.iter().filter(|file| file.cache_hit).count(),
+                        build.files.len(),
+                        build.artifacts.values().sum::<u64>(),
+                    );
+                }
+
+                let successes: Vec<_> = shown.iter().filter(|build| build.success).collect();
+                if !successes.is_empty() {
+                    let average = successes.iter().map(|build| build.duration_ms).sum::<f64>() / successes.len() as f64;
+                    tracing::info!(
+                        "{} of {} build(s) succeeded, taking {:.1} ms on average",
+                        successes.len(),
+                        shown.len(),
+                        average
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Logs the details of a build: how long each pass took, and the size of each artifact.
+fn log_build(build: &BuildMetrics) {
+    tracing::info!(
+        "The last build {} {}, with Leo {}, and took {:.1} ms",
+        format_result(build),
+        format_age(build.timestamp),
+        build.leo_version,
+        build.duration_ms
+    );
+    tracing::info!("{} error(s), {} warning(s)", build.errors, build.warnings);
+
+    for file in build.files.iter() {
+        let cache = match file.cache_hit {
+            true => "unchanged since the last build",
+            false => "changed since the last build",
+        };
+        tracing::info!("{} ({}):", file.file, cache);
+        for (pass, duration) in file.passes.iter() {
+            tracing::info!("    {:<26} {:>10.3} ms", pass, duration);
+        }
+    }
+
+    if !build.artifacts.is_empty() {
+        tracing::info!("Artifacts:");
+        for (artifact, size) in build.artifacts.iter() {
+            tracing::info!("    {:<26} {:>10} bytes", artifact, size);
+        }
+    }
+}
+
+/// Returns whether a build succeeded, colored.
+fn format_result(build: &BuildMetrics) -> colored::ColoredString {
+    match build.success {
+        true => "succeeded".green(),
+        false => "failed".red(),
+    }
+}
+
+/// Returns how long ago a build started at `timestamp` was, e.g. `3h ago`.
+fn format_age(timestamp: u64) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs())
+        .unwrap_or_default();
+    let age = now.saturating_sub(timestamp);
+    match age {
+        0..=59 => format!("{age}s ago"),
+        60..=3599 => format!("{}m ago", age / 60),
+        3600..=86399 => format!("{}h ago", age / 3600),
+        _ => format!("{}d ago", age / 86400),
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::build::BuildOptions;
+use crate::{commands::Command, context::Context};
+
+use leo_compiler::TestOutcome;
+use leo_errors::{CliError, Result};
+use leo_span::symbol::with_session_globals;
+
+use clap::StructOpt;
+use colored::Colorize;
+use serde_json::json;
+use tracing::span::Span;
+
+/// Run the functions of the package that are annotated with `@test`, in the interpreter, without proving them.
+#[derive(StructOpt, Debug)]
+pub struct Test {
+    #[structopt(
+        name = "FILTER",
+        help = "Only runs the tests whose names contain FILTER.",
+        default_value = ""
+    )]
+    pub(crate) filter: String,
+
+    #[structopt(long, help = "Prints the outcome of each test as JSON, instead of logging it.")]
+    pub(crate) json: bool,
+
+    #[structopt(
+        long,
+        help = "The seed of the random inputs of the tests with inputs. A random seed is used if not given."
+    )]
+    pub(crate) seed: Option<u64>,
+
+    #[structopt(
+        long,
+        help = "The number of threads that the tests run on. The number of cores of the machine is used if not given."
+    )]
+    pub(crate) threads: Option<usize>,
+
+    #[structopt(flatten)]
+    pub(crate) compiler_options: BuildOptions,
+}
+
+impl Command for Test {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Testing")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let handler = context.handler();
+        let mut compiler = context.open_compiler(&handler, self.compiler_options.clone())?;
+        compiler.check()?;
+
+        // Log the seed, so that a run that fails on random inputs can be reproduced.
+        let seed = self.seed.unwrap_or_else(rand::random);
+
+        let threads = self
+            .threads
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |threads| threads.get()));
+
+        // Each test is reported as soon as it finishes, since tests with many cases can take a while.
+        let outcomes = compiler
+            .test_harness()?
+            .with_seed(seed)
+            .with_threads(threads)
+            .run_with_progress(&self.filter, |finished, total, outcome| {
+                if !self.json {
+                    report(finished, total, outcome);
+                }
+            });
+        let failed = outcomes.iter().filter(|outcome| outcome.failure.is_some()).count();
+
+        if self.json {
+            let tests: Vec<_> = outcomes.iter().map(outcome_to_json).collect();
+            println!(
+                "{}",
+                json!({ "tests": tests, "passed": outcomes.len() - failed, "failed": failed, "seed": seed })
+            );
+        } else {
+            tracing::info!("{} passed, {} failed (seed {})", outcomes.len() - failed, failed, seed);
+        }
+
+        match failed {
+            0 => Ok(()),
+            failed => Err(CliError::tests_failed(failed).into()),
+        }
+    }
+}
+
+/// Logs the outcome of a test, e.g. `[2/5] add ... ok (13ms)`, followed by its failure, if any.
+fn report(finished: usize, total: usize, outcome: &TestOutcome) {
+    let progress = format!("[{}/{}]", finished, total);
+    let duration = format!("({}ms)", outcome.duration.as_millis());
+    match &outcome.failure {
+        None => tracing::info!(
+            "{} {} ... {} {}",
+            progress,
+            outcome.name,
+            "ok".green(),
+            duration.dimmed()
+        ),
+        Some(failure) => {
+            tracing::info!(
+                "{} {} ... {} {}",
+                progress,
+                outcome.name,
+                "FAILED".red(),
+                duration.dimmed()
+            );
+            if !outcome.inputs.is_empty() {
+                tracing::info!("Failing inputs: ({})", format_inputs(outcome));
+            }
+            eprintln!("{}", failure);
+        }
+    }
+}
+
+/// Returns the failing inputs of a test separated by commas, e.g. `0u8, true`.
+fn format_inputs(outcome: &TestOutcome) -> String {
+    outcome
+        .inputs
+        .iter()
+        .map(|input| input.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns the outcome of a test as JSON, with the message and the location of its failure, if any.
+fn outcome_to_json(outcome: &TestOutcome) -> serde_json::Value {
+    let failure = match (&outcome.failure, outcome.failure_span()) {
+        (Some(failure), Some(span)) => {
+            let location = with_session_globals(|s| s.source_map.span_to_location(span));
+            json!({
+                "message": failure.message(),
+                "file": location.as_ref().map(|location| location.source_file.name.to_string()),
+                "line": location.as_ref().map(|location| location.line_start),
+                "column": location.as_ref().map(|location| location.col_start),
+            })
+        }
+        _ => serde_json::Value::Null,
+    };
+    json!({
+        "name": outcome.name.to_string(),
+        "passed": outcome.failure.is_none(),
+        "failure": failure,
+        "inputs": outcome.inputs.iter().map(|input| input.to_string()).collect::<Vec<_>>(),
+        "duration_ms": outcome.duration.as_millis() as u64,
+    })
+}
@@ -0,0 +1,98 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::Network;
+use crate::{commands::Command, context::Context};
+use leo_errors::{CliError, Result};
+use leo_package::build::BuildDirectory;
+
+use snarkvm::file::VerifierFile;
+use snarkvm::package::Package;
+use snarkvm::prelude::Execution;
+
+use clap::StructOpt;
+use std::{path::PathBuf, str::FromStr};
+use tracing::span::Span;
+
+/// Verifies the proofs of an execution of the package's program locally, without contacting a node.
+#[derive(StructOpt, Debug)]
+pub struct Verify {
+    #[structopt(
+        name = "EXECUTION",
+        help = "The path to the execution to verify, as a JSON file.",
+        parse(from_os_str)
+    )]
+    execution: PathBuf,
+}
+
+impl Command for Verify {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Verifying")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        // Read the execution before anything else, since the path may be relative to the current directory.
+        let execution = std::fs::read_to_string(&self.execution)
+            .map_err(|err| CliError::failed_to_read_execution(self.execution.display(), err))?;
+        let execution = Execution::<Network>::from_str(&execution)
+            .map_err(|err| CliError::failed_to_read_execution(self.execution.display(), err))?;
+
+        // The Leo build/ directory is the Aleo package of the program, with the keys of its functions.
+        let path = context.dir()?;
+        let build_directory = BuildDirectory::open(&path).map_err(|_| CliError::needs_leo_build())?;
+        let package = Package::<Network>::open(&build_directory).map_err(CliError::failed_to_verify_execution)?;
+        let process = package.get_process().map_err(CliError::failed_to_verify_execution)?;
+
+        // Load the verifying key of each function that has been run, which is written when it is first run.
+        let program = package.program();
+        for function_name in program.functions().keys() {
+            if let Ok(verifier) = VerifierFile::<Network>::open(&package.build_directory(), function_name) {
+                process
+                    .insert_verifying_key(program.id(), function_name, verifier.verifying_key().clone())
+                    .map_err(CliError::failed_to_verify_execution)?;
+            }
+        }
+
+        process
+            .verify_execution(&execution)
+            .map_err(CliError::failed_to_verify_execution)?;
+
+        // Report what was proven by each transition.
+        for transition in execution.transitions() {
+            tracing::info!(
+                "Verified '{}/{}' (transition {})",
+                transition.program_id(),
+                transition.function_name(),
+                transition.id()
+            );
+            for input in transition.inputs() {
+                tracing::info!("    input  {}", input);
+            }
+            for output in transition.outputs() {
+                tracing::info!("    output {}", output);
+            }
+        }
+
+        Ok(())
+    }
+}
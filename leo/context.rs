@@ -14,11 +14,22 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::commands::Network;
+use crate::commands::{build::BuildOptions, Network};
+use crate::manifest;
+use crate::profiles::{Environment, Profile};
+use crate::scripts::Scripts;
+use leo_ast::FormatConfig;
+use leo_compiler::{Compiler, HeaderConfig, ImportPolicy, LanguageVersion, NamingConfig};
+use leo_errors::emitter::{ErrorFormat, Handler};
 use leo_errors::{CliError, PackageError, Result};
+use leo_package::dependencies::Dependency;
+use leo_package::workspace::Workspace;
 use snarkvm::file::Manifest;
 
 use leo_package::build::{BuildDirectory, BUILD_DIRECTORY_NAME};
+use leo_package::outputs::OutputsDirectory;
+use leo_package::source::SourceDirectory;
+
 use std::fs::File;
 use std::io::Write;
 use std::{
@@ -30,21 +41,30 @@ use std::{
 /// All the info that is relevant in most of the commands
 #[derive(Clone)]
 pub struct Context {
-    /// Path at which the command is called, None when default
-    pub path: Option<PathBuf>,
+    /// Path of the package, which is the directory the command is called in unless given
+    pub path: PathBuf,
+    /// The format in which diagnostics are printed.
+    pub error_format: ErrorFormat,
 }
 
 impl Context {
-    pub fn new(path: Option<PathBuf>) -> Result<Context> {
-        Ok(Context { path })
+    pub fn new(path: Option<PathBuf>, error_format: ErrorFormat) -> Result<Context> {
+        // Resolve the default path now, since commands such as `build` change the current directory.
+        let path = match path {
+            Some(path) => path,
+            None => current_dir().map_err(CliError::cli_io_error)?,
+        };
+        Ok(Context { path, error_format })
+    }
+
+    /// Returns a handler that prints diagnostics in the configured format.
+    pub fn handler(&self) -> Handler {
+        Handler::new(self.error_format.emitter())
     }
 
     /// Returns the path to the Leo package.
     pub fn dir(&self) -> Result<PathBuf> {
-        match &self.path {
-            Some(path) => Ok(path.clone()),
-            None => Ok(current_dir().map_err(CliError::cli_io_error)?),
-        }
+        Ok(self.path.clone())
     }
 
     /// Returns the package name as a String.
@@ -81,4 +101,115 @@ impl Context {
         // Get package name from program id.
         Ok(manifest)
     }
+
+    /// Returns the naming conventions configured under `lints.naming` in `program.json`.
+    /// Conventions that are not configured keep their defaults.
+    pub fn open_naming_config(&self) -> Result<NamingConfig> {
+        manifest::naming_config(&self.read_manifest()?)
+    }
+
+    /// Returns the header of generated code configured under `generated` in `program.json`.
+    pub fn open_header_config(&self) -> Result<HeaderConfig> {
+        manifest::header_config(&self.read_manifest()?)
+    }
+
+    /// Returns the layout of printed source and generated comments configured under `format` in `program.json`.
+    pub fn open_format_config(&self) -> Result<FormatConfig> {
+        manifest::format_config(&self.read_manifest()?)
+    }
+
+    /// Returns the dependencies declared under `dependencies` in `program.json`.
+    pub fn open_dependencies(&self) -> Result<Vec<Dependency>> {
+        manifest::dependencies(&self.read_manifest()?)
+    }
+
+    /// Returns the programs that the package may import, configured by `allowed_imports` and `denied_imports` in `program.json`.
+    pub fn open_import_policy(&self) -> Result<ImportPolicy> {
+        manifest::import_policy(&self.read_manifest()?)
+    }
+
+    /// Returns the scripts declared under `scripts` in `program.json`, which are run before and after commands.
+    pub fn open_scripts(&self) -> Result<Scripts> {
+        manifest::scripts(&self.read_manifest()?, self.dir()?)
+    }
+
+    /// Returns the version of Leo declared by the `leo` field of the manifest, if any.
+    pub fn open_language_version(&self) -> Result<Option<LanguageVersion>> {
+        manifest::language_version(&self.read_manifest()?)
+    }
+
+    /// Returns the path to the main file of the package, which is the only source file.
+    pub fn main_file(&self) -> Result<PathBuf> {
+        let source_files = SourceDirectory::files(&self.dir()?)?;
+        SourceDirectory::check_files(&source_files)?;
+        Ok(source_files[0].clone())
+    }
+
+    /// Returns a compiler of the main file of the package, with `options` and the naming conventions, import policy
+    /// and version of Leo configured in `program.json`. The compiler writes its outputs to the `outputs/` directory.
+    pub fn open_compiler<'a>(&self, handler: &'a Handler, mut options: BuildOptions) -> Result<Compiler<'a>> {
+        let manifest = self.open_manifest()?;
+        let program_id = manifest.program_id();
+
+        options.naming = self.open_naming_config()?;
+        options.import_policy = self.open_import_policy()?;
+        options.language_version = self.open_language_version()?;
+
+        Ok(Compiler::new(
+            program_id.name().to_string(),
+            program_id.network().to_string(),
+            handler,
+            self.main_file()?,
+            OutputsDirectory::create(&self.dir()?)?,
+            Some(options.into()),
+        ))
+    }
+
+    /// Returns the network profile `name`, or the one named by `LEO_NETWORK` in the environment or `.env` if no name is given.
+    pub fn open_profile(&self, name: Option<&str>) -> Result<Profile> {
+        let environment = Environment::read_from(&self.dir()?)?;
+        Profile::resolve(name, &manifest::network_profiles(&self.read_manifest()?)?, &environment)
+    }
+
+    /// Returns the contexts of the members of the workspace in `leo.workspace.json`, in the order they are built in:
+    /// each member after the members that it declares as `path` dependencies.
+    pub fn open_workspace(&self) -> Result<Vec<Context>> {
+        // The members are built from their own directories, so their paths must not depend on the current directory.
+        let root = current_dir().map_err(CliError::cli_io_error)?.join(self.dir()?);
+        let workspace = Workspace::read_from(&root)?;
+        let members = workspace
+            .members
+            .iter()
+            .map(|member| Context {
+                path: root.join(member),
+                error_format: self.error_format,
+            })
+            .collect::<Vec<_>>();
+        let dependencies = members
+            .iter()
+            .map(|member| member.open_dependencies())
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(workspace
+            .build_order(&dependencies)?
+            .into_iter()
+            .map(|index| members[index].clone())
+            .collect())
+    }
+
+    /// Declares the dependency `name` under `dependencies` in `program.json`.
+    pub fn add_dependency(&self, name: &str, declaration: serde_json::Value) -> Result<()> {
+        let path = self.dir()?.join(Manifest::<Network>::file_name());
+        let manifest_string = std::fs::read_to_string(&path).map_err(PackageError::failed_to_open_manifest)?;
+        let manifest_string = manifest::add_dependency(&manifest_string, name, declaration)?;
+        std::fs::write(&path, manifest_string)
+            .map_err(|err| PackageError::failed_to_write_file(path.display(), err).into())
+    }
+
+    /// Reads `program.json` as JSON, for the fields that Leo reads itself.
+    fn read_manifest(&self) -> Result<serde_json::Value> {
+        let path = self.dir()?.join(Manifest::<Network>::file_name());
+        let manifest_string = std::fs::read_to_string(path).map_err(PackageError::failed_to_open_manifest)?;
+        manifest::parse(&manifest_string)
+    }
 }
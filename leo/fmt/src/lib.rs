@@ -0,0 +1,197 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use leo_ast::{pretty, FormatConfig};
+use leo_errors::emitter::Handler;
+use leo_errors::Result;
+use leo_span::span::BytePos;
+
+/// Formats the Leo program in `source`, whose text starts at `start_pos` in the source map.
+/// Returns an error if the program does not parse.
+pub fn format(handler: &Handler, source: &str, start_pos: BytePos) -> Result<String> {
+    format_with_config(handler, source, start_pos, &FormatConfig::default())
+}
+
+/// Formats the Leo program in `source` like [`format`], in the layout of `config`.
+pub fn format_with_config(
+    handler: &Handler,
+    source: &str,
+    start_pos: BytePos,
+    config: &FormatConfig,
+) -> Result<String> {
+    let program = leo_parser::parse(handler, source, start_pos)?;
+    handler.last_err()?;
+
+    let trivia = leo_parser::tokenize_trivia(source, start_pos)?;
+    Ok(pretty::program_with_trivia(&program, trivia, config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use leo_ast::LiteralStyle;
+    use leo_errors::emitter::Handler;
+    use leo_span::{source_map::FileName, symbol::create_session_if_not_set_then, symbol::with_session_globals};
+
+    /// Formats `source`, and checks that formatting the result does not change it.
+    fn format_source(source: &str) -> String {
+        format_source_with_config(source, &FormatConfig::default())
+    }
+
+    /// Formats `source` in the layout of `config`, and checks that formatting the result does not change it.
+    fn format_source_with_config(source: &str, config: &FormatConfig) -> String {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let file = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom("test".into())));
+            let formatted = format_with_config(&handler, &file.src, file.start_pos, config).unwrap();
+
+            let file = with_session_globals(|s| s.source_map.new_source(&formatted, FileName::Custom("test".into())));
+            assert_eq!(
+                format_with_config(&handler, &file.src, file.start_pos, config).unwrap(),
+                formatted
+            );
+            formatted
+        })
+    }
+
+    #[test]
+    fn canonical_layout() {
+        let source = "program test.aleo{ record Token{owner:address,gates:u64,amount:u64}
+mapping balances:address=>u64;
+@program
+transition mint(public a:u64,b:u64)->(u64,u64){ let c:u64=(a+b)*2u64;
+if(c>10u64){c+=1u64;}else if c==0u64{return (c,c);}else{ c=c.add_wrapped(a); }
+for i:u8 in 0u8..4u8{ console.assert_eq(a,b); }
+async finalize(a);
+return (-(c + 1u64) ** 2u64, (a - (b - c)).abs());}finalize mint(public a:u64){increment(balances,self.caller,a);}
+}";
+        let expected = "program test.aleo {
+    record Token {
+        owner: address,
+        gates: u64,
+        amount: u64,
+    }
+
+    mapping balances: address => u64;
+
+    @program
+    transition mint(public a: u64, b: u64) -> (u64, u64) {
+        let c: u64 = (a + b) * 2u64;
+        if c > 10u64 {
+            c += 1u64;
+        } else if c == 0u64 {
+            return (c, c);
+        } else {
+            c = c.add_wrapped(a);
+        }
+        for i: u8 in 0u8..4u8 {
+            console.assert_eq(a, b);
+        }
+        async finalize(a);
+        return (-(c + 1u64) ** 2u64, (a - (b - c)).abs());
+    } finalize mint(public a: u64) {
+        increment(balances, self.caller, a);
+    }
+}
+";
+        assert_eq!(format_source(source), expected);
+    }
+
+    #[test]
+    fn comments_and_blank_lines() {
+        let source = "// leo:version 1.5
+
+/* The program. */
+program test.aleo {
+    // A point.
+    struct Point { x: u8, // The first coordinate.
+        y: u8 }
+
+
+
+    function sum(p: Point) -> u8 { // Adds the coordinates.
+        let x: u8 = p.x;
+
+        // The second coordinate.
+        let y: u8 = p.y;
+        return x + y;
+        // Done.
+    } // End of `sum`.
+}
+// End of the program.
+";
+        let expected = "// leo:version 1.5
+
+/* The program. */
+program test.aleo {
+    // A point.
+    struct Point {
+        x: u8, // The first coordinate.
+        y: u8,
+    }
+
+    function sum(p: Point) -> u8 { // Adds the coordinates.
+        let x: u8 = p.x;
+
+        // The second coordinate.
+        let y: u8 = p.y;
+        return x + y;
+        // Done.
+    } // End of `sum`.
+}
+// End of the program.
+";
+        assert_eq!(format_source(source), expected);
+    }
+
+    #[test]
+    fn configured_layout() {
+        let source = "program test.aleo {
+    transition mint(public receiver: address, amount: u64, fee: u64) -> u64 {
+        return 007u64 + amount - fee;
+    }
+
+    function zero() -> u8 {
+        return 000u8;
+    }
+}
+";
+        let expected = "program test.aleo {
+  transition mint(
+    public receiver: address,
+    amount: u64,
+    fee: u64,
+  ) -> u64 {
+    return 7u64 + amount - fee;
+  }
+
+  function zero() -> u8 {
+    return 0u8;
+  }
+}
+";
+        let config = FormatConfig {
+            indent_width: 2,
+            max_line_length: 60,
+            literal_style: LiteralStyle::Normalize,
+        };
+        assert_eq!(format_source_with_config(source, &config), expected);
+    }
+}
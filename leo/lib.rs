@@ -20,6 +20,12 @@
 pub mod commands;
 pub mod context;
 pub mod logger;
+pub mod manifest;
+pub mod plugins;
+pub mod profiles;
+pub mod prover;
+pub mod scripts;
+pub mod signer;
 pub mod updater;
 
 #[cfg(test)]
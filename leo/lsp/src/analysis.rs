@@ -0,0 +1,342 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Compiles a single Leo file in memory, and indexes the names in it for editor queries.
+
+use crate::index::{byte_range, Indexer};
+
+use leo_ast::Type;
+use leo_compiler::{Compiler, NamingConfig, OutputOptions};
+use leo_errors::{emitter::Handler, LeoError, LeoWarning};
+use leo_span::{
+    source_map::FileName,
+    symbol::{SessionGlobals, SESSION_GLOBALS},
+    Span,
+};
+
+use std::{
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+/// Whether a diagnostic is an error or a warning.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// An error or warning in the analyzed file.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// The code of the diagnostic, e.g. `ETYC0372003`.
+    pub code: Option<String>,
+    /// The message of the diagnostic, followed by its help text.
+    pub message: String,
+    /// The byte range that the diagnostic points to, or the start of the file if it has no location.
+    pub range: Range<usize>,
+    /// The secondary locations that the diagnostic points to, with a message explaining each.
+    pub labels: Vec<(Range<usize>, String)>,
+}
+
+impl Diagnostic {
+    fn new(
+        severity: Severity,
+        code: Option<String>,
+        message: &str,
+        help: Option<&str>,
+        span: Option<Span>,
+        labels: Vec<(Range<usize>, String)>,
+        len: usize,
+    ) -> Self {
+        Self {
+            severity,
+            code,
+            message: match help {
+                Some(help) => format!("{message}\n{help}"),
+                None => message.to_string(),
+            },
+            range: span.and_then(|span| byte_range(span, len)).unwrap_or(0..0),
+            labels,
+        }
+    }
+
+    fn from_error(err: &LeoError, len: usize) -> Self {
+        // Errors from snarkVM have neither a code nor a span.
+        if let LeoError::Anyhow(err) = err {
+            return Self::new(Severity::Error, None, &err.to_string(), None, None, Vec::new(), len);
+        }
+        let labels = err
+            .labels()
+            .iter()
+            .filter_map(|label| Some((byte_range(label.span, len)?, label.message.clone())))
+            .collect();
+        Self::new(
+            Severity::Error,
+            Some(err.error_code()),
+            &err.message(),
+            err.help(),
+            err.span(),
+            labels,
+            len,
+        )
+    }
+
+    fn from_warning(warning: &LeoWarning, len: usize) -> Self {
+        let labels = warning
+            .labels()
+            .iter()
+            .filter_map(|label| Some((byte_range(label.span, len)?, label.message.clone())))
+            .collect();
+        Self::new(
+            Severity::Warning,
+            Some(warning.error_code()),
+            warning.message(),
+            warning.help(),
+            warning.span(),
+            labels,
+            len,
+        )
+    }
+}
+
+/// The kind of item that a name is declared as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DefinitionKind {
+    Function,
+    Struct,
+    Record,
+    Member,
+    Mapping,
+    Variable,
+}
+
+/// The declaration of a name in the analyzed file.
+#[derive(Clone, Debug)]
+pub struct Definition {
+    pub name: String,
+    pub kind: DefinitionKind,
+    /// The byte range of the declared name.
+    pub range: Range<usize>,
+    /// The byte range of the whole declaration.
+    pub full_range: Range<usize>,
+    /// The declaration as it is written, e.g. `let total: u64` or `transition main(a: u8) -> u8`.
+    pub detail: String,
+    /// The definitions nested in this one, i.e. the members of a struct or the finalize block of a function.
+    pub children: Vec<Definition>,
+}
+
+/// The result of checking a file, along with the declarations of the names in it.
+#[derive(Clone, Debug, Default)]
+pub struct Analysis {
+    /// The errors and warnings in the file.
+    pub diagnostics: Vec<Diagnostic>,
+    /// The structs, records, mappings, and functions of the file, in the order they are declared.
+    pub items: Vec<Definition>,
+    /// Each name in the file, with its declaration. A declaration refers to itself.
+    pub references: Vec<(Range<usize>, Definition)>,
+    /// The type of each expression in the file whose type is known,
+    /// including those in functions that do not type check.
+    pub types: Vec<(Range<usize>, String)>,
+}
+
+impl Analysis {
+    /// Checks `text` as the contents of the file at `path`, which does not have to be saved.
+    pub fn new(path: &Path, text: &str) -> Self {
+        // Each analysis has its own session, so that the source map does not grow with every edit.
+        // As the file is the first source in the session, its spans are byte offsets into `text`.
+        SESSION_GLOBALS.set(&SessionGlobals::default(), || Self::analyze(path, text))
+    }
+
+    fn analyze(path: &Path, text: &str) -> Self {
+        let (program_name, naming) = program_config(path);
+        let (handler, buffer) = Handler::new_with_buf();
+        let mut compiler = Compiler::new(
+            program_name,
+            String::from("aleo"),
+            &handler,
+            path.to_path_buf(),
+            PathBuf::new(),
+            Some(OutputOptions {
+                naming,
+                ..Default::default()
+            }),
+        );
+        // The types are kept even if some functions do not type check, so that they can be shown while the file is edited.
+        let type_table = handler
+            .extend_if_error(compiler.check_source_partially(text, FileName::Real(path.to_path_buf())))
+            .map(|checked| checked.type_table)
+            .unwrap_or_default();
+
+        let mut diagnostics: Vec<_> = buffer
+            .extract_errs()
+            .into_inner()
+            .iter()
+            // Errors that have already been reported only carry an exit code.
+            .filter(|err| !matches!(err, LeoError::LastErrorCode(_)))
+            .map(|err| Diagnostic::from_error(err, text.len()))
+            .collect();
+        diagnostics.extend(
+            buffer
+                .extract_warnings()
+                .into_inner()
+                .iter()
+                .map(|warning| Diagnostic::from_warning(warning, text.len())),
+        );
+
+        // The program is indexed even if it does not type check, so that editor queries keep working while it is edited.
+        let indexer = Indexer::index(compiler.ast.as_repr(), text.len());
+
+        let types = indexer
+            .expressions
+            .into_iter()
+            .filter_map(|(id, range)| match type_table.get(&id) {
+                Some(Type::Err) | None => None,
+                Some(type_) => Some((range, type_.to_string())),
+            })
+            .collect();
+
+        Self {
+            diagnostics,
+            items: indexer.items,
+            references: indexer.references,
+            types,
+        }
+    }
+
+    /// Returns the declaration of the name at byte `offset`, if there is one.
+    pub fn definition_at(&self, offset: usize) -> Option<&Definition> {
+        self.references
+            .iter()
+            .filter(|(range, _)| range.start <= offset && offset <= range.end)
+            .min_by_key(|(range, _)| range.len())
+            .map(|(_, definition)| definition)
+    }
+
+    /// Returns the type of the innermost expression at byte `offset`, if it is known.
+    pub fn type_at(&self, offset: usize) -> Option<&str> {
+        self.types
+            .iter()
+            .filter(|(range, _)| range.start <= offset && offset < range.end)
+            .min_by_key(|(range, _)| range.len())
+            .map(|(_, type_)| type_.as_str())
+    }
+}
+
+/// Returns the name of the program in the file at `path`, and the naming conventions it is checked against.
+/// A file in the `src` directory of a package is named after the program in `program.json`,
+/// and any other file after its file name, as `leo check` does.
+pub(crate) fn program_config(path: &Path) -> (String, NamingConfig) {
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let directory = path.parent();
+    let manifest = directory
+        .and_then(Path::parent)
+        .and_then(|package| fs::read_to_string(package.join("program.json")).ok())
+        .and_then(|manifest| serde_json::from_str::<serde_json::Value>(&manifest).ok());
+    let manifest = match manifest {
+        Some(manifest) => manifest,
+        None => return (stem, NamingConfig::default()),
+    };
+
+    let naming = manifest
+        .get("lints")
+        .and_then(|lints| lints.get("naming"))
+        .and_then(|naming| serde_json::from_value(naming.clone()).ok())
+        .unwrap_or_default();
+    let in_source_directory = directory.and_then(Path::file_name).map_or(false, |name| name == "src");
+    let program = manifest
+        .get("program")
+        .and_then(|program| program.as_str())
+        .and_then(|program| program.strip_suffix(".aleo"));
+    match program {
+        Some(program) if in_source_directory => (program.to_string(), naming),
+        _ => (stem, naming),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "program test.aleo {
+    struct Point {
+        x: u8,
+        y: u8,
+    }
+
+    transition main(a: u8) -> u8 {
+        let p: Point = Point { x: a, y: 1u8 };
+        return p.x + b;
+    }
+}
+";
+
+    /// Returns the declaration of the name at `index` in the first occurrence of `context` in `PROGRAM`.
+    fn definition_of(analysis: &Analysis, context: &str, index: usize) -> Option<String> {
+        let offset = PROGRAM.find(context)? + index;
+        Some(analysis.definition_at(offset)?.detail.clone())
+    }
+
+    #[test]
+    fn definitions() {
+        let analysis = Analysis::new(Path::new("test.leo"), PROGRAM);
+
+        let names: Vec<_> = analysis.items.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names, ["Point", "main"]);
+        let members: Vec<_> = analysis.items[0]
+            .children
+            .iter()
+            .map(|child| child.name.as_str())
+            .collect();
+        assert_eq!(members, ["x", "y"]);
+
+        assert_eq!(definition_of(&analysis, "= Point", 2).as_deref(), Some("struct Point"));
+        assert_eq!(definition_of(&analysis, "x: a", 3).as_deref(), Some("a: u8"));
+        assert_eq!(definition_of(&analysis, "p.x", 0).as_deref(), Some("let p: Point"));
+        assert_eq!(definition_of(&analysis, "p.x", 2).as_deref(), Some("x: u8"));
+        // `b` is not declared.
+        assert_eq!(definition_of(&analysis, "b;", 0), None);
+    }
+
+    #[test]
+    fn diagnostics() {
+        let analysis = Analysis::new(Path::new("test.leo"), PROGRAM);
+
+        assert_eq!(analysis.diagnostics.len(), 1);
+        let diagnostic = &analysis.diagnostics[0];
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(&PROGRAM[diagnostic.range.clone()], "b");
+    }
+
+    #[test]
+    fn types_in_a_function_with_errors() {
+        let analysis = Analysis::new(Path::new("test.leo"), PROGRAM);
+
+        let type_of = |context: &str| analysis.type_at(PROGRAM.find(context).unwrap());
+        assert_eq!(type_of("1u8"), Some("u8"));
+        assert_eq!(type_of("Point { x"), Some("Point"));
+        assert_eq!(type_of("p.x"), Some("Point"));
+        // `b` is not declared, so neither it nor the sum has a type.
+        assert_eq!(type_of("b;"), None);
+    }
+}
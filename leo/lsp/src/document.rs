@@ -0,0 +1,109 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Analysis;
+
+use lsp_types::{Position, Range as LspRange};
+use std::{ops::Range, path::PathBuf};
+
+/// A file that is open in the editor, with its unsaved contents.
+pub struct Document {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The contents of the file in the editor.
+    pub text: String,
+    /// The analysis of `text`, if it has been analyzed since it last changed.
+    analysis: Option<Analysis>,
+}
+
+impl Document {
+    pub fn new(path: PathBuf, text: String) -> Self {
+        Self {
+            path,
+            text,
+            analysis: None,
+        }
+    }
+
+    /// Replaces the contents of the document after an edit.
+    pub fn set_text(&mut self, text: String) {
+        self.text = text;
+        self.analysis = None;
+    }
+
+    /// Returns the analysis of the contents of the document, analyzing them if they changed since the last analysis.
+    pub fn analysis(&mut self) -> &Analysis {
+        let (path, text) = (&self.path, &self.text);
+        self.analysis.get_or_insert_with(|| Analysis::new(path, text))
+    }
+
+    /// Returns the position of the byte `offset`, as a line and a column in UTF-16 code units, both counted from 0.
+    pub fn position(&self, offset: usize) -> Position {
+        let before = &self.text[..offset.min(self.text.len())];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        Position::new(
+            before.matches('\n').count() as u32,
+            before[line_start..].encode_utf16().count() as u32,
+        )
+    }
+
+    /// Returns the byte offset of `position`, clamped to the end of its line and to the end of the document.
+    pub fn offset(&self, position: Position) -> usize {
+        let mut line_start = 0;
+        for _ in 0..position.line {
+            match self.text[line_start..].find('\n') {
+                Some(index) => line_start += index + 1,
+                None => return self.text.len(),
+            }
+        }
+
+        let line = self.text[line_start..].split('\n').next().unwrap_or_default();
+        let mut column = 0;
+        for (index, c) in line.char_indices() {
+            if column >= position.character {
+                return line_start + index;
+            }
+            column += c.len_utf16() as u32;
+        }
+        line_start + line.len()
+    }
+
+    /// Returns the range of positions covering the bytes in `range`.
+    pub fn range(&self, range: &Range<usize>) -> LspRange {
+        LspRange::new(self.position(range.start), self.position(range.end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions() {
+        let document = Document::new(PathBuf::from("main.leo"), "let a: u8;\n// é𝄞\nb".to_string());
+
+        assert_eq!(document.position(4), Position::new(0, 4));
+        assert_eq!(document.position(11), Position::new(1, 0));
+        // `é` is one UTF-16 code unit and two bytes, and `𝄞` is two UTF-16 code units and four bytes.
+        assert_eq!(document.position(20), Position::new(1, 6));
+        assert_eq!(document.position(21), Position::new(2, 0));
+
+        assert_eq!(document.offset(Position::new(1, 6)), 20);
+        assert_eq!(document.offset(Position::new(1, 100)), 20);
+        assert_eq!(document.offset(Position::new(2, 0)), 21);
+        assert_eq!(document.offset(Position::new(5, 0)), 22);
+    }
+}
@@ -0,0 +1,431 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Definition, DefinitionKind};
+
+use leo_ast::*;
+use leo_span::{span::Pos, sym, Span, Symbol};
+
+use indexmap::IndexMap;
+use std::ops::Range;
+
+/// Returns the byte range of `span` in a file of length `len`, if the span points into the file.
+/// This relies on the file being the first source in the session, as it is in an [`Analysis`](crate::Analysis).
+pub(crate) fn byte_range(span: Span, len: usize) -> Option<Range<usize>> {
+    let range = span.lo.to_usize()..span.hi.to_usize();
+    (range.start < range.end && range.end <= len).then_some(range)
+}
+
+/// Resolves each name in a program to its declaration.
+/// Variables are resolved by scope, as in the type checker, and names that are not declared are skipped.
+/// Imported programs are not indexed.
+pub(crate) struct Indexer {
+    /// The length of the file.
+    len: usize,
+    /// The top-level items, in the order they are declared.
+    pub(crate) items: Vec<Definition>,
+    /// Each name in the file, with its declaration.
+    pub(crate) references: Vec<(Range<usize>, Definition)>,
+    /// Each expression in the file, by node id, so that the types recorded by the type checker can be located.
+    pub(crate) expressions: Vec<(NodeID, Range<usize>)>,
+    /// The structs and records, by name.
+    structs: IndexMap<Symbol, Definition>,
+    /// The members of each struct and record, by name, with their types.
+    members: IndexMap<Symbol, IndexMap<Symbol, (Definition, Type)>>,
+    /// The functions, by name.
+    functions: IndexMap<Symbol, Definition>,
+    /// The mappings, by name.
+    mappings: IndexMap<Symbol, Definition>,
+    /// The variables in scope, with their types. The innermost scope is last.
+    scopes: Vec<IndexMap<Symbol, (Definition, Type)>>,
+}
+
+impl Indexer {
+    /// Indexes `program`, which is parsed from a file of length `len`.
+    pub(crate) fn index(program: &Program, len: usize) -> Self {
+        let mut indexer = Self {
+            len,
+            items: Vec::new(),
+            references: Vec::new(),
+            expressions: Vec::new(),
+            structs: IndexMap::new(),
+            members: IndexMap::new(),
+            functions: IndexMap::new(),
+            mappings: IndexMap::new(),
+            scopes: Vec::new(),
+        };
+
+        // Items can be used before they are declared, so they are collected first.
+        program
+            .program_scopes
+            .values()
+            .for_each(|scope| indexer.declare_items(scope));
+        indexer.visit_program(program);
+
+        indexer.items.sort_by_key(|definition| definition.full_range.start);
+        indexer
+    }
+
+    /// Returns the definition of a name declared by `identifier`, if it is in the file.
+    fn definition(
+        &self,
+        identifier: &Identifier,
+        kind: DefinitionKind,
+        full_span: Span,
+        detail: String,
+    ) -> Option<Definition> {
+        let range = byte_range(identifier.span, self.len)?;
+        Some(Definition {
+            name: identifier.name.to_string(),
+            kind,
+            full_range: byte_range(full_span, self.len).unwrap_or_else(|| range.clone()),
+            range,
+            detail,
+            children: Vec::new(),
+        })
+    }
+
+    /// Records that `identifier` refers to `definition`.
+    fn refer(&mut self, identifier: &Identifier, definition: Option<Definition>) {
+        if let (Some(range), Some(definition)) = (byte_range(identifier.span, self.len), definition) {
+            self.references.push((range, definition));
+        }
+    }
+
+    /// Collects the structs, records, mappings, and functions of `scope`.
+    fn declare_items(&mut self, scope: &ProgramScope) {
+        for struct_ in scope.structs.values() {
+            let (kind, keyword) = match struct_.is_record {
+                true => (DefinitionKind::Record, "record"),
+                false => (DefinitionKind::Struct, "struct"),
+            };
+            let mut members = IndexMap::new();
+            for member in struct_.members.iter() {
+                let detail = format!("{}: {}", member.identifier, member.type_);
+                let span = member.identifier.span;
+                if let Some(definition) = self.definition(&member.identifier, DefinitionKind::Member, span, detail) {
+                    members.insert(member.identifier.name, (definition, member.type_.clone()));
+                }
+            }
+            let detail = format!("{keyword} {}", struct_.identifier);
+            if let Some(mut definition) = self.definition(&struct_.identifier, kind, struct_.span, detail) {
+                definition.children = members.values().map(|(member, _)| member.clone()).collect();
+                self.items.push(definition.clone());
+                self.structs.insert(struct_.identifier.name, definition);
+            }
+            self.members.insert(struct_.identifier.name, members);
+        }
+
+        for mapping in scope.mappings.values() {
+            let detail = mapping.to_string();
+            if let Some(definition) =
+                self.definition(&mapping.identifier, DefinitionKind::Mapping, mapping.span, detail)
+            {
+                self.items.push(definition.clone());
+                self.mappings.insert(mapping.identifier.name, definition);
+            }
+        }
+
+        for function in scope.functions.values() {
+            let keyword = match function.call_type {
+                CallType::Inline => "inline",
+                CallType::Standard => "function",
+                CallType::Transition => "transition",
+                CallType::AsyncFunction => "async function",
+                CallType::AsyncTransition => "async transition",
+            };
+            let detail = signature(keyword, &function.identifier, &function.input, &function.output_type);
+            if let Some(mut definition) =
+                self.definition(&function.identifier, DefinitionKind::Function, function.span, detail)
+            {
+                if let Some(finalize) = &function.finalize {
+                    let detail = signature("finalize", &finalize.identifier, &finalize.input, &finalize.output_type);
+                    definition.children.extend(self.definition(
+                        &finalize.identifier,
+                        DefinitionKind::Function,
+                        finalize.span,
+                        detail,
+                    ));
+                }
+                self.items.push(definition.clone());
+                self.functions.insert(function.identifier.name, definition);
+            }
+        }
+    }
+
+    /// Brings the variable declared by `identifier` into the innermost scope.
+    fn declare_variable(&mut self, identifier: &Identifier, type_: &Type, full_span: Span, detail: String) {
+        let definition = self.definition(identifier, DefinitionKind::Variable, full_span, detail);
+        self.refer(identifier, definition.clone());
+        if let (Some(definition), Some(scope)) = (definition, self.scopes.last_mut()) {
+            scope.insert(identifier.name, (definition, type_.clone()));
+        }
+    }
+
+    /// Brings the parameters of a function or finalize block into the innermost scope.
+    fn declare_inputs(&mut self, inputs: &[Input]) {
+        for input in inputs {
+            let identifier = input.identifier();
+            let type_ = input.type_();
+            self.visit_type(&type_);
+            let detail = parameter(input);
+            let span = match input {
+                Input::Internal(input) => input.span,
+                Input::External(input) => input.span,
+            };
+            self.declare_variable(&identifier, &type_, span, detail);
+        }
+    }
+
+    /// Returns the variable named `name` in the innermost scope that declares it.
+    fn lookup_variable(&self, name: Symbol) -> Option<&(Definition, Type)> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(&name))
+    }
+
+    /// Returns the declared type of `expression`, if it is a variable or a member of one.
+    fn type_of(&self, expression: &Expression) -> Option<Type> {
+        match expression {
+            Expression::Identifier(identifier) => self.lookup_variable(identifier.name).map(|(_, type_)| type_.clone()),
+            Expression::Access(AccessExpression::Member(access)) => self.member(access).map(|(_, type_)| type_.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the declaration of the member accessed by `access`, if the type of its struct is known.
+    fn member(&self, access: &MemberAccess) -> Option<&(Definition, Type)> {
+        match self.type_of(&access.inner)? {
+            Type::Identifier(struct_) => self.members.get(&struct_.name)?.get(&access.name.name),
+            _ => None,
+        }
+    }
+
+    /// Records the structs and records named in `type_`.
+    fn visit_type(&mut self, type_: &Type) {
+        match type_ {
+            Type::Identifier(identifier) => self.refer(identifier, self.structs.get(&identifier.name).cloned()),
+            Type::Mapping(mapping) => {
+                self.visit_type(&mapping.key);
+                self.visit_type(&mapping.value);
+            }
+            Type::Tuple(tuple) => tuple.0.iter().for_each(|type_| self.visit_type(type_)),
+            _ => {}
+        }
+    }
+
+    /// Records the structs and records named in the outputs of a function or finalize block.
+    fn visit_outputs(&mut self, outputs: &[Output]) {
+        for output in outputs {
+            if let Output::Internal(output) = output {
+                self.visit_type(&output.type_);
+            }
+        }
+    }
+}
+
+/// Returns a parameter as it is declared, e.g. `public a: u8`.
+fn parameter(input: &Input) -> String {
+    match input.mode() {
+        Mode::None => format!("{}: {}", input.identifier(), input.type_()),
+        mode => format!("{} {}: {}", mode, input.identifier(), input.type_()),
+    }
+}
+
+/// Returns the signature of a function or finalize block, e.g. `transition main(a: u8) -> u8`.
+fn signature(keyword: &str, identifier: &Identifier, inputs: &[Input], output_type: &Type) -> String {
+    let parameters = inputs.iter().map(parameter).collect::<Vec<_>>().join(", ");
+    match output_type {
+        Type::Unit => format!("{keyword} {identifier}({parameters})"),
+        _ => format!("{keyword} {identifier}({parameters}) -> {output_type}"),
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for Indexer {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_expression(&mut self, input: &'a Expression, additional: &Self::AdditionalInput) -> Self::Output {
+        if let (Some(id), Some(range)) = (input.id(), byte_range(input.span(), self.len)) {
+            self.expressions.push((id, range));
+        }
+        match input {
+            Expression::Access(access) => self.visit_access(access, additional),
+            Expression::Binary(binary) => self.visit_binary(binary, additional),
+            Expression::Call(call) => self.visit_call(call, additional),
+            Expression::Cast(cast) => self.visit_cast(cast, additional),
+            Expression::Struct(struct_) => self.visit_struct_init(struct_, additional),
+            Expression::Err(err) => self.visit_err(err, additional),
+            Expression::Identifier(identifier) => self.visit_identifier(identifier, additional),
+            Expression::Literal(literal) => self.visit_literal(literal, additional),
+            Expression::Ternary(ternary) => self.visit_ternary(ternary, additional),
+            Expression::Tuple(tuple) => self.visit_tuple(tuple, additional),
+            Expression::Unary(unary) => self.visit_unary(unary, additional),
+        }
+    }
+
+    fn visit_access(&mut self, input: &'a AccessExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        match input {
+            AccessExpression::AssociatedFunction(function) => {
+                let mut args = function.args.iter();
+                // The first argument of a mapping operation names the mapping, not a variable.
+                if matches!(&function.ty, Type::Identifier(ty) if ty.name == sym::Mapping) {
+                    if let Some(Expression::Identifier(mapping)) = function.args.first() {
+                        self.refer(mapping, self.mappings.get(&mapping.name).cloned());
+                        args.next();
+                    }
+                }
+                args.for_each(|arg| self.visit_expression(arg, additional));
+            }
+            AccessExpression::Member(access) => {
+                self.visit_expression(&access.inner, additional);
+                let definition = self.member(access).map(|(definition, _)| definition.clone());
+                self.refer(&access.name, definition);
+            }
+            AccessExpression::Tuple(access) => self.visit_expression(&access.tuple, additional),
+            _ => {}
+        }
+    }
+
+    fn visit_call(&mut self, input: &'a CallExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        // Calls to other programs are not indexed.
+        if let (Expression::Identifier(function), None) = (&*input.function, &input.external) {
+            self.refer(function, self.functions.get(&function.name).cloned());
+        }
+        input
+            .arguments
+            .iter()
+            .for_each(|argument| self.visit_expression(argument, additional));
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        self.refer(&input.name, self.structs.get(&input.name.name).cloned());
+        for member in input.members.iter() {
+            match &member.expression {
+                Some(expression) => {
+                    let definition = self
+                        .members
+                        .get(&input.name.name)
+                        .and_then(|members| members.get(&member.identifier.name))
+                        .map(|(definition, _)| definition.clone());
+                    self.refer(&member.identifier, definition);
+                    self.visit_expression(expression, additional);
+                }
+                // The shorthand `Foo { x }` reads the variable `x`.
+                None => self.visit_identifier(&member.identifier, additional),
+            }
+        }
+    }
+
+    fn visit_identifier(&mut self, input: &'a Identifier, _additional: &Self::AdditionalInput) -> Self::Output {
+        let definition = self
+            .lookup_variable(input.name)
+            .map(|(definition, _)| definition.clone());
+        self.refer(input, definition);
+    }
+}
+
+impl<'a> StatementVisitor<'a> for Indexer {
+    fn visit_assign(&mut self, input: &'a AssignStatement) {
+        self.visit_expression(&input.place, &Default::default());
+        self.visit_expression(&input.value, &Default::default());
+    }
+
+    fn visit_block(&mut self, input: &'a Block) {
+        self.scopes.push(IndexMap::new());
+        input
+            .statements
+            .iter()
+            .for_each(|statement| self.visit_statement(statement));
+        self.scopes.pop();
+    }
+
+    fn visit_decrement(&mut self, input: &'a DecrementStatement) {
+        self.refer(&input.mapping, self.mappings.get(&input.mapping.name).cloned());
+        self.visit_expression(&input.index, &Default::default());
+        self.visit_expression(&input.amount, &Default::default());
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        // The variable is not in scope within its own definition.
+        self.visit_expression(&input.value, &Default::default());
+        self.visit_type(&input.type_);
+        let detail = format!("{} {}: {}", input.declaration_type, input.variable_name, input.type_);
+        self.declare_variable(&input.variable_name, &input.type_, input.span, detail);
+    }
+
+    fn visit_increment(&mut self, input: &'a IncrementStatement) {
+        self.refer(&input.mapping, self.mappings.get(&input.mapping.name).cloned());
+        self.visit_expression(&input.index, &Default::default());
+        self.visit_expression(&input.amount, &Default::default());
+    }
+
+    fn visit_iteration(&mut self, input: &'a IterationStatement) {
+        self.visit_expression(&input.start, &Default::default());
+        self.visit_expression(&input.stop, &Default::default());
+
+        // The loop variable is scoped to the body of the loop.
+        self.scopes.push(IndexMap::new());
+        self.visit_type(&input.type_);
+        let detail = format!("for {}: {}", input.variable, input.type_);
+        self.declare_variable(&input.variable, &input.type_, input.variable.span, detail);
+        self.visit_block(&input.block);
+        self.scopes.pop();
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for Indexer {
+    fn visit_import(&mut self, _input: &'a Program) {}
+
+    fn visit_struct(&mut self, input: &'a Struct) {
+        self.refer(&input.identifier, self.structs.get(&input.identifier.name).cloned());
+        for member in input.members.iter() {
+            let definition = self
+                .members
+                .get(&input.identifier.name)
+                .and_then(|members| members.get(&member.identifier.name))
+                .map(|(definition, _)| definition.clone());
+            self.refer(&member.identifier, definition);
+            self.visit_type(&member.type_);
+        }
+    }
+
+    fn visit_mapping(&mut self, input: &'a Mapping) {
+        self.refer(&input.identifier, self.mappings.get(&input.identifier.name).cloned());
+        self.visit_type(&input.key_type);
+        self.visit_type(&input.value_type);
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        let definition = self.functions.get(&input.identifier.name).cloned();
+        self.refer(&input.identifier, definition.clone());
+
+        // The parameters of a function are in scope throughout its body, but not in its finalize block.
+        self.scopes.push(IndexMap::new());
+        self.declare_inputs(&input.input);
+        self.visit_outputs(&input.output);
+        self.visit_block(&input.block);
+        self.scopes.pop();
+
+        if let Some(finalize) = &input.finalize {
+            let definition = definition.and_then(|definition| definition.children.into_iter().next());
+            self.refer(&finalize.identifier, definition);
+            self.scopes.push(IndexMap::new());
+            self.declare_inputs(&finalize.input);
+            self.visit_outputs(&finalize.output);
+            self.visit_block(&finalize.block);
+            self.scopes.pop();
+        }
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Applies the refactorings of the compiler to a single Leo file, as edits of its text for the code actions of an editor.
+
+use crate::analysis::program_config;
+
+use leo_ast::{pretty, Block, Function, Program, Statement};
+use leo_compiler::{Compiler, OutputOptions};
+use leo_errors::{emitter::Handler, Result};
+use leo_span::{
+    source_map::FileName,
+    span::{BytePos, Pos},
+    symbol::{SessionGlobals, SESSION_GLOBALS},
+    Span, Symbol,
+};
+
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+/// The name of the function that selected statements are extracted into, followed by a number if it is taken.
+const EXTRACTED_FUNCTION: &str = "extracted";
+
+/// The kind of a refactoring.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RefactoringKind {
+    /// Inlines the variable defined at the cursor into each of its uses.
+    InlineVariable,
+    /// Extracts the selected statements into a new function.
+    ExtractFunction,
+}
+
+/// A refactoring of the file, as the edits of its text that apply it.
+#[derive(Clone, Debug)]
+pub struct Refactoring {
+    pub kind: RefactoringKind,
+    /// The title that the editor shows for the refactoring.
+    pub title: String,
+    /// Each byte range of the file that is replaced, with its new text, in the order of the file.
+    pub edits: Vec<(Range<usize>, String)>,
+}
+
+impl Refactoring {
+    /// Returns the refactorings that apply to `selection`, a byte range of `text`, which is the contents of the file at `path`.
+    /// A refactoring is only returned if the refactored program type checks,
+    /// so none are returned while the file does not.
+    pub fn available(path: &Path, text: &str, selection: Range<usize>) -> Vec<Self> {
+        let mut refactorings = Vec::new();
+
+        let cursor = span(selection.start..selection.start);
+        if let Some(edits) = refactor(path, text, |compiler| compiler.inline_variable(cursor)) {
+            refactorings.push(Self {
+                kind: RefactoringKind::InlineVariable,
+                title: "Inline variable".to_string(),
+                edits,
+            });
+        }
+
+        if !selection.is_empty() {
+            let selection = span(selection);
+            if let Some(edits) = refactor(path, text, |compiler| {
+                let name = unused_function_name(compiler.ast.as_repr());
+                compiler.extract_function(selection, name)
+            }) {
+                refactorings.push(Self {
+                    kind: RefactoringKind::ExtractFunction,
+                    title: "Extract function".to_string(),
+                    edits,
+                });
+            }
+        }
+
+        refactorings
+    }
+}
+
+/// Returns the span of the byte `range` of the analyzed file.
+/// As the file is the first source in the session of a refactoring, its spans are byte offsets into it.
+fn span(range: Range<usize>) -> Span {
+    Span::new(BytePos::from_usize(range.start), BytePos::from_usize(range.end))
+}
+
+/// Parses `text` as the contents of the file at `path` in a session of its own, and applies a refactoring to it,
+/// returning the edits that turn `text` into the refactored program, or `None` if the refactoring fails.
+fn refactor(
+    path: &Path,
+    text: &str,
+    apply: impl FnOnce(&mut Compiler) -> Result<()>,
+) -> Option<Vec<(Range<usize>, String)>> {
+    SESSION_GLOBALS.set(&SessionGlobals::default(), || {
+        let (program_name, naming) = program_config(path);
+        // The errors of a refactoring that does not apply are not shown, since the editor asks for refactorings on every selection.
+        let (handler, _) = Handler::new_with_buf();
+        let mut compiler = Compiler::new(
+            program_name,
+            String::from("aleo"),
+            &handler,
+            path.to_path_buf(),
+            PathBuf::new(),
+            Some(OutputOptions {
+                naming,
+                ..Default::default()
+            }),
+        );
+        compiler
+            .parse_program_from_string(text, FileName::Real(path.to_path_buf()))
+            .ok()?;
+        let original = compiler.ast.as_repr().clone();
+        apply(&mut compiler).ok()?;
+        Some(edits(&original, compiler.ast.as_repr(), text))
+    })
+}
+
+/// Returns the edits that turn `text`, the source of `original`, into the source of `refactored`.
+/// Only the blocks of the functions that the refactoring changed are printed again, so that the comments elsewhere are kept,
+/// and each function that it added is inserted after the function that precedes it.
+fn edits(original: &Program, refactored: &Program, text: &str) -> Vec<(Range<usize>, String)> {
+    let mut edits = Vec::new();
+    for (scope, refactored_scope) in original.program_scopes.values().zip(refactored.program_scopes.values()) {
+        let mut previous: Option<&Function> = None;
+        for function in refactored_scope.functions.values() {
+            let existing = scope
+                .functions
+                .values()
+                .find(|existing| existing.identifier.name == function.identifier.name);
+            match (existing, previous) {
+                (Some(existing), _) => {
+                    edits.extend(block_edit(&existing.block, &function.block, text));
+                    if let (Some(finalize), Some(refactored_finalize)) = (&existing.finalize, &function.finalize) {
+                        edits.extend(block_edit(&finalize.block, &refactored_finalize.block, text));
+                    }
+                    previous = Some(existing);
+                }
+                (None, Some(previous)) => {
+                    let end = previous
+                        .finalize
+                        .as_ref()
+                        .map_or(previous.span, |finalize| finalize.span)
+                        .hi
+                        .to_usize();
+                    let indent = indentation(text, previous.span.lo.to_usize());
+                    let source = reindent(pretty::function(function).trim_end(), indent);
+                    edits.push((end..end, format!("\n\n{}{}", indent, source)));
+                }
+                (None, None) => {}
+            }
+        }
+    }
+    edits.sort_by_key(|(range, _)| range.start);
+    edits
+}
+
+/// Returns the edit that replaces `block` with `refactored`, if the refactoring changed it.
+fn block_edit(block: &Block, refactored: &Block, text: &str) -> Option<(Range<usize>, String)> {
+    let source = pretty::statement(&Statement::Block(refactored.clone()));
+    if pretty::statement(&Statement::Block(block.clone())) == source {
+        return None;
+    }
+    let range = block.span.lo.to_usize()..block.span.hi.to_usize();
+    let indent = indentation(text, range.start);
+    Some((range, reindent(source.trim_end(), indent)))
+}
+
+/// Returns the whitespace at the start of the line that contains the byte `offset` of `text`.
+fn indentation(text: &str, offset: usize) -> &str {
+    let line_start = text[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line = &text[line_start..];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Indents each line of `source` but the first by `indent`, since the first continues a line of the file.
+fn reindent(source: &str, indent: &str) -> String {
+    source
+        .split('\n')
+        .enumerate()
+        .map(|(index, line)| match index == 0 || line.is_empty() {
+            true => line.to_string(),
+            false => format!("{}{}", indent, line),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns a name for an extracted function that no item of `program` has.
+fn unused_function_name(program: &Program) -> Symbol {
+    let taken = |name: &str| {
+        program.program_scopes.values().any(|scope| {
+            scope.functions.keys().any(|id| id.name.to_string() == name)
+                || scope.structs.keys().any(|id| id.name.to_string() == name)
+                || scope.mappings.keys().any(|id| id.name.to_string() == name)
+        })
+    };
+    let name = (0..)
+        .map(|index| match index {
+            0 => EXTRACTED_FUNCTION.to_string(),
+            index => format!("{}_{}", EXTRACTED_FUNCTION, index),
+        })
+        .find(|name| !taken(name))
+        .unwrap();
+    Symbol::intern(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "program test.aleo {
+    // The sum of the inputs.
+    transition main(a: u8, b: u8) -> u8 {
+        let c: u8 = a + b;
+        let d: u8 = c * 2u8;
+        return d + c;
+    }
+}
+";
+
+    /// Returns the refactorings of `PROGRAM` at the selection from the start of `from` to the end of `to`.
+    fn refactorings(from: &str, to: &str) -> Vec<Refactoring> {
+        let start = PROGRAM.find(from).unwrap();
+        let end = PROGRAM.find(to).unwrap() + to.len();
+        Refactoring::available(Path::new("test.leo"), PROGRAM, start..end)
+    }
+
+    /// Returns `PROGRAM` with the edits of `refactoring` applied.
+    fn apply(refactoring: &Refactoring) -> String {
+        let mut text = PROGRAM.to_string();
+        for (range, replacement) in refactoring.edits.iter().rev() {
+            text.replace_range(range.clone(), replacement);
+        }
+        text
+    }
+
+    #[test]
+    fn inline_variable() {
+        let refactorings = refactorings("let d", "let d");
+        assert_eq!(refactorings.len(), 1);
+        assert_eq!(refactorings[0].kind, RefactoringKind::InlineVariable);
+        assert_eq!(
+            apply(&refactorings[0]),
+            "program test.aleo {
+    // The sum of the inputs.
+    transition main(a: u8, b: u8) -> u8 {
+        let c: u8 = a + b;
+        return c * 2u8 + c;
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn extract_function() {
+        let refactorings = refactorings("let d", "c * 2u8;");
+        let extract = refactorings
+            .iter()
+            .find(|refactoring| refactoring.kind == RefactoringKind::ExtractFunction)
+            .unwrap();
+        assert_eq!(
+            apply(extract),
+            "program test.aleo {
+    // The sum of the inputs.
+    transition main(a: u8, b: u8) -> u8 {
+        let c: u8 = a + b;
+        let d: u8 = extracted(c);
+        return d + c;
+    }
+
+    function extracted(c: u8) -> u8 {
+        let d: u8 = c * 2u8;
+        return d;
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn extract_function_rejected() {
+        // Both `c` and `d` are declared by the selection and used after it, but a function returns one of them.
+        let refactorings = refactorings("let c", "c * 2u8;");
+        assert!(refactorings
+            .iter()
+            .all(|refactoring| refactoring.kind != RefactoringKind::ExtractFunction));
+    }
+
+    #[test]
+    fn nothing_to_refactor() {
+        // The cursor is on a `return` statement, which defines no variable.
+        assert!(refactorings("return", "return").is_empty());
+        // A file that does not parse cannot be refactored.
+        assert!(Refactoring::available(Path::new("test.leo"), "program test.aleo {", 0..5).is_empty());
+    }
+}
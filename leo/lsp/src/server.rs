@@ -0,0 +1,304 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The language server, which answers the requests of an editor over the standard input and output.
+
+use crate::{Definition, DefinitionKind, Document, Refactoring, RefactoringKind, Severity};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument, Notification as _,
+        PublishDiagnostics,
+    },
+    request::{CodeActionRequest, DocumentSymbolRequest, GotoDefinition, HoverRequest, Request as _},
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, CodeActionProviderCapability,
+    CodeActionResponse, DiagnosticRelatedInformation, DiagnosticSeverity, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams, DocumentSymbol,
+    DocumentSymbolParams, DocumentSymbolResponse, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents,
+    HoverParams, HoverProviderCapability, LanguageString, Location, MarkedString, NumberOrString, OneOf,
+    PublishDiagnosticsParams, SaveOptions, ServerCapabilities, SymbolKind, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextDocumentSyncOptions, TextDocumentSyncSaveOptions, TextEdit, Url, WorkspaceEdit,
+};
+use serde::de::DeserializeOwned;
+use std::{collections::HashMap, error::Error};
+
+/// The errors that stop the language server.
+pub type ServerError = Box<dyn Error + Send + Sync>;
+
+/// Runs the language server until the editor shuts it down.
+pub fn run() -> Result<(), ServerError> {
+    let (connection, io_threads) = Connection::stdio();
+    connection.initialize(serde_json::to_value(capabilities())?)?;
+
+    let mut server = Server::default();
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    break;
+                }
+                connection
+                    .sender
+                    .send(Message::Response(server.handle_request(request)))?;
+            }
+            Message::Notification(notification) => {
+                for notification in server.handle_notification(notification)? {
+                    connection.sender.send(Message::Notification(notification))?;
+                }
+            }
+            Message::Response(_) => {}
+        }
+    }
+
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Returns the features that the language server provides.
+pub fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        // Diagnostics are published when a file is opened or saved, and the other features use the unsaved contents.
+        text_document_sync: Some(TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+            open_close: Some(true),
+            change: Some(TextDocumentSyncKind::FULL),
+            save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
+                include_text: Some(false),
+            })),
+            ..Default::default()
+        })),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        ..Default::default()
+    }
+}
+
+/// The state of the language server: the documents that are open in the editor.
+#[derive(Default)]
+pub struct Server {
+    documents: HashMap<Url, Document>,
+}
+
+impl Server {
+    /// Answers a request from the editor.
+    pub fn handle_request(&mut self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            HoverRequest::METHOD => parse_params(request).map(|params| serde_json::to_value(self.hover(params))),
+            GotoDefinition::METHOD => parse_params(request).map(|params| serde_json::to_value(self.definition(params))),
+            DocumentSymbolRequest::METHOD => {
+                parse_params(request).map(|params| serde_json::to_value(self.document_symbols(params)))
+            }
+            CodeActionRequest::METHOD => {
+                parse_params(request).map(|params| serde_json::to_value(self.code_actions(params)))
+            }
+            method => return error_response(id, ErrorCode::MethodNotFound, format!("unknown request `{method}`")),
+        };
+        match result {
+            Ok(Ok(result)) => Response::new_ok(id, result),
+            Ok(Err(error)) => error_response(id, ErrorCode::InternalError, error.to_string()),
+            Err(error) => error_response(id, ErrorCode::InvalidParams, error.to_string()),
+        }
+    }
+
+    /// Handles a notification from the editor, returning the notifications to send in reply.
+    pub fn handle_notification(&mut self, notification: Notification) -> Result<Vec<Notification>, ServerError> {
+        Ok(match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                match uri.to_file_path() {
+                    Ok(path) => {
+                        self.documents
+                            .insert(uri.clone(), Document::new(path, params.text_document.text));
+                        self.publish_diagnostics(&uri)
+                    }
+                    Err(()) => Vec::new(),
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+                // With full synchronization, the last change holds the whole contents of the document.
+                if let (Some(document), Some(change)) = (
+                    self.documents.get_mut(&params.text_document.uri),
+                    params.content_changes.into_iter().last(),
+                ) {
+                    document.set_text(change.text);
+                }
+                Vec::new()
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: DidSaveTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.publish_diagnostics(&params.text_document.uri)
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                // Clear the diagnostics of the closed document.
+                vec![Notification::new(
+                    PublishDiagnostics::METHOD.to_string(),
+                    PublishDiagnosticsParams::new(params.text_document.uri, Vec::new(), None),
+                )]
+            }
+            _ => Vec::new(),
+        })
+    }
+
+    /// Returns the notification that publishes the diagnostics of the document at `uri`.
+    fn publish_diagnostics(&mut self, uri: &Url) -> Vec<Notification> {
+        let document = match self.documents.get_mut(uri) {
+            Some(document) => document,
+            None => return Vec::new(),
+        };
+        let diagnostics = document.analysis().diagnostics.clone();
+        let diagnostics = diagnostics
+            .iter()
+            .map(|diagnostic| lsp_types::Diagnostic {
+                range: document.range(&diagnostic.range),
+                severity: Some(match diagnostic.severity {
+                    Severity::Error => DiagnosticSeverity::ERROR,
+                    Severity::Warning => DiagnosticSeverity::WARNING,
+                }),
+                code: diagnostic.code.clone().map(NumberOrString::String),
+                source: Some("leo".to_string()),
+                message: diagnostic.message.clone(),
+                related_information: Some(
+                    diagnostic
+                        .labels
+                        .iter()
+                        .map(|(range, message)| DiagnosticRelatedInformation {
+                            location: Location::new(uri.clone(), document.range(range)),
+                            message: message.clone(),
+                        })
+                        .collect(),
+                ),
+                ..Default::default()
+            })
+            .collect();
+
+        vec![Notification::new(
+            PublishDiagnostics::METHOD.to_string(),
+            PublishDiagnosticsParams::new(uri.clone(), diagnostics, None),
+        )]
+    }
+
+    /// Returns the declaration of the name under the cursor, or the type of the expression under it.
+    fn hover(&mut self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let document = self.documents.get_mut(&position.text_document.uri)?;
+        let offset = document.offset(position.position);
+        let analysis = document.analysis();
+        let value = match analysis.definition_at(offset) {
+            Some(definition) => definition.detail.clone(),
+            None => analysis.type_at(offset)?.to_string(),
+        };
+        Some(Hover {
+            contents: HoverContents::Scalar(MarkedString::LanguageString(LanguageString {
+                language: "leo".to_string(),
+                value,
+            })),
+            range: None,
+        })
+    }
+
+    /// Returns the location of the declaration of the name under the cursor.
+    fn definition(&mut self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let position = params.text_document_position_params;
+        let document = self.documents.get_mut(&position.text_document.uri)?;
+        let offset = document.offset(position.position);
+        let range = document.analysis().definition_at(offset)?.range.clone();
+        Some(GotoDefinitionResponse::Scalar(Location::new(
+            position.text_document.uri,
+            document.range(&range),
+        )))
+    }
+
+    /// Returns the structs, records, mappings, and functions of a document.
+    fn document_symbols(&mut self, params: DocumentSymbolParams) -> Option<DocumentSymbolResponse> {
+        let document = self.documents.get_mut(&params.text_document.uri)?;
+        let items = document.analysis().items.clone();
+        Some(DocumentSymbolResponse::Nested(
+            items.iter().map(|item| document_symbol(document, item)).collect(),
+        ))
+    }
+
+    /// Returns the refactorings that apply to the selection, i.e. inlining the variable defined at the cursor
+    /// and extracting the selected statements into a function.
+    fn code_actions(&mut self, params: CodeActionParams) -> Option<CodeActionResponse> {
+        let uri = params.text_document.uri;
+        let document = self.documents.get(&uri)?;
+        let selection = document.offset(params.range.start)..document.offset(params.range.end);
+        let actions = Refactoring::available(&document.path, &document.text, selection)
+            .into_iter()
+            .map(|refactoring| {
+                let edits = refactoring
+                    .edits
+                    .iter()
+                    .map(|(range, text)| TextEdit::new(document.range(range), text.clone()))
+                    .collect();
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    title: refactoring.title,
+                    kind: Some(match refactoring.kind {
+                        RefactoringKind::InlineVariable => CodeActionKind::REFACTOR_INLINE,
+                        RefactoringKind::ExtractFunction => CodeActionKind::REFACTOR_EXTRACT,
+                    }),
+                    edit: Some(WorkspaceEdit::new([(uri.clone(), edits)].into_iter().collect())),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        Some(actions)
+    }
+}
+
+/// Returns the symbol that describes `definition` in `document`.
+fn document_symbol(document: &Document, definition: &Definition) -> DocumentSymbol {
+    #[allow(deprecated)] // `deprecated` is superseded by `tags`, but must still be given.
+    DocumentSymbol {
+        name: definition.name.clone(),
+        detail: Some(definition.detail.clone()),
+        kind: match definition.kind {
+            DefinitionKind::Function => SymbolKind::FUNCTION,
+            DefinitionKind::Struct | DefinitionKind::Record => SymbolKind::STRUCT,
+            DefinitionKind::Member => SymbolKind::FIELD,
+            DefinitionKind::Mapping => SymbolKind::OBJECT,
+            DefinitionKind::Variable => SymbolKind::VARIABLE,
+        },
+        tags: None,
+        deprecated: None,
+        range: document.range(&definition.full_range),
+        selection_range: document.range(&definition.range),
+        children: Some(
+            definition
+                .children
+                .iter()
+                .map(|child| document_symbol(document, child))
+                .collect(),
+        ),
+    }
+}
+
+/// Returns the parameters of `request`.
+fn parse_params<P: DeserializeOwned>(request: Request) -> Result<P, serde_json::Error> {
+    serde_json::from_value(request.params)
+}
+
+/// Returns a response that reports an error to the editor.
+fn error_response(id: RequestId, code: ErrorCode, message: String) -> Response {
+    Response::new_err(id, code as i32, message)
+}
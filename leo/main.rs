@@ -17,12 +17,21 @@
 pub mod commands;
 pub mod context;
 pub mod logger;
+pub mod manifest;
+pub mod plugins;
+pub mod profiles;
+pub mod prover;
+pub mod scripts;
+pub mod signer;
 pub mod updater;
 
 use crate::commands::*;
 use crate::context::*;
-use leo_errors::Result;
-use leo_span::symbol::create_session_if_not_set_then;
+use crate::plugins::Plugin;
+use leo_errors::emitter::{Emitter, ErrorFormat};
+use leo_errors::{CliError, Result};
+use leo_span::symbol::{create_session_if_not_set_then, with_session_globals};
+use leo_span::SnippetConfig;
 
 use clap::StructOpt;
 use std::path::PathBuf;
@@ -51,6 +60,37 @@ pub struct CLI {
         parse(from_os_str)
     )]
     path: Option<PathBuf>,
+
+    #[structopt(
+        long,
+        global = true,
+        default_value = "human",
+        possible_values = &["human", "json"],
+        help = "Print diagnostics for humans, or as newline-delimited JSON"
+    )]
+    error_format: ErrorFormat,
+
+    #[structopt(
+        long,
+        global = true,
+        default_value = "0",
+        help = "The number of source lines shown before and after the location of a diagnostic"
+    )]
+    context_lines: usize,
+
+    #[structopt(
+        long,
+        global = true,
+        help = "The maximum width of the source lines in diagnostics [default: the width of the terminal]"
+    )]
+    diagnostic_width: Option<usize>,
+
+    #[structopt(
+        long,
+        global = true,
+        help = "Expand each tab in the source lines of diagnostics to this many spaces"
+    )]
+    tab_width: Option<usize>,
 }
 
 ///Leo compiler and package manager
@@ -72,11 +112,51 @@ enum Commands {
         #[structopt(flatten)]
         command: Build,
     },
+    #[structopt(about = "Check the current package for errors and warnings")]
+    Check {
+        #[structopt(flatten)]
+        command: Check,
+    },
     #[structopt(about = "Clean the output directory")]
     Clean {
         #[structopt(flatten)]
         command: Clean,
     },
+    #[structopt(about = "Apply automatic fixes to the current package's source files")]
+    Fix {
+        #[structopt(flatten)]
+        command: Fix,
+    },
+    #[structopt(about = "Format the current package's source files")]
+    Fmt {
+        #[structopt(flatten)]
+        command: Fmt,
+    },
+    #[structopt(about = "Run a function with the reference interpreter")]
+    Interpret {
+        #[structopt(flatten)]
+        command: Interpret,
+    },
+    #[structopt(about = "Debug a function with the reference interpreter")]
+    Debug {
+        #[structopt(flatten)]
+        command: Debug,
+    },
+    #[structopt(about = "Evaluate Leo expressions and definitions interactively")]
+    Repl {
+        #[structopt(flatten)]
+        command: Repl,
+    },
+    #[structopt(about = "Run the functions annotated with `@test`")]
+    Test {
+        #[structopt(flatten)]
+        command: Test,
+    },
+    #[structopt(about = "Show the metrics recorded by the builds of the current package")]
+    Stats {
+        #[structopt(flatten)]
+        command: Stats,
+    },
     #[structopt(about = "Run a program with input variables")]
     Run {
         #[structopt(flatten)]
@@ -85,11 +165,56 @@ enum Commands {
     #[structopt(subcommand)]
     Node(Node),
 
-    #[structopt(about = "Deploy a program")]
+    #[structopt(
+        subcommand,
+        about = "Inspect and reset the mapping entries kept by `leo run --no-snark --persist`"
+    )]
+    State(State),
+
+    #[structopt(subcommand, about = "Inspect the grammar of the Leo language")]
+    Grammar(Grammar),
+
+    #[structopt(subcommand, about = "Create, import and show accounts, and sign messages with them")]
+    Account(Account),
+
+    #[structopt(about = "Deploy a program, or estimate its deployment fee")]
     Deploy {
         #[structopt(flatten)]
         command: Deploy,
     },
+    #[structopt(about = "Execute a transition of the program on the Aleo network, or only produce its transaction")]
+    Execute {
+        #[structopt(flatten)]
+        command: Execute,
+    },
+    #[structopt(about = "Verify the proofs of an execution of the current package's program")]
+    Verify {
+        #[structopt(flatten)]
+        command: Verify,
+    },
+    #[structopt(about = "Start the Leo language server")]
+    Lsp {
+        #[structopt(flatten)]
+        command: Lsp,
+    },
+    #[structopt(about = "Fetch a deployed program from the Aleo network and add it as a dependency")]
+    Add {
+        #[structopt(flatten)]
+        command: Add,
+    },
+    #[structopt(about = "List the plugins on PATH, which are run as `leo <name>`")]
+    Plugins {
+        #[structopt(flatten)]
+        command: Plugins,
+    },
+    #[structopt(hide = true, about = "Compare the items and statements of two versions of a program")]
+    AstDiff {
+        #[structopt(flatten)]
+        command: AstDiff,
+    },
+    /// Runs the plugin `leo-<name>` on PATH.
+    #[structopt(external_subcommand)]
+    External(Vec<String>),
 }
 
 fn set_panic_hook() {
@@ -121,12 +246,13 @@ fn set_panic_hook() {
     });
 }
 
-pub fn handle_error<T>(res: Result<T>) -> T {
+pub fn handle_error<T>(res: Result<T>, error_format: ErrorFormat) -> T {
     match res {
         Ok(t) => t,
         Err(err) => {
-            eprintln!("{}", err);
-            exit(err.exit_code());
+            let code = err.exit_code();
+            error_format.emitter().emit_err(err);
+            exit(code);
         }
     }
 }
@@ -144,21 +270,66 @@ pub fn run_with_args(cli: CLI) -> Result<()> {
         )?;
     }
 
+    // Render the source lines of diagnostics to fit the terminal, unless a width is given.
+    let max_width = cli.diagnostic_width.or_else(|| {
+        console::Term::stderr()
+            .size_checked()
+            .map(|(_, columns)| columns as usize)
+    });
+    with_session_globals(|s| {
+        s.set_snippet_config(SnippetConfig {
+            context_before: cli.context_lines,
+            context_after: cli.context_lines,
+            max_width,
+            tab_width: cli.tab_width,
+        })
+    });
+
     // Get custom root folder and create context for it.
     // If not specified, default context will be created in cwd.
-    let context = handle_error(Context::new(cli.path));
+    let context = handle_error(Context::new(cli.path, cli.error_format), cli.error_format);
 
     match cli.command {
         Commands::New { command } => command.try_execute(context),
         Commands::Build { command } => command.try_execute(context),
+        Commands::Check { command } => command.try_execute(context),
         Commands::Clean { command } => command.try_execute(context),
+        Commands::Fix { command } => command.try_execute(context),
+        Commands::Fmt { command } => command.try_execute(context),
+        Commands::Repl { command } => command.try_execute(context),
         Commands::Run { command } => command.try_execute(context),
+        Commands::Test { command } => command.try_execute(context),
+        Commands::Stats { command } => command.try_execute(context),
         Commands::Node(command) => command.try_execute(context),
+        Commands::State(command) => command.try_execute(context),
+        Commands::Grammar(command) => command.try_execute(context),
         Commands::Deploy { command } => command.try_execute(context),
+        Commands::Account(command) => command.try_execute(context),
+        Commands::Interpret { command } => command.try_execute(context),
+        Commands::Debug { command } => command.try_execute(context),
+        Commands::Execute { command } => command.try_execute(context),
+        Commands::Verify { command } => command.try_execute(context),
+        Commands::Lsp { command } => command.try_execute(context),
+        Commands::Add { command } => command.try_execute(context),
+        Commands::Plugins { command } => command.try_execute(context),
+        Commands::AstDiff { command } => command.try_execute(context),
+        Commands::External(args) => {
+            let (name, args) = args
+                .split_first()
+                .expect("clap always passes the name of an external subcommand");
+            match Plugin::find(name) {
+                Some(plugin) => plugin.run(&context, args),
+                None => Err(CliError::unknown_command(name).into()),
+            }
+        }
     }
 }
 
 fn main() {
     set_panic_hook();
-    create_session_if_not_set_then(|_| handle_error(run_with_args(CLI::parse())));
+    create_session_if_not_set_then(|_| {
+        let cli = CLI::parse();
+        let error_format = cli.error_format;
+        handle_error(run_with_args(cli), error_format)
+    });
 }
@@ -0,0 +1,144 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The fields of `program.json` that Leo reads itself, besides the program id and version read by snarkVM.
+//! The manifest is untrusted input, so each field is read into a diagnostic rather than a panic when it is malformed.
+
+use crate::{profiles::Profile, scripts::Scripts};
+use leo_ast::FormatConfig;
+use leo_compiler::{HeaderConfig, ImportPolicy, LanguageVersion, NamingConfig};
+use leo_errors::{PackageError, Result};
+use leo_package::dependencies::{Dependency, DependencyDeclaration};
+
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// Parses the contents of a manifest as JSON.
+pub fn parse(manifest: &str) -> Result<Value> {
+    Ok(serde_json::from_str(manifest).map_err(PackageError::failed_to_open_manifest)?)
+}
+
+/// Returns the naming conventions configured under `lints.naming`.
+/// Conventions that are not configured keep their defaults.
+pub fn naming_config(manifest: &Value) -> Result<NamingConfig> {
+    match manifest.get("lints").and_then(|lints| lints.get("naming")) {
+        Some(naming) => Ok(serde_json::from_value(naming.clone()).map_err(PackageError::invalid_naming_config)?),
+        None => Ok(NamingConfig::default()),
+    }
+}
+
+/// Returns the header of generated code configured under `generated`.
+/// No header is written if it is not configured.
+pub fn header_config(manifest: &Value) -> Result<HeaderConfig> {
+    match manifest.get("generated") {
+        Some(generated) => Ok(serde_json::from_value(generated.clone()).map_err(PackageError::invalid_header_config)?),
+        None => Ok(HeaderConfig::default()),
+    }
+}
+
+/// Returns the layout of printed source and generated comments configured under `format`.
+/// Settings that are not configured keep their defaults.
+pub fn format_config(manifest: &Value) -> Result<FormatConfig> {
+    match manifest.get("format") {
+        Some(format) => Ok(serde_json::from_value(format.clone()).map_err(PackageError::invalid_format_config)?),
+        None => Ok(FormatConfig::default()),
+    }
+}
+
+/// Returns the programs that the package may import, listed under `allowed_imports` and `denied_imports`.
+/// Any program may be imported if `allowed_imports` is not configured.
+pub fn import_policy(manifest: &Value) -> Result<ImportPolicy> {
+    let names = |field: &str| -> Result<Option<Vec<String>>> {
+        match manifest.get(field) {
+            Some(names) => Ok(Some(
+                serde_json::from_value(names.clone()).map_err(|_| PackageError::invalid_import_policy(field))?,
+            )),
+            None => Ok(None),
+        }
+    };
+    Ok(ImportPolicy {
+        allowed: names("allowed_imports")?,
+        denied: names("denied_imports")?.unwrap_or_default(),
+    })
+}
+
+/// Returns the dependencies declared under `dependencies`, in the order they are declared.
+pub fn dependencies(manifest: &Value) -> Result<Vec<Dependency>> {
+    let declarations: IndexMap<String, DependencyDeclaration> = match manifest.get("dependencies") {
+        Some(dependencies) => {
+            serde_json::from_value(dependencies.clone()).map_err(PackageError::invalid_dependencies)?
+        }
+        None => return Ok(Vec::new()),
+    };
+    declarations
+        .into_iter()
+        .map(|(name, declaration)| Dependency::new(name, declaration))
+        .collect()
+}
+
+/// Returns the contents of `manifest` with the dependency `name` declared by `declaration`,
+/// replacing any previous declaration of it. The fields of the manifest keep their order.
+pub fn add_dependency(manifest: &str, name: &str, declaration: Value) -> Result<String> {
+    let mut fields: IndexMap<String, Value> =
+        serde_json::from_str(manifest).map_err(PackageError::failed_to_open_manifest)?;
+    let dependencies = fields
+        .entry("dependencies".to_string())
+        .or_insert_with(|| Value::Object(Default::default()));
+    match dependencies.as_object_mut() {
+        Some(dependencies) => dependencies.insert(name.to_string(), declaration),
+        None => return Err(PackageError::invalid_dependencies("expected an object").into()),
+    };
+    // Note that this unwrap is safe since the manifest was parsed from JSON.
+    Ok(serde_json::to_string_pretty(&fields).unwrap() + "\n")
+}
+
+/// Returns the scripts declared under `scripts`, which are run in the package at `package_path`.
+pub fn scripts(manifest: &Value, package_path: PathBuf) -> Result<Scripts> {
+    let program = manifest
+        .get("program")
+        .and_then(|program| program.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let scripts = match manifest.get("scripts") {
+        Some(scripts) => serde_json::from_value(scripts.clone()).map_err(PackageError::invalid_scripts)?,
+        None => IndexMap::new(),
+    };
+    Scripts::new(package_path, program, scripts)
+}
+
+/// Returns the version of Leo declared by the `leo` field, if any.
+/// Fails if the field is not a version string, or declares a version newer than the compiler.
+pub fn language_version(manifest: &Value) -> Result<Option<LanguageVersion>> {
+    let declared = match manifest.get("leo") {
+        Some(Value::String(declared)) => declared,
+        Some(declared) => return Err(PackageError::invalid_language_version(declared).into()),
+        None => return Ok(None),
+    };
+    let version = LanguageVersion::parse(declared).ok_or_else(|| PackageError::invalid_language_version(declared))?;
+    if version > LanguageVersion::CURRENT {
+        return Err(PackageError::unsupported_language_version(version, LanguageVersion::CURRENT).into());
+    }
+    Ok(Some(version))
+}
+
+/// Returns the network profiles configured under `networks`, which shadow the built-in profiles of the same name.
+pub fn network_profiles(manifest: &Value) -> Result<IndexMap<String, Profile>> {
+    match manifest.get("networks") {
+        Some(networks) => Ok(serde_json::from_value(networks.clone()).map_err(PackageError::invalid_network_profiles)?),
+        None => Ok(IndexMap::new()),
+    }
+}
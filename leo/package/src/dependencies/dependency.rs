@@ -0,0 +1,162 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The dependencies declared under `dependencies` in `program.json`.
+
+use crate::{
+    dependencies::Registry,
+    source::{MAIN_FILENAME, SOURCE_DIRECTORY_NAME},
+};
+use leo_errors::{PackageError, Result};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// The directory in the home directory of the user that caches the programs fetched from the network.
+pub const REGISTRY_DIRECTORY: &str = ".leo/registry";
+
+/// The directory in the home directory of the user that holds the checkouts of dependencies from git.
+pub const GIT_DIRECTORY: &str = ".leo/git";
+
+/// A dependency as it is written in `program.json`, e.g. `"token": { "version": "0.1.0", "network": "testnet3" }`.
+/// Exactly one of `network`, `git` and `path` names where the dependency comes from.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DependencyDeclaration {
+    /// The version of the dependency.
+    pub version: Option<String>,
+    /// The network that the dependency is published on.
+    pub network: Option<String>,
+    /// The URL of the git repository of the dependency.
+    pub git: Option<String>,
+    /// The revision of the git repository, if the dependency is not taken from its default branch.
+    pub rev: Option<String>,
+    /// The path of the dependency, relative to the package.
+    pub path: Option<PathBuf>,
+}
+
+/// Where a dependency comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DependencySource {
+    /// A program published on a network, e.g. `testnet3`, that is cached in the registry of the user.
+    Network(String),
+    /// A git repository, checked out into the home directory of the user, at a revision if there is one.
+    Git { url: String, rev: Option<String> },
+    /// A package or a directory with the Leo file of the program, relative to the package.
+    Path(PathBuf),
+}
+
+impl fmt::Display for DependencySource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Network(network) => write!(f, "network+{}", network),
+            Self::Git { url, rev: Some(rev) } => write!(f, "git+{}#{}", url, rev),
+            Self::Git { url, rev: None } => write!(f, "git+{}", url),
+            Self::Path(path) => write!(f, "path+{}", path.display()),
+        }
+    }
+}
+
+/// A dependency of the package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dependency {
+    /// The name of the program, without the `.aleo` or `.leo` extension.
+    pub name: String,
+    /// The version of the dependency, if it is declared.
+    pub version: Option<String>,
+    /// Where the dependency comes from.
+    pub source: DependencySource,
+}
+
+/// A dependency whose file was found, with the checksum of the file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolvedDependency {
+    /// The dependency as it is declared.
+    pub dependency: Dependency,
+    /// The file that the program is built from:
+    /// its bytecode if it was fetched from a network, and its Leo file otherwise.
+    pub file: PathBuf,
+    /// The SHA-256 hash of the file.
+    pub checksum: String,
+}
+
+impl Dependency {
+    /// Returns the dependency named `name` in `program.json`, as it is declared by `declaration`.
+    pub fn new(name: String, declaration: DependencyDeclaration) -> Result<Self> {
+        let has_rev = declaration.rev.is_some();
+        let source = match (declaration.network, declaration.git, declaration.path) {
+            (Some(network), None, None) => DependencySource::Network(network),
+            (None, Some(url), None) => DependencySource::Git {
+                url,
+                rev: declaration.rev,
+            },
+            (None, None, Some(path)) => DependencySource::Path(path),
+            _ => return Err(PackageError::invalid_dependency_source(name).into()),
+        };
+        if has_rev && !matches!(source, DependencySource::Git { .. }) {
+            return Err(PackageError::invalid_dependency_source(name).into());
+        }
+
+        Ok(Self {
+            name,
+            version: declaration.version,
+            source,
+        })
+    }
+
+    /// Finds the file that the dependency is built from and hashes it.
+    /// Paths are relative to `package_path`, and dependencies from networks and git are looked for in `home`.
+    pub fn resolve(&self, package_path: &Path, home: Option<&Path>) -> Result<ResolvedDependency> {
+        let file = match &self.source {
+            DependencySource::Network(_) => home.map(|home| Registry::at_home(home).bytecode_path(&self.name)),
+            DependencySource::Git { .. } => {
+                home.map(|home| self.program_file(&home.join(GIT_DIRECTORY).join(&self.name)))
+            }
+            DependencySource::Path(path) => Some(self.program_file(&package_path.join(path))),
+        };
+        let file = file
+            .filter(|file| file.is_file())
+            .ok_or_else(|| PackageError::dependency_not_found(&self.name, &self.source))?;
+
+        let contents = fs::read(&file).map_err(|err| PackageError::failed_to_read_file(file.display(), err))?;
+        Ok(ResolvedDependency {
+            dependency: self.clone(),
+            checksum: format!("{:x}", Sha256::digest(&contents)),
+            file,
+        })
+    }
+
+    /// Returns the Leo file of the program in `directory`, which is either a directory with `<name>.leo`,
+    /// or a package whose program is in `src/main.leo`.
+    fn program_file(&self, directory: &Path) -> PathBuf {
+        let file = directory.join(format!("{}.leo", self.name));
+        match file.is_file() {
+            true => file,
+            false => directory.join(SOURCE_DIRECTORY_NAME).join(MAIN_FILENAME),
+        }
+    }
+}
+
+/// Returns the home directory of the user, if it is known.
+pub fn home_directory() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
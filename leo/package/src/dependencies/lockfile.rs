@@ -0,0 +1,184 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The `leo.lock` file, which pins the checksums of the dependencies of a package.
+
+use crate::dependencies::{Dependency, ResolvedDependency};
+use leo_errors::{PackageError, Result};
+
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, fs, path::Path};
+
+pub static LOCKFILE_FILENAME: &str = "leo.lock";
+
+/// The comment written at the top of the lockfile.
+const LOCKFILE_HEADER: &str = "# This file is generated by `leo build` and pins the checksums of the dependencies.\n\
+# Commit it, so that every build of the package uses the same dependencies.\n\n";
+
+/// The dependencies of a package, as they were resolved when the lockfile was written.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Lockfile {
+    #[serde(default, rename = "dependency")]
+    pub dependencies: Vec<LockedDependency>,
+}
+
+/// A dependency pinned by the lockfile.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LockedDependency {
+    /// The name of the program.
+    pub name: String,
+    /// The version of the dependency, if it is declared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Where the dependency comes from, e.g. `network+testnet3` or `path+../token`.
+    pub source: String,
+    /// The SHA-256 hash of the file that the program is built from.
+    pub checksum: String,
+}
+
+impl From<&ResolvedDependency> for LockedDependency {
+    fn from(resolved: &ResolvedDependency) -> Self {
+        Self {
+            name: resolved.dependency.name.clone(),
+            version: resolved.dependency.version.clone(),
+            source: resolved.dependency.source.to_string(),
+            checksum: resolved.checksum.clone(),
+        }
+    }
+}
+
+impl Lockfile {
+    /// Returns the lockfile that pins the given dependencies.
+    pub fn new(resolved: &[ResolvedDependency]) -> Self {
+        Self {
+            dependencies: resolved.iter().map(LockedDependency::from).collect(),
+        }
+    }
+
+    pub fn exists_at(path: &Path) -> bool {
+        Self::setup_file_path(path).exists()
+    }
+
+    /// Reads the lockfile of the package at `path`, or returns `None` if it has none.
+    pub fn read_from(path: &Path) -> Result<Option<Self>> {
+        let path = Self::setup_file_path(path);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let string = fs::read_to_string(&path).map_err(|err| PackageError::failed_to_read_file(path.display(), err))?;
+        let lockfile = toml::from_str(&string).map_err(|err| PackageError::invalid_lockfile(path.display(), err))?;
+        Ok(Some(lockfile))
+    }
+
+    /// Writes the lockfile of the package at `path`.
+    pub fn write_to(&self, path: &Path) -> Result<()> {
+        let path = Self::setup_file_path(path);
+        let contents = toml::to_string(self).map_err(|err| PackageError::invalid_lockfile(path.display(), err))?;
+        fs::write(&path, format!("{}{}", LOCKFILE_HEADER, contents))
+            .map_err(|err| PackageError::failed_to_write_file(path.display(), err))?;
+        Ok(())
+    }
+
+    /// Checks that each dependency that is pinned with the same name, version and source still has the same checksum.
+    /// Dependencies that were added, removed or declared differently since the lockfile was written are not checked.
+    pub fn verify(&self, resolved: &[ResolvedDependency]) -> Result<()> {
+        for current in resolved.iter().map(LockedDependency::from) {
+            let locked = self.dependencies.iter().find(|locked| {
+                locked.name == current.name && locked.version == current.version && locked.source == current.source
+            });
+            if let Some(locked) = locked.filter(|locked| locked.checksum != current.checksum) {
+                return Err(PackageError::lockfile_drift(&current.name, &locked.checksum, &current.checksum).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolves the dependencies of the package at `path` and checks them against its lockfile.
+    /// The lockfile is written if the dependencies changed since it was written, or if the package has none
+    /// and declares dependencies.
+    pub fn update(path: &Path, dependencies: &[Dependency], home: Option<&Path>) -> Result<Self> {
+        let resolved = dependencies
+            .iter()
+            .map(|dependency| dependency.resolve(path, home))
+            .collect::<Result<Vec<_>>>()?;
+        let lockfile = Self::new(&resolved);
+
+        match Self::read_from(path)? {
+            Some(locked) => {
+                locked.verify(&resolved)?;
+                if locked != lockfile {
+                    lockfile.write_to(path)?;
+                }
+            }
+            None if !lockfile.dependencies.is_empty() => lockfile.write_to(path)?,
+            None => {}
+        }
+        Ok(lockfile)
+    }
+
+    fn setup_file_path(path: &Path) -> Cow<'_, Path> {
+        let mut path = Cow::from(path);
+        if path.is_dir() {
+            path.to_mut().push(LOCKFILE_FILENAME);
+        }
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::DependencySource;
+
+    use std::path::PathBuf;
+
+    fn resolved(name: &str, source: DependencySource, checksum: &str) -> ResolvedDependency {
+        ResolvedDependency {
+            dependency: Dependency {
+                name: name.to_string(),
+                version: Some("0.1.0".to_string()),
+                source,
+            },
+            file: PathBuf::from(format!("{}.leo", name)),
+            checksum: checksum.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_lockfile_round_trip() {
+        let lockfile = Lockfile::new(&[
+            resolved("token", DependencySource::Network("testnet3".to_string()), "ab"),
+            resolved("utils", DependencySource::Path(PathBuf::from("../utils")), "cd"),
+        ]);
+        let string = toml::to_string(&lockfile).unwrap();
+        assert_eq!(toml::from_str::<Lockfile>(&string).unwrap(), lockfile);
+        assert!(string.contains("source = \"path+../utils\""));
+    }
+
+    #[test]
+    fn test_lockfile_drift() {
+        let token = |checksum| resolved("token", DependencySource::Network("testnet3".to_string()), checksum);
+        let lockfile = Lockfile::new(&[token("ab")]);
+
+        assert!(lockfile.verify(&[token("ab")]).is_ok());
+        assert!(lockfile.verify(&[token("cd")]).is_err());
+
+        // A dependency that is declared from another source is pinned again rather than rejected.
+        let moved = resolved("token", DependencySource::Path(PathBuf::from("../token")), "cd");
+        assert!(lockfile.verify(&[moved]).is_ok());
+    }
+}
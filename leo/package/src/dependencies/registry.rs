@@ -0,0 +1,88 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The registry of programs fetched from the Aleo network, cached in the home directory of the user.
+
+use crate::dependencies::REGISTRY_DIRECTORY;
+use leo_errors::{PackageError, Result};
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The REST endpoint of the Aleo node that programs are fetched from by default.
+pub const DEFAULT_ENDPOINT: &str = "https://vm.aleo.org/api";
+
+/// Downloads the bytecode of deployed programs from the REST API of an Aleo node.
+pub struct Fetcher {
+    endpoint: String,
+    network: String,
+}
+
+impl Fetcher {
+    /// Returns a fetcher for the programs deployed on `network`, e.g. `testnet3`, served by the node at `endpoint`.
+    pub fn new(endpoint: &str, network: &str) -> Self {
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            network: network.to_string(),
+        }
+    }
+
+    /// Returns the bytecode of the program `program_id`, e.g. `token.aleo`.
+    pub fn fetch(&self, program_id: &str) -> Result<String> {
+        let url = format!("{}/{}/program/{}", self.endpoint, self.network, program_id);
+        let body = reqwest::blocking::get(&url)
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.text())
+            .map_err(|err| PackageError::failed_to_fetch_program(program_id, &url, err))?;
+
+        // Nodes serve the program as a JSON string.
+        Ok(serde_json::from_str::<String>(&body).unwrap_or(body))
+    }
+}
+
+/// The bytecode of the programs fetched from the network, in `<name>.aleo` files.
+/// Leo programs import them with `import <name>.aleo;`, and they are built along with the programs that import them.
+pub struct Registry {
+    directory: PathBuf,
+}
+
+impl Registry {
+    /// Returns the registry in `directory`.
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// Returns the registry in the home directory `home`.
+    pub fn at_home(home: &Path) -> Self {
+        Self::new(home.join(REGISTRY_DIRECTORY))
+    }
+
+    /// Returns the path of the bytecode of the program `name`, without its `.aleo` extension.
+    pub fn bytecode_path(&self, name: &str) -> PathBuf {
+        self.directory.join(format!("{}.aleo", name))
+    }
+
+    /// Stores the bytecode of the program `name`.
+    pub fn store(&self, name: &str, bytecode: &str) -> Result<()> {
+        fs::create_dir_all(&self.directory)
+            .map_err(|err| PackageError::failed_to_create_directory(self.directory.display(), err))?;
+        let path = self.bytecode_path(name);
+        fs::write(&path, bytecode).map_err(|err| PackageError::failed_to_write_file(path.display(), err))?;
+        Ok(())
+    }
+}
@@ -27,11 +27,14 @@ use std::{
         File, {self},
     },
     io::Write,
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 pub static INPUT_FILE_EXTENSION: &str = ".in";
 
+/// The extensions of input files in TOML and JSON, which are read if there is no `.in` file.
+pub static INPUT_DATA_EXTENSIONS: &[&str] = &["toml", "json"];
+
 #[derive(Deserialize)]
 pub struct InputFile {
     pub package_name: String,
@@ -49,8 +52,7 @@ impl InputFile {
     }
 
     pub fn exists_at(&self, path: &Path) -> bool {
-        let path = self.setup_file_path(path);
-        path.exists()
+        self.find_file_path(path).exists()
     }
 
     /// Reads the program input variables from the given file path if it exists.
@@ -94,4 +96,17 @@ b: u32 = 2u32;
         }
         path
     }
+    /// Returns the path of the input file: the `.in` file if it exists, or else the TOML or JSON file that exists.
+    /// The path of the `.in` file is returned if none exists.
+    pub fn find_file_path(&self, path: &Path) -> PathBuf {
+        let path = self.setup_file_path(path).into_owned();
+        if path.exists() {
+            return path;
+        }
+        INPUT_DATA_EXTENSIONS
+            .iter()
+            .map(|extension| path.with_extension(extension))
+            .find(|path| path.exists())
+            .unwrap_or(path)
+    }
 }
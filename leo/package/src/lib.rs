@@ -18,12 +18,14 @@
 #![doc = include_str!("../README.md")]
 
 pub mod build;
+pub mod dependencies;
 pub mod imports;
 pub mod inputs;
 pub mod outputs;
 pub mod package;
 pub mod root;
 pub mod source;
+pub mod workspace;
 
 use leo_errors::{PackageError, Result};
 
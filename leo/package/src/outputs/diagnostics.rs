@@ -0,0 +1,79 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The warnings reported by the last check of the package.
+
+use crate::outputs::OUTPUTS_DIRECTORY_NAME;
+use leo_errors::{PackageError, Result};
+
+use std::{
+    borrow::Cow,
+    fs::{self, File},
+    io::Write,
+    path::Path,
+};
+
+pub static DIAGNOSTICS_FILE_EXTENSION: &str = ".diagnostics";
+
+/// A file storing the fingerprint of each warning reported by the last check, one per line.
+pub struct DiagnosticsFile {
+    pub package_name: String,
+}
+
+impl DiagnosticsFile {
+    pub fn new(package_name: &str) -> Self {
+        Self {
+            package_name: package_name.to_string(),
+        }
+    }
+
+    pub fn exists_at(&self, path: &Path) -> bool {
+        let path = self.setup_file_path(path);
+        path.exists()
+    }
+
+    /// Reads the warning fingerprints from the given file path if it exists.
+    pub fn read_from(&self, path: &Path) -> Result<Vec<String>> {
+        let path = self.setup_file_path(path);
+
+        let string =
+            fs::read_to_string(&path).map_err(|_| PackageError::failed_to_read_diagnostics_file(path.into_owned()))?;
+        Ok(string.lines().map(|line| line.to_string()).collect())
+    }
+
+    /// Writes the given warning fingerprints to a file.
+    pub fn write_to(&self, path: &Path, fingerprints: &[String]) -> Result<()> {
+        let path = self.setup_file_path(path);
+        let mut file = File::create(&path).map_err(PackageError::io_error_diagnostics_file)?;
+
+        for fingerprint in fingerprints {
+            writeln!(file, "{fingerprint}").map_err(PackageError::io_error_diagnostics_file)?;
+        }
+        Ok(())
+    }
+
+    fn setup_file_path<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
+        let mut path = Cow::from(path);
+        if path.is_dir() {
+            if !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
+                path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
+            }
+            path.to_mut()
+                .push(format!("{}{}", self.package_name, DIAGNOSTICS_FILE_EXTENSION));
+        }
+        path
+    }
+}
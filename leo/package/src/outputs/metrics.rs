@@ -0,0 +1,111 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The metrics of the builds of the package, which are only ever written locally.
+
+use crate::outputs::OUTPUTS_DIRECTORY_NAME;
+use leo_errors::{PackageError, Result};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+pub static METRICS_FILE_NAME: &str = "metrics.ndjson";
+
+/// The metrics of a build, e.g. how long each compiler pass took.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BuildMetrics {
+    /// When the build started, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The version of Leo that ran the build.
+    pub leo_version: String,
+    /// Whether the build succeeded.
+    pub success: bool,
+    /// How long the whole build took, in milliseconds.
+    pub duration_ms: f64,
+    /// The metrics of each compiled Leo file.
+    pub files: Vec<FileMetrics>,
+    /// The size in bytes of each file in the build directory, by its path relative to the directory.
+    pub artifacts: IndexMap<String, u64>,
+    /// The number of errors reported by the build.
+    pub errors: usize,
+    /// The number of warnings reported by the build.
+    pub warnings: usize,
+}
+
+/// The metrics of the compilation of a Leo file.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FileMetrics {
+    /// The name of the file, e.g. `main.leo`.
+    pub file: String,
+    /// Whether the checksum of the file matched that of the last build, so that its output could have been reused.
+    pub cache_hit: bool,
+    /// How long each compiler pass took, in milliseconds, in the order they ran.
+    pub passes: IndexMap<String, f64>,
+}
+
+/// A file storing the metrics of each build of the package, as one JSON object per line.
+pub struct MetricsFile;
+
+impl MetricsFile {
+    pub fn exists_at(path: &Path) -> bool {
+        let path = Self::setup_file_path(path);
+        path.exists()
+    }
+
+    /// Reads the metrics of all builds from the given file path, from the oldest to the newest.
+    pub fn read_from(path: &Path) -> Result<Vec<BuildMetrics>> {
+        let path = Self::setup_file_path(path);
+
+        let string =
+            fs::read_to_string(&path).map_err(|_| PackageError::failed_to_read_metrics_file(path.into_owned()))?;
+        string
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(|e| PackageError::invalid_metrics_record(e).into()))
+            .collect()
+    }
+
+    /// Appends the metrics of a build to the file at the given path, creating it if it doesn't exist.
+    pub fn append_to(path: &Path, metrics: &BuildMetrics) -> Result<()> {
+        let path = Self::setup_file_path(path);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(PackageError::io_error_metrics_file)?;
+
+        let record = serde_json::to_string(metrics).map_err(PackageError::io_error_metrics_file)?;
+        writeln!(file, "{record}").map_err(PackageError::io_error_metrics_file)?;
+        Ok(())
+    }
+
+    fn setup_file_path(path: &Path) -> Cow<'_, Path> {
+        let mut path = Cow::from(path);
+        if path.is_dir() {
+            if !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
+                path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
+            }
+            path.to_mut().push(METRICS_FILE_NAME);
+        }
+        path
+    }
+}
@@ -23,7 +23,13 @@ pub use self::circuit::*;
 pub mod checksum;
 pub use self::checksum::*;
 
+pub mod diagnostics;
+pub use self::diagnostics::*;
+
 pub mod directory;
 pub use directory::*;
 
+pub mod metrics;
+pub use self::metrics::*;
+
 pub static MAIN_ALEO_FILE_NAME: &str = "main.aleo";
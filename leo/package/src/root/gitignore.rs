@@ -52,6 +52,7 @@ impl Gitignore {
     }
 
     fn template(&self) -> String {
-        "outputs/\nbuild/\n".to_string()
+        // The `.env` file may hold private keys, so it is not committed.
+        "outputs/\nbuild/\n.env\n".to_string()
     }
 }
@@ -0,0 +1,164 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The `leo.workspace.json` file, which lists the packages of a workspace that are built together.
+
+use crate::dependencies::{Dependency, DependencySource};
+use leo_errors::{PackageError, Result};
+
+use serde::Deserialize;
+use std::{
+    borrow::Cow,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+pub static WORKSPACE_FILENAME: &str = "leo.workspace.json";
+
+/// The packages of a workspace, e.g. a token program and a DEX that imports it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Workspace {
+    /// The directories of the member packages, relative to the workspace.
+    pub members: Vec<PathBuf>,
+}
+
+impl Workspace {
+    pub fn exists_at(path: &Path) -> bool {
+        Self::setup_file_path(path).exists()
+    }
+
+    /// Reads the workspace manifest in the directory `path`.
+    pub fn read_from(path: &Path) -> Result<Self> {
+        let path = Self::setup_file_path(path);
+        let string = fs::read_to_string(&path).map_err(|err| PackageError::failed_to_read_file(path.display(), err))?;
+        let workspace =
+            serde_json::from_str(&string).map_err(|err| PackageError::invalid_workspace(path.display(), err))?;
+        Ok(workspace)
+    }
+
+    /// Returns the indices of the members in the order they are built in, given the `dependencies` of each member:
+    /// each member is built after the members that it declares as `path` dependencies,
+    /// and otherwise in the order they are listed in.
+    pub fn build_order(&self, dependencies: &[Vec<Dependency>]) -> Result<Vec<usize>> {
+        let members = self.members.iter().map(|member| normalize(member)).collect::<Vec<_>>();
+        let edges = members
+            .iter()
+            .zip(dependencies)
+            .map(|(member, dependencies)| {
+                dependencies
+                    .iter()
+                    .filter_map(|dependency| match &dependency.source {
+                        DependencySource::Path(path) => {
+                            let path = normalize(&member.join(path));
+                            members.iter().position(|other| *other == path)
+                        }
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut order = Vec::new();
+        let mut path = Vec::new();
+        for index in 0..members.len() {
+            self.visit(index, &edges, &mut path, &mut order)?;
+        }
+        Ok(order)
+    }
+
+    /// Adds the member `index` to `order` after the members that it depends on,
+    /// where `path` holds the members that depend on it and are not in `order` yet.
+    fn visit(&self, index: usize, edges: &[Vec<usize>], path: &mut Vec<usize>, order: &mut Vec<usize>) -> Result<()> {
+        if order.contains(&index) {
+            return Ok(());
+        }
+        if let Some(start) = path.iter().position(|member| *member == index) {
+            let cycle = path[start..]
+                .iter()
+                .chain(Some(&index))
+                .map(|member| format!("`{}`", self.members[*member].display()))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(PackageError::cyclic_workspace_dependencies(cycle).into());
+        }
+
+        path.push(index);
+        for dependency in edges[index].iter() {
+            self.visit(*dependency, edges, path, order)?;
+        }
+        path.pop();
+        order.push(index);
+        Ok(())
+    }
+
+    fn setup_file_path(path: &Path) -> Cow<'_, Path> {
+        let mut path = Cow::from(path);
+        if path.is_dir() {
+            path.to_mut().push(WORKSPACE_FILENAME);
+        }
+        path
+    }
+}
+
+/// Returns `path` with its `.` and `..` components resolved, without accessing the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_dependency(name: &str, path: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            version: None,
+            source: DependencySource::Path(PathBuf::from(path)),
+        }
+    }
+
+    #[test]
+    fn test_build_order() {
+        let workspace: Workspace = serde_json::from_str(r#"{ "members": ["dex", "token", "./utils"] }"#).unwrap();
+        let dependencies = vec![
+            vec![
+                path_dependency("token", "../token"),
+                path_dependency("utils", "../utils"),
+            ],
+            vec![path_dependency("utils", "../utils/")],
+            vec![],
+        ];
+        assert_eq!(workspace.build_order(&dependencies).unwrap(), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn test_build_order_cycle() {
+        let workspace: Workspace = serde_json::from_str(r#"{ "members": ["a", "b"] }"#).unwrap();
+        let dependencies = vec![vec![path_dependency("b", "../b")], vec![path_dependency("a", "../a")]];
+        assert!(workspace.build_order(&dependencies).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The plugins of the CLI: executables named `leo-<name>` on `PATH`, which are run by `leo <name>`.
+
+use crate::{commands::Network, context::Context};
+use leo_errors::emitter::ErrorFormat;
+use leo_errors::{CliError, PackageError, Result};
+use leo_package::{
+    build::BUILD_DIRECTORY_NAME,
+    imports::IMPORTS_DIRECTORY_NAME,
+    inputs::INPUTS_DIRECTORY_NAME,
+    outputs::OUTPUTS_DIRECTORY_NAME,
+    source::{MAIN_FILENAME, SOURCE_DIRECTORY_NAME},
+};
+
+use serde_json::json;
+use snarkvm::{file::Manifest, prelude::Network as _};
+use std::{
+    env,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// The prefix of the file name of a plugin.
+pub const PLUGIN_PREFIX: &str = "leo-";
+
+/// The version of the JSON document that plugins receive on stdin.
+/// It is incremented whenever a field is removed or changes meaning; fields may be added without notice.
+pub const PLUGIN_INTERFACE_VERSION: u32 = 1;
+
+/// An executable named `leo-<name>` on `PATH`, which extends the CLI with the subcommand `leo <name>`.
+///
+/// The plugin is run in the current directory with the arguments that follow its name, and receives
+/// the context of the invocation as a JSON document on stdin:
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "leo_version": "1.5.3",
+///   "error_format": "human",
+///   "network": { "id": 3, "name": "Aleo Testnet3" },
+///   "package": {
+///     "path": "/home/user/hello",
+///     "manifest": { "program": "hello.aleo", "version": "0.0.0", "description": "", "license": "MIT" },
+///     "layout": { "source": "...", "main": "...", "inputs": "...", "imports": "...", "build": "...", "outputs": "..." }
+///   }
+/// }
+/// ```
+///
+/// `package` is `null` when the command is not run in a Leo package.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Plugin {
+    /// The name of the subcommand, e.g. `audit` for `leo-audit`.
+    pub name: String,
+    /// The path to the executable.
+    pub path: PathBuf,
+}
+
+impl Plugin {
+    /// Returns the plugin named `name`, from the first directory of `PATH` that contains it.
+    pub fn find(name: &str) -> Option<Self> {
+        let file_name = format!("{}{}{}", PLUGIN_PREFIX, name, env::consts::EXE_SUFFIX);
+        path_directories()
+            .map(|directory| directory.join(&file_name))
+            .find(|path| is_executable(path))
+            .map(|path| Self {
+                name: name.to_string(),
+                path,
+            })
+    }
+
+    /// Returns all the plugins on `PATH`, sorted by name.
+    /// A plugin found in several directories is listed once, from the first of them.
+    pub fn discover() -> Vec<Self> {
+        let mut plugins: Vec<Self> = Vec::new();
+        for directory in path_directories() {
+            let entries = match std::fs::read_dir(&directory) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let name = path
+                    .file_name()
+                    .and_then(|file_name| file_name.to_str())
+                    .and_then(|file_name| file_name.strip_prefix(PLUGIN_PREFIX))
+                    .and_then(|name| name.strip_suffix(env::consts::EXE_SUFFIX));
+                if let Some(name) = name {
+                    if !name.is_empty() && is_executable(&path) && plugins.iter().all(|plugin| plugin.name != name) {
+                        plugins.push(Self {
+                            name: name.to_string(),
+                            path,
+                        });
+                    }
+                }
+            }
+        }
+        plugins.sort_by(|a, b| a.name.cmp(&b.name));
+        plugins
+    }
+
+    /// Runs the plugin with `args`, writing the context of the invocation to its stdin.
+    pub fn run(&self, context: &Context, args: &[String]) -> Result<()> {
+        let input = plugin_input(context)?;
+
+        let mut child = Command::new(&self.path)
+            .args(args)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|error| CliError::failed_to_run_plugin(&self.name, error))?;
+
+        // The plugin may exit without reading its input, so a closed pipe is not an error.
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(input.to_string().as_bytes());
+        }
+
+        let status = child
+            .wait()
+            .map_err(|error| CliError::failed_to_run_plugin(&self.name, error))?;
+        if !status.success() {
+            return Err(CliError::plugin_failed(&self.name, status).into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the JSON document describing the context of an invocation, which plugins receive on stdin.
+fn plugin_input(context: &Context) -> Result<serde_json::Value> {
+    let path = context.dir()?;
+    let manifest_path = path.join(Manifest::<Network>::file_name());
+
+    let package = match manifest_path.exists() {
+        true => {
+            let manifest_string =
+                std::fs::read_to_string(&manifest_path).map_err(PackageError::failed_to_open_manifest)?;
+            let manifest: serde_json::Value =
+                serde_json::from_str(&manifest_string).map_err(PackageError::failed_to_open_manifest)?;
+            let source = path.join(SOURCE_DIRECTORY_NAME);
+            json!({
+                "path": path,
+                "manifest": manifest,
+                "layout": {
+                    "main": source.join(MAIN_FILENAME),
+                    "source": source,
+                    "inputs": path.join(INPUTS_DIRECTORY_NAME),
+                    "imports": path.join(IMPORTS_DIRECTORY_NAME),
+                    "build": path.join(BUILD_DIRECTORY_NAME),
+                    "outputs": path.join(OUTPUTS_DIRECTORY_NAME),
+                },
+            })
+        }
+        false => serde_json::Value::Null,
+    };
+
+    Ok(json!({
+        "version": PLUGIN_INTERFACE_VERSION,
+        "leo_version": env!("CARGO_PKG_VERSION"),
+        "error_format": match context.error_format {
+            ErrorFormat::Human => "human",
+            ErrorFormat::Json => "json",
+        },
+        "network": { "id": Network::ID, "name": Network::NAME },
+        "package": package,
+    }))
+}
+
+/// Returns the directories of `PATH`, in order.
+fn path_directories() -> impl Iterator<Item = PathBuf> {
+    env::var_os("PATH")
+        .map(|paths| env::split_paths(&paths).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+}
+
+/// Returns whether `path` is a file that can be executed.
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Network profiles, which name the node that commands talk to, the network it serves and the account that signs,
+//! so that `run`, `deploy` and `execute` switch between e.g. a local devnet and the testnet with `--network`.
+//! Besides the built-in profiles, profiles are configured under `networks` in `program.json`,
+//! and the `.env` file of the package sets the default profile and the variables that profiles refer to.
+
+use crate::signer::PRIVATE_KEY_VAR;
+use leo_errors::{CliError, PackageError, Result};
+use leo_package::dependencies::DEFAULT_ENDPOINT;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+use std::path::Path;
+
+/// The file with the environment variables of the package.
+pub const ENV_FILENAME: &str = ".env";
+
+/// The variable that names the profile used when `--network` is not given.
+pub const NETWORK_VAR: &str = "LEO_NETWORK";
+
+/// The profile used when neither `--network` nor `LEO_NETWORK` name one.
+pub const DEFAULT_PROFILE: &str = "testnet";
+
+/// The profiles that are known without being configured.
+const BUILTIN_PROFILES: &[(&str, &str, &str)] = &[
+    ("devnet", "http://localhost:3030", "testnet3"),
+    ("testnet", DEFAULT_ENDPOINT, "testnet3"),
+];
+
+/// A network that commands talk to.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// The REST endpoint of the node.
+    pub endpoint: String,
+    /// The network served by the node, which prefixes the paths of its REST API, e.g. `testnet3`.
+    #[serde(default = "default_network")]
+    pub network: String,
+    /// The account that signs, as `$VAR` to read its private key from the variable `VAR`, e.g. one set in `.env`.
+    /// The private key itself is rejected, since `program.json` is committed along with the program.
+    #[serde(default)]
+    pub private_key: Option<String>,
+    /// The proving service that proving is offloaded to, if any.
+    #[serde(default)]
+    pub prover: Option<String>,
+}
+
+fn default_network() -> String {
+    "testnet3".to_string()
+}
+
+impl Profile {
+    /// Returns the built-in profile `name`, if there is one.
+    pub fn builtin(name: &str) -> Option<Self> {
+        BUILTIN_PROFILES
+            .iter()
+            .find(|(builtin, ..)| *builtin == name)
+            .map(|(_, endpoint, network)| Self {
+                endpoint: endpoint.to_string(),
+                network: network.to_string(),
+                private_key: None,
+                prover: None,
+            })
+    }
+
+    /// Returns the profile `name`, or the one named by `LEO_NETWORK` if no name is given.
+    /// Profiles configured in `profiles` shadow the built-in ones.
+    /// The private key is resolved in `environment`, falling back to `ALEO_PRIVATE_KEY`.
+    pub fn resolve(
+        name: Option<&str>,
+        profiles: &IndexMap<String, Profile>,
+        environment: &Environment,
+    ) -> Result<Self> {
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => environment
+                .var(NETWORK_VAR)
+                .unwrap_or_else(|| DEFAULT_PROFILE.to_string()),
+        };
+        let mut profile = match profiles.get(&name).cloned().or_else(|| Self::builtin(&name)) {
+            Some(profile) => profile,
+            None => return Err(CliError::unknown_network_profile(name).into()),
+        };
+        profile.private_key = match profile.private_key.take() {
+            Some(key) => match key.strip_prefix('$') {
+                Some(var) => Some(environment.var(var).ok_or_else(|| CliError::unset_variable(var))?),
+                None => return Err(CliError::private_key_in_profile(name).into()),
+            },
+            None => environment.var(PRIVATE_KEY_VAR),
+        };
+        Ok(profile)
+    }
+}
+
+/// The variables of the `.env` file of a package, which the variables of the process take precedence over.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Environment {
+    vars: IndexMap<String, String>,
+}
+
+impl Environment {
+    /// Parses the contents of a `.env` file, with a `KEY=VALUE` assignment on each line.
+    /// Blank lines and lines starting with `#` are skipped, and values may be quoted.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut vars = IndexMap::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| PackageError::invalid_env_file(index + 1))?;
+            let value = value.trim();
+            let value = match (value.strip_prefix('"'), value.strip_prefix('\'')) {
+                (Some(quoted), _) => quoted.strip_suffix('"'),
+                (_, Some(quoted)) => quoted.strip_suffix('\''),
+                _ => Some(value),
+            }
+            .ok_or_else(|| PackageError::invalid_env_file(index + 1))?;
+            vars.insert(key.trim().to_string(), value.to_string());
+        }
+        Ok(Self { vars })
+    }
+
+    /// Reads the `.env` file of the package at `package_path`, which is empty if there is none.
+    pub fn read_from(package_path: &Path) -> Result<Self> {
+        let path = package_path.join(ENV_FILENAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents =
+            std::fs::read_to_string(&path).map_err(|err| PackageError::failed_to_read_file(path.display(), err))?;
+        Self::parse(&contents)
+    }
+
+    /// Returns the variable `name` of the process, or else of the `.env` file.
+    pub fn var(&self, name: &str) -> Option<String> {
+        std::env::var(name).ok().or_else(|| self.vars.get(name).cloned())
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Provers, which execute a function of the built program and prove the execution on behalf of `leo run` and `leo execute`,
+//! so that proving can be offloaded to a proving service, e.g. a farm of GPUs, instead of running in snarkVM locally.
+
+use crate::commands::{Network, ALEO_CLI_COMMAND};
+use leo_errors::{CliError, PackageError, Result};
+use leo_package::outputs::MAIN_ALEO_FILE_NAME;
+
+use aleo::commands::Run as AleoRun;
+use snarkvm::prelude::Request;
+
+use clap::StructOpt;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A request to execute a function of a program and prove the execution.
+/// It either carries the inputs of the function, for a local run of the program,
+/// or the requests of the caller that authorize the execution, for a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvingRequest {
+    /// The id of the program, e.g. `token.aleo`.
+    pub program_id: String,
+    /// The Aleo instructions of the program, if it is not deployed.
+    pub program: Option<String>,
+    /// The name of the function to execute.
+    pub function: String,
+    /// The inputs of the function, as Aleo values, e.g. `1u32`, if the execution is not authorized.
+    pub inputs: Vec<String>,
+    /// The requests of the authorization of the execution, signed by the caller, if it is authorized.
+    #[serde(default)]
+    pub authorization: Vec<String>,
+    /// The proving key of the function in hexadecimal, if it was synthesized by a local build.
+    pub proving_key: Option<String>,
+    /// The verifying key of the function in hexadecimal, if it was synthesized by a local build.
+    pub verifying_key: Option<String>,
+}
+
+impl ProvingRequest {
+    /// Returns a request to execute `function` of the program `program_id` on `inputs`.
+    /// It carries neither the program nor the keys of the function, which a local prover reads from the build directory.
+    pub fn new(program_id: String, function: String, inputs: Vec<String>) -> Self {
+        Self {
+            program_id,
+            program: None,
+            function,
+            inputs,
+            authorization: Vec::new(),
+            proving_key: None,
+            verifying_key: None,
+        }
+    }
+
+    /// Attaches the program built in `build_directory` to the request, for a proving service, which does not have it.
+    /// The keys of the function are attached too if the build directory has them.
+    pub fn with_program(self, build_directory: &Path) -> Result<Self> {
+        let program_path = build_directory.join(MAIN_ALEO_FILE_NAME);
+        let program = fs::read_to_string(&program_path)
+            .map_err(|err| PackageError::failed_to_read_file(program_path.display(), err))?;
+        let key = |extension: &str| {
+            fs::read(build_directory.join(format!("{}.{}", self.function, extension)))
+                .ok()
+                .map(hex::encode)
+        };
+        let (proving_key, verifying_key) = (key("prover"), key("verifier"));
+        Ok(Self {
+            program: Some(program),
+            proving_key,
+            verifying_key,
+            ..self
+        })
+    }
+
+    /// Returns a request to prove the execution of `function` of the deployed program `program_id`
+    /// that the caller authorized by signing `requests`.
+    /// Neither the inputs nor the program are sent on their own, since the signed requests bind the inputs to the caller,
+    /// and the prover reads the program from the network.
+    pub fn authorized(program_id: String, function: String, requests: &[Request<Network>]) -> Self {
+        Self {
+            program_id,
+            program: None,
+            function,
+            inputs: Vec::new(),
+            authorization: requests.iter().map(ToString::to_string).collect(),
+            proving_key: None,
+            verifying_key: None,
+        }
+    }
+}
+
+/// The result of executing a function and proving the execution.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvingResponse {
+    /// The outputs of the function, as Aleo values.
+    pub outputs: Vec<String>,
+    /// The execution along with its proof, as the prover serializes it, if it returns it.
+    pub execution: Option<String>,
+}
+
+/// Executes functions and proves their executions.
+pub trait Prover {
+    /// Executes the function of `request` and proves the execution.
+    fn prove(&self, request: &ProvingRequest) -> Result<ProvingResponse>;
+}
+
+/// Proves with snarkVM on this machine, by running `aleo run` in the build directory of the package.
+/// Its outputs are logged by `aleo run` itself, so the response has none.
+pub struct LocalProver {
+    build_directory: PathBuf,
+    offline: bool,
+}
+
+impl LocalProver {
+    /// Returns a prover that runs the program built in `build_directory`, without fetching anything if `offline` is set.
+    pub fn new(build_directory: PathBuf, offline: bool) -> Self {
+        Self {
+            build_directory,
+            offline,
+        }
+    }
+}
+
+impl Prover for LocalProver {
+    fn prove(&self, request: &ProvingRequest) -> Result<ProvingResponse> {
+        // Compose the `aleo run` command.
+        let mut arguments = vec![ALEO_CLI_COMMAND.to_string(), request.function.clone()];
+        arguments.extend(request.inputs.iter().cloned());
+        if self.offline {
+            arguments.push(String::from("--offline"));
+        }
+
+        // Change the cwd to the Leo build/ directory to run the Aleo files.
+        std::env::set_current_dir(&self.build_directory)
+            .map_err(|err| PackageError::failed_to_set_cwd(self.build_directory.display(), err))?;
+
+        // Call the `aleo run` command from the Aleo SDK.
+        println!();
+        let command = AleoRun::try_parse_from(&arguments).map_err(CliError::failed_to_parse_aleo_run)?;
+        let res = command.parse().map_err(CliError::failed_to_execute_aleo_run)?;
+
+        // Log the output of the `aleo run` command.
+        tracing::info!("{}", res);
+
+        Ok(ProvingResponse::default())
+    }
+}
+
+/// Delegates to a proving service over HTTP.
+/// The request is posted as JSON to the URL of the service, which answers with the response as JSON.
+pub struct HttpProver {
+    url: String,
+}
+
+impl HttpProver {
+    /// Returns a prover that posts requests to `url`.
+    pub fn new(url: String) -> Self {
+        Self { url }
+    }
+}
+
+impl Prover for HttpProver {
+    fn prove(&self, request: &ProvingRequest) -> Result<ProvingResponse> {
+        tracing::info!("Proving `{}` with {}", request.function, self.url);
+        reqwest::blocking::Client::new()
+            .post(&self.url)
+            .json(request)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.json())
+            .map_err(|err| CliError::failed_to_prove(&self.url, err).into())
+    }
+}
+
+/// The options that choose the prover of a command.
+/// The program is proven locally with snarkVM if no proving service is given.
+#[derive(StructOpt, Clone, Debug, Default)]
+pub struct ProverOptions {
+    #[structopt(
+        long,
+        value_name = "URL",
+        help = "Offloads the execution and its proof to the proving service at URL, which is posted the function, its inputs and its keys as JSON."
+    )]
+    pub prover: Option<String>,
+}
+
+impl ProverOptions {
+    /// Returns the chosen prover for the program built in `build_directory`.
+    pub fn open(&self, build_directory: PathBuf, offline: bool) -> Box<dyn Prover> {
+        match &self.prover {
+            Some(url) => Box::new(HttpProver::new(url.clone())),
+            None => Box::new(LocalProver::new(build_directory, offline)),
+        }
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The scripts declared under `scripts` in `program.json`, which the CLI runs before and after its commands.
+
+use leo_errors::{CliError, PackageError, Result};
+use leo_package::{
+    build::BUILD_DIRECTORY_NAME,
+    outputs::{MAIN_ALEO_FILE_NAME, OUTPUTS_DIRECTORY_NAME},
+};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// A point at which the scripts declared for it are run, e.g. `pre-build`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Hook {
+    PreBuild,
+    PostBuild,
+    PreRun,
+    PostRun,
+    PreDeploy,
+    PostDeploy,
+}
+
+impl Hook {
+    /// All the hooks, in the order of their names in the manifest.
+    pub const ALL: [Hook; 6] = [
+        Hook::PreBuild,
+        Hook::PostBuild,
+        Hook::PreRun,
+        Hook::PostRun,
+        Hook::PreDeploy,
+        Hook::PostDeploy,
+    ];
+
+    /// Returns the name of the hook in the manifest.
+    pub fn name(self) -> &'static str {
+        match self {
+            Hook::PreBuild => "pre-build",
+            Hook::PostBuild => "post-build",
+            Hook::PreRun => "pre-run",
+            Hook::PostRun => "post-run",
+            Hook::PreDeploy => "pre-deploy",
+            Hook::PostDeploy => "post-deploy",
+        }
+    }
+}
+
+impl fmt::Display for Hook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The commands of a hook in the manifest, either a single command or a list of them.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ScriptCommands {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl ScriptCommands {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            ScriptCommands::One(command) => vec![command],
+            ScriptCommands::Many(commands) => commands,
+        }
+    }
+}
+
+/// The scripts of a package, e.g. `"scripts": { "pre-build": "./generate.sh" }` in `program.json`.
+///
+/// Each command is run by the shell in the package directory, with these environment variables:
+/// - `LEO_HOOK`: the name of the hook, e.g. `pre-build`.
+/// - `LEO_PROGRAM`: the program id, e.g. `hello.aleo`.
+/// - `LEO_PACKAGE_DIR`, `LEO_BUILD_DIR` and `LEO_OUTPUTS_DIR`: the paths of the package and of its directories.
+/// - `LEO_PROGRAM_HASH`: the SHA256 hash of `build/main.aleo`, if the program has been built.
+#[derive(Debug, Default)]
+pub struct Scripts {
+    package_path: PathBuf,
+    program: String,
+    hooks: IndexMap<Hook, Vec<String>>,
+}
+
+impl Scripts {
+    /// Returns the scripts of the package at `package_path`, from the `scripts` of its manifest.
+    pub fn new(package_path: PathBuf, program: String, scripts: IndexMap<String, ScriptCommands>) -> Result<Self> {
+        let mut hooks = IndexMap::new();
+        for (name, commands) in scripts {
+            let hook = Hook::ALL
+                .into_iter()
+                .find(|hook| hook.name() == name)
+                .ok_or_else(|| PackageError::unknown_script_hook(&name))?;
+            hooks.insert(hook, commands.into_vec());
+        }
+
+        Ok(Self {
+            package_path,
+            program,
+            hooks,
+        })
+    }
+
+    /// Runs the commands declared for `hook`, in order, failing if any of them fails.
+    pub fn run(&self, hook: Hook) -> Result<()> {
+        let commands = match self.hooks.get(&hook) {
+            Some(commands) => commands,
+            None => return Ok(()),
+        };
+
+        let build_directory = self.package_path.join(BUILD_DIRECTORY_NAME);
+        let outputs_directory = self.package_path.join(OUTPUTS_DIRECTORY_NAME);
+        let program_hash = program_hash(&build_directory);
+
+        for command in commands {
+            tracing::info!("Running {} script `{}`", hook, command);
+
+            let mut shell = shell(command);
+            shell
+                .current_dir(&self.package_path)
+                .env("LEO_HOOK", hook.name())
+                .env("LEO_PROGRAM", &self.program)
+                .env("LEO_PACKAGE_DIR", &self.package_path)
+                .env("LEO_BUILD_DIR", &build_directory)
+                .env("LEO_OUTPUTS_DIR", &outputs_directory);
+            if let Some(hash) = &program_hash {
+                shell.env("LEO_PROGRAM_HASH", hash);
+            }
+
+            let status = shell
+                .status()
+                .map_err(|error| CliError::failed_to_run_script(hook, command, error))?;
+            if !status.success() {
+                return Err(CliError::script_failed(hook, command, status).into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns a process that runs `command` with the shell of the platform.
+fn shell(command: &str) -> process::Command {
+    #[cfg(windows)]
+    let (shell, flag) = ("cmd", "/C");
+    #[cfg(not(windows))]
+    let (shell, flag) = ("sh", "-c");
+
+    let mut process = process::Command::new(shell);
+    process.arg(flag).arg(command);
+    process
+}
+
+/// Returns the SHA256 hash of the built program in `build_directory`, if it has been built.
+fn program_hash(build_directory: &Path) -> Option<String> {
+    let program = std::fs::read(build_directory.join(MAIN_ALEO_FILE_NAME)).ok()?;
+    Some(format!("{:x}", Sha256::digest(&program)))
+}
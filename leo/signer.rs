@@ -0,0 +1,303 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Signers, which sign for an account on behalf of the commands that deploy programs,
+//! so that the private key of the account does not have to be passed on the command line.
+
+use crate::{commands::Network, profiles::Profile};
+use leo_errors::{CliError, Result};
+use leo_package::dependencies::home_directory;
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use clap::StructOpt;
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+use snarkvm::prelude::{Address, PrivateKey, Signature};
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+
+/// The environment variable with the private key that is signed with if no other signer is chosen.
+pub const PRIVATE_KEY_VAR: &str = "ALEO_PRIVATE_KEY";
+
+/// The environment variable with the password of a keystore, which is asked for at a prompt if it is not set.
+pub const KEYSTORE_PASSWORD_VAR: &str = "LEO_KEYSTORE_PASSWORD";
+
+/// The directory in the home directory of the user that keystores are written to by default.
+pub const KEYSTORE_DIRECTORY: &str = ".leo/accounts";
+
+/// Signs for an account, without the commands that use it having to hold its private key.
+pub trait Signer {
+    /// Returns the address of the account.
+    fn address(&self) -> Result<Address<Network>>;
+
+    /// Signs `message` with the private key of the account.
+    fn sign(&self, message: &[u8]) -> Result<Signature<Network>>;
+
+    /// Returns the private key of the account if the signer holds it, which executing a transition with snarkVM needs.
+    fn private_key(&self) -> Option<&PrivateKey<Network>> {
+        None
+    }
+}
+
+/// Signs with a private key that is held in memory, e.g. one from an environment variable or a keystore.
+pub struct PrivateKeySigner {
+    private_key: PrivateKey<Network>,
+}
+
+impl PrivateKeySigner {
+    /// Returns a signer for the private key in the environment variable `var`, or `None` if it is not set.
+    pub fn from_env(var: &str) -> Result<Option<Self>> {
+        match std::env::var(var) {
+            Ok(private_key) => Ok(Some(Self {
+                private_key: PrivateKey::from_str(private_key.trim())
+                    .map_err(|err| CliError::failed_to_load_signer(var, err))?,
+            })),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+impl Signer for PrivateKeySigner {
+    fn address(&self) -> Result<Address<Network>> {
+        Address::try_from(&self.private_key).map_err(|err| CliError::failed_to_sign(err).into())
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Signature<Network>> {
+        self.private_key
+            .sign_bytes(message, &mut thread_rng())
+            .map_err(|err| CliError::failed_to_sign(err).into())
+    }
+
+    fn private_key(&self) -> Option<&PrivateKey<Network>> {
+        Some(&self.private_key)
+    }
+}
+
+/// Signs with an external program, e.g. the bridge of a hardware wallet, so that the private key never enters Leo.
+/// The program is run as `PROGRAM address`, printing the address of the account,
+/// and as `PROGRAM sign MESSAGE`, with the message in hexadecimal, printing its signature.
+pub struct ExternalSigner {
+    program: PathBuf,
+}
+
+impl ExternalSigner {
+    /// Returns a signer that runs `program`.
+    pub fn new(program: PathBuf) -> Self {
+        Self { program }
+    }
+
+    /// Runs the program with `arguments`, returning what it prints.
+    fn run(&self, arguments: &[&str]) -> Result<String> {
+        let output = Command::new(&self.program)
+            .args(arguments)
+            .output()
+            .map_err(|err| CliError::failed_to_load_signer(self.program.display(), err))?;
+        if !output.status.success() {
+            return Err(CliError::failed_to_sign(String::from_utf8_lossy(&output.stderr).trim()).into());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+impl Signer for ExternalSigner {
+    fn address(&self) -> Result<Address<Network>> {
+        Address::from_str(&self.run(&["address"])?).map_err(|err| CliError::failed_to_sign(err).into())
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Signature<Network>> {
+        Signature::from_str(&self.run(&["sign", &hex::encode(message)])?)
+            .map_err(|err| CliError::failed_to_sign(err).into())
+    }
+}
+
+/// A private key encrypted with a password, as stored in a keystore file.
+/// The key of the cipher is derived from the password with scrypt, and the private key is encrypted with AES-GCM.
+#[derive(Serialize, Deserialize)]
+pub struct Keystore {
+    /// The address of the account, which can be shown without the password.
+    pub address: String,
+    /// The salt of the key derivation, in hexadecimal.
+    salt: String,
+    /// The nonce of the cipher, in hexadecimal.
+    nonce: String,
+    /// The encrypted private key, in hexadecimal.
+    ciphertext: String,
+}
+
+impl Keystore {
+    /// Encrypts `private_key` with `password`.
+    pub fn encrypt(private_key: &PrivateKey<Network>, password: &str) -> Result<Self> {
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        thread_rng().fill_bytes(&mut salt);
+        thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = cipher(password, &salt)?
+            .encrypt(Nonce::from_slice(&nonce), private_key.to_string().as_bytes())
+            .map_err(|_| CliError::failed_to_encrypt_keystore())?;
+        Ok(Self {
+            address: Address::try_from(private_key)
+                .map_err(CliError::failed_to_sign)?
+                .to_string(),
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypts the private key with `password`.
+    pub fn decrypt(&self, password: &str) -> Result<PrivateKey<Network>> {
+        let decode = |field: &str| hex::decode(field).map_err(|_| CliError::failed_to_decrypt_keystore());
+        let plaintext = cipher(password, &decode(&self.salt)?)?
+            .decrypt(
+                Nonce::from_slice(&decode(&self.nonce)?),
+                decode(&self.ciphertext)?.as_slice(),
+            )
+            .map_err(|_| CliError::failed_to_decrypt_keystore())?;
+        String::from_utf8(plaintext)
+            .ok()
+            .and_then(|private_key| PrivateKey::from_str(&private_key).ok())
+            .ok_or_else(|| CliError::failed_to_decrypt_keystore().into())
+    }
+
+    /// Reads the keystore file at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path).map_err(|err| CliError::failed_to_load_signer(path.display(), err))?;
+        serde_json::from_str(&contents).map_err(|err| CliError::failed_to_load_signer(path.display(), err).into())
+    }
+
+    /// Returns the default path of the keystore, `~/.leo/accounts/ADDRESS.json`, if the home directory is known.
+    pub fn default_path(&self) -> Option<PathBuf> {
+        home_directory().map(|home| home.join(KEYSTORE_DIRECTORY).join(format!("{}.json", self.address)))
+    }
+
+    /// Writes the keystore file at `path`, creating its directory if needed.
+    /// On unix, only the owner of the file can read or write it.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        // Note that this unwrap is safe since the keystore only has string fields.
+        let contents = serde_json::to_string_pretty(self).unwrap();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| CliError::failed_to_write_keystore(path.display(), err))?;
+        }
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options
+            .open(path)
+            .map_err(|err| CliError::failed_to_write_keystore(path.display(), err))?;
+        // The mode only applies to a new file, so the permissions of a keystore that is overwritten are restricted too.
+        #[cfg(unix)]
+        file.set_permissions(fs::Permissions::from_mode(0o600))
+            .map_err(|err| CliError::failed_to_write_keystore(path.display(), err))?;
+        file.write_all(contents.as_bytes())
+            .map_err(|err| CliError::failed_to_write_keystore(path.display(), err).into())
+    }
+}
+
+/// Returns the cipher whose key is derived from `password` and `salt`.
+fn cipher(password: &str, salt: &[u8]) -> Result<Aes256Gcm> {
+    let mut key = [0u8; 32];
+    scrypt::scrypt(password.as_bytes(), salt, &scrypt::Params::recommended(), &mut key)
+        .map_err(|_| CliError::failed_to_decrypt_keystore())?;
+    // Note that this unwrap is safe since the key has the length of an AES-256 key.
+    Ok(Aes256Gcm::new_from_slice(&key).unwrap())
+}
+
+/// Returns the password of a keystore from `LEO_KEYSTORE_PASSWORD`, or asks for it at a prompt.
+pub fn keystore_password(prompt: &str) -> Result<String> {
+    if let Ok(password) = std::env::var(KEYSTORE_PASSWORD_VAR) {
+        return Ok(password);
+    }
+    let term = console::Term::stderr();
+    term.write_str(prompt).map_err(CliError::cli_io_error)?;
+    term.read_secure_line()
+        .map_err(|err| CliError::cli_io_error(err).into())
+}
+
+/// The options that choose the account of a command that needs its private key, e.g. one that authorizes a transition,
+/// since snarkVM signs the requests of transitions and fees itself. An external signer cannot be chosen for it.
+/// The private key in `ALEO_PRIVATE_KEY` is used if no keystore is given.
+#[derive(StructOpt, Clone, Debug, Default)]
+pub struct PrivateKeyOptions {
+    #[structopt(
+        long,
+        value_name = "PATH",
+        help = "Signs with the private key in the keystore at PATH. Its password is read from `LEO_KEYSTORE_PASSWORD`, or asked for."
+    )]
+    pub keystore: Option<PathBuf>,
+}
+
+impl PrivateKeyOptions {
+    /// Returns the private key of the chosen keystore, or else the one in `ALEO_PRIVATE_KEY`, if it is set.
+    pub fn open(&self) -> Result<Option<PrivateKey<Network>>> {
+        if let Some(path) = &self.keystore {
+            let keystore = Keystore::open(path)?;
+            let password = keystore_password(&format!("Password of {}: ", keystore.address))?;
+            return keystore.decrypt(&password).map(Some);
+        }
+        Ok(PrivateKeySigner::from_env(PRIVATE_KEY_VAR)?.map(|signer| signer.private_key))
+    }
+
+    /// Returns the private key of the chosen keystore, or else the private key of `profile`, if it has one.
+    pub fn open_in(&self, profile: &Profile) -> Result<Option<PrivateKey<Network>>> {
+        match (&self.keystore, &profile.private_key) {
+            (None, Some(private_key)) => PrivateKey::from_str(private_key.trim())
+                .map(Some)
+                .map_err(|err| CliError::failed_to_load_signer("the network profile", err).into()),
+            _ => self.open(),
+        }
+    }
+}
+
+/// The options that choose the signer of a command, which only needs the address of its account and signatures.
+/// The private key in `ALEO_PRIVATE_KEY` is signed with if neither a keystore nor an external signer is given.
+#[derive(StructOpt, Clone, Debug, Default)]
+pub struct SignerOptions {
+    #[structopt(flatten)]
+    pub key: PrivateKeyOptions,
+
+    #[structopt(
+        long,
+        value_name = "PROGRAM",
+        conflicts_with = "keystore",
+        help = "Signs with an external program, e.g. a hardware wallet bridge, which is run as `PROGRAM address` and `PROGRAM sign MESSAGE`."
+    )]
+    pub external_signer: Option<PathBuf>,
+}
+
+impl SignerOptions {
+    /// Returns the chosen signer, or `None` if none is chosen and `ALEO_PRIVATE_KEY` is not set.
+    pub fn open(&self) -> Result<Option<Box<dyn Signer>>> {
+        if let Some(program) = &self.external_signer {
+            return Ok(Some(Box::new(ExternalSigner::new(program.clone()))));
+        }
+        Ok(self
+            .key
+            .open()?
+            .map(|private_key| Box::new(PrivateKeySigner { private_key }) as Box<dyn Signer>))
+    }
+}
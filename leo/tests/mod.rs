@@ -44,6 +44,188 @@ pub fn format_event() -> Result<()> {
     Ok(())
 }
 
+#[test]
+pub fn keystore_round_trip() -> Result<()> {
+    use crate::{commands::Network, signer::Keystore};
+    use snarkvm::prelude::{Address, PrivateKey};
+
+    let private_key = PrivateKey::<Network>::new(&mut rand::thread_rng()).unwrap();
+    let keystore = Keystore::encrypt(&private_key, "correct horse")?;
+    assert_eq!(keystore.address, Address::try_from(&private_key).unwrap().to_string());
+
+    // The keystore is decrypted after being written and read back, as `leo account` and `leo deploy` do.
+    let keystore: Keystore = serde_json::from_str(&serde_json::to_string(&keystore).unwrap()).unwrap();
+    assert_eq!(keystore.decrypt("correct horse")?, private_key);
+    assert!(keystore.decrypt("battery staple").is_err());
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+pub fn keystore_is_only_readable_by_its_owner() -> Result<()> {
+    use crate::{commands::Network, signer::Keystore};
+    use snarkvm::prelude::PrivateKey;
+    use std::os::unix::fs::PermissionsExt;
+
+    let directory = std::env::temp_dir().join(format!("leo-keystore-{}", std::process::id()));
+    let path = directory.join("accounts/keystore.json");
+    let private_key = PrivateKey::<Network>::new(&mut rand::thread_rng()).unwrap();
+    let keystore = Keystore::encrypt(&private_key, "correct horse")?;
+
+    // A new keystore is created with the mode, and an existing one that others can read is restricted when overwritten.
+    let mode = || std::fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+    keystore.write(&path)?;
+    assert_eq!(mode(), 0o600);
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+    keystore.write(&path)?;
+    assert_eq!(mode(), 0o600);
+    assert_eq!(Keystore::open(&path)?.decrypt("correct horse")?, private_key);
+
+    std::fs::remove_dir_all(&directory).unwrap();
+    Ok(())
+}
+
+#[test]
+pub fn remote_prover_receives_the_signed_authorization() -> Result<()> {
+    use crate::{
+        commands::{execute::prove_authorized, Network},
+        prover::{Prover, ProvingRequest, ProvingResponse},
+    };
+    use snarkvm::prelude::{Address, Identifier, PrivateKey, ProgramID, Request, Value, ValueType};
+    use std::{cell::RefCell, str::FromStr};
+
+    /// A prover that records the requests it is sent, and proves nothing.
+    #[derive(Default)]
+    struct MockProver {
+        requests: RefCell<Vec<ProvingRequest>>,
+    }
+
+    impl Prover for MockProver {
+        fn prove(&self, request: &ProvingRequest) -> Result<ProvingResponse> {
+            self.requests.borrow_mut().push(request.clone());
+            Ok(ProvingResponse::default())
+        }
+    }
+
+    let rng = &mut rand::thread_rng();
+    let private_key = PrivateKey::<Network>::new(rng).unwrap();
+    let input_types = [ValueType::<Network>::from_str("u64.private").unwrap()];
+    let request = Request::sign(
+        &private_key,
+        ProgramID::from_str("token.aleo").unwrap(),
+        Identifier::from_str("transfer").unwrap(),
+        &[Value::from_str("5u64").unwrap()],
+        &input_types,
+        rng,
+    )
+    .unwrap();
+
+    // The mock returns no execution, so proving fails after the request is sent.
+    let prover = MockProver::default();
+    let proven = prove_authorized(
+        &prover,
+        "mock",
+        "token.aleo".to_string(),
+        "transfer".to_string(),
+        &[request],
+    );
+    assert!(proven.is_err());
+
+    // The prover is sent the request signed by the caller, rather than the inputs or the program.
+    let requests = prover.requests.into_inner();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].inputs.is_empty());
+    assert!(requests[0].program.is_none());
+    assert_eq!(requests[0].authorization.len(), 1);
+    let signed = Request::<Network>::from_str(&requests[0].authorization[0]).unwrap();
+    assert!(signed.verify(&input_types));
+    assert_eq!(*signed.caller(), Address::try_from(&private_key).unwrap());
+    Ok(())
+}
+
+#[test]
+pub fn only_proving_services_are_sent_the_program() -> Result<()> {
+    use crate::prover::ProvingRequest;
+    use std::fs;
+
+    let directory = std::env::temp_dir().join(format!("leo-prover-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(directory.join("main.aleo"), "program hello.aleo;").unwrap();
+    fs::write(directory.join("main.prover"), [1, 2]).unwrap();
+
+    // A request for a local run carries only the function and its inputs, and reads nothing.
+    let request = ProvingRequest::new("hello.aleo".to_string(), "main".to_string(), vec!["1u32".to_string()]);
+    assert!(request.program.is_none() && request.proving_key.is_none());
+
+    // The request for a proving service carries the program and the keys the build directory has.
+    let attached = request.clone().with_program(&directory);
+    fs::remove_dir_all(&directory).unwrap();
+    let attached = attached?;
+    assert_eq!(attached.program.as_deref(), Some("program hello.aleo;"));
+    assert_eq!(attached.proving_key.as_deref(), Some("0102"));
+    assert!(attached.verifying_key.is_none());
+    assert_eq!(attached.inputs, request.inputs);
+
+    // A request for a program that is not built cannot be sent.
+    assert!(request.with_program(&directory).is_err());
+    Ok(())
+}
+
+#[test]
+pub fn deployment_fee_estimate() {
+    use crate::commands::deploy::DeploymentFee;
+
+    // A name of ten characters pays no namespace fee.
+    let fee = DeploymentFee::estimate("token_bank", 2_000, 40_000);
+    assert_eq!(
+        fee,
+        DeploymentFee {
+            storage: 2_000_000,
+            synthesis: 1_000_000,
+            namespace: 0
+        }
+    );
+    assert_eq!(fee.total(), 3_000_000);
+    assert_eq!(
+        fee.to_string(),
+        "~3.000000 credits (storage 2.000000, synthesis 1.000000, namespace 0.000000)"
+    );
+
+    // Each character less makes the namespace fee ten times as large.
+    assert_eq!(DeploymentFee::estimate("token", 0, 0).namespace, 100_000 * 1_000_000);
+    assert_eq!(DeploymentFee::estimate("bank", 0, 0).namespace, 1_000_000 * 1_000_000);
+}
+
+#[test]
+pub fn profile_private_key_reference() -> Result<()> {
+    use crate::profiles::{Environment, Profile};
+    use indexmap::IndexMap;
+
+    let environment = Environment::parse("LEO_TEST_PROFILE_KEY=APrivateKey1zkp")?;
+    let profile = |private_key: &str| {
+        let profile = Profile {
+            endpoint: "http://localhost:3030".to_string(),
+            network: "testnet3".to_string(),
+            private_key: Some(private_key.to_string()),
+            prover: None,
+        };
+        Profile::resolve(
+            Some("local"),
+            &IndexMap::from([("local".to_string(), profile)]),
+            &environment,
+        )
+    };
+
+    assert_eq!(
+        profile("$LEO_TEST_PROFILE_KEY")?.private_key.as_deref(),
+        Some("APrivateKey1zkp")
+    );
+    // A private key written in `program.json` is rejected, and so is a reference to a variable that is not set.
+    assert!(profile("APrivateKey1zkp").is_err());
+    assert!(profile("$LEO_TEST_PROFILE_UNSET").is_err());
+    Ok(())
+}
+
 // todo (collin): uncomment after refactor
 // #[test]
 // pub fn build_pedersen_hash() -> Result<()> {
@@ -225,3 +407,242 @@ pub fn format_event() -> Result<()> {
 
     Ok(context)
 } */
+
+#[cfg(unix)]
+#[test]
+pub fn plugins_on_path_are_discovered_and_run() -> Result<()> {
+    use crate::{context::Context, plugins::Plugin};
+    use leo_errors::emitter::ErrorFormat;
+    use std::{env, fs, os::unix::fs::PermissionsExt};
+
+    // A stub plugin that records its arguments and its input, and exits with the code given as its first argument.
+    let directory = env::temp_dir().join(format!("leo-plugins-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let stub = directory.join("leo-stub");
+    fs::write(
+        &stub,
+        "#!/bin/sh\nprintf '%s\\n' \"$@\" > \"$(dirname \"$0\")/args\"\ncat > \"$(dirname \"$0\")/stdin\"\nexit \"$1\"\n",
+    )
+    .unwrap();
+    fs::set_permissions(&stub, fs::Permissions::from_mode(0o755)).unwrap();
+    // A file with the prefix that cannot be executed is not a plugin.
+    fs::write(directory.join("leo-readme"), "").unwrap();
+
+    // The directory is put first on `PATH`, so that the commands of other tests are still found.
+    let path = env::var_os("PATH").unwrap_or_default();
+    let paths = std::iter::once(directory.clone()).chain(env::split_paths(&path));
+    env::set_var("PATH", env::join_paths(paths).unwrap());
+
+    let discovered = Plugin::discover();
+    let found = Plugin::find("stub");
+    let missing = Plugin::find("readme");
+    let context = Context::new(Some(directory.clone()), ErrorFormat::Human)?;
+    let passed = found
+        .as_ref()
+        .unwrap()
+        .run(&context, &["0".to_string(), "--flag".to_string()]);
+    let args = fs::read_to_string(directory.join("args")).unwrap();
+    let stdin = fs::read_to_string(directory.join("stdin")).unwrap();
+    let failed = found.as_ref().unwrap().run(&context, &["3".to_string()]);
+
+    env::set_var("PATH", path);
+    fs::remove_dir_all(&directory).unwrap();
+
+    // The stub is discovered under its name, and the file that cannot be executed is not.
+    assert!(discovered
+        .iter()
+        .any(|plugin| plugin.name == "stub" && plugin.path == stub));
+    assert!(discovered.iter().all(|plugin| plugin.name != "readme"));
+    assert_eq!(found.as_ref().map(|plugin| &plugin.path), Some(&stub));
+    assert!(missing.is_none());
+
+    // The arguments that follow the name of the plugin are forwarded as they are, and the context is sent on stdin.
+    passed?;
+    assert_eq!(args, "0\n--flag\n");
+    let input: serde_json::Value = serde_json::from_str(&stdin).unwrap();
+    assert_eq!(input["version"], crate::plugins::PLUGIN_INTERFACE_VERSION);
+    assert!(input["package"].is_null());
+
+    // A plugin that exits with a non-zero code fails the command, which reports the code.
+    let error = failed.unwrap_err().to_string();
+    assert!(error.contains("leo-stub"), "{error}");
+    assert!(error.contains("exit status: 3"), "{error}");
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+pub fn scripts_of_the_manifest_are_run() -> Result<()> {
+    use crate::{context::Context, scripts::Hook};
+    use leo_errors::emitter::ErrorFormat;
+    use std::fs;
+
+    let directory = std::env::temp_dir().join(format!("leo-scripts-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let context = Context::new(Some(directory.clone()), ErrorFormat::Human)?;
+    let write_manifest = |scripts: serde_json::Value| {
+        let manifest = serde_json::json!({ "program": "hello.aleo", "version": "0.0.0", "scripts": scripts });
+        fs::write(directory.join("program.json"), manifest.to_string()).unwrap();
+    };
+
+    // The commands of a hook are run in order in the package directory, with the context in the environment.
+    write_manifest(serde_json::json!({
+        "pre-build": ["printf '%s ' \"$LEO_HOOK\" > hook.txt", "printf '%s' \"$LEO_PROGRAM\" >> hook.txt"],
+        "pre-deploy": ["exit 7", "touch unreachable.txt"],
+    }));
+    let scripts = context.open_scripts()?;
+    scripts.run(Hook::PreBuild)?;
+    assert_eq!(
+        fs::read_to_string(directory.join("hook.txt")).unwrap(),
+        "pre-build hello.aleo"
+    );
+    // A hook without scripts runs nothing.
+    scripts.run(Hook::PostBuild)?;
+
+    // A command that exits with a non-zero code fails the hook, which reports the code and runs no further commands.
+    let error = scripts.run(Hook::PreDeploy).unwrap_err().to_string();
+    assert!(error.contains("exit status: 7"), "{error}");
+    assert!(!directory.join("unreachable.txt").exists());
+
+    // A script declared for a hook that does not exist is rejected when the manifest is read.
+    write_manifest(serde_json::json!({ "pre-test": "true" }));
+    let error = context.open_scripts().unwrap_err().to_string();
+    assert!(error.contains("Unknown hook `pre-test`"), "{error}");
+
+    fs::remove_dir_all(&directory).unwrap();
+    Ok(())
+}
+
+#[test]
+pub fn account_keystore_round_trip() -> Result<()> {
+    use crate::{
+        commands::{Account, Command},
+        context::Context,
+        signer::{PrivateKeyOptions, SignerOptions, KEYSTORE_DIRECTORY, KEYSTORE_PASSWORD_VAR},
+    };
+    use leo_errors::emitter::ErrorFormat;
+    use std::{env, fs};
+
+    // The keystore is saved under a temporary home directory, with the password given in the environment.
+    let home = env::temp_dir().join(format!("leo-account-{}", std::process::id()));
+    fs::create_dir_all(&home).unwrap();
+    let previous_home = env::var_os("HOME");
+    env::set_var("HOME", &home);
+    env::set_var(KEYSTORE_PASSWORD_VAR, "correct horse");
+
+    let context = Context::new(Some(home.clone()), ErrorFormat::Human)?;
+    let created = Account::New { save: true }.try_execute(context.clone());
+    let keystores: Vec<_> = fs::read_dir(home.join(KEYSTORE_DIRECTORY))
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    let show = |keystore: &std::path::PathBuf| {
+        Account::Show {
+            signer: SignerOptions {
+                key: PrivateKeyOptions {
+                    keystore: Some(keystore.clone()),
+                },
+                external_signer: None,
+            },
+        }
+        .try_execute(context.clone())
+    };
+    let unlocked = keystores.first().map(show);
+    env::set_var(KEYSTORE_PASSWORD_VAR, "battery staple");
+    let locked = keystores.first().map(show);
+
+    env::remove_var(KEYSTORE_PASSWORD_VAR);
+    match previous_home {
+        Some(previous_home) => env::set_var("HOME", previous_home),
+        None => env::remove_var("HOME"),
+    }
+    fs::remove_dir_all(&home).unwrap();
+
+    // `leo account new --save` writes one keystore, which unlocks with its password and only with it.
+    created?;
+    assert_eq!(keystores.len(), 1);
+    unlocked.unwrap()?;
+    let error = locked.unwrap().unwrap_err().to_string();
+    assert!(error.contains("Failed to decrypt the private key"), "{error}");
+    Ok(())
+}
+
+#[test]
+pub fn external_signers_are_rejected_by_commands_that_authorize_transitions() {
+    use crate::commands::{Deploy, Execute};
+    use clap::Parser;
+
+    // snarkVM signs the requests of transitions and fees with a private key, which an external signer does not give.
+    let deploy = Deploy::try_parse_from([
+        "deploy",
+        "--external-signer",
+        "signer",
+        "--fee",
+        "1",
+        "--fee-record",
+        "r",
+    ]);
+    let execute = Execute::try_parse_from(["execute", "main", "1u32", "--external-signer", "signer"]);
+    for result in [deploy.map(|_| ()), execute.map(|_| ())] {
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("--external-signer"), "{error}");
+    }
+
+    // A keystore is still accepted.
+    assert!(Deploy::try_parse_from(["deploy", "--keystore", "key.json", "--dry-run"]).is_ok());
+    assert!(Execute::try_parse_from(["execute", "main", "--keystore", "key.json"]).is_ok());
+}
+
+#[test]
+pub fn input_files_that_do_not_match_the_program_are_rejected() {
+    use leo_errors::emitter::Handler;
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    let program = "program test.aleo {
+    transition main(public a: u32, b: u32) -> u32 {
+        return a + b;
+    }
+}
+";
+    // Returns the error of checking the input file with the section `[main]` and the definitions `main`, if any.
+    let check = |main: &str| {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let program = leo_parser::parse_ast(&handler, program, Default::default()).unwrap();
+            let input = leo_parser::parse_input(&handler, &format!("[main]\n{main}"), Default::default()).unwrap();
+            leo_compiler::check_input(&input, program.as_repr())
+                .err()
+                .map(|error| error.to_string())
+        })
+    };
+
+    // An input file may leave out the modes of the inputs.
+    assert_eq!(check("a: u32 = 1u32;\nb: u32 = 2u32;"), None);
+    assert_eq!(check("public a: u32 = 1u32;\nprivate b: u32 = 2u32;"), None);
+
+    // It must give each input of the function, and no other.
+    let missing = check("a: u32 = 1u32;").unwrap();
+    assert!(
+        missing.contains("no value is given for the input `b` of `main`"),
+        "{missing}"
+    );
+    let unknown = check("a: u32 = 1u32;\nb: u32 = 2u32;\nc: u32 = 3u32;").unwrap();
+    assert!(unknown.contains("the function `main` has no input `c`"), "{unknown}");
+
+    // Each input must have the type of the input of the function, both as declared and as given.
+    let declared = check("a: u64 = 1u64;\nb: u32 = 2u32;").unwrap();
+    assert!(declared.contains("input `a` expects u32, found u64"), "{declared}");
+    let given = check("a: u32 = 1u32;\nb: u32 = 2field;").unwrap();
+    assert!(given.contains("input `b` expects u32, found field"), "{given}");
+
+    // And the mode of the input of the function, if it is given one.
+    let public = check("a: u32 = 1u32;\npublic b: u32 = 2u32;").unwrap();
+    assert!(
+        public.contains("input `b` is private, but is given as public"),
+        "{public}"
+    );
+    let private = check("private a: u32 = 1u32;\nb: u32 = 2u32;").unwrap();
+    assert!(
+        private.contains("input `a` is public, but is given as private"),
+        "{private}"
+    );
+}
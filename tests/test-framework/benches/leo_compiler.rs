@@ -157,7 +157,7 @@ impl Sample {
     }
 
     fn bench_type_checker(&self, c: &mut Criterion) {
-        self.bencher_after_parse(c, "type checker pass", |compiler| {
+        self.bencher_after_parse(c, "type checker pass", |mut compiler| {
             let symbol_table = compiler.symbol_table_pass().expect("failed to generate symbol table");
             let start = Instant::now();
             let out = compiler.type_checker_pass(symbol_table);
@@ -170,11 +170,13 @@ impl Sample {
     fn bench_loop_unroller(&self, c: &mut Criterion) {
         self.bencher_after_parse(c, "loop unrolling pass", |mut compiler| {
             let symbol_table = compiler.symbol_table_pass().expect("failed to generate symbol table");
-            let symbol_table = compiler
+            let (symbol_table, widenings, match_types, mut type_table) = compiler
                 .type_checker_pass(symbol_table)
                 .expect("failed to run type check pass");
+            compiler.match_lowering_pass(match_types);
+            compiler.implicit_widening_pass(widenings);
             let start = Instant::now();
-            let out = compiler.loop_unrolling_pass(symbol_table);
+            let out = compiler.loop_unrolling_pass(symbol_table, &mut type_table);
             let time = start.elapsed();
             out.expect("failed to run loop unrolling pass");
             time
@@ -184,11 +186,13 @@ impl Sample {
     fn bench_ssa(&self, c: &mut Criterion) {
         self.bencher_after_parse(c, "full", |mut compiler| {
             let symbol_table = compiler.symbol_table_pass().expect("failed to generate symbol table");
-            let symbol_table = compiler
+            let (symbol_table, widenings, match_types, mut type_table) = compiler
                 .type_checker_pass(symbol_table)
                 .expect("failed to run type check pass");
+            compiler.match_lowering_pass(match_types);
+            compiler.implicit_widening_pass(widenings);
             let symbol_table = compiler
-                .loop_unrolling_pass(symbol_table)
+                .loop_unrolling_pass(symbol_table, &mut type_table)
                 .expect("failed to run loop unrolling pass");
             let start = Instant::now();
             let out = compiler.static_single_assignment_pass(&symbol_table);
@@ -201,17 +205,19 @@ impl Sample {
     fn bench_flattener(&self, c: &mut Criterion) {
         self.bencher_after_parse(c, "flattener pass", |mut compiler| {
             let symbol_table = compiler.symbol_table_pass().expect("failed to generate symbol table");
-            let symbol_table = compiler
+            let (symbol_table, widenings, match_types, mut type_table) = compiler
                 .type_checker_pass(symbol_table)
                 .expect("failed to run type check pass");
+            compiler.match_lowering_pass(match_types);
+            compiler.implicit_widening_pass(widenings);
             let symbol_table = compiler
-                .loop_unrolling_pass(symbol_table)
+                .loop_unrolling_pass(symbol_table, &mut type_table)
                 .expect("failed to run loop unrolling pass");
             let assigner = compiler
                 .static_single_assignment_pass(&symbol_table)
                 .expect("failed to run ssa pass");
             let start = Instant::now();
-            let out = compiler.flattening_pass(&symbol_table, assigner);
+            let out = compiler.flattening_pass(&symbol_table, &type_table, assigner);
             let time = start.elapsed();
             out.expect("failed to run flattener pass");
             time
@@ -226,17 +232,19 @@ impl Sample {
                 .parse_program_from_string(input, name)
                 .expect("Failed to parse program");
             let symbol_table = compiler.symbol_table_pass().expect("failed to generate symbol table");
-            let symbol_table = compiler
+            let (symbol_table, widenings, match_types, mut type_table) = compiler
                 .type_checker_pass(symbol_table)
                 .expect("failed to run type check pass");
+            compiler.match_lowering_pass(match_types);
+            compiler.implicit_widening_pass(widenings);
             let symbol_table = compiler
-                .loop_unrolling_pass(symbol_table)
+                .loop_unrolling_pass(symbol_table, &mut type_table)
                 .expect("failed to run loop unrolling pass");
             let assigner = compiler
                 .static_single_assignment_pass(&symbol_table)
                 .expect("failed to run ssa pass");
             compiler
-                .flattening_pass(&symbol_table, assigner)
+                .flattening_pass(&symbol_table, &type_table, assigner)
                 .expect("failed to run flattening pass");
             start.elapsed()
         })
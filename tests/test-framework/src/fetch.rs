@@ -21,8 +21,13 @@ use std::{
 
 use walkdir::WalkDir;
 
+/// Returns the paths and contents of the tests in `path`.
+/// The programs in `imports` directories are imported by the tests next to them, and are not tests themselves.
 pub fn find_tests(path: &Path) -> impl Iterator<Item = (PathBuf, String)> {
-    WalkDir::new(path).into_iter().flatten().filter_map(move |f| {
+    let walker = WalkDir::new(path)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !(entry.file_type().is_dir() && entry.file_name() == "imports"));
+    walker.flatten().filter_map(move |f| {
         let path = f.path();
         path.extension().filter(|s| *s == "leo").map(|_| {
             (
@@ -16,12 +16,13 @@
 
 use serde_yaml::Value;
 use std::{
-    any::Any,
+    cell::RefCell,
     collections::BTreeMap,
     panic::{self, RefUnwindSafe, UnwindSafe},
     path::{Path, PathBuf},
-    sync::{Arc, Mutex},
+    sync::atomic::{AtomicUsize, Ordering},
     thread,
+    time::{Duration, Instant},
 };
 
 use crate::{error::*, fetch::find_tests, output::TestExpectation, test::*};
@@ -54,32 +55,198 @@ fn is_env_var_set(var: &str) -> bool {
     std::env::var(var).unwrap_or_else(|_| "".to_string()).trim().is_empty()
 }
 
-fn set_hook() -> Arc<Mutex<Option<String>>> {
-    let panic_buf = Arc::new(Mutex::new(None));
-    let thread_id = thread::current().id();
-    panic::set_hook({
-        let panic_buf = panic_buf.clone();
-        Box::new(move |e| {
-            if thread::current().id() == thread_id {
-                if !is_env_var_set("RUST_BACKTRACE") {
-                    *panic_buf.lock().unwrap() = Some(format!("{:?}", backtrace::Backtrace::new()));
-                } else {
-                    *panic_buf.lock().unwrap() = Some(e.to_string());
-                }
-            } else {
-                println!("{}", e)
+thread_local! {
+    /// The message of the last panic in a test run on this thread, if this thread runs tests.
+    static PANIC_BUF: RefCell<Option<Option<String>>> = RefCell::new(None);
+}
+
+/// Installs a panic hook that records the panics of tests, so that they are reported as test failures.
+/// Panics on threads that do not run tests are printed.
+fn set_hook() {
+    panic::set_hook(Box::new(|e| {
+        PANIC_BUF.with(|buf| match &mut *buf.borrow_mut() {
+            Some(panic_buf) if !is_env_var_set("RUST_BACKTRACE") => {
+                *panic_buf = Some(format!("{:?}", backtrace::Backtrace::new()))
             }
+            Some(panic_buf) => *panic_buf = Some(e.to_string()),
+            None => println!("{}", e),
         })
-    });
-    panic_buf
+    }));
 }
 
-fn take_hook(
-    output: Result<Result<Value, String>, Box<dyn Any + Send>>,
-    panic_buf: Arc<Mutex<Option<String>>>,
-) -> Result<Result<Value, String>, String> {
-    let _ = panic::take_hook();
-    output.map_err(|_| panic_buf.lock().unwrap().take().expect("failed to get panic message"))
+/// Runs `test` on this thread, and returns the message of its panic if it panics.
+fn catch_panic(test: impl FnOnce() -> Result<Value, String> + UnwindSafe) -> Result<Result<Value, String>, String> {
+    PANIC_BUF.with(|buf| *buf.borrow_mut() = Some(None));
+    let output = panic::catch_unwind(test);
+    let panic_message = PANIC_BUF.with(|buf| buf.borrow_mut().take().flatten());
+    output.map_err(|_| panic_message.expect("failed to get panic message"))
+}
+
+/// Selects the tests to run, and how to run them.
+///
+/// Options are read from the command line, e.g. `cargo test -p leo-test-framework -- --category flatten`,
+/// or from the environment, e.g. `TEST_CATEGORY=flatten`, for tests that run under the Rust test harness.
+#[derive(Debug)]
+pub struct TestOptions {
+    /// The categories of the tests to run, or every category if empty.
+    pub categories: Vec<TestCategory>,
+    /// The expectation of the tests to run, or every expectation if `None`.
+    pub expectation: Option<TestExpectationMode>,
+    /// The number of threads that run tests.
+    pub threads: usize,
+}
+
+impl TestOptions {
+    /// Reads the options from `--category`, `--expectation`, and `--test-threads`,
+    /// or from `TEST_CATEGORY`, `TEST_EXPECTATION`, and `TEST_THREADS`.
+    /// Several categories are separated by commas, e.g. `--category typeck,flatten`.
+    pub fn from_env() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let option = |flag: &str, var: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1).cloned())
+                .or_else(|| {
+                    args.iter()
+                        .find_map(|arg| arg.strip_prefix(&format!("{}=", flag)).map(str::to_string))
+                })
+                .or_else(|| std::env::var(var).ok())
+                .filter(|value| !value.trim().is_empty())
+        };
+
+        let categories = option("--category", "TEST_CATEGORY")
+            .map(|categories| {
+                categories
+                    .split(',')
+                    .map(|category| category.trim().parse().unwrap_or_else(|e: String| panic!("{}", e)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let expectation = option("--expectation", "TEST_EXPECTATION")
+            .map(|expectation| expectation.trim().parse().unwrap_or_else(|e: String| panic!("{}", e)));
+        let threads = option("--test-threads", "TEST_THREADS")
+            .map(|threads| threads.trim().parse().expect("invalid number of test threads"))
+            .unwrap_or_else(|| thread::available_parallelism().map_or(1, |threads| threads.get()));
+
+        Self {
+            categories,
+            expectation,
+            threads: threads.max(1),
+        }
+    }
+
+    /// Returns `true` if a test of the given `category` and `expectation` should run.
+    fn selects(&self, category: TestCategory, expectation: &TestExpectationMode) -> bool {
+        (self.categories.is_empty() || self.categories.contains(&category))
+            && self
+                .expectation
+                .as_ref()
+                .map_or(true, |selected| selected == expectation)
+    }
+}
+
+/// A test file that is selected to run, with its expectations.
+struct TestJob {
+    path: PathBuf,
+    name: String,
+    /// The content of the file after its configuration header.
+    content: String,
+    config: TestConfig,
+    category: TestCategory,
+    expectation_path: PathBuf,
+    expectations: Option<TestExpectation>,
+}
+
+/// The result of running the tests of a file.
+struct TestJobResult {
+    errors: Vec<TestError>,
+    outputs: Vec<Value>,
+    passed: usize,
+    failed: usize,
+    elapsed: Duration,
+}
+
+/// Returns the category of a test, which is given in its configuration,
+/// or inferred from the error it is expected to fail with.
+fn test_category(
+    expectation_category: &str,
+    config: &TestConfig,
+    expectations: Option<&TestExpectation>,
+) -> TestCategory {
+    if let Some(category) = config.category {
+        return category;
+    }
+    if expectation_category == "parser" {
+        return TestCategory::Parser;
+    }
+    match config.expectation {
+        TestExpectationMode::Fail => expectations
+            .and_then(|expectations| expectations.outputs.first())
+            .and_then(|output| output.as_str())
+            .and_then(|error| error.find("Error [").map(|i| &error[i + "Error [".len()..]))
+            .map_or(TestCategory::Codegen, TestCategory::from_error_code),
+        TestExpectationMode::Pass | TestExpectationMode::Skip => TestCategory::Codegen,
+    }
+}
+
+/// Runs the tests of the file of `job`, and compares their outputs with its expectations.
+fn run_job<T: Runner>(runner: &T, job: &TestJob) -> TestJobResult {
+    let start = Instant::now();
+    let mut result = TestJobResult {
+        errors: Vec::new(),
+        outputs: Vec::new(),
+        passed: 0,
+        failed: 0,
+        elapsed: Duration::default(),
+    };
+    let namespace = runner
+        .resolve_namespace(&job.config.namespace)
+        .expect("only tests of known namespaces are run");
+
+    let tests = match namespace.parse_type() {
+        ParseType::Line => crate::fetch::split_tests_one_line(&job.content)
+            .into_iter()
+            .map(|x| x.to_string())
+            .collect(),
+        ParseType::ContinuousLines => crate::fetch::split_tests_two_line(&job.content),
+        ParseType::Whole => vec![job.content.to_string()],
+    };
+
+    if let Some(expectations) = job.expectations.as_ref() {
+        if tests.len() != expectations.outputs.len() {
+            result.errors.push(TestError::MismatchedTestExpectationLength);
+        }
+    }
+
+    let mut expected_output = job.expectations.as_ref().map(|x| x.outputs.iter());
+    for (i, test) in tests.into_iter().enumerate() {
+        let expected_output = expected_output.as_mut().and_then(|x| x.next()).cloned();
+        println!("running test {} @ '{}'", job.name, job.path.to_str().unwrap());
+        let output = catch_panic(|| {
+            namespace.run_test(Test {
+                name: job.name.to_string(),
+                content: test.clone(),
+                path: job.path.clone(),
+                config: job.config.extra.clone(),
+            })
+        });
+        if let Some(error) = emit_errors(&test, &output, &job.config.expectation, expected_output, i) {
+            result.failed += 1;
+            result.errors.push(error);
+        } else {
+            result.passed += 1;
+            result.outputs.push(
+                output
+                    .unwrap()
+                    .as_ref()
+                    .map(|x| serde_yaml::to_value(x).expect("serialization failed"))
+                    .unwrap_or_else(|e| Value::String(e.clone())),
+            );
+        }
+    }
+
+    result.elapsed = start.elapsed();
+    result
 }
 
 pub struct TestCases {
@@ -181,87 +348,101 @@ impl TestCases {
     }
 }
 
-pub fn run_tests<T: Runner>(runner: &T, expectation_category: &str) {
+pub fn run_tests<T: Runner + Sync>(runner: &T, expectation_category: &str) {
+    let options = TestOptions::from_env();
     let (mut cases, configs) = TestCases::new(expectation_category, |_| true);
 
+    // Select the tests of known namespaces, in the selected categories.
+    let jobs: Vec<TestJob> = cases
+        .process_tests(configs, |cases, (path, content, test_name, config)| {
+            runner.resolve_namespace(&config.namespace)?;
+            let (expectation_path, expectations) = cases.load_expectations(path);
+            let category = test_category(expectation_category, &config, expectations.as_ref());
+            Some(TestJob {
+                path: path.to_path_buf(),
+                name: test_name.to_string(),
+                content: content.to_string(),
+                config,
+                category,
+                expectation_path,
+                expectations,
+            })
+        })
+        .into_iter()
+        .flatten()
+        .filter(|job| options.selects(job.category, &job.config.expectation))
+        .collect();
+
+    // Run the tests on a pool of threads, each taking the next file that has not been run.
+    set_hook();
+    let next_job = AtomicUsize::new(0);
+    let mut results: Vec<(usize, TestJobResult)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..options.threads.min(jobs.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next_job.fetch_add(1, Ordering::Relaxed);
+                        match jobs.get(index) {
+                            Some(job) => results.push((index, run_job(runner, job))),
+                            None => return results,
+                        }
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("test thread panicked"))
+            .collect()
+    });
+    let _ = panic::take_hook();
+    results.sort_by_key(|(index, _)| *index);
+
     let mut pass_categories = 0;
     let mut pass_tests = 0;
     let mut fail_tests = 0;
+    let mut timings: BTreeMap<TestCategory, (usize, Duration)> = BTreeMap::new();
 
     let mut outputs = vec![];
-    cases.process_tests(configs, |cases, (path, content, test_name, config)| {
-        let namespace = match runner.resolve_namespace(&config.namespace) {
-            Some(ns) => ns,
-            None => return,
-        };
-
-        let (expectation_path, expectations) = cases.load_expectations(path);
-
-        let tests = match namespace.parse_type() {
-            ParseType::Line => crate::fetch::split_tests_one_line(content)
-                .into_iter()
-                .map(|x| x.to_string())
-                .collect(),
-            ParseType::ContinuousLines => crate::fetch::split_tests_two_line(content),
-            ParseType::Whole => vec![content.to_string()],
-        };
-
-        let mut errors = vec![];
-        if let Some(expectations) = expectations.as_ref() {
-            if tests.len() != expectations.outputs.len() {
-                errors.push(TestError::MismatchedTestExpectationLength);
-            }
-        }
-
-        let mut new_outputs = vec![];
-        let mut expected_output = expectations.as_ref().map(|x| x.outputs.iter());
-        for (i, test) in tests.into_iter().enumerate() {
-            let expected_output = expected_output.as_mut().and_then(|x| x.next()).cloned();
-            println!("running test {} @ '{}'", test_name, path.to_str().unwrap());
-            let panic_buf = set_hook();
-            let leo_output = panic::catch_unwind(|| {
-                namespace.run_test(Test {
-                    name: test_name.to_string(),
-                    content: test.clone(),
-                    path: path.into(),
-                    config: config.extra.clone(),
-                })
-            });
-            let output = take_hook(leo_output, panic_buf);
-            if let Some(error) = emit_errors(&test, &output, &config.expectation, expected_output, i) {
-                fail_tests += 1;
-                errors.push(error);
-            } else {
-                pass_tests += 1;
-                new_outputs.push(
-                    output
-                        .unwrap()
-                        .as_ref()
-                        .map(|x| serde_yaml::to_value(x).expect("serialization failed"))
-                        .unwrap_or_else(|e| Value::String(e.clone())),
-                );
-            }
-        }
-
-        if errors.is_empty() {
-            if expectations.is_none() {
+    for (job, (_, result)) in jobs.into_iter().zip(results) {
+        pass_tests += result.passed;
+        fail_tests += result.failed;
+        let timing = timings.entry(job.category).or_default();
+        timing.0 += 1;
+        timing.1 += result.elapsed;
+
+        if result.errors.is_empty() {
+            if job.expectations.is_none() {
                 outputs.push((
-                    expectation_path,
+                    job.expectation_path,
                     TestExpectation {
-                        namespace: config.namespace,
-                        expectation: config.expectation,
-                        outputs: new_outputs,
+                        namespace: job.config.namespace,
+                        expectation: job.config.expectation,
+                        outputs: result.outputs,
                     },
                 ));
             }
             pass_categories += 1;
         } else {
             cases.fail_categories.push(TestFailure {
-                path: path.to_str().unwrap().to_string(),
-                errors,
+                path: job.path.to_str().unwrap().to_string(),
+                errors: result.errors,
             })
         }
-    });
+    }
+
+    // Print the time spent on the tests of each category.
+    // The tests run in parallel, so the times add up to more than the time of the run.
+    println!("\n{:<10} {:>6} {:>10}", "category", "files", "time");
+    for (category, (files, elapsed)) in &timings {
+        println!(
+            "{:<10} {:>6} {:>9.2}s",
+            category.to_string(),
+            files,
+            elapsed.as_secs_f64()
+        );
+    }
 
     if !cases.fail_categories.is_empty() {
         for (i, fail) in cases.fail_categories.iter().enumerate() {
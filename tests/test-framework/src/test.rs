@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, fmt, str::FromStr};
 
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
 pub enum TestExpectationMode {
@@ -23,10 +23,76 @@ pub enum TestExpectationMode {
     Skip,
 }
 
+impl FromStr for TestExpectationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pass" => Ok(Self::Pass),
+            "fail" => Ok(Self::Fail),
+            "skip" => Ok(Self::Skip),
+            _ => Err(format!("unknown expectation `{s}`, expected `pass`, `fail`, or `skip`")),
+        }
+    }
+}
+
+/// The stage of the compiler that a test exercises.
+/// Passing tests exercise every stage, and failing tests exercise the stage that rejects them.
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum TestCategory {
+    Parser,
+    Typeck,
+    Flatten,
+    Codegen,
+}
+
+impl TestCategory {
+    /// Returns the category of a failing test, given the code of the first error it expects, e.g. `ETYC0372005`.
+    pub fn from_error_code(code: &str) -> Self {
+        match code.get(1..4) {
+            Some("PAR" | "INP") => Self::Parser,
+            Some("AST" | "TYC") => Self::Typeck,
+            Some("FLA") => Self::Flatten,
+            _ => Self::Codegen,
+        }
+    }
+}
+
+impl fmt::Display for TestCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parser => write!(f, "parser"),
+            Self::Typeck => write!(f, "typeck"),
+            Self::Flatten => write!(f, "flatten"),
+            Self::Codegen => write!(f, "codegen"),
+        }
+    }
+}
+
+impl FromStr for TestCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parser" => Ok(Self::Parser),
+            "typeck" => Ok(Self::Typeck),
+            "flatten" => Ok(Self::Flatten),
+            "codegen" => Ok(Self::Codegen),
+            _ => Err(format!(
+                "unknown test category `{s}`, expected `parser`, `typeck`, `flatten`, or `codegen`"
+            )),
+        }
+    }
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct TestConfig {
     pub namespace: String,
     pub expectation: TestExpectationMode,
+    /// The category of the test, if it is not inferred from its expectations.
+    #[serde(default)]
+    pub category: Option<TestCategory>,
     #[serde(flatten)]
     pub extra: BTreeMap<String, serde_yaml::Value>,
 }
@@ -14,13 +14,17 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Compiler, OutputOptions};
+//! Runs the compiler tests in `tests/compiler`.
+//!
+//! The tests can be filtered by category, e.g. `cargo test -p leo-test-framework -- --category flatten`.
+
+use leo_compiler::{value_to_source, Ast, Compiler, ImportSearchPath, OutputOptions};
 
 use leo_errors::{
     emitter::{Buffer, Emitter, Handler},
     LeoError, LeoWarning,
 };
-use leo_span::{source_map::FileName, symbol::create_session_if_not_set_then};
+use leo_span::{source_map::FileName, symbol::create_session_if_not_set_then, Symbol};
 use leo_test_framework::{
     runner::{Namespace, ParseType, Runner},
     Test,
@@ -42,10 +46,13 @@ use std::{fs::File, io::Write};
 
 type CurrentNetwork = Testnet3;
 
-fn new_compiler(handler: &Handler, main_file_path: PathBuf) -> Compiler<'_> {
-    let output_dir = PathBuf::from("/tmp/output/");
-    fs::create_dir_all(output_dir.clone()).unwrap();
-
+fn new_compiler(
+    handler: &Handler,
+    main_file_path: PathBuf,
+    output_dir: PathBuf,
+    import_search_path: ImportSearchPath,
+    keep_proven_asserts: bool,
+) -> Compiler<'_> {
     Compiler::new(
         String::from("test"),
         String::from("aleo"),
@@ -54,11 +61,29 @@ fn new_compiler(handler: &Handler, main_file_path: PathBuf) -> Compiler<'_> {
         output_dir,
         Some(OutputOptions {
             spans_enabled: false,
+            ast_format: Default::default(),
             initial_input_ast: true,
             initial_ast: true,
+            type_checked_ast: false,
             unrolled_ast: true,
             ssa_ast: true,
             flattened_ast: true,
+            canonicalized_ast: false,
+            dump_all: false,
+            dump_format: Default::default(),
+            warnings: Default::default(),
+            error_limit: None,
+            naming: Default::default(),
+            language_version: None,
+            list_halt_points: false,
+            estimate_costs: false,
+            keep_unused_imports: false,
+            keep_proven_asserts,
+            warn_implicit_widening: false,
+            header: Default::default(),
+            format: Default::default(),
+            import_policy: Default::default(),
+            import_search_path,
         }),
     )
 }
@@ -67,8 +92,17 @@ fn parse_program<'a>(
     handler: &'a Handler,
     program_string: &str,
     cwd: Option<PathBuf>,
+    output_dir: PathBuf,
+    import_search_path: ImportSearchPath,
+    keep_proven_asserts: bool,
 ) -> Result<Compiler<'a>, LeoError> {
-    let mut compiler = new_compiler(handler, cwd.clone().unwrap_or_else(|| "compiler-test".into()));
+    let mut compiler = new_compiler(
+        handler,
+        cwd.clone().unwrap_or_else(|| "compiler-test".into()),
+        output_dir,
+        import_search_path,
+        keep_proven_asserts,
+    );
     let name = cwd.map_or_else(|| FileName::Custom("compiler-test".into()), FileName::Real);
     compiler.parse_program_from_string(program_string, name)?;
 
@@ -84,8 +118,8 @@ fn hash_content(content: &str) -> String {
     format!("{:x}", hash)
 }
 
-fn hash_file(path: &str) -> String {
-    let file = fs::read_to_string(Path::new(path)).unwrap();
+fn hash_file(path: &Path) -> String {
+    let file = fs::read_to_string(path).unwrap();
     hash_content(&file)
 }
 
@@ -116,6 +150,15 @@ struct CompileOutput {
     pub unrolled_ast: String,
     pub ssa_ast: String,
     pub flattened_ast: String,
+    /// The assertions in the generated instructions, if the test sets `assert_report`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asserts: Option<Vec<String>>,
+    /// The report of the assertions that were removed, if the test sets `assert_report`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assert_report: Option<Value>,
+    /// The warnings emitted while compiling, if the test sets `warnings`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<String>,
 }
 
 /// Get the path of the `input_file` given in `input` into `list`.
@@ -192,16 +235,29 @@ fn temp_dir() -> PathBuf {
         .into_path()
 }
 
-fn compile_and_process<'a>(parsed: &'a mut Compiler<'a>, handler: &Handler) -> Result<String, LeoError> {
+fn compile_and_process(
+    parsed: &mut Compiler<'_>,
+    handler: &Handler,
+    keep_proven_asserts: bool,
+) -> Result<String, LeoError> {
     let st = parsed.symbol_table_pass()?;
-    let st = parsed.type_checker_pass(st)?;
-    let st = parsed.loop_unrolling_pass(st)?;
+    let (st, widenings, match_types, mut type_table) = parsed.type_checker_pass(st)?;
+    parsed.finalize_validation_pass()?;
+    parsed.lint_pass(&widenings);
+    parsed.match_lowering_pass(match_types);
+    parsed.implicit_widening_pass(widenings);
+    let st = parsed.loop_unrolling_pass(st, &mut type_table)?;
     let assigner = parsed.static_single_assignment_pass(&st)?;
+    parsed.range_analysis_pass();
+    if !keep_proven_asserts {
+        parsed.assert_elimination_pass();
+    }
 
-    parsed.flattening_pass(&st, assigner)?;
+    parsed.flattening_pass(&st, &type_table, assigner)?;
+    parsed.import_pruning_pass();
 
     // Compile Leo program to bytecode.
-    let bytecode = CodeGenerator::do_pass((&parsed.ast, handler))?;
+    let (bytecode, _) = CodeGenerator::do_pass((&parsed.ast, handler, &Default::default()))?;
 
     Ok(bytecode)
 }
@@ -217,7 +273,35 @@ fn run_test(test: Test, handler: &Handler, err_buf: &BufferEmitter) -> Result<Va
         cwd.join(val.as_str().unwrap())
     });
 
-    let mut parsed = handler.extend_if_error(parse_program(handler, &test.content, cwd))?;
+    // Tests run in parallel, so each writes its outputs to its own directory.
+    let output_dir = temp_dir();
+    // Programs are imported from the `imports` directory next to the test.
+    let imports = ImportSearchPath::new(vec![test.path.parent().expect("no test parent dir").join("imports")]);
+
+    // Assertions that are proven to always hold are removed, unless the test keeps them with `keep_proven_asserts`.
+    let keep_proven_asserts = test
+        .config
+        .get("keep_proven_asserts")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let mut parsed = handler.extend_if_error(parse_program(
+        handler,
+        &test.content,
+        cwd,
+        output_dir.clone(),
+        imports,
+        keep_proven_asserts,
+    ))?;
+
+    // The binary encoding of the AST, which the compilation cache uses, must decode to the same AST.
+    let encoded = handler.extend_if_error(parsed.ast.to_binary())?;
+    if handler.extend_if_error(Ast::from_binary(&encoded))? != parsed.ast {
+        buffer_if_err(
+            err_buf,
+            Err("the binary encoding of the AST does not decode to the same AST".to_string()),
+        )?;
+    }
 
     // (name, content)
     let inputs = buffer_if_err(err_buf, collect_all_inputs(&test))?;
@@ -233,7 +317,23 @@ fn run_test(test: Test, handler: &Handler, err_buf: &BufferEmitter) -> Result<Va
         for input in inputs {
             let mut parsed = parsed.clone();
             handler.extend_if_error(parsed.parse_input(input))?;
-            let initial_input_ast = hash_file("/tmp/output/initial_input_ast.json");
+            let initial_input_ast = hash_file(&output_dir.join("initial_input_ast.json"));
+
+            // If the test names a function to execute, check its output on the inputs with the interpreter.
+            if let Some(function) = test.config.get("execute").and_then(Value::as_str) {
+                let execution = handler.extend_if_error(parsed.interpret(Symbol::intern(function), &[]))?;
+                let output = value_to_source(&execution.output);
+                let expected = test.config.get("expected_output").and_then(Value::as_str);
+                if expected != Some(output.as_str()) {
+                    buffer_if_err(
+                        err_buf,
+                        Err(format!(
+                            "expected `{}` to output {:?}, but it output `{}`",
+                            function, expected, output
+                        )),
+                    )?;
+                }
+            }
 
             output_items.push(OutputItem { initial_input_ast });
         }
@@ -241,7 +341,25 @@ fn run_test(test: Test, handler: &Handler, err_buf: &BufferEmitter) -> Result<Va
 
     // Compile the program to bytecode.
     let program_name = format!("{}.{}", parsed.program_name, parsed.network);
-    let bytecode = handler.extend_if_error(compile_and_process(&mut parsed, handler))?;
+    let bytecode = handler.extend_if_error(compile_and_process(&mut parsed, handler, keep_proven_asserts))?;
+
+    // If the test sets `assert_report`, output the assertions that remain and the report of those that were removed.
+    let (asserts, assert_report) = match test.config.get("assert_report").and_then(Value::as_bool) {
+        Some(true) => (
+            Some(
+                bytecode
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| line.starts_with("assert"))
+                    .map(str::to_string)
+                    .collect(),
+            ),
+            // Like `leo build`, there is no report if the proven assertions are kept.
+            (!keep_proven_asserts)
+                .then(|| serde_yaml::to_value(parsed.proven_asserts_report()).expect("serialization failed")),
+        ),
+        _ => (None, None),
+    };
 
     // Run snarkvm package.
     {
@@ -271,13 +389,13 @@ fn run_test(test: Test, handler: &Handler, err_buf: &BufferEmitter) -> Result<Va
         // handler.extend_if_error(package.get_process().map_err(LeoError::Anyhow))?;
     }
 
-    let initial_ast = hash_file("/tmp/output/initial_ast.json");
-    let unrolled_ast = hash_file("/tmp/output/unrolled_ast.json");
-    let ssa_ast = hash_file("/tmp/output/ssa_ast.json");
-    let flattened_ast = hash_file("/tmp/output/flattened_ast.json");
+    let initial_ast = hash_file(&output_dir.join("initial_ast.json"));
+    let unrolled_ast = hash_file(&output_dir.join("unrolled_ast.json"));
+    let ssa_ast = hash_file(&output_dir.join("ssa_ast.json"));
+    let flattened_ast = hash_file(&output_dir.join("flattened_ast.json"));
 
-    if fs::read_dir("/tmp/output").is_ok() {
-        fs::remove_dir_all(Path::new("/tmp/output")).expect("Error failed to clean up output dir.");
+    if fs::read_dir(&output_dir).is_ok() {
+        fs::remove_dir_all(&output_dir).expect("Error failed to clean up output dir.");
     }
 
     let final_output = CompileOutput {
@@ -286,6 +404,12 @@ fn run_test(test: Test, handler: &Handler, err_buf: &BufferEmitter) -> Result<Va
         unrolled_ast,
         ssa_ast,
         flattened_ast,
+        asserts,
+        assert_report,
+        warnings: match test.config.get("warnings").and_then(Value::as_bool) {
+            Some(true) => Some(err_buf.1.borrow().to_string()),
+            _ => None,
+        },
     };
     Ok(serde_yaml::to_value(&final_output).expect("serialization failed"))
 }
@@ -301,7 +425,6 @@ impl Runner for TestRunner {
     }
 }
 
-#[test]
-pub fn compiler_tests() {
+fn main() {
     leo_test_framework::run_tests(&TestRunner, "compiler");
 }